@@ -0,0 +1,150 @@
+//! Bridge benchmarks
+//!
+//! Run with `cargo bench --features bench`. They measure what a call costs
+//! before any tool logic runs, how calls queue behind the per-domain locks when
+//! many arrive at once, and how long large projects take to list and snapshot.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use serde_json::{json, Value};
+use std::sync::Arc;
+use tokio::runtime::Runtime;
+
+use davinci_mcp_rs::bridge::{ConnectionMode, ResolveBridge};
+use davinci_mcp_rs::Config;
+
+/// Calls in flight at once in the contention benchmarks
+const CONCURRENT_CALLS: usize = 64;
+
+fn runtime() -> Runtime {
+    tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .unwrap()
+}
+
+/// Initialized simulation bridge seeded with `clips` clips and `timelines` timelines
+fn seeded_bridge(runtime: &Runtime, clips: usize, timelines: usize) -> Arc<ResolveBridge> {
+    let mut config = Config::default();
+    config.limits.max_clips = config.limits.max_clips.max(clips);
+    config.limits.max_timelines = config.limits.max_timelines.max(timelines);
+    let bridge = ResolveBridge::with_config(ConnectionMode::Simulation, Arc::new(config));
+    runtime.block_on(async {
+        bridge.initialize().await.unwrap();
+        bridge.seed_synthetic_state(clips, timelines).await;
+    });
+    Arc::new(bridge)
+}
+
+async fn call(bridge: &ResolveBridge, method: &str, args: Value) -> Value {
+    bridge
+        .call_api(method, args)
+        .await
+        .unwrap_or_else(|e| panic!("{} failed: {}", method, e))
+}
+
+fn dispatch(c: &mut Criterion) {
+    let runtime = runtime();
+    let bridge = seeded_bridge(&runtime, 100, 10);
+
+    let mut group = c.benchmark_group("dispatch");
+    group.bench_function("read", |b| {
+        b.to_async(&runtime)
+            .iter(|| call(&bridge, "get_timeline_name", json!({})))
+    });
+    group.bench_function("write", |b| {
+        b.to_async(&runtime)
+            .iter(|| call(&bridge, "switch_page", json!({ "page": "edit" })))
+    });
+    group.bench_function("unknown_method", |b| {
+        b.to_async(&runtime)
+            .iter(|| async { bridge.call_api("no_such_method", json!({})).await })
+    });
+    group.finish();
+}
+
+fn lock_contention(c: &mut Criterion) {
+    let runtime = runtime();
+    let bridge = seeded_bridge(&runtime, 1_000, 100);
+
+    // Each call lists its method and arguments; calls are spread over the batch in turn
+    let mixes: [(&str, Vec<(&str, Value)>); 4] = [
+        ("readers", vec![("get_timeline_name", json!({}))]),
+        (
+            "writers_one_domain",
+            vec![("switch_page", json!({ "page": "color" }))],
+        ),
+        (
+            "writers_disjoint_domains",
+            vec![
+                ("switch_page", json!({ "page": "color" })),
+                (
+                    "set_color_wheel_param",
+                    json!({ "clip_name": "clip_00000.mov", "wheel": "gain", "param": "master", "value": 0.1 }),
+                ),
+            ],
+        ),
+        (
+            "readers_and_writers",
+            vec![
+                ("get_timeline_name", json!({})),
+                ("get_timeline_name", json!({})),
+                ("get_timeline_name", json!({})),
+                ("switch_page", json!({ "page": "color" })),
+            ],
+        ),
+    ];
+
+    let mut group = c.benchmark_group("lock_contention");
+    group.throughput(Throughput::Elements(CONCURRENT_CALLS as u64));
+    for (name, calls) in mixes {
+        group.bench_function(name, |b| {
+            b.to_async(&runtime).iter(|| {
+                let bridge = bridge.clone();
+                let calls = calls.clone();
+                async move {
+                    let mut tasks = tokio::task::JoinSet::new();
+                    for (method, args) in calls.into_iter().cycle().take(CONCURRENT_CALLS) {
+                        let bridge = bridge.clone();
+                        tasks.spawn(async move { call(&bridge, method, args).await });
+                    }
+                    while let Some(result) = tasks.join_next().await {
+                        result.unwrap();
+                    }
+                }
+            })
+        });
+    }
+    group.finish();
+}
+
+fn large_state(c: &mut Criterion) {
+    let runtime = runtime();
+
+    let mut group = c.benchmark_group("large_state");
+    group.sample_size(20);
+    for (clips, timelines) in [(1_000, 100), (10_000, 1_000)] {
+        let bridge = seeded_bridge(&runtime, clips, timelines);
+        let size = format!("{}_clips_{}_timelines", clips, timelines);
+        // Responses are serialized as the server does before sending them
+        for (method, args) in [
+            ("get_media_pool_item_list", json!({})),
+            ("list_timelines_tool", json!({})),
+            ("list_unused_clips", json!({})),
+        ] {
+            group.bench_with_input(BenchmarkId::new(method, &size), &args, |b, args| {
+                b.to_async(&runtime).iter(|| async {
+                    serde_json::to_string_pretty(&call(&bridge, method, args.clone()).await)
+                        .unwrap()
+                })
+            });
+        }
+        group.bench_function(BenchmarkId::new("seed_synthetic_state", &size), |b| {
+            b.to_async(&runtime)
+                .iter(|| bridge.seed_synthetic_state(clips, timelines))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, dispatch, lock_contention, large_state);
+criterion_main!(benches);
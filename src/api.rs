@@ -0,0 +1,191 @@
+//! Stable API for Rust callers
+//!
+//! Everything reachable from this module follows semantic versioning under
+//! [`API_VERSION`]: within a major version its items keep their names,
+//! signatures and meaning. The rest of the crate is free to change in any
+//! release, so depend on this module rather than on `bridge`, `tools` or
+//! `server` directly.
+//!
+//! Minor versions may add items, enum variants and struct fields. The public
+//! enums and response structs are `#[non_exhaustive]` for that reason: match
+//! enums with a wildcard arm and read responses through their fields instead
+//! of building them. `tests/public_api_test.rs` pins the signature of every
+//! item here, so a change that would break callers fails CI.
+//!
+//! Only the types declared or re-exported here are covered. The bridge, the
+//! server and the configuration structs stay behind [`Client`] and
+//! [`ClientConfig`], so their own methods and fields can change freely.
+//!
+//! ```no_run
+//! use davinci_mcp_rs::api::{Client, ClientConfig, ConnectionMode};
+//! use serde_json::json;
+//!
+//! # async fn run() -> davinci_mcp_rs::api::ResolveResult<()> {
+//! let client = Client::new(ConnectionMode::Simulation, ClientConfig::default());
+//! client.connect().await?;
+//! let response = client
+//!     .call_tool("create_project", json!({ "name": "Trailer" }))
+//!     .await?;
+//! println!("{}", response.text);
+//! # Ok(())
+//! # }
+//! ```
+
+use serde_json::Value;
+use std::sync::Arc;
+
+use crate::config::Config;
+use crate::server::DaVinciResolveServer;
+
+pub use crate::bridge::ConnectionMode;
+pub use crate::error::{ErrorKind, ResolveError, ResolveResult};
+
+/// Version of this API; the major number changes only with a breaking change
+pub const API_VERSION: &str = "1.0.0";
+
+/// Settings a [`Client`] starts with
+#[derive(Debug, Clone, Default)]
+pub struct ClientConfig {
+    config: Config,
+}
+
+impl ClientConfig {
+    /// Settings laid out as in the server's configuration file, as a JSON
+    /// object; sections and fields it leaves out keep their defaults
+    pub fn from_json(settings: Value) -> ResolveResult<Self> {
+        if !settings.is_object() {
+            return Err(ResolveError::invalid_parameter(
+                "settings",
+                "must be a JSON object",
+            ));
+        }
+        let mut merged = serde_json::to_value(Config::default())?;
+        overlay(&mut merged, settings);
+        let config: Config = serde_json::from_value(merged)
+            .map_err(|e| ResolveError::invalid_parameter("settings", e.to_string()))?;
+        config
+            .validate()
+            .map_err(|e| ResolveError::invalid_parameter("settings", e))?;
+        Ok(Self { config })
+    }
+}
+
+/// Replace the fields of `base` that `settings` gives, object by object
+fn overlay(base: &mut Value, settings: Value) {
+    match (base, settings) {
+        (Value::Object(base), Value::Object(settings)) => {
+            for (key, value) in settings {
+                match base.get_mut(&key) {
+                    Some(field) => overlay(field, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, settings) => *base = settings,
+    }
+}
+
+/// Handle to a DaVinci Resolve connection, cheap to clone and share between tasks
+#[derive(Debug, Clone)]
+pub struct Client {
+    server: Arc<DaVinciResolveServer>,
+}
+
+impl Client {
+    /// Client for the given mode and configuration; call [`Client::connect`] before any tool
+    pub fn new(mode: ConnectionMode, config: ClientConfig) -> Self {
+        Self {
+            server: Arc::new(DaVinciResolveServer::with_mode_and_config(
+                mode,
+                config.config,
+            )),
+        }
+    }
+
+    /// Connect to DaVinci Resolve, or set up the simulated state. Connecting
+    /// again does nothing.
+    pub async fn connect(&self) -> ResolveResult<()> {
+        self.server.initialize().await
+    }
+
+    /// Call a tool by name with its arguments, a JSON object
+    pub async fn call_tool(&self, name: &str, arguments: Value) -> ResolveResult<ToolResponse> {
+        let arguments = match arguments {
+            Value::Object(arguments) => Some(arguments),
+            Value::Null => None,
+            _ => {
+                return Err(ResolveError::invalid_parameter(
+                    "arguments",
+                    "must be a JSON object",
+                ))
+            }
+        };
+        let text = self.server.handle_tool_call(name, arguments).await?;
+        Ok(ToolResponse::new(name, text))
+    }
+
+    /// Every tool this client can call
+    pub fn tools(&self) -> Vec<ToolInfo> {
+        self.server
+            .get_tools()
+            .into_iter()
+            .map(|tool| ToolInfo {
+                name: tool.name.to_string(),
+                description: tool.description.as_ref().map(|text| text.to_string()),
+                input_schema: Value::Object((*tool.input_schema).clone()),
+            })
+            .collect()
+    }
+
+    /// Whether the client is connected; clones share one connection
+    pub async fn is_connected(&self) -> bool {
+        self.server.bridge().is_connected().await
+    }
+}
+
+/// A tool a [`Client`] can call
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct ToolInfo {
+    pub name: String,
+    pub description: Option<String>,
+    /// JSON schema of the tool's arguments
+    pub input_schema: Value,
+}
+
+/// What a tool returned
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct ToolResponse {
+    /// Name of the tool that was called
+    pub tool: String,
+    /// The response as the server sends it to MCP clients
+    pub text: String,
+    /// The response parsed, when the tool answers in JSON
+    pub data: Option<Value>,
+}
+
+impl ToolResponse {
+    fn new(tool: &str, text: String) -> Self {
+        let data = serde_json::from_str::<Value>(&text)
+            .ok()
+            .filter(|data| data.is_object() || data.is_array());
+        Self {
+            tool: tool.to_string(),
+            text,
+            data,
+        }
+    }
+
+    /// A top-level field of a JSON response
+    pub fn field(&self, name: &str) -> Option<&Value> {
+        self.data.as_ref()?.get(name)
+    }
+
+    /// ID the bridge gave the operation, when the tool reports one
+    pub fn operation_id(&self) -> Option<&str> {
+        self.field("operation_id")?.as_str()
+    }
+}
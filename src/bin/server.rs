@@ -1,4 +1,5 @@
-use davinci_mcp_rs::{bridge::ConnectionMode, DaVinciResolveServer};
+use davinci_mcp_rs::config::FixtureMode;
+use davinci_mcp_rs::{bridge::ConnectionMode, Config, DaVinciResolveServer};
 use rmcp::ServiceExt;
 use std::env;
 use tokio::io::{stdin, stdout};
@@ -26,11 +27,20 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         connection_mode
     );
 
+    // Record real responses to, or answer from, a fixture file
+    let mut config = Config::default();
+    if let Ok(mode) = env::var("DAVINCI_FIXTURE_MODE") {
+        config.fixtures.mode = match mode.to_lowercase().as_str() {
+            "record" => FixtureMode::Record,
+            "playback" => FixtureMode::Playback,
+            _ => FixtureMode::Off,
+        };
+        config.fixtures.path = env::var("DAVINCI_FIXTURE_PATH").ok().map(Into::into);
+        config.validate()?;
+    }
+
     // Create the DaVinci Resolve MCP server with the determined mode
-    let server = match connection_mode {
-        ConnectionMode::Simulation => DaVinciResolveServer::new(),
-        ConnectionMode::Real => DaVinciResolveServer::new_real(),
-    };
+    let server = DaVinciResolveServer::with_mode_and_config(connection_mode, config);
 
     // Initialize the server
     if let Err(e) = server.initialize().await {
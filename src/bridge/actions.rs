@@ -0,0 +1,212 @@
+//! Named actions from the config, run with run_action
+//!
+//! An action is a fixed sequence of API calls set up in the `[actions]`
+//! config, such as preparing dailies or archiving a project. The caller gives
+//! the action's parameters, which fill the `{{name}}` placeholders in each
+//! step's arguments. Steps run one after another, each taking its own locks,
+//! and the action stops at the first step that fails; earlier steps keep
+//! their effect. Actions limited to some projects only run while one of those
+//! projects is open.
+
+use serde_json::{json, Map, Value};
+use uuid::Uuid;
+
+use super::ResolveBridge;
+use crate::config::ActionConfig;
+use crate::error::{ResolveError, ResolveResult};
+
+/// Replace `{{name}}` placeholders with parameter values; a string that is a
+/// single placeholder takes the parameter's value as is, keeping numbers and
+/// objects intact
+fn fill(value: &Value, parameters: &Map<String, Value>) -> Value {
+    match value {
+        Value::String(text) => {
+            if let Some(value) = text
+                .strip_prefix("{{")
+                .and_then(|rest| rest.strip_suffix("}}"))
+                .and_then(|name| parameters.get(name))
+            {
+                return value.clone();
+            }
+            let mut filled = text.clone();
+            for (name, value) in parameters {
+                let replacement = match value {
+                    Value::String(text) => text.clone(),
+                    other => other.to_string(),
+                };
+                filled = filled.replace(&format!("{{{{{}}}}}", name), &replacement);
+            }
+            Value::String(filled)
+        }
+        Value::Array(items) => {
+            Value::Array(items.iter().map(|item| fill(item, parameters)).collect())
+        }
+        Value::Object(fields) => Value::Object(
+            fields
+                .iter()
+                .map(|(key, value)| (key.clone(), fill(value, parameters)))
+                .collect(),
+        ),
+        other => other.clone(),
+    }
+}
+
+/// First placeholder left in a value after filling, if any
+fn unfilled(value: &Value) -> Option<String> {
+    match value {
+        Value::String(text) => {
+            let start = text.find("{{")?;
+            let end = text[start..].find("}}")?;
+            Some(text[start..start + end + 2].to_string())
+        }
+        Value::Array(items) => items.iter().find_map(unfilled),
+        Value::Object(fields) => fields.values().find_map(unfilled),
+        _ => None,
+    }
+}
+
+/// Whether an action can run while `project` is open
+fn offered_in(action: &ActionConfig, project: Option<&str>) -> bool {
+    action.projects.is_empty()
+        || project.is_some_and(|project| action.projects.iter().any(|name| name == project))
+}
+
+impl ResolveBridge {
+    /// Runs a configured action, or lists the actions available without one
+    pub(super) async fn run_action(&self, args: Value) -> ResolveResult<Value> {
+        let project = self.state.current_project.read().await.clone();
+        let available: Vec<(&String, &ActionConfig)> = self
+            .config
+            .actions
+            .iter()
+            .filter(|(_, action)| offered_in(action, project.as_deref()))
+            .collect();
+
+        let Some(name) = args["action"].as_str() else {
+            let actions: Vec<Value> = available
+                .iter()
+                .map(|(name, action)| {
+                    json!({
+                        "name": name,
+                        "description": action.description,
+                        "parameters": action.parameters,
+                        "steps": action.steps.iter().map(|step| &step.method).collect::<Vec<_>>()
+                    })
+                })
+                .collect();
+            return Ok(json!({
+                "result": format!("{} actions available", actions.len()),
+                "project": project,
+                "actions": actions,
+                "operation_id": Uuid::new_v4().to_string()
+            }));
+        };
+        let action = match self.config.actions.get(name) {
+            Some(action) if offered_in(action, project.as_deref()) => action,
+            Some(action) => {
+                return Err(ResolveError::invalid_parameter(
+                    "action",
+                    format!(
+                        "action '{}' only runs in projects: {}",
+                        name,
+                        action.projects.join(", ")
+                    ),
+                ))
+            }
+            None => {
+                let names: Vec<&str> = available.iter().map(|(name, _)| name.as_str()).collect();
+                return Err(ResolveError::invalid_parameter(
+                    "action",
+                    format!(
+                        "unknown action '{}'; available actions: {}",
+                        name,
+                        if names.is_empty() {
+                            "none".to_string()
+                        } else {
+                            names.join(", ")
+                        }
+                    ),
+                ));
+            }
+        };
+
+        let parameters = match &args["parameters"] {
+            Value::Null => Map::new(),
+            Value::Object(parameters) => parameters.clone(),
+            _ => {
+                return Err(ResolveError::invalid_parameter(
+                    "parameters",
+                    "must be an object of parameter names and values",
+                ))
+            }
+        };
+        if let Some(missing) = action
+            .parameters
+            .iter()
+            .find(|name| !parameters.contains_key(*name))
+        {
+            return Err(ResolveError::invalid_parameter(
+                "parameters",
+                format!("action '{}' needs parameter '{}'", name, missing),
+            ));
+        }
+        if let Some(unknown) = parameters
+            .keys()
+            .find(|key| !action.parameters.contains(key))
+        {
+            return Err(ResolveError::invalid_parameter(
+                "parameters",
+                format!("action '{}' has no parameter '{}'", name, unknown),
+            ));
+        }
+
+        let mut steps = Vec::with_capacity(action.steps.len());
+        for (index, step) in action.steps.iter().enumerate() {
+            let arguments = match &step.arguments {
+                Value::Null => json!({}),
+                arguments => fill(arguments, &parameters),
+            };
+            if let Some(placeholder) = unfilled(&arguments) {
+                return Err(ResolveError::invalid_parameter(
+                    "action",
+                    format!(
+                        "step {} ({}) of action '{}' uses {}, which is not a parameter",
+                        index + 1,
+                        step.method,
+                        name,
+                        placeholder
+                    ),
+                ));
+            }
+            let result = self
+                .call_api_boxed(step.method.clone(), arguments)
+                .await
+                .map_err(|e| {
+                    ResolveError::api_call(
+                        "run_action",
+                        format!(
+                            "step {} ({}) of action '{}' failed after {} completed: {}",
+                            index + 1,
+                            step.method,
+                            name,
+                            index,
+                            e
+                        ),
+                    )
+                })?;
+            steps.push(json!({
+                "step": index + 1,
+                "method": step.method,
+                "result": result
+            }));
+        }
+
+        Ok(json!({
+            "result": format!("Ran action '{}' in {} steps", name, steps.len()),
+            "action": name,
+            "parameters": parameters,
+            "steps": steps,
+            "operation_id": Uuid::new_v4().to_string()
+        }))
+    }
+}
@@ -0,0 +1,339 @@
+//! Avid Log Exchange files of media pool metadata
+//!
+//! export_ale writes the clips of the media pool, or of a bin or a list of
+//! clips, as an ALE: a tab-delimited log with a Heading of global settings,
+//! a Column line naming the fields and a Data line per clip. Name, Tracks,
+//! Start, End, FPS and Source File describe the media; Tape is the clip's
+//! Reel Name, Comments its notes, and every other metadata field the clips
+//! carry, or the active metadata schema names, gets a column of its own.
+//!
+//! import_ale merges a log edited in Avid, or written by a lab, back onto the
+//! clips: rows match by Name, then by Source File. Tape, Comments and the
+//! extra columns are written as metadata; the columns describing the media
+//! are left alone, as are empty cells. Values are checked against the active
+//! metadata schema, and a log that breaks it is refused as a whole.
+
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+use uuid::Uuid;
+
+use super::audio_sync::is_audio_only;
+use super::embedded_timecode::timecode_frames;
+use super::metadata_schema::{self, READ_ONLY_FIELDS};
+use super::{notes, Clip, ResolveBridge, StateView, SIMULATED_CLIP_SECONDS};
+use crate::error::{ResolveError, ResolveResult};
+use crate::timecode::FrameRate;
+
+/// Columns that describe the media, written on export and never imported
+const MEDIA_COLUMNS: [&str; 7] = [
+    "Name",
+    "Tracks",
+    "Start",
+    "End",
+    "Duration",
+    "FPS",
+    "Source File",
+];
+
+/// ALE columns that hold a metadata field under another name
+const FIELD_COLUMNS: [(&str, &str); 2] = [
+    ("Tape", "Reel Name"),
+    ("Comments", notes::CLIP_NOTES_METADATA),
+];
+
+/// Metadata field an ALE column is imported into
+fn column_field(column: &str) -> &str {
+    FIELD_COLUMNS
+        .iter()
+        .find(|(name, _)| *name == column)
+        .map_or(column, |(_, field)| field)
+}
+
+/// A value as one cell: tabs and line breaks would end the cell or the row
+fn cell(value: &str) -> String {
+    value.replace(['\t', '\r', '\n'], " ")
+}
+
+/// Avid's name for a frame height
+fn video_format(height: u32) -> String {
+    match height {
+        1080 | 720 => height.to_string(),
+        576 => "PAL".to_string(),
+        486 => "NTSC".to_string(),
+        _ => "CUSTOM".to_string(),
+    }
+}
+
+/// One data row of an ALE, by column
+type Row = BTreeMap<String, String>;
+
+/// Columns and data rows of an ALE
+fn parse(data: &str) -> ResolveResult<(Vec<String>, Vec<Row>)> {
+    let invalid = |message: String| ResolveError::invalid_parameter("data", message);
+    let mut lines = data.lines().map(|line| line.trim_end_matches('\r'));
+    if !lines.any(|line| line.trim() == "Column") {
+        return Err(invalid("not an ALE: no Column section".to_string()));
+    }
+    let columns: Vec<String> = lines
+        .next()
+        .ok_or_else(|| invalid("the Column section is empty".to_string()))?
+        .split('\t')
+        .map(|column| column.trim().to_string())
+        .collect();
+    if !columns.iter().any(|column| column == "Name") {
+        return Err(invalid("the log has no Name column".to_string()));
+    }
+    if !lines.any(|line| line.trim() == "Data") {
+        return Err(invalid("not an ALE: no Data section".to_string()));
+    }
+    let rows = lines
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            columns
+                .iter()
+                .cloned()
+                .zip(line.split('\t').map(|value| value.trim().to_string()))
+                .collect()
+        })
+        .collect();
+    Ok((columns, rows))
+}
+
+/// File name of a path, which ALE Source File columns usually hold
+fn file_name(path: &str) -> &str {
+    Path::new(path)
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or(path)
+}
+
+impl ResolveBridge {
+    /// One clip's cells, by column
+    fn ale_row(
+        &self,
+        state: &StateView<'_>,
+        clip: &Clip,
+        fields: &BTreeSet<String>,
+    ) -> ResolveResult<Row> {
+        let rate = FrameRate::parse(&self.clip_frame_rate(clip))
+            .map_err(|e| ResolveError::invalid_parameter("frame_rate", e))?;
+        let start = clip.start_timecode.as_deref().unwrap_or("00:00:00:00");
+        let start_frame = timecode_frames(start, rate).unwrap_or(0);
+        let duration = SIMULATED_CLIP_SECONDS * rate.nominal();
+
+        let mut row = BTreeMap::from([
+            ("Name".to_string(), clip.name.clone()),
+            (
+                "Tracks".to_string(),
+                if is_audio_only(clip) { "A1A2" } else { "VA1A2" }.to_string(),
+            ),
+            ("Start".to_string(), rate.frames_to_timecode(start_frame)),
+            (
+                "End".to_string(),
+                rate.frames_to_timecode(start_frame + duration),
+            ),
+            ("Duration".to_string(), rate.frames_to_timecode(duration)),
+            ("FPS".to_string(), rate.to_string()),
+            (
+                "Source File".to_string(),
+                file_name(&clip.file_path).to_string(),
+            ),
+        ]);
+        for (column, field) in FIELD_COLUMNS {
+            let value = metadata_schema::clip_value(state, &clip.name, field).unwrap_or_default();
+            row.insert(column.to_string(), value);
+        }
+        for field in fields {
+            let value = metadata_schema::clip_value(state, &clip.name, field).unwrap_or_default();
+            row.insert(field.clone(), value);
+        }
+        Ok(row)
+    }
+
+    pub(super) async fn export_ale(
+        &self,
+        state: &mut StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        let state: &StateView<'_> = state;
+        let clip_names: Option<Vec<&str>> = args["clip_names"]
+            .as_array()
+            .map(|names| names.iter().filter_map(Value::as_str).collect());
+        let bin_name = args["bin_name"].as_str();
+        if let Some(bin_name) = bin_name.filter(|bin| !state.media_pool.bins.contains_key(*bin)) {
+            return Err(ResolveError::invalid_parameter(
+                "bin_name",
+                format!("bin '{}' not found", bin_name),
+            ));
+        }
+        if let Some(missing) = clip_names
+            .iter()
+            .flatten()
+            .find(|name| !state.media_pool.clips.contains_key(**name))
+        {
+            return Err(ResolveError::invalid_parameter(
+                "clip_names",
+                format!("clip '{}' not found in media pool", missing),
+            ));
+        }
+
+        let mut clips: Vec<&Clip> = state
+            .media_pool
+            .clips
+            .values()
+            .filter(|clip| {
+                clip_names
+                    .as_ref()
+                    .is_none_or(|names| names.contains(&clip.name.as_str()))
+            })
+            .filter(|clip| bin_name.is_none() || clip.bin.as_deref() == bin_name)
+            .collect();
+        clips.sort_by(|a, b| a.name.cmp(&b.name));
+        if clips.is_empty() {
+            return Err(ResolveError::invalid_parameter(
+                "clip_names",
+                "no clips to export",
+            ));
+        }
+
+        // Every field the clips carry or the active schema names
+        let mut fields: BTreeSet<String> = clips
+            .iter()
+            .flat_map(|clip| clip.metadata.keys().cloned())
+            .collect();
+        if let Some((_, schema)) = self.active_metadata_schema() {
+            fields.extend(schema.fields.keys().cloned());
+        }
+        fields.retain(|field| {
+            !READ_ONLY_FIELDS.contains(&field.as_str())
+                && !FIELD_COLUMNS.iter().any(|(_, mapped)| mapped == field)
+                && !MEDIA_COLUMNS.contains(&field.as_str())
+        });
+        let columns: Vec<String> = MEDIA_COLUMNS
+            .iter()
+            .chain(FIELD_COLUMNS.iter().map(|(column, _)| column))
+            .map(|column| column.to_string())
+            .chain(fields.iter().cloned())
+            .collect();
+
+        let project_rate = self.project_frame_rate()?;
+        let mut content = format!(
+            "Heading\nFIELD_DELIM\tTABS\nVIDEO_FORMAT\t{}\nAUDIO_FORMAT\t48khz\nFPS\t{}\n\nColumn\n{}\n\nData\n",
+            video_format(self.config.resolve.default_project.height),
+            project_rate,
+            columns.iter().map(|column| cell(column)).collect::<Vec<_>>().join("\t")
+        );
+        for clip in &clips {
+            let row = self.ale_row(state, clip, &fields)?;
+            let cells: Vec<String> = columns
+                .iter()
+                .map(|column| cell(row.get(column).map_or("", String::as_str)))
+                .collect();
+            content.push_str(&cells.join("\t"));
+            content.push('\n');
+        }
+
+        let output_path = args["output_path"].as_str();
+        if let Some(path) = output_path {
+            std::fs::write(path, &content).map_err(|e| {
+                ResolveError::internal(format!("failed to write ALE '{}': {}", path, e))
+            })?;
+        }
+
+        Ok(json!({
+            "result": format!("Exported {} clips to ALE", clips.len()),
+            "clip_count": clips.len(),
+            "columns": columns,
+            "content": content,
+            "output_path": output_path,
+            "operation_id": Uuid::new_v4().to_string()
+        }))
+    }
+
+    pub(super) async fn import_ale(
+        &self,
+        state: &mut StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        let data = args["data"]
+            .as_str()
+            .ok_or_else(|| ResolveError::invalid_parameter("data", "required string"))?;
+        let dry_run = args["dry_run"].as_bool().unwrap_or(false);
+        let (columns, rows) = parse(data)?;
+        let imported: Vec<&String> = columns
+            .iter()
+            .filter(|column| {
+                !MEDIA_COLUMNS.contains(&column.as_str())
+                    && !READ_ONLY_FIELDS.contains(&column_field(column))
+                    && !column.is_empty()
+            })
+            .collect();
+
+        // Clip, field and value of every write, by row
+        let by_file: BTreeMap<&str, &str> = state
+            .media_pool
+            .clips
+            .values()
+            .map(|clip| (file_name(&clip.file_path), clip.name.as_str()))
+            .collect();
+        let mut writes: Vec<(String, String, String)> = Vec::new();
+        let mut matched = Vec::new();
+        let mut unmatched = Vec::new();
+        for (index, row) in rows.iter().enumerate() {
+            let name = row.get("Name").map_or("", String::as_str);
+            let clip_name = if state.media_pool.clips.contains_key(name) {
+                Some(name)
+            } else {
+                row.get("Source File")
+                    .and_then(|source| by_file.get(file_name(source)).copied())
+            };
+            let Some(clip_name) = clip_name else {
+                unmatched.push(json!({ "row": index + 1, "name": name }));
+                continue;
+            };
+            let fields: BTreeMap<&str, &str> = imported
+                .iter()
+                .filter_map(|column| {
+                    let value = row.get(*column).filter(|value| !value.is_empty())?;
+                    Some((column_field(column), value.as_str()))
+                })
+                .collect();
+            for (field, value) in &fields {
+                writes.push((clip_name.to_string(), field.to_string(), value.to_string()));
+            }
+            matched.push(json!({
+                "row": index + 1,
+                "clip_name": clip_name,
+                "clip_id": state.media_pool.clips[clip_name].id,
+                "fields": fields
+            }));
+        }
+
+        self.enforce_metadata_schema(
+            "data",
+            writes.iter().map(|(clip_name, field, value)| {
+                (clip_name.as_str(), field.as_str(), value.as_str())
+            }),
+        )?;
+        if !dry_run {
+            for (clip_name, field, value) in writes {
+                metadata_schema::set_clip_value(state, &clip_name, &field, value);
+            }
+        }
+
+        Ok(json!({
+            "result": format!(
+                "{} metadata from {} of {} ALE rows",
+                if dry_run { "Would merge" } else { "Merged" },
+                matched.len(),
+                rows.len()
+            ),
+            "dry_run": dry_run,
+            "columns": imported,
+            "matched": matched,
+            "unmatched": unmatched,
+            "operation_id": Uuid::new_v4().to_string()
+        }))
+    }
+}
@@ -0,0 +1,135 @@
+//! Where the session stands
+//!
+//! get_app_state answers in one call what an agent would otherwise piece
+//! together from several reads at the start of each turn: the open project,
+//! the current timeline and its playhead, the page, the clip selected on the
+//! color page and how the bridge is connected. get_current_page is the page
+//! alone.
+//!
+//! In Real mode both read Resolve itself; the selection there is the video
+//! item under the playhead, which is what the color page grades.
+
+use serde_json::{json, Value};
+
+use super::locking::StateView;
+use super::{ConnectionMode, ResolveBridge};
+use crate::error::ResolveResult;
+use crate::timecode::FrameRate;
+
+/// Methods with a real path
+pub(super) const REAL_METHODS: [&str; 2] = ["get_current_page", "get_app_state"];
+
+const READ_STATE: &str = r#"
+    output = {"success": True, "page": resolve.GetCurrentPage(), "project": None, "timeline": None, "clip": None}
+    project = resolve.GetProjectManager().GetCurrentProject()
+    timeline = project.GetCurrentTimeline() if project else None
+    if project:
+        output["project"] = project.GetName()
+    if timeline:
+        output["timeline"] = {
+            "id": timeline.GetUniqueId(),
+            "name": timeline.GetName(),
+            "frame_rate": timeline.GetSetting("timelineFrameRate") or None,
+            "playhead_frame": None,
+            "timecode": timeline.GetCurrentTimecode()
+        }
+        item = timeline.GetCurrentVideoItem()
+        if item:
+            media = item.GetMediaPoolItem()
+            output["clip"] = media.GetName() if media else item.GetName()
+    print(json.dumps(output))
+"#;
+
+impl ResolveBridge {
+    /// How the bridge is connected, and whether `source` answered the call:
+    /// Resolve, or the simulation, which Real mode falls back to
+    async fn connection(&self, source: &str) -> Value {
+        json!({
+            "mode": match self.mode {
+                ConnectionMode::Real => "real",
+                ConnectionMode::Simulation => "simulation",
+            },
+            "connected": self.is_connected().await,
+            "source": source
+        })
+    }
+
+    pub(super) async fn get_current_page(
+        &self,
+        state: &mut StateView<'_>,
+        _args: Value,
+    ) -> ResolveResult<Value> {
+        Ok(json!({
+            "result": format!("Current page: {}", *state.current_page),
+            "page": *state.current_page
+        }))
+    }
+
+    pub(super) async fn get_app_state(
+        &self,
+        state: &mut StateView<'_>,
+        _args: Value,
+    ) -> ResolveResult<Value> {
+        let timeline = state.current_timeline.as_ref().and_then(|name| {
+            let timeline = state.timelines.get(name)?;
+            let timecode = timeline.playhead.and_then(|frame| {
+                let rate = FrameRate::parse(timeline.frame_rate.as_deref()?).ok()?;
+                Some(rate.frames_to_timecode(frame))
+            });
+            Some(json!({
+                "id": timeline.id,
+                "name": name,
+                "frame_rate": timeline.frame_rate,
+                "playhead_frame": timeline.playhead,
+                "timecode": timecode
+            }))
+        });
+        Ok(json!({
+            "result": format!(
+                "Project '{}', timeline '{}', {} page",
+                state.current_project.as_deref().unwrap_or("none"),
+                state.current_timeline.as_deref().unwrap_or("none"),
+                *state.current_page
+            ),
+            "project": *state.current_project,
+            "timeline": timeline,
+            "page": *state.current_page,
+            "selection": {
+                "clip": state.color_state.current_clip,
+                "still_album": state.gallery.current_album
+            },
+            "connection": self.connection("simulation").await
+        }))
+    }
+
+    /// Read the page, project, timeline and selection of the running Resolve
+    pub(super) async fn real_app_state(&self, method: &str) -> ResolveResult<Value> {
+        let output = self.run_resolve_script(
+            method,
+            &Self::resolve_script("", "", &Value::Null, READ_STATE)?,
+        )?;
+        let page = output["page"].as_str().unwrap_or_default();
+        if method == "get_current_page" {
+            return Ok(json!({
+                "result": format!("Current page: {}", page),
+                "page": page
+            }));
+        }
+        Ok(json!({
+            "result": format!(
+                "Project '{}', timeline '{}', {} page",
+                output["project"].as_str().unwrap_or("none"),
+                output["timeline"]["name"].as_str().unwrap_or("none"),
+                page
+            ),
+            "project": output["project"],
+            "timeline": output["timeline"],
+            "page": page,
+            "selection": {
+                "clip": output["clip"],
+                "still_album": Value::Null
+            },
+            "connection": self.connection("resolve").await
+        }))
+    }
+}
@@ -0,0 +1,318 @@
+//! Archive manifests for finished projects
+//!
+//! Before a project goes to LTO, the manifest records every file its media
+//! pool refers to, original media and proxies alike, with its size, modified
+//! time and SHA-256 checksum. Files keep their folder structure below the
+//! archive root, and a shell script outline copies each one back to where it
+//! came from and verifies its checksum, for restoring the project years later.
+//!
+//! Files are read once even when several clips use them. Files that cannot be
+//! read are listed as missing, and the restore script leaves them out.
+
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::io::Read;
+use std::path::{Component, Path};
+use uuid::Uuid;
+
+use super::markers::csv_field;
+use super::{paths, ResolveBridge, StateView};
+use crate::error::{ResolveError, ResolveResult};
+
+/// One file in the manifest
+struct ArchiveFile {
+    source_path: String,
+    /// Path below the archive root
+    archive_path: String,
+    /// Clips using the file, with whether it is their proxy
+    clips: Vec<(String, bool)>,
+    /// Size, modified time and checksum, or why the file could not be read
+    contents: Result<(u64, Option<String>, String), String>,
+}
+
+impl ArchiveFile {
+    fn role(&self) -> &'static str {
+        if self.clips.iter().all(|(_, proxy)| *proxy) {
+            "proxy"
+        } else {
+            "media"
+        }
+    }
+
+    fn clip_names(&self) -> Vec<&str> {
+        self.clips.iter().map(|(name, _)| name.as_str()).collect()
+    }
+
+    fn to_json(&self) -> Value {
+        let (size, modified, checksum, error) = match &self.contents {
+            Ok((size, modified, checksum)) => (Some(*size), modified.clone(), Some(checksum), None),
+            Err(error) => (None, None, None, Some(error)),
+        };
+        json!({
+            "source_path": self.source_path,
+            "archive_path": self.archive_path,
+            "role": self.role(),
+            "clips": self.clip_names(),
+            "size_bytes": size,
+            "modified": modified,
+            "sha256": checksum,
+            "status": if error.is_some() { "missing" } else { "ok" },
+            "error": error
+        })
+    }
+}
+
+/// Where a file sits below the archive root: its own path made relative
+fn archive_path(source_path: &str) -> String {
+    Path::new(source_path)
+        .components()
+        .filter_map(|component| match component {
+            Component::Normal(part) => Some(part.to_string_lossy().replace(':', "_")),
+            Component::Prefix(prefix) => Some(
+                prefix
+                    .as_os_str()
+                    .to_string_lossy()
+                    .trim_end_matches(':')
+                    .to_string(),
+            ),
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Size, modified time and SHA-256 of a file, read in chunks
+fn read_file(path: &str) -> Result<(u64, Option<String>, String), String> {
+    let mut file = std::fs::File::open(path).map_err(|e| e.to_string())?;
+    let metadata = file.metadata().map_err(|e| e.to_string())?;
+    if !metadata.is_file() {
+        return Err("not a file".to_string());
+    }
+    let modified = metadata
+        .modified()
+        .ok()
+        .map(|time| chrono::DateTime::<chrono::Utc>::from(time).to_rfc3339());
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; 1 << 20];
+    loop {
+        let read = file.read(&mut buffer).map_err(|e| e.to_string())?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+    let checksum = hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect();
+    Ok((metadata.len(), modified, checksum))
+}
+
+/// Quote a value for a POSIX shell
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
+
+fn manifest_csv(files: &[ArchiveFile]) -> String {
+    let mut csv = String::from("Source Path,Archive Path,Role,Clips,Size,Modified,SHA256,Status\n");
+    for file in files {
+        let (size, modified, checksum, status) = match &file.contents {
+            Ok((size, modified, checksum)) => (
+                size.to_string(),
+                modified.clone().unwrap_or_default(),
+                checksum.as_str(),
+                "ok",
+            ),
+            Err(_) => (String::new(), String::new(), "", "missing"),
+        };
+        csv.push_str(&format!(
+            "{},{},{},{},{},{},{},{}\n",
+            csv_field(&file.source_path),
+            csv_field(&file.archive_path),
+            file.role(),
+            csv_field(&file.clip_names().join("; ")),
+            size,
+            modified,
+            checksum,
+            status
+        ));
+    }
+    csv
+}
+
+fn restore_script(project: &str, archive_root: &str, files: &[ArchiveFile]) -> String {
+    let mut script = format!(
+        "#!/bin/sh\n\
+         # Restore outline for project {project}\n\
+         # Generated {generated_at} from its archive manifest; review before running.\n\
+         # ARCHIVE_ROOT is where the archive is mounted, such as an LTFS tape.\n\
+         # RESTORE_PREFIX is put in front of each original path, empty to restore in place.\n\
+         set -eu\n\
+         ARCHIVE_ROOT=${{ARCHIVE_ROOT:-{archive_root}}}\n\
+         RESTORE_PREFIX=\"${{RESTORE_PREFIX:-}}\"\n\
+         \n\
+         restore() {{\n\
+         \tdest=\"$RESTORE_PREFIX$2\"\n\
+         \tmkdir -p \"$(dirname \"$dest\")\"\n\
+         \tcp -p \"$ARCHIVE_ROOT/$1\" \"$dest\"\n\
+         \techo \"$3  $dest\" | sha256sum -c -\n\
+         }}\n\n",
+        project = project.replace(['\r', '\n'], " "),
+        generated_at = chrono::Utc::now().to_rfc3339(),
+        archive_root = shell_quote(archive_root),
+    );
+    for file in files {
+        match &file.contents {
+            Ok((_, _, checksum)) => script.push_str(&format!(
+                "restore {} {} {}\n",
+                shell_quote(&file.archive_path),
+                shell_quote(&file.source_path),
+                checksum
+            )),
+            Err(_) => script.push_str(&format!(
+                "# missing at archive time: {}\n",
+                file.source_path.replace(['\r', '\n'], " ")
+            )),
+        }
+    }
+    script
+}
+
+impl ResolveBridge {
+    pub(super) async fn generate_archive_manifest(
+        &self,
+        state: &mut StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        let format = args["format"]
+            .as_str()
+            .unwrap_or("json")
+            .to_ascii_lowercase();
+        if format != "json" && format != "csv" {
+            return Err(ResolveError::invalid_parameter(
+                "format",
+                "must be 'json' or 'csv'",
+            ));
+        }
+        let include_proxies = args["include_proxies"].as_bool().unwrap_or(true);
+        let project = state
+            .current_project
+            .clone()
+            .unwrap_or_else(|| "Untitled".to_string());
+        let archive_root = match args["archive_root"].as_str() {
+            Some(root) if !root.trim().is_empty() => root.trim_end_matches('/').to_string(),
+            Some(_) => {
+                return Err(ResolveError::invalid_parameter(
+                    "archive_root",
+                    "must not be empty",
+                ))
+            }
+            None => format!("/archive/{}", archive_path(&project)),
+        };
+        let script_path = args["output_path"].as_str().map(|path| {
+            let path = Path::new(path);
+            path.with_file_name(format!(
+                "{}.restore.sh",
+                path.file_stem().unwrap_or_default().to_string_lossy()
+            ))
+        });
+        if let Some(script_path) = &script_path {
+            paths::check_path(&self.config.sandbox, &script_path.to_string_lossy())?;
+        }
+
+        // Each file once, with every clip that uses it
+        let mut clips: Vec<_> = state.media_pool.clips.values().collect();
+        clips.sort_by(|a, b| a.name.cmp(&b.name));
+        let mut used: BTreeMap<String, Vec<(String, bool)>> = BTreeMap::new();
+        for clip in clips {
+            used.entry(clip.file_path.clone())
+                .or_default()
+                .push((clip.name.clone(), false));
+            if let Some(proxy) = clip.proxy_path.as_ref().filter(|_| include_proxies) {
+                used.entry(proxy.clone())
+                    .or_default()
+                    .push((clip.name.clone(), true));
+            }
+        }
+        let sources: Vec<String> = used.keys().cloned().collect();
+        let contents = tokio::task::spawn_blocking(move || {
+            sources
+                .iter()
+                .map(|path| read_file(path))
+                .collect::<Vec<_>>()
+        })
+        .await
+        .map_err(|e| ResolveError::internal(format!("checksum task failed: {}", e)))?;
+        let files: Vec<ArchiveFile> = used
+            .into_iter()
+            .zip(contents)
+            .map(|((source_path, clips), contents)| ArchiveFile {
+                archive_path: archive_path(&source_path),
+                source_path,
+                clips,
+                contents,
+            })
+            .collect();
+
+        let generated_at = chrono::Utc::now().to_rfc3339();
+        let total_bytes: u64 = files
+            .iter()
+            .filter_map(|file| file.contents.as_ref().ok())
+            .map(|(size, _, _)| size)
+            .sum();
+        let missing: Vec<&str> = files
+            .iter()
+            .filter(|file| file.contents.is_err())
+            .map(|file| file.source_path.as_str())
+            .collect();
+        let entries: Vec<Value> = files.iter().map(ArchiveFile::to_json).collect();
+        let manifest = match format.as_str() {
+            "csv" => manifest_csv(&files),
+            _ => serde_json::to_string_pretty(&json!({
+                "project": project,
+                "generated_at": generated_at,
+                "checksum_algorithm": "sha256",
+                "archive_root": archive_root,
+                "file_count": files.len(),
+                "total_bytes": total_bytes,
+                "files": entries
+            }))?,
+        };
+        let script = restore_script(&project, &archive_root, &files);
+
+        let output_path = args["output_path"].as_str();
+        if let (Some(path), Some(script_path)) = (output_path, &script_path) {
+            let write_failed = |path: &Path, e: std::io::Error| {
+                ResolveError::internal(format!("failed to write '{}': {}", path.display(), e))
+            };
+            std::fs::write(path, &manifest).map_err(|e| write_failed(Path::new(path), e))?;
+            std::fs::write(script_path, &script).map_err(|e| write_failed(script_path, e))?;
+        }
+
+        Ok(json!({
+            "result": format!(
+                "Archive manifest of project '{}' lists {} files, {} bytes, {} missing",
+                project,
+                files.len(),
+                total_bytes,
+                missing.len()
+            ),
+            "project": project,
+            "format": format,
+            "checksum_algorithm": "sha256",
+            "archive_root": archive_root,
+            "file_count": files.len(),
+            "total_bytes": total_bytes,
+            "missing": missing,
+            "files": entries,
+            "manifest": manifest,
+            "restore_script": script,
+            "output_path": output_path,
+            "restore_script_path": script_path.map(|path| path.display().to_string()),
+            "generated_at": generated_at,
+            "operation_id": Uuid::new_v4().to_string()
+        }))
+    }
+}
@@ -0,0 +1,386 @@
+//! Syncing sound recorder audio to picture
+//!
+//! Each picture clip is paired with one of the audio-only clips given and
+//! lined up with it, producing a synced clip in the media pool that plays the
+//! picture with the recorder audio appended to, or replacing, its own sound.
+//!
+//! Timecode sync pairs clips by their embedded start timecodes, read with
+//! read_embedded_timecode, and offsets them by the difference. When ffmpeg
+//! can decode both files the offset is refined by cross-correlating the
+//! camera's scratch audio with the recorder's. Waveform sync relies on the
+//! correlation alone, pairing each picture clip with the audio clip it
+//! matches best, and searches around the timecode offset when there is one.
+//!
+//! Correlating a stretch near the start and a stretch near the end of each
+//! pair also measures drift: how far the two clocks wander apart over the
+//! take, reported in milliseconds and parts per million.
+
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::path::Path;
+use uuid::Uuid;
+
+use super::delivery_qc::AUDIO_EXTENSIONS;
+use super::embedded_timecode::timecode_frames;
+use super::rename_batch::Renames;
+use super::waveform::{PeakSource, BLOCKS_PER_SECOND};
+use super::{ensure_capacity, Bin, Clip, ResolveBridge, StateView};
+use crate::error::{ResolveError, ResolveResult};
+
+/// Furthest from the timecode offset, or from zero, waveform sync searches
+const SEARCH_SECONDS: i64 = 10;
+
+/// Furthest from the timecode offset waveform refinement moves it
+const REFINE_SECONDS: i64 = 2;
+
+/// Shortest stretch of both clips a correlation is trusted on
+const MIN_OVERLAP_SECONDS: i64 = 2;
+
+/// Length of the stretches drift is measured on
+const DRIFT_WINDOW_SECONDS: i64 = 10;
+
+/// How far drift measurement looks around the overall offset
+const DRIFT_SEARCH_SECONDS: f64 = 0.5;
+
+/// How a synced clip plays its picture and sound
+#[derive(Debug, Clone, Serialize)]
+pub(super) struct SyncedClip {
+    picture_clip: String,
+    audio_clip: String,
+    /// Audio frame that plays with the first picture frame
+    offset_frames: i64,
+    /// Keep the camera audio next to the recorder's
+    append_audio: bool,
+}
+
+impl SyncedClip {
+    pub(super) fn to_json(&self) -> Value {
+        json!({
+            "picture_clip": self.picture_clip,
+            "audio_clip": self.audio_clip,
+            "offset_frames": self.offset_frames,
+            "audio_mode": if self.append_audio { "append" } else { "replace" }
+        })
+    }
+
+    /// Follow renames of the clips it was made from
+    pub(super) fn rename_clips(&mut self, renames: &Renames) {
+        for clip in [&mut self.picture_clip, &mut self.audio_clip] {
+            if let Some(new_name) = renames.get(clip.as_str()) {
+                *clip = new_name.clone();
+            }
+        }
+    }
+}
+
+/// Mean-removed amplitude envelope of waveform peaks
+pub(super) fn envelope(peaks: &[(f32, f32)]) -> Vec<f64> {
+    let levels: Vec<f64> = peaks
+        .iter()
+        .map(|(min, max)| f64::from(max - min))
+        .collect();
+    let mean = levels.iter().sum::<f64>() / levels.len().max(1) as f64;
+    levels.into_iter().map(|level| level - mean).collect()
+}
+
+/// Normalized correlation of `picture[i]` with `audio[i + lag]` over `range`
+/// of picture blocks, or None when they overlap too little
+fn correlation(
+    picture: &[f64],
+    audio: &[f64],
+    range: std::ops::Range<usize>,
+    lag: i64,
+) -> Option<f64> {
+    let start = (range.start as i64).max(-lag).max(0);
+    let end = (range.end as i64)
+        .min(picture.len() as i64)
+        .min(audio.len() as i64 - lag);
+    if end - start < MIN_OVERLAP_SECONDS * BLOCKS_PER_SECOND as i64 {
+        return None;
+    }
+    let (mut product, mut picture_energy, mut audio_energy) = (0.0, 0.0, 0.0);
+    for i in start..end {
+        let (p, a) = (picture[i as usize], audio[(i + lag) as usize]);
+        product += p * a;
+        picture_energy += p * p;
+        audio_energy += a * a;
+    }
+    let energy = (picture_energy * audio_energy).sqrt();
+    Some(if energy > 0.0 { product / energy } else { 0.0 })
+}
+
+/// Lag between `around - reach` and `around + reach` blocks that correlates best
+pub(super) fn best_lag(
+    picture: &[f64],
+    audio: &[f64],
+    range: std::ops::Range<usize>,
+    around: i64,
+    reach: i64,
+) -> Option<(i64, f64)> {
+    (around - reach..=around + reach)
+        .filter_map(|lag| Some((lag, correlation(picture, audio, range.clone(), lag)?)))
+        .max_by(|a, b| a.1.total_cmp(&b.1).then(b.0.abs().cmp(&a.0.abs())))
+}
+
+/// Clock drift over a pair in blocks, with the seconds it was measured across
+pub(super) fn drift(picture: &[f64], audio: &[f64], lag: i64) -> Option<(i64, f64)> {
+    let window = (DRIFT_WINDOW_SECONDS * BLOCKS_PER_SECOND as i64) as usize;
+    let start = (-lag).max(0) as usize;
+    let end = picture
+        .len()
+        .min((audio.len() as i64 - lag).max(0) as usize);
+    if end < start + 2 * window {
+        return None;
+    }
+    let reach = (DRIFT_SEARCH_SECONDS * f64::from(BLOCKS_PER_SECOND)) as i64;
+    let (first, _) = best_lag(picture, audio, start..start + window, lag, reach)?;
+    let (last, _) = best_lag(picture, audio, end - window..end, lag, reach)?;
+    let span = (end - window - start) as f64 / f64::from(BLOCKS_PER_SECOND);
+    Some((last - first, span))
+}
+
+/// Whether a clip holds sound only, judged by its file extension
+pub(super) fn is_audio_only(clip: &Clip) -> bool {
+    Path::new(&clip.file_path)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| AUDIO_EXTENSIONS.contains(&ext.to_ascii_lowercase().as_str()))
+}
+
+/// Waveform match of one picture clip against one audio clip
+struct WaveformMatch {
+    lag: i64,
+    score: f64,
+    /// Drift in blocks and the seconds it was measured across
+    drift: Option<(i64, f64)>,
+}
+
+impl ResolveBridge {
+    pub(super) async fn auto_sync_audio(
+        &self,
+        state: &mut StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        let clip_references = args["clip_names"]
+            .as_array()
+            .ok_or_else(|| ResolveError::invalid_parameter("clip_names", "required array"))?;
+        let by_timecode = match args["sync_method"].as_str().unwrap_or("waveform") {
+            "waveform" => false,
+            "timecode" => true,
+            _ => {
+                return Err(ResolveError::invalid_parameter(
+                    "sync_method",
+                    "must be 'waveform' or 'timecode'",
+                ))
+            }
+        };
+        let append_audio = args["append_mode"].as_bool().unwrap_or(false);
+        let target_bin = args["target_bin"].as_str();
+        let mut clip_names = Vec::with_capacity(clip_references.len());
+        for reference in clip_references {
+            let reference = reference.as_str().ok_or_else(|| {
+                ResolveError::invalid_parameter("clip_names", "clip names must be strings")
+            })?;
+            let name = Self::resolve_clip(state, reference)?;
+            if !clip_names.contains(&name) {
+                clip_names.push(name);
+            }
+        }
+        let rate = self.project_frame_rate()?;
+
+        let starts: HashMap<&str, i64> = clip_names
+            .iter()
+            .filter_map(|name| {
+                let timecode = state.media_pool.clips[name].start_timecode.as_deref()?;
+                Some((name.as_str(), timecode_frames(timecode, rate).ok()?))
+            })
+            .collect();
+        // Timecode sync also reports every clip's offset from the first to start
+        let timecode_report = if by_timecode {
+            Some(self.sync_by_timecode(state, &clip_names)?)
+        } else {
+            None
+        };
+
+        let (audio_clips, picture_clips): (Vec<&String>, Vec<&String>) = clip_names
+            .iter()
+            .partition(|name| is_audio_only(&state.media_pool.clips[*name]));
+        let blocks_per_frame = f64::from(BLOCKS_PER_SECOND) / rate.fps();
+        let to_blocks = |frames: i64| (frames as f64 * blocks_per_frame).round() as i64;
+        let to_frames = |blocks: i64| (blocks as f64 / blocks_per_frame).round() as i64;
+
+        // Each file is decoded once however many pairs it is tried in
+        let mut envelopes: HashMap<&str, (Vec<f64>, PeakSource)> = HashMap::new();
+        for name in &clip_names {
+            let (peaks, source) = self.media_peaks(&state.media_pool.clips[name].file_path);
+            envelopes.insert(name, (envelope(&peaks), source));
+        }
+        let waveform_match = |picture: &str, audio: &str, around: i64, reach: i64| {
+            let (picture, _) = &envelopes[picture];
+            let (audio, _) = &envelopes[audio];
+            let (lag, score) = best_lag(picture, audio, 0..picture.len(), around, reach)?;
+            Some(WaveformMatch {
+                lag,
+                score,
+                drift: drift(picture, audio, lag),
+            })
+        };
+        let decoded = |name: &str| envelopes[name].1 != PeakSource::Simulated;
+
+        let mut pairs = Vec::new();
+        let mut unpaired = Vec::new();
+        let mut synced = Vec::new();
+        for picture in &picture_clips {
+            let offset_by_timecode =
+                |audio: &str| Some(starts.get(picture.as_str())? - starts.get(audio)?);
+            let paired = if by_timecode {
+                // The audio clip that started nearest the picture
+                audio_clips
+                    .iter()
+                    .filter_map(|audio| Some((*audio, offset_by_timecode(audio)?)))
+                    .min_by_key(|(audio, offset)| (offset.abs(), audio.to_string()))
+                    .map(|(audio, offset)| {
+                        let refined = (decoded(picture) && decoded(audio))
+                            .then(|| {
+                                let reach = REFINE_SECONDS * BLOCKS_PER_SECOND as i64;
+                                waveform_match(picture, audio, to_blocks(offset), reach)
+                            })
+                            .flatten();
+                        (audio, offset, refined)
+                    })
+            } else {
+                // The audio clip whose waveform matches best
+                audio_clips
+                    .iter()
+                    .filter_map(|audio| {
+                        let around = offset_by_timecode(audio).map_or(0, to_blocks);
+                        let reach = SEARCH_SECONDS * BLOCKS_PER_SECOND as i64;
+                        Some((*audio, waveform_match(picture, audio, around, reach)?))
+                    })
+                    .max_by(|a, b| a.1.score.total_cmp(&b.1.score))
+                    .map(|(audio, found)| (audio, to_frames(found.lag), Some(found)))
+            };
+            let Some((audio, timecode_offset, found)) = paired else {
+                unpaired.push(picture.to_string());
+                continue;
+            };
+
+            let (offset_frames, method) = match (&found, by_timecode) {
+                (Some(found), true) => (to_frames(found.lag), "timecode+waveform"),
+                (Some(found), false) => (to_frames(found.lag), "waveform"),
+                (None, _) => (timecode_offset, "timecode"),
+            };
+            let block_ms = 1000.0 / f64::from(BLOCKS_PER_SECOND);
+            let drift_ms = found
+                .as_ref()
+                .and_then(|found| found.drift)
+                .map(|(blocks, span)| (blocks as f64 * block_ms, span));
+            let frame_ms = 1000.0 / rate.fps();
+            pairs.push(json!({
+                "picture_clip": picture,
+                "audio_clip": audio,
+                "method": method,
+                "offset_frames": offset_frames,
+                "offset_timecode": rate.frames_to_timecode(offset_frames.abs()),
+                "audio_starts_first": offset_frames >= 0,
+                "timecode_offset_frames": by_timecode.then_some(timecode_offset),
+                "correlation": found.as_ref().map(|found| found.score),
+                "waveform_source": if found.is_none() {
+                    None
+                } else if decoded(picture) && decoded(audio) {
+                    Some("decoded")
+                } else {
+                    Some("simulated")
+                },
+                "drift_ms": drift_ms.map(|(ms, _)| ms),
+                "drift_ppm": drift_ms.map(|(ms, span)| ms / (span * 1000.0) * 1_000_000.0),
+                "drift_measured_over_seconds": drift_ms.map(|(_, span)| span),
+                "drift_exceeds_frame": drift_ms.map(|(ms, _)| ms.abs() >= frame_ms)
+            }));
+            synced.push((
+                picture.to_string(),
+                SyncedClip {
+                    picture_clip: picture.to_string(),
+                    audio_clip: audio.to_string(),
+                    offset_frames,
+                    append_audio,
+                },
+            ));
+        }
+
+        // A synced clip plays the picture's media with the paired audio
+        let new_clips = synced
+            .iter()
+            .filter(|(picture, _)| {
+                !state
+                    .media_pool
+                    .clips
+                    .contains_key(&format!("{} (Synced)", picture))
+            })
+            .count();
+        ensure_capacity(
+            "clips",
+            state.media_pool.clips.len(),
+            new_clips,
+            self.config.limits.max_clips,
+        )?;
+        let media_pool = &mut *state.media_pool;
+        if let Some(bin) = target_bin.filter(|bin| !media_pool.bins.contains_key(*bin)) {
+            media_pool.bins.insert(
+                bin.to_string(),
+                Bin {
+                    id: Uuid::new_v4().to_string(),
+                    name: bin.to_string(),
+                    clips: Vec::new(),
+                    color_rules: Vec::new(),
+                },
+            );
+        }
+        let mut synced_clips = Vec::with_capacity(synced.len());
+        for (picture, sync) in synced {
+            let name = format!("{} (Synced)", picture);
+            let source = &media_pool.clips[&picture];
+            let bin = target_bin
+                .map(str::to_string)
+                .or_else(|| source.bin.clone());
+            let clip = Clip {
+                id: media_pool
+                    .clips
+                    .get(&name)
+                    .map_or_else(|| Uuid::new_v4().to_string(), |clip| clip.id.clone()),
+                name: name.clone(),
+                bin: bin.clone(),
+                proxy_path: None,
+                ..source.clone()
+            };
+            let clip_id = clip.id.clone();
+            media_pool.clips.insert(name.clone(), clip);
+            if let Some(bin) = bin.and_then(|bin| media_pool.bins.get_mut(&bin)) {
+                if !bin.clips.contains(&name) {
+                    bin.clips.push(name.clone());
+                }
+            }
+            let mut entry = sync.to_json();
+            entry["clip_name"] = json!(name);
+            entry["clip_id"] = json!(clip_id);
+            synced_clips.push(entry);
+            media_pool.synced_clips.insert(name, sync);
+        }
+
+        let mut report = timecode_report.unwrap_or_else(|| json!({}));
+        report["result"] = json!(format!(
+            "Synchronized {} of {} picture clips to audio using {} method",
+            synced_clips.len(),
+            picture_clips.len(),
+            if by_timecode { "timecode" } else { "waveform" }
+        ));
+        report["sync_id"] = json!(Uuid::new_v4().to_string());
+        report["sync_method"] = json!(if by_timecode { "timecode" } else { "waveform" });
+        report["frame_rate"] = json!(rate.to_string());
+        report["pairs"] = json!(pairs);
+        report["synced_clips"] = json!(synced_clips);
+        report["unpaired"] = json!(unpaired);
+        Ok(report)
+    }
+}
@@ -0,0 +1,312 @@
+//! Auto reframe to vertical
+//!
+//! auto_reframe_timeline makes a 9:16 copy of a timeline for vertical
+//! deliveries. Each picture item on the copy is zoomed until the source frame
+//! fills the vertical frame's height, and panned across it to keep the subject
+//! in view. Subject positions come from an external detector as horizontal
+//! centers at frames of the item, each with the detector's confidence; the
+//! pan follows them with a keyframe per position. Items without positions are
+//! held at the center of the frame and reported with no confidence, as the
+//! ones to check by eye.
+//!
+//! A subject closer to the edge than the zoomed frame can pan is framed as
+//! near as the edge allows, and counted as clamped.
+
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use super::delivery_qc::MediaKind;
+use super::{
+    ensure_capacity, output_framing, timeline_versions, InterpolationType, Keyframe, KeyframeModes,
+    ResolveBridge, StateView, TimelineItemKeyframes,
+};
+use crate::error::{ResolveError, ResolveResult};
+
+/// Confidence below which an item is listed for review, unless given
+const DEFAULT_MIN_CONFIDENCE: f64 = 0.5;
+
+/// A subject position reported by a detector
+struct SubjectPosition {
+    /// Frame from the start of the item
+    frame: i64,
+    /// Horizontal center of the subject, from 0 at the left edge to 1 at the right
+    x: f64,
+    confidence: f64,
+}
+
+/// Subject positions by timeline item ID, sorted by frame
+fn subject_positions(args: &Value) -> ResolveResult<HashMap<String, Vec<SubjectPosition>>> {
+    let mut subjects = HashMap::new();
+    let Some(entries) = args["subjects"].as_array() else {
+        return Ok(subjects);
+    };
+    for entry in entries {
+        let item_id = entry["timeline_item_id"].as_str().ok_or_else(|| {
+            ResolveError::invalid_parameter("subjects", "each entry needs a timeline_item_id")
+        })?;
+        let mut positions = Vec::new();
+        for position in entry["positions"].as_array().into_iter().flatten() {
+            let frame = position["frame"].as_i64().filter(|frame| *frame >= 0);
+            let x = position["x"].as_f64().filter(|x| (0.0..=1.0).contains(x));
+            let confidence = match &position["confidence"] {
+                Value::Null => Some(1.0),
+                value => value.as_f64().filter(|c| (0.0..=1.0).contains(c)),
+            };
+            let (Some(frame), Some(x), Some(confidence)) = (frame, x, confidence) else {
+                return Err(ResolveError::invalid_parameter(
+                    "subjects",
+                    format!(
+                        "positions of '{}' need a non-negative frame, x from 0 to 1 and confidence from 0 to 1",
+                        item_id
+                    ),
+                ));
+            };
+            positions.push(SubjectPosition {
+                frame,
+                x,
+                confidence,
+            });
+        }
+        if positions.is_empty() {
+            return Err(ResolveError::invalid_parameter(
+                "subjects",
+                format!("'{}' has no positions", item_id),
+            ));
+        }
+        positions.sort_by_key(|position| position.frame);
+        positions.dedup_by_key(|position| position.frame);
+        if subjects.insert(item_id.to_string(), positions).is_some() {
+            return Err(ResolveError::invalid_parameter(
+                "subjects",
+                format!("'{}' is listed twice", item_id),
+            ));
+        }
+    }
+    Ok(subjects)
+}
+
+/// Keyframes of one property, numbered from the global counter
+fn keyframes(
+    counter: &mut u64,
+    values: &[(i64, f64)],
+    interpolation: InterpolationType,
+) -> Vec<Keyframe> {
+    values
+        .iter()
+        .map(|&(frame, value)| {
+            *counter += 1;
+            Keyframe {
+                id: *counter,
+                frame: frame as i32,
+                value,
+                interpolation: interpolation.clone(),
+                created_at: chrono::Utc::now().to_rfc3339(),
+            }
+        })
+        .collect()
+}
+
+impl ResolveBridge {
+    pub(super) async fn auto_reframe_timeline(
+        &self,
+        state: &mut StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        let source_name = Self::resolve_timeline_name(state, &args)?;
+        let new_name = args["new_timeline_name"]
+            .as_str()
+            .map(str::to_string)
+            .unwrap_or_else(|| format!("{} 9x16", source_name));
+        if state.timelines.contains_key(&new_name) {
+            return Err(ResolveError::invalid_parameter(
+                "new_timeline_name",
+                format!("timeline '{}' already exists", new_name),
+            ));
+        }
+        let min_confidence = args["min_confidence"]
+            .as_f64()
+            .unwrap_or(DEFAULT_MIN_CONFIDENCE);
+        let mut subjects = subject_positions(&args)?;
+
+        // Picture items are reframed; titles, generators and audio keep their place
+        let mut reframed: Vec<(String, i64)> = state
+            .timeline_items
+            .items
+            .values()
+            .filter(|item| item.timeline_name == source_name && item.generator.is_none())
+            .filter(|item| {
+                state
+                    .media_pool
+                    .clips
+                    .get(&item.clip_name)
+                    .is_none_or(|clip| MediaKind::of(&clip.file_path) != MediaKind::Audio)
+            })
+            .filter_map(|item| Some((item.id.clone(), item.placement.as_ref()?.duration)))
+            .collect();
+        reframed.sort();
+        for (item_id, positions) in &subjects {
+            let Some((_, duration)) = reframed.iter().find(|(id, _)| id == item_id) else {
+                return Err(ResolveError::invalid_parameter(
+                    "subjects",
+                    format!(
+                        "'{}' is not a picture item on timeline '{}'",
+                        item_id, source_name
+                    ),
+                ));
+            };
+            if let Some(position) = positions.iter().find(|p| p.frame >= *duration) {
+                return Err(ResolveError::invalid_parameter(
+                    "subjects",
+                    format!(
+                        "frame {} is past the end of '{}', which is {} frames long",
+                        position.frame, item_id, duration
+                    ),
+                ));
+            }
+            let existing = state
+                .keyframe_state
+                .timeline_item_keyframes
+                .get(item_id)
+                .map_or(0, |item| {
+                    item.property_keyframes
+                        .iter()
+                        .filter(|(property, _)| !matches!(property.as_str(), "Pan" | "ZoomX"))
+                        .map(|(_, keyframes)| keyframes.len())
+                        .sum()
+                });
+            ensure_capacity(
+                &format!("keyframes on timeline item '{}'", item_id),
+                existing,
+                positions.len() + 1,
+                self.config.limits.max_keyframes_per_item,
+            )?;
+        }
+        ensure_capacity(
+            "timelines",
+            state.timelines.len(),
+            1,
+            self.config.limits.max_timelines,
+        )?;
+
+        let source = &state.timelines[&source_name];
+        let source_size = output_framing::timeline_size(source);
+        let (width, height) = output_framing::reframed_size("vertical", source_size);
+        // Zoom that makes the source frame, fitted to the width, fill the height
+        let source_aspect = f64::from(source_size.0) / f64::from(source_size.1);
+        let vertical_aspect = f64::from(width) / f64::from(height);
+        let zoom = (source_aspect / vertical_aspect).max(1.0);
+        let zoomed_width = f64::from(width) * zoom;
+        let max_pan = (zoomed_width - f64::from(width)) / 2.0;
+
+        let mut timeline = source.clone();
+        timeline.id = Uuid::new_v4().to_string();
+        timeline.name = new_name.clone();
+        timeline.resolution_width = Some(width as i32);
+        timeline.resolution_height = Some(height as i32);
+        timeline.lineage = None;
+        timeline.output = Default::default();
+        let timeline_id = timeline.id.clone();
+        state.timelines.insert(new_name.clone(), timeline);
+        let copies = timeline_versions::copy_timeline_items(state, &source_name, &new_name);
+
+        let mut items = Vec::new();
+        let mut review = Vec::new();
+        let mut confidences = Vec::new();
+        for (source_id, _) in &reframed {
+            let Some((_, copy_id)) = copies.iter().find(|(from, _)| from == source_id) else {
+                continue;
+            };
+            let positions = subjects.remove(source_id);
+            let mut clamped = 0;
+            let (pans, confidence) = match &positions {
+                Some(positions) => {
+                    let pans: Vec<(i64, f64)> = positions
+                        .iter()
+                        .map(|position| {
+                            let pan = -(position.x - 0.5) * zoomed_width;
+                            if pan.abs() > max_pan {
+                                clamped += 1;
+                            }
+                            let pan = pan.clamp(-max_pan, max_pan);
+                            (position.frame, (pan * 100.0).round() / 100.0)
+                        })
+                        .collect();
+                    let confidence = positions.iter().map(|p| p.confidence).sum::<f64>()
+                        / positions.len() as f64;
+                    (pans, Some((confidence * 1000.0).round() / 1000.0))
+                }
+                None => (vec![(0, 0.0)], None),
+            };
+
+            let keyframe_state = &mut *state.keyframe_state;
+            let pan_keyframes = keyframes(
+                &mut keyframe_state.keyframe_counter,
+                &pans,
+                InterpolationType::Bezier,
+            );
+            let zoom_keyframes = keyframes(
+                &mut keyframe_state.keyframe_counter,
+                &[(0, zoom)],
+                InterpolationType::Linear,
+            );
+            let item_keyframes = keyframe_state
+                .timeline_item_keyframes
+                .entry(copy_id.clone())
+                .or_insert_with(|| TimelineItemKeyframes {
+                    timeline_item_id: copy_id.clone(),
+                    property_keyframes: HashMap::new(),
+                    keyframe_modes: KeyframeModes::default(),
+                });
+            item_keyframes
+                .property_keyframes
+                .insert("Pan".to_string(), pan_keyframes);
+            item_keyframes
+                .property_keyframes
+                .insert("ZoomX".to_string(), zoom_keyframes);
+
+            let clip_name = state
+                .timeline_items
+                .items
+                .get(copy_id)
+                .map(|item| item.clip_name.clone());
+            if confidence.is_none_or(|confidence| confidence < min_confidence) {
+                review.push(copy_id.clone());
+            }
+            confidences.extend(confidence);
+            items.push(json!({
+                "timeline_item_id": copy_id,
+                "source_timeline_item_id": source_id,
+                "clip_name": clip_name,
+                "subject_source": if positions.is_some() { "detector" } else { "center" },
+                "confidence": confidence,
+                "pan_keyframes": pans.len(),
+                "clamped_positions": clamped
+            }));
+        }
+        *state.current_timeline = Some(new_name.clone());
+
+        let average_confidence = (!confidences.is_empty()).then(|| {
+            (confidences.iter().sum::<f64>() / confidences.len() as f64 * 1000.0).round() / 1000.0
+        });
+        Ok(json!({
+            "result": format!(
+                "Created vertical timeline '{}' from '{}' with {} reframed items",
+                new_name,
+                source_name,
+                items.len()
+            ),
+            "timeline_name": new_name,
+            "timeline_id": timeline_id,
+            "source_timeline": source_name,
+            "resolution": { "width": width, "height": height },
+            "zoom": (zoom * 10000.0).round() / 10000.0,
+            "max_pan": (max_pan * 100.0).round() / 100.0,
+            "items": items,
+            "average_confidence": average_confidence,
+            "min_confidence": min_confidence,
+            "needs_review": review,
+            "operation_id": Uuid::new_v4().to_string()
+        }))
+    }
+}
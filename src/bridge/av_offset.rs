@@ -0,0 +1,345 @@
+//! Audio and picture offset between versions of a cut
+//!
+//! detect_av_offset checks a render against a reference: another render, such
+//! as the one approved before a conform or an audio mix round-trip, or the
+//! timeline it was rendered from. Sound is compared by cross-correlating
+//! waveform envelopes, as auto_sync_audio does, which also measures drift
+//! between the start and the end. Picture is compared by cross-correlating
+//! where the image changes from one frame to the next; a timeline's picture
+//! changes at its edit points. The two offsets differ when the render's sound
+//! has slipped against its picture, which is reported as the sync error.
+//!
+//! Decoding takes ffmpeg. Without it, or for a render it cannot decode, the
+//! render is simulated as a faithful copy of the reference, so the offsets
+//! come out as zero and are marked simulated, and picture is not compared.
+
+use serde_json::{json, Value};
+use std::path::Path;
+use uuid::Uuid;
+
+use super::audio_sync::{best_lag, drift, envelope};
+use super::ffmpeg;
+use super::waveform::{PeakSource, BLOCKS_PER_SECOND};
+use super::{ResolveBridge, StateView};
+use crate::error::{ResolveError, ResolveResult};
+use crate::timecode::FrameRate;
+
+/// Furthest apart the versions are searched for, unless the call says otherwise
+const DEFAULT_MAX_OFFSET_SECONDS: f64 = 10.0;
+
+/// Shortest stretch of both pictures a correlation is trusted on
+const MIN_PICTURE_OVERLAP_SECONDS: f64 = 2.0;
+
+/// Size frames are scaled to before their changes are compared
+const SIGNATURE_WIDTH: u32 = 32;
+const SIGNATURE_HEIGHT: u32 = 18;
+
+/// What a render is checked against
+enum Reference {
+    Render(String),
+    Timeline(String),
+}
+
+/// Mean-removed amount each frame differs from the one before it
+fn change_signature(frames: &[Vec<u8>]) -> Vec<f64> {
+    let changes: Vec<f64> = std::iter::once(0.0)
+        .chain(frames.windows(2).map(|pair| {
+            let total: u64 = pair[0]
+                .iter()
+                .zip(&pair[1])
+                .map(|(a, b)| u64::from(a.abs_diff(*b)))
+                .sum();
+            total as f64 / pair[0].len().max(1) as f64
+        }))
+        .collect();
+    let mean = changes.iter().sum::<f64>() / changes.len().max(1) as f64;
+    changes.into_iter().map(|change| change - mean).collect()
+}
+
+/// Lag within `reach` frames at which `render[i + lag]` best matches
+/// `reference[i]`, with its normalized correlation
+fn picture_lag(
+    reference: &[f64],
+    render: &[f64],
+    reach: i64,
+    rate: FrameRate,
+) -> Option<(i64, f64)> {
+    let min_overlap = (MIN_PICTURE_OVERLAP_SECONDS * rate.fps()).round() as i64;
+    (-reach..=reach)
+        .filter_map(|lag| {
+            let start = (-lag).max(0);
+            let end = (reference.len() as i64).min(render.len() as i64 - lag);
+            if end - start < min_overlap {
+                return None;
+            }
+            let (mut product, mut reference_energy, mut render_energy) = (0.0, 0.0, 0.0);
+            for i in start..end {
+                let (a, b) = (reference[i as usize], render[(i + lag) as usize]);
+                product += a * b;
+                reference_energy += a * a;
+                render_energy += b * b;
+            }
+            let energy = (reference_energy * render_energy).sqrt();
+            Some((lag, if energy > 0.0 { product / energy } else { 0.0 }))
+        })
+        .max_by(|a, b| a.1.total_cmp(&b.1).then(b.0.abs().cmp(&a.0.abs())))
+}
+
+/// Fail with `FileNotFound` unless `path` is a file
+fn require_file(path: &str) -> ResolveResult<()> {
+    if Path::new(path).is_file() {
+        Ok(())
+    } else {
+        Err(ResolveError::FileNotFound {
+            path: path.to_string(),
+        })
+    }
+}
+
+impl ResolveBridge {
+    /// Peaks of a timeline's sound, laid out from its items' media
+    fn timeline_peaks(
+        &self,
+        state: &StateView<'_>,
+        timeline_name: &str,
+        rate: FrameRate,
+    ) -> (Vec<(f32, f32)>, PeakSource) {
+        let blocks_per_frame = f64::from(BLOCKS_PER_SECOND) / rate.fps();
+        let to_blocks = |frames: i64| (frames.max(0) as f64 * blocks_per_frame).round() as usize;
+        let placed: Vec<_> = state
+            .timeline_items
+            .items
+            .values()
+            .filter(|item| item.timeline_name == timeline_name && item.generator.is_none())
+            .filter_map(|item| {
+                Some((
+                    item.placement.as_ref()?,
+                    state.media_pool.clips.get(&item.clip_name)?,
+                ))
+            })
+            .collect();
+        let end = placed
+            .iter()
+            .map(|(placement, _)| to_blocks(placement.record_out()))
+            .max()
+            .unwrap_or(0);
+
+        let mut peaks = vec![(0.0f32, 0.0f32); end];
+        let mut source = PeakSource::Decoded;
+        for (placement, clip) in placed {
+            let (clip_peaks, clip_source) = self.media_peaks(&clip.file_path);
+            if clip_source == PeakSource::Simulated {
+                source = PeakSource::Simulated;
+            }
+            let at = to_blocks(placement.record_in);
+            let length = to_blocks(placement.record_out()) - at;
+            let blocks = clip_peaks
+                .iter()
+                .skip(to_blocks(placement.source_in))
+                .take(length);
+            for (peak, &(min, max)) in peaks.iter_mut().skip(at).zip(blocks) {
+                peak.0 = peak.0.min(min);
+                peak.1 = peak.1.max(max);
+            }
+        }
+        (peaks, source)
+    }
+
+    /// Mean-removed signature of a timeline's picture: a spike at every edit
+    fn timeline_signature(state: &StateView<'_>, timeline_name: &str) -> Vec<f64> {
+        let placements: Vec<_> = state
+            .timeline_items
+            .items
+            .values()
+            .filter(|item| item.timeline_name == timeline_name)
+            .filter_map(|item| item.placement.as_ref())
+            .collect();
+        let end = placements
+            .iter()
+            .map(|placement| placement.record_out())
+            .max()
+            .unwrap_or(0)
+            .max(0) as usize;
+        let mut edits = vec![0.0; end];
+        for placement in placements {
+            for frame in [placement.record_in, placement.record_out()] {
+                if let Some(edit) = edits.get_mut(frame.max(1) as usize) {
+                    *edit = 1.0;
+                }
+            }
+        }
+        let mean = edits.iter().sum::<f64>() / edits.len().max(1) as f64;
+        edits.into_iter().map(|edit| edit - mean).collect()
+    }
+
+    pub(super) async fn detect_av_offset(
+        &self,
+        state: &mut StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        let file_path = args["file_path"]
+            .as_str()
+            .ok_or_else(|| ResolveError::invalid_parameter("file_path", "required string"))?;
+        require_file(file_path)?;
+        let reference = match (
+            args["reference_path"].as_str(),
+            args["timeline_name"].is_null(),
+        ) {
+            (Some(_), false) => {
+                return Err(ResolveError::invalid_parameter(
+                    "reference_path",
+                    "give reference_path or timeline_name, not both",
+                ))
+            }
+            (Some(path), true) => {
+                require_file(path)?;
+                Reference::Render(path.to_string())
+            }
+            (None, _) => Reference::Timeline(Self::resolve_timeline_name(state, &args)?),
+        };
+        let max_offset_seconds = args["max_offset_seconds"]
+            .as_f64()
+            .unwrap_or(DEFAULT_MAX_OFFSET_SECONDS);
+        if !(max_offset_seconds > 0.0 && max_offset_seconds <= 600.0) {
+            return Err(ResolveError::invalid_parameter(
+                "max_offset_seconds",
+                "must be greater than 0 and at most 600",
+            ));
+        }
+        let rate = match &reference {
+            Reference::Timeline(name) => self.timeline_frame_rate(&state.timelines[name])?,
+            Reference::Render(_) => self.project_frame_rate()?,
+        };
+        let frame_ms = 1000.0 / rate.fps();
+        let block_ms = 1000.0 / f64::from(BLOCKS_PER_SECOND);
+
+        // Sound
+        let (reference_peaks, reference_source) = match &reference {
+            Reference::Render(path) => self.media_peaks(path),
+            Reference::Timeline(name) => self.timeline_peaks(state, name, rate),
+        };
+        let (render_peaks, render_source) = self.media_peaks(file_path);
+        let simulated = render_source == PeakSource::Simulated;
+        let reference_envelope = envelope(&reference_peaks);
+        let render_envelope = if simulated {
+            reference_envelope.clone()
+        } else {
+            envelope(&render_peaks)
+        };
+        let reach = (max_offset_seconds * f64::from(BLOCKS_PER_SECOND)).round() as i64;
+        let sound = best_lag(
+            &reference_envelope,
+            &render_envelope,
+            0..reference_envelope.len(),
+            0,
+            reach,
+        )
+        .map(|(lag, score)| {
+            let offset_ms = lag as f64 * block_ms;
+            let drift_ms = drift(&reference_envelope, &render_envelope, lag)
+                .map(|(blocks, span)| (blocks as f64 * block_ms, span));
+            json!({
+                "offset_frames": (offset_ms / frame_ms).round() as i64,
+                "offset_ms": offset_ms,
+                "correlation": score,
+                "waveform_source": if simulated || reference_source == PeakSource::Simulated {
+                    "simulated"
+                } else {
+                    "decoded"
+                },
+                "drift_ms": drift_ms.map(|(ms, _)| ms),
+                "drift_ppm": drift_ms.map(|(ms, span)| ms / (span * 1000.0) * 1_000_000.0),
+                "drift_measured_over_seconds": drift_ms.map(|(_, span)| span),
+                "drift_exceeds_frame": drift_ms.map(|(ms, _)| ms.abs() >= frame_ms)
+            })
+        });
+
+        // Picture
+        let decode = |path: &str| {
+            ffmpeg::gray_frames(
+                Path::new(path),
+                rate.fps(),
+                SIGNATURE_WIDTH,
+                SIGNATURE_HEIGHT,
+            )
+            .map(|frames| change_signature(&frames))
+        };
+        let picture = if simulated || !ffmpeg::available() {
+            Err("the render cannot be decoded without ffmpeg".to_string())
+        } else {
+            decode(file_path).and_then(|render| {
+                let reference = match &reference {
+                    Reference::Render(path) => decode(path)?,
+                    Reference::Timeline(name) => Self::timeline_signature(state, name),
+                };
+                let reach = (max_offset_seconds * rate.fps()).round() as i64;
+                picture_lag(&reference, &render, reach, rate)
+                    .ok_or_else(|| "the pictures overlap too little to compare".to_string())
+            })
+        };
+        let (picture, picture_error) = match picture {
+            Ok((lag, score)) => (
+                Some(json!({
+                    "offset_frames": lag,
+                    "offset_ms": lag as f64 * frame_ms,
+                    "correlation": score
+                })),
+                None,
+            ),
+            Err(error) => (None, Some(error)),
+        };
+
+        let sound_frames = sound
+            .as_ref()
+            .and_then(|sound| sound["offset_frames"].as_i64());
+        let picture_frames = picture
+            .as_ref()
+            .and_then(|picture| picture["offset_frames"].as_i64());
+        let sync_error_frames = sound_frames.zip(picture_frames).map(|(a, b)| a - b);
+        let drifts = sound
+            .as_ref()
+            .is_some_and(|sound| sound["drift_exceeds_frame"] == json!(true));
+        let in_sync = sound_frames.is_some_and(|frames| frames == 0)
+            && picture_frames.unwrap_or(0) == 0
+            && !drifts;
+        let describe = |frames: i64| match frames {
+            0 => "in place".to_string(),
+            frames if frames > 0 => format!("{} frames late", frames),
+            frames => format!("{} frames early", -frames),
+        };
+        let against = match &reference {
+            Reference::Render(path) => format!("render {}", path),
+            Reference::Timeline(name) => format!("timeline '{}'", name),
+        };
+        let result = match (sound_frames, picture_frames) {
+            (Some(sound), Some(picture)) => format!(
+                "Against {}: sound is {}, picture is {}",
+                against,
+                describe(sound),
+                describe(picture)
+            ),
+            (Some(sound), None) => format!("Against {}: sound is {}", against, describe(sound)),
+            _ => format!("Could not line up the render with {}", against),
+        };
+
+        Ok(json!({
+            "result": result,
+            "file_path": file_path,
+            "reference": match &reference {
+                Reference::Render(path) => json!({ "type": "render", "path": path }),
+                Reference::Timeline(name) => json!({
+                    "type": "timeline",
+                    "timeline_name": name,
+                    "timeline_id": state.timelines[name].id
+                }),
+            },
+            "frame_rate": rate.to_string(),
+            "audio": sound,
+            "video": picture,
+            "video_not_measured": picture_error,
+            "sync_error_frames": sync_error_frames,
+            "in_sync": in_sync,
+            "operation_id": Uuid::new_v4().to_string()
+        }))
+    }
+}
@@ -0,0 +1,367 @@
+//! Project backups and restore points
+//!
+//! A backup snapshots the project-scoped parts of the simulation state. The
+//! most recent `Config::backup.max_backups` snapshots are kept in memory and,
+//! when a backup directory is configured, summarized to timestamped JSON files.
+//! Only the snapshots in memory can be restored; a backup file is a summary for
+//! people to read and does not survive a restart as a restore point.
+
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use uuid::Uuid;
+
+use super::notes::NoteState;
+use super::review::ReviewState;
+use super::state_integrity::Tables;
+use super::tags::TagState;
+use super::transcripts::TranscriptState;
+use super::{
+    ColorState, GalleryState, KeyframeState, MediaPool, RenderState, ResolveBridge, StateView,
+    Timeline, TimelineItemsState,
+};
+use crate::error::{ResolveError, ResolveResult};
+
+/// Backups taken so far, oldest first
+#[derive(Debug, Default)]
+pub(super) struct BackupState {
+    backups: Vec<ProjectBackup>,
+    /// Counter used to generate backup IDs
+    backup_counter: u64,
+}
+
+impl BackupState {
+    /// The parts of a backup checked by verify_state_integrity
+    pub(super) fn tables(&self, backup_id: &str) -> Option<Tables<'_>> {
+        let snapshot = &self
+            .backups
+            .iter()
+            .find(|backup| backup.id == backup_id)?
+            .snapshot;
+        Some(Tables {
+            timelines: &snapshot.timelines,
+            current_timeline: &snapshot.current_timeline,
+            media_pool: &snapshot.media_pool,
+            timeline_items: &snapshot.timeline_items,
+            keyframe_state: &snapshot.keyframe_state,
+            color_state: &snapshot.color_state,
+            render_state: &snapshot.render_state,
+        })
+    }
+}
+
+#[derive(Debug)]
+struct ProjectBackup {
+    /// Unique backup ID
+    id: String,
+    name: String,
+    created_at: chrono::DateTime<chrono::Utc>,
+    /// Backup file written for this restore point, if any
+    file_path: Option<PathBuf>,
+    snapshot: ProjectSnapshot,
+}
+
+impl ProjectBackup {
+    fn to_json(&self) -> Value {
+        json!({
+            "backup_id": self.id,
+            "name": self.name,
+            "project": self.snapshot.project,
+            "created_at": self.created_at.to_rfc3339(),
+            "timeline_count": self.snapshot.timelines.len(),
+            "clip_count": self.snapshot.media_pool.clips.len(),
+            "file_path": self.file_path.as_ref().map(|path| path.display().to_string())
+        })
+    }
+}
+
+/// Project-scoped state captured by a backup
+#[derive(Debug, Clone)]
+struct ProjectSnapshot {
+    project: Option<String>,
+    project_settings: HashMap<String, Value>,
+    current_timeline: Option<String>,
+    timelines: HashMap<String, Timeline>,
+    media_pool: MediaPool,
+    color_state: ColorState,
+    timeline_items: TimelineItemsState,
+    keyframe_state: KeyframeState,
+    render_state: RenderState,
+    gallery: GalleryState,
+    review: ReviewState,
+    tags: TagState,
+    notes: NoteState,
+    transcripts: TranscriptState,
+}
+
+impl ProjectSnapshot {
+    fn capture(state: &StateView<'_>) -> Self {
+        Self {
+            project: state.current_project.clone(),
+            project_settings: state.project_settings.clone(),
+            current_timeline: state.current_timeline.clone(),
+            timelines: state.timelines.clone(),
+            media_pool: state.media_pool.clone(),
+            color_state: state.color_state.clone(),
+            timeline_items: state.timeline_items.clone(),
+            keyframe_state: state.keyframe_state.clone(),
+            render_state: state.render_state.clone(),
+            gallery: state.gallery.clone(),
+            review: state.review.clone(),
+            tags: state.tags.clone(),
+            notes: state.notes.clone(),
+            transcripts: state.transcripts.clone(),
+        }
+    }
+
+    fn restore(self, state: &mut StateView<'_>) {
+        *state.current_project = self.project;
+        *state.project_settings = self.project_settings;
+        *state.current_timeline = self.current_timeline;
+        *state.timelines = self.timelines;
+        *state.media_pool = self.media_pool;
+        *state.color_state = self.color_state;
+        // IDs handed out since the backup are never handed out again
+        let item_counter = state
+            .timeline_items
+            .item_counter
+            .max(self.timeline_items.item_counter);
+        *state.timeline_items = self.timeline_items;
+        state.timeline_items.item_counter = item_counter;
+        let keyframe_counter = state
+            .keyframe_state
+            .keyframe_counter
+            .max(self.keyframe_state.keyframe_counter);
+        *state.keyframe_state = self.keyframe_state;
+        state.keyframe_state.keyframe_counter = keyframe_counter;
+        let job_counter = state
+            .render_state
+            .job_counter
+            .max(self.render_state.job_counter);
+        *state.render_state = self.render_state;
+        state.render_state.job_counter = job_counter;
+        let still_counter = state.gallery.still_counter.max(self.gallery.still_counter);
+        *state.gallery = self.gallery;
+        state.gallery.still_counter = still_counter;
+        let note_counter = state.review.note_counter.max(self.review.note_counter);
+        *state.review = self.review;
+        state.review.note_counter = note_counter;
+        *state.tags = self.tags;
+        *state.notes = self.notes;
+        *state.transcripts = self.transcripts;
+    }
+
+    /// Human-readable summary written to backup files; it is not enough to
+    /// restore the snapshot from
+    fn summary(&self) -> Value {
+        let mut timelines: Vec<Value> = self
+            .timelines
+            .iter()
+            .map(|(name, timeline)| {
+                json!({
+                    "name": name,
+                    "frame_rate": timeline.frame_rate,
+                    "resolution_width": timeline.resolution_width,
+                    "resolution_height": timeline.resolution_height,
+                    "markers": timeline.markers.iter().map(|marker| json!({
+                        "frame": marker.frame,
+                        "color": marker.color,
+                        "note": marker.note
+                    })).collect::<Vec<_>>()
+                })
+            })
+            .collect();
+        timelines.sort_by(|a, b| a["name"].as_str().cmp(&b["name"].as_str()));
+
+        let mut clips: Vec<Value> = self
+            .media_pool
+            .clips
+            .values()
+            .map(|clip| {
+                json!({
+                    "name": clip.name,
+                    "clip_id": clip.id,
+                    "file_path": clip.file_path,
+                    "bin": clip.bin
+                })
+            })
+            .collect();
+        clips.sort_by(|a, b| a["name"].as_str().cmp(&b["name"].as_str()));
+
+        let mut bins: Vec<&String> = self.media_pool.bins.keys().collect();
+        bins.sort();
+
+        json!({
+            "project": self.project,
+            "current_timeline": self.current_timeline,
+            "timelines": timelines,
+            "bins": bins,
+            "clips": clips
+        })
+    }
+}
+
+/// Make a project name safe to use in a file name
+fn file_stem(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+impl ResolveBridge {
+    pub(super) async fn create_backup(
+        &self,
+        state: &mut StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        let project = state
+            .current_project
+            .clone()
+            .ok_or(ResolveError::NotRunning)?;
+        let write_file = args["write_file"].as_bool().unwrap_or(false);
+        let backup_config = &self.config.backup;
+
+        let directory = if write_file {
+            Some(backup_config.directory.as_ref().ok_or_else(|| {
+                ResolveError::invalid_parameter("write_file", "no backup directory is configured")
+            })?)
+        } else {
+            None
+        };
+
+        state.backups.backup_counter += 1;
+        let id = format!("backup_{:04}", state.backups.backup_counter);
+        let created_at = chrono::Utc::now();
+        let name = args["name"]
+            .as_str()
+            .map(|name| name.to_string())
+            .unwrap_or_else(|| format!("{} {}", project, created_at.format("%Y-%m-%d %H:%M:%S")));
+        let snapshot = ProjectSnapshot::capture(state);
+
+        let file_path = match directory {
+            Some(directory) => {
+                let path = directory.join(format!(
+                    "{}_{}_{}.json",
+                    file_stem(&project),
+                    created_at.format("%Y%m%d_%H%M%S"),
+                    id
+                ));
+                let contents = json!({
+                    "backup_id": id,
+                    "name": name,
+                    "created_at": created_at.to_rfc3339(),
+                    "snapshot": snapshot.summary()
+                });
+                std::fs::create_dir_all(directory)
+                    .and_then(|_| std::fs::write(&path, serde_json::to_vec_pretty(&contents)?))
+                    .map_err(|e| {
+                        ResolveError::internal(format!(
+                            "failed to write backup file '{}': {}",
+                            path.display(),
+                            e
+                        ))
+                    })?;
+                Some(path)
+            }
+            None => None,
+        };
+
+        let backup = ProjectBackup {
+            id: id.clone(),
+            name,
+            created_at,
+            file_path,
+            snapshot,
+        };
+        let response = backup.to_json();
+        state.backups.backups.push(backup);
+
+        // Rotate out the oldest backups, along with any files they wrote
+        let excess = state
+            .backups
+            .backups
+            .len()
+            .saturating_sub(backup_config.max_backups);
+        let rotated: Vec<String> = state
+            .backups
+            .backups
+            .drain(..excess)
+            .map(|old| {
+                if let Some(path) = &old.file_path {
+                    if let Err(e) = std::fs::remove_file(path) {
+                        tracing::warn!("Failed to remove backup file {}: {}", path.display(), e);
+                    }
+                }
+                old.id
+            })
+            .collect();
+
+        Ok(json!({
+            "result": format!("Created backup '{}' of project '{}'", id, project),
+            "backup": response,
+            "rotated_out": rotated,
+            "backup_count": state.backups.backups.len(),
+            "operation_id": Uuid::new_v4().to_string()
+        }))
+    }
+
+    pub(super) async fn restore_backup(
+        &self,
+        state: &mut StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        let backup_id = args["backup_id"]
+            .as_str()
+            .ok_or_else(|| ResolveError::invalid_parameter("backup_id", "required string"))?;
+
+        let backup = state
+            .backups
+            .backups
+            .iter()
+            .find(|backup| backup.id == backup_id)
+            .ok_or_else(|| {
+                ResolveError::invalid_parameter(
+                    "backup_id",
+                    format!("backup '{}' not found", backup_id),
+                )
+            })?;
+        let snapshot = backup.snapshot.clone();
+        let response = backup.to_json();
+        snapshot.restore(state);
+
+        Ok(json!({
+            "result": format!("Restored backup '{}'", backup_id),
+            "backup": response,
+            "operation_id": Uuid::new_v4().to_string()
+        }))
+    }
+
+    pub(super) async fn list_backups(
+        &self,
+        state: &StateView<'_>,
+        _args: Value,
+    ) -> ResolveResult<Value> {
+        // Newest first, matching the order restore points are usually picked in
+        let backups: Vec<Value> = state
+            .backups
+            .backups
+            .iter()
+            .rev()
+            .map(ProjectBackup::to_json)
+            .collect();
+
+        Ok(json!({
+            "result": format!("Found {} backups", backups.len()),
+            "backups": backups,
+            "count": backups.len(),
+            "max_backups": self.config.backup.max_backups,
+            "operation_id": Uuid::new_v4().to_string()
+        }))
+    }
+}
@@ -0,0 +1,165 @@
+//! Bars and tone at the head of a timeline for delivery prep
+//!
+//! Puts a colour bars generator from the standard inventory at the very start
+//! of the timeline with a reference tone under it. Simulated timelines keep no
+//! audio items, so the tone travels with the bars item as its linked audio and
+//! is reported on the audio track it would occupy. When the timeline already
+//! starts with content, everything, slate included, moves later to make room.
+
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use super::generators::{self, GeneratorKind};
+use super::item_lookup::timeline_clock;
+use super::slate::{is_generated, ripple_later, seconds, track_index};
+use super::{ItemPlacement, ResolveBridge, StateView, TimelineItemState};
+use crate::error::{ResolveError, ResolveResult};
+
+const DEFAULT_PATTERN: &str = "SMPTE Color Bar";
+const DEFAULT_SECONDS: f64 = 60.0;
+const DEFAULT_FREQUENCY_HZ: f64 = 1_000.0;
+/// SMPTE RP 155 alignment level
+const DEFAULT_LEVEL_DBFS: f64 = -20.0;
+
+impl ResolveBridge {
+    pub(super) async fn insert_bars_and_tone(
+        &self,
+        state: &mut StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        let timeline_name = Self::resolve_timeline_name(state, &args)?;
+        let (rate, start_frame) = timeline_clock(self, state, &timeline_name, &args)?;
+        let duration_seconds = seconds(&args, "duration_seconds", DEFAULT_SECONDS, 0.1)?;
+        let pattern = generators::require_standard(
+            "pattern",
+            args["pattern"].as_str().unwrap_or(DEFAULT_PATTERN),
+            Some(GeneratorKind::ColorBars),
+        )?;
+        let track_index = track_index(&args)?;
+
+        let frequency_hz = match &args["tone_frequency_hz"] {
+            Value::Null => DEFAULT_FREQUENCY_HZ,
+            value => value
+                .as_f64()
+                .filter(|hz| (20.0..=20_000.0).contains(hz))
+                .ok_or_else(|| {
+                    ResolveError::invalid_parameter(
+                        "tone_frequency_hz",
+                        "must be between 20 and 20000 Hz",
+                    )
+                })?,
+        };
+        let level_dbfs = match &args["tone_level_dbfs"] {
+            Value::Null => DEFAULT_LEVEL_DBFS,
+            value => value
+                .as_f64()
+                .filter(|level| *level <= 0.0)
+                .ok_or_else(|| {
+                    ResolveError::invalid_parameter("tone_level_dbfs", "must be 0 dBFS or lower")
+                })?,
+        };
+        let audio_track = match args["audio_track_index"].as_i64() {
+            Some(index) if index >= 1 => index,
+            Some(_) => {
+                return Err(ResolveError::invalid_parameter(
+                    "audio_track_index",
+                    "must be 1 or greater",
+                ))
+            }
+            None => 1,
+        };
+        let channels = match args["channels"].as_i64() {
+            Some(channels) if (1..=16).contains(&channels) => channels,
+            Some(_) => {
+                return Err(ResolveError::invalid_parameter(
+                    "channels",
+                    "must be between 1 and 16",
+                ))
+            }
+            None => 2,
+        };
+
+        let on_timeline = |item: &&TimelineItemState| item.timeline_name == timeline_name;
+        if state
+            .timeline_items
+            .items
+            .values()
+            .filter(on_timeline)
+            .any(|item| is_generated(item, "bars"))
+        {
+            return Err(ResolveError::invalid_parameter(
+                "timeline_name",
+                format!("timeline '{}' already has bars and tone", timeline_name),
+            ));
+        }
+
+        // Make room at the head when the timeline starts too early
+        let duration = ((duration_seconds * rate.fps()).round() as i64).max(1);
+        let first_in = state
+            .timeline_items
+            .items
+            .values()
+            .filter(on_timeline)
+            .filter_map(|item| item.placement.as_ref())
+            .map(|placement| placement.record_in)
+            .min()
+            .unwrap_or(duration);
+        let shift = (duration - first_in).max(0);
+        ripple_later(state, &timeline_name, 0, shift);
+
+        let tone = json!({
+            "frequency_hz": frequency_hz,
+            "level_dbfs": level_dbfs,
+            "channels": channels,
+            "track": format!("A{}", audio_track)
+        });
+        let generator = json!({
+            "type": "bars",
+            "generator_name": pattern.name,
+            "tone": tone.clone()
+        });
+        let item_id = Uuid::new_v4().to_string();
+        state.timeline_items.item_counter += 1;
+        state.timeline_items.items.insert(
+            item_id.clone(),
+            TimelineItemState {
+                id: item_id.clone(),
+                timeline_name: timeline_name.clone(),
+                clip_name: pattern.name.to_string(),
+                placement: Some(ItemPlacement {
+                    track_index,
+                    record_in: 0,
+                    source_in: 0,
+                    duration,
+                }),
+                generator: Some(generator),
+                ..Default::default()
+            },
+        );
+        let record_in = rate.frames_to_timecode(start_frame);
+        let record_out = rate.frames_to_timecode(start_frame + duration);
+        let mut tone = tone;
+        tone["linked_to"] = json!(item_id);
+        tone["record_in"] = json!(record_in);
+        tone["record_out"] = json!(record_out);
+
+        Ok(json!({
+            "result": format!(
+                "Added {} seconds of {} and {} Hz tone at {} dBFS to timeline '{}'",
+                duration_seconds, pattern.name, frequency_hz, level_dbfs, timeline_name
+            ),
+            "timeline_name": timeline_name,
+            "bars": {
+                "timeline_item_id": item_id,
+                "generator_name": pattern.name,
+                "track": format!("V{}", track_index),
+                "record_in": record_in,
+                "record_out": record_out
+            },
+            "tone": tone,
+            "duration_frames": duration,
+            "shifted_frames": shift,
+            "operation_id": Uuid::new_v4().to_string()
+        }))
+    }
+}
@@ -0,0 +1,181 @@
+//! Batch render submission
+//!
+//! Queues one render job per timeline in a single call, for deliveries where
+//! every episode or version goes out with the same preset. Timelines are named
+//! in a list or chosen by a glob over their names, and each job's output
+//! folder comes from a template. The jobs are queued together: if any timeline
+//! cannot be queued, none is.
+
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use super::{paths, ResolveBridge, StateView};
+use crate::error::{ResolveError, ResolveResult};
+
+/// Output folder template used when none is given
+pub(super) const DEFAULT_OUTPUT_TEMPLATE: &str = "/tmp/renders/{project}/{timeline}";
+
+/// Whether `name` matches a glob where `*` stands for any run of characters
+/// and `?` for a single one
+pub(super) fn glob_match(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    let (mut p, mut n) = (0, 0);
+    // Position after the last `*` and the name position it is matched up to
+    let mut backtrack = None;
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                p += 1;
+                backtrack = Some((p, n));
+            }
+            Some(&c) if c == '?' || c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => match backtrack {
+                Some((after_star, matched)) => {
+                    p = after_star;
+                    n = matched + 1;
+                    backtrack = Some((after_star, n));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+/// A name made safe to use as one path component
+pub(super) fn path_component(name: &str) -> String {
+    let component: String = name
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '\0' => '_',
+            c => c,
+        })
+        .collect();
+    if component.trim_matches('.').is_empty() {
+        "_".to_string()
+    } else {
+        component
+    }
+}
+
+impl ResolveBridge {
+    pub(super) async fn queue_renders_for_timelines(
+        &self,
+        state: &mut StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        let preset_name = args["preset_name"]
+            .as_str()
+            .ok_or_else(|| ResolveError::invalid_parameter("preset_name", "required string"))?;
+        let template = args["output_directory"]
+            .as_str()
+            .unwrap_or(DEFAULT_OUTPUT_TEMPLATE)
+            .trim_end_matches('/');
+        if template.is_empty() {
+            return Err(ResolveError::invalid_parameter(
+                "output_directory",
+                "must not be empty",
+            ));
+        }
+
+        // Listed timelines keep their order; a filter takes matches by name
+        let mut timelines: Vec<String> = Vec::new();
+        match (args["timeline_names"].as_array(), args["filter"].as_str()) {
+            (Some(_), Some(_)) => {
+                return Err(ResolveError::invalid_parameter(
+                    "filter",
+                    "give timeline_names or filter, not both",
+                ))
+            }
+            (Some(names), None) => {
+                for name in names {
+                    let name = name.as_str().ok_or_else(|| {
+                        ResolveError::invalid_parameter(
+                            "timeline_names",
+                            "timeline names must be strings",
+                        )
+                    })?;
+                    let name = Self::resolve_timeline(state, name)?;
+                    if !timelines.contains(&name) {
+                        timelines.push(name);
+                    }
+                }
+            }
+            (None, Some(filter)) => {
+                timelines = state
+                    .timelines
+                    .keys()
+                    .filter(|name| glob_match(filter, name))
+                    .cloned()
+                    .collect();
+                timelines.sort();
+            }
+            (None, None) => {
+                return Err(ResolveError::invalid_parameter(
+                    "timeline_names",
+                    "give timeline_names or filter",
+                ))
+            }
+        }
+        if timelines.is_empty() {
+            return Err(ResolveError::invalid_parameter(
+                "filter",
+                "no timeline matches",
+            ));
+        }
+
+        let project = path_component(state.current_project.as_deref().unwrap_or("Untitled"));
+        let preset_component = path_component(preset_name);
+        let queue_length = state.render_state.render_queue.len();
+        let job_counter = state.render_state.job_counter;
+        let mut jobs = Vec::with_capacity(timelines.len());
+        let mut failure = None;
+        for (index, timeline_name) in timelines.iter().enumerate() {
+            let timeline = path_component(timeline_name);
+            let directory = template
+                .replace("{project}", &project)
+                .replace("{timeline}", &timeline)
+                .replace("{preset}", &preset_component)
+                .replace("{index}", &format!("{:02}", index + 1));
+            let queued = paths::check_path(&self.config.sandbox, &directory).and_then(|_| {
+                let output_stem = format!("{}/{}", directory, timeline);
+                self.queue_timeline_render(
+                    state,
+                    timeline_name,
+                    preset_name,
+                    Some(output_stem),
+                    None,
+                    &args,
+                )
+            });
+            match queued {
+                Ok(job) => jobs.push(job),
+                Err(error) => {
+                    failure = Some(error);
+                    break;
+                }
+            }
+        }
+        if let Some(error) = failure {
+            state.render_state.render_queue.truncate(queue_length);
+            state.render_state.job_counter = job_counter;
+            return Err(error);
+        }
+
+        Ok(json!({
+            "result": format!(
+                "Queued {} render jobs with preset '{}'",
+                jobs.len(),
+                preset_name
+            ),
+            "preset_name": preset_name,
+            "jobs": jobs,
+            "queue_length": state.render_state.render_queue.len(),
+            "operation_id": Uuid::new_v4().to_string()
+        }))
+    }
+}
@@ -0,0 +1,194 @@
+//! Per-bin color management rules
+//!
+//! A bin can carry rules for the input color space and input LUT of the clips
+//! imported into it, so camera originals sorted into an `ARRI` bin come in as
+//! LogC without setting every clip by hand. Each rule matches clip file names
+//! by a glob, `*.ari` or `A0*`, or every file when it has none; the first
+//! matching rule sets the clip's color settings. Rules run when media is
+//! imported into the bin, and on clips already in it when asked.
+
+use serde::Serialize;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use super::batch_render::glob_match;
+use super::{ResolveBridge, StateView};
+use crate::error::{ResolveError, ResolveResult};
+
+/// Clip-level color management settings
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub(super) struct ClipColorSettings {
+    pub(super) input_color_space: Option<String>,
+    /// Path of the LUT applied before the grade, or the name of a known LUT
+    pub(super) input_lut: Option<String>,
+}
+
+impl ClipColorSettings {
+    fn to_json(&self) -> Value {
+        json!({
+            "input_color_space": self.input_color_space,
+            "input_lut": self.input_lut
+        })
+    }
+}
+
+/// Color settings given to clips whose file name matches
+#[derive(Debug, Clone, Serialize)]
+pub(super) struct BinColorRule {
+    /// Glob over the clip's file name, matched without regard to case; None matches all
+    file_pattern: Option<String>,
+    settings: ClipColorSettings,
+}
+
+impl BinColorRule {
+    fn matches(&self, file_name: &str) -> bool {
+        self.file_pattern
+            .as_ref()
+            .is_none_or(|pattern| glob_match(&pattern.to_lowercase(), &file_name.to_lowercase()))
+    }
+
+    fn to_json(&self) -> Value {
+        json!({
+            "file_pattern": self.file_pattern,
+            "input_color_space": self.settings.input_color_space,
+            "input_lut": self.settings.input_lut
+        })
+    }
+}
+
+impl ResolveBridge {
+    /// Set a clip's color settings from the first rule of its bin matching its
+    /// file name, returning the settings it was given
+    pub(super) fn apply_bin_color_rules(
+        state: &mut StateView<'_>,
+        clip_name: &str,
+    ) -> Option<ClipColorSettings> {
+        let media_pool = &mut *state.media_pool;
+        let clip = media_pool.clips.get_mut(clip_name)?;
+        let bin = media_pool.bins.get(clip.bin.as_deref()?)?;
+        let file_name = std::path::Path::new(&clip.file_path)
+            .file_name()
+            .map_or_else(
+                || clip.name.clone(),
+                |name| name.to_string_lossy().to_string(),
+            );
+        let rule = bin
+            .color_rules
+            .iter()
+            .find(|rule| rule.matches(&file_name))?;
+        if let Some(color_space) = &rule.settings.input_color_space {
+            clip.color.input_color_space = Some(color_space.clone());
+        }
+        if let Some(lut) = &rule.settings.input_lut {
+            clip.color.input_lut = Some(lut.clone());
+        }
+        Some(clip.color.clone())
+    }
+
+    pub(super) async fn set_bin_color_rules(
+        &self,
+        state: &mut StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        let bin_name = args["bin_name"]
+            .as_str()
+            .ok_or_else(|| ResolveError::invalid_parameter("bin_name", "required string"))?;
+        if !state.media_pool.bins.contains_key(bin_name) {
+            return Err(ResolveError::invalid_parameter(
+                "bin_name",
+                format!("no bin named '{}'", bin_name),
+            ));
+        }
+        let rules = args["rules"]
+            .as_array()
+            .ok_or_else(|| ResolveError::invalid_parameter("rules", "required array"))?
+            .iter()
+            .enumerate()
+            .map(|(index, rule)| Self::bin_color_rule(state, index, rule))
+            .collect::<ResolveResult<Vec<_>>>()?;
+        let apply_to_existing = args["apply_to_existing"].as_bool().unwrap_or(false);
+
+        let rule_json: Vec<Value> = rules.iter().map(BinColorRule::to_json).collect();
+        if let Some(bin) = state.media_pool.bins.get_mut(bin_name) {
+            bin.color_rules = rules;
+        }
+
+        let mut updated = Vec::new();
+        if apply_to_existing {
+            let mut clip_names: Vec<String> = state
+                .media_pool
+                .clips
+                .values()
+                .filter(|clip| clip.bin.as_deref() == Some(bin_name))
+                .map(|clip| clip.name.clone())
+                .collect();
+            clip_names.sort();
+            for clip_name in clip_names {
+                if let Some(settings) = Self::apply_bin_color_rules(state, &clip_name) {
+                    let mut entry = settings.to_json();
+                    entry["clip_name"] = json!(clip_name);
+                    updated.push(entry);
+                }
+            }
+        }
+
+        Ok(json!({
+            "result": if rule_json.is_empty() {
+                format!("Cleared the color rules of bin '{}'", bin_name)
+            } else {
+                format!(
+                    "Set {} color rules on bin '{}' and applied them to {} existing clips",
+                    rule_json.len(),
+                    bin_name,
+                    updated.len()
+                )
+            },
+            "bin_name": bin_name,
+            "rules": rule_json,
+            "updated_clips": updated,
+            "operation_id": Uuid::new_v4().to_string()
+        }))
+    }
+
+    /// Check one rule of set_bin_color_rules
+    fn bin_color_rule(
+        state: &StateView<'_>,
+        index: usize,
+        rule: &Value,
+    ) -> ResolveResult<BinColorRule> {
+        let param = |field: &str| format!("rules[{}].{}", index, field);
+        let text = |field: &str| match &rule[field] {
+            Value::Null => Ok(None),
+            Value::String(value) if !value.trim().is_empty() => Ok(Some(value.trim().to_string())),
+            _ => Err(ResolveError::invalid_parameter(
+                param(field),
+                "must be a non-empty string",
+            )),
+        };
+        let file_pattern = text("file_pattern")?;
+        let input_color_space = text("input_color_space")?;
+        let input_lut = text("input_lut")?;
+        if input_color_space.is_none() && input_lut.is_none() {
+            return Err(ResolveError::invalid_parameter(
+                param("input_color_space"),
+                "give an input color space, an input LUT or both",
+            ));
+        }
+        // LUTs are file paths or known LUT names, as for apply_lut
+        if let Some(lut) = input_lut.as_deref() {
+            if !lut.starts_with('/') && !state.color_state.available_luts.contains_key(lut) {
+                return Err(ResolveError::invalid_parameter(
+                    param("input_lut"),
+                    format!("'{}' is not a LUT path or a known LUT", lut),
+                ));
+            }
+        }
+        Ok(BinColorRule {
+            file_pattern,
+            settings: ClipColorSettings {
+                input_color_space,
+                input_lut,
+            },
+        })
+    }
+}
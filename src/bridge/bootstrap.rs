@@ -0,0 +1,199 @@
+//! Session bootstrap
+//!
+//! The `[bootstrap]` config names the project, timeline and page sessions
+//! start from. They are applied when the bridge initializes, in either mode,
+//! and bootstrap_session applies them again or applies one of the named
+//! `[bootstrap.profiles]`. Each part is an ordinary API call, made in turn:
+//! open_project, set_current_timeline, then switch_page. Each call takes its
+//! own locks and goes to Resolve first in Real mode, and the bootstrap stops
+//! at the first call that fails.
+//!
+//! In Real mode the project and timeline are opened in Resolve itself, and
+//! the project Resolve then has open is mirrored into the simulation state,
+//! so calls that fall back to the simulation start from the same place.
+
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use super::{ConnectionMode, ResolveBridge};
+use crate::config::BootstrapProfile;
+use crate::error::{ResolveError, ResolveResult};
+
+/// Methods with a real path used by the bootstrap
+pub(super) const REAL_METHODS: [&str; 2] = ["open_project", "set_current_timeline"];
+
+/// Defines `find_timeline`
+const DEFINITIONS: &str = r#"
+def find_timeline(project, name):
+    for index in range(1, project.GetTimelineCount() + 1):
+        timeline = project.GetTimelineByIndex(index)
+        if timeline and name in (timeline.GetName(), timeline.GetUniqueId()):
+            return timeline
+    return None
+"#;
+
+/// Finds the project manager as `project_manager`
+const SETUP: &str = r#"
+    project_manager = resolve.GetProjectManager()
+"#;
+
+const OPEN_PROJECT: &str = r#"
+    project = project_manager.GetCurrentProject()
+    if not project or project.GetName() != request["name"]:
+        project = project_manager.LoadProject(request["name"])
+    if not project:
+        fail("Project not found: " + request["name"])
+    print(json.dumps({"success": True, "timelines": project.GetTimelineCount()}))
+"#;
+
+const SET_TIMELINE: &str = r#"
+    project = project_manager.GetCurrentProject()
+    if not project:
+        fail("No project open")
+    timeline = find_timeline(project, request["name"])
+    if not timeline:
+        fail("Timeline not found: " + request["name"])
+    if not project.SetCurrentTimeline(timeline):
+        fail("Resolve could not open timeline " + request["name"])
+    print(json.dumps({"success": True, "timeline_name": timeline.GetName()}))
+"#;
+
+/// The calls that apply a profile, in order
+fn steps(profile: &BootstrapProfile) -> Vec<(&'static str, Value)> {
+    let mut steps = Vec::new();
+    if let Some(project) = &profile.project {
+        steps.push(("open_project", json!({ "name": project })));
+    }
+    if let Some(timeline) = &profile.timeline {
+        steps.push(("set_current_timeline", json!({ "name": timeline })));
+    }
+    if let Some(page) = &profile.page {
+        steps.push(("switch_page", json!({ "page": page })));
+    }
+    steps
+}
+
+impl ResolveBridge {
+    /// Apply the startup profile, if one is configured; a failure is logged
+    /// and the server starts anyway
+    pub(super) async fn bootstrap_at_startup(&self) {
+        let startup = &self.config.bootstrap.startup;
+        if startup.is_empty() {
+            return;
+        }
+        match self.apply_bootstrap(startup).await {
+            Ok(_) => tracing::info!("Applied the startup bootstrap profile"),
+            Err(e) => tracing::warn!("Applying the startup bootstrap profile failed: {}", e),
+        }
+    }
+
+    /// Make the profile's calls, then mirror Resolve in Real mode
+    async fn apply_bootstrap(&self, profile: &BootstrapProfile) -> ResolveResult<Value> {
+        let mut results = Vec::new();
+        for (method, arguments) in steps(profile) {
+            let result = self
+                .call_api_boxed(method.to_string(), arguments)
+                .await
+                .map_err(|e| {
+                    ResolveError::api_call(
+                        "bootstrap_session",
+                        format!("{} failed after {} steps: {}", method, results.len(), e),
+                    )
+                })?;
+            results.push(json!({ "method": method, "result": result }));
+        }
+        let mirrored = match self.mode {
+            ConnectionMode::Real => self.sync_real_state(json!({})).await?["mirrored"].clone(),
+            ConnectionMode::Simulation => Value::Null,
+        };
+        Ok(json!({ "steps": results, "mirrored": mirrored }))
+    }
+
+    /// Applies a named bootstrap profile, or the startup one without a name
+    pub(super) async fn bootstrap_session(&self, args: Value) -> ResolveResult<Value> {
+        let (name, profile) = match args["profile"].as_str() {
+            Some(name) => {
+                let profile = self.config.bootstrap.profiles.get(name).ok_or_else(|| {
+                    let names: Vec<&str> = self
+                        .config
+                        .bootstrap
+                        .profiles
+                        .keys()
+                        .map(String::as_str)
+                        .collect();
+                    ResolveError::invalid_parameter(
+                        "profile",
+                        format!(
+                            "unknown bootstrap profile '{}'; available profiles: {}",
+                            name,
+                            if names.is_empty() {
+                                "none".to_string()
+                            } else {
+                                names.join(", ")
+                            }
+                        ),
+                    )
+                })?;
+                (name, profile)
+            }
+            None => ("startup", &self.config.bootstrap.startup),
+        };
+        if profile.is_empty() {
+            return Err(ResolveError::invalid_parameter(
+                "profile",
+                "no startup bootstrap is configured; name a profile",
+            ));
+        }
+
+        let applied = self.apply_bootstrap(profile).await?;
+        let profiles: Vec<Value> = self
+            .config
+            .bootstrap
+            .profiles
+            .iter()
+            .map(|(name, profile)| json!({ "name": name, "description": profile.description }))
+            .collect();
+        Ok(json!({
+            "result": format!(
+                "Applied bootstrap profile '{}' in {} steps",
+                name,
+                applied["steps"].as_array().map_or(0, Vec::len)
+            ),
+            "profile": name,
+            "project": profile.project,
+            "timeline": profile.timeline,
+            "page": profile.page,
+            "steps": applied["steps"],
+            "mirrored": applied["mirrored"],
+            "profiles": profiles,
+            "operation_id": Uuid::new_v4().to_string()
+        }))
+    }
+
+    /// Open a project or timeline in the running Resolve
+    pub(super) fn real_session_operation(
+        &self,
+        method: &str,
+        args: &Value,
+    ) -> ResolveResult<Value> {
+        let name = args["name"]
+            .as_str()
+            .ok_or_else(|| ResolveError::invalid_parameter("name", "required string"))?;
+        let operation = match method {
+            "open_project" => OPEN_PROJECT,
+            _ => SET_TIMELINE,
+        };
+        let script = Self::resolve_script(DEFINITIONS, SETUP, &json!({ "name": name }), operation)?;
+        let output = self.run_resolve_script(method, &script)?;
+        Ok(match method {
+            "open_project" => json!({
+                "result": format!("Opened project '{}'", name),
+                "timelines": output["timelines"]
+            }),
+            _ => json!({
+                "result": format!("Set current timeline to '{}'", output["timeline_name"].as_str().unwrap_or(name)),
+                "operation_id": Uuid::new_v4().to_string()
+            }),
+        })
+    }
+}
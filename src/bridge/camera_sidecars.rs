@@ -0,0 +1,263 @@
+//! Camera sidecar metadata read at import
+//!
+//! Cameras and their offload tools leave XML next to the media: RED's RMD
+//! files, ARRI's clip XML and the NonRealTimeMeta XML of Sony cameras. When
+//! a clip is imported and one of these sits beside its file, the clip takes
+//! its start timecode, reel and frame rate from it, which conform_timeline and
+//! timecode relinking match on, and camera settings such as ISO, white point,
+//! serial number and lens become metadata fields.
+//!
+//! A sidecar is found by the media file's stem, in any case: `<stem>.RMD` for
+//! RED, `<stem>M01.XML` for Sony and `<stem>.xml` for ARRI. Values that do not
+//! parse are skipped, and a sidecar that is not well-formed XML is ignored.
+//! Built with the `camera-sidecars` feature.
+
+use roxmltree::{Document, Node};
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use super::embedded_timecode::timecode_frames;
+use super::{ResolveBridge, StateView};
+use crate::timecode::FrameRate;
+
+/// Metadata fields read from ARRI clip XML, by element
+const ARRI_FIELDS: [(&str, &str); 11] = [
+    ("CameraModel", "Camera Type"),
+    ("CameraSerialNumber", "Camera Serial #"),
+    ("CameraIndex", "Camera #"),
+    ("ExposureIndex", "ISO"),
+    ("WhiteBalance", "White Point (Kelvin)"),
+    ("WhiteBalanceCC", "White Balance Tint"),
+    ("ShutterAngle", "Shutter"),
+    ("LensModel", "Lens Type"),
+    ("FocalLength", "Focal Point (mm)"),
+    ("Scene", "Scene"),
+    ("Take", "Take"),
+];
+
+/// Metadata fields read from RED RMD files, by element
+const RED_FIELDS: [(&str, &str); 6] = [
+    ("CameraModel", "Camera Type"),
+    ("ISO", "ISO"),
+    ("Kelvin", "White Point (Kelvin)"),
+    ("Tint", "White Balance Tint"),
+    ("Shutter", "Shutter"),
+    ("Lens", "Lens Type"),
+];
+
+/// What one sidecar says about its clip
+#[derive(Debug, Default)]
+struct Sidecar {
+    format: &'static str,
+    path: PathBuf,
+    start_timecode: Option<String>,
+    reel_name: Option<String>,
+    frame_rate: Option<String>,
+    fields: BTreeMap<String, String>,
+}
+
+/// Reads one camera's sidecar from its parsed XML
+type SidecarReader = fn(&Node) -> Option<Sidecar>;
+
+/// The first sidecar next to `media` that parses
+fn find(media: &Path) -> Option<Sidecar> {
+    let stem = media.file_stem()?.to_str()?.to_ascii_lowercase();
+    let folder = media.parent()?;
+    let files: Vec<PathBuf> = std::fs::read_dir(folder)
+        .ok()?
+        .flatten()
+        .map(|entry| entry.path())
+        .collect();
+    let named = |name: String| {
+        files.iter().find(|path| {
+            path.file_name()
+                .and_then(|file| file.to_str())
+                .is_some_and(|file| file.to_ascii_lowercase() == name)
+        })
+    };
+
+    let candidates: [(String, SidecarReader); 3] = [
+        (format!("{}.rmd", stem), read_red),
+        (format!("{}m01.xml", stem), read_sony),
+        (format!("{}.xml", stem), read_arri),
+    ];
+    candidates.into_iter().find_map(|(name, read)| {
+        let path = named(name)?;
+        let text = std::fs::read_to_string(path).ok()?;
+        let document = match Document::parse(&text) {
+            Ok(document) => document,
+            Err(e) => {
+                tracing::warn!("Ignoring sidecar {}: {}", path.display(), e);
+                return None;
+            }
+        };
+        let sidecar = read(&document.root_element())?;
+        Some(Sidecar {
+            path: path.clone(),
+            ..sidecar
+        })
+    })
+}
+
+/// Text or `value` attribute of the first element named `name`, in any case
+fn element_value(root: &Node, name: &str) -> Option<String> {
+    root.descendants()
+        .filter(|node| node.is_element())
+        .find(|node| node.tag_name().name().eq_ignore_ascii_case(name))
+        .and_then(|node| {
+            node.attribute("value")
+                .or_else(|| node.text())
+                .map(|value| value.trim().to_string())
+        })
+        .filter(|value| !value.is_empty())
+}
+
+/// Fields of `root` named in `names`
+fn read_fields(root: &Node, names: &[(&str, &str)]) -> BTreeMap<String, String> {
+    names
+        .iter()
+        .filter_map(|(element, field)| Some((field.to_string(), element_value(root, element)?)))
+        .collect()
+}
+
+/// A sidecar frame rate such as `23.98p` as FrameRate reads it
+fn normalize_rate(rate: &str) -> Option<String> {
+    let rate = rate.trim().trim_end_matches(['p', 'P', 'i', 'I']);
+    let rate = if rate == "23.98" { "23.976" } else { rate };
+    FrameRate::parse(rate).ok().map(|_| rate.to_string())
+}
+
+fn read_red(root: &Node) -> Option<Sidecar> {
+    let mut fields = read_fields(root, &RED_FIELDS);
+    fields.insert("Camera Manufacturer".to_string(), "RED".to_string());
+    Some(Sidecar {
+        format: "red_rmd",
+        start_timecode: element_value(root, "Timecode"),
+        reel_name: element_value(root, "ReelName"),
+        frame_rate: element_value(root, "FrameRate").and_then(|rate| normalize_rate(&rate)),
+        fields,
+        ..Default::default()
+    })
+}
+
+fn read_arri(root: &Node) -> Option<Sidecar> {
+    let mut fields = read_fields(root, &ARRI_FIELDS);
+    fields.insert("Camera Manufacturer".to_string(), "ARRI".to_string());
+    Some(Sidecar {
+        format: "arri_xml",
+        start_timecode: element_value(root, "MasterTC").or_else(|| element_value(root, "StartTC")),
+        reel_name: element_value(root, "ReelName"),
+        frame_rate: element_value(root, "ProjectFPS")
+            .or_else(|| element_value(root, "SensorFPS"))
+            .and_then(|rate| normalize_rate(&rate)),
+        fields,
+        ..Default::default()
+    })
+}
+
+/// Timecode of a Sony LTC value, BCD frames, seconds, minutes and hours
+fn sony_timecode(value: &str) -> Option<String> {
+    if value.len() != 8 || !value.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+        return None;
+    }
+    let digits = |at: usize, mask: u8| {
+        let byte = u8::from_str_radix(&value[at..at + 2], 16).ok()? & mask;
+        Some((byte >> 4) * 10 + (byte & 0x0F))
+    };
+    let frames = digits(0, 0x3F)?;
+    let drop_frame = u8::from_str_radix(&value[0..2], 16).ok()? & 0x40 != 0;
+    Some(format!(
+        "{:02}:{:02}:{:02}{}{:02}",
+        digits(6, 0x3F)?,
+        digits(4, 0x7F)?,
+        digits(2, 0x7F)?,
+        if drop_frame { ';' } else { ':' },
+        frames
+    ))
+}
+
+fn read_sony(root: &Node) -> Option<Sidecar> {
+    if !root
+        .tag_name()
+        .name()
+        .eq_ignore_ascii_case("NonRealTimeMeta")
+    {
+        return None;
+    }
+    let element = |name: &str| {
+        root.descendants()
+            .find(|node| node.is_element() && node.tag_name().name() == name)
+    };
+    let start_timecode = root
+        .descendants()
+        .filter(|node| node.is_element() && node.tag_name().name() == "LtcChange")
+        .find(|node| node.attribute("frameCount") == Some("0"))
+        .and_then(|node| sony_timecode(node.attribute("value")?));
+    let frame_rate = element("VideoFrame")
+        .and_then(|node| node.attribute("captureFps"))
+        .or_else(|| element("LtcChangeTable").and_then(|node| node.attribute("tcFps")))
+        .and_then(normalize_rate);
+
+    let mut fields = BTreeMap::new();
+    let attributes = [
+        ("Device", "manufacturer", "Camera Manufacturer"),
+        ("Device", "modelName", "Camera Type"),
+        ("Device", "serialNo", "Camera Serial #"),
+        ("Lens", "modelName", "Lens Type"),
+    ];
+    for (name, attribute, field) in attributes {
+        if let Some(value) = element(name)
+            .and_then(|node| node.attribute(attribute))
+            .filter(|value| !value.trim().is_empty())
+        {
+            fields.insert(field.to_string(), value.trim().to_string());
+        }
+    }
+    Some(Sidecar {
+        format: "sony_xml",
+        start_timecode,
+        frame_rate,
+        fields,
+        ..Default::default()
+    })
+}
+
+impl ResolveBridge {
+    /// Enrich a newly imported clip from the sidecar next to its media,
+    /// describing what was read, or Null when there is none
+    pub(super) fn apply_camera_sidecar(&self, state: &mut StateView<'_>, clip_name: &str) -> Value {
+        let Some(clip) = state.media_pool.clips.get_mut(clip_name) else {
+            return Value::Null;
+        };
+        let Some(sidecar) = find(Path::new(&clip.file_path)) else {
+            return Value::Null;
+        };
+
+        if let Some(rate) = &sidecar.frame_rate {
+            clip.frame_rate = Some(rate.clone());
+        }
+        let rate = FrameRate::parse(&self.clip_frame_rate(clip)).ok();
+        let start_timecode = sidecar
+            .start_timecode
+            .filter(|timecode| rate.is_some_and(|rate| timecode_frames(timecode, rate).is_ok()));
+        if let Some(timecode) = &start_timecode {
+            clip.start_timecode = Some(timecode.clone());
+        }
+        if let Some(reel) = &sidecar.reel_name {
+            clip.reel_name = Some(reel.clone());
+        }
+        for (field, value) in &sidecar.fields {
+            clip.metadata.insert(field.clone(), value.clone());
+        }
+
+        json!({
+            "format": sidecar.format,
+            "path": sidecar.path.display().to_string(),
+            "start_timecode": start_timecode,
+            "reel_name": sidecar.reel_name,
+            "frame_rate": sidecar.frame_rate,
+            "fields": sidecar.fields
+        })
+    }
+}
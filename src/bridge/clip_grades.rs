@@ -0,0 +1,240 @@
+//! Reading back clip grades
+//!
+//! The getters mirror set_color_wheel_param, apply_lut and add_node, so an
+//! agent can read a grade before changing it and make relative adjustments on
+//! its side. They act on the named clip or the clip current on the color page;
+//! a clip that was never graded reads as the neutral default grade.
+//!
+//! Relative adjustments add to the current value in a single call, so two
+//! agents nudging the same wheel cannot overwrite each other between a read
+//! and a write. The result is clamped to the wheel range.
+
+use serde_json::{json, Map, Value};
+use uuid::Uuid;
+
+use super::{ClipGrade, ColorWheelParams, ResolveBridge, StateView};
+use crate::error::{ResolveError, ResolveResult};
+
+pub(super) const WHEELS: [&str; 4] = ["lift", "gamma", "gain", "offset"];
+pub(super) const WHEEL_PARAMS: [&str; 4] = ["red", "green", "blue", "master"];
+
+/// Range of a color wheel parameter, with 0 as neutral
+const WHEEL_RANGE: (f64, f64) = (-1.0, 1.0);
+
+/// Keep an adjusted wheel value inside the wheel range
+pub(super) fn clamp_wheel_value(value: f64) -> f64 {
+    value.clamp(WHEEL_RANGE.0, WHEEL_RANGE.1)
+}
+
+impl ColorWheelParams {
+    fn get(&self, param: &str) -> Option<f64> {
+        match param {
+            "red" => Some(self.red),
+            "green" => Some(self.green),
+            "blue" => Some(self.blue),
+            "master" => Some(self.master),
+            _ => None,
+        }
+    }
+
+    pub(super) fn to_json(&self) -> Value {
+        json!({
+            "red": self.red,
+            "green": self.green,
+            "blue": self.blue,
+            "master": self.master
+        })
+    }
+}
+
+impl ClipGrade {
+    fn wheel(&self, wheel: &str) -> Option<&ColorWheelParams> {
+        match wheel {
+            "lift" => Some(&self.lift),
+            "gamma" => Some(&self.gamma),
+            "gain" => Some(&self.gain),
+            "offset" => Some(&self.offset),
+            _ => None,
+        }
+    }
+
+    pub(super) fn wheels_json(&self) -> Value {
+        json!({
+            "lift": self.lift.to_json(),
+            "gamma": self.gamma.to_json(),
+            "gain": self.gain.to_json(),
+            "offset": self.offset.to_json()
+        })
+    }
+
+    /// Motion effects of the nodes that have any set, keyed by node index
+    pub(super) fn motion_effects_json(&self) -> Map<String, Value> {
+        self.motion_effects
+            .iter()
+            .map(|(node, effects)| (node.to_string(), effects.to_json()))
+            .collect()
+    }
+
+    /// Privacy blur windows keyed by node index
+    pub(super) fn privacy_masks_json(&self) -> Map<String, Value> {
+        self.privacy_masks
+            .iter()
+            .map(|(node, mask)| (node.to_string(), mask.to_json()))
+            .collect()
+    }
+
+    /// Power Windows of the nodes that have any, keyed by node index
+    pub(super) fn power_windows_json(&self) -> Map<String, Value> {
+        self.power_windows
+            .iter()
+            .map(|(node, windows)| {
+                (
+                    node.to_string(),
+                    windows.iter().map(|window| window.to_json()).collect(),
+                )
+            })
+            .collect()
+    }
+
+    /// Qualifiers of the nodes that have any control set, keyed by node index
+    pub(super) fn qualifiers_json(&self) -> Map<String, Value> {
+        self.qualifiers
+            .iter()
+            .map(|(node, qualifier)| (node.to_string(), qualifier.to_json()))
+            .collect()
+    }
+
+    /// Node labels keyed by node index, in node order
+    pub(super) fn node_labels_json(&self) -> Map<String, Value> {
+        let mut labels: Vec<_> = self.node_labels.iter().collect();
+        labels.sort();
+        labels
+            .into_iter()
+            .map(|(node, label)| (node.to_string(), json!(label)))
+            .collect()
+    }
+}
+
+impl ResolveBridge {
+    /// Clip a grade call acts on: `clip_name` or the color page's current clip
+    pub(super) fn grade_clip(state: &StateView<'_>, args: &Value) -> ResolveResult<Option<String>> {
+        match args["clip_name"].as_str() {
+            Some(clip_name) => Ok(Some(Self::resolve_clip(state, clip_name)?)),
+            None => Ok(state.color_state.current_clip.clone()),
+        }
+    }
+
+    pub(super) fn required_grade_clip(
+        state: &StateView<'_>,
+        args: &Value,
+    ) -> ResolveResult<String> {
+        Self::grade_clip(state, args)?.ok_or_else(|| {
+            ResolveError::invalid_parameter("clip_name", "no current clip; name a clip")
+        })
+    }
+
+    pub(super) async fn get_color_wheel_param(
+        &self,
+        state: &StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        let wheel = args["wheel"]
+            .as_str()
+            .ok_or_else(|| ResolveError::invalid_parameter("wheel", "required string"))?;
+        let param = args["param"].as_str();
+        let clip_name = Self::required_grade_clip(state, &args)?;
+        let node_index = args["node_index"]
+            .as_i64()
+            .unwrap_or(state.color_state.current_node_index as i64) as i32;
+
+        let default_grade = ClipGrade::default();
+        let grade = state.color_state.clip_grades.get(&clip_name);
+        let wheel_params = grade
+            .unwrap_or(&default_grade)
+            .wheel(wheel)
+            .ok_or_else(|| {
+                ResolveError::invalid_parameter("wheel", "must be lift, gamma, gain, or offset")
+            })?;
+
+        // Without a param the whole wheel is returned
+        let (result, value) = match param {
+            Some(param) => {
+                let value = wheel_params.get(param).ok_or_else(|| {
+                    ResolveError::invalid_parameter("param", "must be red, green, blue, or master")
+                })?;
+                (
+                    format!("{} {} is {} on node {}", wheel, param, value, node_index),
+                    json!(value),
+                )
+            }
+            None => (
+                format!("Read {} wheel on node {}", wheel, node_index),
+                wheel_params.to_json(),
+            ),
+        };
+
+        Ok(json!({
+            "result": result,
+            "clip_name": clip_name,
+            "wheel": wheel,
+            "param": param,
+            "value": value,
+            "node_index": node_index,
+            "graded": grade.is_some(),
+            "operation_id": Uuid::new_v4().to_string()
+        }))
+    }
+
+    /// set_color_wheel_param with `delta` added to the current value
+    pub(super) async fn adjust_color_wheel_param(
+        &self,
+        state: &mut StateView<'_>,
+        mut args: Value,
+    ) -> ResolveResult<Value> {
+        let delta = args["delta"]
+            .as_f64()
+            .ok_or_else(|| ResolveError::invalid_parameter("delta", "required number"))?;
+        args["value"] = json!(delta);
+        args["relative"] = json!(true);
+        self.set_color_wheel_param(state, args).await
+    }
+
+    pub(super) async fn get_clip_grade(
+        &self,
+        state: &StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        let clip_name = Self::required_grade_clip(state, &args)?;
+        let default_grade = ClipGrade::default();
+        let grade = state.color_state.clip_grades.get(&clip_name);
+        let values = grade.unwrap_or(&default_grade);
+        let color_group = state
+            .color_state
+            .color_groups
+            .iter()
+            .find(|(_, clips)| clips.contains(&clip_name))
+            .map(|(group, _)| group);
+
+        Ok(json!({
+            "result": format!(
+                "Grade of clip '{}' has {} nodes and {} LUTs",
+                clip_name,
+                values.node_count,
+                values.applied_luts.len()
+            ),
+            "clip_name": clip_name,
+            "graded": grade.is_some(),
+            "wheels": values.wheels_json(),
+            "luts": values.applied_luts,
+            "node_count": values.node_count,
+            "node_labels": values.node_labels_json(),
+            "motion_effects": values.motion_effects_json(),
+            "privacy_masks": values.privacy_masks_json(),
+            "power_windows": values.power_windows_json(),
+            "qualifiers": values.qualifiers_json(),
+            "current_node_index": state.color_state.current_node_index,
+            "color_group": color_group,
+            "operation_id": Uuid::new_v4().to_string()
+        }))
+    }
+}
@@ -0,0 +1,235 @@
+//! In and out points on media pool clips
+//!
+//! Logging marks the part of each clip worth using before any of it is cut
+//! in. set_clip_in_out marks a clip's video, its audio or both, as Resolve's
+//! SetMarkInOut does, with the out frame included; get_clip_in_out reads the
+//! marks back and clear_clip_in_out removes them. add_clip_to_timeline and
+//! append_to_timeline take a clip's video marks as its source range when the
+//! call gives none, so logged clips can be assembled one after another.
+//!
+//! Marks are frames of the clip's media at the clip's frame rate, and must
+//! fall within the media, whose length is probed from the file with ffprobe
+//! when it can be and is otherwise the length simulated media is given.
+
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::path::Path;
+use uuid::Uuid;
+
+use super::{ffmpeg, Clip, ResolveBridge, StateView, SIMULATED_CLIP_SECONDS};
+use crate::error::{ResolveError, ResolveResult};
+use crate::timecode::FrameRate;
+
+/// Marked frames of one kind of media, out frame included
+#[derive(Debug, Clone, Copy, Serialize)]
+pub(super) struct MarkRange {
+    in_frame: i64,
+    out_frame: i64,
+}
+
+impl MarkRange {
+    fn to_json(self) -> Value {
+        json!({
+            "in_frame": self.in_frame,
+            "out_frame": self.out_frame,
+            "duration": self.out_frame - self.in_frame + 1
+        })
+    }
+}
+
+/// A clip's video and audio marks; None where nothing is marked
+#[derive(Debug, Clone, Default, Serialize)]
+pub(super) struct ClipMarks {
+    video: Option<MarkRange>,
+    audio: Option<MarkRange>,
+}
+
+impl ClipMarks {
+    /// Source range the video marks give a timeline item, end exclusive
+    pub(super) fn video_range(&self) -> Option<(i64, i64)> {
+        self.video
+            .map(|marks| (marks.in_frame, marks.out_frame + 1))
+    }
+
+    fn to_json(&self) -> Value {
+        json!({
+            "video": self.video.map(MarkRange::to_json),
+            "audio": self.audio.map(MarkRange::to_json)
+        })
+    }
+}
+
+/// Frames of a clip's media at `rate`, and where the length came from
+pub(super) fn media_frames(clip: &Clip, rate: FrameRate) -> (i64, &'static str) {
+    let path = Path::new(&clip.file_path);
+    let probed = (path.is_file() && ffmpeg::probe_available())
+        .then(|| ffmpeg::probe_duration(path).ok().flatten())
+        .flatten();
+    match probed {
+        Some(seconds) => ((seconds * rate.fps()).round() as i64, "probed"),
+        None => (SIMULATED_CLIP_SECONDS * rate.nominal(), "simulated"),
+    }
+}
+
+/// Whether the call marks video and audio, from `mark_type`
+fn mark_kinds(args: &Value) -> ResolveResult<(bool, bool)> {
+    match args["mark_type"].as_str().unwrap_or("all") {
+        "all" => Ok((true, true)),
+        "video" => Ok((true, false)),
+        "audio" => Ok((false, true)),
+        _ => Err(ResolveError::invalid_parameter(
+            "mark_type",
+            "must be 'all', 'video' or 'audio'",
+        )),
+    }
+}
+
+impl ResolveBridge {
+    pub(super) async fn set_clip_in_out(
+        &self,
+        state: &mut StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        let clip_reference = args["clip_name"]
+            .as_str()
+            .ok_or_else(|| ResolveError::invalid_parameter("clip_name", "required string"))?;
+        let clip_name = Self::resolve_clip(state, clip_reference)?;
+        let (video, audio) = mark_kinds(&args)?;
+        let in_frame = args["in_frame"]
+            .as_i64()
+            .ok_or_else(|| ResolveError::invalid_parameter("in_frame", "required whole number"))?;
+        let out_frame = args["out_frame"]
+            .as_i64()
+            .ok_or_else(|| ResolveError::invalid_parameter("out_frame", "required whole number"))?;
+        if in_frame < 0 {
+            return Err(ResolveError::invalid_parameter(
+                "in_frame",
+                "must be 0 or later",
+            ));
+        }
+        if out_frame < in_frame {
+            return Err(ResolveError::invalid_parameter(
+                "out_frame",
+                "must not be before the in frame",
+            ));
+        }
+
+        let clip = &state.media_pool.clips[&clip_name];
+        let rate = FrameRate::parse(&self.clip_frame_rate(clip))
+            .map_err(|e| ResolveError::invalid_parameter("clip_name", e))?;
+        let (length, length_source) = media_frames(clip, rate);
+        if out_frame >= length {
+            return Err(ResolveError::invalid_parameter(
+                "out_frame",
+                format!(
+                    "clip '{}' has {} frames ({}), so its last frame is {}",
+                    clip_name,
+                    length,
+                    length_source,
+                    length - 1
+                ),
+            ));
+        }
+
+        let marks = &mut state
+            .media_pool
+            .clips
+            .get_mut(&clip_name)
+            .expect("resolved clip exists")
+            .marks;
+        let range = MarkRange {
+            in_frame,
+            out_frame,
+        };
+        if video {
+            marks.video = Some(range);
+        }
+        if audio {
+            marks.audio = Some(range);
+        }
+
+        Ok(json!({
+            "result": format!(
+                "Marked frames {} to {} of clip '{}' for {}",
+                in_frame,
+                out_frame,
+                clip_name,
+                match (video, audio) {
+                    (true, true) => "video and audio",
+                    (true, false) => "video",
+                    _ => "audio",
+                }
+            ),
+            "clip_name": clip_name,
+            "marks": marks.to_json(),
+            "media_frames": length,
+            "media_length_source": length_source,
+            "operation_id": Uuid::new_v4().to_string()
+        }))
+    }
+
+    pub(super) async fn get_clip_in_out(
+        &self,
+        state: &mut StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        let clip_reference = args["clip_name"]
+            .as_str()
+            .ok_or_else(|| ResolveError::invalid_parameter("clip_name", "required string"))?;
+        let clip_name = Self::resolve_clip(state, clip_reference)?;
+        let clip = &state.media_pool.clips[&clip_name];
+        let marked = clip.marks.video.is_some() || clip.marks.audio.is_some();
+
+        Ok(json!({
+            "result": if marked {
+                format!("Read the in and out points of clip '{}'", clip_name)
+            } else {
+                format!("Clip '{}' has no in or out points", clip_name)
+            },
+            "clip_name": clip_name,
+            "frame_rate": self.clip_frame_rate(clip),
+            "marks": clip.marks.to_json()
+        }))
+    }
+
+    pub(super) async fn clear_clip_in_out(
+        &self,
+        state: &mut StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        let clip_reference = args["clip_name"]
+            .as_str()
+            .ok_or_else(|| ResolveError::invalid_parameter("clip_name", "required string"))?;
+        let clip_name = Self::resolve_clip(state, clip_reference)?;
+        let (video, audio) = mark_kinds(&args)?;
+        let marks = &mut state
+            .media_pool
+            .clips
+            .get_mut(&clip_name)
+            .expect("resolved clip exists")
+            .marks;
+        let mut cleared = Vec::new();
+        if video && marks.video.take().is_some() {
+            cleared.push("video");
+        }
+        if audio && marks.audio.take().is_some() {
+            cleared.push("audio");
+        }
+
+        Ok(json!({
+            "result": if cleared.is_empty() {
+                format!("Clip '{}' had no in or out points to clear", clip_name)
+            } else {
+                format!(
+                    "Cleared the {} in and out points of clip '{}'",
+                    cleared.join(" and "),
+                    clip_name
+                )
+            },
+            "clip_name": clip_name,
+            "cleared": cleared,
+            "marks": marks.to_json(),
+            "operation_id": Uuid::new_v4().to_string()
+        }))
+    }
+}
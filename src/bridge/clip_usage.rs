@@ -0,0 +1,118 @@
+//! Where media pool clips are used across timelines
+//!
+//! Timeline items point back at the media pool clip they were created from by
+//! clip ID, so usage follows the clip itself rather than whatever clip later
+//! takes its name. Items only known by ID have no clip and never count as usage.
+
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use super::{Clip, ResolveBridge, StateView, TimelineItemState};
+use crate::error::{ResolveError, ResolveResult};
+
+/// Placed timeline items that use a clip
+fn items_using<'a>(
+    state: &'a StateView<'a>,
+    clip: &'a Clip,
+) -> impl Iterator<Item = &'a TimelineItemState> {
+    state.timeline_items.items.values().filter(move |item| {
+        item.placement.is_some() && item.clip_id.as_deref() == Some(clip.id.as_str())
+    })
+}
+
+impl ResolveBridge {
+    pub(super) async fn find_clip_usage(
+        &self,
+        state: &StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        let clip_reference = args["clip_name"]
+            .as_str()
+            .ok_or_else(|| ResolveError::invalid_parameter("clip_name", "required string"))?;
+        let clip_name = &Self::resolve_clip(state, clip_reference)?;
+        let clip = &state.media_pool.clips[clip_name];
+
+        let mut items: Vec<&TimelineItemState> = items_using(state, clip).collect();
+        items.sort_by_key(|item| {
+            let placement = item.placement.as_ref();
+            (
+                item.timeline_name.clone(),
+                placement.map(|placement| placement.record_in),
+                placement.map(|placement| placement.track_index),
+            )
+        });
+        let usage: Vec<Value> = items
+            .iter()
+            .filter_map(|item| {
+                let placement = item.placement.as_ref()?;
+                Some(json!({
+                    "timeline_name": item.timeline_name,
+                    "timeline_item_id": item.id,
+                    "track": format!("Video {}", placement.track_index),
+                    "record_in": placement.record_in,
+                    "record_out": placement.record_out(),
+                    "source_in": placement.source_in,
+                    "source_out": placement.source_out()
+                }))
+            })
+            .collect();
+        let mut timelines: Vec<&str> = items
+            .iter()
+            .map(|item| item.timeline_name.as_str())
+            .collect();
+        timelines.dedup();
+
+        Ok(json!({
+            "result": format!(
+                "Clip '{}' is used {} times on {} timelines",
+                clip_name,
+                usage.len(),
+                timelines.len()
+            ),
+            "clip_name": clip_name,
+            "clip_id": clip.id,
+            "timelines": timelines,
+            "usage": usage,
+            "count": usage.len(),
+            "operation_id": Uuid::new_v4().to_string()
+        }))
+    }
+
+    pub(super) async fn list_unused_clips(
+        &self,
+        state: &StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        let bin_name = match args["bin_name"].as_str() {
+            Some(bin) => Some(Self::resolve_bin(state, bin)?),
+            None => None,
+        };
+
+        let mut unused: Vec<&Clip> = state
+            .media_pool
+            .clips
+            .values()
+            .filter(|clip| bin_name.is_none() || clip.bin == bin_name)
+            .filter(|clip| items_using(state, clip).next().is_none())
+            .collect();
+        unused.sort_by(|a, b| a.name.cmp(&b.name));
+        let clips: Vec<Value> = unused
+            .iter()
+            .map(|clip| {
+                json!({
+                    "clip_name": clip.name,
+                    "clip_id": clip.id,
+                    "file_path": clip.file_path,
+                    "bin": clip.bin
+                })
+            })
+            .collect();
+
+        Ok(json!({
+            "result": format!("Found {} clips not used on any timeline", clips.len()),
+            "clips": clips,
+            "count": clips.len(),
+            "operation_id": Uuid::new_v4().to_string()
+        }))
+    }
+}
@@ -0,0 +1,190 @@
+//! Applying one color preset or grade to many clips at once
+//!
+//! The targets are a list of clips, a bin, a project color group or every
+//! clip used in a timeline. Each clip is graded on its own, so a missing clip
+//! is reported in the results without stopping the rest of the batch.
+
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use super::{ResolveBridge, StateView, DEFAULT_PRESET_ALBUM};
+use crate::error::{ResolveError, ResolveResult};
+
+/// What the batch targets, as a label for messages, and the clip references
+/// it covers in order without repeats
+fn batch_targets(state: &StateView<'_>, args: &Value) -> ResolveResult<(String, Vec<String>)> {
+    let given = ["clip_names", "bin_name", "color_group", "timeline_name"]
+        .iter()
+        .filter(|key| !args[**key].is_null())
+        .count();
+    if given != 1 {
+        return Err(ResolveError::invalid_parameter(
+            "clip_names",
+            "give exactly one of clip_names, bin_name, color_group or timeline_name",
+        ));
+    }
+
+    let (label, references) = if let Some(clips) = args["clip_names"].as_array() {
+        if clips.is_empty() {
+            return Err(ResolveError::invalid_parameter(
+                "clip_names",
+                "must name at least one clip",
+            ));
+        }
+        let references = clips
+            .iter()
+            .map(|clip| {
+                clip.as_str().map(str::to_string).ok_or_else(|| {
+                    ResolveError::invalid_parameter("clip_names", "must be a list of strings")
+                })
+            })
+            .collect::<ResolveResult<Vec<String>>>()?;
+        ("clip list".to_string(), references)
+    } else if let Some(reference) = args["bin_name"].as_str() {
+        let bin_name = ResolveBridge::resolve_bin(state, reference)?;
+        let clips = state.media_pool.bins[&bin_name].clips.clone();
+        (format!("bin '{}'", bin_name), clips)
+    } else if let Some(group_name) = args["color_group"].as_str() {
+        let clips = state
+            .color_state
+            .color_groups
+            .get(group_name)
+            .cloned()
+            .ok_or_else(|| {
+                ResolveError::invalid_parameter(
+                    "color_group",
+                    format!("color group '{}' not found", group_name),
+                )
+            })?;
+        (format!("color group '{}'", group_name), clips)
+    } else if let Some(reference) = args["timeline_name"].as_str() {
+        let timeline_name = ResolveBridge::resolve_timeline(state, reference)?;
+        let mut items: Vec<_> = state
+            .timeline_items
+            .items
+            .values()
+            .filter(|item| item.timeline_name == timeline_name && item.clip_id.is_some())
+            .collect();
+        items.sort_by_key(|item| {
+            item.placement
+                .as_ref()
+                .map(|placement| (placement.track_index, placement.record_in))
+        });
+        let clips = items.iter().map(|item| item.clip_name.clone()).collect();
+        (format!("timeline '{}'", timeline_name), clips)
+    } else {
+        return Err(ResolveError::invalid_parameter(
+            "clip_names",
+            "targets must be given as strings or a list of strings",
+        ));
+    };
+
+    let mut unique = Vec::with_capacity(references.len());
+    for reference in references {
+        if !unique.contains(&reference) {
+            unique.push(reference);
+        }
+    }
+    Ok((label, unique))
+}
+
+impl ResolveBridge {
+    pub(super) async fn apply_color_preset_batch(
+        &self,
+        state: &mut StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        let album_name = args["album_name"].as_str().unwrap_or(DEFAULT_PRESET_ALBUM);
+
+        // The grade comes from a saved preset or from another clip
+        let (grade, source, source_details, source_clip) = match args["source_clip"].as_str() {
+            Some(reference) => {
+                if !args["preset_id"].is_null() || !args["preset_name"].is_null() {
+                    return Err(ResolveError::invalid_parameter(
+                        "source_clip",
+                        "give either a preset or a source clip, not both",
+                    ));
+                }
+                let clip_name = Self::resolve_clip(state, reference)?;
+                let grade = state
+                    .color_state
+                    .clip_grades
+                    .get(&clip_name)
+                    .cloned()
+                    .unwrap_or_default();
+                (
+                    grade,
+                    format!("grade from clip '{}'", clip_name),
+                    json!({ "source_clip": clip_name }),
+                    Some(clip_name),
+                )
+            }
+            None => {
+                let preset = state.color_state.find_preset(
+                    album_name,
+                    args["preset_id"].as_str(),
+                    args["preset_name"].as_str(),
+                )?;
+                (
+                    preset.grade_data.clone(),
+                    format!("color preset '{}' from album '{}'", preset.name, album_name),
+                    json!({
+                        "preset_id": preset.id,
+                        "preset_name": preset.name,
+                        "album": album_name
+                    }),
+                    None,
+                )
+            }
+        };
+        let (targeted, references) = batch_targets(state, &args)?;
+
+        let mut results = Vec::with_capacity(references.len());
+        let (mut applied, mut skipped, mut failed) = (0, 0, 0);
+        for reference in references {
+            let result = match Self::resolve_clip(state, &reference) {
+                Ok(clip_name) if source_clip.as_ref() == Some(&clip_name) => {
+                    skipped += 1;
+                    json!({
+                        "clip_name": clip_name,
+                        "status": "skipped",
+                        "reason": "clip is the source of the grade"
+                    })
+                }
+                Ok(clip_name) => {
+                    state
+                        .color_state
+                        .clip_grades
+                        .insert(clip_name.clone(), grade.clone());
+                    applied += 1;
+                    json!({ "clip_name": clip_name, "status": "applied" })
+                }
+                Err(e) => {
+                    failed += 1;
+                    json!({ "clip_name": reference, "status": "failed", "error": e.to_string() })
+                }
+            };
+            results.push(result);
+        }
+
+        Ok(json!({
+            "result": format!(
+                "Applied {} to {} of {} clips in {}",
+                source,
+                applied,
+                results.len(),
+                targeted
+            ),
+            "source": source_details,
+            "targets": targeted,
+            "results": results,
+            "summary": {
+                "total": results.len(),
+                "applied": applied,
+                "skipped": skipped,
+                "failed": failed
+            },
+            "operation_id": Uuid::new_v4().to_string()
+        }))
+    }
+}
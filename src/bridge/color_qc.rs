@@ -0,0 +1,268 @@
+//! Automated color QC over a timeline
+//!
+//! Frames are sampled at a fixed interval from the topmost timeline item at
+//! each position and checked for levels outside the 8-bit video range
+//! (16-235), clipped highlights and shadows, and sudden jumps in average luma
+//! between consecutive samples.
+
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use super::markers::{marker_color, timeline_start};
+use super::scopes::{rec709_luma, round};
+use super::{ensure_capacity, Marker, ResolveBridge, StateView};
+use crate::error::{ResolveError, ResolveResult};
+
+/// Lowest and highest legal 8-bit video levels
+const LEGAL_MIN: u8 = 16;
+const LEGAL_MAX: u8 = 235;
+
+const DEFAULT_ILLEGAL_PERCENT: f64 = 1.0;
+const DEFAULT_CLIPPED_PERCENT: f64 = 2.0;
+const DEFAULT_LUMA_JUMP: f64 = 0.25;
+
+/// Levels summary of one sampled frame
+struct FrameLevels {
+    mean_luma: f64,
+    illegal_percent: f64,
+    clipped_highlights_percent: f64,
+    clipped_shadows_percent: f64,
+}
+
+impl FrameLevels {
+    fn measure(pixels: &[u8]) -> Self {
+        let (mut luma_sum, mut illegal, mut highlights, mut shadows) = (0.0, 0, 0, 0);
+        for rgb in pixels.chunks_exact(3) {
+            if rgb
+                .iter()
+                .any(|&level| !(LEGAL_MIN..=LEGAL_MAX).contains(&level))
+            {
+                illegal += 1;
+            }
+            let [r, g, b] = [rgb[0], rgb[1], rgb[2]].map(|level| f64::from(level) / 255.0);
+            let y = rec709_luma(r, g, b);
+            luma_sum += y;
+            if y >= 254.0 / 255.0 {
+                highlights += 1;
+            } else if y <= 1.0 / 255.0 {
+                shadows += 1;
+            }
+        }
+        let count = (pixels.len() / 3).max(1) as f64;
+        let percent = |n: i32| round(f64::from(n) / count * 100.0);
+        Self {
+            mean_luma: luma_sum / count,
+            illegal_percent: percent(illegal),
+            clipped_highlights_percent: percent(highlights),
+            clipped_shadows_percent: percent(shadows),
+        }
+    }
+}
+
+/// Read an optional non-negative threshold argument
+fn threshold(args: &Value, param: &str, default: f64) -> ResolveResult<f64> {
+    match args[param].as_f64() {
+        Some(value) if value >= 0.0 => Ok(value),
+        Some(_) => Err(ResolveError::invalid_parameter(
+            param,
+            "must be 0 or greater",
+        )),
+        None => Ok(default),
+    }
+}
+
+impl ResolveBridge {
+    pub(super) async fn run_color_qc(
+        &self,
+        state: &mut StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        let timeline_name = Self::resolve_timeline_name(state, &args)?;
+        let timeline = &state.timelines[&timeline_name];
+        let start = timeline_start(timeline, &args);
+        let rate = self
+            .timeline_frame_rate(timeline)?
+            .for_timecode(start)
+            .map_err(|e| ResolveError::invalid_parameter("timeline_start_timecode", e))?;
+        let start_frame = rate
+            .timecode_to_frames(start)
+            .map_err(|e| ResolveError::invalid_parameter("timeline_start_timecode", e))?;
+
+        let interval = match args["interval_frames"].as_i64() {
+            Some(interval) if interval >= 1 => interval,
+            Some(_) => {
+                return Err(ResolveError::invalid_parameter(
+                    "interval_frames",
+                    "must be 1 or greater",
+                ))
+            }
+            None => rate.nominal(),
+        };
+        let illegal_threshold =
+            threshold(&args, "illegal_percent_threshold", DEFAULT_ILLEGAL_PERCENT)?;
+        let clipped_threshold =
+            threshold(&args, "clipped_percent_threshold", DEFAULT_CLIPPED_PERCENT)?;
+        let jump_threshold = threshold(&args, "luma_jump_threshold", DEFAULT_LUMA_JUMP)?;
+        let create_markers = args["create_markers"].as_bool().unwrap_or(false);
+        let color = match args["marker_color"].as_str() {
+            Some(color) => marker_color(color).ok_or_else(|| {
+                ResolveError::invalid_parameter("marker_color", "unknown marker color")
+            })?,
+            None => "Red",
+        };
+
+        let placed: Vec<_> = state
+            .timeline_items
+            .items
+            .values()
+            .filter(|item| item.timeline_name == timeline_name)
+            .filter_map(|item| item.placement.as_ref().map(|placement| (item, placement)))
+            .collect();
+        let timeline_end = placed
+            .iter()
+            .map(|(_, placement)| placement.record_out())
+            .max()
+            .unwrap_or(0);
+
+        let mut samples_analyzed = 0;
+        let mut gaps_skipped = 0;
+        let mut summary = [0usize; 4];
+        let mut flagged = Vec::new();
+        let mut previous: Option<(f64, &str)> = None;
+        for frame in (0..timeline_end).step_by(interval as usize) {
+            // The topmost item at a position is what is seen
+            let Some((item, placement)) = placed
+                .iter()
+                .filter(|(_, placement)| {
+                    (placement.record_in..placement.record_out()).contains(&frame)
+                })
+                .max_by_key(|(item, placement)| (placement.track_index, &item.id))
+            else {
+                gaps_skipped += 1;
+                previous = None;
+                continue;
+            };
+            let Some(clip) = state.media_pool.clips.get(&item.clip_name) else {
+                gaps_skipped += 1;
+                previous = None;
+                continue;
+            };
+            let source_frame = placement.source_in + frame - placement.record_in;
+            let (pixels, source) = self.analysis_frame(
+                &clip.file_path,
+                source_frame,
+                rate,
+                state.color_state.clip_grades.get(&item.clip_name),
+            );
+            let levels = FrameLevels::measure(&pixels);
+            samples_analyzed += 1;
+
+            let mut issues = Vec::new();
+            if levels.illegal_percent > illegal_threshold {
+                summary[0] += 1;
+                issues.push(json!({"type": "illegal_levels", "percent": levels.illegal_percent}));
+            }
+            if levels.clipped_highlights_percent > clipped_threshold {
+                summary[1] += 1;
+                issues.push(json!({"type": "clipped_highlights", "percent": levels.clipped_highlights_percent}));
+            }
+            if levels.clipped_shadows_percent > clipped_threshold {
+                summary[2] += 1;
+                issues.push(
+                    json!({"type": "clipped_shadows", "percent": levels.clipped_shadows_percent}),
+                );
+            }
+            if let Some((previous_luma, previous_item)) = previous {
+                let delta = levels.mean_luma - previous_luma;
+                if delta.abs() > jump_threshold {
+                    summary[3] += 1;
+                    issues.push(json!({
+                        "type": "luminance_jump",
+                        "delta": round(delta),
+                        "at_cut": previous_item != item.id
+                    }));
+                }
+            }
+            previous = Some((levels.mean_luma, &item.id));
+
+            if !issues.is_empty() {
+                flagged.push(json!({
+                    "frame": frame,
+                    "timecode": rate.frames_to_timecode(start_frame + frame),
+                    "timeline_item_id": item.id,
+                    "clip_name": item.clip_name,
+                    "source_frame": source_frame,
+                    "source": source,
+                    "mean_luma": round(levels.mean_luma),
+                    "issues": issues
+                }));
+            }
+        }
+
+        let mut markers_created = 0;
+        if create_markers {
+            let mut markers: Vec<Marker> = Vec::new();
+            for sample in &flagged {
+                let Some(frame) = sample["frame"]
+                    .as_i64()
+                    .and_then(|frame| i32::try_from(frame).ok())
+                else {
+                    continue;
+                };
+                if timeline.markers.iter().any(|m| m.frame == Some(frame)) {
+                    continue;
+                }
+                let issues: Vec<&str> = sample["issues"]
+                    .as_array()
+                    .map(|issues| {
+                        issues
+                            .iter()
+                            .filter_map(|issue| issue["type"].as_str())
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                markers.push(Marker {
+                    id: Uuid::new_v4().to_string(),
+                    frame: Some(frame),
+                    color: color.to_string(),
+                    note: format!("QC: {}", issues.join(", ")),
+                    name: String::new(),
+                    duration: 1,
+                });
+            }
+            ensure_capacity(
+                &format!("markers on timeline '{}'", timeline_name),
+                timeline.markers.len(),
+                markers.len(),
+                self.config.limits.max_markers_per_timeline,
+            )?;
+            markers_created = markers.len();
+            if let Some(timeline) = state.timelines.get_mut(&timeline_name) {
+                timeline.markers.extend(markers);
+            }
+        }
+
+        Ok(json!({
+            "result": format!(
+                "Color QC flagged {} of {} sampled frames on timeline '{}'",
+                flagged.len(),
+                samples_analyzed,
+                timeline_name
+            ),
+            "timeline_name": timeline_name,
+            "passed": flagged.is_empty(),
+            "interval_frames": interval,
+            "samples_analyzed": samples_analyzed,
+            "gaps_skipped": gaps_skipped,
+            "summary": {
+                "illegal_levels": summary[0],
+                "clipped_highlights": summary[1],
+                "clipped_shadows": summary[2],
+                "luminance_jumps": summary[3]
+            },
+            "flagged": flagged,
+            "markers_created": markers_created,
+            "operation_id": Uuid::new_v4().to_string()
+        }))
+    }
+}
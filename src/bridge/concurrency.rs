@@ -0,0 +1,221 @@
+//! Weighted limits on CPU-heavy work
+//!
+//! Renders, proxy generation, transcription and analysis jobs each take a
+//! configured weight out of a shared capacity before they run and give it back
+//! when they finish. Work that does not fit waits in line in the order it
+//! arrived, so a heavy render is not starved by a stream of light jobs.
+
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::sync::atomic::Ordering;
+use std::sync::{Arc, Mutex};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use uuid::Uuid;
+
+use super::ResolveBridge;
+use crate::config::ConcurrencyConfig;
+use crate::error::ResolveResult;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) enum Workload {
+    Render,
+    Proxy,
+    Transcription,
+    Analysis,
+}
+
+impl Workload {
+    /// Workload of an API call that does heavy work before it returns
+    pub(super) fn for_method(method: &str) -> Option<Self> {
+        match method {
+            "generate_optimized_media" => Some(Self::Proxy),
+            "generate_archive_manifest" | "auto_sync_audio" | "detect_av_offset" => {
+                Some(Self::Analysis)
+            }
+            "transcribe_audio" | "transcribe_folder_audio" | "transcribe_media_pool_item_audio" => {
+                Some(Self::Transcription)
+            }
+            _ => None,
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Render => "render",
+            Self::Proxy => "proxy",
+            Self::Transcription => "transcription",
+            Self::Analysis => "analysis",
+        }
+    }
+}
+
+/// Work holding or waiting for capacity
+#[derive(Debug)]
+struct Task {
+    label: String,
+    workload: Workload,
+    weight: u32,
+    queued_at: chrono::DateTime<chrono::Utc>,
+    started_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Debug, Default)]
+struct Tasks {
+    next_id: u64,
+    entries: BTreeMap<u64, Task>,
+}
+
+/// Shared capacity for CPU-heavy work
+#[derive(Debug)]
+pub(super) struct CpuScheduler {
+    settings: ConcurrencyConfig,
+    semaphore: Arc<Semaphore>,
+    tasks: Arc<Mutex<Tasks>>,
+}
+
+/// Capacity held by running work; given back when dropped
+pub(super) struct CpuPermit {
+    _permit: OwnedSemaphorePermit,
+    _ticket: Ticket,
+}
+
+/// A task's place in the listing, removed when the work finishes or stops waiting
+struct Ticket {
+    tasks: Arc<Mutex<Tasks>>,
+    id: u64,
+}
+
+impl Drop for Ticket {
+    fn drop(&mut self) {
+        if let Ok(mut tasks) = self.tasks.lock() {
+            tasks.entries.remove(&self.id);
+        }
+    }
+}
+
+impl CpuScheduler {
+    pub(super) fn new(settings: &ConcurrencyConfig) -> Self {
+        let settings = ConcurrencyConfig {
+            max_weight: settings.max_weight.max(1),
+            ..settings.clone()
+        };
+        Self {
+            semaphore: Arc::new(Semaphore::new(settings.max_weight as usize)),
+            settings,
+            tasks: Arc::default(),
+        }
+    }
+
+    /// Weight of a workload, capped at the capacity so it can always run
+    fn weight(&self, workload: Workload) -> u32 {
+        let weight = match workload {
+            Workload::Render => self.settings.render_weight,
+            Workload::Proxy => self.settings.proxy_weight,
+            Workload::Transcription => self.settings.transcription_weight,
+            Workload::Analysis => self.settings.analysis_weight,
+        };
+        weight.clamp(1, self.settings.max_weight)
+    }
+
+    /// Wait until the workload's weight is free and take it
+    pub(super) async fn acquire(&self, workload: Workload, label: String) -> CpuPermit {
+        let weight = self.weight(workload);
+        let ticket = {
+            let mut tasks = self.tasks.lock().expect("CPU task list poisoned");
+            tasks.next_id += 1;
+            let id = tasks.next_id;
+            tasks.entries.insert(
+                id,
+                Task {
+                    label,
+                    workload,
+                    weight,
+                    queued_at: chrono::Utc::now(),
+                    started_at: None,
+                },
+            );
+            Ticket {
+                tasks: Arc::clone(&self.tasks),
+                id,
+            }
+        };
+        let permit = Arc::clone(&self.semaphore)
+            .acquire_many_owned(weight)
+            .await
+            .expect("the CPU semaphore is never closed");
+        if let Some(task) = self
+            .tasks
+            .lock()
+            .expect("CPU task list poisoned")
+            .entries
+            .get_mut(&ticket.id)
+        {
+            task.started_at = Some(chrono::Utc::now());
+        }
+        CpuPermit {
+            _permit: permit,
+            _ticket: ticket,
+        }
+    }
+
+    fn stats(&self) -> Value {
+        let tasks = self.tasks.lock().expect("CPU task list poisoned");
+        let (running, waiting): (Vec<&Task>, Vec<&Task>) = tasks
+            .entries
+            .values()
+            .partition(|task| task.started_at.is_some());
+        let in_use: u32 = running.iter().map(|task| task.weight).sum();
+        let describe = |task: &&Task| {
+            json!({
+                "label": task.label,
+                "workload": task.workload.as_str(),
+                "weight": task.weight,
+                "queued_at": task.queued_at.to_rfc3339(),
+                "started_at": task.started_at.map(|at| at.to_rfc3339())
+            })
+        };
+        let weights: BTreeMap<&str, u32> = [
+            Workload::Render,
+            Workload::Proxy,
+            Workload::Transcription,
+            Workload::Analysis,
+        ]
+        .into_iter()
+        .map(|workload| (workload.as_str(), self.weight(workload)))
+        .collect();
+
+        json!({
+            "capacity": self.settings.max_weight,
+            "in_use": in_use,
+            "available": self.settings.max_weight.saturating_sub(in_use),
+            "utilization_percent": f64::from(in_use) / f64::from(self.settings.max_weight) * 100.0,
+            "weights": weights,
+            "running": running.iter().map(describe).collect::<Vec<_>>(),
+            "waiting": waiting.iter().map(describe).collect::<Vec<_>>()
+        })
+    }
+}
+
+impl ResolveBridge {
+    /// Takes only the locks it reads, briefly, so it answers while heavy work runs
+    pub(super) async fn get_bridge_stats(&self, _args: Value) -> ResolveResult<Value> {
+        let cpu = self.cpu.stats();
+        let active_renders = self.state.render_state.read().await.active_renders.len();
+        let background_jobs = self.state.jobs.read().await.status_counts();
+        Ok(json!({
+            "result": format!(
+                "CPU capacity {}% in use with {} tasks waiting",
+                cpu["utilization_percent"],
+                cpu["waiting"].as_array().map_or(0, Vec::len)
+            ),
+            "mode": format!("{:?}", self.mode),
+            "operation_count": self.state.operation_count.load(Ordering::Relaxed),
+            "cpu": cpu,
+            "active_renders": active_renders,
+            "background_jobs": background_jobs,
+            "fixtures": self.fixtures.stats(),
+            "state_sync": self.state_sync_status().await,
+            "operation_id": Uuid::new_v4().to_string()
+        }))
+    }
+}
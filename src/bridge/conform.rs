@@ -0,0 +1,252 @@
+//! Offline to online conform
+//!
+//! Rebuilds a timeline cut with offline media (proxies, dailies) against the
+//! camera originals imported afterwards. Each item's source range is turned
+//! into source timecode through its clip's start timecode, then matched to
+//! the original from the same reel whose timecode covers the whole range.
+//! Clips without timecode or reel are probed first, as read_embedded_timecode
+//! does, and keep what was read. Items nothing matches, or more than one
+//! original matches, are reported and left as they are.
+
+use serde_json::{json, Value};
+use std::collections::BTreeSet;
+use uuid::Uuid;
+
+use super::embedded_timecode::timecode_frames;
+use super::{ResolveBridge, StateView, SIMULATED_CLIP_SECONDS};
+use crate::error::{ResolveError, ResolveResult};
+
+/// Online media an item can be conformed to
+struct Original {
+    name: String,
+    id: String,
+    reel_name: Option<String>,
+    /// Source timecode range the media covers, in frames
+    start: i64,
+    end: i64,
+}
+
+impl ResolveBridge {
+    pub(super) async fn conform_timeline(
+        &self,
+        state: &mut StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        let by_reel = match args["match_by"].as_str().unwrap_or("reel_and_timecode") {
+            "reel_and_timecode" => true,
+            "timecode" => false,
+            _ => {
+                return Err(ResolveError::invalid_parameter(
+                    "match_by",
+                    "must be 'reel_and_timecode' or 'timecode'",
+                ))
+            }
+        };
+        let dry_run = args["dry_run"].as_bool().unwrap_or(false);
+        let timeline_name = Self::resolve_timeline_name(state, &args)?;
+        let rate = self.project_frame_rate()?;
+
+        // Items with media, in record order
+        let mut item_ids: Vec<(i64, u32, String)> = state
+            .timeline_items
+            .items
+            .values()
+            .filter(|item| item.timeline_name == timeline_name && item.generator.is_none())
+            .filter_map(|item| {
+                let placement = item.placement.as_ref()?;
+                Some((placement.record_in, placement.track_index, item.id.clone()))
+            })
+            .collect();
+        item_ids.sort();
+        let offline: BTreeSet<String> = item_ids
+            .iter()
+            .map(|(_, _, id)| state.timeline_items.items[id].clip_name.clone())
+            .collect();
+
+        // Originals are the named clips, a bin's clips, or every clip not on the timeline
+        let original_names: BTreeSet<String> = if let Some(names) = args["clip_names"].as_array() {
+            names
+                .iter()
+                .map(|name| {
+                    let name = name.as_str().ok_or_else(|| {
+                        ResolveError::invalid_parameter("clip_names", "clip names must be strings")
+                    })?;
+                    Self::resolve_clip(state, name)
+                })
+                .collect::<ResolveResult<_>>()?
+        } else if let Some(bin) = args["bin_name"].as_str() {
+            let bin = Self::resolve_bin(state, bin)?;
+            let mut names: BTreeSet<String> =
+                state.media_pool.bins[&bin].clips.iter().cloned().collect();
+            names.extend(
+                state
+                    .media_pool
+                    .clips
+                    .values()
+                    .filter(|clip| clip.bin.as_deref() == Some(bin.as_str()))
+                    .map(|clip| clip.name.clone()),
+            );
+            names
+        } else {
+            state
+                .media_pool
+                .clips
+                .keys()
+                .filter(|name| !offline.contains(*name))
+                .cloned()
+                .collect()
+        };
+        if original_names.is_empty() {
+            return Err(ResolveError::invalid_parameter(
+                "clip_names",
+                "no media to conform to; import the originals first",
+            ));
+        }
+
+        // Read timecode and reel from any clip that does not have them yet
+        for name in offline.iter().chain(&original_names) {
+            let Some(clip) = state.media_pool.clips.get_mut(name) else {
+                continue;
+            };
+            if clip.start_timecode.is_none() {
+                let embedded = self.probe_embedded(&clip.file_path, name, rate);
+                if embedded.start_timecode.is_some() {
+                    clip.start_timecode = embedded.start_timecode;
+                    clip.reel_name = embedded.reel_name;
+                }
+            }
+        }
+        let clip_start = |name: &str| {
+            state
+                .media_pool
+                .clips
+                .get(name)
+                .and_then(|clip| clip.start_timecode.as_deref())
+                .and_then(|timecode| timecode_frames(timecode, rate).ok())
+        };
+        let media_frames = SIMULATED_CLIP_SECONDS * rate.nominal();
+        let without_timecode: Vec<&String> = original_names
+            .iter()
+            .filter(|name| clip_start(name).is_none())
+            .collect();
+        let originals: Vec<Original> = original_names
+            .iter()
+            .filter_map(|name| {
+                let start = clip_start(name)?;
+                let clip = &state.media_pool.clips[name];
+                Some(Original {
+                    name: name.clone(),
+                    id: clip.id.clone(),
+                    reel_name: clip.reel_name.clone(),
+                    start,
+                    end: start + media_frames,
+                })
+            })
+            .collect();
+
+        let mut conformed = Vec::new();
+        let mut unmatched = Vec::new();
+        let mut changes = Vec::new();
+        for (_, _, item_id) in &item_ids {
+            let item = &state.timeline_items.items[item_id];
+            let placement = item
+                .placement
+                .as_ref()
+                .expect("items were chosen by placement");
+            let reel_name = state
+                .media_pool
+                .clips
+                .get(&item.clip_name)
+                .and_then(|clip| clip.reel_name.clone());
+            let mut report = json!({
+                "timeline_item_id": item.id,
+                "clip_name": item.clip_name,
+                "track": format!("V{}", placement.track_index),
+                "reel_name": reel_name
+            });
+            let Some(start) = clip_start(&item.clip_name) else {
+                report["reason"] = json!("clip has no start timecode");
+                unmatched.push(report);
+                continue;
+            };
+            let source_in = start + placement.source_in;
+            let source_out = source_in + placement.duration;
+            report["source_in"] = json!(rate.frames_to_timecode(source_in));
+            report["source_out"] = json!(rate.frames_to_timecode(source_out));
+            if by_reel && reel_name.is_none() {
+                report["reason"] = json!("clip has no reel name; match by timecode alone instead");
+                unmatched.push(report);
+                continue;
+            }
+
+            let matches: Vec<&Original> = originals
+                .iter()
+                .filter(|original| original.name != item.clip_name)
+                .filter(|original| !by_reel || original.reel_name == reel_name)
+                .filter(|original| original.start <= source_in && source_out <= original.end)
+                .collect();
+            match matches.as_slice() {
+                [original] => {
+                    report["conformed_to"] = json!(original.name);
+                    report["new_source_in"] = json!(source_in - original.start);
+                    changes.push((
+                        item_id.clone(),
+                        original.name.clone(),
+                        original.id.clone(),
+                        source_in - original.start,
+                    ));
+                    conformed.push(report);
+                }
+                [] => {
+                    report["reason"] = json!(if by_reel {
+                        "no media from this reel covers the source range"
+                    } else {
+                        "no media covers the source range"
+                    });
+                    unmatched.push(report);
+                }
+                several => {
+                    report["reason"] = json!("more than one clip covers the source range");
+                    report["candidates"] = json!(several
+                        .iter()
+                        .map(|original| original.name.as_str())
+                        .collect::<Vec<_>>());
+                    unmatched.push(report);
+                }
+            }
+        }
+
+        if !dry_run {
+            for (item_id, clip_name, clip_id, source_in) in changes {
+                let item = state
+                    .timeline_items
+                    .items
+                    .get_mut(&item_id)
+                    .expect("conformed items exist");
+                item.clip_name = clip_name;
+                item.clip_id = Some(clip_id);
+                if let Some(placement) = item.placement.as_mut() {
+                    placement.source_in = source_in;
+                }
+            }
+        }
+
+        Ok(json!({
+            "result": format!(
+                "{} {} of {} items on '{}'",
+                if dry_run { "Would conform" } else { "Conformed" },
+                conformed.len(),
+                item_ids.len(),
+                timeline_name
+            ),
+            "timeline_name": timeline_name,
+            "match_by": if by_reel { "reel_and_timecode" } else { "timecode" },
+            "dry_run": dry_run,
+            "conformed": conformed,
+            "unmatched": unmatched,
+            "originals_considered": originals.len(),
+            "originals_without_timecode": without_timecode,
+            "operation_id": Uuid::new_v4().to_string()
+        }))
+    }
+}
@@ -0,0 +1,333 @@
+//! Pull lists and media consolidation
+//!
+//! The pull list is the set of source ranges a timeline uses, per clip, with
+//! handles added and overlapping ranges merged. Handles never reach outside
+//! the media, whose length is probed from the file with ffprobe when it can
+//! be. Consolidating trims each range into a destination folder with ffmpeg
+//! and relinks a duplicate of the timeline to the trimmed media.
+
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::path::Path;
+use uuid::Uuid;
+
+use super::ffmpeg;
+use super::{
+    clip_marks, ensure_capacity, Clip, ItemPlacement, ResolveBridge, StateView,
+    SIMULATED_CLIP_SECONDS,
+};
+use crate::error::{ResolveError, ResolveResult};
+
+/// Handle length used when the request does not give one, in frames
+const DEFAULT_HANDLES: i64 = 24;
+
+/// A merged source range of one clip, in source frames
+#[derive(Debug, Clone)]
+struct PullRange {
+    start: i64,
+    end: i64,
+    /// Name of the consolidated clip covering this range
+    clip_name: String,
+}
+
+/// Merge `[start, end)` ranges that overlap or touch
+fn merge_ranges(mut ranges: Vec<(i64, i64)>) -> Vec<(i64, i64)> {
+    ranges.sort();
+    let mut merged: Vec<(i64, i64)> = Vec::with_capacity(ranges.len());
+    for (start, end) in ranges {
+        match merged.last_mut() {
+            Some(last) if start <= last.1 => last.1 = last.1.max(end),
+            _ => merged.push((start, end)),
+        }
+    }
+    merged
+}
+
+/// Name of the consolidated clip for range `index` of `clip_name`
+fn consolidated_name(clip_name: &str, index: usize) -> String {
+    let path = Path::new(clip_name);
+    let stem = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_else(|| clip_name.to_string());
+    match path.extension() {
+        Some(ext) => format!("{}_{:03}.{}", stem, index + 1, ext.to_string_lossy()),
+        None => format!("{}_{:03}", stem, index + 1),
+    }
+}
+
+impl ResolveBridge {
+    pub(super) async fn consolidate_media(
+        &self,
+        state: &mut StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        let handles = match args["handles"].as_i64() {
+            Some(handles) if handles >= 0 => handles,
+            Some(_) => {
+                return Err(ResolveError::invalid_parameter(
+                    "handles",
+                    "must be 0 or greater",
+                ))
+            }
+            None => DEFAULT_HANDLES,
+        };
+        let timeline_name = Self::resolve_timeline_name(state, &args)?;
+        let rate = self.timeline_frame_rate(&state.timelines[&timeline_name])?;
+
+        let items: Vec<(String, String, ItemPlacement)> = state
+            .timeline_items
+            .items
+            .values()
+            .filter(|item| item.timeline_name == timeline_name)
+            .filter_map(|item| {
+                item.placement
+                    .clone()
+                    .map(|placement| (item.id.clone(), item.clip_name.clone(), placement))
+            })
+            .collect();
+
+        // Handles stop at the first and last frames of the media
+        let mut used: BTreeMap<&str, Vec<(i64, i64)>> = BTreeMap::new();
+        for (_, clip_name, placement) in &items {
+            let media_frames = state
+                .media_pool
+                .clips
+                .get(clip_name)
+                .map_or(SIMULATED_CLIP_SECONDS * rate.nominal(), |clip| {
+                    clip_marks::media_frames(clip, rate).0
+                });
+            used.entry(clip_name.as_str()).or_default().push((
+                (placement.source_in - handles).max(0),
+                (placement.source_out() + handles).min(media_frames),
+            ));
+        }
+        let pull_list: BTreeMap<&str, Vec<PullRange>> = used
+            .into_iter()
+            .map(|(clip_name, ranges)| {
+                let ranges = merge_ranges(ranges)
+                    .into_iter()
+                    .enumerate()
+                    .map(|(index, (start, end))| PullRange {
+                        start,
+                        end,
+                        clip_name: consolidated_name(clip_name, index),
+                    })
+                    .collect();
+                (clip_name, ranges)
+            })
+            .collect();
+
+        let destination = match args["destination_folder"].as_str() {
+            Some(folder) => {
+                let resolved = self.resolve_storage_path(folder)?;
+                if !resolved.is_dir() {
+                    return Err(ResolveError::invalid_parameter(
+                        "destination_folder",
+                        format!("'{}' is not a folder", folder),
+                    ));
+                }
+                Some(resolved)
+            }
+            None => None,
+        };
+
+        let range_json = |range: &PullRange| {
+            json!({
+                "source_in": range.start,
+                "source_out": range.end,
+                "source_in_timecode": rate.frames_to_timecode(range.start),
+                "source_out_timecode": rate.frames_to_timecode(range.end),
+                "duration_frames": range.end - range.start
+            })
+        };
+        let total_frames: i64 = pull_list
+            .values()
+            .flatten()
+            .map(|range| range.end - range.start)
+            .sum();
+        let range_count: usize = pull_list.values().map(Vec::len).sum();
+
+        let Some(destination) = destination else {
+            let clips: Vec<Value> = pull_list
+                .iter()
+                .map(|(clip_name, ranges)| {
+                    json!({
+                        "clip_name": clip_name,
+                        "file_path": state.media_pool.clips.get(*clip_name).map(|clip| clip.file_path.as_str()),
+                        "ranges": ranges.iter().map(range_json).collect::<Vec<_>>()
+                    })
+                })
+                .collect();
+            return Ok(json!({
+                "result": format!(
+                    "Pull list for timeline '{}' has {} ranges from {} clips",
+                    timeline_name,
+                    range_count,
+                    clips.len()
+                ),
+                "timeline_name": timeline_name,
+                "handles": handles,
+                "frame_rate": rate.to_string(),
+                "clips": clips,
+                "total_frames": total_frames,
+                "operation_id": Uuid::new_v4().to_string()
+            }));
+        };
+
+        let new_timeline_name = args["new_timeline_name"]
+            .as_str()
+            .map(|name| name.to_string())
+            .unwrap_or_else(|| format!("{} (Consolidated)", timeline_name));
+
+        // Check everything before writing files or touching the project
+        if state.timelines.contains_key(&new_timeline_name) {
+            return Err(ResolveError::invalid_parameter(
+                "new_timeline_name",
+                format!("timeline '{}' already exists", new_timeline_name),
+            ));
+        }
+        ensure_capacity(
+            "timelines",
+            state.timelines.len(),
+            1,
+            self.config.limits.max_timelines,
+        )?;
+        ensure_capacity(
+            "clips",
+            state.media_pool.clips.len(),
+            range_count,
+            self.config.limits.max_clips,
+        )?;
+        for range in pull_list.values().flatten() {
+            let output = destination.join(&range.clip_name);
+            if state.media_pool.clips.contains_key(&range.clip_name) || output.exists() {
+                return Err(ResolveError::invalid_parameter(
+                    "destination_folder",
+                    format!("'{}' already exists", output.display()),
+                ));
+            }
+        }
+
+        let can_trim = ffmpeg::available();
+        let mut clips = Vec::with_capacity(pull_list.len());
+        let mut written = 0;
+        for (clip_name, ranges) in &pull_list {
+            let source = state.media_pool.clips.get(*clip_name);
+            let source_path = source.map(|clip| clip.file_path.clone());
+            let bin = source.and_then(|clip| clip.bin.clone());
+            let frame_rate = source.and_then(|clip| clip.frame_rate.clone());
+            let color = source.map(|clip| clip.color.clone()).unwrap_or_default();
+            let metadata = source.map(|clip| clip.metadata.clone()).unwrap_or_default();
+            let mut range_results = Vec::with_capacity(ranges.len());
+            for range in ranges {
+                let output = destination.join(&range.clip_name);
+                let status = match &source_path {
+                    Some(path) if !Path::new(path).is_file() => "source_offline".to_string(),
+                    None => "source_offline".to_string(),
+                    Some(_) if !can_trim => "ffmpeg_unavailable".to_string(),
+                    Some(path) => {
+                        match ffmpeg::trim(
+                            path,
+                            &output,
+                            rate,
+                            range.start,
+                            range.end - range.start,
+                        ) {
+                            Ok(()) => "written".to_string(),
+                            Err(e) => format!("failed: {}", e),
+                        }
+                    }
+                };
+                let linked = status == "written";
+                if linked {
+                    written += 1;
+                }
+
+                state.media_pool.clips.insert(
+                    range.clip_name.clone(),
+                    Clip {
+                        id: Uuid::new_v4().to_string(),
+                        name: range.clip_name.clone(),
+                        file_path: output.display().to_string(),
+                        bin: bin.clone(),
+                        linked,
+                        proxy_path: None,
+                        start_timecode: None,
+                        reel_name: None,
+                        frame_rate: frame_rate.clone(),
+                        color: color.clone(),
+                        metadata: metadata.clone(),
+                        marks: Default::default(),
+                    },
+                );
+                if let Some(bin) = bin
+                    .as_ref()
+                    .and_then(|bin| state.media_pool.bins.get_mut(bin))
+                {
+                    bin.clips.push(range.clip_name.clone());
+                }
+
+                let mut result = range_json(range);
+                result["consolidated_clip"] = json!(range.clip_name);
+                result["file_path"] = json!(output.display().to_string());
+                result["status"] = json!(status);
+                range_results.push(result);
+            }
+            clips.push(json!({
+                "clip_name": clip_name,
+                "file_path": source_path,
+                "ranges": range_results
+            }));
+        }
+
+        // Relink a duplicate of the timeline to the consolidated clips
+        let mut timeline = state.timelines[&timeline_name].clone();
+        timeline.id = Uuid::new_v4().to_string();
+        timeline.name = new_timeline_name.clone();
+        timeline.lineage = None;
+        state.timelines.insert(new_timeline_name.clone(), timeline);
+        for (id, clip_name, placement) in &items {
+            let Some(range) = pull_list[clip_name.as_str()].iter().find(|range| {
+                range.start <= placement.source_in && placement.source_out() <= range.end
+            }) else {
+                continue;
+            };
+            let mut item = state.timeline_items.items[id].clone();
+            let new_id = Uuid::new_v4().to_string();
+            item.id = new_id.clone();
+            item.timeline_name = new_timeline_name.clone();
+            item.clip_name = range.clip_name.clone();
+            item.clip_id = state
+                .media_pool
+                .clips
+                .get(&range.clip_name)
+                .map(|clip| clip.id.clone());
+            item.placement = Some(ItemPlacement {
+                source_in: placement.source_in - range.start,
+                ..placement.clone()
+            });
+            state.timeline_items.item_counter += 1;
+            state.timeline_items.items.insert(new_id, item);
+        }
+
+        Ok(json!({
+            "result": format!(
+                "Consolidated {} ranges from {} clips into '{}' and relinked timeline '{}'",
+                range_count,
+                clips.len(),
+                destination.display(),
+                new_timeline_name
+            ),
+            "timeline_name": timeline_name,
+            "new_timeline_name": new_timeline_name,
+            "handles": handles,
+            "frame_rate": rate.to_string(),
+            "destination_folder": destination.display().to_string(),
+            "clips": clips,
+            "total_frames": total_frames,
+            "files_written": written,
+            "operation_id": Uuid::new_v4().to_string()
+        }))
+    }
+}
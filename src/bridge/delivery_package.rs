@@ -0,0 +1,282 @@
+//! Delivery packages
+//!
+//! A delivery package is a set of renders made of every timeline in one
+//! delivery: a ProRes master, an H.264 review copy and audio stems, each with
+//! its own preset. queue_delivery_package queues one job per output per
+//! timeline, from a package in the config or outputs given with the call.
+//! The outputs of a timeline share a folder and a file name stem, so
+//! `EP101_master.mov` sits next to `EP101_review.mp4`, and a manifest listing
+//! them, their jobs and the package they came from is written beside them.
+//! As with batch renders, if any job cannot be queued, none is.
+
+use chrono::Utc;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use super::batch_render::{path_component, DEFAULT_OUTPUT_TEMPLATE};
+use super::{paths, ResolveBridge, StateView};
+use crate::config::{DeliveryOutputConfig, DeliveryPackageConfig};
+use crate::error::{ResolveError, ResolveResult};
+
+/// Package name given to outputs listed with the call
+const CALL_PACKAGE: &str = "custom";
+
+/// One timeline's outputs, and where its manifest goes
+struct TimelineDelivery {
+    timeline: String,
+    manifest_path: String,
+    outputs: Vec<Value>,
+}
+
+/// Outputs given with the call, which may leave out the optional settings
+fn parse_outputs(outputs: &[Value]) -> ResolveResult<Vec<DeliveryOutputConfig>> {
+    outputs
+        .iter()
+        .enumerate()
+        .map(|(index, output)| {
+            let mut output = output.clone();
+            if let Some(fields) = output.as_object_mut() {
+                fields.retain(|_, value| !value.is_null());
+            }
+            serde_json::from_value(output).map_err(|e| {
+                ResolveError::invalid_parameter(format!("outputs[{}]", index), e.to_string())
+            })
+        })
+        .collect()
+}
+
+impl ResolveBridge {
+    /// The package named by the call, or made of the outputs it lists
+    fn delivery_package(&self, args: &Value) -> ResolveResult<(String, DeliveryPackageConfig)> {
+        let (name, package, param) = match (args["package"].as_str(), args["outputs"].as_array()) {
+            (Some(_), Some(_)) => {
+                return Err(ResolveError::invalid_parameter(
+                    "outputs",
+                    "give a package or outputs, not both",
+                ))
+            }
+            (Some(name), None) => {
+                let package = self.config.delivery_packages.get(name).ok_or_else(|| {
+                    let configured: Vec<&str> = self
+                        .config
+                        .delivery_packages
+                        .keys()
+                        .map(String::as_str)
+                        .collect();
+                    ResolveError::invalid_parameter(
+                        "package",
+                        format!(
+                            "no delivery package named '{}'; configured: {}",
+                            name,
+                            if configured.is_empty() {
+                                "none".to_string()
+                            } else {
+                                configured.join(", ")
+                            }
+                        ),
+                    )
+                })?;
+                (name.to_string(), package.clone(), "package")
+            }
+            (None, Some(outputs)) => {
+                let package = DeliveryPackageConfig {
+                    description: String::new(),
+                    outputs: parse_outputs(outputs)?,
+                };
+                (CALL_PACKAGE.to_string(), package, "outputs")
+            }
+            (None, None) => {
+                return Err(ResolveError::invalid_parameter(
+                    "package",
+                    "give a configured package or a list of outputs",
+                ))
+            }
+        };
+        match package.problem() {
+            Some(problem) => Err(ResolveError::invalid_parameter(
+                param,
+                format!("package '{}' {}", name, problem),
+            )),
+            None => Ok((name, package)),
+        }
+    }
+
+    pub(super) async fn queue_delivery_package(
+        &self,
+        state: &mut StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        let (package_name, package) = self.delivery_package(&args)?;
+        let template = args["output_directory"]
+            .as_str()
+            .unwrap_or(DEFAULT_OUTPUT_TEMPLATE)
+            .trim_end_matches('/');
+        if template.is_empty() {
+            return Err(ResolveError::invalid_parameter(
+                "output_directory",
+                "must not be empty",
+            ));
+        }
+        let write_manifest = args["write_manifest"].as_bool().unwrap_or(true);
+
+        let mut timelines: Vec<String> = Vec::new();
+        match args["timeline_names"].as_array() {
+            Some(names) => {
+                for name in names {
+                    let name = name.as_str().ok_or_else(|| {
+                        ResolveError::invalid_parameter(
+                            "timeline_names",
+                            "timeline names must be strings",
+                        )
+                    })?;
+                    let name = Self::resolve_timeline(state, name)?;
+                    if !timelines.contains(&name) {
+                        timelines.push(name);
+                    }
+                }
+            }
+            None => timelines.push(Self::resolve_timeline_name(state, &json!({}))?),
+        }
+        if timelines.is_empty() {
+            return Err(ResolveError::invalid_parameter(
+                "timeline_names",
+                "name at least one timeline",
+            ));
+        }
+
+        let package_id = Uuid::new_v4().to_string();
+        let project = state
+            .current_project
+            .clone()
+            .unwrap_or_else(|| "Untitled".to_string());
+        let package_component = path_component(&package_name);
+        let queue_length = state.render_state.render_queue.len();
+        let job_counter = state.render_state.job_counter;
+        let mut deliveries = Vec::with_capacity(timelines.len());
+        let mut queued = Ok(());
+        'timelines: for timeline_name in &timelines {
+            let stem = path_component(timeline_name);
+            let directory = template
+                .replace("{project}", &path_component(&project))
+                .replace("{timeline}", &stem)
+                .replace("{package}", &package_component);
+            if let Err(error) = paths::check_path(&self.config.sandbox, &directory) {
+                queued = Err(error);
+                break;
+            }
+            let mut delivery = TimelineDelivery {
+                timeline: timeline_name.clone(),
+                manifest_path: format!("{}/{}_manifest.json", directory, stem),
+                outputs: Vec::with_capacity(package.outputs.len()),
+            };
+            for output in &package.outputs {
+                let name = output.name.trim();
+                let job_args = json!({
+                    "use_in_out_range": args["use_in_out_range"],
+                    "export_video": output.export_video,
+                    "export_audio": output.export_audio,
+                    "audio_channel_layout": output.audio_channel_layout
+                });
+                let output_stem = format!("{}/{}_{}", directory, stem, path_component(name));
+                match self.queue_timeline_render(
+                    state,
+                    timeline_name,
+                    &output.preset,
+                    Some(output_stem),
+                    None,
+                    &job_args,
+                ) {
+                    Ok(job) => delivery.outputs.push(json!({
+                        "name": name,
+                        "preset": output.preset,
+                        "job_id": job["job_id"],
+                        "output_path": job["output_path"],
+                        "export_video": job["export_video"],
+                        "export_audio": job["export_audio"],
+                        "audio_channel_layout": job["audio_channel_layout"]
+                    })),
+                    Err(error) => {
+                        queued = Err(error);
+                        break 'timelines;
+                    }
+                }
+            }
+            deliveries.push(delivery);
+        }
+
+        let queued_at = Utc::now().to_rfc3339();
+        let manifests: Vec<Value> = deliveries
+            .iter()
+            .map(|delivery| {
+                json!({
+                    "package": package_name,
+                    "package_id": package_id,
+                    "description": package.description,
+                    "project": project,
+                    "timeline": delivery.timeline,
+                    "queued_at": queued_at,
+                    "outputs": delivery.outputs
+                })
+            })
+            .collect();
+        if queued.is_ok() && write_manifest {
+            queued = deliveries
+                .iter()
+                .zip(&manifests)
+                .try_for_each(|(delivery, manifest)| {
+                    Self::write_delivery_manifest(&delivery.manifest_path, manifest)
+                });
+        }
+        if let Err(error) = queued {
+            state.render_state.render_queue.truncate(queue_length);
+            state.render_state.job_counter = job_counter;
+            return Err(error);
+        }
+
+        let job_count: usize = deliveries
+            .iter()
+            .map(|delivery| delivery.outputs.len())
+            .sum();
+        let timelines: Vec<Value> = deliveries
+            .iter()
+            .zip(manifests)
+            .map(|(delivery, manifest)| {
+                json!({
+                    "timeline_name": delivery.timeline,
+                    "manifest_path": write_manifest.then_some(&delivery.manifest_path),
+                    "manifest": manifest
+                })
+            })
+            .collect();
+        Ok(json!({
+            "result": format!(
+                "Queued delivery package '{}': {} outputs for each of {} timelines, {} render jobs",
+                package_name,
+                package.outputs.len(),
+                timelines.len(),
+                job_count
+            ),
+            "package": package_name,
+            "package_id": package_id,
+            "timelines": timelines,
+            "job_count": job_count,
+            "queue_length": state.render_state.render_queue.len(),
+            "operation_id": Uuid::new_v4().to_string()
+        }))
+    }
+
+    fn write_delivery_manifest(path: &str, manifest: &Value) -> ResolveResult<()> {
+        let write = || -> std::io::Result<()> {
+            if let Some(directory) = std::path::Path::new(path).parent() {
+                std::fs::create_dir_all(directory)?;
+            }
+            std::fs::write(path, serde_json::to_vec_pretty(manifest)?)
+        };
+        write().map_err(|e| {
+            ResolveError::internal(format!(
+                "failed to write delivery manifest '{}': {}",
+                path, e
+            ))
+        })
+    }
+}
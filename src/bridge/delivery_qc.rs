@@ -0,0 +1,494 @@
+//! Black, freeze-frame and silence detection for deliverables
+//!
+//! A rendered file, or the picture and mix of a timeline, is checked frame by
+//! frame at low resolution in a background job. Thresholds follow ffmpeg's
+//! `blackdetect`, `freezedetect` and `silencedetect` defaults. Timeline gaps
+//! are black and silent, audio-only clips have no picture and still images
+//! hold a frozen frame without audio. Media that cannot be decoded is
+//! simulated.
+
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+use uuid::Uuid;
+
+use super::ffmpeg;
+use super::markers::timeline_start;
+use super::waveform::simulated_peaks;
+use super::{ResolveBridge, StateView, SIMULATED_CLIP_SECONDS};
+use crate::error::{ResolveError, ResolveResult};
+use crate::timecode::FrameRate;
+
+/// Size frames are scaled to before analysis
+const QC_WIDTH: u32 = 64;
+const QC_HEIGHT: u32 = 36;
+
+/// Luma at or below which a pixel counts as black
+const BLACK_PIXEL_LEVEL: u8 = 25;
+/// Share of black pixels that makes a frame black
+const BLACK_PICTURE_RATIO: f64 = 0.98;
+/// Mean luma difference from the previous frame below which a frame is frozen
+const FREEZE_NOISE: f64 = 0.001;
+/// Audio peak blocks per second
+const BLOCKS_PER_SECOND: u32 = 100;
+
+const DEFAULT_MIN_BLACK_SECONDS: f64 = 0.5;
+const DEFAULT_MIN_FREEZE_SECONDS: f64 = 2.0;
+const DEFAULT_MIN_SILENCE_SECONDS: f64 = 2.0;
+const DEFAULT_SILENCE_THRESHOLD_DB: f64 = -60.0;
+
+pub(super) const AUDIO_EXTENSIONS: &[&str] = &["wav", "aif", "aiff", "mp3", "m4a", "flac", "aac"];
+const STILL_EXTENSIONS: &[&str] = &["tif", "tiff", "png", "jpg", "jpeg", "bmp"];
+
+/// What a media file contributes to picture and sound
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) enum MediaKind {
+    Video,
+    Audio,
+    Still,
+}
+
+impl MediaKind {
+    pub(super) fn of(file_path: &str) -> Self {
+        let extension = Path::new(file_path)
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(str::to_ascii_lowercase)
+            .unwrap_or_default();
+        if AUDIO_EXTENSIONS.contains(&extension.as_str()) {
+            Self::Audio
+        } else if STILL_EXTENSIONS.contains(&extension.as_str()) {
+            Self::Still
+        } else {
+            Self::Video
+        }
+    }
+}
+
+/// Decoded, or simulated, picture and sound of one media file
+struct MediaSource {
+    kind: MediaKind,
+    /// Luma frames from the start of the file; None when simulated
+    frames: Option<Vec<Vec<u8>>>,
+    /// Audio peak blocks from the start of the file
+    peaks: Vec<(f32, f32)>,
+    origin: String,
+}
+
+/// Moving gradient used when video cannot be decoded
+fn simulated_frame(frame: i64) -> Vec<u8> {
+    let width = QC_WIDTH as i64;
+    (0..QC_WIDTH * QC_HEIGHT)
+        .map(|index| ((index as i64 % width * 255 / (width - 1) + frame * 3) % 256) as u8)
+        .collect()
+}
+
+impl MediaSource {
+    fn load(file_path: &str, rate: FrameRate, can_decode: bool) -> Self {
+        let kind = MediaKind::of(file_path);
+        let media = Path::new(file_path);
+        if !can_decode || !media.is_file() {
+            let peaks = if kind == MediaKind::Still {
+                Vec::new()
+            } else {
+                simulated_peaks()
+            };
+            return Self {
+                kind,
+                frames: None,
+                peaks,
+                origin: "simulated".to_string(),
+            };
+        }
+
+        let mut problems = Vec::new();
+        let frames = if kind == MediaKind::Audio {
+            None
+        } else {
+            match ffmpeg::gray_frames(media, rate.fps(), QC_WIDTH, QC_HEIGHT) {
+                Ok(frames) => Some(frames),
+                Err(e) => {
+                    problems.push(format!("video: {}", e));
+                    Some(Vec::new())
+                }
+            }
+        };
+        // Media without an audio stream is silent
+        let peaks = if kind == MediaKind::Still {
+            Vec::new()
+        } else {
+            ffmpeg::audio_peaks(media, BLOCKS_PER_SECOND).unwrap_or_else(|e| {
+                problems.push(format!("audio: {}", e));
+                Vec::new()
+            })
+        };
+        let origin = if problems.is_empty() {
+            "decoded".to_string()
+        } else {
+            format!("decoded with errors ({})", problems.join("; "))
+        };
+        Self {
+            kind,
+            frames,
+            peaks,
+            origin,
+        }
+    }
+
+    /// Luma pixels at a source frame; None when there is no picture
+    fn picture(&self, frame: i64) -> Option<Vec<u8>> {
+        let frame = match self.kind {
+            MediaKind::Audio => return None,
+            MediaKind::Still => 0,
+            MediaKind::Video => frame,
+        };
+        match &self.frames {
+            Some(frames) => usize::try_from(frame)
+                .ok()
+                .and_then(|frame| frames.get(frame))
+                .cloned(),
+            None => Some(simulated_frame(frame)),
+        }
+    }
+
+    /// Peak level of the audio block at a source time
+    fn level(&self, seconds: f64) -> f32 {
+        self.peaks
+            .get((seconds * BLOCKS_PER_SECOND as f64) as usize)
+            .map_or(0.0, |&(min, max)| min.abs().max(max.abs()))
+    }
+
+    /// Frames of picture when decoded, for sizing a single file
+    fn length(&self, rate: FrameRate) -> i64 {
+        match &self.frames {
+            Some(frames) if self.kind == MediaKind::Video => frames.len() as i64,
+            _ if self.kind == MediaKind::Video => SIMULATED_CLIP_SECONDS * rate.nominal(),
+            _ => (self.peaks.len() as f64 / BLOCKS_PER_SECOND as f64 * rate.fps()).ceil() as i64,
+        }
+    }
+}
+
+/// One media source laid onto the output
+struct Segment {
+    clip_name: String,
+    track_index: u32,
+    record_in: i64,
+    source_in: i64,
+    duration: i64,
+}
+
+impl Segment {
+    fn covers(&self, frame: f64) -> bool {
+        frame >= self.record_in as f64 && frame < (self.record_in + self.duration) as f64
+    }
+}
+
+/// Everything the background job needs, detached from the bridge state
+struct DeliveryQc {
+    rate: FrameRate,
+    start_frame: i64,
+    /// File paths by clip name, or the file name for a single file
+    media: BTreeMap<String, String>,
+    segments: Vec<Segment>,
+    /// Output length in frames; None sizes a single file from its media
+    total_frames: Option<i64>,
+    min_black_frames: i64,
+    min_freeze_frames: i64,
+    min_silence_blocks: usize,
+    silence_level: f32,
+    target: Value,
+}
+
+/// Half-open ranges of consecutive true values at least `min` long
+fn runs(flags: &[bool], min: usize) -> Vec<(usize, usize)> {
+    let mut runs = Vec::new();
+    let mut start = None;
+    for (index, &flag) in flags.iter().chain([&false]).enumerate() {
+        match (flag, start) {
+            (true, None) => start = Some(index),
+            (false, Some(from)) => {
+                if index - from >= min {
+                    runs.push((from, index));
+                }
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    runs
+}
+
+impl DeliveryQc {
+    fn run(self) -> Result<Value, String> {
+        let can_decode = ffmpeg::available();
+        let sources: BTreeMap<&String, MediaSource> = self
+            .media
+            .iter()
+            .map(|(name, path)| (name, MediaSource::load(path, self.rate, can_decode)))
+            .collect();
+        let total_frames = self.total_frames.unwrap_or_else(|| {
+            sources
+                .values()
+                .map(|source| source.length(self.rate))
+                .max()
+                .unwrap_or(0)
+        });
+        let fps = self.rate.fps();
+
+        // Picture: the topmost segment at each frame is what is seen
+        let mut black = Vec::with_capacity(total_frames as usize);
+        let mut frozen = Vec::with_capacity(total_frames as usize);
+        let mut previous: Option<Vec<u8>> = None;
+        for frame in 0..total_frames {
+            let picture = self
+                .segments
+                .iter()
+                .filter(|segment| segment.covers(frame as f64))
+                .max_by_key(|segment| segment.track_index)
+                .and_then(|segment| {
+                    sources[&segment.clip_name]
+                        .picture(segment.source_in + frame - segment.record_in)
+                });
+            let is_black = picture.as_ref().is_none_or(|pixels| {
+                let dark = pixels.iter().filter(|&&y| y <= BLACK_PIXEL_LEVEL).count();
+                dark as f64 >= pixels.len() as f64 * BLACK_PICTURE_RATIO
+            });
+            // Black stretches are reported as black, not frozen
+            let is_frozen = !is_black
+                && picture
+                    .as_ref()
+                    .zip(previous.as_ref())
+                    .is_some_and(|(now, before)| {
+                        let difference: u64 = now
+                            .iter()
+                            .zip(before)
+                            .map(|(&a, &b)| u64::from(a.abs_diff(b)))
+                            .sum();
+                        (difference as f64 / now.len().max(1) as f64 / 255.0) < FREEZE_NOISE
+                    });
+            black.push(is_black);
+            frozen.push(is_frozen);
+            previous = if is_black { None } else { picture };
+        }
+
+        // Sound: silent only where every covering segment is silent
+        let total_blocks = (total_frames as f64 / fps * BLOCKS_PER_SECOND as f64).ceil() as usize;
+        let silent: Vec<bool> = (0..total_blocks)
+            .map(|block| {
+                let frame = block as f64 / BLOCKS_PER_SECOND as f64 * fps;
+                self.segments
+                    .iter()
+                    .filter(|segment| segment.covers(frame))
+                    .all(|segment| {
+                        let source_frame =
+                            segment.source_in as f64 + frame - segment.record_in as f64;
+                        sources[&segment.clip_name].level(source_frame / fps) < self.silence_level
+                    })
+            })
+            .collect();
+
+        let event = |kind: &str, from: i64, to: i64| {
+            let clips: BTreeSet<&String> = self
+                .segments
+                .iter()
+                .filter(|segment| {
+                    segment.record_in < to && segment.record_in + segment.duration > from
+                })
+                .map(|segment| &segment.clip_name)
+                .collect();
+            json!({
+                "type": kind,
+                "start_frame": from,
+                "end_frame": to,
+                "duration_seconds": ((to - from) as f64 / fps * 1000.0).round() / 1000.0,
+                "start_timecode": self.rate.frames_to_timecode(self.start_frame + from),
+                "end_timecode": self.rate.frames_to_timecode(self.start_frame + to),
+                "clips": clips
+            })
+        };
+        let mut events = Vec::new();
+        for (from, to) in runs(&black, self.min_black_frames as usize) {
+            events.push(event("black", from as i64, to as i64));
+        }
+        // A freeze starts on the frame that is held
+        for (from, to) in runs(&frozen, (self.min_freeze_frames - 1).max(1) as usize) {
+            events.push(event("freeze", from as i64 - 1, to as i64));
+        }
+        for (from, to) in runs(&silent, self.min_silence_blocks) {
+            let to_frame =
+                ((to as f64 / BLOCKS_PER_SECOND as f64 * fps).ceil() as i64).min(total_frames);
+            let from_frame = (from as f64 / BLOCKS_PER_SECOND as f64 * fps).floor() as i64;
+            events.push(event("silence", from_frame, to_frame));
+        }
+        events.sort_by_key(|event| event["start_frame"].as_i64());
+
+        let count = |kind: &str| events.iter().filter(|event| event["type"] == kind).count();
+        let sources: BTreeMap<&String, &str> = sources
+            .iter()
+            .map(|(name, source)| (*name, source.origin.as_str()))
+            .collect();
+        Ok(json!({
+            "target": self.target,
+            "frame_rate": self.rate.to_string(),
+            "duration_frames": total_frames,
+            "passed": events.is_empty(),
+            "summary": {
+                "black": count("black"),
+                "freeze": count("freeze"),
+                "silence": count("silence")
+            },
+            "events": events,
+            "sources": sources
+        }))
+    }
+}
+
+/// Read an optional minimum duration argument in seconds
+fn min_seconds(args: &Value, param: &str, default: f64) -> ResolveResult<f64> {
+    match args[param].as_f64() {
+        Some(seconds) if seconds > 0.0 => Ok(seconds),
+        Some(_) => Err(ResolveError::invalid_parameter(
+            param,
+            "must be greater than 0",
+        )),
+        None => Ok(default),
+    }
+}
+
+impl ResolveBridge {
+    pub(super) async fn run_delivery_qc(
+        &self,
+        state: &mut StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        let min_black = min_seconds(&args, "min_black_seconds", DEFAULT_MIN_BLACK_SECONDS)?;
+        let min_freeze = min_seconds(&args, "min_freeze_seconds", DEFAULT_MIN_FREEZE_SECONDS)?;
+        let min_silence = min_seconds(&args, "min_silence_seconds", DEFAULT_MIN_SILENCE_SECONDS)?;
+        let silence_db = args["silence_threshold_db"]
+            .as_f64()
+            .unwrap_or(DEFAULT_SILENCE_THRESHOLD_DB);
+        if silence_db >= 0.0 {
+            return Err(ResolveError::invalid_parameter(
+                "silence_threshold_db",
+                "must be below 0",
+            ));
+        }
+
+        let (rate, start_frame, media, segments, total_frames, target, description) = match args
+            ["file_path"]
+            .as_str()
+        {
+            Some(file_path) => {
+                if args["timeline_name"].is_string() {
+                    return Err(ResolveError::invalid_parameter(
+                        "file_path",
+                        "give either file_path or timeline_name, not both",
+                    ));
+                }
+                let resolved = self.resolve_storage_path(file_path)?;
+                if !resolved.is_file() {
+                    return Err(ResolveError::invalid_parameter(
+                        "file_path",
+                        "must be a media file",
+                    ));
+                }
+                let path = resolved.display().to_string();
+                let rate = self.project_frame_rate()?;
+                let segments = vec![Segment {
+                    clip_name: path.clone(),
+                    track_index: 1,
+                    record_in: 0,
+                    source_in: 0,
+                    duration: i64::MAX / 2,
+                }];
+                (
+                    rate,
+                    0,
+                    BTreeMap::from([(path.clone(), path.clone())]),
+                    segments,
+                    None,
+                    json!({ "file_path": path }),
+                    format!("Delivery QC of '{}'", path),
+                )
+            }
+            None => {
+                let timeline_name = Self::resolve_timeline_name(state, &args)?;
+                let timeline = &state.timelines[&timeline_name];
+                let start = timeline_start(timeline, &args);
+                let rate = self
+                    .timeline_frame_rate(timeline)?
+                    .for_timecode(start)
+                    .map_err(|e| ResolveError::invalid_parameter("timeline_start_timecode", e))?;
+                let start_frame = rate
+                    .timecode_to_frames(start)
+                    .map_err(|e| ResolveError::invalid_parameter("timeline_start_timecode", e))?;
+                let mut media = BTreeMap::new();
+                let mut segments: Vec<Segment> = Vec::new();
+                for item in state.timeline_items.items.values() {
+                    let Some(placement) = &item.placement else {
+                        continue;
+                    };
+                    if item.timeline_name != timeline_name {
+                        continue;
+                    }
+                    // Items whose clip has left the media pool render as gaps
+                    let Some(clip) = state.media_pool.clips.get(&item.clip_name) else {
+                        continue;
+                    };
+                    media.insert(item.clip_name.clone(), clip.file_path.clone());
+                    segments.push(Segment {
+                        clip_name: item.clip_name.clone(),
+                        track_index: placement.track_index,
+                        record_in: placement.record_in,
+                        source_in: placement.source_in,
+                        duration: placement.duration,
+                    });
+                }
+                segments.sort_by_key(|segment| (segment.record_in, segment.track_index));
+                let total = segments
+                    .iter()
+                    .map(|segment| segment.record_in + segment.duration)
+                    .max()
+                    .unwrap_or(0);
+                if total == 0 {
+                    return Err(ResolveError::invalid_parameter(
+                        "timeline_name",
+                        format!("timeline '{}' has no media to check", timeline_name),
+                    ));
+                }
+                (
+                    rate,
+                    start_frame,
+                    media,
+                    segments,
+                    Some(total),
+                    json!({ "timeline_name": timeline_name }),
+                    format!("Delivery QC of timeline '{}'", timeline_name),
+                )
+            }
+        };
+
+        let fps = rate.fps();
+        let qc = DeliveryQc {
+            rate,
+            start_frame,
+            media,
+            segments,
+            total_frames,
+            min_black_frames: ((min_black * fps).round() as i64).max(1),
+            min_freeze_frames: ((min_freeze * fps).round() as i64).max(2),
+            min_silence_blocks: ((min_silence * BLOCKS_PER_SECOND as f64).round() as usize).max(1),
+            silence_level: 10f64.powf(silence_db / 20.0) as f32,
+            target: target.clone(),
+        };
+        let job_id = self.spawn_job(state, "delivery_qc", description.clone(), move || qc.run());
+
+        Ok(json!({
+            "result": format!("Started {} as job '{}'", description, job_id),
+            "job_id": job_id,
+            "status": "running",
+            "target": target,
+            "operation_id": Uuid::new_v4().to_string()
+        }))
+    }
+}
@@ -0,0 +1,210 @@
+//! Grades from .drx files
+//!
+//! A .drx file holds a grade exported from the gallery, node graph and all.
+//! Resolve applies one to timeline items with `Timeline.ApplyGradeFromDRX`,
+//! keeping the file's keyframes aligned to the item's source timecode or its
+//! first frame, or dropping them. In Real mode the call goes to Resolve; the
+//! simulation records on each item which file was applied and how, since the
+//! grade inside the file is not read.
+
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::path::Path;
+use uuid::Uuid;
+
+use super::{ResolveBridge, StateView, SCRIPT_PROJECT};
+use crate::error::{ResolveError, ResolveResult};
+
+/// Grade modes of ApplyGradeFromDRX, by the value Resolve takes
+const GRADE_MODES: [&str; 3] = ["no_keyframes", "source_timecode", "start_frames"];
+
+/// A .drx grade applied to a timeline item
+#[derive(Debug, Clone, Serialize)]
+pub(super) struct DrxGrade {
+    path: String,
+    /// How the file's keyframes line up with the item, one of [`GRADE_MODES`]
+    grade_mode: &'static str,
+    applied_at: String,
+}
+
+/// The .drx path and grade mode of an apply_drx_file call
+fn drx_arguments(args: &Value) -> ResolveResult<(&str, usize)> {
+    let drx_path = args["drx_path"]
+        .as_str()
+        .ok_or_else(|| ResolveError::invalid_parameter("drx_path", "required string"))?;
+    let is_drx = Path::new(drx_path)
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("drx"));
+    if !is_drx {
+        return Err(ResolveError::invalid_parameter(
+            "drx_path",
+            "must be a .drx grade file",
+        ));
+    }
+    let grade_mode = args["grade_mode"].as_str().unwrap_or("no_keyframes");
+    let mode = GRADE_MODES
+        .iter()
+        .position(|mode| *mode == grade_mode)
+        .ok_or_else(|| {
+            ResolveError::invalid_parameter(
+                "grade_mode",
+                "must be 'no_keyframes', 'source_timecode' or 'start_frames'",
+            )
+        })?;
+    Ok((drx_path, mode))
+}
+
+/// Timeline item IDs of an apply_drx_file call; None applies to every item
+fn item_ids(args: &Value) -> ResolveResult<Option<Vec<String>>> {
+    args["timeline_item_ids"]
+        .as_array()
+        .map(|ids| {
+            ids.iter()
+                .map(|id| {
+                    id.as_str().map(str::to_string).ok_or_else(|| {
+                        ResolveError::invalid_parameter(
+                            "timeline_item_ids",
+                            "timeline item IDs must be strings",
+                        )
+                    })
+                })
+                .collect()
+        })
+        .transpose()
+}
+
+/// Applies the grade to the items `request` names on its timeline
+const APPLY_DRX: &str = r#"
+    timeline = project.GetCurrentTimeline()
+    if request["timeline_name"]:
+        timeline = None
+        for index in range(1, project.GetTimelineCount() + 1):
+            candidate = project.GetTimelineByIndex(index)
+            if candidate.GetName() == request["timeline_name"] or candidate.GetUniqueId() == request["timeline_name"]:
+                timeline = candidate
+                break
+    if not timeline:
+        fail("Timeline not found")
+
+    items = []
+    for index in range(1, timeline.GetTrackCount("video") + 1):
+        items.extend(timeline.GetItemListInTrack("video", index) or [])
+    wanted = request["timeline_item_ids"]
+    if wanted is not None:
+        items = [item for item in items if item.GetUniqueId() in wanted]
+        found = set(item.GetUniqueId() for item in items)
+        missing = [item_id for item_id in wanted if item_id not in found]
+        if missing:
+            fail("Timeline item not found: " + missing[0])
+    if not items:
+        fail("No timeline items to grade")
+
+    if not timeline.ApplyGradeFromDRX(request["drx_path"], request["grade_mode"], items):
+        fail("Resolve could not apply " + request["drx_path"])
+    print(json.dumps({
+        "success": True,
+        "timeline_name": timeline.GetName(),
+        "timeline_item_ids": [item.GetUniqueId() for item in items]
+    }))
+"#;
+
+impl ResolveBridge {
+    pub(super) async fn apply_drx_file(
+        &self,
+        state: &mut StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        let (drx_path, mode) = drx_arguments(&args)?;
+        let timeline_name = Self::resolve_timeline_name(state, &args)?;
+        let on_timeline: Vec<String> = state
+            .timeline_items
+            .items
+            .values()
+            .filter(|item| item.timeline_name == timeline_name && item.generator.is_none())
+            .map(|item| item.id.clone())
+            .collect();
+        let mut ids = match item_ids(&args)? {
+            Some(ids) => {
+                if let Some(missing) = ids.iter().find(|id| !on_timeline.contains(id)) {
+                    return Err(ResolveError::invalid_parameter(
+                        "timeline_item_ids",
+                        format!(
+                            "timeline item '{}' is not on timeline '{}'",
+                            missing, timeline_name
+                        ),
+                    ));
+                }
+                ids
+            }
+            None => on_timeline,
+        };
+        ids.sort();
+        ids.dedup();
+        if ids.is_empty() {
+            return Err(ResolveError::invalid_parameter(
+                "timeline_item_ids",
+                format!("timeline '{}' has no items to grade", timeline_name),
+            ));
+        }
+
+        let grade = DrxGrade {
+            path: drx_path.to_string(),
+            grade_mode: GRADE_MODES[mode],
+            applied_at: chrono::Utc::now().to_rfc3339(),
+        };
+        for id in &ids {
+            if let Some(item) = state.timeline_items.items.get_mut(id) {
+                item.drx_grade = Some(grade.clone());
+            }
+        }
+
+        Ok(json!({
+            "result": format!(
+                "Applied the grade from '{}' to {} items on timeline '{}'",
+                drx_path,
+                ids.len(),
+                timeline_name
+            ),
+            "drx_path": drx_path,
+            "grade_mode": grade.grade_mode,
+            "timeline_name": timeline_name,
+            "timeline_item_ids": ids,
+            "file_found": Path::new(drx_path).is_file(),
+            "operation_id": Uuid::new_v4().to_string()
+        }))
+    }
+
+    /// Apply a .drx grade through Resolve's ApplyGradeFromDRX
+    pub(super) fn real_apply_drx_file(&self, args: &Value) -> ResolveResult<Value> {
+        let (drx_path, mode) = drx_arguments(args)?;
+        // Resolve reports a missing file only as a failed apply
+        if !Path::new(drx_path).is_file() {
+            return Err(ResolveError::invalid_parameter(
+                "drx_path",
+                format!("no file at '{}'", drx_path),
+            ));
+        }
+        let request = json!({
+            "drx_path": drx_path,
+            "grade_mode": mode,
+            "timeline_name": args["timeline_name"],
+            "timeline_item_ids": item_ids(args)?
+        });
+        let script = Self::resolve_script("", SCRIPT_PROJECT, &request, APPLY_DRX)?;
+        let output = self.run_resolve_script("apply_drx_file", &script)?;
+        let count = output["timeline_item_ids"].as_array().map_or(0, Vec::len);
+
+        Ok(json!({
+            "result": format!(
+                "Applied the grade from '{}' to {} items on timeline '{}'",
+                drx_path, count, output["timeline_name"].as_str().unwrap_or_default()
+            ),
+            "drx_path": drx_path,
+            "grade_mode": GRADE_MODES[mode],
+            "timeline_name": output["timeline_name"],
+            "timeline_item_ids": output["timeline_item_ids"],
+            "file_found": true,
+            "operation_id": Uuid::new_v4().to_string()
+        }))
+    }
+}
@@ -0,0 +1,311 @@
+//! Embedded start timecode and reel names
+//!
+//! The start timecode and reel name are read from container or stream tags
+//! with ffprobe and stored on the clip, where timecode relinking and timecode
+//! sync use them. Offline media, or any media when ffprobe is missing, gets a
+//! simulated timecode derived from the clip name so results are repeatable.
+
+use serde_json::{json, Value};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+use walkdir::WalkDir;
+
+use super::ffmpeg;
+use super::media_storage::is_media_file;
+use super::{ResolveBridge, StateView};
+use crate::error::{ResolveError, ResolveResult};
+use crate::timecode::FrameRate;
+
+/// Longest simulated reel name, as on an 8-character tape label
+const REEL_NAME_LENGTH: usize = 8;
+
+/// Timecode and reel name read from one file, and where they came from
+pub(super) struct EmbeddedTimecode {
+    pub(super) start_timecode: Option<String>,
+    pub(super) reel_name: Option<String>,
+    pub(super) source: String,
+}
+
+/// Stable stand-in timecode within the first hour after 01:00:00:00
+fn simulated_timecode(clip_name: &str, rate: FrameRate) -> EmbeddedTimecode {
+    let mut hasher = DefaultHasher::new();
+    clip_name.hash(&mut hasher);
+    let seconds = (hasher.finish() % 3600) as i64;
+    let stem = Path::new(clip_name)
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let reel: String = stem
+        .chars()
+        .filter(char::is_ascii_alphanumeric)
+        .take(REEL_NAME_LENGTH)
+        .collect::<String>()
+        .to_ascii_uppercase();
+    EmbeddedTimecode {
+        start_timecode: Some(rate.frames_to_timecode((3600 + seconds) * rate.nominal())),
+        reel_name: (!reel.is_empty()).then_some(reel),
+        source: "simulated".to_string(),
+    }
+}
+
+/// Frame count of a timecode at the project rate, honoring `;` drop-frame notation
+pub(super) fn timecode_frames(timecode: &str, rate: FrameRate) -> Result<i64, String> {
+    rate.for_timecode(timecode)?.timecode_to_frames(timecode)
+}
+
+impl ResolveBridge {
+    /// Read a file's embedded timecode, falling back to simulation when it cannot be probed
+    pub(super) fn probe_embedded(
+        &self,
+        file_path: &str,
+        clip_name: &str,
+        rate: FrameRate,
+    ) -> EmbeddedTimecode {
+        let media = Path::new(file_path);
+        if !media.is_file() || !ffmpeg::probe_available() {
+            return simulated_timecode(clip_name, rate);
+        }
+        match ffmpeg::probe_timecode(media) {
+            Ok((Some(timecode), reel_name)) => match timecode_frames(&timecode, rate) {
+                Ok(_) => EmbeddedTimecode {
+                    start_timecode: Some(timecode),
+                    reel_name,
+                    source: "probed".to_string(),
+                },
+                Err(e) => EmbeddedTimecode {
+                    start_timecode: None,
+                    reel_name,
+                    source: format!("invalid timecode '{}': {}", timecode, e),
+                },
+            },
+            Ok((None, reel_name)) => EmbeddedTimecode {
+                start_timecode: None,
+                reel_name,
+                source: "no_timecode".to_string(),
+            },
+            Err(e) => EmbeddedTimecode {
+                start_timecode: None,
+                reel_name: None,
+                source: format!("failed: {}", e),
+            },
+        }
+    }
+
+    pub(super) async fn read_embedded_timecode(
+        &self,
+        state: &mut StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        // Every clip in the media pool unless names are given
+        let clip_names: Vec<String> = match args["clip_names"].as_array() {
+            Some(names) => names
+                .iter()
+                .map(|name| {
+                    let name = name.as_str().ok_or_else(|| {
+                        ResolveError::invalid_parameter("clip_names", "clip names must be strings")
+                    })?;
+                    Self::resolve_clip(state, name)
+                })
+                .collect::<ResolveResult<_>>()?,
+            None => {
+                let mut names: Vec<String> = state.media_pool.clips.keys().cloned().collect();
+                names.sort();
+                names
+            }
+        };
+        let rate = self.project_frame_rate()?;
+        let mut clips = Vec::with_capacity(clip_names.len());
+        let mut found = 0;
+        for name in &clip_names {
+            let Some(clip) = state.media_pool.clips.get_mut(name) else {
+                continue;
+            };
+            let embedded = self.probe_embedded(&clip.file_path, name, rate);
+            if embedded.start_timecode.is_some() {
+                found += 1;
+                clip.start_timecode = embedded.start_timecode.clone();
+                clip.reel_name = embedded.reel_name.clone();
+            }
+            clips.push(json!({
+                "clip_name": name,
+                "start_timecode": embedded.start_timecode,
+                "reel_name": embedded.reel_name,
+                "source": embedded.source
+            }));
+        }
+
+        Ok(json!({
+            "result": format!("Read embedded timecode for {} of {} clips", found, clip_names.len()),
+            "clips": clips,
+            "found": found,
+            "operation_id": Uuid::new_v4().to_string()
+        }))
+    }
+
+    /// Relink clips to files in a folder matched by file name or by embedded timecode and reel
+    pub(super) fn relink_from_folder(
+        &self,
+        state: &mut StateView<'_>,
+        clip_names: &[String],
+        folder: &str,
+        recursive: bool,
+        match_by: &str,
+    ) -> ResolveResult<Value> {
+        let by_timecode = match match_by {
+            "name" => false,
+            "timecode" => true,
+            _ => {
+                return Err(ResolveError::invalid_parameter(
+                    "match_by",
+                    "must be 'name' or 'timecode'",
+                ))
+            }
+        };
+        let folder_path = self.resolve_storage_path(folder)?;
+        if !folder_path.is_dir() {
+            return Err(ResolveError::invalid_parameter(
+                "folder_path",
+                format!("'{}' is not a folder", folder),
+            ));
+        }
+        let clip_names = clip_names
+            .iter()
+            .map(|name| Self::resolve_clip(state, name))
+            .collect::<ResolveResult<Vec<_>>>()?;
+
+        let candidates: Vec<PathBuf> = WalkDir::new(&folder_path)
+            .max_depth(if recursive { usize::MAX } else { 1 })
+            .sort_by_file_name()
+            .into_iter()
+            .flatten()
+            .filter(|entry| entry.file_type().is_file() && is_media_file(entry.path()))
+            .map(walkdir::DirEntry::into_path)
+            .collect();
+        // Candidates without readable timecode can never match by timecode
+        let rate = self.project_frame_rate()?;
+        let probed: Vec<(&PathBuf, i64, Option<String>)> =
+            if by_timecode && ffmpeg::probe_available() {
+                candidates
+                    .iter()
+                    .filter_map(|path| {
+                        let (timecode, reel) = ffmpeg::probe_timecode(path).ok()?;
+                        let frames = timecode_frames(&timecode?, rate).ok()?;
+                        Some((path, frames, reel))
+                    })
+                    .collect()
+            } else {
+                Vec::new()
+            };
+
+        let mut relinked = Vec::new();
+        let mut unmatched = Vec::new();
+        for name in &clip_names {
+            let Some(clip) = state.media_pool.clips.get_mut(name) else {
+                continue;
+            };
+            let found = if by_timecode {
+                let Some(start) = clip
+                    .start_timecode
+                    .as_deref()
+                    .and_then(|timecode| timecode_frames(timecode, rate).ok())
+                else {
+                    unmatched.push(json!({
+                        "clip_name": name,
+                        "reason": "clip has no embedded timecode; run read_embedded_timecode first"
+                    }));
+                    continue;
+                };
+                probed
+                    .iter()
+                    .find(|(_, frames, reel)| {
+                        *frames == start && (clip.reel_name.is_none() || *reel == clip.reel_name)
+                    })
+                    .map(|(path, _, _)| *path)
+            } else {
+                let file_name = Path::new(&clip.file_path).file_name();
+                candidates
+                    .iter()
+                    .find(|path| file_name.is_some() && path.file_name() == file_name)
+            };
+            match found {
+                Some(path) => {
+                    clip.file_path = path.display().to_string();
+                    clip.linked = true;
+                    relinked.push(json!({"clip_name": name, "file_path": clip.file_path}));
+                }
+                None => unmatched.push(json!({
+                    "clip_name": name,
+                    "reason": if by_timecode {
+                        "no file with matching timecode and reel"
+                    } else {
+                        "no file with matching name"
+                    }
+                })),
+            }
+        }
+
+        Ok(json!({
+            "result": format!(
+                "Relinked {} of {} clips by {} from '{}'",
+                relinked.len(),
+                clip_names.len(),
+                match_by,
+                folder_path.display()
+            ),
+            "relinked": relinked,
+            "unmatched": unmatched,
+            "candidates_scanned": candidates.len(),
+            "operation_id": Uuid::new_v4().to_string()
+        }))
+    }
+
+    /// Offsets that line clips up by their embedded start timecode
+    pub(super) fn sync_by_timecode(
+        &self,
+        state: &StateView<'_>,
+        clip_names: &[String],
+    ) -> ResolveResult<Value> {
+        let rate = self.project_frame_rate()?;
+        let mut starts = Vec::new();
+        let mut missing = Vec::new();
+        for reference in clip_names {
+            let name = Self::resolve_clip(state, reference)?;
+            let clip = &state.media_pool.clips[&name];
+            match clip
+                .start_timecode
+                .as_deref()
+                .and_then(|timecode| Some((timecode, timecode_frames(timecode, rate).ok()?)))
+            {
+                Some((timecode, frames)) => starts.push((name, timecode, frames)),
+                None => missing.push(name),
+            }
+        }
+        let Some(earliest) = starts.iter().map(|(_, _, frames)| *frames).min() else {
+            return Err(ResolveError::invalid_parameter(
+                "clip_names",
+                "no clip has an embedded timecode; run read_embedded_timecode first",
+            ));
+        };
+
+        // Offsets are from the clip that starts first
+        let synced: Vec<Value> = starts
+            .iter()
+            .map(|(name, timecode, frames)| {
+                json!({
+                    "clip_name": name,
+                    "start_timecode": timecode,
+                    "offset_frames": frames - earliest
+                })
+            })
+            .collect();
+        Ok(json!({
+            "result": format!("Synchronized {} clips using timecode method", synced.len()),
+            "sync_id": Uuid::new_v4().to_string(),
+            "frame_rate": rate.to_string(),
+            "clips": synced,
+            "missing_timecode": missing
+        }))
+    }
+}
@@ -0,0 +1,86 @@
+//! Stable IDs for clips, bins, timelines and markers
+//!
+//! Every entity gets a UUID when it is created, which survives renames and is
+//! returned by the tools that create or list it. Lookups take either the ID or
+//! the name; addressing by name still works but is deprecated, which is logged
+//! once per entity kind.
+
+use std::sync::Once;
+
+use super::{ResolveBridge, StateView};
+use crate::error::{ResolveError, ResolveResult};
+
+static CLIP_NAME_LOOKUP: Once = Once::new();
+static BIN_NAME_LOOKUP: Once = Once::new();
+static TIMELINE_NAME_LOOKUP: Once = Once::new();
+
+fn deprecated_name_lookup(once: &Once, kind: &str, name: &str) {
+    once.call_once(|| {
+        tracing::warn!(
+            "Addressing a {} by name ('{}') is deprecated; pass its ID instead",
+            kind,
+            name
+        );
+    });
+}
+
+impl ResolveBridge {
+    /// Name of the media pool clip with this ID or name
+    pub(super) fn resolve_clip(state: &StateView<'_>, reference: &str) -> ResolveResult<String> {
+        if let Some(clip) = state
+            .media_pool
+            .clips
+            .values()
+            .find(|clip| clip.id == reference)
+        {
+            return Ok(clip.name.clone());
+        }
+        if state.media_pool.clips.contains_key(reference) {
+            deprecated_name_lookup(&CLIP_NAME_LOOKUP, "clip", reference);
+            return Ok(reference.to_string());
+        }
+        Err(ResolveError::MediaNotFound {
+            name: reference.to_string(),
+        })
+    }
+
+    /// Name of the bin with this ID or name
+    pub(super) fn resolve_bin(state: &StateView<'_>, reference: &str) -> ResolveResult<String> {
+        if let Some(bin) = state
+            .media_pool
+            .bins
+            .values()
+            .find(|bin| bin.id == reference)
+        {
+            return Ok(bin.name.clone());
+        }
+        if state.media_pool.bins.contains_key(reference) {
+            deprecated_name_lookup(&BIN_NAME_LOOKUP, "bin", reference);
+            return Ok(reference.to_string());
+        }
+        Err(ResolveError::BinNotFound {
+            name: reference.to_string(),
+        })
+    }
+
+    /// Key of the timeline with this ID or name
+    pub(super) fn resolve_timeline(
+        state: &StateView<'_>,
+        reference: &str,
+    ) -> ResolveResult<String> {
+        if let Some((key, _)) = state
+            .timelines
+            .iter()
+            .find(|(_, timeline)| timeline.id == reference)
+        {
+            return Ok(key.clone());
+        }
+        if state.timelines.contains_key(reference) {
+            deprecated_name_lookup(&TIMELINE_NAME_LOOKUP, "timeline", reference);
+            return Ok(reference.to_string());
+        }
+        Err(ResolveError::TimelineNotFound {
+            name: reference.to_string(),
+        })
+    }
+}
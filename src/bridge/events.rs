@@ -0,0 +1,156 @@
+//! Webhook notifications for server events
+//!
+//! Events are posted as JSON to the endpoints in the `[webhooks]` config that
+//! subscribe to them. Each delivery runs in the background and is retried with
+//! exponential backoff until it succeeds or runs out of attempts. When an
+//! endpoint has a secret, the body is signed with HMAC-SHA256 and the
+//! signature sent as `X-DaVinci-Signature: sha256=<hex>`. The secret is read
+//! for each event, so a rotated keychain entry or file takes effect at once.
+
+use hmac::{Hmac, Mac};
+use serde_json::{json, Value};
+use sha2::Sha256;
+use std::process::Command;
+use std::sync::Arc;
+use std::time::Duration;
+use uuid::Uuid;
+
+use super::render_hooks::run_with_timeout;
+use crate::config::{Config, WebhookEndpoint};
+
+pub(super) const RENDER_COMPLETED: &str = "render.completed";
+const TRANSCRIPTION_COMPLETED: &str = "transcription.completed";
+const PROJECT_SAVED: &str = "project.saved";
+
+/// Event a successful API call announces, if any
+pub(super) fn event_for_method(method: &str) -> Option<&'static str> {
+    match method {
+        "save_project" => Some(PROJECT_SAVED),
+        "transcribe_audio" | "transcribe_folder_audio" | "transcribe_media_pool_item_audio" => {
+            Some(TRANSCRIPTION_COMPLETED)
+        }
+        _ => None,
+    }
+}
+
+/// Post an event to every endpoint subscribed to it, in the background
+pub(super) fn publish(config: &Arc<Config>, event: &str, data: Value) {
+    let endpoints: Vec<WebhookEndpoint> = config
+        .webhooks
+        .endpoints
+        .iter()
+        .filter(|endpoint| {
+            endpoint.events.is_empty() || endpoint.events.iter().any(|name| name == event)
+        })
+        .cloned()
+        .collect();
+    if endpoints.is_empty() {
+        return;
+    }
+
+    let delivery_id = Uuid::new_v4().to_string();
+    let body = json!({
+        "id": delivery_id,
+        "event": event,
+        "created_at": chrono::Utc::now().to_rfc3339(),
+        "data": data
+    })
+    .to_string();
+    for endpoint in endpoints {
+        let config = Arc::clone(config);
+        let body = body.clone();
+        let event = event.to_string();
+        let delivery_id = delivery_id.clone();
+        tokio::spawn(async move {
+            let settings = &config.webhooks;
+            let mut headers = vec![
+                format!("X-DaVinci-Event: {}", event),
+                format!("X-DaVinci-Delivery: {}", delivery_id),
+            ];
+            if let Some(secret) = &endpoint.secret {
+                // Unsigned deliveries would be rejected, so none is made
+                let secret = match secret.reveal() {
+                    Ok(secret) => secret,
+                    Err(error) => {
+                        tracing::warn!(
+                            "Not delivering {} event to {}: webhook secret cannot be read: {}",
+                            event,
+                            endpoint.url,
+                            error
+                        );
+                        return;
+                    }
+                };
+                headers.push(format!(
+                    "X-DaVinci-Signature: sha256={}",
+                    sign(&secret, &body)
+                ));
+            }
+            let timeout = Duration::from_secs(settings.timeout_seconds);
+
+            for attempt in 1..=settings.max_attempts {
+                let (url, body, headers) = (endpoint.url.clone(), body.clone(), headers.clone());
+                let outcome =
+                    tokio::task::spawn_blocking(move || post_json(&url, &body, &headers, timeout))
+                        .await
+                        .unwrap_or_else(|e| Err(e.to_string()));
+                let Err(error) = outcome else {
+                    tracing::debug!("Delivered {} event to {}", event, endpoint.url);
+                    return;
+                };
+                if attempt == settings.max_attempts {
+                    tracing::warn!(
+                        "Giving up on {} event for {} after {} attempts: {}",
+                        event,
+                        endpoint.url,
+                        attempt,
+                        error
+                    );
+                    return;
+                }
+                let backoff = settings
+                    .initial_backoff_ms
+                    .saturating_mul(1 << (attempt - 1).min(16));
+                tracing::debug!(
+                    "Delivering {} event to {} failed ({}); retrying in {} ms",
+                    event,
+                    endpoint.url,
+                    error,
+                    backoff
+                );
+                tokio::time::sleep(Duration::from_millis(backoff)).await;
+            }
+        });
+    }
+}
+
+/// Hex HMAC-SHA256 of `body` keyed with `secret`
+fn sign(secret: &str, body: &str) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(body.as_bytes());
+    mac.finalize()
+        .into_bytes()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+/// POST a JSON body with curl; non-2xx responses are errors
+pub(super) fn post_json(
+    url: &str,
+    body: &str,
+    headers: &[String],
+    timeout: Duration,
+) -> Result<(), String> {
+    let mut curl = Command::new("curl");
+    curl.args(["-sS", "--fail", "-X", "POST"])
+        .args(["-H", "Content-Type: application/json"]);
+    for header in headers {
+        curl.arg("-H").arg(header);
+    }
+    curl.args(["--data-binary", "@-", "--max-time"])
+        .arg(timeout.as_secs().to_string())
+        .arg(url);
+    run_with_timeout(curl, Some(body.as_bytes()), timeout)
+}
@@ -0,0 +1,382 @@
+//! Helpers for running the ffmpeg command-line tool
+//!
+//! ffmpeg is optional; callers check `available()` and fall back to simulated
+//! results when it is missing.
+
+use std::path::Path;
+use std::process::Command;
+
+use crate::timecode::FrameRate;
+
+/// Sample rate audio is decoded at for peak analysis
+const PEAK_SAMPLE_RATE: u32 = 8_000;
+
+/// Whether an `ffmpeg` binary can be run
+pub(super) fn available() -> bool {
+    Command::new("ffmpeg")
+        .arg("-version")
+        .output()
+        .is_ok_and(|output| output.status.success())
+}
+
+/// Stream-copy `duration` frames starting at `start` from `source` into `output`
+pub(super) fn trim(
+    source: &str,
+    output: &Path,
+    rate: FrameRate,
+    start: i64,
+    duration: i64,
+) -> Result<(), String> {
+    let result = Command::new("ffmpeg")
+        .args(["-v", "error", "-n", "-ss"])
+        .arg(format!("{:.3}", start as f64 / rate.fps()))
+        .arg("-i")
+        .arg(source)
+        .arg("-t")
+        .arg(format!("{:.3}", duration as f64 / rate.fps()))
+        .args(["-c", "copy", "-map", "0"])
+        .arg(output)
+        .output()
+        .map_err(|e| e.to_string())?;
+    if result.status.success() {
+        Ok(())
+    } else {
+        Err(String::from_utf8_lossy(&result.stderr).trim().to_string())
+    }
+}
+
+/// Stream-copy `source` into `output` with the global metadata and chapters
+/// of the FFMETADATA file `metadata`
+pub(super) fn embed_metadata(source: &Path, metadata: &Path, output: &Path) -> Result<(), String> {
+    let result = Command::new("ffmpeg")
+        .args(["-v", "error", "-y", "-i"])
+        .arg(source)
+        .arg("-i")
+        .arg(metadata)
+        .args([
+            "-map",
+            "0",
+            "-map_metadata",
+            "1",
+            "-map_chapters",
+            "1",
+            "-c",
+            "copy",
+        ])
+        .arg(output)
+        .output()
+        .map_err(|e| e.to_string())?;
+    if result.status.success() {
+        Ok(())
+    } else {
+        Err(String::from_utf8_lossy(&result.stderr).trim().to_string())
+    }
+}
+
+/// Decode a file's audio to mono and return (min, max) sample peaks for
+/// each of `blocks_per_second` blocks per second, in the range -1.0 to 1.0
+pub(super) fn audio_peaks(
+    source: &Path,
+    blocks_per_second: u32,
+) -> Result<Vec<(f32, f32)>, String> {
+    let result = Command::new("ffmpeg")
+        .args(["-v", "error", "-i"])
+        .arg(source)
+        .args(["-vn", "-ac", "1", "-ar"])
+        .arg(PEAK_SAMPLE_RATE.to_string())
+        .args(["-f", "s16le", "-"])
+        .output()
+        .map_err(|e| e.to_string())?;
+    if !result.status.success() {
+        return Err(String::from_utf8_lossy(&result.stderr).trim().to_string());
+    }
+
+    let samples_per_block = (PEAK_SAMPLE_RATE / blocks_per_second.max(1)).max(1) as usize;
+    let peaks = result
+        .stdout
+        .chunks_exact(2)
+        .map(|bytes| f32::from(i16::from_le_bytes([bytes[0], bytes[1]])) / 32_768.0)
+        .collect::<Vec<f32>>()
+        .chunks(samples_per_block)
+        .map(|block| {
+            block.iter().fold((0.0f32, 0.0f32), |(min, max), &sample| {
+                (min.min(sample), max.max(sample))
+            })
+        })
+        .collect();
+    Ok(peaks)
+}
+
+/// Decode the frame at `seconds` into `width` x `height` packed RGB24 pixels
+pub(super) fn rgb_frame(
+    source: &Path,
+    seconds: f64,
+    width: u32,
+    height: u32,
+) -> Result<Vec<u8>, String> {
+    let result = Command::new("ffmpeg")
+        .args(["-v", "error", "-ss"])
+        .arg(format!("{:.3}", seconds))
+        .arg("-i")
+        .arg(source)
+        .args(["-frames:v", "1", "-vf"])
+        .arg(format!("scale={}:{}", width, height))
+        .args(["-pix_fmt", "rgb24", "-f", "rawvideo", "-"])
+        .output()
+        .map_err(|e| e.to_string())?;
+    if !result.status.success() {
+        return Err(String::from_utf8_lossy(&result.stderr).trim().to_string());
+    }
+    let expected = (width * height * 3) as usize;
+    if result.stdout.len() != expected {
+        return Err(format!(
+            "expected {} bytes of frame data, got {}",
+            expected,
+            result.stdout.len()
+        ));
+    }
+    Ok(result.stdout)
+}
+
+/// Encode the frame at `seconds` as a JPEG `width` pixels wide
+pub(super) fn jpeg_frame(source: &Path, seconds: f64, width: u32) -> Result<Vec<u8>, String> {
+    let result = Command::new("ffmpeg")
+        .args(["-v", "error", "-ss"])
+        .arg(format!("{:.3}", seconds))
+        .arg("-i")
+        .arg(source)
+        .args(["-frames:v", "1", "-vf"])
+        .arg(format!("scale={}:-2", width))
+        .args(["-c:v", "mjpeg", "-q:v", "3", "-f", "image2pipe", "-"])
+        .output()
+        .map_err(|e| e.to_string())?;
+    if !result.status.success() {
+        return Err(String::from_utf8_lossy(&result.stderr).trim().to_string());
+    }
+    if result.stdout.is_empty() {
+        return Err("no frame at that time".to_string());
+    }
+    Ok(result.stdout)
+}
+
+/// Decode all of a file's video at `fps` into `width` x `height` 8-bit luma frames
+pub(super) fn gray_frames(
+    source: &Path,
+    fps: f64,
+    width: u32,
+    height: u32,
+) -> Result<Vec<Vec<u8>>, String> {
+    let result = Command::new("ffmpeg")
+        .args(["-v", "error", "-i"])
+        .arg(source)
+        .args(["-an", "-vf"])
+        .arg(format!("fps={},scale={}:{}", fps, width, height))
+        .args(["-pix_fmt", "gray", "-f", "rawvideo", "-"])
+        .output()
+        .map_err(|e| e.to_string())?;
+    if !result.status.success() {
+        return Err(String::from_utf8_lossy(&result.stderr).trim().to_string());
+    }
+    Ok(result
+        .stdout
+        .chunks_exact((width * height) as usize)
+        .map(<[u8]>::to_vec)
+        .collect())
+}
+
+/// Whether an `ffprobe` binary can be run
+pub(super) fn probe_available() -> bool {
+    Command::new("ffprobe")
+        .arg("-version")
+        .output()
+        .is_ok_and(|output| output.status.success())
+}
+
+/// Start timecode and reel name from a file's container or stream tags
+pub(super) fn probe_timecode(source: &Path) -> Result<(Option<String>, Option<String>), String> {
+    let result = Command::new("ffprobe")
+        .args(["-v", "error", "-show_entries"])
+        .arg("format_tags:stream_tags")
+        .args(["-of", "json"])
+        .arg(source)
+        .output()
+        .map_err(|e| e.to_string())?;
+    if !result.status.success() {
+        return Err(String::from_utf8_lossy(&result.stderr).trim().to_string());
+    }
+    let probed: serde_json::Value =
+        serde_json::from_slice(&result.stdout).map_err(|e| e.to_string())?;
+
+    // Container tags win over stream tags; key case varies between formats
+    let mut tag_sets = vec![&probed["format"]["tags"]];
+    if let Some(streams) = probed["streams"].as_array() {
+        tag_sets.extend(streams.iter().map(|stream| &stream["tags"]));
+    }
+    let tag = |keys: &[&str]| {
+        tag_sets.iter().find_map(|tags| {
+            tags.as_object()?.iter().find_map(|(key, value)| {
+                keys.iter()
+                    .any(|wanted| key.eq_ignore_ascii_case(wanted))
+                    .then(|| value.as_str())
+                    .flatten()
+                    .map(|value| value.trim().to_string())
+                    .filter(|value| !value.is_empty())
+            })
+        })
+    };
+    Ok((
+        tag(&["timecode"]),
+        tag(&["reel_name", "reel", "com.apple.quicktime.reel", "tape_name"]),
+    ))
+}
+
+/// Frame rate of a file's first video stream, such as `23.976`
+pub(super) fn probe_frame_rate(source: &Path) -> Result<Option<String>, String> {
+    let result = Command::new("ffprobe")
+        .args(["-v", "error", "-select_streams", "v:0", "-show_entries"])
+        .arg("stream=r_frame_rate")
+        .args(["-of", "json"])
+        .arg(source)
+        .output()
+        .map_err(|e| e.to_string())?;
+    if !result.status.success() {
+        return Err(String::from_utf8_lossy(&result.stderr).trim().to_string());
+    }
+    let probed: serde_json::Value =
+        serde_json::from_slice(&result.stdout).map_err(|e| e.to_string())?;
+
+    // Reported as a fraction, 24000/1001 for 23.976
+    let Some(rate) = probed["streams"][0]["r_frame_rate"].as_str() else {
+        return Ok(None);
+    };
+    let (numerator, denominator) = rate.split_once('/').unwrap_or((rate, "1"));
+    let fps = match (numerator.parse::<f64>(), denominator.parse::<f64>()) {
+        (Ok(numerator), Ok(denominator)) if numerator > 0.0 && denominator > 0.0 => {
+            numerator / denominator
+        }
+        _ => return Ok(None),
+    };
+    let rounded = format!("{:.3}", fps);
+    Ok(Some(
+        rounded
+            .trim_end_matches('0')
+            .trim_end_matches('.')
+            .to_string(),
+    ))
+}
+
+/// Length of a file in seconds, from its container
+pub(super) fn probe_duration(source: &Path) -> Result<Option<f64>, String> {
+    let result = Command::new("ffprobe")
+        .args(["-v", "error", "-show_entries"])
+        .arg("format=duration")
+        .args(["-of", "json"])
+        .arg(source)
+        .output()
+        .map_err(|e| e.to_string())?;
+    if !result.status.success() {
+        return Err(String::from_utf8_lossy(&result.stderr).trim().to_string());
+    }
+    let probed: serde_json::Value =
+        serde_json::from_slice(&result.stdout).map_err(|e| e.to_string())?;
+    Ok(probed["format"]["duration"]
+        .as_str()
+        .and_then(|duration| duration.parse::<f64>().ok())
+        .filter(|seconds| *seconds > 0.0))
+}
+
+/// Render `seconds` of `source` from `start`, or black where there is no
+/// source, fitted into `width` x `height` at `fps` as an intermediate MP4.
+/// A `still` source is held for the whole piece.
+pub(super) fn preview_piece(
+    source: Option<&Path>,
+    still: bool,
+    start: f64,
+    seconds: f64,
+    (width, height): (u32, u32),
+    fps: f64,
+    output: &Path,
+) -> Result<(), String> {
+    let mut command = Command::new("ffmpeg");
+    command.args(["-v", "error", "-y"]);
+    match source {
+        Some(source) if still => {
+            command.args(["-loop", "1", "-i"]).arg(source);
+        }
+        Some(source) => {
+            command
+                .arg("-ss")
+                .arg(format!("{:.3}", start))
+                .arg("-i")
+                .arg(source);
+        }
+        None => {
+            command
+                .args(["-f", "lavfi", "-i"])
+                .arg(format!("color=c=black:s={}x{}:r={}", width, height, fps));
+        }
+    }
+    let result = command
+        .arg("-t")
+        .arg(format!("{:.3}", seconds))
+        .arg("-vf")
+        .arg(format!(
+            "scale={w}:{h}:force_original_aspect_ratio=decrease,\
+             pad={w}:{h}:(ow-iw)/2:(oh-ih)/2,fps={fps},format=yuv420p",
+            w = width,
+            h = height,
+            fps = fps
+        ))
+        .args([
+            "-an",
+            "-c:v",
+            "libx264",
+            "-preset",
+            "ultrafast",
+            "-crf",
+            "18",
+        ])
+        .arg(output)
+        .output()
+        .map_err(|e| e.to_string())?;
+    if result.status.success() {
+        Ok(())
+    } else {
+        Err(String::from_utf8_lossy(&result.stderr).trim().to_string())
+    }
+}
+
+/// Join intermediate pieces, listed in the concat file `list`, into a
+/// looping GIF or a web-friendly MP4
+pub(super) fn join_preview(list: &Path, gif: bool, output: &Path) -> Result<(), String> {
+    let mut command = Command::new("ffmpeg");
+    command
+        .args(["-v", "error", "-y", "-f", "concat", "-safe", "0", "-i"])
+        .arg(list);
+    if gif {
+        // A palette made from the preview itself keeps GIF banding down
+        command
+            .arg("-vf")
+            .arg("split[a][b];[a]palettegen=stats_mode=diff[p];[b][p]paletteuse=dither=bayer")
+            .args(["-loop", "0"]);
+    } else {
+        command.args([
+            "-c:v",
+            "libx264",
+            "-preset",
+            "veryfast",
+            "-crf",
+            "28",
+            "-pix_fmt",
+            "yuv420p",
+            "-movflags",
+            "+faststart",
+        ]);
+    }
+    let result = command.arg(output).output().map_err(|e| e.to_string())?;
+    if result.status.success() {
+        Ok(())
+    } else {
+        Err(String::from_utf8_lossy(&result.stderr).trim().to_string())
+    }
+}
@@ -0,0 +1,146 @@
+//! Recorded Resolve API responses for simulation fixtures
+//!
+//! In record mode every successful real-mode call is appended to the fixture
+//! file as a JSON line holding the method, arguments and result. In playback
+//! mode the file is loaded once and calls with a recorded method and
+//! arguments are answered from it instead of being simulated, which pins
+//! simulation to the behaviour of the Resolve version that was recorded.
+//! Repeated calls replay their recordings in order and then keep returning
+//! the last one. Played back calls do not change the simulated state.
+
+use serde_json::{json, Value};
+use std::collections::{HashMap, VecDeque};
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+use crate::config::{FixtureMode, FixturesConfig};
+
+#[derive(Debug)]
+pub(super) struct FixtureStore {
+    mode: FixtureMode,
+    path: Option<PathBuf>,
+    /// Recorded results by call key, for playback
+    replies: Mutex<HashMap<String, VecDeque<Value>>>,
+    /// Serializes appends to the fixture file
+    writer: Mutex<()>,
+    recorded: AtomicU64,
+    replayed: AtomicU64,
+}
+
+/// Identifies a call; argument objects serialize with sorted keys
+fn call_key(method: &str, args: &Value) -> String {
+    format!("{}\n{}", method, args)
+}
+
+impl FixtureStore {
+    pub(super) fn new(config: &FixturesConfig) -> Self {
+        let mut replies: HashMap<String, VecDeque<Value>> = HashMap::new();
+        if let (FixtureMode::Playback, Some(path)) = (config.mode, &config.path) {
+            match std::fs::read_to_string(path) {
+                Ok(contents) => {
+                    for (number, line) in contents.lines().enumerate() {
+                        if line.trim().is_empty() {
+                            continue;
+                        }
+                        let entry: Value = match serde_json::from_str(line) {
+                            Ok(entry) => entry,
+                            Err(e) => {
+                                tracing::warn!(
+                                    "Skipping fixture {}:{}: {}",
+                                    path.display(),
+                                    number + 1,
+                                    e
+                                );
+                                continue;
+                            }
+                        };
+                        let Some(method) = entry["method"].as_str() else {
+                            tracing::warn!(
+                                "Skipping fixture {}:{}: no method",
+                                path.display(),
+                                number + 1
+                            );
+                            continue;
+                        };
+                        replies
+                            .entry(call_key(method, &entry["args"]))
+                            .or_default()
+                            .push_back(entry["result"].clone());
+                    }
+                    tracing::info!(
+                        "Loaded fixtures for {} calls from {}",
+                        replies.len(),
+                        path.display()
+                    );
+                }
+                Err(e) => tracing::warn!("Failed to read fixtures {}: {}", path.display(), e),
+            }
+        }
+        Self {
+            mode: config.mode,
+            path: config.path.clone(),
+            replies: Mutex::new(replies),
+            writer: Mutex::new(()),
+            recorded: AtomicU64::new(0),
+            replayed: AtomicU64::new(0),
+        }
+    }
+
+    /// The recorded result for a call, when playing back
+    pub(super) fn playback(&self, method: &str, args: &Value) -> Option<Value> {
+        if self.mode != FixtureMode::Playback {
+            return None;
+        }
+        let mut replies = self.replies.lock().expect("fixture replies poisoned");
+        let queue = replies.get_mut(&call_key(method, args))?;
+        let result = if queue.len() > 1 {
+            queue.pop_front()
+        } else {
+            queue.front().cloned()
+        }?;
+        self.replayed.fetch_add(1, Ordering::Relaxed);
+        tracing::debug!("Answered {} from fixtures", method);
+        Some(result)
+    }
+
+    /// Append a real call's result to the fixture file, when recording
+    pub(super) fn record(&self, method: &str, args: &Value, result: &Value) {
+        let (FixtureMode::Record, Some(path)) = (self.mode, &self.path) else {
+            return;
+        };
+        let entry = json!({
+            "method": method,
+            "args": args,
+            "result": result,
+            "recorded_at": chrono::Utc::now().to_rfc3339()
+        });
+        let _writer = self.writer.lock().expect("fixture writer poisoned");
+        let written = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .and_then(|mut file| writeln!(file, "{}", entry));
+        match written {
+            Ok(()) => {
+                self.recorded.fetch_add(1, Ordering::Relaxed);
+            }
+            Err(e) => tracing::warn!(
+                "Failed to record {} to fixtures {}: {}",
+                method,
+                path.display(),
+                e
+            ),
+        }
+    }
+
+    pub(super) fn stats(&self) -> Value {
+        json!({
+            "mode": self.mode,
+            "path": self.path,
+            "recorded": self.recorded.load(Ordering::Relaxed),
+            "replayed": self.replayed.load(Ordering::Relaxed)
+        })
+    }
+}
@@ -0,0 +1,250 @@
+//! Still frame cache
+//!
+//! Frames decoded for analysis are kept in memory, keyed by a hash of the
+//! media file (path, size and modification time), the source frame and frame
+//! rate, and the clip's grade. Agents inspecting the same frames again are
+//! answered without decoding. Changing the grade or rewriting the media gives new keys, so a
+//! stale frame is never returned; it ages out instead. Once the cache is over
+//! `cache.max_frame_cache_mb` the least recently used frames are evicted.
+
+use serde_json::{json, Value};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+use super::{ClipGrade, ColorWheelParams, ResolveBridge, StateView};
+use crate::config::CacheConfig;
+use crate::error::ResolveResult;
+use crate::timecode::FrameRate;
+
+#[derive(Debug)]
+struct CachedFrame {
+    pixels: Arc<Vec<u8>>,
+    /// Whether the pixels were decoded or simulated
+    source: &'static str,
+    /// Use counter value when the frame was last returned
+    last_used: u64,
+}
+
+#[derive(Debug, Default)]
+struct Frames {
+    entries: HashMap<u64, CachedFrame>,
+    bytes: usize,
+    clock: u64,
+    hits: u64,
+    misses: u64,
+    evictions: u64,
+}
+
+#[derive(Debug)]
+pub(super) struct FrameCache {
+    max_bytes: usize,
+    frames: Mutex<Frames>,
+}
+
+impl ColorWheelParams {
+    fn hash_into(&self, hasher: &mut DefaultHasher) {
+        for value in [self.red, self.green, self.blue, self.master] {
+            value.to_bits().hash(hasher);
+        }
+    }
+}
+
+impl ClipGrade {
+    /// Hash of everything that changes how the graded frame looks
+    fn hash_into(&self, hasher: &mut DefaultHasher) {
+        for wheel in [&self.lift, &self.gamma, &self.gain, &self.offset] {
+            wheel.hash_into(hasher);
+        }
+        self.applied_luts.hash(hasher);
+        self.node_count.hash(hasher);
+        let mut labels: Vec<_> = self.node_labels.iter().collect();
+        labels.sort();
+        labels.hash(hasher);
+        for (node, effects) in &self.motion_effects {
+            node.hash(hasher);
+            for (control, value) in effects.set_values() {
+                control.hash(hasher);
+                value.to_string().hash(hasher);
+            }
+        }
+        for (node, mask) in &self.privacy_masks {
+            node.hash(hasher);
+            mask.shape.hash(hasher);
+            mask.blur_strength.to_bits().hash(hasher);
+            mask.softness.to_bits().hash(hasher);
+            mask.timeline_item_id.hash(hasher);
+        }
+        for (node, qualifier) in &self.qualifiers {
+            node.hash(hasher);
+            for (control, value) in qualifier.set_values() {
+                control.hash(hasher);
+                value.to_string().hash(hasher);
+            }
+        }
+        for (node, windows) in &self.power_windows {
+            node.hash(hasher);
+            for window in windows {
+                window.shape.hash(hasher);
+                let corners = window.points.iter().flat_map(|&(x, y)| [x, y]);
+                for value in [
+                    window.center_x,
+                    window.center_y,
+                    window.width,
+                    window.height,
+                    window.rotation,
+                    window.softness,
+                ]
+                .into_iter()
+                .chain(corners)
+                {
+                    value.to_bits().hash(hasher);
+                }
+                window.invert.hash(hasher);
+                window.tracked_item.hash(hasher);
+            }
+        }
+    }
+}
+
+/// Cache key for a frame of a media file under a grade
+fn frame_key(file_path: &str, frame: i64, rate: FrameRate, grade: Option<&ClipGrade>) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    file_path.hash(&mut hasher);
+    // Offline media keys by path alone
+    if let Ok(metadata) = std::fs::metadata(Path::new(file_path)) {
+        metadata.len().hash(&mut hasher);
+        metadata.modified().ok().hash(&mut hasher);
+    }
+    frame.hash(&mut hasher);
+    rate.fps().to_bits().hash(&mut hasher);
+    if let Some(grade) = grade {
+        grade.hash_into(&mut hasher);
+    }
+    hasher.finish()
+}
+
+impl FrameCache {
+    pub(super) fn new(config: &CacheConfig) -> Self {
+        Self {
+            max_bytes: config.max_frame_cache_mb as usize * 1024 * 1024,
+            frames: Mutex::new(Frames::default()),
+        }
+    }
+
+    /// Cached pixels of a frame, or the frame `decode` produces, which is then kept
+    pub(super) fn get_or_decode(
+        &self,
+        file_path: &str,
+        frame: i64,
+        rate: FrameRate,
+        grade: Option<&ClipGrade>,
+        decode: impl FnOnce() -> (Vec<u8>, &'static str),
+    ) -> (Arc<Vec<u8>>, &'static str) {
+        let key = frame_key(file_path, frame, rate, grade);
+        {
+            let mut frames = self.frames.lock().unwrap();
+            frames.clock += 1;
+            let clock = frames.clock;
+            if let Some(cached) = frames.entries.get_mut(&key) {
+                cached.last_used = clock;
+                let pixels = cached.pixels.clone();
+                frames.hits += 1;
+                return (pixels, "cache");
+            }
+            frames.misses += 1;
+        }
+
+        // Decode without holding the lock so other frames can be served meanwhile
+        let (pixels, source) = decode();
+        let pixels = Arc::new(pixels);
+        if pixels.len() > self.max_bytes {
+            return (pixels, source);
+        }
+        let mut frames = self.frames.lock().unwrap();
+        let last_used = frames.clock;
+        let previous = frames.entries.insert(
+            key,
+            CachedFrame {
+                pixels: pixels.clone(),
+                source,
+                last_used,
+            },
+        );
+        frames.bytes += pixels.len();
+        frames.bytes -= previous.map_or(0, |previous| previous.pixels.len());
+        while frames.bytes > self.max_bytes {
+            let Some(oldest) = frames
+                .entries
+                .iter()
+                .min_by_key(|(_, cached)| cached.last_used)
+                .map(|(key, _)| *key)
+            else {
+                break;
+            };
+            let evicted = frames.entries.remove(&oldest).unwrap();
+            frames.bytes -= evicted.pixels.len();
+            frames.evictions += 1;
+        }
+        (pixels, source)
+    }
+
+    fn stats(&self) -> Value {
+        let frames = self.frames.lock().unwrap();
+        let lookups = frames.hits + frames.misses;
+        let decoded = frames
+            .entries
+            .values()
+            .filter(|cached| cached.source == "decoded")
+            .count();
+        json!({
+            "frames": frames.entries.len(),
+            "decoded_frames": decoded,
+            "simulated_frames": frames.entries.len() - decoded,
+            "bytes": frames.bytes,
+            "max_bytes": self.max_bytes,
+            "hits": frames.hits,
+            "misses": frames.misses,
+            "hit_rate": if lookups == 0 { 0.0 } else { frames.hits as f64 / lookups as f64 },
+            "evictions": frames.evictions
+        })
+    }
+
+    /// Drop every cached frame, returning how many there were
+    fn clear(&self) -> usize {
+        let mut frames = self.frames.lock().unwrap();
+        let cleared = frames.entries.len();
+        frames.entries.clear();
+        frames.bytes = 0;
+        cleared
+    }
+}
+
+impl ResolveBridge {
+    pub(super) async fn get_frame_cache_stats(
+        &self,
+        _state: &StateView<'_>,
+        _args: Value,
+    ) -> ResolveResult<Value> {
+        let mut response = self.frame_cache.stats();
+        response["result"] = json!(format!("Frame cache holds {} frames", response["frames"]));
+        response["operation_id"] = json!(Uuid::new_v4().to_string());
+        Ok(response)
+    }
+
+    pub(super) async fn clear_frame_cache(
+        &self,
+        _state: &StateView<'_>,
+        _args: Value,
+    ) -> ResolveResult<Value> {
+        let cleared = self.frame_cache.clear();
+        Ok(json!({
+            "result": format!("Cleared {} frames from the frame cache", cleared),
+            "cleared_frames": cleared,
+            "operation_id": Uuid::new_v4().to_string()
+        }))
+    }
+}
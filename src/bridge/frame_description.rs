@@ -0,0 +1,269 @@
+//! Frame descriptions through MCP sampling
+//!
+//! describe_frame sends one frame of a clip to the MCP client and asks the
+//! client's own model, through the sampling capability, to describe it and
+//! tag it with keywords. The answer is stored as the clip's Description and
+//! Keywords metadata through set_media_pool_item_metadata, so the active
+//! metadata schema applies and Real mode writes to Resolve, which lets an
+//! agent log footage as it comes in.
+//!
+//! The frame is the image at `image_path` when one is given, such as a still
+//! exported from the gallery, and is otherwise cut from the clip's media file
+//! with ffmpeg. The server can only sample when the client offered sampling
+//! when it connected; the server hands the bridge a [`Sampler`] for it.
+//! Sampling waits on the client, so no locks are held while it runs.
+//!
+//! The model is asked to answer with a JSON object; an answer that is not one
+//! is stored whole as the description.
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine as _;
+use serde_json::{json, Value};
+use std::future::Future;
+use std::path::Path;
+use std::pin::Pin;
+use uuid::Uuid;
+
+use super::locking::LockPlan;
+use super::{ffmpeg, ResolveBridge};
+use crate::error::{ResolveError, ResolveResult};
+use crate::timecode::FrameRate;
+
+/// Width frames cut from media are sent at
+const FRAME_WIDTH: u32 = 1024;
+
+/// Longest answer asked of the model, in tokens
+const MAX_TOKENS: u32 = 400;
+
+const SYSTEM_PROMPT: &str = "You log footage for a film editor. Describe the frame you are shown \
+in one or two plain sentences: who or what is in it, where, the shot size and the light. Then \
+give up to ten short keywords an editor would search for. Answer with only a JSON object: \
+{\"description\": \"...\", \"keywords\": [\"...\"]}";
+
+/// Metadata fields the answer is stored in
+const DESCRIPTION_FIELD: &str = "Description";
+const KEYWORDS_FIELD: &str = "Keywords";
+
+/// A frame and the question asked about it
+#[derive(Debug, Clone)]
+pub struct SamplingRequest {
+    pub system_prompt: String,
+    pub prompt: String,
+    /// The image, base64-encoded
+    pub image_base64: String,
+    pub mime_type: String,
+    pub max_tokens: u32,
+}
+
+/// The client model's answer
+#[derive(Debug, Clone)]
+pub struct SamplingReply {
+    pub text: String,
+    /// Model the client chose, if it said
+    pub model: Option<String>,
+}
+
+/// Asks the connected client's model for a completion
+pub trait Sampler: std::fmt::Debug + Send + Sync {
+    fn create_message(
+        &self,
+        request: SamplingRequest,
+    ) -> Pin<Box<dyn Future<Output = ResolveResult<SamplingReply>> + Send + '_>>;
+}
+
+/// MIME type of an image file, from its extension
+fn image_type(path: &str) -> ResolveResult<&'static str> {
+    let extension = Path::new(path)
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .map(str::to_ascii_lowercase);
+    match extension.as_deref() {
+        Some("png") => Ok("image/png"),
+        Some("jpg" | "jpeg") => Ok("image/jpeg"),
+        Some("webp") => Ok("image/webp"),
+        Some("gif") => Ok("image/gif"),
+        _ => Err(ResolveError::invalid_parameter(
+            "image_path",
+            "must be a PNG, JPEG, WebP or GIF image",
+        )),
+    }
+}
+
+/// Description and keywords from the model's answer
+fn parse_answer(text: &str) -> (String, Vec<String>) {
+    let object = match (text.find('{'), text.rfind('}')) {
+        (Some(start), Some(end)) if start < end => {
+            serde_json::from_str::<Value>(&text[start..=end]).ok()
+        }
+        _ => None,
+    };
+    let Some(object) = object.filter(Value::is_object) else {
+        return (text.trim().to_string(), Vec::new());
+    };
+    let description = object["description"]
+        .as_str()
+        .unwrap_or_default()
+        .trim()
+        .to_string();
+    let keywords = match &object["keywords"] {
+        Value::Array(keywords) => keywords
+            .iter()
+            .filter_map(Value::as_str)
+            .map(str::to_string)
+            .collect(),
+        Value::String(keywords) => keywords.split(',').map(str::to_string).collect(),
+        _ => Vec::new(),
+    };
+    let keywords = keywords
+        .into_iter()
+        .map(|keyword: String| keyword.trim().to_string())
+        .filter(|keyword| !keyword.is_empty())
+        .collect();
+    (description, keywords)
+}
+
+impl ResolveBridge {
+    /// Set or remove the sampler describe_frame asks, as the client connects
+    pub async fn set_sampler(&self, sampler: Option<std::sync::Arc<dyn Sampler>>) {
+        *self.sampler.lock().await = sampler;
+    }
+
+    /// A frame of the clip's media as a JPEG
+    async fn clip_frame(&self, clip_name: &str, frame: i64) -> ResolveResult<Vec<u8>> {
+        let (file_path, rate) = {
+            let state = self.state.lock(LockPlan::READ).await;
+            let clip = state.media_pool.clips.get(clip_name).ok_or_else(|| {
+                ResolveError::MediaNotFound {
+                    name: clip_name.to_string(),
+                }
+            })?;
+            (clip.file_path.clone(), self.clip_frame_rate(clip))
+        };
+        let rate =
+            FrameRate::parse(&rate).map_err(|e| ResolveError::invalid_parameter("frame", e))?;
+        let media = Path::new(&file_path);
+        let exported = if !media.is_file() {
+            Err(format!("{} is not a file", file_path))
+        } else if !ffmpeg::available() {
+            Err("ffmpeg is not installed".to_string())
+        } else {
+            ffmpeg::jpeg_frame(media, frame as f64 / rate.fps(), FRAME_WIDTH)
+        };
+        exported.map_err(|reason| {
+            ResolveError::api_call(
+                "describe_frame",
+                format!(
+                    "cannot export frame {} of '{}': {}; pass image_path with an exported still",
+                    frame, clip_name, reason
+                ),
+            )
+        })
+    }
+
+    /// Ask the client's model to describe a frame and store the answer
+    pub(super) async fn describe_frame(&self, args: Value) -> ResolveResult<Value> {
+        let clip_name = args["clip_name"]
+            .as_str()
+            .ok_or_else(|| ResolveError::invalid_parameter("clip_name", "required string"))?;
+        let frame = args["frame"].as_i64().unwrap_or(0);
+        if frame < 0 {
+            return Err(ResolveError::invalid_parameter(
+                "frame",
+                "must not be negative",
+            ));
+        }
+        let sampler = self.sampler.lock().await.clone().ok_or_else(|| {
+            ResolveError::not_supported(
+                "describe_frame: the MCP client did not offer sampling when it connected",
+            )
+        })?;
+
+        let (image, mime_type, image_source) = match args["image_path"].as_str() {
+            Some(path) => {
+                let mime_type = image_type(path)?;
+                let image = std::fs::read(path).map_err(|_| ResolveError::FileNotFound {
+                    path: path.to_string(),
+                })?;
+                (image, mime_type, "image_path")
+            }
+            None => (
+                self.clip_frame(clip_name, frame).await?,
+                "image/jpeg",
+                "media",
+            ),
+        };
+
+        let mut prompt = format!("This is a frame of the clip '{}'.", clip_name);
+        if let Some(focus) = args["prompt"].as_str() {
+            prompt.push_str(&format!(" {}", focus));
+        }
+        let reply = sampler
+            .create_message(SamplingRequest {
+                system_prompt: SYSTEM_PROMPT.to_string(),
+                prompt,
+                image_base64: STANDARD.encode(&image),
+                mime_type: mime_type.to_string(),
+                max_tokens: MAX_TOKENS,
+            })
+            .await?;
+        let (description, keywords) = parse_answer(&reply.text);
+        if description.is_empty() && keywords.is_empty() {
+            return Err(ResolveError::api_call(
+                "describe_frame",
+                "the client's model gave an empty answer",
+            ));
+        }
+
+        let mut written = Vec::new();
+        if args["write_metadata"].as_bool().unwrap_or(true) {
+            let fields = [
+                (DESCRIPTION_FIELD, description.clone()),
+                (KEYWORDS_FIELD, keywords.join(", ")),
+            ];
+            for (field, value) in fields {
+                if value.is_empty() {
+                    continue;
+                }
+                let result = self
+                    .call_api_boxed(
+                        "set_media_pool_item_metadata".to_string(),
+                        json!({
+                            "clip_name": clip_name,
+                            "metadata_type": field,
+                            "metadata_value": value
+                        }),
+                    )
+                    .await?;
+                if result["success"] == false {
+                    return Err(ResolveError::api_call(
+                        "describe_frame",
+                        format!(
+                            "storing {} failed: {}",
+                            field,
+                            result["error"].as_str().unwrap_or("unknown error")
+                        ),
+                    ));
+                }
+                written.push(field);
+            }
+        }
+
+        Ok(json!({
+            "result": format!(
+                "Described frame {} of '{}' with {} keywords{}",
+                frame,
+                clip_name,
+                keywords.len(),
+                if written.is_empty() { "" } else { " and stored them as clip metadata" }
+            ),
+            "clip_name": clip_name,
+            "frame": frame,
+            "image_source": image_source,
+            "description": description,
+            "keywords": keywords,
+            "model": reply.model,
+            "metadata_written": written,
+            "operation_id": Uuid::new_v4().to_string()
+        }))
+    }
+}
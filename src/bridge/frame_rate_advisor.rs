@@ -0,0 +1,247 @@
+//! Frame rate conversion advice
+//!
+//! Finds the clips on a timeline whose media runs at a different frame rate
+//! than the timeline and suggests how each should play, the choice editors
+//! otherwise make clip by clip in the clip attributes:
+//!
+//! - conform the clip to the timeline rate when the rates are within a few
+//!   percent, as with 23.976, 24 and 25 fps, so every frame plays once and
+//!   the clip runs marginally faster or slower;
+//! - retime with nearest frame when the clip runs at a whole multiple of the
+//!   timeline rate, as 50 fps on 25, so frames drop evenly;
+//! - retime with optical flow otherwise, as 30 fps on 24, where dropped or
+//!   repeated frames would judder.
+//!
+//! Clip rates come from the clip's FPS property; clips without one are probed
+//! with ffprobe when their media is online, and read as the project rate
+//! otherwise. Suggestions are applied on request: conforming sets the clip's
+//! FPS, which changes it on every timeline, and retiming sets the retime
+//! process of the clip's items on this timeline.
+
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+use uuid::Uuid;
+
+use super::item_properties::{self, PropertyValue};
+use super::{ffmpeg, ResolveBridge, StateView};
+use crate::error::{ResolveError, ResolveResult};
+use crate::timecode::FrameRate;
+
+/// Largest speed change, as a fraction, that conforming may introduce
+const CONFORM_TOLERANCE: f64 = 0.05;
+
+/// How far a rate ratio may be from a whole number to drop frames evenly
+const MULTIPLE_TOLERANCE: f64 = 0.01;
+
+/// How a mismatched clip should play on the timeline
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Conversion {
+    /// Play every frame once at the timeline rate
+    Conform,
+    /// Keep real time speed and let the retime process fill or drop frames
+    Retime(&'static str),
+}
+
+impl Conversion {
+    fn action(self) -> &'static str {
+        match self {
+            Conversion::Conform => "conform",
+            Conversion::Retime(_) => "retime",
+        }
+    }
+}
+
+/// Retime process for a clip at `ratio` times the timeline rate
+fn retime_process(ratio: f64) -> &'static str {
+    if ratio > 1.0 && (ratio - ratio.round()).abs() < MULTIPLE_TOLERANCE {
+        "NearestFrame"
+    } else {
+        "OpticalFlow"
+    }
+}
+
+/// Conversion suggested for a clip at `ratio` times the timeline rate, and why
+fn suggest(ratio: f64, strategy: &str) -> (Conversion, String) {
+    let speed_change = (1.0 / ratio - 1.0) * 100.0;
+    let conform_reason = || {
+        format!(
+            "conforming plays every frame once, {:+.1}% speed",
+            speed_change
+        )
+    };
+    let retime_reason = |process: &str| match process {
+        "NearestFrame" => format!(
+            "the clip runs at {}x the timeline rate, so frames drop evenly",
+            ratio.round()
+        ),
+        _ => "the rates do not divide evenly, so frames are interpolated".to_string(),
+    };
+    match strategy {
+        "conform" => (Conversion::Conform, conform_reason()),
+        "retime" => {
+            let process = retime_process(ratio);
+            (Conversion::Retime(process), retime_reason(process))
+        }
+        _ if (ratio - 1.0).abs() <= CONFORM_TOLERANCE => (Conversion::Conform, conform_reason()),
+        _ => {
+            let process = retime_process(ratio);
+            (Conversion::Retime(process), retime_reason(process))
+        }
+    }
+}
+
+impl ResolveBridge {
+    pub(super) async fn analyze_frame_rate_mismatches(
+        &self,
+        state: &mut StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        let strategy = args["strategy"].as_str().unwrap_or("auto");
+        if !["auto", "conform", "retime"].contains(&strategy) {
+            return Err(ResolveError::invalid_parameter(
+                "strategy",
+                "must be 'auto', 'conform' or 'retime'",
+            ));
+        }
+        let apply = args["apply"].as_bool().unwrap_or(false);
+        let probe = args["probe"].as_bool().unwrap_or(true);
+        let timeline_name = Self::resolve_timeline_name(state, &args)?;
+        let rate = self.timeline_frame_rate(&state.timelines[&timeline_name])?;
+
+        // Items of each media pool clip on the timeline
+        let mut items_by_clip: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for item in state.timeline_items.items.values() {
+            if item.timeline_name == timeline_name
+                && item.generator.is_none()
+                && state.media_pool.clips.contains_key(&item.clip_name)
+            {
+                items_by_clip
+                    .entry(item.clip_name.clone())
+                    .or_default()
+                    .push(item.id.clone());
+            }
+        }
+        items_by_clip
+            .values_mut()
+            .for_each(|item_ids| item_ids.sort());
+
+        // Probe clips without a frame rate, checking for ffprobe once
+        let mut rate_sources: BTreeMap<&str, &str> = BTreeMap::new();
+        let mut can_probe = None;
+        for clip_name in items_by_clip.keys() {
+            let Some(clip) = state.media_pool.clips.get_mut(clip_name) else {
+                continue;
+            };
+            let source = if clip.frame_rate.is_some() {
+                "clip"
+            } else if probe
+                && Path::new(&clip.file_path).is_file()
+                && *can_probe.get_or_insert_with(ffmpeg::probe_available)
+            {
+                match ffmpeg::probe_frame_rate(Path::new(&clip.file_path)) {
+                    Ok(Some(probed)) => {
+                        clip.frame_rate = Some(probed);
+                        "probed"
+                    }
+                    _ => "project",
+                }
+            } else {
+                "project"
+            };
+            rate_sources.insert(clip_name, source);
+        }
+
+        let mut clips = Vec::new();
+        let mut applied = 0;
+        for (clip_name, item_ids) in &items_by_clip {
+            let clip_rate = self.clip_frame_rate(&state.media_pool.clips[clip_name]);
+            let clip_fps = FrameRate::parse(&clip_rate)
+                .map_err(|e| ResolveError::invalid_parameter("frame_rate", e))?
+                .fps();
+            if (clip_fps - rate.fps()).abs() <= 0.001 {
+                continue;
+            }
+            let ratio = clip_fps / rate.fps();
+            let (conversion, reason) = suggest(ratio, strategy);
+
+            // Conforming changes the clip wherever it is used
+            let mut other_timelines: BTreeSet<&str> = BTreeSet::new();
+            for item in state.timeline_items.items.values() {
+                if &item.clip_name == clip_name && item.timeline_name != timeline_name {
+                    other_timelines.insert(&item.timeline_name);
+                }
+            }
+            let other_timelines: Vec<String> =
+                other_timelines.into_iter().map(str::to_string).collect();
+
+            if apply {
+                match conversion {
+                    Conversion::Conform => {
+                        if let Some(clip) = state.media_pool.clips.get_mut(clip_name) {
+                            clip.frame_rate = Some(rate.to_string());
+                        }
+                    }
+                    Conversion::Retime(process) => {
+                        let property = item_properties::require("process", "RetimeProcess")?;
+                        for item_id in item_ids {
+                            let item = Self::timeline_item_mut(state, item_id);
+                            item.properties
+                                .set(property, PropertyValue::Choice(process));
+                        }
+                    }
+                }
+                applied += 1;
+            }
+
+            clips.push(json!({
+                "clip_name": clip_name,
+                "clip_frame_rate": clip_rate,
+                "rate_source": rate_sources[clip_name.as_str()],
+                "ratio": ratio,
+                "timeline_item_ids": item_ids,
+                "suggestion": {
+                    "action": conversion.action(),
+                    "conform_frame_rate": (conversion == Conversion::Conform)
+                        .then(|| rate.to_string()),
+                    "retime_process": match conversion {
+                        Conversion::Retime(process) => Some(process),
+                        Conversion::Conform => None,
+                    },
+                    "speed_percent": match conversion {
+                        Conversion::Conform => 100.0 / ratio,
+                        Conversion::Retime(_) => 100.0,
+                    },
+                    "reason": reason
+                },
+                "also_on_timelines": other_timelines,
+                "applied": apply
+            }));
+        }
+
+        Ok(json!({
+            "result": if apply {
+                format!(
+                    "Applied conform settings to {} clips on timeline '{}'",
+                    applied, timeline_name
+                )
+            } else {
+                format!(
+                    "{} of {} clips on timeline '{}' differ from its {} fps",
+                    clips.len(),
+                    items_by_clip.len(),
+                    timeline_name,
+                    rate
+                )
+            },
+            "timeline_name": timeline_name,
+            "timeline_frame_rate": rate.to_string(),
+            "strategy": strategy,
+            "clip_count": items_by_clip.len(),
+            "mismatch_count": clips.len(),
+            "clips": clips,
+            "applied": applied,
+            "operation_id": Uuid::new_v4().to_string()
+        }))
+    }
+}
@@ -0,0 +1,67 @@
+//! Inventory of Resolve's built-in generators
+//!
+//! Lists the standard generators Resolve offers in the Effects Library, so
+//! insert_generator can reject names Resolve would not find and delivery prep
+//! tools can pick test patterns by name. Fusion and OFX generators depend on
+//! what is installed and are not listed.
+
+use crate::error::{ResolveError, ResolveResult};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) enum GeneratorKind {
+    /// Broadcast colour bars, usable as a bars-and-tone leader
+    ColorBars,
+    /// Other test signals such as steps and ramps
+    TestPattern,
+    /// Solid fills, gradients and mattes
+    Fill,
+}
+
+#[derive(Debug)]
+pub(super) struct Generator {
+    pub(super) name: &'static str,
+    pub(super) kind: GeneratorKind,
+}
+
+const fn generator(name: &'static str, kind: GeneratorKind) -> Generator {
+    Generator { name, kind }
+}
+
+/// Standard generators, as named in the Effects Library
+pub(super) const STANDARD_GENERATORS: &[Generator] = &[
+    generator("10 Step", GeneratorKind::TestPattern),
+    generator("100mV Steps", GeneratorKind::TestPattern),
+    generator("EBU Color Bar", GeneratorKind::ColorBars),
+    generator("Four Color Gradient", GeneratorKind::Fill),
+    generator("Grey Scale", GeneratorKind::TestPattern),
+    generator("SMPTE Color Bar", GeneratorKind::ColorBars),
+    generator("Solid Color", GeneratorKind::Fill),
+    generator("Window", GeneratorKind::Fill),
+    generator("YCbCr Ramp", GeneratorKind::TestPattern),
+];
+
+/// Standard generator with this name, ignoring case; `param` names the argument in errors
+pub(super) fn require_standard(
+    param: &str,
+    name: &str,
+    kind: Option<GeneratorKind>,
+) -> ResolveResult<&'static Generator> {
+    let candidates = || {
+        STANDARD_GENERATORS
+            .iter()
+            .filter(move |generator| kind.is_none_or(|kind| generator.kind == kind))
+    };
+    candidates()
+        .find(|generator| generator.name.eq_ignore_ascii_case(name))
+        .ok_or_else(|| {
+            let names: Vec<&str> = candidates().map(|generator| generator.name).collect();
+            ResolveError::invalid_parameter(
+                param,
+                format!(
+                    "unknown generator '{}'; expected one of: {}",
+                    name,
+                    names.join(", ")
+                ),
+            )
+        })
+}
@@ -0,0 +1,359 @@
+//! Grading history
+//!
+//! With grading history on, every call that changes a clip's grade keeps the
+//! grade it leaves as a new version of that clip's grade, numbered from 1.
+//! The grade a clip had before its first recorded change is kept as version
+//! 0. Each version records the call that made it and its arguments, and with
+//! thumbnails on a gallery still of the clip is grabbed into the Grade
+//! History album. get_grade_history lists the versions with what changed
+//! from one to the next, and revert_grade_to makes an earlier version the
+//! clip's grade again; that is a change like any other, so it adds a version
+//! rather than dropping the ones after it.
+//!
+//! This is finer than a whole-state backup: one clip goes back, and nothing
+//! else does. Versions are kept in the simulation's color state; in Real
+//! mode only calls that fall back to the simulation are recorded.
+
+use chrono::{DateTime, Utc};
+use serde_json::{json, Value};
+use std::collections::{BTreeSet, HashMap};
+use uuid::Uuid;
+
+use super::locking::{Domain, LockPlan};
+use super::state_diff::diff_fields;
+use super::{ClipGrade, GalleryStill, GalleryStillAlbum, ResolveBridge, StateView};
+use crate::config::GradeHistoryConfig;
+use crate::error::{ResolveError, ResolveResult};
+
+/// Still album version thumbnails are grabbed into
+const HISTORY_ALBUM: &str = "Grade History";
+
+/// One grade a clip had
+#[derive(Debug, Clone)]
+struct GradeVersion {
+    version: u64,
+    at: DateTime<Utc>,
+    /// Call that made the version; None for the grade before the first one
+    tool: Option<String>,
+    arguments: Value,
+    grade: ClipGrade,
+    still_id: Option<String>,
+}
+
+/// Versions of one clip's grade, oldest first
+#[derive(Debug, Clone, Default)]
+struct ClipHistory {
+    versions: Vec<GradeVersion>,
+    next_version: u64,
+}
+
+/// Whether versions are being kept, and the versions of every clip
+#[derive(Debug, Clone, Default)]
+pub(super) struct GradeHistory {
+    enabled: bool,
+    thumbnails: bool,
+    clips: HashMap<String, ClipHistory>,
+}
+
+impl GradeHistory {
+    pub(super) fn new(config: &GradeHistoryConfig) -> Self {
+        Self {
+            enabled: config.enabled,
+            thumbnails: config.thumbnails,
+            clips: HashMap::new(),
+        }
+    }
+
+    /// Drop every version, as when the project closes
+    pub(super) fn clear(&mut self) {
+        self.clips.clear();
+    }
+}
+
+/// Clip grades before a call that may change them, and its arguments
+pub(super) struct PendingGrades {
+    grades: HashMap<String, ClipGrade>,
+    arguments: Value,
+}
+
+/// A grade as get_clip_grade reports its parts
+fn grade_json(grade: &ClipGrade) -> Value {
+    json!({
+        "wheels": grade.wheels_json(),
+        "luts": grade.applied_luts,
+        "node_count": grade.node_count,
+        "node_labels": grade.node_labels_json(),
+        "motion_effects": grade.motion_effects_json(),
+        "privacy_masks": grade.privacy_masks_json(),
+        "power_windows": grade.power_windows_json(),
+        "qualifiers": grade.qualifiers_json()
+    })
+}
+
+impl GradeVersion {
+    fn to_json(&self, previous: Option<&GradeVersion>, full: bool) -> Value {
+        let grade = grade_json(&self.grade);
+        let mut changes = Vec::new();
+        if let Some(previous) = previous {
+            diff_fields("", &grade_json(&previous.grade), &grade, &mut changes);
+        }
+        let mut entry = json!({
+            "version": self.version,
+            "at": self.at.to_rfc3339(),
+            "tool": self.tool,
+            "arguments": self.arguments,
+            "still_id": self.still_id,
+            "changes": changes
+        });
+        if full {
+            entry["grade"] = grade;
+        }
+        entry
+    }
+}
+
+/// Capture the clip grades before a call that writes the color state, while
+/// grading history is on
+pub(super) fn before(state: &StateView<'_>, plan: LockPlan, args: &Value) -> Option<PendingGrades> {
+    let recorded = plan.writes().contains(&Domain::Color);
+    (recorded && state.color_state.grade_history.enabled).then(|| PendingGrades {
+        grades: state.color_state.clip_grades.clone(),
+        arguments: args.clone(),
+    })
+}
+
+impl ResolveBridge {
+    /// Keep a version of every clip grade the call changed
+    pub(super) fn record_grade_history(
+        &self,
+        state: &mut StateView<'_>,
+        pending: PendingGrades,
+        method: &str,
+    ) {
+        let default_grade = ClipGrade::default();
+        let clips: BTreeSet<String> = pending
+            .grades
+            .keys()
+            .chain(state.color_state.clip_grades.keys())
+            .cloned()
+            .collect();
+        let now = Utc::now();
+        for clip in clips {
+            let before = pending.grades.get(&clip).unwrap_or(&default_grade);
+            let after = state
+                .color_state
+                .clip_grades
+                .get(&clip)
+                .cloned()
+                .unwrap_or_default();
+            if grade_json(before) == grade_json(&after) {
+                continue;
+            }
+
+            let still_id = state
+                .color_state
+                .grade_history
+                .thumbnails
+                .then(|| Self::grab_history_still(state, &clip));
+            let history = state
+                .color_state
+                .grade_history
+                .clips
+                .entry(clip.clone())
+                .or_default();
+            if history.versions.is_empty() && history.next_version == 0 {
+                history.versions.push(GradeVersion {
+                    version: 0,
+                    at: now,
+                    tool: None,
+                    arguments: Value::Null,
+                    grade: before.clone(),
+                    still_id: None,
+                });
+                history.next_version = 1;
+            }
+            history.versions.push(GradeVersion {
+                version: history.next_version,
+                at: now,
+                tool: Some(method.to_string()),
+                arguments: pending.arguments.clone(),
+                grade: after,
+                still_id,
+            });
+            history.next_version += 1;
+            let excess = history
+                .versions
+                .len()
+                .saturating_sub(self.config.grade_history.max_versions);
+            history.versions.drain(..excess);
+        }
+    }
+
+    /// Grab a still of `clip` into the Grade History album, returning its ID
+    fn grab_history_still(state: &mut StateView<'_>, clip: &str) -> String {
+        let id = state.gallery.next_still_id();
+        let version = state
+            .color_state
+            .grade_history
+            .clips
+            .get(clip)
+            .map_or(1, |history| history.next_version.max(1));
+        let still = GalleryStill {
+            id: id.clone(),
+            label: format!("{} v{}", clip, version),
+            timeline_name: state.current_timeline.clone(),
+            source_clip: Some(clip.to_string()),
+            grabbed_at: Utc::now().to_rfc3339(),
+        };
+        if state.gallery.album(HISTORY_ALBUM).is_none() {
+            state
+                .gallery
+                .albums
+                .push(GalleryStillAlbum::new(HISTORY_ALBUM));
+        }
+        if let Some(album) = state.gallery.album_mut(HISTORY_ALBUM) {
+            album.stills.push(still);
+        }
+        id
+    }
+
+    pub(super) async fn set_grade_history(
+        &self,
+        state: &mut StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        let history = &mut state.color_state.grade_history;
+        history.enabled = args["enabled"]
+            .as_bool()
+            .ok_or_else(|| ResolveError::invalid_parameter("enabled", "required boolean"))?;
+        if let Some(thumbnails) = args["thumbnails"].as_bool() {
+            history.thumbnails = thumbnails;
+        }
+        let versions: usize = history.clips.values().map(|clip| clip.versions.len()).sum();
+        Ok(json!({
+            "result": format!(
+                "Grading history is {}{}",
+                if history.enabled { "on" } else { "off" },
+                if history.enabled && history.thumbnails {
+                    ", with thumbnails"
+                } else {
+                    ""
+                }
+            ),
+            "enabled": history.enabled,
+            "thumbnails": history.thumbnails,
+            "max_versions": self.config.grade_history.max_versions,
+            "clips": history.clips.len(),
+            "versions": versions
+        }))
+    }
+
+    pub(super) async fn get_grade_history(
+        &self,
+        state: &mut StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        let history = &state.color_state.grade_history;
+        let Some(clip) = args["clip_name"].as_str() else {
+            let mut clips: Vec<(&String, &ClipHistory)> = history.clips.iter().collect();
+            clips.sort_by(|a, b| a.0.cmp(b.0));
+            let clips: Vec<Value> = clips
+                .into_iter()
+                .map(|(clip, versions)| {
+                    let latest = versions.versions.last();
+                    json!({
+                        "clip_name": clip,
+                        "version_count": versions.versions.len(),
+                        "latest_version": latest.map(|version| version.version),
+                        "latest_tool": latest.and_then(|version| version.tool.clone()),
+                        "latest_at": latest.map(|version| version.at.to_rfc3339())
+                    })
+                })
+                .collect();
+            return Ok(json!({
+                "result": format!("Grading history holds versions of {} clips", clips.len()),
+                "enabled": history.enabled,
+                "thumbnails": history.thumbnails,
+                "clips": clips
+            }));
+        };
+
+        let versions = history
+            .clips
+            .get(clip)
+            .map(|clip| clip.versions.as_slice())
+            .unwrap_or_default();
+        let full = args["include_grades"].as_bool().unwrap_or(false);
+        let entries: Vec<Value> = versions
+            .iter()
+            .enumerate()
+            .map(|(index, version)| {
+                let previous = index.checked_sub(1).map(|index| &versions[index]);
+                version.to_json(previous, full)
+            })
+            .collect();
+        Ok(json!({
+            "result": format!("Clip '{}' has {} grade versions", clip, entries.len()),
+            "clip_name": clip,
+            "enabled": history.enabled,
+            "versions": entries
+        }))
+    }
+
+    pub(super) async fn revert_grade_to(
+        &self,
+        state: &mut StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        let clip = args["clip_name"]
+            .as_str()
+            .ok_or_else(|| ResolveError::invalid_parameter("clip_name", "required string"))?;
+        let version = args["version"]
+            .as_u64()
+            .ok_or_else(|| ResolveError::invalid_parameter("version", "required version number"))?;
+        let versions = state
+            .color_state
+            .grade_history
+            .clips
+            .get(clip)
+            .map(|clip| clip.versions.as_slice())
+            .unwrap_or_default();
+        let target = versions
+            .iter()
+            .find(|kept| kept.version == version)
+            .ok_or_else(|| {
+                let kept: Vec<String> = versions
+                    .iter()
+                    .map(|kept| kept.version.to_string())
+                    .collect();
+                ResolveError::invalid_parameter(
+                    "version",
+                    format!(
+                        "clip '{}' has no grade version {}; versions kept: {}",
+                        clip,
+                        version,
+                        if kept.is_empty() {
+                            "none".to_string()
+                        } else {
+                            kept.join(", ")
+                        }
+                    ),
+                )
+            })?;
+        let grade = target.grade.clone();
+        let tool = target.tool.clone();
+        state
+            .color_state
+            .clip_grades
+            .insert(clip.to_string(), grade.clone());
+
+        Ok(json!({
+            "result": format!("Reverted the grade of clip '{}' to version {}", clip, version),
+            "clip_name": clip,
+            "version": version,
+            "version_tool": tool,
+            "grade": grade_json(&grade),
+            "operation_id": Uuid::new_v4().to_string()
+        }))
+    }
+}
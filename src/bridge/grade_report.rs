@@ -0,0 +1,348 @@
+//! Colorist grade reports for client review and archival
+//!
+//! A report lists every item on a timeline with the grade of the clip it uses:
+//! color wheels, LUTs, node count and labels, the Power Windows of its nodes,
+//! the item's CDL when one was set with set_cdl, and the gallery stills
+//! grabbed from the clip. Simulated stills have no image files, so they are
+//! listed by label without thumbnails.
+
+use serde::Serialize;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use super::item_lookup::timeline_clock;
+use super::{ClipGrade, ResolveBridge, StateView};
+use crate::error::{ResolveError, ResolveResult};
+
+/// ASC CDL values for one node, as passed to Resolve's SetCDL
+#[derive(Debug, Clone, Serialize)]
+pub(super) struct Cdl {
+    node_index: u32,
+    slope: [f64; 3],
+    offset: [f64; 3],
+    power: [f64; 3],
+    saturation: f64,
+}
+
+impl Cdl {
+    /// Read a SetCDL map; missing values keep their identity defaults
+    pub(super) fn parse(map: &Value) -> ResolveResult<Self> {
+        let map = map
+            .as_object()
+            .ok_or_else(|| ResolveError::invalid_parameter("cdl_map", "must be an object"))?;
+        if let Some(key) = map.keys().find(|key| {
+            !["NodeIndex", "Slope", "Offset", "Power", "Saturation"].contains(&key.as_str())
+        }) {
+            return Err(ResolveError::invalid_parameter(
+                "cdl_map",
+                format!(
+                    "unknown key '{}'; use NodeIndex, Slope, Offset, Power or Saturation",
+                    key
+                ),
+            ));
+        }
+
+        let node_index = match map.get("NodeIndex") {
+            Some(value) => numbers(value, "NodeIndex")
+                .ok()
+                .and_then(|index| match index[..] {
+                    [index] if index >= 1.0 && index.fract() == 0.0 => Some(index as u32),
+                    _ => None,
+                })
+                .ok_or_else(|| {
+                    ResolveError::invalid_parameter("cdl_map", "NodeIndex must be 1 or greater")
+                })?,
+            None => 1,
+        };
+        let triple = |key: &str, default: f64| -> ResolveResult<[f64; 3]> {
+            match map.get(key) {
+                Some(value) => numbers(value, key)?.try_into().map_err(|_| {
+                    ResolveError::invalid_parameter(
+                        "cdl_map",
+                        format!("{} needs three values for red, green and blue", key),
+                    )
+                }),
+                None => Ok([default; 3]),
+            }
+        };
+        let saturation = match map.get("Saturation") {
+            Some(value) => match numbers(value, "Saturation")?[..] {
+                [saturation] if saturation >= 0.0 => saturation,
+                _ => {
+                    return Err(ResolveError::invalid_parameter(
+                        "cdl_map",
+                        "Saturation must be one value of 0 or more",
+                    ))
+                }
+            },
+            None => 1.0,
+        };
+        let cdl = Self {
+            node_index,
+            slope: triple("Slope", 1.0)?,
+            offset: triple("Offset", 0.0)?,
+            power: triple("Power", 1.0)?,
+            saturation,
+        };
+        if cdl.slope.iter().chain(&cdl.power).any(|value| *value < 0.0) {
+            return Err(ResolveError::invalid_parameter(
+                "cdl_map",
+                "Slope and Power values must be 0 or more",
+            ));
+        }
+        Ok(cdl)
+    }
+
+    pub(super) fn to_json(&self) -> Value {
+        json!({
+            "node_index": self.node_index,
+            "slope": self.slope,
+            "offset": self.offset,
+            "power": self.power,
+            "saturation": self.saturation
+        })
+    }
+}
+
+/// Numbers from a CDL value given as a space-separated string, a number or a list
+fn numbers(value: &Value, key: &str) -> ResolveResult<Vec<f64>> {
+    let invalid = || ResolveError::invalid_parameter("cdl_map", format!("{} must be numeric", key));
+    match value {
+        Value::String(text) => text
+            .split_whitespace()
+            .map(|part| part.parse::<f64>().map_err(|_| invalid()))
+            .collect(),
+        Value::Number(number) => Ok(vec![number.as_f64().ok_or_else(invalid)?]),
+        Value::Array(values) => values
+            .iter()
+            .map(|value| value.as_f64().ok_or_else(invalid))
+            .collect(),
+        _ => Err(invalid()),
+    }
+}
+
+fn html_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// One table cell per report entry, in the order of the HTML header
+fn html_row(entry: &Value) -> String {
+    let text = |value: &Value| match value {
+        Value::Null => String::new(),
+        Value::String(text) => text.clone(),
+        other => other.to_string(),
+    };
+    let wheel = |name: &str| {
+        let wheel = &entry["wheels"][name];
+        format!(
+            "R {} G {} B {} M {}",
+            wheel["red"], wheel["green"], wheel["blue"], wheel["master"]
+        )
+    };
+    let list = |key: &str, field: Option<&str>| {
+        entry[key]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .map(|value| text(field.map_or(value, |field| &value[field])))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    let windows = entry["power_windows"]
+        .as_object()
+        .into_iter()
+        .flatten()
+        .flat_map(|(node, windows)| {
+            windows
+                .as_array()
+                .into_iter()
+                .flatten()
+                .map(move |window| format!("Node {} {}", node, text(&window["shape"])))
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    let cdl = &entry["cdl"];
+    let cdl = if cdl.is_null() {
+        String::new()
+    } else {
+        format!(
+            "Slope {} Offset {} Power {} Sat {}",
+            cdl["slope"], cdl["offset"], cdl["power"], cdl["saturation"]
+        )
+    };
+    let cells = [
+        text(&entry["index"]),
+        text(&entry["clip_name"]),
+        text(&entry["track"]),
+        text(&entry["record_in"]),
+        text(&entry["record_out"]),
+        text(&entry["node_count"]),
+        windows,
+        list("luts", None),
+        wheel("lift"),
+        wheel("gamma"),
+        wheel("gain"),
+        wheel("offset"),
+        cdl,
+        list("stills", Some("label")),
+    ];
+    let cells: String = cells
+        .iter()
+        .map(|cell| format!("<td>{}</td>", html_escape(cell)))
+        .collect();
+    format!("<tr>{}</tr>\n", cells)
+}
+
+impl ResolveBridge {
+    pub(super) async fn generate_grade_report(
+        &self,
+        state: &StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        let format = args["format"]
+            .as_str()
+            .unwrap_or("json")
+            .to_ascii_lowercase();
+        if format != "json" && format != "html" {
+            return Err(ResolveError::invalid_parameter(
+                "format",
+                "must be 'json' or 'html'",
+            ));
+        }
+        let timeline_name = Self::resolve_timeline_name(state, &args)?;
+        let (rate, start_frame) = timeline_clock(self, state, &timeline_name, &args)?;
+
+        let mut items: Vec<_> = state
+            .timeline_items
+            .items
+            .values()
+            .filter(|item| item.timeline_name == timeline_name)
+            .collect();
+        items.sort_by_key(|item| {
+            let position = item
+                .placement
+                .as_ref()
+                .map(|placement| (placement.record_in, placement.track_index));
+            (position.is_none(), position, &item.id)
+        });
+
+        let default_grade = ClipGrade::default();
+        let mut luts_used: Vec<&str> = Vec::new();
+        let mut graded = 0;
+        let mut still_count = 0;
+        let entries: Vec<Value> = items
+            .iter()
+            .enumerate()
+            .map(|(index, item)| {
+                let grade = state.color_state.clip_grades.get(&item.clip_name);
+                let values = grade.unwrap_or(&default_grade);
+                if grade.is_some() {
+                    graded += 1;
+                }
+                for lut in &values.applied_luts {
+                    if !luts_used.contains(&lut.as_str()) {
+                        luts_used.push(lut);
+                    }
+                }
+                let stills: Vec<Value> = state
+                    .gallery
+                    .albums
+                    .iter()
+                    .flat_map(|album| album.stills.iter().map(move |still| (album, still)))
+                    .filter(|(_, still)| {
+                        still.source_clip.as_ref() == Some(&item.clip_name)
+                            && still
+                                .timeline_name
+                                .as_ref()
+                                .is_none_or(|name| *name == timeline_name)
+                    })
+                    .map(|(album, still)| still.to_json(&album.name))
+                    .collect();
+                still_count += stills.len();
+
+                let placement = item.placement.as_ref();
+                json!({
+                    "index": index + 1,
+                    "timeline_item_id": item.id,
+                    "clip_name": item.clip_name,
+                    "track": placement.map(|placement| format!("V{}", placement.track_index)),
+                    "record_in": placement.map(|placement| rate.frames_to_timecode(start_frame + placement.record_in)),
+                    "record_out": placement.map(|placement| rate.frames_to_timecode(start_frame + placement.record_out())),
+                    "graded": grade.is_some(),
+                    "wheels": values.wheels_json(),
+                    "luts": values.applied_luts,
+                    "node_count": values.node_count,
+                    "node_labels": values.node_labels_json(),
+                    "power_windows": values.power_windows_json(),
+                    "cdl": item.cdl.as_ref().map(Cdl::to_json),
+                    "stills": stills
+                })
+            })
+            .collect();
+        let generated_at = chrono::Utc::now().to_rfc3339();
+        let summary = json!({
+            "item_count": entries.len(),
+            "graded_count": graded,
+            "luts_used": luts_used,
+            "still_count": still_count
+        });
+
+        let content = if format == "html" {
+            let rows: String = entries.iter().map(html_row).collect();
+            Some(format!(
+                "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n\
+                 <title>Grade report: {title}</title>\n\
+                 <style>body {{ font-family: sans-serif; }} table {{ border-collapse: collapse; }} \
+                 th, td {{ border: 1px solid #999; padding: 4px 8px; text-align: left; }}</style>\n\
+                 </head>\n<body>\n<h1>Grade report: {title}</h1>\n\
+                 <p>Generated {generated_at}. {graded} of {count} items graded.</p>\n\
+                 <table>\n<tr><th>#</th><th>Clip</th><th>Track</th><th>Record In</th>\
+                 <th>Record Out</th><th>Nodes</th><th>Windows</th><th>LUTs</th><th>Lift</th><th>Gamma</th>\
+                 <th>Gain</th><th>Offset</th><th>CDL</th><th>Stills</th></tr>\n\
+                 {rows}</table>\n</body>\n</html>\n",
+                title = html_escape(&timeline_name),
+                generated_at = generated_at,
+                graded = graded,
+                count = entries.len(),
+                rows = rows
+            ))
+        } else {
+            None
+        };
+
+        let output_path = args["output_path"].as_str();
+        if let Some(path) = output_path {
+            let bytes = match &content {
+                Some(html) => html.clone().into_bytes(),
+                None => serde_json::to_vec_pretty(&json!({
+                    "timeline_name": timeline_name,
+                    "generated_at": generated_at,
+                    "summary": summary,
+                    "items": entries
+                }))?,
+            };
+            std::fs::write(path, bytes).map_err(|e| {
+                ResolveError::internal(format!("failed to write grade report '{}': {}", path, e))
+            })?;
+        }
+
+        Ok(json!({
+            "result": format!(
+                "Generated grade report of {} items for timeline '{}'",
+                entries.len(),
+                timeline_name
+            ),
+            "timeline_name": timeline_name,
+            "format": format,
+            "generated_at": generated_at,
+            "summary": summary,
+            "items": entries,
+            "content": content,
+            "output_path": output_path,
+            "operation_id": Uuid::new_v4().to_string()
+        }))
+    }
+}
@@ -0,0 +1,209 @@
+//! Color trace between timeline versions
+//!
+//! After a recut, the grades made on the old version of a timeline have to be
+//! brought over to the items of the new cut. trace_grades matches each item of
+//! the new cut to the item of the old version that used the same source media
+//! over the most of the same source frames, and copies that item's grade: its
+//! CDL, the .drx grade applied to it, and the clip grade when the new item
+//! uses a different media pool clip of the same source, such as a re-import.
+//!
+//! Items use the same source when they share a clip, a media file or a reel
+//! name. Source ranges are compared in source timecode when both clips have a
+//! start timecode, and in frames from the start of the media otherwise. Items
+//! of the new cut that nothing in the old version overlaps are reported and
+//! keep their grade.
+
+use serde_json::{json, Value};
+use std::cmp::Reverse;
+use uuid::Uuid;
+
+use super::embedded_timecode::timecode_frames;
+use super::{timeline_versions, ResolveBridge, StateView};
+use crate::error::{ResolveError, ResolveResult};
+use crate::timecode::FrameRate;
+
+/// Source media and source frame range of a timeline item
+struct SourceUse {
+    item_id: String,
+    clip_name: String,
+    file_path: Option<String>,
+    reel_name: Option<String>,
+    /// Source frames used, counted from the start of the media
+    start: i64,
+    end: i64,
+    /// Start timecode of the media in frames, when known
+    timecode_start: Option<i64>,
+    record_in: i64,
+}
+
+impl SourceUse {
+    fn same_source(&self, other: &SourceUse) -> bool {
+        self.clip_name == other.clip_name
+            || (self.file_path.is_some() && self.file_path == other.file_path)
+            || (self.reel_name.is_some() && self.reel_name == other.reel_name)
+    }
+
+    /// Source frames both items use
+    fn overlap(&self, other: &SourceUse) -> i64 {
+        if !self.same_source(other) {
+            return 0;
+        }
+        let (own, others) = match (self.timecode_start, other.timecode_start) {
+            (Some(own), Some(others)) => (own, others),
+            _ => (0, 0),
+        };
+        ((self.end + own).min(other.end + others) - (self.start + own).max(other.start + others))
+            .max(0)
+    }
+}
+
+/// Source uses of the picture items on a timeline, in record order
+fn source_uses(state: &StateView<'_>, timeline_name: &str, rate: FrameRate) -> Vec<SourceUse> {
+    let mut uses: Vec<SourceUse> = state
+        .timeline_items
+        .items
+        .values()
+        .filter(|item| item.timeline_name == timeline_name && item.generator.is_none())
+        .filter_map(|item| {
+            let placement = item.placement.as_ref()?;
+            let clip = state.media_pool.clips.get(&item.clip_name);
+            Some(SourceUse {
+                item_id: item.id.clone(),
+                clip_name: item.clip_name.clone(),
+                file_path: clip.map(|clip| clip.file_path.clone()),
+                reel_name: clip.and_then(|clip| clip.reel_name.clone()),
+                start: placement.source_in,
+                end: placement.source_in + placement.duration,
+                timecode_start: clip
+                    .and_then(|clip| clip.start_timecode.as_deref())
+                    .and_then(|timecode| timecode_frames(timecode, rate).ok()),
+                record_in: placement.record_in,
+            })
+        })
+        .collect();
+    uses.sort_by(|a, b| (a.record_in, &a.item_id).cmp(&(b.record_in, &b.item_id)));
+    uses
+}
+
+impl ResolveBridge {
+    pub(super) async fn trace_grades(
+        &self,
+        state: &mut StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        let dry_run = args["dry_run"].as_bool().unwrap_or(false);
+        let target_name = Self::resolve_timeline_name(state, &args)?;
+        let source_name = match args["source_timeline_name"].as_str() {
+            Some(reference) => Self::resolve_timeline(state, reference)?,
+            None => timeline_versions::parent_timeline(state, &state.timelines[&target_name])
+                .ok_or_else(|| {
+                    ResolveError::invalid_parameter(
+                        "source_timeline_name",
+                        format!(
+                            "timeline '{}' was not versioned from another; name the old version",
+                            target_name
+                        ),
+                    )
+                })?,
+        };
+        if source_name == target_name {
+            return Err(ResolveError::invalid_parameter(
+                "source_timeline_name",
+                "must be a different timeline from the one traced to",
+            ));
+        }
+        let rate = self.project_frame_rate()?;
+        let old_cut = source_uses(state, &source_name, rate);
+        let new_cut = source_uses(state, &target_name, rate);
+
+        let mut traced = Vec::new();
+        let mut unmatched = Vec::new();
+        for new in &new_cut {
+            // The longest overlap wins, then the earliest in the old cut
+            let best = old_cut
+                .iter()
+                .map(|old| (old, old.overlap(new)))
+                .filter(|(_, overlap)| *overlap > 0)
+                .max_by_key(|(old, overlap)| (*overlap, Reverse(old.record_in)));
+            let Some((old, overlap)) = best else {
+                unmatched.push(json!({
+                    "timeline_item_id": new.item_id,
+                    "clip_name": new.clip_name,
+                    "record_in": new.record_in
+                }));
+                continue;
+            };
+
+            let source_item = &state.timeline_items.items[&old.item_id];
+            let cdl = source_item.cdl.clone();
+            let drx_grade = source_item.drx_grade.clone();
+            let clip_grade = (old.clip_name != new.clip_name)
+                .then(|| state.color_state.clip_grades.get(&old.clip_name).cloned())
+                .flatten();
+            let mut copied = Vec::new();
+            if cdl.is_some() {
+                copied.push("cdl");
+            }
+            if drx_grade.is_some() {
+                copied.push("drx_grade");
+            }
+            if clip_grade.is_some() {
+                copied.push("clip_grade");
+            }
+            if !dry_run {
+                if let Some(item) = state.timeline_items.items.get_mut(&new.item_id) {
+                    if cdl.is_some() {
+                        item.cdl = cdl;
+                    }
+                    if drx_grade.is_some() {
+                        item.drx_grade = drx_grade;
+                    }
+                }
+                if let Some(grade) = clip_grade {
+                    state
+                        .color_state
+                        .clip_grades
+                        .insert(new.clip_name.clone(), grade);
+                }
+            }
+            traced.push(json!({
+                "timeline_item_id": new.item_id,
+                "clip_name": new.clip_name,
+                "source_timeline_item_id": old.item_id,
+                "source_clip_name": old.clip_name,
+                "overlap_frames": overlap,
+                "coverage": ((overlap as f64 / (new.end - new.start).max(1) as f64) * 1000.0)
+                    .round()
+                    / 1000.0,
+                "copied": copied
+            }));
+        }
+        let used: Vec<&str> = traced
+            .iter()
+            .filter_map(|trace| trace["source_timeline_item_id"].as_str())
+            .collect();
+        let untraced: Vec<&String> = old_cut
+            .iter()
+            .map(|old| &old.item_id)
+            .filter(|id| !used.contains(&id.as_str()))
+            .collect();
+
+        Ok(json!({
+            "result": format!(
+                "{} grades from '{}' to {} of {} items on '{}'",
+                if dry_run { "Would trace" } else { "Traced" },
+                source_name,
+                traced.len(),
+                new_cut.len(),
+                target_name
+            ),
+            "source_timeline": source_name,
+            "timeline_name": target_name,
+            "dry_run": dry_run,
+            "traced": traced,
+            "unmatched": unmatched,
+            "untraced_source_items": untraced,
+            "operation_id": Uuid::new_v4().to_string()
+        }))
+    }
+}
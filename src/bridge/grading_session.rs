@@ -0,0 +1,470 @@
+//! Grading sessions
+//!
+//! start_grading_session opens a session that every grading call made until
+//! end_grading_session joins: calls that write the color state, and color
+//! tools that write anything. Each call is kept with its arguments and, in
+//! simulation, the entities it changed, captured the way `return_diff`
+//! captures them. Ending the session folds those calls into an artifact: the
+//! clips touched, each parameter changed with its value before the session
+//! and after it, the presets applied and to which clips, and a summary in a
+//! few plain sentences for a colorist's notes or a client email.
+//!
+//! Ended sessions are kept until the server restarts and read back with
+//! get_grading_session; end_grading_session can also write the artifact to a
+//! JSON file to keep it longer. Only one session is open at a time.
+
+use chrono::{DateTime, Utc};
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, BTreeSet};
+use uuid::Uuid;
+
+use super::locking::{Domain, LockPlan, StateView};
+use super::state_diff::{diff_fields, Snapshot};
+use super::{ClipGrade, ResolveBridge};
+use crate::error::{ResolveError, ResolveResult};
+use crate::tools::registry;
+
+/// One grading call made during a session
+#[derive(Debug)]
+struct Operation {
+    tool: String,
+    at: DateTime<Utc>,
+    arguments: Value,
+    result: Value,
+    changes: Vec<Value>,
+}
+
+/// The open session
+#[derive(Debug)]
+struct Session {
+    id: String,
+    name: String,
+    notes: Option<String>,
+    timeline: Option<String>,
+    started_at: DateTime<Utc>,
+    operations: Vec<Operation>,
+}
+
+/// The open session, if any, and the artifacts of ended ones, oldest first
+#[derive(Debug, Default)]
+pub(super) struct GradingSessions {
+    active: Option<Session>,
+    ended: Vec<Value>,
+}
+
+/// A grading call about to run: the state before it and its arguments
+pub(super) struct PendingOperation {
+    before: Snapshot,
+    arguments: Value,
+}
+
+/// Whether a call is grading work a session records
+fn is_grading(method: &str, plan: LockPlan) -> bool {
+    if plan.is_read_only() || method.ends_with("_grading_session") {
+        return false;
+    }
+    plan.writes().contains(&Domain::Color)
+        || registry::find(method).is_some_and(|spec| spec.category == "color")
+}
+
+/// Changed fields of one entity change, whatever kind of change it was; a
+/// clip graded for the first time is compared with the default grade it had
+fn changed_fields(change: &Value) -> Vec<Value> {
+    let empty = json!({});
+    let mut fields = Vec::new();
+    match change["change"].as_str() {
+        Some("added") if change["entity"] == "clip_grade" => {
+            let default = serde_json::to_value(ClipGrade::default()).unwrap_or(empty);
+            diff_fields("", &default, &change["after"], &mut fields)
+        }
+        Some("added") => diff_fields("", &empty, &change["after"], &mut fields),
+        Some("removed") => diff_fields("", &change["before"], &empty, &mut fields),
+        _ => fields = change["fields"].as_array().cloned().unwrap_or_default(),
+    }
+    fields
+}
+
+/// Preset a call applied and the clips it applied it to, if it applied one
+fn applied_preset(operation: &Operation) -> Option<(String, Vec<String>)> {
+    let result = &operation.result;
+    let preset = result["preset_name"]
+        .as_str()
+        .or_else(|| result["source"]["preset_name"].as_str())?;
+    let clips = match result["target_clip"].as_str() {
+        Some(clip) => vec![clip.to_string()],
+        None => result["results"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter(|entry| entry["status"] == "applied")
+            .filter_map(|entry| entry["clip_name"].as_str().map(str::to_string))
+            .collect(),
+    };
+    Some((preset.to_string(), clips))
+}
+
+/// Clips a call named in its arguments, for calls with no recorded changes
+fn argument_clips(arguments: &Value) -> Vec<String> {
+    let named = arguments["clip_name"].as_str().map(str::to_string);
+    let listed = arguments["clip_names"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|clip| clip.as_str().map(str::to_string));
+    named.into_iter().chain(listed).collect()
+}
+
+/// The artifact of an ended session
+fn artifact(session: Session, ended_at: DateTime<Utc>) -> Value {
+    // Net change of each parameter: its first before and last after
+    let mut parameters: BTreeMap<(String, String, String), (Value, Value)> = BTreeMap::new();
+    let mut clips: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+    let mut presets: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+    let mut tools: BTreeMap<&str, usize> = BTreeMap::new();
+
+    for operation in &session.operations {
+        *tools.entry(operation.tool.as_str()).or_default() += 1;
+        for change in &operation.changes {
+            let entity = change["entity"].as_str().unwrap_or_default().to_string();
+            let id = change["id"].as_str().unwrap_or_default().to_string();
+            if entity == "clip_grade" {
+                clips
+                    .entry(id.clone())
+                    .or_default()
+                    .insert(operation.tool.clone());
+            }
+            for field in changed_fields(change) {
+                let key = (
+                    entity.clone(),
+                    id.clone(),
+                    field["field"].as_str().unwrap_or_default().to_string(),
+                );
+                let after = field["after"].clone();
+                parameters
+                    .entry(key)
+                    .and_modify(|(_, last)| *last = after.clone())
+                    .or_insert((field["before"].clone(), after));
+            }
+        }
+        if operation.changes.is_empty() {
+            for clip in argument_clips(&operation.arguments) {
+                clips
+                    .entry(clip)
+                    .or_default()
+                    .insert(operation.tool.clone());
+            }
+        }
+        if let Some((preset, targets)) = applied_preset(operation) {
+            for clip in &targets {
+                clips
+                    .entry(clip.clone())
+                    .or_default()
+                    .insert(operation.tool.clone());
+            }
+            presets.entry(preset).or_default().extend(targets);
+        }
+    }
+    parameters.retain(|_, (before, after)| before != after);
+
+    let duration_seconds = (ended_at - session.started_at).num_seconds().max(0);
+    let mut summary = format!(
+        "Grading session '{}' ran {} minutes{} with {} grading operations.",
+        session.name,
+        duration_seconds / 60,
+        session
+            .timeline
+            .as_ref()
+            .map(|timeline| format!(" on timeline '{}'", timeline))
+            .unwrap_or_default(),
+        session.operations.len()
+    );
+    if clips.is_empty() {
+        summary.push_str(" No clips were graded.");
+    } else {
+        summary.push_str(&format!(
+            " {} clips were graded: {}.",
+            clips.len(),
+            clips.keys().cloned().collect::<Vec<_>>().join(", ")
+        ));
+    }
+    summary.push_str(&format!(" {} parameters changed", parameters.len()));
+    let changed_clip_parameters = parameters
+        .keys()
+        .filter(|(entity, _, _)| entity == "clip_grade")
+        .count();
+    if changed_clip_parameters > 0 {
+        summary.push_str(&format!(
+            ", {} of them in clip grades",
+            changed_clip_parameters
+        ));
+    }
+    summary.push('.');
+    for (preset, targets) in &presets {
+        summary.push_str(&format!(
+            " Preset '{}' was applied to {}.",
+            preset,
+            if targets.is_empty() {
+                "no clips".to_string()
+            } else {
+                targets.iter().cloned().collect::<Vec<_>>().join(", ")
+            }
+        ));
+    }
+    if let Some(notes) = &session.notes {
+        summary.push_str(&format!(" Notes: {}", notes));
+    }
+
+    json!({
+        "session_id": session.id,
+        "name": session.name,
+        "notes": session.notes,
+        "timeline": session.timeline,
+        "started_at": session.started_at.to_rfc3339(),
+        "ended_at": ended_at.to_rfc3339(),
+        "duration_seconds": duration_seconds,
+        "operation_count": session.operations.len(),
+        "tools_used": tools,
+        "clips_touched": clips
+            .iter()
+            .map(|(clip, tools)| json!({ "clip_name": clip, "tools": tools }))
+            .collect::<Vec<_>>(),
+        "parameters_changed": parameters
+            .into_iter()
+            .map(|((entity, id, field), (before, after))| json!({
+                "entity": entity,
+                "id": id,
+                "field": field,
+                "before": before,
+                "after": after
+            }))
+            .collect::<Vec<_>>(),
+        "presets_applied": presets
+            .iter()
+            .map(|(preset, targets)| json!({ "preset_name": preset, "clips": targets }))
+            .collect::<Vec<_>>(),
+        "operations": session
+            .operations
+            .iter()
+            .map(|operation| json!({
+                "tool": operation.tool,
+                "at": operation.at.to_rfc3339(),
+                "arguments": operation.arguments,
+                "result": operation.result["result"],
+                "changes": operation.changes.len()
+            }))
+            .collect::<Vec<_>>(),
+        "summary": summary
+    })
+}
+
+/// The short listing of an artifact
+fn listing(artifact: &Value) -> Value {
+    json!({
+        "session_id": artifact["session_id"],
+        "name": artifact["name"],
+        "timeline": artifact["timeline"],
+        "started_at": artifact["started_at"],
+        "ended_at": artifact["ended_at"],
+        "operation_count": artifact["operation_count"],
+        "clip_count": artifact["clips_touched"].as_array().map_or(0, Vec::len)
+    })
+}
+
+impl ResolveBridge {
+    /// Capture the state before a grading call, if a session is open
+    pub(super) async fn grading_before(
+        &self,
+        state: &StateView<'_>,
+        method: &str,
+        plan: LockPlan,
+        args: &Value,
+    ) -> Option<PendingOperation> {
+        if !is_grading(method, plan) || self.grading.lock().await.active.is_none() {
+            return None;
+        }
+        Some(PendingOperation {
+            before: Snapshot::capture(state, plan),
+            arguments: args.clone(),
+        })
+    }
+
+    /// Add a grading call that succeeded to the open session, with what it
+    /// changed since `pending` was captured
+    pub(super) async fn record_grading(
+        &self,
+        pending: PendingOperation,
+        state: &StateView<'_>,
+        plan: LockPlan,
+        method: &str,
+        result: &Value,
+    ) {
+        let changes = pending.before.changes(state, plan, method);
+        self.push_grading_operation(method, pending.arguments, result, changes)
+            .await;
+    }
+
+    /// Add a grading call real Resolve served to the open session; there is
+    /// no capture of what it changed, so only the call is kept
+    pub(super) async fn record_real_grading(&self, method: &str, args: &Value, result: &Value) {
+        if is_grading(method, Self::lock_plan(method)) {
+            self.push_grading_operation(method, args.clone(), result, Vec::new())
+                .await;
+        }
+    }
+
+    async fn push_grading_operation(
+        &self,
+        method: &str,
+        arguments: Value,
+        result: &Value,
+        changes: Vec<Value>,
+    ) {
+        if let Some(session) = self.grading.lock().await.active.as_mut() {
+            session.operations.push(Operation {
+                tool: method.to_string(),
+                at: Utc::now(),
+                arguments,
+                result: result.clone(),
+                changes,
+            });
+        }
+    }
+
+    pub(super) async fn start_grading_session(
+        &self,
+        state: &mut StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        let mut sessions = self.grading.lock().await;
+        if let Some(active) = &sessions.active {
+            return Err(ResolveError::invalid_parameter(
+                "name",
+                format!(
+                    "grading session '{}' is already open; end it with end_grading_session first",
+                    active.name
+                ),
+            ));
+        }
+        let started_at = Utc::now();
+        let id = Uuid::new_v4().to_string();
+        let name = args["name"]
+            .as_str()
+            .map(str::to_string)
+            .unwrap_or_else(|| format!("Grading {}", started_at.format("%Y-%m-%d %H:%M")));
+        let timeline = args["timeline_name"]
+            .as_str()
+            .map(str::to_string)
+            .or_else(|| state.current_timeline.clone());
+        let session = Session {
+            id: id.clone(),
+            name: name.clone(),
+            notes: args["notes"].as_str().map(str::to_string),
+            timeline: timeline.clone(),
+            started_at,
+            operations: Vec::new(),
+        };
+        sessions.active = Some(session);
+
+        Ok(json!({
+            "result": format!("Started grading session '{}'", name),
+            "session_id": id,
+            "name": name,
+            "timeline": timeline,
+            "started_at": started_at.to_rfc3339(),
+            "operation_id": Uuid::new_v4().to_string()
+        }))
+    }
+
+    pub(super) async fn end_grading_session(
+        &self,
+        _state: &mut StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        let mut sessions = self.grading.lock().await;
+        let mut session = sessions.active.take().ok_or_else(|| {
+            ResolveError::invalid_parameter("session_id", "no grading session is open")
+        })?;
+        if let Some(id) = args["session_id"].as_str().filter(|id| *id != session.id) {
+            let open = session.id.clone();
+            sessions.active = Some(session);
+            return Err(ResolveError::invalid_parameter(
+                "session_id",
+                format!(
+                    "session '{}' is not open; the open session is '{}'",
+                    id, open
+                ),
+            ));
+        }
+        if let Some(notes) = args["notes"].as_str() {
+            session.notes = Some(match session.notes.take() {
+                Some(earlier) => format!("{} {}", earlier, notes),
+                None => notes.to_string(),
+            });
+        }
+        let artifact = artifact(session, Utc::now());
+
+        let output_path = args["output_path"].as_str();
+        if let Some(path) = output_path {
+            let text = serde_json::to_string_pretty(&artifact)
+                .map_err(|e| ResolveError::internal(e.to_string()))?;
+            std::fs::write(path, text).map_err(|e| {
+                ResolveError::internal(format!("failed to write grading session '{}': {}", path, e))
+            })?;
+        }
+        sessions.ended.push(artifact.clone());
+
+        Ok(json!({
+            "result": artifact["summary"],
+            "session": artifact,
+            "output_path": output_path,
+            "operation_id": Uuid::new_v4().to_string()
+        }))
+    }
+
+    pub(super) async fn get_grading_session(
+        &self,
+        _state: &mut StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        let sessions = self.grading.lock().await;
+        let active = sessions.active.as_ref().map(|session| {
+            json!({
+                "session_id": session.id,
+                "name": session.name,
+                "timeline": session.timeline,
+                "started_at": session.started_at.to_rfc3339(),
+                "operation_count": session.operations.len()
+            })
+        });
+        let wanted = args["session_id"]
+            .as_str()
+            .or_else(|| args["name"].as_str());
+        let Some(wanted) = wanted else {
+            return Ok(json!({
+                "result": format!("{} ended grading sessions", sessions.ended.len()),
+                "active": active,
+                "sessions": sessions.ended.iter().map(listing).collect::<Vec<_>>(),
+                "operation_id": Uuid::new_v4().to_string()
+            }));
+        };
+
+        // The latest session of a name, since names need not be unique
+        let artifact = sessions
+            .ended
+            .iter()
+            .rev()
+            .find(|artifact| artifact["session_id"] == wanted || artifact["name"] == wanted)
+            .ok_or_else(|| {
+                ResolveError::invalid_parameter(
+                    "session_id",
+                    format!("no ended grading session '{}'", wanted),
+                )
+            })?;
+        Ok(json!({
+            "result": artifact["summary"],
+            "active": active,
+            "session": artifact,
+            "operation_id": Uuid::new_v4().to_string()
+        }))
+    }
+}
@@ -0,0 +1,292 @@
+//! Who is calling the server
+//!
+//! Editors sharing a server are told apart by the MCP client they connect
+//! with, or by a token from the `[users]` config given to `identify`; a token
+//! takes precedence over the client. Calls that change the project, and cloud
+//! operations, are recorded in an audit log under the current user. A
+//! configured user's default bin and render preset fill in calls that leave
+//! them out, their locale is used when the client asks for none, and review
+//! notes without an author are signed with the user.
+//! Every tool call, read or write, is also counted in the tool usage
+//! statistics kept with the log.
+
+use serde_json::{json, Value};
+use std::collections::VecDeque;
+use uuid::Uuid;
+
+use super::tool_stats::ToolStats;
+use super::{ResolveBridge, StateView};
+use crate::config::UserConfig;
+use crate::error::{ResolveError, ResolveResult};
+
+/// Cloud calls are audited even though they leave the local state alone
+const CLOUD_METHODS: [&str; 6] = [
+    "create_cloud_project",
+    "import_cloud_project",
+    "restore_cloud_project",
+    "export_project_to_cloud",
+    "add_user_to_cloud_project",
+    "remove_user_from_cloud_project",
+];
+
+/// How the current user was identified
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum IdentitySource {
+    /// MCP client info sent when the session started
+    Client,
+    /// Token given to `identify`
+    Token,
+}
+
+impl IdentitySource {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Client => "client",
+            Self::Token => "token",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Identity {
+    user: String,
+    source: IdentitySource,
+    /// Whether the user is in the `[users]` config
+    configured: bool,
+}
+
+#[derive(Debug, Clone)]
+struct AuditEntry {
+    sequence: u64,
+    at: chrono::DateTime<chrono::Utc>,
+    user: Option<String>,
+    method: String,
+    result: Option<String>,
+}
+
+impl AuditEntry {
+    fn to_json(&self) -> Value {
+        json!({
+            "sequence": self.sequence,
+            "at": self.at.to_rfc3339(),
+            "user": self.user,
+            "method": self.method,
+            "result": self.result
+        })
+    }
+}
+
+/// Current user and the audit log
+#[derive(Debug, Default)]
+pub(super) struct IdentityStore {
+    /// Client name sent when the session started
+    client: Option<String>,
+    current: Option<Identity>,
+    audit: VecDeque<AuditEntry>,
+    next_sequence: u64,
+    /// Calls, errors and latency of each tool
+    pub(super) tool_stats: ToolStats,
+}
+
+impl ResolveBridge {
+    /// Identify the caller by the MCP client name sent at initialization.
+    /// A user identified by token keeps that identity.
+    pub async fn identify_client(&self, client_name: &str) {
+        let mut store = self.identity.lock().await;
+        store.client = Some(client_name.to_string());
+        if store
+            .current
+            .as_ref()
+            .is_some_and(|identity| identity.source == IdentitySource::Token)
+        {
+            return;
+        }
+        // Unknown clients are still told apart by name
+        let configured = self
+            .config
+            .users
+            .iter()
+            .find(|(_, user)| user.clients.iter().any(|client| client == client_name));
+        store.current = Some(Identity {
+            user: configured.map_or(client_name, |(name, _)| name).to_string(),
+            source: IdentitySource::Client,
+            configured: configured.is_some(),
+        });
+    }
+
+    /// Locale configured for the current user, if any
+    pub async fn user_locale(&self) -> Option<String> {
+        let store = self.identity.lock().await;
+        let identity = store.current.as_ref()?;
+        self.user_config(identity)?.locale.clone()
+    }
+
+    /// Config of an identified user, if they are configured
+    fn user_config(&self, identity: &Identity) -> Option<&UserConfig> {
+        self.config
+            .users
+            .get(&identity.user)
+            .filter(|_| identity.configured)
+    }
+
+    /// Fill in arguments a call leaves to the current user
+    pub(super) async fn apply_user_defaults(&self, method: &str, args: &mut Value) {
+        let Some(identity) = self.identity.lock().await.current.clone() else {
+            return;
+        };
+        if !args.is_object() {
+            return;
+        }
+        let user_config = self.user_config(&identity);
+        let (field, value) = match method {
+            "add_review_note" | "create_timeline_version" => {
+                ("author", Some(identity.user.as_str()))
+            }
+            "import_media" => (
+                "bin_name",
+                user_config.and_then(|user| user.default_bin.as_deref()),
+            ),
+            "add_to_render_queue" => (
+                "preset_name",
+                user_config.and_then(|user| user.default_render_preset.as_deref()),
+            ),
+            _ => return,
+        };
+        if let Some(value) = value.filter(|_| args[field].is_null()) {
+            args[field] = json!(value);
+        }
+    }
+
+    /// Record a successful call in the audit log if it changed anything
+    pub(super) async fn audit(&self, method: &str, result: &Value) {
+        let writes = !Self::lock_plan(method).is_read_only();
+        if !writes && !CLOUD_METHODS.contains(&method) {
+            return;
+        }
+        let mut store = self.identity.lock().await;
+        store.next_sequence += 1;
+        let entry = AuditEntry {
+            sequence: store.next_sequence,
+            at: chrono::Utc::now(),
+            user: store.current.as_ref().map(|identity| identity.user.clone()),
+            method: method.to_string(),
+            result: result["result"].as_str().map(str::to_string),
+        };
+        store.audit.push_back(entry);
+        let max_entries = self.config.limits.max_audit_entries;
+        while store.audit.len() > max_entries {
+            store.audit.pop_front();
+        }
+    }
+
+    pub(super) async fn identify(
+        &self,
+        _state: &StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        let token = args["token"]
+            .as_str()
+            .ok_or_else(|| ResolveError::invalid_parameter("token", "required string"))?;
+        let (user, _) = self
+            .config
+            .users
+            .iter()
+            .find(|(_, user)| {
+                user.token
+                    .as_ref()
+                    .and_then(|secret| secret.reveal().ok())
+                    .is_some_and(|secret| secret == token)
+            })
+            .ok_or_else(|| {
+                ResolveError::invalid_parameter("token", "does not belong to a configured user")
+            })?;
+
+        let mut store = self.identity.lock().await;
+        let previous = store
+            .current
+            .replace(Identity {
+                user: user.clone(),
+                source: IdentitySource::Token,
+                configured: true,
+            })
+            .map(|identity| identity.user);
+        Ok(json!({
+            "result": format!("Identified as '{}'", user),
+            "user": user,
+            "previous_user": previous,
+            "operation_id": Uuid::new_v4().to_string()
+        }))
+    }
+
+    pub(super) async fn get_current_user(
+        &self,
+        _state: &StateView<'_>,
+        _args: Value,
+    ) -> ResolveResult<Value> {
+        let store = self.identity.lock().await;
+        let client = store.client.clone();
+        let identity = store.current.clone();
+        drop(store);
+        let defaults = identity
+            .as_ref()
+            .and_then(|identity| self.user_config(identity))
+            .map(|user| {
+                json!({
+                    "bin": user.default_bin,
+                    "render_preset": user.default_render_preset
+                })
+            });
+
+        Ok(json!({
+            "result": match &identity {
+                Some(identity) => format!("Calls are made as '{}'", identity.user),
+                None => "No user has been identified".to_string(),
+            },
+            "user": identity.as_ref().map(|identity| &identity.user),
+            "source": identity.as_ref().map(|identity| identity.source.as_str()),
+            "configured": identity.as_ref().is_some_and(|identity| identity.configured),
+            "client": client,
+            "defaults": defaults,
+            "operation_id": Uuid::new_v4().to_string()
+        }))
+    }
+
+    pub(super) async fn get_audit_log(
+        &self,
+        _state: &StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        let user = args["user"].as_str();
+        let method = args["method"].as_str();
+        let limit = match args["limit"].as_u64() {
+            Some(0) => {
+                return Err(ResolveError::invalid_parameter(
+                    "limit",
+                    "must be at least 1",
+                ))
+            }
+            Some(limit) => limit as usize,
+            None => 100,
+        };
+
+        // The most recent matching entries, oldest first
+        let store = self.identity.lock().await;
+        let mut entries: Vec<Value> = store
+            .audit
+            .iter()
+            .rev()
+            .filter(|entry| user.is_none_or(|user| entry.user.as_deref() == Some(user)))
+            .filter(|entry| method.is_none_or(|method| entry.method == method))
+            .take(limit)
+            .map(AuditEntry::to_json)
+            .collect();
+        entries.reverse();
+
+        Ok(json!({
+            "result": format!("Found {} audit entries", entries.len()),
+            "entries": entries,
+            "count": entries.len(),
+            "operation_id": Uuid::new_v4().to_string()
+        }))
+    }
+}
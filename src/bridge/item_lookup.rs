@@ -0,0 +1,240 @@
+//! Finding timeline items and their IDs
+//!
+//! Items are found on a timeline by track and position, by the media pool
+//! clip they use, or by a record timecode such as the playhead position set
+//! with set_timeline_timecode. Every match carries the item's stable ID, which
+//! all item-level tools accept.
+
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use super::markers::timeline_start;
+use super::{ItemPlacement, ResolveBridge, StateView, TimelineItemState};
+use crate::error::{ResolveError, ResolveResult};
+use crate::timecode::FrameRate;
+
+/// Placed items on one video track of a timeline, in record order
+pub(super) fn items_on_track<'a>(
+    state: &'a StateView<'_>,
+    timeline_name: &str,
+    track_index: u64,
+) -> Vec<(&'a TimelineItemState, &'a ItemPlacement)> {
+    let mut items: Vec<(&TimelineItemState, &ItemPlacement)> = state
+        .timeline_items
+        .items
+        .values()
+        .filter(|item| item.timeline_name == timeline_name)
+        .filter_map(|item| Some((item, item.placement.as_ref()?)))
+        .filter(|(_, placement)| u64::from(placement.track_index) == track_index)
+        .collect();
+    items.sort_by_key(|(_, placement)| placement.record_in);
+    items
+}
+
+/// Simulated items all sit on video tracks
+pub(super) fn check_track_type(args: &Value) -> ResolveResult<()> {
+    match args["track_type"].as_str() {
+        Some(kind) if kind != "video" => Err(ResolveError::invalid_parameter(
+            "track_type",
+            "only video tracks hold timeline items in simulation",
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Frame rate for a timeline's timecodes and the frame its start timecode falls on
+pub(super) fn timeline_clock(
+    bridge: &ResolveBridge,
+    state: &StateView<'_>,
+    timeline_name: &str,
+    args: &Value,
+) -> ResolveResult<(FrameRate, i64)> {
+    let timeline = &state.timelines[timeline_name];
+    let start = timeline_start(timeline, args);
+    let rate = bridge
+        .timeline_frame_rate(timeline)?
+        .for_timecode(start)
+        .map_err(|e| ResolveError::invalid_parameter("timeline_start_timecode", e))?;
+    let start_frame = rate
+        .timecode_to_frames(start)
+        .map_err(|e| ResolveError::invalid_parameter("timeline_start_timecode", e))?;
+    Ok((rate, start_frame))
+}
+
+/// Timeline frame, relative to the start, that a record timecode falls on
+fn record_frame(
+    rate: FrameRate,
+    start_frame: i64,
+    param: &str,
+    timecode: &str,
+) -> ResolveResult<i64> {
+    let frame = rate
+        .for_timecode(timecode)
+        .and_then(|rate| rate.timecode_to_frames(timecode))
+        .map_err(|e| ResolveError::invalid_parameter(param, e))?
+        - start_frame;
+    if frame < 0 {
+        return Err(ResolveError::invalid_parameter(
+            param,
+            format!("{} is before the timeline start", timecode),
+        ));
+    }
+    Ok(frame)
+}
+
+impl ResolveBridge {
+    pub(super) async fn set_timeline_timecode(
+        &self,
+        state: &mut StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        let timecode = args["timecode"]
+            .as_str()
+            .ok_or_else(|| ResolveError::invalid_parameter("timecode", "parameter is required"))?;
+        let timeline_name = Self::resolve_timeline_name(state, &args)?;
+        let (rate, start_frame) = timeline_clock(self, state, &timeline_name, &args)?;
+        let frame = record_frame(rate, start_frame, "timecode", timecode)?;
+        if let Some(timeline) = state.timelines.get_mut(&timeline_name) {
+            timeline.playhead = Some(frame);
+        }
+
+        Ok(json!({
+            "result": format!("Set timeline timecode to: {}", timecode),
+            "timeline_name": timeline_name,
+            "timecode": timecode,
+            "frame": frame,
+            "operation_id": Uuid::new_v4().to_string()
+        }))
+    }
+
+    pub(super) async fn find_timeline_item(
+        &self,
+        state: &StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        let timeline_name = Self::resolve_timeline_name(state, &args)?;
+        let (rate, start_frame) = timeline_clock(self, state, &timeline_name, &args)?;
+        check_track_type(&args)?;
+
+        let criteria = [
+            !args["track_index"].is_null(),
+            !args["clip_name"].is_null(),
+            !args["timecode"].is_null(),
+            args["at_playhead"].as_bool() == Some(true),
+        ];
+        if criteria.iter().filter(|given| **given).count() != 1 {
+            return Err(ResolveError::invalid_parameter(
+                "track_index",
+                "give exactly one of track_index, clip_name, timecode or at_playhead",
+            ));
+        }
+
+        // Every placed item with its position on its track, starting at 1
+        let mut tracks: Vec<u32> = state
+            .timeline_items
+            .items
+            .values()
+            .filter(|item| item.timeline_name == timeline_name)
+            .filter_map(|item| Some(item.placement.as_ref()?.track_index))
+            .collect();
+        tracks.sort_unstable();
+        tracks.dedup();
+        let placed: Vec<(usize, &TimelineItemState, &ItemPlacement)> = tracks
+            .iter()
+            .flat_map(|&track| {
+                items_on_track(state, &timeline_name, u64::from(track))
+                    .into_iter()
+                    .enumerate()
+                    .map(|(index, (item, placement))| (index + 1, item, placement))
+            })
+            .collect();
+
+        let (found, searched_by): (Vec<_>, String) = if let Some(track_index) =
+            args["track_index"].as_u64()
+        {
+            let item_index = args["item_index"].as_u64();
+            let found = placed
+                .into_iter()
+                .filter(|(index, _, placement)| {
+                    u64::from(placement.track_index) == track_index
+                        && item_index.is_none_or(|wanted| *index as u64 == wanted)
+                })
+                .collect();
+            let searched_by = match item_index {
+                Some(index) => format!("at position {} on track {}", index, track_index),
+                None => format!("on track {}", track_index),
+            };
+            (found, searched_by)
+        } else if let Some(clip_reference) = args["clip_name"].as_str() {
+            let clip_name = Self::resolve_clip(state, clip_reference)?;
+            let clip_id = &state.media_pool.clips[&clip_name].id;
+            let found = placed
+                .into_iter()
+                .filter(|(_, item, _)| item.clip_id.as_ref() == Some(clip_id))
+                .collect();
+            (found, format!("using clip '{}'", clip_name))
+        } else {
+            let (frame, timecode) = match args["timecode"].as_str() {
+                Some(timecode) => (
+                    record_frame(rate, start_frame, "timecode", timecode)?,
+                    timecode.to_string(),
+                ),
+                None => {
+                    let frame = state.timelines[&timeline_name].playhead.ok_or_else(|| {
+                            ResolveError::invalid_parameter(
+                                "at_playhead",
+                                "the timeline has no playhead position; set one with set_timeline_timecode",
+                            )
+                        })?;
+                    (frame, rate.frames_to_timecode(start_frame + frame))
+                }
+            };
+            let found = placed
+                .into_iter()
+                .filter(|(_, _, placement)| {
+                    placement.record_in <= frame && frame < placement.record_out()
+                })
+                .collect();
+            (found, format!("at timecode {}", timecode))
+        };
+
+        if args["track_index"].is_u64() && args["item_index"].is_u64() && found.is_empty() {
+            return Err(ResolveError::invalid_parameter(
+                "item_index",
+                format!("no timeline item {}", searched_by),
+            ));
+        }
+        let items: Vec<Value> = found
+            .iter()
+            .map(|(index, item, placement)| {
+                json!({
+                    "timeline_item_id": item.id,
+                    "clip_name": item.clip_name,
+                    "clip_id": item.clip_id,
+                    "track": format!("Video {}", placement.track_index),
+                    "track_index": placement.track_index,
+                    "item_index": index,
+                    "record_in": placement.record_in,
+                    "record_out": placement.record_out(),
+                    "record_in_timecode": rate.frames_to_timecode(start_frame + placement.record_in),
+                    "record_out_timecode": rate.frames_to_timecode(start_frame + placement.record_out()),
+                    "source_in": placement.source_in,
+                    "source_out": placement.source_out()
+                })
+            })
+            .collect();
+
+        Ok(json!({
+            "result": format!(
+                "Found {} timeline items {} in timeline '{}'",
+                items.len(),
+                searched_by,
+                timeline_name
+            ),
+            "timeline_name": timeline_name,
+            "items": items,
+            "count": items.len(),
+            "operation_id": Uuid::new_v4().to_string()
+        }))
+    }
+}
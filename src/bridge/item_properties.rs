@@ -0,0 +1,1051 @@
+//! Timeline item property registry
+//!
+//! Every timeline item property is described once here: its key, the Resolve
+//! API names it also answers to, its category, type, range and default. The
+//! granular set_timeline_item_* tools, the generic property get and set,
+//! property listing and reset, and keyframe validation all look properties up
+//! in this table, so they always agree on what exists and which values are
+//! valid. Items store only the values that were set; everything else reads as
+//! the registry default.
+
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::fmt;
+use uuid::Uuid;
+
+use super::item_lookup::{check_track_type, items_on_track};
+use super::{ResolveBridge, StateView, TimelineItemState, SCRIPT_TIMELINE};
+use crate::error::{ResolveError, ResolveResult};
+
+/// Group a property belongs to, as reported by get_timeline_item_properties
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) enum Category {
+    Transform,
+    Crop,
+    Composite,
+    Retime,
+    Sizing,
+    Stabilization,
+    Audio,
+}
+
+impl Category {
+    pub(super) const ALL: [Category; 7] = [
+        Category::Transform,
+        Category::Crop,
+        Category::Composite,
+        Category::Retime,
+        Category::Sizing,
+        Category::Stabilization,
+        Category::Audio,
+    ];
+
+    pub(super) fn name(self) -> &'static str {
+        match self {
+            Category::Transform => "transform",
+            Category::Crop => "crop",
+            Category::Composite => "composite",
+            Category::Retime => "retime",
+            Category::Sizing => "sizing",
+            Category::Stabilization => "stabilization",
+            Category::Audio => "audio",
+        }
+    }
+
+    pub(super) fn parse(name: &str) -> Option<Category> {
+        Self::ALL
+            .into_iter()
+            .find(|category| category.name() == name)
+    }
+}
+
+/// Value type, valid range and default of a property
+#[derive(Debug)]
+enum Kind {
+    /// Number within an inclusive range; unbounded ends are None
+    Number {
+        min: Option<f64>,
+        max: Option<f64>,
+        default: f64,
+    },
+    Bool {
+        default: bool,
+    },
+    /// One of a fixed set of names
+    Choice {
+        choices: &'static [&'static str],
+        /// Resolve API constant for each choice, in the same order
+        constants: &'static [&'static str],
+        default: &'static str,
+    },
+}
+
+/// Value of one property on one item
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(untagged)]
+pub(super) enum PropertyValue {
+    Number(f64),
+    Bool(bool),
+    Choice(&'static str),
+}
+
+impl fmt::Display for PropertyValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PropertyValue::Number(value) => write!(f, "{}", value),
+            PropertyValue::Bool(value) => write!(f, "{}", value),
+            PropertyValue::Choice(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+impl PropertyValue {
+    pub(super) fn to_json(&self) -> Value {
+        match self {
+            PropertyValue::Number(value) => json!(value),
+            PropertyValue::Bool(value) => json!(value),
+            PropertyValue::Choice(value) => json!(value),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub(super) struct ItemProperty {
+    /// Name used by the granular tools and keyframes
+    pub(super) key: &'static str,
+    /// Resolve's GetProperty and SetProperty key, if Resolve exposes the property
+    resolve: Option<ResolveKey>,
+    /// Other names accepted for the property, such as Resolve API keys
+    aliases: &'static [&'static str],
+    pub(super) category: Category,
+    /// Name within its category in get_timeline_item_properties
+    field: &'static str,
+    kind: Kind,
+    /// Whether the property can be animated with keyframes
+    pub(super) keyframable: bool,
+}
+
+/// How a value in registry units converts to Resolve's units
+#[derive(Debug, Clone, Copy)]
+enum Scale {
+    Same,
+    /// Multiplied by a fixed factor, like opacity's 0 to 100
+    Factor(f64),
+    /// Fraction of the timeline width or height, in pixels
+    Width,
+    Height,
+}
+
+#[derive(Debug)]
+struct ResolveKey {
+    key: &'static str,
+    scale: Scale,
+}
+
+impl ResolveKey {
+    /// Key and unit conversion as sent to the Resolve script
+    fn to_json(&self) -> Value {
+        let scale = match self.scale {
+            Scale::Same => json!(1.0),
+            Scale::Factor(factor) => json!(factor),
+            Scale::Width => json!("width"),
+            Scale::Height => json!("height"),
+        };
+        json!({ "key": self.key, "scale": scale })
+    }
+}
+
+const fn resolve(key: &'static str) -> Option<ResolveKey> {
+    scaled(key, Scale::Same)
+}
+
+const fn scaled(key: &'static str, scale: Scale) -> Option<ResolveKey> {
+    Some(ResolveKey { key, scale })
+}
+
+const COMPOSITE_MODES: &[&str] = &[
+    "Normal",
+    "Add",
+    "Multiply",
+    "Screen",
+    "Overlay",
+    "SoftLight",
+    "HardLight",
+    "ColorDodge",
+    "ColorBurn",
+    "Darken",
+    "Lighten",
+    "Difference",
+    "Exclusion",
+];
+const COMPOSITE_CONSTANTS: &[&str] = &[
+    "COMPOSITE_NORMAL",
+    "COMPOSITE_ADD",
+    "COMPOSITE_MULTIPLY",
+    "COMPOSITE_SCREEN",
+    "COMPOSITE_OVERLAY",
+    "COMPOSITE_SOFTLIGHT",
+    "COMPOSITE_HARDLIGHT",
+    "COMPOSITE_COLOR_DODGE",
+    "COMPOSITE_COLOR_BURN",
+    "COMPOSITE_DARKEN",
+    "COMPOSITE_LIGHTEN",
+    "COMPOSITE_DIFF",
+    "COMPOSITE_EXCLUSION",
+];
+/// UseProject follows the project's retime setting, Resolve's default for new items
+const RETIME_PROCESSES: &[&str] = &["UseProject", "NearestFrame", "FrameBlend", "OpticalFlow"];
+const RETIME_CONSTANTS: &[&str] = &[
+    "RETIME_USE_PROJECT",
+    "RETIME_NEAREST",
+    "RETIME_FRAME_BLEND",
+    "RETIME_OPTICAL_FLOW",
+];
+/// How the source fills the output frame; UseProject follows the project's
+/// mismatched resolution setting
+const SCALING_MODES: &[&str] = &["UseProject", "Crop", "Fit", "Fill", "Stretch"];
+const SCALING_CONSTANTS: &[&str] = &[
+    "SCALE_USE_PROJECT",
+    "SCALE_CROP",
+    "SCALE_FIT",
+    "SCALE_FILL",
+    "SCALE_STRETCH",
+];
+const SUPER_SCALE_FACTORS: &[&str] = &["2x", "3x", "4x"];
+const STABILIZATION_METHODS: &[&str] = &["Perspective", "Similarity", "Translation"];
+
+const fn number(
+    key: &'static str,
+    resolve: Option<ResolveKey>,
+    aliases: &'static [&'static str],
+    category: Category,
+    field: &'static str,
+    (min, max): (Option<f64>, Option<f64>),
+    default: f64,
+) -> ItemProperty {
+    ItemProperty {
+        key,
+        resolve,
+        aliases,
+        category,
+        field,
+        kind: Kind::Number { min, max, default },
+        keyframable: true,
+    }
+}
+
+const UNBOUNDED: (Option<f64>, Option<f64>) = (None, None);
+const UNIT: (Option<f64>, Option<f64>) = (Some(0.0), Some(1.0));
+
+/// Every timeline item property, in the order they are listed
+static PROPERTIES: &[ItemProperty] = &[
+    number(
+        "Pan",
+        resolve("Pan"),
+        &[],
+        Category::Transform,
+        "pan",
+        UNBOUNDED,
+        0.0,
+    ),
+    number(
+        "Tilt",
+        resolve("Tilt"),
+        &[],
+        Category::Transform,
+        "tilt",
+        UNBOUNDED,
+        0.0,
+    ),
+    number(
+        "ZoomX",
+        resolve("ZoomX"),
+        &[],
+        Category::Transform,
+        "zoom_x",
+        (Some(0.0), Some(100.0)),
+        1.0,
+    ),
+    number(
+        "ZoomY",
+        resolve("ZoomY"),
+        &[],
+        Category::Transform,
+        "zoom_y",
+        (Some(0.0), Some(100.0)),
+        1.0,
+    ),
+    number(
+        "Rotation",
+        resolve("RotationAngle"),
+        &["RotationAngle"],
+        Category::Transform,
+        "rotation",
+        (Some(-360.0), Some(360.0)),
+        0.0,
+    ),
+    number(
+        "AnchorPointX",
+        resolve("AnchorPointX"),
+        &[],
+        Category::Transform,
+        "anchor_point_x",
+        UNBOUNDED,
+        0.0,
+    ),
+    number(
+        "AnchorPointY",
+        resolve("AnchorPointY"),
+        &[],
+        Category::Transform,
+        "anchor_point_y",
+        UNBOUNDED,
+        0.0,
+    ),
+    number(
+        "Pitch",
+        resolve("Pitch"),
+        &[],
+        Category::Transform,
+        "pitch",
+        (Some(-1.5), Some(1.5)),
+        0.0,
+    ),
+    number(
+        "Yaw",
+        resolve("Yaw"),
+        &[],
+        Category::Transform,
+        "yaw",
+        (Some(-1.5), Some(1.5)),
+        0.0,
+    ),
+    number(
+        "Left",
+        scaled("CropLeft", Scale::Width),
+        &["CropLeft"],
+        Category::Crop,
+        "left",
+        UNIT,
+        0.0,
+    ),
+    number(
+        "Right",
+        scaled("CropRight", Scale::Width),
+        &["CropRight"],
+        Category::Crop,
+        "right",
+        UNIT,
+        0.0,
+    ),
+    number(
+        "Top",
+        scaled("CropTop", Scale::Height),
+        &["CropTop"],
+        Category::Crop,
+        "top",
+        UNIT,
+        0.0,
+    ),
+    number(
+        "Bottom",
+        scaled("CropBottom", Scale::Height),
+        &["CropBottom"],
+        Category::Crop,
+        "bottom",
+        UNIT,
+        0.0,
+    ),
+    ItemProperty {
+        key: "CompositeMode",
+        resolve: resolve("CompositeMode"),
+        aliases: &[],
+        category: Category::Composite,
+        field: "mode",
+        kind: Kind::Choice {
+            choices: COMPOSITE_MODES,
+            constants: COMPOSITE_CONSTANTS,
+            default: "Normal",
+        },
+        keyframable: false,
+    },
+    number(
+        "Opacity",
+        scaled("Opacity", Scale::Factor(100.0)),
+        &[],
+        Category::Composite,
+        "opacity",
+        UNIT,
+        1.0,
+    ),
+    number(
+        "Speed",
+        None,
+        &["RetimeSpeed"],
+        Category::Retime,
+        "speed",
+        (Some(0.01), Some(10.0)),
+        1.0,
+    ),
+    ItemProperty {
+        key: "RetimeProcess",
+        resolve: resolve("RetimeProcess"),
+        aliases: &[],
+        category: Category::Retime,
+        field: "process",
+        kind: Kind::Choice {
+            choices: RETIME_PROCESSES,
+            constants: RETIME_CONSTANTS,
+            default: "NearestFrame",
+        },
+        keyframable: false,
+    },
+    ItemProperty {
+        key: "Scaling",
+        resolve: resolve("Scaling"),
+        aliases: &["ScalingMode", "OutputSizing"],
+        category: Category::Sizing,
+        field: "scaling",
+        kind: Kind::Choice {
+            choices: SCALING_MODES,
+            constants: SCALING_CONSTANTS,
+            default: "UseProject",
+        },
+        keyframable: false,
+    },
+    ItemProperty {
+        key: "SuperScale",
+        resolve: None,
+        aliases: &["Super Scale"],
+        category: Category::Sizing,
+        field: "super_scale",
+        kind: Kind::Bool { default: false },
+        keyframable: false,
+    },
+    ItemProperty {
+        key: "SuperScaleFactor",
+        resolve: None,
+        aliases: &[],
+        category: Category::Sizing,
+        field: "super_scale_factor",
+        kind: Kind::Choice {
+            choices: SUPER_SCALE_FACTORS,
+            constants: &[],
+            default: "2x",
+        },
+        keyframable: false,
+    },
+    number(
+        "LensCorrection",
+        resolve("Distortion"),
+        &["Distortion"],
+        Category::Sizing,
+        "lens_correction",
+        (Some(-1.0), Some(1.0)),
+        0.0,
+    ),
+    ItemProperty {
+        key: "StabilizationEnabled",
+        resolve: None,
+        aliases: &["Stabilization"],
+        category: Category::Stabilization,
+        field: "enabled",
+        kind: Kind::Bool { default: false },
+        keyframable: false,
+    },
+    ItemProperty {
+        key: "StabilizationMethod",
+        resolve: None,
+        aliases: &[],
+        category: Category::Stabilization,
+        field: "method",
+        kind: Kind::Choice {
+            choices: STABILIZATION_METHODS,
+            constants: &[],
+            default: "Perspective",
+        },
+        keyframable: false,
+    },
+    number(
+        "Strength",
+        None,
+        &["StabilizationStrength"],
+        Category::Stabilization,
+        "strength",
+        UNIT,
+        0.5,
+    ),
+    number(
+        "Volume",
+        None,
+        &[],
+        Category::Audio,
+        "volume",
+        (Some(0.0), Some(2.0)),
+        1.0,
+    ),
+    number(
+        "AudioPan",
+        None,
+        &[],
+        Category::Audio,
+        "pan",
+        (Some(-1.0), Some(1.0)),
+        0.0,
+    ),
+    ItemProperty {
+        key: "AudioEQEnabled",
+        resolve: None,
+        aliases: &["EqEnabled"],
+        category: Category::Audio,
+        field: "eq_enabled",
+        kind: Kind::Bool { default: false },
+        keyframable: false,
+    },
+];
+
+/// Property with this key or alias, ignoring case
+pub(super) fn lookup(name: &str) -> Option<&'static ItemProperty> {
+    PROPERTIES.iter().find(|property| {
+        property.key.eq_ignore_ascii_case(name)
+            || property
+                .aliases
+                .iter()
+                .any(|alias| alias.eq_ignore_ascii_case(name))
+    })
+}
+
+/// Property with this key or alias, or an error naming `param`
+pub(super) fn require(param: &str, name: &str) -> ResolveResult<&'static ItemProperty> {
+    lookup(name).ok_or_else(|| {
+        ResolveError::invalid_parameter(
+            param,
+            format!(
+                "unknown timeline item property '{}'; see list_timeline_item_properties",
+                name
+            ),
+        )
+    })
+}
+
+/// Properties set through the given arguments of a granular tool, skipping absent ones
+pub(super) fn changes_from_args<'a>(
+    args: &'a Value,
+    params: &[(&'a str, &str)],
+) -> ResolveResult<Vec<(&'a str, &'static ItemProperty, &'a Value)>> {
+    params
+        .iter()
+        .filter(|(param, _)| !args[*param].is_null())
+        .map(|&(param, key)| Ok((param, require(param, key)?, &args[param])))
+        .collect()
+}
+
+/// Registry key for a property name, or the name itself if it is not registered
+pub(super) fn canonical_key(name: &str) -> &str {
+    lookup(name).map_or(name, |property| property.key)
+}
+
+impl ItemProperty {
+    pub(super) fn default_value(&self) -> PropertyValue {
+        match self.kind {
+            Kind::Number { default, .. } => PropertyValue::Number(default),
+            Kind::Bool { default } => PropertyValue::Bool(default),
+            Kind::Choice { default, .. } => PropertyValue::Choice(default),
+        }
+    }
+
+    /// Check a value against the property's type and range; errors name `param`
+    pub(super) fn parse(&self, param: &str, value: &Value) -> ResolveResult<PropertyValue> {
+        match self.kind {
+            Kind::Number { min, max, .. } => {
+                let number = value.as_f64().ok_or_else(|| {
+                    ResolveError::invalid_parameter(param, format!("{} must be a number", self.key))
+                })?;
+                let out_of_range =
+                    min.is_some_and(|min| number < min) || max.is_some_and(|max| number > max);
+                if out_of_range {
+                    return Err(ResolveError::invalid_parameter(
+                        param,
+                        format!("{} must be {}", self.key, self.range_text()),
+                    ));
+                }
+                Ok(PropertyValue::Number(number))
+            }
+            Kind::Bool { .. } => value.as_bool().map(PropertyValue::Bool).ok_or_else(|| {
+                ResolveError::invalid_parameter(param, format!("{} must be a boolean", self.key))
+            }),
+            Kind::Choice { choices, .. } => value
+                .as_str()
+                .and_then(|name| {
+                    choices
+                        .iter()
+                        .find(|choice| choice.eq_ignore_ascii_case(name))
+                })
+                .map(|choice| PropertyValue::Choice(choice))
+                .ok_or_else(|| {
+                    ResolveError::invalid_parameter(
+                        param,
+                        format!("{} must be one of {}", self.key, choices.join(", ")),
+                    )
+                }),
+        }
+    }
+
+    /// Resolve constants the property's choices map to
+    fn constants(&self) -> &'static [&'static str] {
+        match self.kind {
+            Kind::Choice { constants, .. } => constants,
+            _ => &[],
+        }
+    }
+
+    /// Resolve constant for a choice value, if the property has one
+    fn constant_for(&self, value: &PropertyValue) -> Option<&'static str> {
+        let (
+            Kind::Choice {
+                choices, constants, ..
+            },
+            PropertyValue::Choice(name),
+        ) = (&self.kind, value)
+        else {
+            return None;
+        };
+        let position = choices.iter().position(|choice| choice == name)?;
+        constants.get(position).copied()
+    }
+
+    /// Value read from Resolve, given the values of the Resolve constants by name
+    fn read_resolve(&self, raw: &Value, constants: &Value) -> Option<PropertyValue> {
+        match self.kind {
+            Kind::Number { .. } => raw.as_f64().map(PropertyValue::Number),
+            Kind::Bool { .. } => raw.as_bool().map(PropertyValue::Bool),
+            Kind::Choice {
+                choices,
+                constants: names,
+                ..
+            } => names
+                .iter()
+                .position(|name| constants.get(*name) == Some(raw))
+                .and_then(|position| choices.get(position))
+                .map(|choice| PropertyValue::Choice(choice)),
+        }
+    }
+
+    fn range_text(&self) -> String {
+        match self.kind {
+            Kind::Number {
+                min: Some(min),
+                max: Some(max),
+                ..
+            } => format!("between {} and {}", min, max),
+            Kind::Number { min: Some(min), .. } => format!("at least {}", min),
+            Kind::Number { max: Some(max), .. } => format!("at most {}", max),
+            _ => "a number".to_string(),
+        }
+    }
+
+    fn schema(&self) -> Value {
+        let mut schema = json!({
+            "key": self.key,
+            "aliases": self.aliases,
+            "category": self.category.name(),
+            "field": self.field,
+            "resolve_key": self.resolve.as_ref().map(|resolve| resolve.key),
+            "keyframable": self.keyframable,
+            "default": self.default_value().to_json()
+        });
+        match self.kind {
+            Kind::Number { min, max, .. } => {
+                schema["type"] = json!("number");
+                schema["min"] = json!(min);
+                schema["max"] = json!(max);
+            }
+            Kind::Bool { .. } => schema["type"] = json!("boolean"),
+            Kind::Choice { choices, .. } => {
+                schema["type"] = json!("choice");
+                schema["choices"] = json!(choices);
+            }
+        }
+        schema
+    }
+}
+
+/// Property values set on one timeline item, by registry key
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(transparent)]
+pub(super) struct ItemProperties {
+    values: HashMap<&'static str, PropertyValue>,
+}
+
+impl ItemProperties {
+    pub(super) fn get(&self, property: &ItemProperty) -> PropertyValue {
+        self.values
+            .get(property.key)
+            .cloned()
+            .unwrap_or_else(|| property.default_value())
+    }
+
+    pub(super) fn set(&mut self, property: &ItemProperty, value: PropertyValue) {
+        self.values.insert(property.key, value);
+    }
+
+    /// Return one category, or every property, to its defaults
+    pub(super) fn reset(&mut self, category: Option<Category>) {
+        self.values.retain(|key, _| {
+            category.is_some_and(|category| {
+                lookup(key).is_some_and(|property| property.category != category)
+            })
+        });
+    }
+
+    /// Every property grouped by category, keyed by field name
+    pub(super) fn by_category(&self) -> Value {
+        let mut categories = serde_json::Map::new();
+        for category in Category::ALL {
+            let fields: serde_json::Map<String, Value> = PROPERTIES
+                .iter()
+                .filter(|property| property.category == category)
+                .map(|property| (property.field.to_string(), self.get(property).to_json()))
+                .collect();
+            categories.insert(category.name().to_string(), Value::Object(fields));
+        }
+        Value::Object(categories)
+    }
+}
+
+/// Defines `find_item`, by unique ID or track position
+const FIND_ITEM: &str = r#"
+def find_item(timeline, locator):
+    if "timeline_item_id" in locator:
+        for track_type in ("video", "audio", "subtitle"):
+            for index in range(1, timeline.GetTrackCount(track_type) + 1):
+                for item in timeline.GetItemListInTrack(track_type, index) or []:
+                    if item.GetUniqueId() == locator["timeline_item_id"]:
+                        return item
+        return None
+    items = timeline.GetItemListInTrack(locator["track_type"], locator["track_index"]) or []
+    position = locator["item_index"]
+    return items[position - 1] if 1 <= position <= len(items) else None
+"#;
+
+/// Sets the property `request` changes, if any, and reads back its keys
+const PROPERTIES: &str = r#"
+    item = find_item(timeline, request["item"])
+    if not item:
+        fail("Timeline item not found")
+
+    # Crop is in pixels in Resolve and a fraction of the frame here
+    sizes = {
+        "width": float(timeline.GetSetting("timelineResolutionWidth") or 1),
+        "height": float(timeline.GetSetting("timelineResolutionHeight") or 1),
+    }
+    def factor(scale):
+        return sizes.get(scale, scale)
+
+    constants = {name: getattr(resolve, name) for name in request["constants"] if hasattr(resolve, name)}
+    change = request.get("set")
+    if change:
+        if change["constant"]:
+            value = constants[change["constant"]]
+        else:
+            value = change["value"] * factor(change["scale"])
+        if not item.SetProperty(change["key"], value):
+            fail("Resolve rejected " + change["key"])
+
+    values = {}
+    for entry in request["keys"]:
+        value = item.GetProperty(entry["key"])
+        if isinstance(value, (int, float)) and not isinstance(value, bool) and factor(entry["scale"]) != 1:
+            value = value / factor(entry["scale"])
+        values[entry["key"]] = value
+    print(json.dumps({"success": True, "timeline_item_id": item.GetUniqueId(), "name": item.GetName(), "values": values, "constants": constants}))
+"#;
+
+impl ResolveBridge {
+    /// Timeline item with this ID, created on the current timeline if it does not exist yet
+    pub(super) fn timeline_item_mut<'a>(
+        state: &'a mut StateView<'_>,
+        timeline_item_id: &str,
+    ) -> &'a mut TimelineItemState {
+        let timeline_name = state.current_timeline.clone().unwrap_or_default();
+        let timeline_items = &mut *state.timeline_items;
+        timeline_items
+            .items
+            .entry(timeline_item_id.to_string())
+            .or_insert_with(|| {
+                timeline_items.item_counter += 1;
+                TimelineItemState {
+                    id: timeline_item_id.to_string(),
+                    timeline_name,
+                    clip_name: format!("clip_{}", timeline_items.item_counter),
+                    ..Default::default()
+                }
+            })
+    }
+
+    /// Validate and set the given properties on an item, returning a description of each change
+    pub(super) fn set_item_properties(
+        state: &mut StateView<'_>,
+        timeline_item_id: &str,
+        changes: &[(&str, &'static ItemProperty, &Value)],
+    ) -> ResolveResult<Vec<String>> {
+        let parsed = changes
+            .iter()
+            .map(|(param, property, value)| Ok((*property, property.parse(param, value)?)))
+            .collect::<ResolveResult<Vec<_>>>()?;
+        let item = Self::timeline_item_mut(state, timeline_item_id);
+        Ok(parsed
+            .into_iter()
+            .map(|(property, value)| {
+                let described = format!("{} to {}", property.key, value);
+                item.properties.set(property, value);
+                described
+            })
+            .collect())
+    }
+
+    pub(super) async fn list_timeline_item_properties(
+        &self,
+        _state: &StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        let category = match args["category"].as_str() {
+            Some(name) => Some(Category::parse(name).ok_or_else(|| {
+                ResolveError::invalid_parameter(
+                    "category",
+                    "must be transform, crop, composite, retime, sizing, stabilization, or audio",
+                )
+            })?),
+            None => None,
+        };
+        let properties: Vec<Value> = PROPERTIES
+            .iter()
+            .filter(|property| category.is_none_or(|category| property.category == category))
+            .map(ItemProperty::schema)
+            .collect();
+
+        Ok(json!({
+            "result": format!("Listed {} timeline item properties", properties.len()),
+            "properties": properties,
+            "count": properties.len(),
+            "operation_id": Uuid::new_v4().to_string()
+        }))
+    }
+
+    /// ID of the item named by `timeline_item_id`, or by `track_index` and
+    /// `item_index` on the current timeline
+    fn locate_timeline_item(state: &StateView<'_>, args: &Value) -> ResolveResult<String> {
+        if let Some(id) = args["timeline_item_id"].as_str() {
+            return Ok(id.to_string());
+        }
+        let (Some(track_index), Some(item_index)) =
+            (args["track_index"].as_u64(), args["item_index"].as_u64())
+        else {
+            return Err(ResolveError::invalid_parameter(
+                "timeline_item_id",
+                "give timeline_item_id, or track_index and item_index",
+            ));
+        };
+        check_track_type(args)?;
+        let timeline_name = state.current_timeline.clone().unwrap_or_default();
+        items_on_track(state, &timeline_name, track_index)
+            .get((item_index as usize).wrapping_sub(1))
+            .map(|(item, _)| item.id.clone())
+            .ok_or_else(|| {
+                ResolveError::invalid_parameter(
+                    "item_index",
+                    format!("track {} has no item {}", track_index, item_index),
+                )
+            })
+    }
+
+    pub(super) async fn get_timeline_item_property(
+        &self,
+        state: &StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        let timeline_item_id = &Self::locate_timeline_item(state, &args)?;
+        let item = state
+            .timeline_items
+            .items
+            .get(timeline_item_id)
+            .ok_or_else(|| {
+                ResolveError::invalid_parameter("timeline_item_id", "timeline item not found")
+            })?;
+
+        let (property_key, properties) = match args["property_key"].as_str() {
+            Some(key) => {
+                let property = require("property_key", key)?;
+                (
+                    Some(property.key),
+                    json!({ property.key: item.properties.get(property).to_json() }),
+                )
+            }
+            None => {
+                let all: serde_json::Map<String, Value> = PROPERTIES
+                    .iter()
+                    .map(|property| {
+                        (
+                            property.key.to_string(),
+                            item.properties.get(property).to_json(),
+                        )
+                    })
+                    .collect();
+                (None, Value::Object(all))
+            }
+        };
+
+        Ok(json!({
+            "result": match property_key {
+                Some(key) => format!("Retrieved {} for timeline item '{}'", key, timeline_item_id),
+                None => format!("Retrieved all properties for timeline item '{}'", timeline_item_id),
+            },
+            "timeline_item_id": timeline_item_id,
+            "property_key": property_key,
+            "properties": properties,
+            "operation_id": Uuid::new_v4().to_string()
+        }))
+    }
+
+    pub(super) async fn set_timeline_item_property(
+        &self,
+        state: &mut StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        let timeline_item_id = &Self::locate_timeline_item(state, &args)?;
+        let property_key = args["property_key"].as_str().ok_or_else(|| {
+            ResolveError::invalid_parameter("property_key", "parameter is required")
+        })?;
+        let property = require("property_key", property_key)?;
+        Self::set_item_properties(
+            state,
+            timeline_item_id,
+            &[("property_value", property, &args["property_value"])],
+        )?;
+        let value = state.timeline_items.items[timeline_item_id]
+            .properties
+            .get(property)
+            .to_json();
+
+        Ok(json!({
+            "result": format!(
+                "Set {} to {} for timeline item '{}'",
+                property.key, value, timeline_item_id
+            ),
+            "timeline_item_id": timeline_item_id,
+            "property_key": property.key,
+            "property_value": value,
+            "category": property.category.name(),
+            "operation_id": Uuid::new_v4().to_string()
+        }))
+    }
+
+    /// Get or set a property on an item in the running Resolve through GetProperty and SetProperty
+    pub(super) fn real_timeline_item_property(
+        &self,
+        method: &str,
+        args: &Value,
+    ) -> ResolveResult<Value> {
+        let locator = match args["timeline_item_id"].as_str() {
+            Some(id) => json!({ "timeline_item_id": id }),
+            None => json!({
+                "track_type": args["track_type"].as_str().unwrap_or("video"),
+                "track_index": args["track_index"].as_u64().ok_or_else(|| {
+                    ResolveError::invalid_parameter(
+                        "timeline_item_id",
+                        "give timeline_item_id, or track_index and item_index",
+                    )
+                })?,
+                "item_index": args["item_index"].as_u64().ok_or_else(|| {
+                    ResolveError::invalid_parameter("item_index", "required with track_index")
+                })?
+            }),
+        };
+        let requested = match args["property_key"].as_str() {
+            Some(key) => Some(require("property_key", key)?),
+            None if method == "set_timeline_item_property" => {
+                return Err(ResolveError::invalid_parameter(
+                    "property_key",
+                    "parameter is required",
+                ))
+            }
+            None => None,
+        };
+        let properties: Vec<(&ItemProperty, &ResolveKey)> = match requested {
+            Some(property) => match &property.resolve {
+                Some(resolve) => vec![(property, resolve)],
+                None => {
+                    return Err(ResolveError::not_supported(format!(
+                        "{} has no Resolve API equivalent",
+                        property.key
+                    )))
+                }
+            },
+            None => PROPERTIES
+                .iter()
+                .filter_map(|property| Some((property, property.resolve.as_ref()?)))
+                .collect(),
+        };
+
+        let mut request = json!({
+            "item": locator,
+            "keys": properties.iter().map(|(_, resolve)| resolve.to_json()).collect::<Vec<_>>(),
+            "constants": properties.iter().flat_map(|(property, _)| property.constants()).collect::<Vec<_>>()
+        });
+        if let (Some((property, resolve)), "set_timeline_item_property") =
+            (properties.first(), method)
+        {
+            let value = property.parse("property_value", &args["property_value"])?;
+            request["set"] = resolve.to_json();
+            request["set"]["value"] = value.to_json();
+            request["set"]["constant"] = json!(property.constant_for(&value));
+        }
+        let script = Self::resolve_script(FIND_ITEM, SCRIPT_TIMELINE, &request, PROPERTIES)?;
+        let output = self.run_resolve_script(method, &script)?;
+
+        // Translate Resolve's keys and constants back to registry keys and choices
+        let mut values = serde_json::Map::new();
+        for (property, resolve) in &properties {
+            let raw = &output["values"][resolve.key];
+            let value = property
+                .read_resolve(raw, &output["constants"])
+                .ok_or_else(|| {
+                    ResolveError::api_call(
+                        method,
+                        format!("unexpected value {} for {}", raw, property.key),
+                    )
+                })?;
+            values.insert(property.key.to_string(), value.to_json());
+        }
+        let timeline_item_id = output["timeline_item_id"].clone();
+        let mut response = json!({
+            "timeline_item_id": timeline_item_id,
+            "timeline_item_name": output["name"],
+            "property_key": requested.map(|property| property.key),
+            "operation_id": Uuid::new_v4().to_string()
+        });
+        match requested {
+            Some(property) if method == "set_timeline_item_property" => {
+                let value = values[property.key].clone();
+                response["result"] = json!(format!(
+                    "Set {} to {} for timeline item '{}'",
+                    property.key,
+                    value,
+                    timeline_item_id.as_str().unwrap_or_default()
+                ));
+                response["property_value"] = value;
+                response["category"] = json!(property.category.name());
+            }
+            _ => {
+                response["result"] = json!(format!(
+                    "Retrieved {} properties for timeline item '{}'",
+                    values.len(),
+                    timeline_item_id.as_str().unwrap_or_default()
+                ));
+                response["properties"] = Value::Object(values);
+            }
+        }
+        Ok(response)
+    }
+}
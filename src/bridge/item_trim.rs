@@ -0,0 +1,307 @@
+//! Trimming, slipping and sliding timeline items
+//!
+//! The four frame-accurate edits on one item. A head or tail trim moves that
+//! edit point of the item, so its length changes; a slip shows a different
+//! part of its media in the same place; a slide moves the item along its
+//! track, the item before it growing or shrinking at its tail and the item
+//! after it at its head, so neither the item nor the track changes length.
+//! Frame amounts move an edit point later when positive and earlier when
+//! negative.
+//!
+//! No edit may leave an item without frames, put it before the timeline
+//! start, run it over another item on its track or reach outside its media.
+//! The media's length is probed from the file with ffprobe when it can be,
+//! and is otherwise the length simulated media is given. Generated items
+//! such as slates have no media and can be made any length.
+
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use super::item_lookup::{items_on_track, timeline_clock};
+use super::{clip_marks, ItemPlacement, ResolveBridge, StateView, SIMULATED_CLIP_SECONDS};
+use crate::error::{ResolveError, ResolveResult};
+use crate::timecode::FrameRate;
+
+#[derive(Debug, Clone, Copy)]
+enum ItemEdit {
+    TrimHead,
+    TrimTail,
+    Slip,
+    Slide,
+}
+
+impl ItemEdit {
+    fn verb(self) -> &'static str {
+        match self {
+            ItemEdit::TrimHead => "Trimmed the head of",
+            ItemEdit::TrimTail => "Trimmed the tail of",
+            ItemEdit::Slip => "Slipped",
+            ItemEdit::Slide => "Slid",
+        }
+    }
+}
+
+/// An item on the edited track, as it is and as the edit leaves it
+struct TrackItem {
+    id: String,
+    clip_name: String,
+    generated: bool,
+    before: ItemPlacement,
+    after: ItemPlacement,
+}
+
+impl TrackItem {
+    fn changed(&self) -> bool {
+        self.before.record_in != self.after.record_in
+            || self.before.source_in != self.after.source_in
+            || self.before.duration != self.after.duration
+    }
+}
+
+/// Move the head of a placement by `frames`, keeping its tail where it is
+fn move_head(placement: &mut ItemPlacement, frames: i64) {
+    placement.record_in += frames;
+    placement.source_in += frames;
+    placement.duration -= frames;
+}
+
+impl ResolveBridge {
+    /// Frames of media behind an item at the timeline's rate, and where the
+    /// length came from; None for generated items
+    fn media_frames(
+        state: &StateView<'_>,
+        item: &TrackItem,
+        rate: FrameRate,
+    ) -> Option<(i64, &'static str)> {
+        if item.generated {
+            return None;
+        }
+        Some(state.media_pool.clips.get(&item.clip_name).map_or(
+            (SIMULATED_CLIP_SECONDS * rate.nominal(), "simulated"),
+            |clip| clip_marks::media_frames(clip, rate),
+        ))
+    }
+
+    /// Apply `edit` to the item the call names and every neighbor it moves
+    fn edit_item(
+        &self,
+        state: &mut StateView<'_>,
+        args: &Value,
+        edit: ItemEdit,
+    ) -> ResolveResult<Value> {
+        let item_id = args["timeline_item_id"].as_str().ok_or_else(|| {
+            ResolveError::invalid_parameter("timeline_item_id", "required string")
+        })?;
+        let frames = args["frames"]
+            .as_i64()
+            .ok_or_else(|| ResolveError::invalid_parameter("frames", "required whole number"))?;
+        if frames == 0 {
+            return Err(ResolveError::invalid_parameter(
+                "frames",
+                "must not be 0; positive frames move later, negative earlier",
+            ));
+        }
+        let item = state.timeline_items.items.get(item_id).ok_or_else(|| {
+            ResolveError::invalid_parameter("timeline_item_id", "timeline item not found")
+        })?;
+        let Some(placement) = item.placement.clone() else {
+            return Err(ResolveError::invalid_parameter(
+                "timeline_item_id",
+                format!(
+                    "timeline item '{}' has no known place on a timeline",
+                    item_id
+                ),
+            ));
+        };
+        let timeline_name = item.timeline_name.clone();
+        if !state.timelines.contains_key(&timeline_name) {
+            return Err(ResolveError::TimelineNotFound {
+                name: timeline_name,
+            });
+        }
+        let (rate, start_frame) = timeline_clock(self, state, &timeline_name, args)?;
+        let track_index = placement.track_index;
+
+        let mut track: Vec<TrackItem> =
+            items_on_track(state, &timeline_name, u64::from(track_index))
+                .into_iter()
+                .map(|(item, placement)| TrackItem {
+                    id: item.id.clone(),
+                    clip_name: item.clip_name.clone(),
+                    generated: item.generator.is_some(),
+                    before: placement.clone(),
+                    after: placement.clone(),
+                })
+                .collect();
+        let position = track
+            .iter()
+            .position(|item| item.id == item_id)
+            .expect("placed item is on its track");
+        let edited = &mut track[position].after;
+        match edit {
+            ItemEdit::TrimHead => move_head(edited, frames),
+            ItemEdit::TrimTail => edited.duration += frames,
+            ItemEdit::Slip => edited.source_in += frames,
+            ItemEdit::Slide => edited.record_in += frames,
+        }
+        // A slide moves the edit points the item shares with the items it touches
+        if let ItemEdit::Slide = edit {
+            if position > 0 && track[position - 1].before.record_out() == placement.record_in {
+                track[position - 1].after.duration += frames;
+            }
+            if track
+                .get(position + 1)
+                .is_some_and(|next| next.before.record_in == placement.record_out())
+            {
+                move_head(&mut track[position + 1].after, frames);
+            }
+        }
+
+        // Every item the edit changes must still fit its media and the timeline
+        let mut media = None;
+        for item in track.iter().filter(|item| item.changed()) {
+            let (before, after) = (&item.before, &item.after);
+            let problem = if after.duration < 1 {
+                Some("would have no frames left".to_string())
+            } else if after.record_in < 0 {
+                Some("would start before the timeline start".to_string())
+            } else if !item.generated && after.source_in < 0 && after.source_in < before.source_in {
+                Some(format!(
+                    "would start {} frames before the first frame of its media",
+                    -after.source_in
+                ))
+            } else {
+                let length = Self::media_frames(state, item, rate);
+                if item.id == item_id {
+                    media = length;
+                }
+                length
+                    .filter(|(length, _)| {
+                        after.source_out() > *length && after.source_out() > before.source_out()
+                    })
+                    .map(|(length, source)| {
+                        format!(
+                            "would run {} frames past the end of its media, which is {} frames long ({})",
+                            after.source_out() - length,
+                            length,
+                            source
+                        )
+                    })
+            };
+            if let Some(problem) = problem {
+                return Err(ResolveError::invalid_parameter(
+                    "frames",
+                    format!("item '{}' ({}) {}", item.id, item.clip_name, problem),
+                ));
+            }
+        }
+        let mut placed: Vec<&TrackItem> = track.iter().collect();
+        placed.sort_by_key(|item| item.after.record_in);
+        if let Some(pair) = placed
+            .windows(2)
+            .find(|pair| pair[0].after.record_out() > pair[1].after.record_in)
+        {
+            let (moved, other) = if pair[0].changed() {
+                (pair[0], pair[1])
+            } else {
+                (pair[1], pair[0])
+            };
+            return Err(ResolveError::invalid_parameter(
+                "frames",
+                format!(
+                    "item '{}' ({}) would overlap item '{}' ({}) on video track {}",
+                    moved.id, moved.clip_name, other.id, other.clip_name, track_index
+                ),
+            ));
+        }
+
+        let placement_json = |placement: &ItemPlacement| {
+            json!({
+                "record_in": placement.record_in,
+                "record_out": placement.record_out(),
+                "source_in": placement.source_in,
+                "source_out": placement.source_out(),
+                "duration": placement.duration,
+                "record_in_timecode": rate.frames_to_timecode(start_frame + placement.record_in),
+                "record_out_timecode": rate.frames_to_timecode(start_frame + placement.record_out())
+            })
+        };
+        let mut neighbors = Vec::new();
+        for item in track.iter().filter(|item| item.changed()) {
+            if let Some(stored) = state
+                .timeline_items
+                .items
+                .get_mut(&item.id)
+                .and_then(|stored| stored.placement.as_mut())
+            {
+                *stored = item.after.clone();
+            }
+            if item.id != item_id {
+                neighbors.push(json!({
+                    "timeline_item_id": item.id,
+                    "clip_name": item.clip_name,
+                    "before": placement_json(&item.before),
+                    "after": placement_json(&item.after)
+                }));
+            }
+        }
+        let edited = &track[position];
+
+        Ok(json!({
+            "result": format!(
+                "{} item '{}' ({}) by {} frames{}",
+                edit.verb(),
+                item_id,
+                edited.clip_name,
+                frames,
+                if neighbors.is_empty() {
+                    String::new()
+                } else {
+                    format!(", adjusting {} neighboring items", neighbors.len())
+                }
+            ),
+            "timeline_item_id": item_id,
+            "timeline_name": timeline_name,
+            "track_index": track_index,
+            "frames": frames,
+            "before": placement_json(&edited.before),
+            "after": placement_json(&edited.after),
+            "neighbors": neighbors,
+            "media_frames": media.map(|(length, _)| length),
+            "media_length_source": media.map(|(_, source)| source),
+            "operation_id": Uuid::new_v4().to_string()
+        }))
+    }
+
+    pub(super) async fn trim_item_head(
+        &self,
+        state: &mut StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        self.edit_item(state, &args, ItemEdit::TrimHead)
+    }
+
+    pub(super) async fn trim_item_tail(
+        &self,
+        state: &mut StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        self.edit_item(state, &args, ItemEdit::TrimTail)
+    }
+
+    pub(super) async fn slip_item(
+        &self,
+        state: &mut StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        self.edit_item(state, &args, ItemEdit::Slip)
+    }
+
+    pub(super) async fn slide_item(
+        &self,
+        state: &mut StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        self.edit_item(state, &args, ItemEdit::Slide)
+    }
+}
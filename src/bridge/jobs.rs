@@ -0,0 +1,495 @@
+//! Background jobs for long-running analysis and queued pipeline steps
+//!
+//! A job's work runs without holding the state lock: analysis on a blocking
+//! thread, queued steps as an API call. A job may list prerequisite jobs, in
+//! which case it waits until all of them have completed and is cancelled if
+//! any of them fails. Prerequisites must exist when the job is created, so
+//! the jobs always form a DAG. The job's report, or error, is stored once the
+//! work finishes so it can be fetched later.
+
+use serde_json::{json, Map, Value};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use uuid::Uuid;
+
+use super::concurrency::Workload;
+use super::{ResolveBridge, StateView};
+use crate::error::{ResolveError, ResolveResult};
+
+/// Jobs by ID
+#[derive(Debug, Default)]
+pub(super) struct JobState {
+    jobs: BTreeMap<String, BackgroundJob>,
+    /// Work of jobs still waiting on their prerequisites
+    waiting: HashMap<String, JobWork>,
+    /// Counter used to generate job IDs
+    job_counter: u64,
+}
+
+/// What a job runs once its prerequisites have completed
+enum JobWork {
+    /// Analysis on a blocking thread
+    Blocking(Box<dyn FnOnce() -> Result<Value, String> + Send + Sync>),
+    /// An API call, such as one step of a transcode, transcribe and render pipeline
+    Api { method: String, args: Value },
+}
+
+impl fmt::Debug for JobWork {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Blocking(_) => f.write_str("Blocking"),
+            Self::Api { method, .. } => write!(f, "Api({})", method),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct BackgroundJob {
+    id: String,
+    /// Tool that started the job, such as `delivery_qc`
+    kind: String,
+    description: String,
+    status: JobStatus,
+    /// Jobs that must complete before this one starts
+    depends_on: Vec<String>,
+    created_at: chrono::DateTime<chrono::Utc>,
+    started_at: Option<chrono::DateTime<chrono::Utc>>,
+    finished_at: Option<chrono::DateTime<chrono::Utc>>,
+    report: Option<Value>,
+    error: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum JobStatus {
+    /// Waiting for prerequisites to complete
+    Waiting,
+    Running,
+    Completed,
+    Failed,
+    /// Never started because a prerequisite failed or was cancelled
+    Cancelled,
+}
+
+impl JobState {
+    /// Summary of every job, by job ID
+    pub(super) fn summaries(&self) -> Map<String, Value> {
+        self.jobs
+            .iter()
+            .map(|(id, job)| (id.clone(), job.summary()))
+            .collect()
+    }
+
+    /// Number of jobs in each status
+    pub(super) fn status_counts(&self) -> BTreeMap<&'static str, usize> {
+        let mut counts = BTreeMap::new();
+        for job in self.jobs.values() {
+            *counts.entry(job.status.as_str()).or_default() += 1;
+        }
+        counts
+    }
+}
+
+impl JobStatus {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Waiting => "waiting",
+            Self::Running => "running",
+            Self::Completed => "completed",
+            Self::Failed => "failed",
+            Self::Cancelled => "cancelled",
+        }
+    }
+
+    fn is_unsuccessful(&self) -> bool {
+        matches!(self, Self::Failed | Self::Cancelled)
+    }
+}
+
+impl BackgroundJob {
+    fn summary(&self) -> Value {
+        json!({
+            "job_id": self.id,
+            "kind": self.kind,
+            "description": self.description,
+            "status": self.status.as_str(),
+            "depends_on": self.depends_on,
+            "created_at": self.created_at.to_rfc3339(),
+            "started_at": self.started_at.map(|at| at.to_rfc3339()),
+            "finished_at": self.finished_at.map(|at| at.to_rfc3339())
+        })
+    }
+}
+
+impl ResolveBridge {
+    /// Register a running job and run `work` in the background; returns the job ID
+    pub(super) fn spawn_job<F>(
+        &self,
+        state: &mut StateView<'_>,
+        kind: &str,
+        description: String,
+        work: F,
+    ) -> String
+    where
+        F: FnOnce() -> Result<Value, String> + Send + Sync + 'static,
+    {
+        self.add_job(
+            state,
+            kind,
+            description,
+            Vec::new(),
+            JobWork::Blocking(Box::new(work)),
+        )
+    }
+
+    /// Register a job that waits for `depends_on` and then runs `work`
+    fn add_job(
+        &self,
+        state: &mut StateView<'_>,
+        kind: &str,
+        description: String,
+        depends_on: Vec<String>,
+        work: JobWork,
+    ) -> String {
+        let jobs = &mut *state.jobs;
+        jobs.job_counter += 1;
+        let id = format!("{}_{:04}", kind, jobs.job_counter);
+        jobs.jobs.insert(
+            id.clone(),
+            BackgroundJob {
+                id: id.clone(),
+                kind: kind.to_string(),
+                description,
+                status: JobStatus::Waiting,
+                depends_on,
+                created_at: chrono::Utc::now(),
+                started_at: None,
+                finished_at: None,
+                report: None,
+                error: None,
+            },
+        );
+        jobs.waiting.insert(id.clone(), work);
+        self.schedule(jobs);
+        id
+    }
+
+    /// Start waiting jobs whose prerequisites have all completed and cancel
+    /// the ones behind a prerequisite that failed or was cancelled
+    fn schedule(&self, jobs: &mut JobState) {
+        // Cancelling a job can cancel the jobs waiting on it in turn
+        loop {
+            let mut cancelled = false;
+            let mut waiting: Vec<String> = jobs.waiting.keys().cloned().collect();
+            waiting.sort();
+            for id in waiting {
+                let prerequisites: Vec<(&String, JobStatus)> = jobs.jobs[&id]
+                    .depends_on
+                    .iter()
+                    .map(|prerequisite| (prerequisite, jobs.jobs[prerequisite].status))
+                    .collect();
+                let blocker = prerequisites
+                    .iter()
+                    .find(|(_, status)| status.is_unsuccessful())
+                    .map(|(prerequisite, status)| {
+                        format!(
+                            "prerequisite '{}' was {}",
+                            prerequisite,
+                            if *status == JobStatus::Failed {
+                                "failed"
+                            } else {
+                                "cancelled"
+                            }
+                        )
+                    });
+                let ready = prerequisites
+                    .iter()
+                    .all(|(_, status)| *status == JobStatus::Completed);
+
+                let job = jobs.jobs.get_mut(&id).expect("waiting jobs are registered");
+                if let Some(reason) = blocker {
+                    jobs.waiting.remove(&id);
+                    tracing::warn!("Background job {} cancelled: {}", id, reason);
+                    job.status = JobStatus::Cancelled;
+                    job.finished_at = Some(chrono::Utc::now());
+                    job.error = Some(reason);
+                    cancelled = true;
+                } else if ready {
+                    let work = jobs.waiting.remove(&id).expect("waiting job has work");
+                    job.status = JobStatus::Running;
+                    job.started_at = Some(chrono::Utc::now());
+                    self.launch(id, work);
+                }
+            }
+            if !cancelled {
+                break;
+            }
+        }
+    }
+
+    /// Run a started job's work in the background, store the outcome and
+    /// schedule the jobs that were waiting on it
+    fn launch(&self, job_id: String, work: JobWork) {
+        let bridge = self.clone();
+        tokio::spawn(async move {
+            let outcome = match work {
+                JobWork::Blocking(work) => {
+                    let _permit = bridge.cpu.acquire(Workload::Analysis, job_id.clone()).await;
+                    tokio::task::spawn_blocking(work)
+                        .await
+                        .unwrap_or_else(|e| Err(format!("job panicked: {}", e)))
+                }
+                JobWork::Api { method, args } => bridge
+                    .call_api_boxed(method, args)
+                    .await
+                    .map_err(|e| e.to_string()),
+            };
+            let mut jobs = bridge.state.jobs.write().await;
+            // The job is gone if the state was reset while it ran
+            let Some(job) = jobs.jobs.get_mut(&job_id) else {
+                return;
+            };
+            job.finished_at = Some(chrono::Utc::now());
+            match outcome {
+                Ok(report) => {
+                    job.status = JobStatus::Completed;
+                    job.report = Some(report);
+                }
+                Err(e) => {
+                    tracing::warn!("Background job {} failed: {}", job_id, e);
+                    job.status = JobStatus::Failed;
+                    job.error = Some(e);
+                }
+            }
+            bridge.schedule(&mut jobs);
+        });
+    }
+
+    /// Boxed so that jobs queued by an API call can make API calls themselves
+    pub(super) fn call_api_boxed(
+        &self,
+        method: String,
+        args: Value,
+    ) -> Pin<Box<dyn Future<Output = ResolveResult<Value>> + Send + '_>> {
+        Box::pin(async move { self.call_api(&method, args).await })
+    }
+
+    pub(super) async fn submit_job(
+        &self,
+        state: &mut StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        let method = args["method"]
+            .as_str()
+            .filter(|method| !method.is_empty())
+            .ok_or_else(|| ResolveError::invalid_parameter("method", "required string"))?
+            .to_string();
+        let call_args = match &args["arguments"] {
+            Value::Null => json!({}),
+            Value::Object(map) => Value::Object(map.clone()),
+            _ => {
+                return Err(ResolveError::invalid_parameter(
+                    "arguments",
+                    "must be an object",
+                ))
+            }
+        };
+        let depends_on: Vec<String> = match &args["depends_on"] {
+            Value::Null => Vec::new(),
+            Value::Array(ids) => ids
+                .iter()
+                .map(|id| {
+                    id.as_str().map(str::to_string).ok_or_else(|| {
+                        ResolveError::invalid_parameter("depends_on", "must be a list of job IDs")
+                    })
+                })
+                .collect::<ResolveResult<_>>()?,
+            _ => {
+                return Err(ResolveError::invalid_parameter(
+                    "depends_on",
+                    "must be a list of job IDs",
+                ))
+            }
+        };
+        for (index, prerequisite) in depends_on.iter().enumerate() {
+            if !state.jobs.jobs.contains_key(prerequisite) {
+                return Err(ResolveError::invalid_parameter(
+                    "depends_on",
+                    format!("job '{}' not found", prerequisite),
+                ));
+            }
+            if depends_on[..index].contains(prerequisite) {
+                return Err(ResolveError::invalid_parameter(
+                    "depends_on",
+                    format!("job '{}' is listed twice", prerequisite),
+                ));
+            }
+        }
+        let description = args["description"]
+            .as_str()
+            .map(str::to_string)
+            .unwrap_or_else(|| format!("API call '{}'", method));
+
+        let job_id = self.add_job(
+            state,
+            &method,
+            description.clone(),
+            depends_on.clone(),
+            JobWork::Api {
+                method: method.clone(),
+                args: call_args,
+            },
+        );
+        let status = state.jobs.jobs[&job_id].status.as_str();
+        Ok(json!({
+            "result": format!("Submitted {} as job '{}' ({})", description, job_id, status),
+            "job_id": job_id,
+            "status": status,
+            "depends_on": depends_on,
+            "operation_id": Uuid::new_v4().to_string()
+        }))
+    }
+
+    pub(super) async fn get_job_graph(
+        &self,
+        state: &StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        let jobs = &state.jobs.jobs;
+        let mut dependents: HashMap<&str, Vec<&str>> = HashMap::new();
+        for job in jobs.values() {
+            for prerequisite in &job.depends_on {
+                dependents.entry(prerequisite).or_default().push(&job.id);
+            }
+        }
+
+        // With a job given, show it with everything it waits on and everything waiting on it
+        let included: Vec<&str> = match args["job_id"].as_str() {
+            Some(job_id) => {
+                if !jobs.contains_key(job_id) {
+                    return Err(ResolveError::invalid_parameter(
+                        "job_id",
+                        format!("job '{}' not found", job_id),
+                    ));
+                }
+                let mut included = vec![job_id];
+                let mut upstream = vec![job_id];
+                while let Some(id) = upstream.pop() {
+                    for prerequisite in &jobs[id].depends_on {
+                        if !included.contains(&prerequisite.as_str()) {
+                            included.push(prerequisite);
+                            upstream.push(prerequisite);
+                        }
+                    }
+                }
+                let mut downstream = vec![job_id];
+                while let Some(id) = downstream.pop() {
+                    for dependent in dependents.get(id).into_iter().flatten() {
+                        if !included.contains(dependent) {
+                            included.push(dependent);
+                            downstream.push(dependent);
+                        }
+                    }
+                }
+                included
+            }
+            None => jobs.keys().map(String::as_str).collect(),
+        };
+
+        // Prerequisites come before their dependents; ties go by creation order
+        let created = |id: &str| (jobs[id].created_at, id.to_string());
+        let mut remaining: Vec<&str> = included.clone();
+        remaining.sort_by_key(|id| created(id));
+        let mut order: Vec<&str> = Vec::with_capacity(remaining.len());
+        while !remaining.is_empty() {
+            let next = remaining
+                .iter()
+                .position(|id| {
+                    jobs[*id]
+                        .depends_on
+                        .iter()
+                        .all(|prerequisite| !remaining.contains(&prerequisite.as_str()))
+                })
+                .expect("job dependencies form a DAG");
+            order.push(remaining.remove(next));
+        }
+
+        let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+        let nodes: Vec<Value> = order
+            .iter()
+            .map(|id| {
+                let job = &jobs[*id];
+                *counts.entry(job.status.as_str()).or_default() += 1;
+                let mut node = job.summary();
+                node["dependents"] = json!(dependents.get(id).cloned().unwrap_or_default());
+                node["error"] = json!(job.error);
+                node
+            })
+            .collect();
+        let edges: Vec<Value> = order
+            .iter()
+            .flat_map(|id| {
+                jobs[*id]
+                    .depends_on
+                    .iter()
+                    .map(move |prerequisite| json!({ "from": prerequisite, "to": id }))
+            })
+            .collect();
+
+        Ok(json!({
+            "result": format!(
+                "Job graph has {} jobs and {} dependencies",
+                nodes.len(),
+                edges.len()
+            ),
+            "jobs": nodes,
+            "edges": edges,
+            "order": order,
+            "status_counts": counts,
+            "operation_id": Uuid::new_v4().to_string()
+        }))
+    }
+
+    pub(super) async fn get_background_job(
+        &self,
+        state: &StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        let job_id = args["job_id"]
+            .as_str()
+            .ok_or_else(|| ResolveError::invalid_parameter("job_id", "required string"))?;
+        let job = state.jobs.jobs.get(job_id).ok_or_else(|| {
+            ResolveError::invalid_parameter("job_id", format!("job '{}' not found", job_id))
+        })?;
+
+        let mut response = job.summary();
+        response["result"] = json!(format!("Job '{}' is {}", job.id, job.status.as_str()));
+        response["report"] = json!(job.report);
+        response["error"] = json!(job.error);
+        response["operation_id"] = json!(Uuid::new_v4().to_string());
+        Ok(response)
+    }
+
+    pub(super) async fn list_background_jobs(
+        &self,
+        state: &StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        let kind = args["kind"].as_str();
+        let jobs: Vec<Value> = state
+            .jobs
+            .jobs
+            .values()
+            .filter(|job| kind.is_none_or(|kind| job.kind == kind))
+            .map(BackgroundJob::summary)
+            .collect();
+
+        Ok(json!({
+            "result": format!("Found {} background jobs", jobs.len()),
+            "jobs": jobs,
+            "count": jobs.len(),
+            "operation_id": Uuid::new_v4().to_string()
+        }))
+    }
+}
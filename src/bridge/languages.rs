@@ -0,0 +1,334 @@
+//! Transcription languages
+//!
+//! Languages are BCP-47 tags such as `en-US`. Calls may also name a language
+//! in plain words ("english") or by its bare subtag ("en"); both resolve to the
+//! registry's tag, which is what transcripts record. Each transcription backend
+//! supports its own set of languages, and a language the current backend cannot
+//! transcribe is rejected before any work starts, with close matches suggested.
+
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use super::{ConnectionMode, ResolveBridge, StateView};
+use crate::error::{ResolveError, ResolveResult};
+
+/// Language transcription uses when none is given
+pub(super) const DEFAULT_LANGUAGE: &str = "en-US";
+
+/// Most suggestions offered for an unknown language
+const MAX_SUGGESTIONS: usize = 5;
+
+/// Service that turns speech into a transcript
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) enum TranscriptionBackend {
+    /// Resolve's own speech-to-text
+    Resolve,
+    /// Sample transcripts produced in simulation mode
+    Simulation,
+}
+
+impl TranscriptionBackend {
+    fn parse(name: &str) -> ResolveResult<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "resolve" => Ok(Self::Resolve),
+            "simulation" => Ok(Self::Simulation),
+            _ => Err(ResolveError::invalid_parameter(
+                "backend",
+                "must be resolve or simulation",
+            )),
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Resolve => "resolve",
+            Self::Simulation => "simulation",
+        }
+    }
+
+    fn supports(self, language: &Language) -> bool {
+        match self {
+            Self::Resolve => language.resolve,
+            Self::Simulation => true,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub(super) struct Language {
+    /// BCP-47 tag
+    pub(super) code: &'static str,
+    name: &'static str,
+    /// Other names and tags that mean this language, in lowercase
+    aliases: &'static [&'static str],
+    /// Whether Resolve's speech-to-text can transcribe it
+    resolve: bool,
+}
+
+const fn language(
+    code: &'static str,
+    name: &'static str,
+    aliases: &'static [&'static str],
+    resolve: bool,
+) -> Language {
+    Language {
+        code,
+        name,
+        aliases,
+        resolve,
+    }
+}
+
+/// Every known language, in the order they are listed
+const LANGUAGES: &[Language] = &[
+    language(
+        "en-US",
+        "English (United States)",
+        &["english", "en", "american english"],
+        true,
+    ),
+    language(
+        "en-GB",
+        "English (United Kingdom)",
+        &["british english"],
+        true,
+    ),
+    language(
+        "es-ES",
+        "Spanish (Spain)",
+        &["spanish", "es", "castilian"],
+        true,
+    ),
+    language(
+        "es-MX",
+        "Spanish (Mexico)",
+        &["mexican spanish", "latin american spanish"],
+        false,
+    ),
+    language("fr-FR", "French (France)", &["french", "fr"], true),
+    language("fr-CA", "French (Canada)", &["canadian french"], false),
+    language("de-DE", "German (Germany)", &["german", "de"], true),
+    language("it-IT", "Italian (Italy)", &["italian", "it"], true),
+    language(
+        "pt-BR",
+        "Portuguese (Brazil)",
+        &["portuguese", "pt", "brazilian portuguese"],
+        true,
+    ),
+    language(
+        "pt-PT",
+        "Portuguese (Portugal)",
+        &["european portuguese"],
+        true,
+    ),
+    language("nl-NL", "Dutch (Netherlands)", &["dutch", "nl"], true),
+    language("sv-SE", "Swedish (Sweden)", &["swedish", "sv"], true),
+    language("da-DK", "Danish (Denmark)", &["danish", "da"], true),
+    language(
+        "nb-NO",
+        "Norwegian Bokmål (Norway)",
+        &["norwegian", "nb", "no", "no-no"],
+        true,
+    ),
+    language("fi-FI", "Finnish (Finland)", &["finnish", "fi"], true),
+    language("pl-PL", "Polish (Poland)", &["polish", "pl"], true),
+    language("ru-RU", "Russian (Russia)", &["russian", "ru"], true),
+    language("tr-TR", "Turkish (Turkey)", &["turkish", "tr"], false),
+    language("ar-SA", "Arabic (Saudi Arabia)", &["arabic", "ar"], false),
+    language("hi-IN", "Hindi (India)", &["hindi", "hi"], false),
+    language("ja-JP", "Japanese (Japan)", &["japanese", "ja"], true),
+    language("ko-KR", "Korean (South Korea)", &["korean", "ko"], true),
+    language(
+        "zh-CN",
+        "Chinese (Simplified, China)",
+        &["chinese", "mandarin", "simplified chinese", "zh", "zh-hans"],
+        true,
+    ),
+    language(
+        "zh-TW",
+        "Chinese (Traditional, Taiwan)",
+        &["traditional chinese", "zh-hant"],
+        true,
+    ),
+];
+
+/// Lowercase, trimmed and with `_` written as `-`, so "EN_us" matches "en-US"
+fn normalize(input: &str) -> String {
+    input.trim().replace('_', "-").to_lowercase()
+}
+
+/// Whether the input is shaped like a BCP-47 tag: a 2-3 letter language
+/// subtag followed by 2-8 character subtags
+fn is_language_tag(tag: &str) -> bool {
+    let mut subtags = tag.split('-');
+    let primary = subtags.next().unwrap_or_default();
+    (2..=3).contains(&primary.len())
+        && primary.chars().all(|c| c.is_ascii_alphabetic())
+        && subtags.all(|subtag| {
+            (2..=8).contains(&subtag.len()) && subtag.chars().all(|c| c.is_ascii_alphanumeric())
+        })
+}
+
+/// Edit distance between two short strings
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, a_char) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, b_char) in b.iter().enumerate() {
+            let above = row[j + 1];
+            row[j + 1] = (diagonal + usize::from(a_char != *b_char))
+                .min(row[j] + 1)
+                .min(above + 1);
+            diagonal = above;
+        }
+    }
+    row[b.len()]
+}
+
+impl Language {
+    fn names(&self) -> impl Iterator<Item = String> + '_ {
+        [self.code.to_lowercase(), self.name.to_lowercase()]
+            .into_iter()
+            .chain(self.aliases.iter().map(|alias| alias.to_string()))
+    }
+
+    /// Whether a normalized input looks like a misspelling or variant of this language
+    fn resembles(&self, input: &str) -> bool {
+        let primary = input.split('-').next().unwrap_or_default();
+        // Short tags are too alike for edit distance to mean anything
+        self.code.to_lowercase().split('-').next() == Some(primary)
+            || (input.len() > 3
+                && self
+                    .names()
+                    .any(|name| name.len() > 3 && edit_distance(input, &name) <= 2))
+    }
+
+    fn to_json(&self) -> Value {
+        let backends: Vec<&str> = [
+            TranscriptionBackend::Resolve,
+            TranscriptionBackend::Simulation,
+        ]
+        .into_iter()
+        .filter(|backend| backend.supports(self))
+        .map(TranscriptionBackend::as_str)
+        .collect();
+        json!({
+            "code": self.code,
+            "name": self.name,
+            "aliases": self.aliases,
+            "backends": backends
+        })
+    }
+}
+
+fn suggestions(input: &str, backend: TranscriptionBackend) -> Vec<&'static str> {
+    LANGUAGES
+        .iter()
+        .filter(|language| backend.supports(language) && language.resembles(input))
+        .map(|language| language.code)
+        .take(MAX_SUGGESTIONS)
+        .collect()
+}
+
+/// Language a transcription call asks for, checked against the backend
+pub(super) fn require_language(
+    input: &str,
+    backend: TranscriptionBackend,
+) -> ResolveResult<&'static Language> {
+    let normalized = normalize(input);
+    let Some(language) = LANGUAGES
+        .iter()
+        .find(|language| language.names().any(|name| name == normalized))
+    else {
+        let suggested = suggestions(&normalized, backend);
+        let message = if !suggested.is_empty() {
+            format!(
+                "unsupported language '{}'; did you mean {}?",
+                input,
+                suggested.join(", ")
+            )
+        } else if is_language_tag(&normalized) {
+            format!(
+                "unsupported language '{}'; see list_supported_languages",
+                input
+            )
+        } else {
+            format!(
+                "'{}' is neither a BCP-47 language tag nor a known language name",
+                input
+            )
+        };
+        return Err(ResolveError::invalid_parameter("language", message));
+    };
+
+    if !backend.supports(language) {
+        let suggested = suggestions(&normalize(language.code), backend);
+        return Err(ResolveError::invalid_parameter(
+            "language",
+            format!(
+                "{} transcription does not support {} ({}){}",
+                backend.as_str(),
+                language.name,
+                language.code,
+                if suggested.is_empty() {
+                    String::new()
+                } else {
+                    format!("; try {}", suggested.join(", "))
+                }
+            ),
+        ));
+    }
+    Ok(language)
+}
+
+/// Any registered language, whatever the transcription backends support
+pub(super) fn require_known_language(input: &str) -> ResolveResult<&'static Language> {
+    require_language(input, TranscriptionBackend::Simulation)
+}
+
+impl ResolveBridge {
+    /// Backend that transcribes in the current connection mode
+    pub(super) fn transcription_backend(&self) -> TranscriptionBackend {
+        match self.mode {
+            ConnectionMode::Real => TranscriptionBackend::Resolve,
+            ConnectionMode::Simulation => TranscriptionBackend::Simulation,
+        }
+    }
+
+    /// Tag of the language a transcription call asks for, or the default
+    pub(super) fn transcription_language(&self, args: &Value) -> ResolveResult<&'static str> {
+        let input = args["language"].as_str().unwrap_or(DEFAULT_LANGUAGE);
+        Ok(require_language(input, self.transcription_backend())?.code)
+    }
+
+    pub(super) async fn list_supported_languages(
+        &self,
+        _state: &StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        let backend = match args["backend"].as_str() {
+            Some(name) => TranscriptionBackend::parse(name)?,
+            None => self.transcription_backend(),
+        };
+        let languages: Vec<Value> = LANGUAGES
+            .iter()
+            .filter(|language| backend.supports(language))
+            .map(Language::to_json)
+            .collect();
+
+        Ok(json!({
+            "result": format!(
+                "{} transcription supports {} languages",
+                backend.as_str(),
+                languages.len()
+            ),
+            "backend": backend.as_str(),
+            "default_language": DEFAULT_LANGUAGE,
+            "languages": languages,
+            "count": languages.len(),
+            "operation_id": Uuid::new_v4().to_string()
+        }))
+    }
+}
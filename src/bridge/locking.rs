@@ -0,0 +1,259 @@
+//! Per-domain locking of the simulation state
+//!
+//! The state is split into domains (project, media pool, timelines, color,
+//! render, review and jobs), each behind its own read-write locks. A call takes
+//! exclusive locks on the domains its lock plan changes and shared locks on the
+//! domains it reads, always in the same order, and leaves the rest unlocked, so
+//! queries run side by side and calls on unrelated domains do not wait on each
+//! other. A plan that does not list what it reads shares every domain it does
+//! not change. Touching a domain the plan left unlocked fails the call.
+
+use serde_json::Value;
+use std::collections::HashMap;
+use std::ops::{Deref, DerefMut};
+use std::sync::atomic::AtomicU64;
+use tokio::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+use super::{
+    backup, jobs, notes, review, tags, transcripts, trash, ColorState, GalleryState, KeyframeState,
+    MediaPool, RenderState, ResolveState, Timeline, TimelineItemsState,
+};
+
+/// Independently locked part of the state
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) enum Domain {
+    /// Open project, project list, current page, project settings, backups
+    /// and trash
+    Project,
+    /// Bins, clips and transcripts
+    MediaPool,
+    /// Timelines, timeline items and keyframes
+    Timelines,
+    /// Grades, presets, LUTs and gallery stills
+    Color,
+    /// Render queue and presets
+    Render,
+    /// Review notes, tags and free-text notes
+    Review,
+    /// Background jobs
+    Jobs,
+}
+
+/// Domains a call may change, and the domains it only reads
+#[derive(Debug, Clone, Copy)]
+pub(super) struct LockPlan {
+    exclusive: &'static [Domain],
+    /// None shares every domain the call does not change
+    shared: Option<&'static [Domain]>,
+}
+
+impl LockPlan {
+    /// Queries that change nothing
+    pub(super) const READ: Self = Self {
+        exclusive: &[],
+        shared: None,
+    };
+    /// Calls that may change any part of the state
+    pub(super) const ALL: Self = Self {
+        exclusive: &[
+            Domain::Project,
+            Domain::MediaPool,
+            Domain::Timelines,
+            Domain::Color,
+            Domain::Render,
+            Domain::Review,
+            Domain::Jobs,
+        ],
+        shared: None,
+    };
+
+    pub(super) const fn write(exclusive: &'static [Domain]) -> Self {
+        Self {
+            exclusive,
+            shared: None,
+        }
+    }
+
+    /// The same plan, reading only `shared` besides the domains it changes
+    pub(super) const fn reading(self, shared: &'static [Domain]) -> Self {
+        Self {
+            exclusive: self.exclusive,
+            shared: Some(shared),
+        }
+    }
+
+    /// Whether the call changes nothing
+    pub(super) fn is_read_only(&self) -> bool {
+        self.exclusive.is_empty()
+    }
+
+    /// Domains the call may change
+    pub(super) fn writes(&self) -> &'static [Domain] {
+        self.exclusive
+    }
+
+    fn is_exclusive(&self, domain: Domain) -> bool {
+        self.exclusive.contains(&domain)
+    }
+
+    fn is_shared(&self, domain: Domain) -> bool {
+        self.shared.is_none_or(|shared| shared.contains(&domain))
+    }
+}
+
+/// Simulation state with one lock per field, grouped into domains
+#[derive(Debug)]
+pub(super) struct SharedState {
+    pub(super) current_project: RwLock<Option<String>>,
+    projects: RwLock<Vec<String>>,
+    current_page: RwLock<String>,
+    project_settings: RwLock<HashMap<String, Value>>,
+    backups: RwLock<backup::BackupState>,
+    trash: RwLock<trash::TrashState>,
+    media_pool: RwLock<MediaPool>,
+    transcripts: RwLock<transcripts::TranscriptState>,
+    timelines: RwLock<HashMap<String, Timeline>>,
+    current_timeline: RwLock<Option<String>>,
+    timeline_items: RwLock<TimelineItemsState>,
+    keyframe_state: RwLock<KeyframeState>,
+    color_state: RwLock<ColorState>,
+    gallery: RwLock<GalleryState>,
+    pub(super) render_state: RwLock<RenderState>,
+    review: RwLock<review::ReviewState>,
+    tags: RwLock<tags::TagState>,
+    notes: RwLock<notes::NoteState>,
+    pub(super) jobs: RwLock<jobs::JobState>,
+    /// Operation counter for realistic responses
+    pub(super) operation_count: AtomicU64,
+}
+
+impl From<ResolveState> for SharedState {
+    fn from(state: ResolveState) -> Self {
+        Self {
+            current_project: RwLock::new(state.current_project),
+            projects: RwLock::new(state.projects),
+            current_page: RwLock::new(state.current_page),
+            project_settings: RwLock::new(state.project_settings),
+            backups: RwLock::new(state.backups),
+            trash: RwLock::new(state.trash),
+            media_pool: RwLock::new(state.media_pool),
+            transcripts: RwLock::new(state.transcripts),
+            timelines: RwLock::new(state.timelines),
+            current_timeline: RwLock::new(state.current_timeline),
+            timeline_items: RwLock::new(state.timeline_items),
+            keyframe_state: RwLock::new(state.keyframe_state),
+            color_state: RwLock::new(state.color_state),
+            gallery: RwLock::new(state.gallery),
+            render_state: RwLock::new(state.render_state),
+            review: RwLock::new(state.review),
+            tags: RwLock::new(state.tags),
+            notes: RwLock::new(state.notes),
+            jobs: RwLock::new(state.jobs),
+            operation_count: AtomicU64::new(state.operation_count),
+        }
+    }
+}
+
+/// Lock on one field, shared or exclusive depending on the lock plan, or no
+/// lock for a domain the plan leaves out
+#[derive(Debug)]
+pub(super) enum DomainGuard<'a, T> {
+    Shared(RwLockReadGuard<'a, T>, Domain),
+    Exclusive(RwLockWriteGuard<'a, T>),
+    Unlocked(Domain),
+}
+
+impl<T> Deref for DomainGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        match self {
+            Self::Shared(guard, _) => guard,
+            Self::Exclusive(guard) => guard,
+            // The panic fails the call, like any other bug in a handler
+            Self::Unlocked(domain) => panic!(
+                "{:?} domain is not locked; add it to the call's lock plan",
+                domain
+            ),
+        }
+    }
+}
+
+impl<T> DerefMut for DomainGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        match self {
+            // A lock plan that misses a changed domain is a bug in the plan
+            Self::Shared(_, domain) => panic!(
+                "{:?} domain is locked for reading; add it to the call's lock plan",
+                domain
+            ),
+            Self::Exclusive(guard) => guard,
+            Self::Unlocked(domain) => panic!(
+                "{:?} domain is not locked; add it to the call's lock plan",
+                domain
+            ),
+        }
+    }
+}
+
+async fn guard<T>(lock: &RwLock<T>, domain: Domain, plan: LockPlan) -> DomainGuard<'_, T> {
+    if plan.is_exclusive(domain) {
+        DomainGuard::Exclusive(lock.write().await)
+    } else if plan.is_shared(domain) {
+        DomainGuard::Shared(lock.read().await, domain)
+    } else {
+        DomainGuard::Unlocked(domain)
+    }
+}
+
+/// Locked view of the state handed to a call, with the same fields as `ResolveState`
+#[derive(Debug)]
+pub(super) struct StateView<'a> {
+    pub(super) current_project: DomainGuard<'a, Option<String>>,
+    pub(super) projects: DomainGuard<'a, Vec<String>>,
+    pub(super) current_page: DomainGuard<'a, String>,
+    pub(super) project_settings: DomainGuard<'a, HashMap<String, Value>>,
+    pub(super) backups: DomainGuard<'a, backup::BackupState>,
+    pub(super) trash: DomainGuard<'a, trash::TrashState>,
+    pub(super) media_pool: DomainGuard<'a, MediaPool>,
+    pub(super) transcripts: DomainGuard<'a, transcripts::TranscriptState>,
+    pub(super) timelines: DomainGuard<'a, HashMap<String, Timeline>>,
+    pub(super) current_timeline: DomainGuard<'a, Option<String>>,
+    pub(super) timeline_items: DomainGuard<'a, TimelineItemsState>,
+    pub(super) keyframe_state: DomainGuard<'a, KeyframeState>,
+    pub(super) color_state: DomainGuard<'a, ColorState>,
+    pub(super) gallery: DomainGuard<'a, GalleryState>,
+    pub(super) render_state: DomainGuard<'a, RenderState>,
+    pub(super) review: DomainGuard<'a, review::ReviewState>,
+    pub(super) tags: DomainGuard<'a, tags::TagState>,
+    pub(super) notes: DomainGuard<'a, notes::NoteState>,
+    pub(super) jobs: DomainGuard<'a, jobs::JobState>,
+}
+
+impl SharedState {
+    /// Lock the fields of the plan's domains in declaration order, so calls
+    /// cannot deadlock, leaving the others unlocked
+    pub(super) async fn lock(&self, plan: LockPlan) -> StateView<'_> {
+        StateView {
+            current_project: guard(&self.current_project, Domain::Project, plan).await,
+            projects: guard(&self.projects, Domain::Project, plan).await,
+            current_page: guard(&self.current_page, Domain::Project, plan).await,
+            project_settings: guard(&self.project_settings, Domain::Project, plan).await,
+            backups: guard(&self.backups, Domain::Project, plan).await,
+            trash: guard(&self.trash, Domain::Project, plan).await,
+            media_pool: guard(&self.media_pool, Domain::MediaPool, plan).await,
+            transcripts: guard(&self.transcripts, Domain::MediaPool, plan).await,
+            timelines: guard(&self.timelines, Domain::Timelines, plan).await,
+            current_timeline: guard(&self.current_timeline, Domain::Timelines, plan).await,
+            timeline_items: guard(&self.timeline_items, Domain::Timelines, plan).await,
+            keyframe_state: guard(&self.keyframe_state, Domain::Timelines, plan).await,
+            color_state: guard(&self.color_state, Domain::Color, plan).await,
+            gallery: guard(&self.gallery, Domain::Color, plan).await,
+            render_state: guard(&self.render_state, Domain::Render, plan).await,
+            review: guard(&self.review, Domain::Review, plan).await,
+            tags: guard(&self.tags, Domain::Review, plan).await,
+            notes: guard(&self.notes, Domain::Review, plan).await,
+            jobs: guard(&self.jobs, Domain::Jobs, plan).await,
+        }
+    }
+}
@@ -0,0 +1,366 @@
+//! Color spaces of applied LUTs
+//!
+//! A LUT converts from one color space, and applied to a node that receives
+//! another it still renders, only wrongly. Before apply_lut runs, in either
+//! mode, the color space the LUT expects is compared with the one its node
+//! receives, and `luts.color_space_check` decides whether a mismatch is
+//! allowed with a warning or refused unless the call allows it.
+//!
+//! The LUT's input comes from `luts.color_spaces` or from its name or title,
+//! which by convention reads `<input> to <output>`, as in
+//! `ARRI LogC3 to Rec709`. What the node receives depends on the project's
+//! color science: with color management it is the timeline color space, and
+//! without it the clip as recorded, which is the clip's input color space or
+//! else the project's. When either side is unknown the check reports the LUT
+//! as unverified and lets it through.
+
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use super::locking::LockPlan;
+use super::{ConnectionMode, ResolveBridge, StateView, SCRIPT_PROJECT};
+use crate::config::{LutColorSpaceCheck, LutColorSpaces};
+use crate::error::{ResolveError, ResolveResult};
+
+/// Color spaces by display name, with the spellings of each in LUT and
+/// setting names, lowercased with everything but letters and digits removed
+const COLOR_SPACES: [(&str, &[&str]); 19] = [
+    (
+        "ARRI LogC3",
+        &[
+            "logc",
+            "logc3",
+            "arrilogc",
+            "arrilogc3",
+            "alexalogc",
+            "logc3ewg",
+        ],
+    ),
+    ("ARRI LogC4", &["logc4", "arrilogc4", "logc4awg4"]),
+    (
+        "Sony S-Log3",
+        &["slog3", "sonyslog3", "slog3sgamut3", "slog3sgamut3cine"],
+    ),
+    ("Sony S-Log2", &["slog2", "sonyslog2", "slog2sgamut"]),
+    ("Panasonic V-Log", &["vlog", "panasonicvlog", "vlogvgamut"]),
+    ("Canon Log 3", &["clog3", "canonlog3", "canonclog3"]),
+    ("Canon Log 2", &["clog2", "canonlog2", "canonclog2"]),
+    (
+        "RED Log3G10",
+        &["log3g10", "redlog3g10", "redwidegamutrgblog3g10"],
+    ),
+    (
+        "Blackmagic Film Gen 5",
+        &[
+            "bmdfilm",
+            "bmdfilmgen5",
+            "blackmagicfilmgen5",
+            "blackmagicdesignfilmgen5",
+        ],
+    ),
+    (
+        "DaVinci Intermediate",
+        &[
+            "davinciintermediate",
+            "davinciwgintermediate",
+            "davinciwidegamutintermediate",
+            "dwgintermediate",
+        ],
+    ),
+    ("ACEScct", &["acescct", "acescctap1"]),
+    ("ACEScc", &["acescc", "acesccap1"]),
+    ("ACES2065-1", &["aces", "acesap0", "aces20651"]),
+    (
+        "Rec.709",
+        &["rec709", "rec709gamma24", "bt709", "rec709g24"],
+    ),
+    ("sRGB", &["srgb"]),
+    ("Rec.2020", &["rec2020", "rec2020gamma24", "bt2020"]),
+    (
+        "Rec.2100 ST2084",
+        &["rec2100st2084", "rec2100pq", "st2084", "pq"],
+    ),
+    ("Rec.2100 HLG", &["rec2100hlg", "hlg"]),
+    ("P3-D65", &["p3d65", "dcip3d65", "p3d65gamma26"]),
+];
+
+/// Lowercase letters and digits of `name`
+fn normalize(name: &str) -> String {
+    name.chars()
+        .filter(char::is_ascii_alphanumeric)
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+
+/// The color space `name` spells, matched whole or, failing that, by the
+/// longest spelling it contains
+fn color_space(name: &str) -> Option<&'static str> {
+    let name = normalize(name);
+    if name.is_empty() {
+        return None;
+    }
+    let spellings = || {
+        COLOR_SPACES.iter().flat_map(|(space, spellings)| {
+            spellings.iter().map(move |spelling| (*space, *spelling))
+        })
+    };
+    spellings()
+        .find(|(_, spelling)| *spelling == name)
+        .or_else(|| {
+            // Short spellings such as "pq" only match whole
+            spellings()
+                .filter(|(_, spelling)| spelling.len() >= 4 && name.contains(spelling))
+                .max_by_key(|(_, spelling)| spelling.len())
+        })
+        .map(|(space, _)| space)
+}
+
+/// Whether two color space names mean the same space
+fn same_space(a: &str, b: &str) -> bool {
+    match (color_space(a), color_space(b)) {
+        (Some(a), Some(b)) => a == b,
+        _ => normalize(a) == normalize(b),
+    }
+}
+
+/// The input side of a name like `ARRI LogC3 to Rec709`, `Rec709_to_sRGB`
+/// or `SLog3SGamut3.CineToLC-709`, if it names a known color space
+fn input_from_name(name: &str) -> Option<&'static str> {
+    let lower = name.to_ascii_lowercase();
+    let split = [" to ", "_to_", "-to-", ".to."]
+        .iter()
+        .find_map(|separator| lower.find(separator))
+        .or_else(|| {
+            // CamelCase, as in Sony's LUT names: "To" after a lowercase letter
+            let bytes = name.as_bytes();
+            (1..bytes.len().saturating_sub(1)).find(|&i| {
+                bytes[i] == b'T' && bytes[i + 1] == b'o' && bytes[i - 1].is_ascii_lowercase()
+            })
+        })?;
+    color_space(&name[..split])
+}
+
+/// The color space the LUT expects and where that came from
+fn lut_input(
+    state: &StateView<'_>,
+    spaces: &BTreeMap<String, LutColorSpaces>,
+    lut_path: &str,
+) -> Option<(String, &'static str)> {
+    let file_name = Path::new(lut_path)
+        .file_name()
+        .and_then(|name| name.to_str());
+    let configured = spaces
+        .get(lut_path)
+        .or_else(|| file_name.and_then(|name| spaces.get(name)));
+    if let Some(spaces) = configured {
+        return Some((spaces.input.clone(), "config"));
+    }
+    let info = state
+        .color_state
+        .available_luts
+        .iter()
+        .find(|(key, info)| *key == lut_path || info.path == lut_path)
+        .map(|(_, info)| info);
+    if let Some(info) = info {
+        if let Some(config) = spaces.get(&info.path) {
+            return Some((config.input.clone(), "config"));
+        }
+        if let Some(space) = input_from_name(&info.name) {
+            return Some((space.to_string(), "name"));
+        }
+    }
+    let stem = Path::new(lut_path)
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or(lut_path);
+    input_from_name(stem).map(|space| (space.to_string(), "name"))
+}
+
+/// What a node is fed, from the project's color science and settings and
+/// the clip's input color space
+struct NodeInput {
+    color_science: Option<String>,
+    timeline_color_space: Option<String>,
+    project_input_color_space: Option<String>,
+    clip_input_color_space: Option<String>,
+}
+
+impl NodeInput {
+    fn managed(&self) -> bool {
+        self.color_science.as_deref().is_some_and(|science| {
+            let science = normalize(science);
+            science.contains("colormanaged") || science.starts_with("aces")
+        })
+    }
+
+    /// The color space the node receives and what decided it
+    fn color_space(&self) -> Option<(String, &'static str)> {
+        if self.managed() {
+            let timeline = self.timeline_color_space.clone().or_else(|| {
+                let science = normalize(self.color_science.as_deref()?);
+                science.starts_with("aces").then(|| {
+                    if science.starts_with("acescct") {
+                        "ACEScct".to_string()
+                    } else {
+                        "ACEScc".to_string()
+                    }
+                })
+            });
+            return timeline.map(|space| (space, "timeline_color_space"));
+        }
+        self.clip_input_color_space
+            .clone()
+            .map(|space| (space, "clip_input_color_space"))
+            .or_else(|| {
+                self.project_input_color_space
+                    .clone()
+                    .map(|space| (space, "project_input_color_space"))
+            })
+    }
+}
+
+/// A setting's value, with the placeholders Resolve uses for none removed
+fn setting(value: Option<&str>) -> Option<String> {
+    let value = value?.trim();
+    (!value.is_empty() && !value.eq_ignore_ascii_case("project")).then(|| value.to_string())
+}
+
+/// Reads the color science and color spaces of the running Resolve
+const READ_NODE_INPUT: &str = r#"
+    output = {
+        "success": True,
+        "color_science": project.GetSetting("colorScienceMode"),
+        "timeline_color_space": project.GetSetting("colorSpaceTimeline"),
+        "project_input_color_space": project.GetSetting("colorSpaceInput"),
+        "clip_input_color_space": None
+    }
+    timeline = project.GetCurrentTimeline()
+    item = timeline.GetCurrentVideoItem() if timeline else None
+    media = item.GetMediaPoolItem() if item else None
+    if media:
+        output["clip_input_color_space"] = media.GetClipProperty("Input Color Space")
+    print(json.dumps(output))
+"#;
+
+/// Add the outcome of a check to an apply_lut response, warning in its
+/// result line when the color spaces did not match
+pub(super) fn attach(mut response: Value, check: Option<Value>) -> Value {
+    let Some(check) = check else {
+        return response;
+    };
+    if let (Some(result), Some(warning)) = (response["result"].as_str(), check["warning"].as_str())
+    {
+        response["result"] = json!(format!("{} (warning: {})", result, warning));
+    }
+    response["color_space_check"] = check;
+    response
+}
+
+impl ResolveBridge {
+    /// What the node the LUT goes on receives, from the simulation
+    fn simulated_node_input(state: &StateView<'_>) -> NodeInput {
+        let text = |name: &str| setting(state.project_settings.get(name).and_then(Value::as_str));
+        let clip = state
+            .color_state
+            .current_clip
+            .as_ref()
+            .and_then(|clip| state.media_pool.clips.get(clip));
+        NodeInput {
+            color_science: text("colorScienceMode"),
+            timeline_color_space: text("colorSpaceTimeline"),
+            project_input_color_space: text("colorSpaceInput"),
+            clip_input_color_space: clip
+                .and_then(|clip| setting(clip.color.input_color_space.as_deref())),
+        }
+    }
+
+    /// What the node the LUT goes on receives in the running Resolve
+    fn real_node_input(&self) -> ResolveResult<NodeInput> {
+        let output = self.run_resolve_script(
+            "apply_lut",
+            &Self::resolve_script("", SCRIPT_PROJECT, &Value::Null, READ_NODE_INPUT)?,
+        )?;
+        Ok(NodeInput {
+            color_science: setting(output["color_science"].as_str()),
+            timeline_color_space: setting(output["timeline_color_space"].as_str()),
+            project_input_color_space: setting(output["project_input_color_space"].as_str()),
+            clip_input_color_space: setting(output["clip_input_color_space"].as_str()),
+        })
+    }
+
+    /// Compare the input color space of the LUT apply_lut is about to apply
+    /// with what its node receives; a mismatch is refused when the check
+    /// blocks and the call does not allow it
+    pub(super) async fn check_lut_color_space(&self, args: &Value) -> ResolveResult<Option<Value>> {
+        let policy = self.config.luts.color_space_check;
+        if policy == LutColorSpaceCheck::Off {
+            return Ok(None);
+        }
+        let Some(lut_path) = args["lut_path"].as_str() else {
+            return Ok(None);
+        };
+        let allowed = args["allow_color_space_mismatch"]
+            .as_bool()
+            .unwrap_or(false);
+
+        let state = self.state.lock(LockPlan::READ).await;
+        let expected = lut_input(&state, &self.config.luts.color_spaces, lut_path);
+        let simulated = Self::simulated_node_input(&state);
+        drop(state);
+        // Resolve is read without holding the locks; the simulation answers
+        // when it cannot be read, as the call would fall back to it
+        let node = match self.mode {
+            ConnectionMode::Real => self.real_node_input().unwrap_or_else(|e| {
+                tracing::debug!("Reading color settings from Resolve failed: {}", e);
+                simulated
+            }),
+            ConnectionMode::Simulation => simulated,
+        };
+        let received = node.color_space();
+
+        let status = match (&expected, &received) {
+            (Some((expected, _)), Some((received, _))) if same_space(expected, received) => "match",
+            (Some(_), Some(_)) => "mismatch",
+            _ => "unverified",
+        };
+        let warning = (status == "mismatch").then(|| {
+            let (expected, _) = expected.as_ref().expect("a mismatch has both sides");
+            let (received, source) = received.as_ref().expect("a mismatch has both sides");
+            format!(
+                "{} expects {} but its node receives {} ({})",
+                lut_path,
+                expected,
+                received,
+                source.replace('_', " ")
+            )
+        });
+        if let Some(warning) = &warning {
+            if policy == LutColorSpaceCheck::Block && !allowed {
+                return Err(ResolveError::invalid_parameter(
+                    "lut_path",
+                    format!(
+                        "{}; set allow_color_space_mismatch to apply it anyway",
+                        warning
+                    ),
+                ));
+            }
+        }
+
+        Ok(Some(json!({
+            "status": status,
+            "policy": match policy {
+                LutColorSpaceCheck::Block => "block",
+                _ => "warn",
+            },
+            "lut_input_color_space": expected.as_ref().map(|(space, _)| space),
+            "lut_input_from": expected.as_ref().map(|(_, from)| from),
+            "node_input_color_space": received.as_ref().map(|(space, _)| space),
+            "node_input_from": received.as_ref().map(|(_, from)| from),
+            "color_science": node.color_science,
+            "color_managed": node.managed(),
+            "mismatch_allowed": status == "mismatch" && allowed,
+            "warning": warning
+        })))
+    }
+}
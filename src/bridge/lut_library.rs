@@ -0,0 +1,374 @@
+//! LUT folders
+//!
+//! The LUTs apply_lut accepts by name come from an index of the LUT folders in
+//! `Config::luts`, next to the few LUTs Resolve ships with. Each .cube, .3dl,
+//! .vlt, .ilut and .olut file is indexed by its path relative to its folder,
+//! `Film Looks/Kodak 2383.cube`, the way Resolve names LUTs, with the format
+//! and size read from the file. Files that cannot be read as a LUT are left
+//! out and reported.
+//!
+//! The index is built on first use and rebuilt by scan_lut_directories. With
+//! `watch` on, it is also rebuilt before use whenever a folder gained, lost or
+//! renamed files, which shows as a change of a folder's modification time.
+
+use serde_json::{json, Value};
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use uuid::Uuid;
+use walkdir::WalkDir;
+
+use super::{LutInfo, ResolveBridge, StateView};
+use crate::error::ResolveResult;
+
+/// LUT file extensions and the format each holds
+const LUT_FORMATS: [(&str, &str); 5] = [
+    ("cube", "Cube"),
+    ("3dl", "3dl"),
+    ("vlt", "Panasonic"),
+    ("ilut", "Davinci"),
+    ("olut", "Davinci"),
+];
+
+/// Largest 3D grid size accepted
+const MAX_GRID_SIZE: u32 = 256;
+
+/// Last scan of the LUT folders
+#[derive(Debug, Clone)]
+pub(super) struct LutScan {
+    scanned_at: String,
+    /// Modification time of every folder below each LUT folder, for watching
+    fingerprint: Vec<(PathBuf, Option<SystemTime>)>,
+    /// Files that could not be read as LUTs
+    errors: Vec<Value>,
+}
+
+/// Modification times of `directories` and every folder below them
+fn fingerprint(directories: &[PathBuf]) -> Vec<(PathBuf, Option<SystemTime>)> {
+    let mut folders = Vec::new();
+    for directory in directories {
+        if !directory.is_dir() {
+            folders.push((directory.clone(), None));
+            continue;
+        }
+        for entry in WalkDir::new(directory)
+            .sort_by_file_name()
+            .into_iter()
+            .filter_map(Result::ok)
+            .filter(|entry| entry.file_type().is_dir())
+        {
+            let modified = entry.metadata().ok().and_then(|meta| meta.modified().ok());
+            folders.push((entry.into_path(), modified));
+        }
+    }
+    folders
+}
+
+/// Format of a LUT file, from its extension
+fn lut_format(path: &Path) -> Option<&'static str> {
+    let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+    LUT_FORMATS
+        .iter()
+        .find(|(ext, _)| *ext == extension)
+        .map(|(_, format)| *format)
+}
+
+/// Lines of a LUT file other than blanks and comments
+fn content_lines(path: &Path) -> Result<impl Iterator<Item = String>, String> {
+    let file = std::fs::File::open(path).map_err(|e| e.to_string())?;
+    Ok(BufReader::new(file)
+        .lines()
+        .map_while(Result::ok)
+        .map(|line| line.trim().to_string())
+        .filter(|line| !line.is_empty() && !line.starts_with('#')))
+}
+
+/// The number after `keyword` on a header line such as `LUT_3D_SIZE 33`
+fn header_size(line: &str, keyword: &str) -> Option<Result<u32, String>> {
+    let value = line.strip_prefix(keyword)?.trim();
+    Some(
+        value
+            .parse()
+            .map_err(|_| format!("{} '{}' is not a number", keyword, value)),
+    )
+}
+
+/// Dimension, size and title of a LUT file of `format`
+fn read_lut(path: &Path, format: &str) -> Result<(&'static str, u32, Option<String>), String> {
+    let mut lines = content_lines(path)?;
+    let (dimension, size, title) = match format {
+        "Cube" => {
+            let mut title = None;
+            let mut found = None;
+            for line in lines.by_ref() {
+                if let Some(rest) = line.strip_prefix("TITLE") {
+                    title = Some(rest.trim().trim_matches('"').to_string());
+                } else if let Some(size) = header_size(&line, "LUT_3D_SIZE") {
+                    found = Some(("3D", size?));
+                    break;
+                } else if let Some(size) = header_size(&line, "LUT_1D_SIZE") {
+                    found = Some(("1D", size?));
+                    break;
+                } else if line.starts_with(|c: char| c.is_ascii_digit() || c == '-' || c == '.') {
+                    break;
+                }
+            }
+            let (dimension, size) = found.ok_or("no LUT_3D_SIZE or LUT_1D_SIZE line")?;
+            (dimension, size, title)
+        }
+        "3dl" => {
+            // The first line lists the input shaper, one value per grid point
+            let shaper = lines.next().ok_or("file is empty")?;
+            let points = shaper.split_whitespace().count();
+            if points < 2 || shaper.split_whitespace().any(|v| v.parse::<u32>().is_err()) {
+                return Err("first line is not a shaper of grid points".to_string());
+            }
+            ("3D", points as u32, None)
+        }
+        "Panasonic" => {
+            let size = lines
+                .find_map(|line| header_size(&line, "GRID_SIZE"))
+                .ok_or("no GRID_SIZE line")??;
+            ("3D", size, None)
+        }
+        _ => ("1D", lines.count() as u32, None),
+    };
+    let valid = match dimension {
+        "3D" => (2..=MAX_GRID_SIZE).contains(&size),
+        _ => size >= 2,
+    };
+    if !valid {
+        return Err(format!("{} size {} is out of range", dimension, size));
+    }
+    Ok((dimension, size, title))
+}
+
+/// Index every LUT file in `directories`, returning the LUTs by relative path
+/// and the files that could not be read
+fn scan(directories: &[PathBuf]) -> (Vec<(String, LutInfo)>, Vec<Value>) {
+    let mut luts: Vec<(String, LutInfo)> = Vec::new();
+    let mut errors = Vec::new();
+    for directory in directories {
+        if !directory.is_dir() {
+            errors.push(json!({
+                "path": directory.display().to_string(),
+                "error": "not a folder"
+            }));
+            continue;
+        }
+        for entry in WalkDir::new(directory)
+            .sort_by_file_name()
+            .into_iter()
+            .filter_map(Result::ok)
+            .filter(|entry| entry.file_type().is_file())
+        {
+            let path = entry.path();
+            let Some(format) = lut_format(path) else {
+                continue;
+            };
+            let Ok(relative) = path.strip_prefix(directory) else {
+                continue;
+            };
+            let key = relative
+                .components()
+                .map(|part| part.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            // An earlier folder's LUT of the same relative path wins
+            if luts.iter().any(|(existing, _)| *existing == key) {
+                continue;
+            }
+            match read_lut(path, format) {
+                Ok((dimension, size, title)) => {
+                    let metadata = entry.metadata().ok();
+                    luts.push((
+                        key,
+                        LutInfo {
+                            name: title.unwrap_or_else(|| {
+                                path.file_stem()
+                                    .map(|stem| stem.to_string_lossy().to_string())
+                                    .unwrap_or_default()
+                            }),
+                            path: path.display().to_string(),
+                            format: format.to_string(),
+                            size: format!("{}Point", size),
+                            dimension,
+                            file_size: metadata.as_ref().map(|meta| meta.len()),
+                            modified: metadata.and_then(|meta| meta.modified().ok()).map(|time| {
+                                chrono::DateTime::<chrono::Utc>::from(time).to_rfc3339()
+                            }),
+                            directory: Some(directory.display().to_string()),
+                        },
+                    ));
+                }
+                Err(error) => errors.push(json!({
+                    "path": path.display().to_string(),
+                    "error": error
+                })),
+            }
+        }
+    }
+    (luts, errors)
+}
+
+impl LutInfo {
+    pub(super) fn to_json(&self, lut: &str) -> Value {
+        json!({
+            "lut": lut,
+            "name": self.name,
+            "path": self.path,
+            "format": self.format,
+            "dimension": self.dimension,
+            "size": self.size,
+            "file_size_bytes": self.file_size,
+            "modified": self.modified,
+            "directory": self.directory,
+            "source": if self.directory.is_some() { "directory" } else { "builtin" }
+        })
+    }
+}
+
+impl ResolveBridge {
+    /// Rebuild the index of the configured LUT folders, returning the LUTs
+    /// added and removed
+    fn scan_luts(&self, state: &mut StateView<'_>) -> (Vec<String>, Vec<String>) {
+        let directories = &self.config.luts.directories;
+        let fingerprint = fingerprint(directories);
+        let (luts, errors) = scan(directories);
+
+        let available = &mut state.color_state.available_luts;
+        let mut removed: Vec<String> = available
+            .iter()
+            .filter(|(key, info)| {
+                info.directory.is_some() && !luts.iter().any(|(lut, _)| lut == *key)
+            })
+            .map(|(key, _)| key.clone())
+            .collect();
+        removed.sort();
+        let added: Vec<String> = luts
+            .iter()
+            .filter(|(key, _)| {
+                available
+                    .get(key)
+                    .is_none_or(|info| info.directory.is_none())
+            })
+            .map(|(key, _)| key.clone())
+            .collect();
+        available.retain(|_, info| info.directory.is_none());
+        available.extend(luts);
+        state.color_state.lut_scan = Some(LutScan {
+            scanned_at: chrono::Utc::now().to_rfc3339(),
+            fingerprint,
+            errors,
+        });
+        (added, removed)
+    }
+
+    /// Build the LUT index on first use, and rebuild it if a watched folder changed
+    pub(super) fn refresh_lut_index(&self, state: &mut StateView<'_>) {
+        let luts = &self.config.luts;
+        if luts.directories.is_empty() {
+            return;
+        }
+        let stale = match &state.color_state.lut_scan {
+            None => true,
+            Some(scan) => luts.watch && scan.fingerprint != fingerprint(&luts.directories),
+        };
+        if stale {
+            self.scan_luts(state);
+        }
+    }
+
+    pub(super) async fn scan_lut_directories(
+        &self,
+        state: &mut StateView<'_>,
+        _args: Value,
+    ) -> ResolveResult<Value> {
+        let (added, removed) = self.scan_luts(state);
+        let directories: Vec<Value> = self
+            .config
+            .luts
+            .directories
+            .iter()
+            .map(|directory| {
+                let folder = directory.display().to_string();
+                json!({
+                    "path": folder,
+                    "exists": directory.is_dir(),
+                    "lut_count": state
+                        .color_state
+                        .available_luts
+                        .values()
+                        .filter(|info| info.directory.as_deref() == Some(folder.as_str()))
+                        .count()
+                })
+            })
+            .collect();
+        let indexed = state
+            .color_state
+            .available_luts
+            .values()
+            .filter(|info| info.directory.is_some())
+            .count();
+        let errors = state
+            .color_state
+            .lut_scan
+            .as_ref()
+            .map(|scan| scan.errors.clone())
+            .unwrap_or_default();
+
+        Ok(json!({
+            "result": format!(
+                "Indexed {} LUTs in {} folders",
+                indexed,
+                directories.len()
+            ),
+            "directories": directories,
+            "lut_count": indexed,
+            "added": added,
+            "removed": removed,
+            "errors": errors,
+            "watching": self.config.luts.watch,
+            "operation_id": Uuid::new_v4().to_string()
+        }))
+    }
+
+    pub(super) async fn list_luts(
+        &self,
+        state: &mut StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        self.refresh_lut_index(state);
+        let format = args["format"].as_str().map(str::to_ascii_lowercase);
+        let search = args["search"].as_str().map(str::to_lowercase);
+        let mut luts: Vec<(&String, &LutInfo)> = state
+            .color_state
+            .available_luts
+            .iter()
+            .filter(|(_, info)| {
+                format
+                    .as_ref()
+                    .is_none_or(|format| info.format.to_ascii_lowercase() == *format)
+            })
+            .filter(|(key, info)| {
+                search.as_ref().is_none_or(|search| {
+                    key.to_lowercase().contains(search) || info.name.to_lowercase().contains(search)
+                })
+            })
+            .collect();
+        luts.sort_by_key(|(key, _)| key.as_str());
+        let scan = state.color_state.lut_scan.as_ref();
+
+        Ok(json!({
+            "result": format!("Found {} LUTs", luts.len()),
+            "count": luts.len(),
+            "luts": luts
+                .iter()
+                .map(|(key, info)| info.to_json(key))
+                .collect::<Vec<_>>(),
+            "scanned_at": scan.map(|scan| &scan.scanned_at),
+            "errors": scan.map_or_else(Vec::new, |scan| scan.errors.clone()),
+            "operation_id": Uuid::new_v4().to_string()
+        }))
+    }
+}
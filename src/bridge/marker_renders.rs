@@ -0,0 +1,210 @@
+//! Rendering marker ranges
+//!
+//! render_marker_ranges queues one render job per marked region of a
+//! timeline, the usual way to cut social clips out of long-form content.
+//! Regions are duration markers, each rendering the frames it spans, or
+//! markers of one color taken two at a time in frame order as in and out
+//! points, the out marker's frame being the last one rendered. Each output is named after its
+//! marker, or the in marker of a pair: the marker's name, else its note.
+//! As with batch renders, if any job cannot be queued, none is.
+
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, HashMap};
+use uuid::Uuid;
+
+use super::batch_render::{path_component, DEFAULT_OUTPUT_TEMPLATE};
+use super::markers::marker_color;
+use super::{paths, Marker, ResolveBridge, StateView};
+use crate::error::{ResolveError, ResolveResult};
+
+/// A region of the timeline to render, end exclusive
+struct MarkedRange<'a> {
+    marker: &'a Marker,
+    /// Out marker of a pair
+    out_marker: Option<&'a Marker>,
+    start: i64,
+    end: i64,
+}
+
+/// What a marker's output is named after
+fn marker_label(marker: &Marker, index: usize) -> String {
+    [&marker.name, &marker.note]
+        .into_iter()
+        .map(|text| text.trim())
+        .find(|text| !text.is_empty())
+        .map_or_else(|| format!("marker_{:02}", index + 1), str::to_string)
+}
+
+/// Regions marked by `markers`, which are on frames and in frame order
+fn marked_ranges<'a>(markers: &[&'a Marker], pairs: bool) -> ResolveResult<Vec<MarkedRange<'a>>> {
+    let frame = |marker: &Marker| i64::from(marker.frame.unwrap_or_default());
+    if !pairs {
+        return Ok(markers
+            .iter()
+            .copied()
+            .filter(|marker| marker.duration > 1)
+            .map(|marker| MarkedRange {
+                marker,
+                out_marker: None,
+                start: frame(marker),
+                end: frame(marker) + marker.duration,
+            })
+            .collect());
+    }
+    // Markers pair up with the next marker of the same color
+    let mut colors: BTreeMap<String, Vec<&'a Marker>> = BTreeMap::new();
+    for &marker in markers {
+        colors
+            .entry(marker.color.to_ascii_lowercase())
+            .or_default()
+            .push(marker);
+    }
+    let mut ranges = Vec::with_capacity(markers.len() / 2);
+    for same_color in colors.values() {
+        if same_color.len() % 2 == 1 {
+            let unpaired = same_color[same_color.len() - 1];
+            return Err(ResolveError::invalid_parameter(
+                "source",
+                format!(
+                    "markers of one color pair up as in and out points, and the {} one at frame {} has no out marker",
+                    unpaired.color,
+                    frame(unpaired)
+                ),
+            ));
+        }
+        ranges.extend(same_color.chunks(2).map(|pair| MarkedRange {
+            marker: pair[0],
+            out_marker: Some(pair[1]),
+            start: frame(pair[0]),
+            end: frame(pair[1]) + 1,
+        }));
+    }
+    ranges.sort_by_key(|range| range.start);
+    Ok(ranges)
+}
+
+impl ResolveBridge {
+    pub(super) async fn render_marker_ranges(
+        &self,
+        state: &mut StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        let timeline_name = Self::resolve_timeline_name(state, &args)?;
+        let preset_name = args["preset_name"]
+            .as_str()
+            .ok_or_else(|| ResolveError::invalid_parameter("preset_name", "required string"))?;
+        let pairs = match args["source"].as_str().unwrap_or("duration") {
+            "duration" => false,
+            "pairs" => true,
+            _ => {
+                return Err(ResolveError::invalid_parameter(
+                    "source",
+                    "must be 'duration' or 'pairs'",
+                ))
+            }
+        };
+        let color = match args["color"].as_str() {
+            Some(color) => Some(marker_color(color).ok_or_else(|| {
+                ResolveError::invalid_parameter(
+                    "color",
+                    format!("'{}' is not a marker color", color),
+                )
+            })?),
+            None => None,
+        };
+        let template = args["output_directory"]
+            .as_str()
+            .unwrap_or(DEFAULT_OUTPUT_TEMPLATE)
+            .trim_end_matches('/');
+        if template.is_empty() {
+            return Err(ResolveError::invalid_parameter(
+                "output_directory",
+                "must not be empty",
+            ));
+        }
+        let project = path_component(state.current_project.as_deref().unwrap_or("Untitled"));
+        let directory = template
+            .replace("{project}", &project)
+            .replace("{timeline}", &path_component(&timeline_name));
+        paths::check_path(&self.config.sandbox, &directory)?;
+
+        // Regions are read from a copy so jobs can be queued while they are held
+        let timeline_markers = state.timelines[&timeline_name].markers.clone();
+        let mut markers: Vec<&Marker> = timeline_markers
+            .iter()
+            .filter(|marker| marker.frame.is_some())
+            .filter(|marker| color.is_none_or(|color| marker.color.eq_ignore_ascii_case(color)))
+            .collect();
+        markers.sort_by_key(|marker| marker.frame);
+        let ranges = marked_ranges(&markers, pairs)?;
+        if ranges.is_empty() {
+            return Err(ResolveError::invalid_parameter(
+                "source",
+                format!(
+                    "timeline '{}' has no {}{}",
+                    timeline_name,
+                    color.map(|color| format!("{} ", color)).unwrap_or_default(),
+                    if pairs { "markers" } else { "duration markers" }
+                ),
+            ));
+        }
+
+        let queue_length = state.render_state.render_queue.len();
+        let job_counter = state.render_state.job_counter;
+        let mut used: HashMap<String, usize> = HashMap::new();
+        let mut jobs = Vec::with_capacity(ranges.len());
+        let mut failure = None;
+        for (index, range) in ranges.iter().enumerate() {
+            let label = marker_label(range.marker, index);
+            let mut file_name = path_component(&label);
+            let uses = used.entry(file_name.clone()).or_insert(0);
+            *uses += 1;
+            if *uses > 1 {
+                file_name = format!("{}_{}", file_name, uses);
+            }
+            let queued = self.queue_timeline_render(
+                state,
+                &timeline_name,
+                preset_name,
+                Some(format!("{}/{}", directory, file_name)),
+                Some((range.start, range.end)),
+                &args,
+            );
+            match queued {
+                Ok(mut job) => {
+                    job["marker_id"] = json!(range.marker.id);
+                    job["out_marker_id"] = json!(range.out_marker.map(|marker| &marker.id));
+                    job["marker_name"] = json!(label);
+                    job["start_frame"] = json!(range.start);
+                    job["frames"] = json!(range.end - range.start);
+                    jobs.push(job);
+                }
+                Err(error) => {
+                    failure = Some(error);
+                    break;
+                }
+            }
+        }
+        if let Some(error) = failure {
+            state.render_state.render_queue.truncate(queue_length);
+            state.render_state.job_counter = job_counter;
+            return Err(error);
+        }
+
+        Ok(json!({
+            "result": format!(
+                "Queued {} render jobs for the marked {} of timeline '{}' with preset '{}'",
+                jobs.len(),
+                if pairs { "in/out pairs" } else { "ranges" },
+                timeline_name,
+                preset_name
+            ),
+            "timeline_name": timeline_name,
+            "preset_name": preset_name,
+            "source": if pairs { "pairs" } else { "duration" },
+            "jobs": jobs,
+            "queue_length": state.render_state.render_queue.len(),
+            "operation_id": Uuid::new_v4().to_string()
+        }))
+    }
+}
@@ -0,0 +1,417 @@
+//! Timeline marker import and export
+//!
+//! Markers are exchanged as CSV, JSON or EDL locators using record timecode,
+//! converted to and from marker frames at the timeline's frame rate.
+
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use super::{ensure_capacity, Marker, ResolveBridge, StateView, Timeline};
+use crate::error::{ResolveError, ResolveResult};
+use crate::timecode::FrameRate;
+
+/// Marker colors Resolve accepts
+pub(super) const MARKER_COLORS: [&str; 16] = [
+    "Blue", "Cyan", "Green", "Yellow", "Red", "Pink", "Purple", "Fuchsia", "Rose", "Lavender",
+    "Sky", "Mint", "Lemon", "Sand", "Cocoa", "Cream",
+];
+
+/// Record timecode of the first frame of a new Resolve timeline
+const DEFAULT_TIMELINE_START: &str = "01:00:00:00";
+
+/// Record timecode of the first frame of `timeline`: the call's
+/// `timeline_start_timecode`, else the timeline's own start timecode
+pub(super) fn timeline_start<'a>(timeline: &'a Timeline, args: &'a Value) -> &'a str {
+    args["timeline_start_timecode"]
+        .as_str()
+        .or(timeline.start_timecode.as_deref())
+        .unwrap_or(DEFAULT_TIMELINE_START)
+}
+
+/// Match a color name case-insensitively against the Resolve marker colors
+pub(super) fn marker_color(color: &str) -> Option<&'static str> {
+    MARKER_COLORS
+        .iter()
+        .find(|candidate| candidate.eq_ignore_ascii_case(color.trim()))
+        .copied()
+}
+
+/// Split CSV text into rows of fields, honouring double-quoted fields
+pub(super) fn parse_csv(data: &str) -> Vec<Vec<String>> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = data.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => in_quotes = !in_quotes,
+            ',' if !in_quotes => row.push(std::mem::take(&mut field)),
+            '\n' if !in_quotes => {
+                row.push(std::mem::take(&mut field));
+                rows.push(std::mem::take(&mut row));
+            }
+            '\r' if !in_quotes => {}
+            _ => field.push(c),
+        }
+    }
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        rows.push(row);
+    }
+    rows.retain(|row| row.iter().any(|field| !field.trim().is_empty()));
+    rows
+}
+
+/// Quote a CSV field when it contains separators, quotes or line breaks
+pub(super) fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// A marker read from an import file, before conversion to a frame
+struct ImportedMarker {
+    /// Row or entry number, for error messages
+    line: usize,
+    timecode: String,
+    color: Option<String>,
+    note: String,
+}
+
+fn read_csv_markers(data: &str) -> ResolveResult<Vec<ImportedMarker>> {
+    let mut rows = parse_csv(data).into_iter();
+    let header: Vec<String> = rows
+        .next()
+        .ok_or_else(|| ResolveError::invalid_parameter("data", "CSV has no header row"))?
+        .iter()
+        .map(|name| name.trim().to_ascii_lowercase())
+        .collect();
+    let column = |names: &[&str]| {
+        header
+            .iter()
+            .position(|name| names.contains(&name.as_str()))
+    };
+    let timecode_column =
+        column(&["timecode", "tc", "record in", "source in"]).ok_or_else(|| {
+            ResolveError::invalid_parameter("data", "CSV header needs a 'Timecode' column")
+        })?;
+    let color_column = column(&["color", "colour"]);
+    let note_column = column(&["note", "notes", "comment", "comments"]);
+
+    Ok(rows
+        .enumerate()
+        .map(|(index, row)| {
+            let field = |column: Option<usize>| {
+                column
+                    .and_then(|column| row.get(column))
+                    .map(|value| value.trim().to_string())
+            };
+            ImportedMarker {
+                // Header is line 1
+                line: index + 2,
+                timecode: field(Some(timecode_column)).unwrap_or_default(),
+                color: field(color_column).filter(|color| !color.is_empty()),
+                note: field(note_column).unwrap_or_default(),
+            }
+        })
+        .collect())
+}
+
+fn read_json_markers(data: &str) -> ResolveResult<Vec<ImportedMarker>> {
+    let value: Value = serde_json::from_str(data)
+        .map_err(|e| ResolveError::invalid_parameter("data", format!("invalid JSON: {}", e)))?;
+    let entries = value
+        .as_array()
+        .or_else(|| value["markers"].as_array())
+        .ok_or_else(|| {
+            ResolveError::invalid_parameter(
+                "data",
+                "JSON must be an array of markers or an object with a 'markers' array",
+            )
+        })?;
+
+    entries
+        .iter()
+        .enumerate()
+        .map(|(index, entry)| {
+            let timecode = entry["timecode"].as_str().ok_or_else(|| {
+                ResolveError::invalid_parameter(
+                    "data",
+                    format!("marker {} has no 'timecode' string", index + 1),
+                )
+            })?;
+            Ok(ImportedMarker {
+                line: index + 1,
+                timecode: timecode.to_string(),
+                color: entry["color"].as_str().map(|color| color.to_string()),
+                note: entry["note"]
+                    .as_str()
+                    .or_else(|| entry["comment"].as_str())
+                    .unwrap_or_default()
+                    .to_string(),
+            })
+        })
+        .collect()
+}
+
+impl ResolveBridge {
+    /// Frame rate of a timeline, falling back to the default project frame rate
+    pub(super) fn timeline_frame_rate(&self, timeline: &Timeline) -> ResolveResult<FrameRate> {
+        let rate = timeline
+            .frame_rate
+            .as_deref()
+            .unwrap_or(&self.config.resolve.default_project.frame_rate);
+        FrameRate::parse(rate).map_err(|e| ResolveError::invalid_parameter("frame_rate", e))
+    }
+
+    /// Default project frame rate, used for clips outside any timeline
+    pub(super) fn project_frame_rate(&self) -> ResolveResult<FrameRate> {
+        FrameRate::parse(&self.config.resolve.default_project.frame_rate)
+            .map_err(|e| ResolveError::invalid_parameter("frame_rate", e))
+    }
+
+    /// Name of the timeline an operation targets, by ID or name, defaulting to the current one
+    pub(super) fn resolve_timeline_name(
+        state: &StateView<'_>,
+        args: &Value,
+    ) -> ResolveResult<String> {
+        match args["timeline_name"].as_str() {
+            Some(reference) => Self::resolve_timeline(state, reference),
+            None => {
+                let name = state.current_timeline.clone().ok_or_else(|| {
+                    ResolveError::TimelineNotFound {
+                        name: "current".to_string(),
+                    }
+                })?;
+                if !state.timelines.contains_key(&name) {
+                    return Err(ResolveError::TimelineNotFound { name });
+                }
+                Ok(name)
+            }
+        }
+    }
+
+    pub(super) async fn import_markers(
+        &self,
+        state: &mut StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        let format = args["format"]
+            .as_str()
+            .ok_or_else(|| ResolveError::invalid_parameter("format", "required string"))?;
+        let data = args["data"]
+            .as_str()
+            .ok_or_else(|| ResolveError::invalid_parameter("data", "required string"))?;
+        let timeline_name = Self::resolve_timeline_name(state, &args)?;
+        let start = timeline_start(&state.timelines[&timeline_name], &args);
+
+        let imported = match format.to_ascii_lowercase().as_str() {
+            "csv" => read_csv_markers(data)?,
+            "json" => read_json_markers(data)?,
+            _ => {
+                return Err(ResolveError::invalid_parameter(
+                    "format",
+                    "must be 'csv' or 'json'",
+                ))
+            }
+        };
+
+        let timeline = &state.timelines[&timeline_name];
+        let rate = self.timeline_frame_rate(timeline)?;
+        let frames_at = |timecode: &str| rate.for_timecode(timecode)?.timecode_to_frames(timecode);
+        let start_frame = frames_at(start)
+            .map_err(|e| ResolveError::invalid_parameter("timeline_start_timecode", e))?;
+
+        // Validate every marker before adding any of them
+        let mut markers = Vec::with_capacity(imported.len());
+        let mut skipped = Vec::new();
+        for entry in imported {
+            let line_error = |reason: String| {
+                ResolveError::invalid_parameter("data", format!("line {}: {}", entry.line, reason))
+            };
+            let frame = frames_at(&entry.timecode).map_err(line_error)? - start_frame;
+            if frame < 0 {
+                return Err(line_error(format!(
+                    "timecode {} is before the timeline start {}",
+                    entry.timecode, start
+                )));
+            }
+            let frame = i32::try_from(frame)
+                .map_err(|_| line_error(format!("timecode {} is out of range", entry.timecode)))?;
+            let color = match &entry.color {
+                Some(color) => marker_color(color)
+                    .ok_or_else(|| line_error(format!("unknown marker color '{}'", color)))?,
+                None => "Blue",
+            };
+
+            // Resolve keeps one marker per frame
+            let taken = timeline.markers.iter().any(|m| m.frame == Some(frame))
+                || markers.iter().any(|m: &Marker| m.frame == Some(frame));
+            if taken {
+                skipped.push(json!({"line": entry.line, "timecode": entry.timecode, "reason": "frame already has a marker"}));
+                continue;
+            }
+            markers.push(Marker {
+                id: Uuid::new_v4().to_string(),
+                frame: Some(frame),
+                color: color.to_string(),
+                note: entry.note,
+                name: String::new(),
+                duration: 1,
+            });
+        }
+
+        ensure_capacity(
+            &format!("markers on timeline '{}'", timeline_name),
+            timeline.markers.len(),
+            markers.len(),
+            self.config.limits.max_markers_per_timeline,
+        )?;
+
+        let imported_count = markers.len();
+        let timeline = state
+            .timelines
+            .get_mut(&timeline_name)
+            .ok_or_else(|| ResolveError::internal("timeline missing"))?;
+        timeline.markers.extend(markers);
+
+        Ok(json!({
+            "result": format!(
+                "Imported {} markers into timeline '{}'",
+                imported_count, timeline_name
+            ),
+            "timeline_name": timeline_name,
+            "frame_rate": rate.to_string(),
+            "imported_count": imported_count,
+            "skipped": skipped,
+            "total_markers": timeline.markers.len(),
+            "operation_id": Uuid::new_v4().to_string()
+        }))
+    }
+
+    pub(super) async fn export_markers(
+        &self,
+        state: &StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        let format = args["format"]
+            .as_str()
+            .ok_or_else(|| ResolveError::invalid_parameter("format", "required string"))?
+            .to_ascii_lowercase();
+        let timeline_name = Self::resolve_timeline_name(state, &args)?;
+        let timeline = &state.timelines[&timeline_name];
+        let rate = self.timeline_frame_rate(timeline)?;
+        let start = timeline_start(timeline, &args);
+        let rate = rate
+            .for_timecode(start)
+            .map_err(|e| ResolveError::invalid_parameter("timeline_start_timecode", e))?;
+        let start_frame = rate
+            .timecode_to_frames(start)
+            .map_err(|e| ResolveError::invalid_parameter("timeline_start_timecode", e))?;
+
+        let mut markers: Vec<(i32, &Marker)> = timeline
+            .markers
+            .iter()
+            .filter_map(|marker| marker.frame.map(|frame| (frame, marker)))
+            .collect();
+        markers.sort_by_key(|(frame, _)| *frame);
+        let timecode_at = |frame: i32| rate.frames_to_timecode(start_frame + i64::from(frame));
+
+        let content = match format.as_str() {
+            "csv" => {
+                let mut csv = String::from("Timecode,Frame,Color,Note\n");
+                for (frame, marker) in &markers {
+                    csv.push_str(&format!(
+                        "{},{},{},{}\n",
+                        timecode_at(*frame),
+                        frame,
+                        marker.color,
+                        csv_field(&marker.note)
+                    ));
+                }
+                csv
+            }
+            "json" => {
+                let entries: Vec<Value> = markers
+                    .iter()
+                    .map(|(frame, marker)| {
+                        json!({
+                            "timecode": timecode_at(*frame),
+                            "frame": frame,
+                            "color": marker.color,
+                            "note": marker.note,
+                            "name": marker.name,
+                            "duration": marker.duration,
+                            "marker_id": marker.id
+                        })
+                    })
+                    .collect();
+                serde_json::to_string_pretty(&json!({
+                    "timeline": timeline_name,
+                    "frame_rate": rate.to_string(),
+                    "markers": entries
+                }))?
+            }
+            "edl" => {
+                let mut edl = format!(
+                    "TITLE: {}\nFCM: {}\n\n",
+                    timeline_name,
+                    if rate.is_drop_frame() {
+                        "DROP FRAME"
+                    } else {
+                        "NON-DROP FRAME"
+                    }
+                );
+                for (index, (frame, marker)) in markers.iter().enumerate() {
+                    let record_in = timecode_at(*frame);
+                    // A locator spans its marker's duration, one frame at least
+                    let duration = marker.duration.max(1);
+                    let record_out =
+                        rate.frames_to_timecode(start_frame + i64::from(*frame) + duration);
+                    edl.push_str(&format!(
+                        "{:03}  001      V     C        {} {} {} {}  \n |C:ResolveColor{} |M:{} |D:{}\n\n",
+                        index + 1,
+                        record_in,
+                        record_out,
+                        record_in,
+                        record_out,
+                        marker.color,
+                        marker.note.replace(['\r', '\n'], " "),
+                        duration
+                    ));
+                }
+                edl
+            }
+            _ => {
+                return Err(ResolveError::invalid_parameter(
+                    "format",
+                    "must be 'csv', 'json' or 'edl'",
+                ))
+            }
+        };
+
+        Ok(json!({
+            "result": format!(
+                "Exported {} markers from timeline '{}' as {}",
+                markers.len(),
+                timeline_name,
+                format.to_ascii_uppercase()
+            ),
+            "timeline_name": timeline_name,
+            "format": format,
+            "frame_rate": rate.to_string(),
+            "marker_count": markers.len(),
+            "skipped_unplaced": timeline.markers.len() - markers.len(),
+            "content": content,
+            "operation_id": Uuid::new_v4().to_string()
+        }))
+    }
+}
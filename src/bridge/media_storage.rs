@@ -0,0 +1,324 @@
+//! MediaStorage operations backed by the local filesystem
+//!
+//! Only locations listed in `Config::media_storage` are visible, mirroring the
+//! Media Storage locations configured in Resolve's preferences.
+
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+use walkdir::WalkDir;
+
+use super::{ensure_capacity, Clip, ResolveBridge, StateView};
+use crate::error::{ResolveError, ResolveResult};
+
+/// File extensions Resolve can import into the media pool
+const MEDIA_EXTENSIONS: &[&str] = &[
+    "mov", "mp4", "m4v", "mxf", "avi", "mkv", "braw", "r3d", "ari", "arx", "crm", "dng", "cin",
+    "dpx", "exr", "tif", "tiff", "png", "jpg", "jpeg", "bmp", "wav", "aif", "aiff", "mp3", "m4a",
+    "flac", "aac",
+];
+
+pub(super) fn is_media_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| MEDIA_EXTENSIONS.contains(&ext.to_ascii_lowercase().as_str()))
+}
+
+fn is_hidden(name: &str) -> bool {
+    name.starts_with('.')
+}
+
+/// Name of the clip a media file is added as
+fn clip_name(file: &Path) -> String {
+    file.file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| "unknown_file".to_string())
+}
+
+impl ResolveBridge {
+    /// Media storage locations that exist on disk, canonicalized
+    fn storage_roots(&self) -> Vec<PathBuf> {
+        self.config
+            .media_storage
+            .allowed_paths
+            .iter()
+            .filter_map(|root| std::fs::canonicalize(root).ok())
+            .collect()
+    }
+
+    /// Resolve a path and check that it lies inside a media storage location
+    pub(super) fn resolve_storage_path(&self, path: &str) -> ResolveResult<PathBuf> {
+        let roots = self.storage_roots();
+        match std::fs::canonicalize(path) {
+            Ok(canonical) if roots.iter().any(|root| canonical.starts_with(root)) => Ok(canonical),
+            // Only report missing files inside allowed locations so nothing leaks about the rest
+            Err(_)
+                if self
+                    .config
+                    .media_storage
+                    .allowed_paths
+                    .iter()
+                    .any(|root| Path::new(path).starts_with(root)) =>
+            {
+                Err(ResolveError::FileNotFound {
+                    path: path.to_string(),
+                })
+            }
+            _ => Err(ResolveError::PermissionDenied {
+                operation: format!("access '{}' outside media storage locations", path),
+            }),
+        }
+    }
+
+    pub(super) async fn get_mounted_volumes(
+        &self,
+        _state: &StateView<'_>,
+        _args: Value,
+    ) -> ResolveResult<Value> {
+        let volumes: Vec<String> = self
+            .storage_roots()
+            .iter()
+            .map(|root| root.display().to_string())
+            .collect();
+
+        Ok(json!({
+            "result": format!("Found {} mounted volumes", volumes.len()),
+            "volumes": volumes,
+            "count": volumes.len(),
+            "operation_id": Uuid::new_v4().to_string()
+        }))
+    }
+
+    pub(super) async fn browse_folder(
+        &self,
+        _state: &StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        let path = args["path"]
+            .as_str()
+            .ok_or_else(|| ResolveError::invalid_parameter("path", "required string"))?;
+        let folder = self.resolve_storage_path(path)?;
+        if !folder.is_dir() {
+            return Err(ResolveError::invalid_parameter(
+                "path",
+                format!("'{}' is not a folder", path),
+            ));
+        }
+
+        let entries = std::fs::read_dir(&folder).map_err(|e| ResolveError::PermissionDenied {
+            operation: format!("read folder '{}': {}", folder.display(), e),
+        })?;
+
+        let mut subfolders = Vec::new();
+        let mut files = Vec::new();
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if is_hidden(&name) {
+                continue;
+            }
+            let entry_path = entry.path();
+            match entry.metadata() {
+                Ok(metadata) if metadata.is_dir() => subfolders.push((name, entry_path)),
+                Ok(metadata) => files.push((name, entry_path, metadata.len())),
+                Err(_) => continue,
+            }
+        }
+        subfolders.sort_by(|a, b| a.0.cmp(&b.0));
+        files.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let subfolders: Vec<Value> = subfolders
+            .into_iter()
+            .map(|(name, path)| json!({"name": name, "path": path.display().to_string()}))
+            .collect();
+        let files: Vec<Value> = files
+            .into_iter()
+            .map(|(name, path, size)| {
+                json!({
+                    "name": name,
+                    "is_media": is_media_file(&path),
+                    "path": path.display().to_string(),
+                    "size_bytes": size
+                })
+            })
+            .collect();
+
+        Ok(json!({
+            "result": format!(
+                "Found {} folders and {} files in '{}'",
+                subfolders.len(),
+                files.len(),
+                folder.display()
+            ),
+            "path": folder.display().to_string(),
+            "subfolders": subfolders,
+            "files": files,
+            "operation_id": Uuid::new_v4().to_string()
+        }))
+    }
+
+    pub(super) async fn add_clips_to_media_pool_from_paths(
+        &self,
+        state: &mut StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        let paths: Vec<&str> = args["paths"]
+            .as_array()
+            .ok_or_else(|| ResolveError::invalid_parameter("paths", "required array"))?
+            .iter()
+            .map(|path| {
+                path.as_str().ok_or_else(|| {
+                    ResolveError::invalid_parameter("paths", "paths must be strings")
+                })
+            })
+            .collect::<ResolveResult<_>>()?;
+        if paths.is_empty() {
+            return Err(ResolveError::invalid_parameter(
+                "paths",
+                "at least one path is required",
+            ));
+        }
+        if state.current_project.is_none() {
+            return Err(ResolveError::NotRunning);
+        }
+        let bin_name = match args["bin_name"].as_str() {
+            Some(bin) => Some(Self::resolve_bin(state, bin)?),
+            None => None,
+        };
+        let bin_name = bin_name.as_deref();
+
+        // Check every path before touching the media pool
+        let mut media_files = Vec::new();
+        let mut skipped = Vec::new();
+        for path in paths {
+            let resolved = self.resolve_storage_path(path)?;
+            if resolved.is_dir() {
+                for entry in WalkDir::new(&resolved)
+                    .sort_by_file_name()
+                    .into_iter()
+                    .filter_entry(|entry| {
+                        entry.depth() == 0 || !is_hidden(&entry.file_name().to_string_lossy())
+                    })
+                    .flatten()
+                {
+                    if !entry.file_type().is_file() {
+                        continue;
+                    }
+                    if is_media_file(entry.path()) {
+                        media_files.push(entry.into_path());
+                    } else {
+                        skipped.push(entry.path().display().to_string());
+                    }
+                }
+            } else if is_media_file(&resolved) {
+                media_files.push(resolved);
+            } else {
+                skipped.push(resolved.display().to_string());
+            }
+        }
+
+        // A clip name stands for one file, so a different file with the same name
+        // would replace the clip it names
+        let mut claimed: HashMap<String, String> = HashMap::new();
+        for file in &media_files {
+            let name = clip_name(file);
+            let file_path = file.display().to_string();
+            let taken = claimed.get(&name).cloned().or_else(|| {
+                state
+                    .media_pool
+                    .clips
+                    .get(&name)
+                    .map(|clip| clip.file_path.clone())
+            });
+            if let Some(taken) = taken.filter(|taken| *taken != file_path) {
+                return Err(ResolveError::invalid_parameter(
+                    "paths",
+                    format!(
+                        "{} would be named '{}', which already names {}",
+                        file_path, name, taken
+                    ),
+                ));
+            }
+            claimed.insert(name, file_path);
+        }
+        let new_clips = claimed
+            .keys()
+            .filter(|name| !state.media_pool.clips.contains_key(*name))
+            .count();
+        ensure_capacity(
+            "clips",
+            state.media_pool.clips.len(),
+            new_clips,
+            self.config.limits.max_clips,
+        )?;
+
+        let mut added = Vec::with_capacity(media_files.len());
+        for file in media_files {
+            let name = clip_name(&file);
+            let file_path = file.display().to_string();
+
+            let media_pool = &mut *state.media_pool;
+            let moved_from = match media_pool.clips.get_mut(&name) {
+                // Adding a clip again keeps what was logged on it
+                Some(clip) => {
+                    clip.linked = true;
+                    match bin_name {
+                        Some(bin) if clip.bin.as_deref() != Some(bin) => {
+                            clip.bin.replace(bin.to_string())
+                        }
+                        _ => None,
+                    }
+                }
+                None => {
+                    media_pool.clips.insert(
+                        name.clone(),
+                        Clip {
+                            id: Uuid::new_v4().to_string(),
+                            name: name.clone(),
+                            file_path: file_path.clone(),
+                            bin: bin_name.map(|bin| bin.to_string()),
+                            linked: true,
+                            proxy_path: None,
+                            start_timecode: None,
+                            reel_name: None,
+                            frame_rate: None,
+                            color: Default::default(),
+                            metadata: Default::default(),
+                            marks: Default::default(),
+                        },
+                    );
+                    None
+                }
+            };
+            if let Some(old) = moved_from.and_then(|old| media_pool.bins.get_mut(&old)) {
+                old.clips.retain(|clip| *clip != name);
+            }
+            if let Some(bin) = bin_name.and_then(|bin| media_pool.bins.get_mut(bin)) {
+                if !bin.clips.contains(&name) {
+                    bin.clips.push(name.clone());
+                }
+            }
+            #[cfg(feature = "camera-sidecars")]
+            let sidecar = self.apply_camera_sidecar(state, &name);
+            #[cfg(not(feature = "camera-sidecars"))]
+            let sidecar = Value::Null;
+            let color = Self::apply_bin_color_rules(state, &name).unwrap_or_default();
+            added.push(json!({
+                "clip_name": name,
+                "file_path": file_path,
+                "sidecar": sidecar,
+                "input_color_space": color.input_color_space,
+                "input_lut": color.input_lut
+            }));
+        }
+
+        Ok(json!({
+            "result": format!("Added {} clips to the media pool", added.len()),
+            "clips": added,
+            "added_count": added.len(),
+            "skipped": skipped,
+            "bin_name": bin_name,
+            "operation_id": Uuid::new_v4().to_string()
+        }))
+    }
+}
@@ -0,0 +1,267 @@
+//! Metadata schemas for clip metadata
+//!
+//! A schema from the `[metadata]` config says which fields every clip must
+//! have and what their values may be: a pattern to match as a whole, such as
+//! `\d+[A-Z]?` for Scene, or a list of allowed values. The active schema is
+//! enforced wherever metadata is written, by set_media_pool_item_metadata, by
+//! metadata batches of rename_batch and by metadata given to import_media; a
+//! write that breaks it changes nothing. Clips missing a required field can
+//! still be imported, since metadata usually follows the media, and are
+//! listed by validate_metadata, which checks every clip against a schema.
+//!
+//! Comments and Reel Name are read and written where the rest of the bridge
+//! keeps them; other writable fields are kept on the clip.
+
+use regex::Regex;
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use uuid::Uuid;
+
+use super::{notes, ResolveBridge, StateView};
+use crate::config::{MetadataFieldRule, MetadataSchema};
+use crate::error::{ResolveError, ResolveResult};
+
+/// Fields Resolve fills in itself, which metadata writes cannot change
+pub(super) const READ_ONLY_FIELDS: [&str; 7] = [
+    "File Name",
+    "Clip Name",
+    "Duration",
+    "Frame Rate",
+    "Resolution",
+    "Codec",
+    "Date Created",
+];
+
+/// Reel Name, kept with the clip's embedded reel
+const REEL_NAME: &str = "Reel Name";
+
+/// Why `value` breaks `rule`, if it does; an empty value is no value
+fn check(rule: &MetadataFieldRule, value: &str) -> Option<String> {
+    if value.is_empty() {
+        return rule.required.then(|| "required but empty".to_string());
+    }
+    if !rule.allowed_values.is_empty() && !rule.allowed_values.iter().any(|v| v == value) {
+        return Some(format!(
+            "'{}' is not one of {}",
+            value,
+            rule.allowed_values.join(", ")
+        ));
+    }
+    let pattern = rule.pattern.as_deref()?;
+    // Patterns are checked when the config loads
+    let matches = Regex::new(&format!("^(?:{})$", pattern))
+        .map(|regex| regex.is_match(value))
+        .unwrap_or(true);
+    (!matches).then(|| format!("'{}' does not match {}", value, pattern))
+}
+
+/// A clip's value for a metadata field, if it has one
+pub(super) fn clip_value(state: &StateView<'_>, clip_name: &str, field: &str) -> Option<String> {
+    let clip = state.media_pool.clips.get(clip_name)?;
+    match field {
+        notes::CLIP_NOTES_METADATA => state.notes.clip(clip_name).map(str::to_string),
+        REEL_NAME => clip.reel_name.clone(),
+        "Clip Name" => Some(clip.name.clone()),
+        _ => clip.metadata.get(field).cloned(),
+    }
+}
+
+/// Write a clip's metadata field; an empty value clears it
+pub(super) fn set_clip_value(
+    state: &mut StateView<'_>,
+    clip_name: &str,
+    field: &str,
+    value: String,
+) {
+    if field == notes::CLIP_NOTES_METADATA {
+        state.notes.set_clip(clip_name, value);
+        return;
+    }
+    let Some(clip) = state.media_pool.clips.get_mut(clip_name) else {
+        return;
+    };
+    match field {
+        REEL_NAME => clip.reel_name = (!value.is_empty()).then_some(value),
+        _ if value.is_empty() => {
+            clip.metadata.remove(field);
+        }
+        _ => {
+            clip.metadata.insert(field.to_string(), value);
+        }
+    }
+}
+
+/// Every problem with one clip's metadata under `schema`
+fn clip_problems(state: &StateView<'_>, schema: &MetadataSchema, clip_name: &str) -> Vec<Value> {
+    schema
+        .fields
+        .iter()
+        .filter_map(|(field, rule)| {
+            let value = clip_value(state, clip_name, field).unwrap_or_default();
+            let problem = check(rule, &value)?;
+            Some(json!({ "field": field, "value": value, "problem": problem }))
+        })
+        .collect()
+}
+
+impl ResolveBridge {
+    /// The schema enforced on writes, with its name
+    pub(super) fn active_metadata_schema(&self) -> Option<(&str, &MetadataSchema)> {
+        let name = self.config.metadata.schema.as_deref()?;
+        Some((name, self.config.metadata.schemas.get(name)?))
+    }
+
+    /// Refuse metadata writes the active schema does not allow
+    ///
+    /// `values` are the clip, field and value of each write; every problem is
+    /// listed in the error.
+    pub(super) fn enforce_metadata_schema<'v>(
+        &self,
+        parameter: &str,
+        values: impl IntoIterator<Item = (&'v str, &'v str, &'v str)>,
+    ) -> ResolveResult<()> {
+        let Some((name, schema)) = self.active_metadata_schema() else {
+            return Ok(());
+        };
+        let problems: Vec<String> = values
+            .into_iter()
+            .filter_map(|(clip_name, field, value)| {
+                let problem = check(schema.fields.get(field)?, value)?;
+                Some(format!("{} of '{}': {}", field, clip_name, problem))
+            })
+            .collect();
+        if problems.is_empty() {
+            return Ok(());
+        }
+        Err(ResolveError::invalid_parameter(
+            parameter,
+            format!(
+                "breaks metadata schema '{}', so nothing was changed: {}",
+                name,
+                problems.join("; ")
+            ),
+        ))
+    }
+
+    /// Metadata given to an import, checked like any other metadata write
+    pub(super) fn import_metadata(
+        &self,
+        metadata: &Value,
+        clip_name: &str,
+    ) -> ResolveResult<BTreeMap<String, String>> {
+        let metadata: BTreeMap<String, String> = match metadata {
+            Value::Null => BTreeMap::new(),
+            value => serde_json::from_value(value.clone()).map_err(|_| {
+                ResolveError::invalid_parameter("metadata", "must map field names to strings")
+            })?,
+        };
+        if let Some(field) = metadata
+            .keys()
+            .find(|field| READ_ONLY_FIELDS.contains(&field.as_str()))
+        {
+            return Err(ResolveError::invalid_parameter(
+                "metadata",
+                format!("'{}' is read-only", field),
+            ));
+        }
+        self.enforce_metadata_schema(
+            "metadata",
+            metadata
+                .iter()
+                .map(|(field, value)| (clip_name, field.as_str(), value.as_str())),
+        )?;
+        Ok(metadata)
+    }
+
+    /// Required fields of the active schema a clip has no value for
+    pub(super) fn missing_required_metadata(
+        &self,
+        state: &StateView<'_>,
+        clip_name: &str,
+    ) -> Vec<String> {
+        let Some((_, schema)) = self.active_metadata_schema() else {
+            return Vec::new();
+        };
+        schema
+            .fields
+            .iter()
+            .filter(|(field, rule)| {
+                rule.required
+                    && clip_value(state, clip_name, field).is_none_or(|value| value.is_empty())
+            })
+            .map(|(field, _)| field.clone())
+            .collect()
+    }
+
+    pub(super) async fn validate_metadata(
+        &self,
+        state: &mut StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        let state: &StateView<'_> = state;
+        let schemas = &self.config.metadata.schemas;
+        let name = match args["schema"].as_str() {
+            Some(name) => name,
+            None => self.config.metadata.schema.as_deref().ok_or_else(|| {
+                ResolveError::invalid_parameter(
+                    "schema",
+                    "no metadata schema is active; name one from the [metadata] config",
+                )
+            })?,
+        };
+        let schema = schemas.get(name).ok_or_else(|| {
+            ResolveError::invalid_parameter(
+                "schema",
+                format!(
+                    "unknown metadata schema '{}'; configured: {}",
+                    name,
+                    schemas.keys().cloned().collect::<Vec<_>>().join(", ")
+                ),
+            )
+        })?;
+        let bin_name = args["bin_name"].as_str();
+        if let Some(bin_name) = bin_name.filter(|bin| !state.media_pool.bins.contains_key(*bin)) {
+            return Err(ResolveError::invalid_parameter(
+                "bin_name",
+                format!("bin '{}' not found", bin_name),
+            ));
+        }
+
+        let clips: BTreeMap<&String, _> = state
+            .media_pool
+            .clips
+            .iter()
+            .filter(|(_, clip)| bin_name.is_none() || clip.bin.as_deref() == bin_name)
+            .collect();
+        let non_conforming: Vec<Value> = clips
+            .iter()
+            .filter_map(|(clip_name, clip)| {
+                let problems = clip_problems(state, schema, clip_name);
+                (!problems.is_empty()).then(|| {
+                    json!({
+                        "clip_name": clip_name,
+                        "clip_id": clip.id,
+                        "bin": clip.bin,
+                        "problems": problems
+                    })
+                })
+            })
+            .collect();
+
+        Ok(json!({
+            "result": format!(
+                "{} of {} clips do not conform to metadata schema '{}'",
+                non_conforming.len(),
+                clips.len(),
+                name
+            ),
+            "schema": name,
+            "description": schema.description,
+            "active": self.config.metadata.schema.as_deref() == Some(name),
+            "fields": schema.fields,
+            "checked_clips": clips.len(),
+            "non_conforming_clips": non_conforming,
+            "operation_id": Uuid::new_v4().to_string()
+        }))
+    }
+}
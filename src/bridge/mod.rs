@@ -1,14 +1,119 @@
+use serde::Serialize;
 use serde_json::{json, Value};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::future::Future;
+use std::panic::AssertUnwindSafe;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
+use std::task::Poll;
 use tokio::sync::Mutex;
 use uuid::Uuid;
 
-use crate::error::{ResolveError, ResolveResult};
+use crate::config::{redacted_arguments, Config};
+use crate::error::{ErrorKind, ResolveError, ResolveResult};
 use crate::native::NativeDaVinciResolve;
+use crate::timecode::FrameRate;
+use item_properties::{Category, PropertyValue};
+use locking::{Domain, LockPlan, SharedState, StateView};
+
+mod actions;
+mod ale;
+mod app_state;
+mod archive_manifest;
+mod audio_sync;
+mod auto_reframe;
+mod av_offset;
+mod backup;
+mod bars_tone;
+mod batch_render;
+mod bin_color_rules;
+mod bootstrap;
+#[cfg(feature = "camera-sidecars")]
+mod camera_sidecars;
+mod clip_grades;
+mod clip_marks;
+mod clip_usage;
+mod color_batch;
+mod color_qc;
+mod concurrency;
+mod conform;
+mod consolidate;
+mod delivery_package;
+mod delivery_qc;
+mod drx;
+mod embedded_timecode;
+mod entity_ids;
+mod events;
+mod ffmpeg;
+mod fixtures;
+mod frame_cache;
+mod frame_description;
+mod frame_rate_advisor;
+mod generators;
+mod grade_history;
+mod grade_report;
+mod grade_trace;
+mod grading_session;
+mod identity;
+mod item_lookup;
+mod item_properties;
+mod item_trim;
+mod jobs;
+mod languages;
+mod locking;
+mod lut_color_space;
+mod lut_library;
+mod marker_renders;
+mod markers;
+mod media_storage;
+mod metadata_schema;
+mod motion_effects;
+mod notes;
+mod organize_media;
+mod output_framing;
+mod pagination;
+mod paths;
+mod power_windows;
+mod preview;
+mod privacy_blur;
+mod qualifier;
+mod real_color;
+mod real_media_pool;
+mod real_render;
+mod registry;
+mod rename_batch;
+mod render_cache;
+mod render_formats;
+mod render_hooks;
+mod render_metadata;
+mod review;
+mod review_adapters;
+mod scopes;
+mod share;
+mod shot_list;
+mod slate;
+mod state_diff;
+mod state_integrity;
+mod state_sync;
+mod stereo;
+mod subtitles;
+#[cfg(feature = "bench")]
+mod synthetic;
+mod tags;
+mod timeline_export;
+mod timeline_summary;
+mod timeline_versions;
+mod tool_stats;
+mod transcripts;
+mod trash;
+mod vfx_plates;
+mod waveform;
+
+pub use frame_description::{Sampler, SamplingReply, SamplingRequest};
 
 /// Connection mode for DaVinci Resolve bridge
 #[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
 pub enum ConnectionMode {
     /// Simulation mode - uses in-memory state (for testing/development)
     Simulation,
@@ -18,14 +123,34 @@ pub enum ConnectionMode {
 
 /// Pure Rust implementation of DaVinci Resolve operations
 /// This can operate in simulation mode or attempt real connections
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ResolveBridge {
     /// Connection mode
     mode: ConnectionMode,
-    /// Simulated state for development and testing
-    state: Arc<Mutex<ResolveState>>,
+    /// Server configuration
+    config: Arc<Config>,
+    /// Simulated state for development and testing, locked per domain
+    state: Arc<SharedState>,
     /// Connection status
     connected: Arc<Mutex<bool>>,
+    /// Stored pages of large list responses
+    pages: Arc<Mutex<pagination::PageStore>>,
+    /// Capacity shared by CPU-heavy work
+    cpu: Arc<concurrency::CpuScheduler>,
+    /// Recorded real responses, written or played back
+    fixtures: Arc<fixtures::FixtureStore>,
+    /// Current user and the audit log
+    identity: Arc<Mutex<identity::IdentityStore>>,
+    /// Decoded frames shared by scopes and QC
+    frame_cache: Arc<frame_cache::FrameCache>,
+    /// Outcome of mirroring real Resolve state
+    state_sync: Arc<Mutex<state_sync::SyncStatus>>,
+    /// Share link server and the files it serves
+    share: Arc<Mutex<share::ShareState>>,
+    /// Open grading session and the artifacts of ended ones
+    grading: Arc<Mutex<grading_session::GradingSessions>>,
+    /// The connected client's model, when the client offered sampling
+    sampler: Arc<Mutex<Option<Arc<dyn Sampler>>>>,
     /// Native DaVinci Resolve integration (future feature)
     #[allow(dead_code)]
     native: Arc<Mutex<Option<NativeDaVinciResolve>>>,
@@ -39,6 +164,8 @@ pub struct ResolveState {
     projects: Vec<String>,
     /// Current page
     current_page: String,
+    /// Project settings set with set_project_setting, by setting name
+    project_settings: HashMap<String, Value>,
     /// Timelines in current project
     timelines: HashMap<String, Timeline>,
     /// Current timeline
@@ -55,6 +182,22 @@ pub struct ResolveState {
     keyframe_state: KeyframeState,
     /// Render and delivery state (Phase 4 Week 3)
     render_state: RenderState,
+    /// Gallery still albums
+    gallery: GalleryState,
+    /// Review and approval notes
+    review: review::ReviewState,
+    /// User-defined tags on clips and timeline items
+    tags: tags::TagState,
+    /// Free-text notes on projects, timelines and clips
+    notes: notes::NoteState,
+    /// Word-level transcripts of media pool clips
+    transcripts: transcripts::TranscriptState,
+    /// Project backups and restore points
+    backups: backup::BackupState,
+    /// Deleted entities that can still be restored
+    trash: trash::TrashState,
+    /// Background analysis jobs and their reports
+    jobs: jobs::JobState,
     /// Response cache for performance optimization
     #[allow(dead_code)]
     response_cache: HashMap<String, (chrono::DateTime<chrono::Utc>, Value)>,
@@ -72,33 +215,54 @@ impl Default for MediaPool {
         clips.insert(
             "default_clip".to_string(),
             Clip {
+                id: Uuid::new_v4().to_string(),
                 name: "default_clip".to_string(),
                 file_path: "/path/to/default_clip.mp4".to_string(),
                 bin: None,
                 linked: true,
                 proxy_path: None,
+                start_timecode: None,
+                reel_name: None,
+                frame_rate: None,
+                color: Default::default(),
+                metadata: Default::default(),
+                marks: Default::default(),
             },
         );
 
         clips.insert(
             "test_video.mp4".to_string(),
             Clip {
+                id: Uuid::new_v4().to_string(),
                 name: "test_video.mp4".to_string(),
                 file_path: "/path/to/test_video.mp4".to_string(),
                 bin: Some("Test Bin".to_string()),
                 linked: true,
                 proxy_path: None,
+                start_timecode: None,
+                reel_name: None,
+                frame_rate: None,
+                color: Default::default(),
+                metadata: Default::default(),
+                marks: Default::default(),
             },
         );
 
         clips.insert(
             "sample_audio.wav".to_string(),
             Clip {
+                id: Uuid::new_v4().to_string(),
                 name: "sample_audio.wav".to_string(),
                 file_path: "/path/to/sample_audio.wav".to_string(),
                 bin: Some("Audio Bin".to_string()),
                 linked: true,
                 proxy_path: None,
+                start_timecode: None,
+                reel_name: None,
+                frame_rate: None,
+                color: Default::default(),
+                metadata: Default::default(),
+                marks: Default::default(),
             },
         );
 
@@ -106,25 +270,33 @@ impl Default for MediaPool {
         bins.insert(
             "Test Bin".to_string(),
             Bin {
+                id: Uuid::new_v4().to_string(),
                 name: "Test Bin".to_string(),
                 clips: vec!["test_video.mp4".to_string()],
+                color_rules: Vec::new(),
             },
         );
 
         bins.insert(
             "Audio Bin".to_string(),
             Bin {
+                id: Uuid::new_v4().to_string(),
                 name: "Audio Bin".to_string(),
                 clips: vec!["sample_audio.wav".to_string()],
+                color_rules: Vec::new(),
             },
         );
 
-        Self { bins, clips }
+        Self {
+            bins,
+            clips,
+            synced_clips: HashMap::new(),
+        }
     }
 }
 
 /// Keyframe animation state management (Phase 4 Week 2)
-#[derive(Debug, Default)]
+#[derive(Debug, Clone, Default)]
 struct KeyframeState {
     /// Keyframes by timeline item ID
     timeline_item_keyframes: HashMap<String, TimelineItemKeyframes>,
@@ -132,7 +304,7 @@ struct KeyframeState {
     keyframe_counter: u64,
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize)]
 struct TimelineItemKeyframes {
     /// Timeline item ID
     #[allow(dead_code)]
@@ -143,7 +315,7 @@ struct TimelineItemKeyframes {
     keyframe_modes: KeyframeModes,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 struct Keyframe {
     /// Unique keyframe ID
     id: u64,
@@ -157,7 +329,7 @@ struct Keyframe {
     created_at: String,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 enum InterpolationType {
     Linear,
     Bezier,
@@ -166,7 +338,7 @@ enum InterpolationType {
     Hold,
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize)]
 struct KeyframeModes {
     /// All properties keyframe mode enabled
     all_enabled: bool,
@@ -176,45 +348,70 @@ struct KeyframeModes {
     sizing_enabled: bool,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 struct Timeline {
+    /// Stable timeline ID, kept when the timeline is recreated under the same name
+    id: String,
     #[allow(dead_code)]
     name: String,
     #[allow(dead_code)]
     frame_rate: Option<String>,
+    /// Timecode of the first frame, 01:00:00:00 when not given
+    start_timecode: Option<String>,
     #[allow(dead_code)]
     resolution_width: Option<i32>,
     #[allow(dead_code)]
     resolution_height: Option<i32>,
     markers: Vec<Marker>,
+    /// Playhead frame relative to the timeline start, set with set_timeline_timecode
+    playhead: Option<i64>,
+    subtitle_tracks: Vec<subtitles::SubtitleTrack>,
+    /// Timeline this one was versioned from, set by create_timeline_version
+    lineage: Option<timeline_versions::Lineage>,
+    /// Blanking and reframing applied to renders and previews of the timeline
+    output: output_framing::OutputFraming,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 struct Marker {
+    /// Stable marker ID
+    id: String,
     #[allow(dead_code)]
     frame: Option<i32>,
     #[allow(dead_code)]
     color: String,
     #[allow(dead_code)]
     note: String,
+    /// Marker name, shown above its note in Resolve
+    name: String,
+    /// Frames the marker spans, 1 for a marker on a single frame
+    duration: i64,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct MediaPool {
     bins: HashMap<String, Bin>,
     clips: HashMap<String, Clip>,
+    /// How each clip made by auto_sync_audio plays its picture and sound
+    synced_clips: HashMap<String, audio_sync::SyncedClip>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 struct Bin {
+    /// Stable bin ID
+    id: String,
     #[allow(dead_code)]
     name: String,
     #[allow(dead_code)]
     clips: Vec<String>,
+    /// Color settings given to clips imported into the bin
+    color_rules: Vec<bin_color_rules::BinColorRule>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 struct Clip {
+    /// Media pool item ID, kept when the clip is re-imported
+    id: String,
     #[allow(dead_code)]
     name: String,
     #[allow(dead_code)]
@@ -225,25 +422,76 @@ struct Clip {
     linked: bool,
     #[allow(dead_code)]
     proxy_path: Option<String>,
+    /// Start timecode embedded in the media, once read
+    start_timecode: Option<String>,
+    /// Reel or tape name embedded in the media, once read
+    reel_name: Option<String>,
+    /// Frame rate of the media, set with the FPS clip property; None reads as
+    /// the project frame rate
+    frame_rate: Option<String>,
+    /// Input color space and LUT, set by hand or by the rules of its bin
+    color: bin_color_rules::ClipColorSettings,
+    /// Other metadata fields by name, such as Scene or Take
+    metadata: BTreeMap<String, String>,
+    /// In and out points marked on the clip's media for logging
+    marks: clip_marks::ClipMarks,
 }
 
 /// Color grading state management (Phase 3 Week 3)
-#[derive(Debug, Default)]
+#[derive(Debug, Clone, Default)]
 struct ColorState {
     /// Current clip being graded
     current_clip: Option<String>,
     /// LUTs available in the system
     available_luts: HashMap<String, LutInfo>,
-    /// Color presets
-    color_presets: HashMap<String, ColorPreset>,
+    /// Color preset albums by name, each holding its presets
+    preset_albums: HashMap<String, ColorPresetAlbum>,
     /// Clip grades (per clip)
     clip_grades: HashMap<String, ClipGrade>,
     /// Current node index for grading
     current_node_index: i32,
+    /// Project color groups by name, each listing its member clips
+    color_groups: BTreeMap<String, Vec<String>>,
+    /// Last index of the configured LUT folders
+    lut_scan: Option<lut_library::LutScan>,
+    /// Versions of clip grades, kept while grading history is on
+    grade_history: grade_history::GradeHistory,
+}
+
+impl ColorState {
+    /// Find a preset in an album by ID or name
+    fn find_preset(
+        &self,
+        album_name: &str,
+        preset_id: Option<&str>,
+        preset_name: Option<&str>,
+    ) -> ResolveResult<&ColorPreset> {
+        if preset_id.is_none() && preset_name.is_none() {
+            return Err(ResolveError::invalid_parameter(
+                "preset_id or preset_name",
+                "one is required",
+            ));
+        }
+        let album = self.preset_albums.get(album_name).ok_or_else(|| {
+            ResolveError::invalid_parameter(
+                "album_name",
+                format!("album '{}' not found", album_name),
+            )
+        })?;
+        album
+            .find_preset_key(preset_id, preset_name)
+            .and_then(|key| album.presets.get(&key))
+            .ok_or_else(|| {
+                ResolveError::invalid_parameter(
+                    "preset",
+                    format!("preset not found in album '{}'", album_name),
+                )
+            })
+    }
 }
 
 /// Timeline item state management (Phase 4 Week 1)
-#[derive(Debug, Default)]
+#[derive(Debug, Clone, Default)]
 struct TimelineItemsState {
     /// Timeline items by ID
     items: HashMap<String, TimelineItemState>,
@@ -251,7 +499,7 @@ struct TimelineItemsState {
     item_counter: u64,
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize)]
 struct TimelineItemState {
     /// Unique timeline item ID
     #[allow(dead_code)]
@@ -260,90 +508,202 @@ struct TimelineItemState {
     timeline_name: String,
     /// Clip name this item references
     clip_name: String,
-    /// Transform properties
-    transform: TransformProperties,
-    /// Crop settings
-    crop: CropProperties,
-    /// Composite settings
-    composite: CompositeProperties,
-    /// Retiming settings
-    retime: RetimeProperties,
-    /// Stabilization settings
-    stabilization: StabilizationProperties,
-    /// Audio properties
-    audio: AudioProperties,
-}
-
-#[derive(Debug, Clone, Default)]
-struct TransformProperties {
-    pan: f64,
-    tilt: f64,
-    zoom_x: f64,
-    zoom_y: f64,
-    rotation: f64,
-    anchor_point_x: f64,
-    anchor_point_y: f64,
-    pitch: f64,
-    yaw: f64,
-}
-
-#[derive(Debug, Clone, Default)]
-struct CropProperties {
-    left: f64,
-    right: f64,
-    top: f64,
-    bottom: f64,
+    /// Media pool clip ID this item uses; None for items only known by ID
+    clip_id: Option<String>,
+    /// Transform, crop, composite, retime, stabilization and audio values
+    properties: item_properties::ItemProperties,
+    /// Where the item sits on its timeline; None for items only known by ID
+    placement: Option<ItemPlacement>,
+    /// CDL values set with set_cdl
+    cdl: Option<grade_report::Cdl>,
+    /// Title or generator settings for items without media, such as slates
+    generator: Option<Value>,
+    /// Color and Fusion output render cache flags
+    render_cache: render_cache::ItemRenderCache,
+    /// Grade last applied from a .drx file
+    drx_grade: Option<drx::DrxGrade>,
+    /// Stereo 3D parameters; None until set
+    stereo: Option<stereo::StereoParams>,
 }
 
-#[derive(Debug, Clone, Default)]
-struct CompositeProperties {
-    mode: String, // "Normal", "Add", "Multiply", etc.
-    opacity: f64, // 0.0 to 1.0
+/// Position of a timeline item and the source range it uses, in frames
+#[derive(Debug, Clone, Serialize)]
+struct ItemPlacement {
+    /// Video track index, starting at 1
+    track_index: u32,
+    /// First timeline frame, relative to the timeline start
+    record_in: i64,
+    /// First source frame used from the clip
+    source_in: i64,
+    duration: i64,
 }
 
-#[derive(Debug, Clone, Default)]
-struct RetimeProperties {
-    speed: f64,      // Speed factor
-    process: String, // "NearestFrame", "FrameBlend", "OpticalFlow"
-}
+impl ItemPlacement {
+    fn record_out(&self) -> i64 {
+        self.record_in + self.duration
+    }
 
-#[derive(Debug, Clone, Default)]
-struct StabilizationProperties {
-    enabled: bool,
-    method: String, // "Perspective", "Similarity", "Translation"
-    strength: f64,  // 0.0 to 1.0
+    fn source_out(&self) -> i64 {
+        self.source_in + self.duration
+    }
 }
 
-#[derive(Debug, Clone, Default)]
-struct AudioProperties {
-    volume: f64, // Volume level (usually 0.0 to 2.0, where 1.0 is unity gain)
-    pan: f64,    // -1.0 to 1.0
-    eq_enabled: bool,
-}
+/// Length given to simulated media, matching the duration import_media reports
+const SIMULATED_CLIP_SECONDS: i64 = 90;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 struct LutInfo {
-    #[allow(dead_code)]
     name: String,
-    #[allow(dead_code)]
     path: String,
-    #[allow(dead_code)]
     format: String, // "Cube", "Davinci", "3dl", "Panasonic"
-    #[allow(dead_code)]
-    size: String, // "17Point", "33Point", "65Point"
+    size: String,   // "17Point", "33Point", "65Point"
+    /// "1D" or "3D"
+    dimension: &'static str,
+    file_size: Option<u64>,
+    modified: Option<String>,
+    /// Configured LUT folder the file was indexed from; None for built-in LUTs
+    directory: Option<String>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
+struct ColorPresetAlbum {
+    name: String,
+    created_at: String,
+    /// Presets in this album by name
+    presets: HashMap<String, ColorPreset>,
+}
+
+impl ColorPresetAlbum {
+    fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            created_at: chrono::Utc::now().to_rfc3339(),
+            presets: HashMap::new(),
+        }
+    }
+
+    /// Resolve a preset key in this album from either its ID or its name
+    fn find_preset_key(
+        &self,
+        preset_id: Option<&str>,
+        preset_name: Option<&str>,
+    ) -> Option<String> {
+        if let Some(id) = preset_id {
+            self.presets
+                .values()
+                .find(|preset| preset.id == id)
+                .map(|preset| preset.name.clone())
+        } else {
+            preset_name
+                .filter(|name| self.presets.contains_key(*name))
+                .map(|name| name.to_string())
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
 struct ColorPreset {
+    /// Unique preset ID
+    id: String,
     name: String,
-    #[allow(dead_code)]
     album: String,
-    #[allow(dead_code)]
     created_at: String,
     grade_data: ClipGrade,
 }
 
+/// Album that always exists and receives presets when no album is specified
+const DEFAULT_PRESET_ALBUM: &str = "DaVinci Resolve";
+
+/// Gallery still albums and the stills they hold
 #[derive(Debug, Clone, Default)]
+struct GalleryState {
+    /// Still albums in gallery order
+    albums: Vec<GalleryStillAlbum>,
+    /// Name of the current still album
+    current_album: Option<String>,
+    /// Counter used to generate still IDs
+    still_counter: u64,
+}
+
+impl GalleryState {
+    fn album(&self, name: &str) -> Option<&GalleryStillAlbum> {
+        self.albums.iter().find(|album| album.name == name)
+    }
+
+    fn album_mut(&mut self, name: &str) -> Option<&mut GalleryStillAlbum> {
+        self.albums.iter_mut().find(|album| album.name == name)
+    }
+
+    /// Resolve an album name argument, falling back to the current album
+    fn resolve_album_name(&self, param: &str, name: Option<&str>) -> ResolveResult<String> {
+        let name = match name {
+            Some(name) => name.to_string(),
+            None => self
+                .current_album
+                .clone()
+                .ok_or_else(|| ResolveError::invalid_parameter(param, "no current still album"))?,
+        };
+        if self.album(&name).is_none() {
+            return Err(ResolveError::invalid_parameter(
+                param,
+                format!("still album '{}' not found", name),
+            ));
+        }
+        Ok(name)
+    }
+
+    fn next_still_id(&mut self) -> String {
+        self.still_counter += 1;
+        format!("still_{:04}", self.still_counter)
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct GalleryStillAlbum {
+    id: String,
+    name: String,
+    stills: Vec<GalleryStill>,
+}
+
+impl GalleryStillAlbum {
+    fn new(name: &str) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            name: name.to_string(),
+            stills: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct GalleryStill {
+    /// Unique still ID
+    id: String,
+    label: String,
+    /// Timeline the still was grabbed from
+    timeline_name: Option<String>,
+    /// Clip the still was grabbed from
+    source_clip: Option<String>,
+    grabbed_at: String,
+}
+
+impl GalleryStill {
+    fn to_json(&self, album: &str) -> Value {
+        json!({
+            "still_id": self.id,
+            "label": self.label,
+            "album": album,
+            "timeline_name": self.timeline_name,
+            "source_clip": self.source_clip,
+            "grabbed_at": self.grabbed_at
+        })
+    }
+}
+
+/// Still albums every gallery starts with; the first one is current
+const DEFAULT_STILL_ALBUMS: [&str; 4] = ["Stills", "PowerGrade", "LUTs", "Custom"];
+
+#[derive(Debug, Clone, Default, Serialize)]
 struct ClipGrade {
     /// Color wheel parameters
     lift: ColorWheelParams,
@@ -356,9 +716,17 @@ struct ClipGrade {
     node_count: i32,
     /// Node labels
     node_labels: HashMap<i32, String>,
+    /// Noise reduction and motion blur, by node index
+    motion_effects: BTreeMap<i32, motion_effects::NodeMotionEffects>,
+    /// Privacy blur windows, by node index
+    privacy_masks: BTreeMap<i32, privacy_blur::PrivacyMask>,
+    /// Power Windows, by node index
+    power_windows: BTreeMap<i32, Vec<power_windows::PowerWindow>>,
+    /// HSL qualifiers, by node index
+    qualifiers: BTreeMap<i32, qualifier::NodeQualifier>,
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize)]
 struct ColorWheelParams {
     red: f64,
     green: f64,
@@ -367,7 +735,7 @@ struct ColorWheelParams {
 }
 
 /// Render and delivery state management (Phase 4 Week 3)
-#[derive(Debug, Default)]
+#[derive(Debug, Clone, Default)]
 struct RenderState {
     /// Active render queue
     render_queue: Vec<RenderJob>,
@@ -379,9 +747,11 @@ struct RenderState {
     render_history: Vec<RenderResult>,
     /// Global render job counter
     job_counter: u64,
+    /// Current render format and codec; None is Resolve's default QuickTime H.264
+    format_and_codec: Option<(String, String)>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 struct RenderJob {
     /// Unique job ID
     id: String,
@@ -393,6 +763,18 @@ struct RenderJob {
     output_path: String,
     /// Use in/out range
     use_in_out_range: bool,
+    /// Record frames to render, relative to the timeline start; None renders the whole range
+    frame_range: Option<(i64, i64)>,
+    /// Whether the video track is rendered
+    export_video: bool,
+    /// Audio channel layout; None renders no audio
+    audio_channel_layout: Option<String>,
+    /// How subtitles are delivered; None renders without them
+    subtitles: Option<subtitles::SubtitleExport>,
+    /// Timeline output framing when the job was queued
+    framing: output_framing::OutputFraming,
+    /// Chapters and metadata to embed in the output; None embeds nothing
+    embedding: Option<render_metadata::Embedding>,
     /// Job creation timestamp
     #[allow(dead_code)]
     created_at: chrono::DateTime<chrono::Utc>,
@@ -400,15 +782,12 @@ struct RenderJob {
     status: RenderJobStatus,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 enum RenderJobStatus {
     Queued,
     Rendering,
-    #[allow(dead_code)]
     Completed,
-    #[allow(dead_code)]
     Failed,
-    #[allow(dead_code)]
     Cancelled,
 }
 
@@ -431,7 +810,7 @@ struct RenderProgress {
     last_update: chrono::DateTime<chrono::Utc>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 struct RenderPreset {
     /// Preset name
     #[allow(dead_code)]
@@ -457,12 +836,26 @@ struct RenderPreset {
     /// Audio bitrate (kbps)
     #[allow(dead_code)]
     audio_bitrate: u32,
+    /// Video bit depth
+    #[allow(dead_code)]
+    bit_depth: u8,
+    /// Whether the alpha channel is rendered
+    #[allow(dead_code)]
+    export_alpha: bool,
+    /// Whether the video track is rendered
+    export_video: bool,
+    /// Audio channel layout; None renders no audio
+    audio_channel_layout: Option<String>,
+    /// Whether timeline markers are embedded as chapters
+    embed_chapters: bool,
+    /// Whether the project's title, author and copyright are embedded
+    embed_metadata: bool,
     /// Preset creation timestamp
     #[allow(dead_code)]
     created_at: chrono::DateTime<chrono::Utc>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 enum RenderQuality {
     #[allow(dead_code)]
     Low,
@@ -473,37 +866,113 @@ enum RenderQuality {
     Custom(u32), // Custom bitrate in kbps
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 struct RenderResult {
     /// Job ID
-    #[allow(dead_code)]
     job_id: String,
     /// Timeline name
-    #[allow(dead_code)]
     timeline_name: String,
     /// Preset used
-    #[allow(dead_code)]
     preset_name: String,
     /// Output path
-    #[allow(dead_code)]
     output_path: String,
     /// Render duration
-    #[allow(dead_code)]
     render_duration: std::time::Duration,
     /// Final status
-    #[allow(dead_code)]
     status: RenderJobStatus,
     /// Completion timestamp
-    #[allow(dead_code)]
     completed_at: chrono::DateTime<chrono::Utc>,
     /// Error message (if failed)
-    #[allow(dead_code)]
     error_message: Option<String>,
+    /// Outcome of each post-render hook, filled in once they have run
+    hooks: Vec<Value>,
+    /// Outcome of embedding chapters and metadata, filled in once it has run
+    embedding: Option<Value>,
+}
+
+/// Fail with `ResourceLimitExceeded` if adding `additional` items to `current` would pass `limit`
+fn ensure_capacity(
+    resource: &str,
+    current: usize,
+    additional: usize,
+    limit: usize,
+) -> ResolveResult<()> {
+    if current + additional > limit {
+        return Err(ResolveError::resource_limit_exceeded(resource, limit));
+    }
+    Ok(())
 }
 
+/// Run a future, turning a panic while it is polled into the panic message.
+/// The future is boxed: dispatch futures are large enough that moving one
+/// into another frame can overflow a small stack.
+async fn catch_panic<F: Future>(future: F) -> Result<F::Output, String> {
+    let mut future = Box::pin(future);
+    std::future::poll_fn(|cx| {
+        match std::panic::catch_unwind(AssertUnwindSafe(|| future.as_mut().poll(cx))) {
+            Ok(Poll::Ready(output)) => Poll::Ready(Ok(output)),
+            Ok(Poll::Pending) => Poll::Pending,
+            Err(payload) => Poll::Ready(Err(payload
+                .downcast_ref::<&str>()
+                .map(|message| message.to_string())
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| "unknown panic".to_string()))),
+        }
+    })
+    .await
+}
+
+/// Imports the scripting module and defines `fail`, which prints an error the
+/// way `run_resolve_script` reads it
+const SCRIPT_PRELUDE: &str = r#"
+import os
+import sys
+import json
+sys.path.append("/opt/resolve/Developer/Scripting/Modules")
+
+def fail(message):
+    print(json.dumps({"error": message}))
+    sys.exit(1)
+"#;
+
+/// Connects to Resolve, opening the `try` the rest of the script runs in
+const SCRIPT_CONNECT: &str = r#"
+try:
+    import DaVinciResolveScript as dvr_script
+    resolve = dvr_script.scriptapp("Resolve")
+    if not resolve:
+        fail("Cannot connect to DaVinci Resolve")
+"#;
+
+/// Reports any exception the script raised
+const SCRIPT_EPILOGUE: &str = r#"
+except Exception as e:
+    fail(str(e))
+"#;
+
+/// Script setup finding the current project as `project`
+pub(super) const SCRIPT_PROJECT: &str = r#"
+    project = resolve.GetProjectManager().GetCurrentProject()
+    if not project:
+        fail("No project open")
+"#;
+
+/// Script setup finding the current project's current timeline as `timeline`
+pub(super) const SCRIPT_TIMELINE: &str = r#"
+    project = resolve.GetProjectManager().GetCurrentProject()
+    timeline = project.GetCurrentTimeline() if project else None
+    if not timeline:
+        fail("No timeline selected")
+"#;
+
 impl ResolveBridge {
     /// Create a new bridge instance
     pub fn new(mode: ConnectionMode) -> Self {
+        Self::with_config(mode, Arc::new(Config::default()))
+    }
+
+    /// Create a new bridge instance that honours the given configuration
+    pub fn with_config(mode: ConnectionMode, config: Arc<Config>) -> Self {
         let mut state = ResolveState::default();
         state.current_page = "media".to_string();
 
@@ -522,6 +991,10 @@ impl ResolveBridge {
                 path: "/usr/share/davinci/luts/rec709_to_srgb.cube".to_string(),
                 format: "Cube".to_string(),
                 size: "33Point".to_string(),
+                dimension: "3D",
+                file_size: None,
+                modified: None,
+                directory: None,
             },
         );
         state.color_state.available_luts.insert(
@@ -531,13 +1004,40 @@ impl ResolveBridge {
                 path: "/usr/share/davinci/luts/cinematic.cube".to_string(),
                 format: "Cube".to_string(),
                 size: "33Point".to_string(),
+                dimension: "3D",
+                file_size: None,
+                modified: None,
+                directory: None,
             },
         );
+        state.color_state.preset_albums.insert(
+            DEFAULT_PRESET_ALBUM.to_string(),
+            ColorPresetAlbum::new(DEFAULT_PRESET_ALBUM),
+        );
+        state.gallery.albums = DEFAULT_STILL_ALBUMS
+            .iter()
+            .map(|name| GalleryStillAlbum::new(name))
+            .collect();
+        state.gallery.current_album = Some(DEFAULT_STILL_ALBUMS[0].to_string());
+        state.color_state.grade_history = grade_history::GradeHistory::new(&config.grade_history);
 
+        let cpu = concurrency::CpuScheduler::new(&config.concurrency);
+        let fixtures = fixtures::FixtureStore::new(&config.fixtures);
+        let frame_cache = frame_cache::FrameCache::new(&config.cache);
         Self {
             mode,
-            state: Arc::new(Mutex::new(state)),
+            config,
+            state: Arc::new(SharedState::from(state)),
             connected: Arc::new(Mutex::new(false)),
+            pages: Arc::new(Mutex::new(pagination::PageStore::default())),
+            cpu: Arc::new(cpu),
+            fixtures: Arc::new(fixtures),
+            identity: Arc::new(Mutex::new(identity::IdentityStore::default())),
+            frame_cache: Arc::new(frame_cache),
+            state_sync: Arc::new(Mutex::new(state_sync::SyncStatus::default())),
+            share: Arc::new(Mutex::new(share::ShareState::default())),
+            grading: Arc::new(Mutex::new(grading_session::GradingSessions::default())),
+            sampler: Arc::new(Mutex::new(None)),
             native: Arc::new(Mutex::new(None)),
         }
     }
@@ -548,6 +1048,7 @@ impl ResolveBridge {
             ConnectionMode::Simulation => {
                 tracing::info!("Initialized DaVinci Resolve bridge in SIMULATION mode");
                 *self.connected.lock().await = true;
+                self.bootstrap_at_startup().await;
                 Ok(())
             }
             ConnectionMode::Real => {
@@ -558,6 +1059,8 @@ impl ResolveBridge {
                     Ok(()) => {
                         tracing::info!("✅ Python API connection established successfully");
                         *self.connected.lock().await = true;
+                        self.start_state_sync().await;
+                        self.bootstrap_at_startup().await;
                         Ok(())
                     }
                     Err(e) => {
@@ -581,25 +1084,87 @@ impl ResolveBridge {
     }
 
     /// Call a DaVinci Resolve API method
+    ///
+    /// A handler that panics fails the call with an internal error instead of
+    /// taking the server down. Locks it held are released; changes it made to
+    /// the state before panicking are kept.
     pub async fn call_api(&self, method: &str, args: Value) -> ResolveResult<Value> {
+        catch_panic(self.dispatch(method, args))
+            .await
+            .unwrap_or_else(|message| {
+                tracing::error!("{} panicked: {}", method, message);
+                Err(ResolveError::internal(format!(
+                    "{} panicked: {}",
+                    method, message
+                )))
+            })
+    }
+
+    async fn dispatch(&self, method: &str, mut args: Value) -> ResolveResult<Value> {
         tracing::debug!(
             "API call: {} with args: {} (mode: {:?})",
             method,
-            args,
+            redacted_arguments(&args),
             self.mode
         );
 
+        // Stats must not wait behind the work they report on
+        if method == "get_bridge_stats" {
+            return self.get_bridge_stats(args).await;
+        }
+        // Each step of an action or a bootstrap takes its own locks
+        if method == "run_action" {
+            return self.run_action(args).await;
+        }
+        if method == "bootstrap_session" {
+            return self.bootstrap_session(args).await;
+        }
+        // Reading Resolve for a snapshot must not hold the locks reads wait on
+        if method == "sync_real_state" {
+            return self.sync_real_state(args).await;
+        }
+        self.apply_user_defaults(method, &mut args).await;
+        paths::check_arguments(&self.config.sandbox, method, &args)?;
+        // Sampling waits on the client, and the metadata it writes takes its own locks
+        if method == "describe_frame" {
+            return self.describe_frame(args).await;
+        }
+        if let Some(result) = self.fixtures.playback(method, &args) {
+            return Ok(result);
+        }
+        // A LUT for another color space is checked before either mode applies it
+        let lut_check = match method {
+            "apply_lut" => self.check_lut_color_space(&args).await?,
+            _ => None,
+        };
+
+        // Heavy work waits for CPU capacity before it takes any locks
+        let _permit = match concurrency::Workload::for_method(method) {
+            Some(workload) => Some(self.cpu.acquire(workload, method.to_string()).await),
+            None => None,
+        };
+
         // Check if we should use real DaVinci Resolve API
         match self.mode {
             ConnectionMode::Real => {
                 // Try to use real DaVinci Resolve API first
                 match self.call_real_api(method, &args).await {
                     Ok(result) => {
+                        let result = lut_color_space::attach(result, lut_check);
                         tracing::info!("Real API call successful for {}", method);
+                        self.fixtures.record(method, &args, &result);
+                        self.record_real_grading(method, &args, &result).await;
+                        self.announce(method, &result);
+                        self.audit(method, &result).await;
                         return Ok(result);
                     }
+                    // Resolve ran the call and refused it; simulating it would hide that
+                    Err(e) if e.kind() == ErrorKind::ApiCall => {
+                        tracing::warn!("Real API call failed for {}: {}", method, e);
+                        return Err(e);
+                    }
                     Err(e) => {
-                        // Fall back to simulation if real API fails
+                        // Fall back to simulation for calls Resolve cannot take
                         tracing::warn!(
                             "Real API call failed for {} ({}), falling back to simulation",
                             method,
@@ -615,309 +1180,572 @@ impl ResolveBridge {
         }
 
         // Simulation mode logic
-        let mut state = self.state.lock().await;
-        state.operation_count += 1;
+        let plan = Self::lock_plan(method);
+        let mut state = self.state.lock(plan).await;
+        self.state.operation_count.fetch_add(1, Ordering::Relaxed);
+        let before = state_diff::Snapshot::before(&state, plan);
+        let grading = Box::pin(self.grading_before(&state, method, plan, &args)).await;
+        let history = grade_history::before(&state, plan, &args);
+
+        // The handlers are dispatched from their own boxed future, which keeps
+        // this one small enough to poll on a debug build's stack
+        let result = Box::pin(self.dispatch_simulated(method, &mut state, args)).await;
+        let result = result.map(|value| lut_color_space::attach(value, lut_check));
+        if let Ok(value) = &result {
+            if let Some(history) = history {
+                self.record_grade_history(&mut state, history, method);
+            }
+            if let Some(before) = before {
+                before.record(&state, plan, method);
+            }
+            if let Some(grading) = grading {
+                Box::pin(self.record_grading(grading, &state, plan, method, value)).await;
+            }
+            self.announce(method, value);
+            self.audit(method, value).await;
+        }
+        result
+    }
 
+    /// Run a call against the simulated state
+    async fn dispatch_simulated(
+        &self,
+        method: &str,
+        state: &mut StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
         match method {
             // Project operations
-            "create_project" => self.create_project(&mut state, args).await,
-            "open_project" => self.open_project(&mut state, args).await,
-            "switch_page" => self.switch_page(&mut state, args).await,
+            "create_project" => self.create_project(state, args).await,
+            "open_project" => self.open_project(state, args).await,
+            "switch_page" => self.switch_page(state, args).await,
 
             // Timeline operations
-            "create_timeline" => self.create_timeline(&mut state, args).await,
-            "add_marker" => self.add_marker(&mut state, args).await,
+            "create_timeline" => self.create_timeline(state, args).await,
+            "add_marker" => self.add_marker(state, args).await,
 
             // Media operations
-            "import_media" => self.import_media(&mut state, args).await,
-            "create_bin" => self.create_bin(&mut state, args).await,
-            "auto_sync_audio" => self.auto_sync_audio(&mut state, args).await,
-            "unlink_clips" => self.unlink_clips(&mut state, args).await,
-            "relink_clips" => self.relink_clips(&mut state, args).await,
-            "create_sub_clip" => self.create_sub_clip(&mut state, args).await,
-            "link_proxy_media" => self.link_proxy_media(&mut state, args).await,
-            "unlink_proxy_media" => self.unlink_proxy_media(&mut state, args).await,
-            "replace_clip" => self.replace_clip(&mut state, args).await,
+            "import_media" => self.import_media(state, args).await,
+            "create_bin" => self.create_bin(state, args).await,
+            "auto_sync_audio" => self.auto_sync_audio(state, args).await,
+            "unlink_clips" => self.unlink_clips(state, args).await,
+            "relink_clips" => self.relink_clips(state, args).await,
+            "create_sub_clip" => self.create_sub_clip(state, args).await,
+            "link_proxy_media" => self.link_proxy_media(state, args).await,
+            "unlink_proxy_media" => self.unlink_proxy_media(state, args).await,
+            "replace_clip" => self.replace_clip(state, args).await,
 
             // Timeline Enhancement operations (Phase 3 Week 2)
-            "delete_timeline" => self.delete_timeline(&mut state, args).await,
-            "set_current_timeline" => self.set_current_timeline(&mut state, args).await,
-            "create_empty_timeline" => self.create_empty_timeline(&mut state, args).await,
-            "add_clip_to_timeline" => self.add_clip_to_timeline(&mut state, args).await,
-            "list_timelines_tool" => self.list_timelines_tool(&mut state, args).await,
-            "get_timeline_tracks" => self.get_timeline_tracks(&mut state, args).await,
+            "delete_timeline" => self.delete_timeline(state, args).await,
+            "set_current_timeline" => self.set_current_timeline(state, args).await,
+            "create_empty_timeline" => self.create_empty_timeline(state, args).await,
+            "add_clip_to_timeline" => self.add_clip_to_timeline(state, args).await,
+            "list_timelines_tool" => self.list_timelines_tool(state, args).await,
+            "get_timeline_tracks" => self.get_timeline_tracks(state, args).await,
 
             // Color Operations (Phase 3 Week 3)
-            "apply_lut" => self.apply_lut(&mut state, args).await,
-            "set_color_wheel_param" => self.set_color_wheel_param(&mut state, args).await,
-            "add_node" => self.add_node(&mut state, args).await,
-            "copy_grade" => self.copy_grade(&mut state, args).await,
-            "save_color_preset" => self.save_color_preset(&mut state, args).await,
-            "apply_color_preset" => self.apply_color_preset(&mut state, args).await,
-            "delete_color_preset" => self.delete_color_preset(&mut state, args).await,
-            "export_lut" => self.export_lut(&mut state, args).await,
+            "apply_lut" => self.apply_lut(state, args).await,
+            "set_color_wheel_param" => self.set_color_wheel_param(state, args).await,
+            "add_node" => self.add_node(state, args).await,
+            "copy_grade" => self.copy_grade(state, args).await,
+            "save_color_preset" => self.save_color_preset(state, args).await,
+            "apply_color_preset" => self.apply_color_preset(state, args).await,
+            "delete_color_preset" => self.delete_color_preset(state, args).await,
+            "list_color_presets" => self.list_color_presets(state, args).await,
+            "export_lut" => self.export_lut(state, args).await,
 
             // Timeline Item Operations (Phase 4 Week 1)
-            "set_timeline_item_transform" => {
-                self.set_timeline_item_transform(&mut state, args).await
-            }
-            "set_timeline_item_crop" => self.set_timeline_item_crop(&mut state, args).await,
-            "set_timeline_item_composite" => {
-                self.set_timeline_item_composite(&mut state, args).await
-            }
-            "set_timeline_item_retime" => self.set_timeline_item_retime(&mut state, args).await,
+            "set_timeline_item_transform" => self.set_timeline_item_transform(state, args).await,
+            "set_timeline_item_crop" => self.set_timeline_item_crop(state, args).await,
+            "set_timeline_item_composite" => self.set_timeline_item_composite(state, args).await,
+            "set_timeline_item_retime" => self.set_timeline_item_retime(state, args).await,
+            "set_item_output_sizing" => self.set_item_output_sizing(state, args).await,
             "set_timeline_item_stabilization" => {
-                self.set_timeline_item_stabilization(&mut state, args).await
-            }
-            "set_timeline_item_audio" => self.set_timeline_item_audio(&mut state, args).await,
-            "get_timeline_item_properties" => {
-                self.get_timeline_item_properties(&mut state, args).await
+                self.set_timeline_item_stabilization(state, args).await
             }
+            "set_timeline_item_audio" => self.set_timeline_item_audio(state, args).await,
+            "get_timeline_item_properties" => self.get_timeline_item_properties(state, args).await,
             "reset_timeline_item_properties" => {
-                self.reset_timeline_item_properties(&mut state, args).await
+                self.reset_timeline_item_properties(state, args).await
             }
 
             // Keyframe Animation Operations (Phase 4 Week 2)
-            "add_keyframe" => self.add_keyframe(&mut state, args).await,
-            "modify_keyframe" => self.modify_keyframe(&mut state, args).await,
-            "delete_keyframe" => self.delete_keyframe(&mut state, args).await,
-            "set_keyframe_interpolation" => self.set_keyframe_interpolation(&mut state, args).await,
-            "enable_keyframes" => self.enable_keyframes(&mut state, args).await,
-            "get_keyframes" => self.get_keyframes(&mut state, args).await,
+            "add_keyframe" => self.add_keyframe(state, args).await,
+            "modify_keyframe" => self.modify_keyframe(state, args).await,
+            "delete_keyframe" => self.delete_keyframe(state, args).await,
+            "set_keyframe_interpolation" => self.set_keyframe_interpolation(state, args).await,
+            "enable_keyframes" => self.enable_keyframes(state, args).await,
+            "get_keyframes" => self.get_keyframes(state, args).await,
 
             // Render & Delivery Operations (Phase 4 Week 3)
-            "add_to_render_queue" => self.add_to_render_queue(&mut state, args).await,
-            "start_render" => self.start_render(&mut state, args).await,
-            "clear_render_queue" => self.clear_render_queue(&mut state, args).await,
-            "get_render_status" => self.get_render_status(&mut state, args).await,
-            "export_project" => self.export_project(&mut state, args).await,
-            "create_render_preset" => self.create_render_preset(&mut state, args).await,
+            "add_to_render_queue" => self.add_to_render_queue(state, args).await,
+            "start_render" => self.start_render(state, args).await,
+            "clear_render_queue" => self.clear_render_queue(state, args).await,
+            "get_render_status" => self.get_render_status(state, args).await,
+            "export_project" => self.export_project(state, args).await,
+            "create_render_preset" => self.create_render_preset(state, args).await,
 
             // Project Management Operations
-            "save_project" => self.save_project(&mut state, args).await,
-            "close_project" => self.close_project(&mut state, args).await,
-            "set_project_setting" => self.set_project_setting(&mut state, args).await,
+            "save_project" => self.save_project(state, args).await,
+            "close_project" => self.close_project(state, args).await,
+            "set_project_setting" => self.set_project_setting(state, args).await,
 
             // Audio Transcription Operations
-            "transcribe_audio" => self.transcribe_audio(&mut state, args).await,
-            "clear_transcription" => self.clear_transcription(&mut state, args).await,
+            "transcribe_audio" => self.transcribe_audio(state, args).await,
+            "clear_transcription" => self.clear_transcription(state, args).await,
 
             // Extended Project Management Operations
-            "delete_media" => self.delete_media(&mut state, args).await,
-            "move_media_to_bin" => self.move_media_to_bin(&mut state, args).await,
-            "export_folder" => self.export_folder(&mut state, args).await,
-            "transcribe_folder_audio" => self.transcribe_folder_audio(&mut state, args).await,
-            "clear_folder_transcription" => self.clear_folder_transcription(&mut state, args).await,
+            "delete_media" => self.delete_media(state, args).await,
+            "move_media_to_bin" => self.move_media_to_bin(state, args).await,
+            "export_folder" => self.export_folder(state, args).await,
+            "transcribe_folder_audio" => self.transcribe_folder_audio(state, args).await,
+            "clear_folder_transcription" => self.clear_folder_transcription(state, args).await,
 
             // Cache and Optimization Operations
-            "set_cache_mode" => self.set_cache_mode(&mut state, args).await,
-            "set_optimized_media_mode" => self.set_optimized_media_mode(&mut state, args).await,
-            "set_proxy_mode" => self.set_proxy_mode(&mut state, args).await,
-            "set_proxy_quality" => self.set_proxy_quality(&mut state, args).await,
-            "set_cache_path" => self.set_cache_path(&mut state, args).await,
-            "generate_optimized_media" => self.generate_optimized_media(&mut state, args).await,
-            "delete_optimized_media" => self.delete_optimized_media(&mut state, args).await,
+            "set_cache_mode" => self.set_cache_mode(state, args).await,
+            "set_optimized_media_mode" => self.set_optimized_media_mode(state, args).await,
+            "set_proxy_mode" => self.set_proxy_mode(state, args).await,
+            "set_proxy_quality" => self.set_proxy_quality(state, args).await,
+            "set_cache_path" => self.set_cache_path(state, args).await,
+            "generate_optimized_media" => self.generate_optimized_media(state, args).await,
+            "delete_optimized_media" => self.delete_optimized_media(state, args).await,
 
             // Extended Color Operations
-            "create_color_preset_album" => self.create_color_preset_album(&mut state, args).await,
-            "delete_color_preset_album" => self.delete_color_preset_album(&mut state, args).await,
-            "export_all_power_grade_luts" => {
-                self.export_all_power_grade_luts(&mut state, args).await
-            }
+            "create_color_preset_album" => self.create_color_preset_album(state, args).await,
+            "delete_color_preset_album" => self.delete_color_preset_album(state, args).await,
+            "export_all_power_grade_luts" => self.export_all_power_grade_luts(state, args).await,
 
             // Layout and Interface Management
-            "save_layout_preset" => self.save_layout_preset(&mut state, args).await,
-            "load_layout_preset" => self.load_layout_preset(&mut state, args).await,
-            "export_layout_preset" => self.export_layout_preset(&mut state, args).await,
-            "import_layout_preset" => self.import_layout_preset(&mut state, args).await,
-            "delete_layout_preset" => self.delete_layout_preset(&mut state, args).await,
+            "save_layout_preset" => self.save_layout_preset(state, args).await,
+            "load_layout_preset" => self.load_layout_preset(state, args).await,
+            "export_layout_preset" => self.export_layout_preset(state, args).await,
+            "import_layout_preset" => self.import_layout_preset(state, args).await,
+            "delete_layout_preset" => self.delete_layout_preset(state, args).await,
 
             // Application Control
-            "quit_app" => self.quit_app(&mut state, args).await,
-            "restart_app" => self.restart_app(&mut state, args).await,
-            "open_settings" => self.open_settings(&mut state, args).await,
-            "open_app_preferences" => self.open_app_preferences(&mut state, args).await,
+            "quit_app" => self.quit_app(state, args).await,
+            "restart_app" => self.restart_app(state, args).await,
+            "open_settings" => self.open_settings(state, args).await,
+            "open_app_preferences" => self.open_app_preferences(state, args).await,
 
             // Cloud Operations
-            "create_cloud_project" => self.create_cloud_project(&mut state, args).await,
-            "import_cloud_project" => self.import_cloud_project(&mut state, args).await,
-            "restore_cloud_project" => self.restore_cloud_project(&mut state, args).await,
-            "export_project_to_cloud" => self.export_project_to_cloud(&mut state, args).await,
-            "add_user_to_cloud_project" => self.add_user_to_cloud_project(&mut state, args).await,
+            "create_cloud_project" => self.create_cloud_project(state, args).await,
+            "import_cloud_project" => self.import_cloud_project(state, args).await,
+            "restore_cloud_project" => self.restore_cloud_project(state, args).await,
+            "export_project_to_cloud" => self.export_project_to_cloud(state, args).await,
+            "add_user_to_cloud_project" => self.add_user_to_cloud_project(state, args).await,
             "remove_user_from_cloud_project" => {
-                self.remove_user_from_cloud_project(&mut state, args).await
+                self.remove_user_from_cloud_project(state, args).await
             }
 
             // Object Inspection
-            "object_help" => self.object_help(&mut state, args).await,
-            "inspect_custom_object" => self.inspect_custom_object(&mut state, args).await,
+            "object_help" => self.object_help(state, args).await,
+            "inspect_custom_object" => self.inspect_custom_object(state, args).await,
 
             // Project Properties
-            "set_project_property" => self.set_project_property(&mut state, args).await,
-            "set_timeline_format" => self.set_timeline_format(&mut state, args).await,
+            "set_project_property" => self.set_project_property(state, args).await,
+            "set_timeline_format" => self.set_timeline_format(state, args).await,
 
             // ---- NEW: Timeline Object API ----
-            "get_timeline_name" => self.get_timeline_name(&mut state, args).await,
-            "set_timeline_name" => self.set_timeline_name(&mut state, args).await,
-            "get_timeline_frames" => self.get_timeline_frames(&mut state, args).await,
-            "set_timeline_timecode" => self.set_timeline_timecode(&mut state, args).await,
-            "get_timeline_track_count" => self.get_timeline_track_count(&mut state, args).await,
-            "get_timeline_items_in_track" => {
-                self.get_timeline_items_in_track(&mut state, args).await
-            }
-            "add_timeline_marker" => self.add_timeline_marker(&mut state, args).await,
-            "get_timeline_markers" => self.get_timeline_markers(&mut state, args).await,
-            "delete_timeline_marker" => self.delete_timeline_marker(&mut state, args).await,
-            "duplicate_timeline" => self.duplicate_timeline(&mut state, args).await,
-            "create_compound_clip" => self.create_compound_clip(&mut state, args).await,
-            "create_fusion_clip" => self.create_fusion_clip(&mut state, args).await,
-            "export_timeline" => self.export_timeline(&mut state, args).await,
-            "insert_generator" => self.insert_generator(&mut state, args).await,
-            "insert_title" => self.insert_title(&mut state, args).await,
-            "grab_still" => self.grab_still(&mut state, args).await,
+            "get_timeline_name" => self.get_timeline_name(state, args).await,
+            "set_timeline_name" => self.set_timeline_name(state, args).await,
+            "get_timeline_frames" => self.get_timeline_frames(state, args).await,
+            "set_timeline_timecode" => self.set_timeline_timecode(state, args).await,
+            "get_timeline_track_count" => self.get_timeline_track_count(state, args).await,
+            "get_timeline_items_in_track" => self.get_timeline_items_in_track(state, args).await,
+            "add_timeline_marker" => self.add_timeline_marker(state, args).await,
+            "get_timeline_markers" => self.get_timeline_markers(state, args).await,
+            "delete_timeline_marker" => self.delete_timeline_marker(state, args).await,
+            "duplicate_timeline" => self.duplicate_timeline(state, args).await,
+            "create_compound_clip" => self.create_compound_clip(state, args).await,
+            "create_fusion_clip" => self.create_fusion_clip(state, args).await,
+            "export_timeline" => self.export_timeline(state, args).await,
+            "insert_generator" => self.insert_generator(state, args).await,
+            "insert_title" => self.insert_title(state, args).await,
+            "grab_still" => self.grab_still(state, args).await,
 
             // ---- NEW: TimelineItem Object API ----
-            "get_timeline_item_property" => self.get_timeline_item_property(&mut state, args).await,
-            "set_timeline_item_property" => self.set_timeline_item_property(&mut state, args).await,
-            "get_timeline_item_details" => self.get_timeline_item_details(&mut state, args).await,
-            "add_timeline_item_marker" => self.add_timeline_item_marker(&mut state, args).await,
-            "get_timeline_item_markers" => self.get_timeline_item_markers(&mut state, args).await,
-            "delete_timeline_item_marker" => {
-                self.delete_timeline_item_marker(&mut state, args).await
-            }
-            "timeline_item_flag" => self.timeline_item_flag(&mut state, args).await,
-            "timeline_item_color" => self.timeline_item_color(&mut state, args).await,
-            "fusion_comp" => self.fusion_comp(&mut state, args).await,
-            "version" => self.version(&mut state, args).await,
-            "stereo_params" => self.stereo_params(&mut state, args).await,
-            "node_lut" => self.node_lut(&mut state, args).await,
-            "set_cdl" => self.set_cdl(&mut state, args).await,
-            "take" => self.take(&mut state, args).await,
-            "copy_grades" => self.copy_grades(&mut state, args).await,
+            "get_timeline_item_property" => self.get_timeline_item_property(state, args).await,
+            "set_timeline_item_property" => self.set_timeline_item_property(state, args).await,
+            "get_timeline_item_details" => self.get_timeline_item_details(state, args).await,
+            "add_timeline_item_marker" => self.add_timeline_item_marker(state, args).await,
+            "get_timeline_item_markers" => self.get_timeline_item_markers(state, args).await,
+            "delete_timeline_item_marker" => self.delete_timeline_item_marker(state, args).await,
+            "timeline_item_flag" => self.timeline_item_flag(state, args).await,
+            "timeline_item_color" => self.timeline_item_color(state, args).await,
+            "fusion_comp" => self.fusion_comp(state, args).await,
+            "version" => self.version(state, args).await,
+            "stereo_params" => self.stereo_params(state, args).await,
+            "node_lut" => self.node_lut(state, args).await,
+            "set_cdl" => self.set_cdl(state, args).await,
+            "take" => self.take(state, args).await,
+            "copy_grades" => self.copy_grades(state, args).await,
 
             // ---- NEW: MediaPoolItem Object API ----
-            "get_media_pool_item_list" => self.get_media_pool_item_list(&mut state, args).await,
-            "get_media_pool_item_name" => self.get_media_pool_item_name(&mut state, args).await,
-            "set_media_pool_item_name" => self.set_media_pool_item_name(&mut state, args).await,
-            "get_media_pool_item_property" => {
-                self.get_media_pool_item_property(&mut state, args).await
-            }
-            "set_media_pool_item_property" => {
-                self.set_media_pool_item_property(&mut state, args).await
-            }
-            "get_media_pool_item_metadata" => {
-                self.get_media_pool_item_metadata(&mut state, args).await
-            }
-            "set_media_pool_item_metadata" => {
-                self.set_media_pool_item_metadata(&mut state, args).await
-            }
-            "add_media_pool_item_marker" => self.add_media_pool_item_marker(&mut state, args).await,
-            "get_media_pool_item_markers" => {
-                self.get_media_pool_item_markers(&mut state, args).await
-            }
-            "add_media_pool_item_flag" => self.add_media_pool_item_flag(&mut state, args).await,
+            "get_media_pool_item_list" => self.get_media_pool_item_list(state, args).await,
+            "get_media_pool_item_name" => self.get_media_pool_item_name(state, args).await,
+            "set_media_pool_item_name" => self.set_media_pool_item_name(state, args).await,
+            "get_media_pool_item_property" => self.get_media_pool_item_property(state, args).await,
+            "set_media_pool_item_property" => self.set_media_pool_item_property(state, args).await,
+            "get_media_pool_item_metadata" => self.get_media_pool_item_metadata(state, args).await,
+            "set_media_pool_item_metadata" => self.set_media_pool_item_metadata(state, args).await,
+            "add_media_pool_item_marker" => self.add_media_pool_item_marker(state, args).await,
+            "get_media_pool_item_markers" => self.get_media_pool_item_markers(state, args).await,
+            "add_media_pool_item_flag" => self.add_media_pool_item_flag(state, args).await,
             "get_media_pool_item_flag_list" => {
-                self.get_media_pool_item_flag_list(&mut state, args).await
+                self.get_media_pool_item_flag_list(state, args).await
             }
             "get_media_pool_item_clip_color" => {
-                self.get_media_pool_item_clip_color(&mut state, args).await
+                self.get_media_pool_item_clip_color(state, args).await
             }
             "set_media_pool_item_clip_color" => {
-                self.set_media_pool_item_clip_color(&mut state, args).await
+                self.set_media_pool_item_clip_color(state, args).await
             }
             "link_media_pool_item_proxy_media" => {
-                self.link_media_pool_item_proxy_media(&mut state, args)
-                    .await
+                self.link_media_pool_item_proxy_media(state, args).await
             }
             "unlink_media_pool_item_proxy_media" => {
-                self.unlink_media_pool_item_proxy_media(&mut state, args)
-                    .await
+                self.unlink_media_pool_item_proxy_media(state, args).await
             }
             "transcribe_media_pool_item_audio" => {
-                self.transcribe_media_pool_item_audio(&mut state, args)
-                    .await
+                self.transcribe_media_pool_item_audio(state, args).await
             }
             "clear_media_pool_item_transcription" => {
-                self.clear_media_pool_item_transcription(&mut state, args)
-                    .await
+                self.clear_media_pool_item_transcription(state, args).await
             }
 
-            // ---- NEW: Missing API Methods ----
-            "get_fusion_tool_list" => self.get_fusion_tool_list(&mut state, args).await,
-            "get_audio_track_count" => self.get_audio_track_count(&mut state, args).await,
-            "get_project_timeline_count" => self.get_project_timeline_count(&mut state, args).await,
-            "get_gallery_still_albums" => self.get_gallery_still_albums(&mut state, args).await,
-            "get_media_pool_root_folder" => self.get_media_pool_root_folder(&mut state, args).await,
-            "add_fusion_tool" => self.add_fusion_tool(&mut state, args).await,
-            "get_audio_track_name" => self.get_audio_track_name(&mut state, args).await,
-            "set_audio_track_name" => self.set_audio_track_name(&mut state, args).await,
-            "add_gallery_still_album" => self.add_gallery_still_album(&mut state, args).await,
-            "add_media_pool_sub_folder" => self.add_media_pool_sub_folder(&mut state, args).await,
-            "append_to_timeline" => self.append_to_timeline(&mut state, args).await,
-            "get_project_timeline_by_index" => {
-                self.get_project_timeline_by_index(&mut state, args).await
-            }
-            "get_project_current_timeline" => {
-                self.get_project_current_timeline(&mut state, args).await
+            // ---- NEW: Resource Limits ----
+            "get_resource_usage" => self.get_resource_usage(state, args).await,
+
+            // ---- NEW: Marker Import/Export ----
+            "import_markers" => self.import_markers(state, args).await,
+            "export_markers" => self.export_markers(state, args).await,
+
+            // ---- NEW: Identity ----
+            "identify" => self.identify(state, args).await,
+            "get_current_user" => self.get_current_user(state, args).await,
+            "get_audit_log" => self.get_audit_log(state, args).await,
+
+            // ---- NEW: Render Formats ----
+            "list_render_formats" => self.list_render_formats(state, args).await,
+
+            // ---- NEW: Clip Grades ----
+            "adjust_color_wheel_param" => self.adjust_color_wheel_param(state, args).await,
+            "get_color_wheel_param" => self.get_color_wheel_param(state, args).await,
+            "get_clip_grade" => self.get_clip_grade(state, args).await,
+
+            // ---- NEW: Notes ----
+            "set_notes" => self.set_notes(state, args).await,
+            "get_notes" => self.get_notes(state, args).await,
+
+            // ---- NEW: Timeline Summary ----
+            "get_timeline_summary" => self.get_timeline_summary(state, args).await,
+
+            // ---- NEW: Bars and Tone ----
+            "insert_bars_and_tone" => self.insert_bars_and_tone(state, args).await,
+
+            // ---- NEW: Slates ----
+            "add_slate" => self.add_slate(state, args).await,
+
+            // ---- NEW: Job Graph ----
+            "submit_job" => self.submit_job(state, args).await,
+            "get_job_graph" => self.get_job_graph(state, args).await,
+
+            // ---- NEW: Grade Reports ----
+            "generate_grade_report" => self.generate_grade_report(state, args).await,
+
+            // ---- NEW: Batch Color Presets ----
+            "apply_color_preset_batch" => self.apply_color_preset_batch(state, args).await,
+
+            // ---- NEW: Timeline Item Lookup ----
+            "find_timeline_item" => self.find_timeline_item(state, args).await,
+
+            // ---- NEW: Timeline Item Properties ----
+            "list_timeline_item_properties" => {
+                self.list_timeline_item_properties(state, args).await
             }
-            "set_project_current_timeline" => {
-                self.set_project_current_timeline(&mut state, args).await
+
+            // ---- NEW: Paged Results ----
+            "get_result_page" => self.get_result_page(state, args).await,
+
+            // ---- NEW: Clip Usage ----
+            "find_clip_usage" => self.find_clip_usage(state, args).await,
+            "list_unused_clips" => self.list_unused_clips(state, args).await,
+
+            // ---- NEW: Embedded Timecode ----
+            "read_embedded_timecode" => self.read_embedded_timecode(state, args).await,
+
+            // ---- NEW: Delivery QC ----
+            "run_delivery_qc" => self.run_delivery_qc(state, args).await,
+            "get_background_job" => self.get_background_job(state, args).await,
+            "list_background_jobs" => self.list_background_jobs(state, args).await,
+
+            // ---- NEW: Color QC ----
+            "run_color_qc" => self.run_color_qc(state, args).await,
+
+            // ---- NEW: Frame Scopes ----
+            "get_frame_scopes" => self.get_frame_scopes(state, args).await,
+
+            // ---- NEW: Audio Waveforms ----
+            "get_audio_waveform" => self.get_audio_waveform(state, args).await,
+
+            // ---- NEW: Transcript Search ----
+            "search_transcript" => self.search_transcript(state, args).await,
+
+            // ---- NEW: Tags ----
+            "tag_clip" => self.tag_clip(state, args).await,
+            "untag_clip" => self.untag_clip(state, args).await,
+            "find_by_tag" => self.find_by_tag(state, args).await,
+
+            // ---- NEW: VFX Plates ----
+            "export_vfx_plates" => self.export_vfx_plates(state, args).await,
+
+            // ---- NEW: Media Consolidation ----
+            "consolidate_media" => self.consolidate_media(state, args).await,
+
+            // ---- NEW: Shot List ----
+            "generate_shot_list" => self.generate_shot_list(state, args).await,
+
+            // ---- NEW: Review Notes ----
+            "add_review_note" => self.add_review_note(state, args).await,
+            "list_review_notes" => self.list_review_notes(state, args).await,
+            "resolve_note" => self.resolve_note(state, args).await,
+
+            // ---- NEW: Backups ----
+            "create_backup" => self.create_backup(state, args).await,
+            "restore_backup" => self.restore_backup(state, args).await,
+            "list_backups" => self.list_backups(state, args).await,
+
+            // ---- NEW: MediaStorage ----
+            "get_mounted_volumes" => self.get_mounted_volumes(state, args).await,
+            "browse_folder" => self.browse_folder(state, args).await,
+            "add_clips_to_media_pool_from_paths" => {
+                self.add_clips_to_media_pool_from_paths(state, args).await
             }
-            "get_project_name" => self.get_project_name(&mut state, args).await,
-            "set_project_name" => self.set_project_name(&mut state, args).await,
-            "get_project_unique_id" => self.get_project_unique_id(&mut state, args).await,
-            "get_project_render_job_list" => {
-                self.get_project_render_job_list(&mut state, args).await
+
+            // ---- NEW: Missing API Methods ----
+            "get_fusion_tool_list" => self.get_fusion_tool_list(state, args).await,
+            "get_audio_track_count" => self.get_audio_track_count(state, args).await,
+            "get_project_timeline_count" => self.get_project_timeline_count(state, args).await,
+            "get_gallery_still_albums" => self.get_gallery_still_albums(state, args).await,
+            "get_media_pool_root_folder" => self.get_media_pool_root_folder(state, args).await,
+            "add_fusion_tool" => self.add_fusion_tool(state, args).await,
+            "get_audio_track_name" => self.get_audio_track_name(state, args).await,
+            "set_audio_track_name" => self.set_audio_track_name(state, args).await,
+            "add_gallery_still_album" => self.add_gallery_still_album(state, args).await,
+            "rename_gallery_still_album" => self.rename_gallery_still_album(state, args).await,
+            "set_current_still_album" => self.set_current_still_album(state, args).await,
+            "get_current_still_album" => self.get_current_still_album(state, args).await,
+            "get_album_stills" => self.get_album_stills(state, args).await,
+            "move_gallery_stills" => self.move_gallery_stills(state, args).await,
+            "copy_gallery_stills" => self.copy_gallery_stills(state, args).await,
+            "add_media_pool_sub_folder" => self.add_media_pool_sub_folder(state, args).await,
+            "append_to_timeline" => self.append_to_timeline(state, args).await,
+            "get_project_timeline_by_index" => {
+                self.get_project_timeline_by_index(state, args).await
             }
-            "start_project_rendering" => self.start_project_rendering(&mut state, args).await,
-            "stop_project_rendering" => self.stop_project_rendering(&mut state, args).await,
+            "get_project_current_timeline" => self.get_project_current_timeline(state, args).await,
+            "set_project_current_timeline" => self.set_project_current_timeline(state, args).await,
+            "get_project_name" => self.get_project_name(state, args).await,
+            "set_project_name" => self.set_project_name(state, args).await,
+            "get_project_unique_id" => self.get_project_unique_id(state, args).await,
+            "get_project_render_job_list" => self.get_project_render_job_list(state, args).await,
+            "start_project_rendering" => self.start_project_rendering(state, args).await,
+            "stop_project_rendering" => self.stop_project_rendering(state, args).await,
             "is_project_rendering_in_progress" => {
-                self.is_project_rendering_in_progress(&mut state, args)
-                    .await
+                self.is_project_rendering_in_progress(state, args).await
             }
-            "get_project_preset_list" => self.get_project_preset_list(&mut state, args).await,
-            "load_project_render_preset" => self.load_project_render_preset(&mut state, args).await,
+            "get_project_preset_list" => self.get_project_preset_list(state, args).await,
+            "load_project_render_preset" => self.load_project_render_preset(state, args).await,
             "save_as_new_project_render_preset" => {
-                self.save_as_new_project_render_preset(&mut state, args)
-                    .await
+                self.save_as_new_project_render_preset(state, args).await
             }
             "get_current_project_render_format_and_codec" => {
-                self.get_current_project_render_format_and_codec(&mut state, args)
+                self.get_current_project_render_format_and_codec(state, args)
                     .await
             }
             "set_current_project_render_format_and_codec" => {
-                self.set_current_project_render_format_and_codec(&mut state, args)
+                self.set_current_project_render_format_and_codec(state, args)
                     .await
             }
             "get_current_project_render_mode" => {
-                self.get_current_project_render_mode(&mut state, args).await
+                self.get_current_project_render_mode(state, args).await
             }
             "set_current_project_render_mode" => {
-                self.set_current_project_render_mode(&mut state, args).await
+                self.set_current_project_render_mode(state, args).await
             }
             "get_project_color_groups_list" => {
-                self.get_project_color_groups_list(&mut state, args).await
+                self.get_project_color_groups_list(state, args).await
             }
-            "add_project_color_group" => self.add_project_color_group(&mut state, args).await,
-            "delete_project_color_group" => self.delete_project_color_group(&mut state, args).await,
+            "add_project_color_group" => self.add_project_color_group(state, args).await,
+            "delete_project_color_group" => self.delete_project_color_group(state, args).await,
+
+            _ => match self.dispatch_registered(method, state, args).await {
+                Some(result) => result,
+                None => Err(ResolveError::not_supported(format!(
+                    "API method: {}",
+                    method
+                ))),
+            },
+        }
+    }
 
-            _ => Err(ResolveError::not_supported(format!(
-                "API method: {}",
-                method
-            ))),
+    /// Send the webhook event a successful call stands for, if any
+    fn announce(&self, method: &str, result: &Value) {
+        if let Some(event) = events::event_for_method(method) {
+            events::publish(&self.config, event, result.clone());
+        }
+    }
+
+    /// Domains a simulated call may change; anything not listed is a query
+    /// and only takes shared locks
+    fn lock_plan(method: &str) -> LockPlan {
+        if let Some(plan) = Self::registered_lock_plan(method) {
+            return plan;
+        }
+        match method {
+            "switch_page" | "create_backup" | "set_project_name" | "set_project_setting" => {
+                LockPlan::write(&[Domain::Project])
+            }
+            "create_bin"
+            | "relink_clips"
+            | "transcribe_audio"
+            | "clear_transcription"
+            | "move_media_to_bin"
+            | "transcribe_folder_audio"
+            | "clear_folder_transcription"
+            | "set_media_pool_item_name"
+            | "set_media_pool_item_property"
+            | "transcribe_media_pool_item_audio"
+            | "clear_media_pool_item_transcription"
+            | "read_embedded_timecode"
+            | "auto_sync_audio"
+            | "add_clips_to_media_pool_from_paths"
+            | "add_media_pool_sub_folder" => LockPlan::write(&[Domain::MediaPool]),
+            // Calls that declare their reads leave every other domain unlocked
+            "add_marker" => LockPlan::write(&[Domain::Timelines]).reading(&[]),
+            "list_review_notes" => LockPlan::READ.reading(&[Domain::Review]),
+            "get_render_status"
+            | "get_project_render_job_list"
+            | "is_project_rendering_in_progress" => LockPlan::READ.reading(&[Domain::Render]),
+            "create_timeline"
+            | "set_current_timeline"
+            | "add_clip_to_timeline"
+            | "append_to_timeline"
+            | "set_timeline_item_transform"
+            | "set_timeline_item_crop"
+            | "set_timeline_item_composite"
+            | "set_timeline_item_retime"
+            | "set_item_output_sizing"
+            | "set_timeline_item_stabilization"
+            | "set_timeline_item_audio"
+            | "set_timeline_item_property"
+            | "set_cdl"
+            | "stereo_params"
+            | "reset_timeline_item_properties"
+            | "add_keyframe"
+            | "modify_keyframe"
+            | "delete_keyframe"
+            | "set_keyframe_interpolation"
+            | "enable_keyframes"
+            | "import_markers"
+            | "run_color_qc"
+            | "search_transcript"
+            | "set_project_current_timeline"
+            | "set_timeline_timecode"
+            | "add_slate"
+            | "insert_bars_and_tone" => LockPlan::write(&[Domain::Timelines]),
+            "apply_lut"
+            | "set_color_wheel_param"
+            | "adjust_color_wheel_param"
+            | "add_node"
+            | "copy_grade"
+            | "save_color_preset"
+            | "apply_color_preset"
+            | "create_color_preset_album"
+            | "delete_color_preset_album"
+            | "grab_still"
+            | "add_gallery_still_album"
+            | "rename_gallery_still_album"
+            | "set_current_still_album"
+            | "move_gallery_stills"
+            | "copy_gallery_stills"
+            | "add_project_color_group"
+            | "delete_project_color_group"
+            | "apply_color_preset_batch" => LockPlan::write(&[Domain::Color]),
+            "add_to_render_queue"
+            | "start_render"
+            | "clear_render_queue"
+            | "create_render_preset"
+            | "export_vfx_plates"
+            | "start_project_rendering"
+            | "stop_project_rendering"
+            | "save_as_new_project_render_preset"
+            | "set_current_project_render_format_and_codec" => LockPlan::write(&[Domain::Render]),
+            "tag_clip" | "untag_clip" | "set_notes" | "add_review_note" | "resolve_note" => {
+                LockPlan::write(&[Domain::Review])
+            }
+            "run_delivery_qc" | "submit_job" => LockPlan::write(&[Domain::Jobs]),
+            "consolidate_media" => LockPlan::write(&[Domain::MediaPool, Domain::Timelines]),
+            // Deleted entities are moved into the trash
+            "delete_media" => LockPlan::write(&[Domain::Project, Domain::MediaPool]),
+            "set_media_pool_item_metadata" | "import_media" => {
+                LockPlan::write(&[Domain::MediaPool, Domain::Review])
+            }
+            "delete_timeline" => LockPlan::write(&[Domain::Project, Domain::Timelines]),
+            "delete_color_preset" => LockPlan::write(&[Domain::Project, Domain::Color]),
+            "open_project" | "create_empty_timeline" => {
+                LockPlan::write(&[Domain::Project, Domain::Timelines])
+            }
+            "create_project" => {
+                LockPlan::write(&[Domain::Project, Domain::MediaPool, Domain::Timelines])
+            }
+            "close_project" => LockPlan::write(&[
+                Domain::Project,
+                Domain::MediaPool,
+                Domain::Timelines,
+                Domain::Color,
+                Domain::Render,
+            ]),
+            "restore_backup" => LockPlan::ALL,
+            _ => LockPlan::READ,
         }
     }
 
     /// Call real DaVinci Resolve API using Python integration
     async fn call_real_api(&self, method: &str, args: &Value) -> ResolveResult<Value> {
-        use std::process::Command;
-
         tracing::debug!(
             "Calling real DaVinci Resolve API: {} with args: {}",
             method,
-            args
+            redacted_arguments(args)
         );
 
+        // Property calls map our property names and values to Resolve's around the script
+        if let "get_timeline_item_property" | "set_timeline_item_property" = method {
+            return self.real_timeline_item_property(method, args);
+        }
+        if method == "apply_drx_file" {
+            return self.real_apply_drx_file(args);
+        }
+        if method == "export_timeline" {
+            return self.real_export_timeline(args);
+        }
+        if app_state::REAL_METHODS.contains(&method) {
+            return self.real_app_state(method).await;
+        }
+        if bootstrap::REAL_METHODS.contains(&method) {
+            return self.real_session_operation(method, args);
+        }
+        if real_color::METHODS.contains(&method) {
+            return self.real_color_operation(method, args).await;
+        }
+        if real_render::METHODS.contains(&method) {
+            return self.real_render_operation(method, args).await;
+        }
+        if real_media_pool::METHODS.contains(&method) {
+            return self.real_media_pool_operation(method, args).await;
+        }
+
         // Create Python script for the specific API call
         let python_script = match method {
             "switch_page" => {
@@ -982,6 +1810,8 @@ except Exception as e:
                 let frame = args["frame"].as_i64().unwrap_or(0);
                 let color = args["color"].as_str().unwrap_or("Blue");
                 let note = args["note"].as_str().unwrap_or("");
+                let name = args["name"].as_str().filter(|name| !name.is_empty()).unwrap_or(note);
+                let duration = args["duration"].as_i64().unwrap_or(1);
                 format!(r#"
 import sys
 import json
@@ -1005,7 +1835,7 @@ try:
         print(json.dumps({{"error": "No timeline selected"}}))
         sys.exit(1)
     
-    result = timeline.AddMarker({}, "{}", "{}", "{}", 1)
+    result = timeline.AddMarker({}, "{}", "{}", "{}", {})
     if result:
         print(json.dumps({{"success": True, "result": "Added {} marker at frame {}"}}))
     else:
@@ -1014,7 +1844,7 @@ try:
 except Exception as e:
     print(json.dumps({{"error": str(e)}}))
     sys.exit(1)
-"#, frame, color, note, note, color, frame)
+"#, frame, color, name, note, duration, color, frame)
             },
             "list_timelines_tool" => {
                 r#"
@@ -1052,38 +1882,116 @@ except Exception as e:
     print(json.dumps({"error": str(e)}))
     sys.exit(1)
 "#.to_string()
+            },
+            "sync_review_marker" => {
+                // Pass the payload as a JSON string literal so note text needs no escaping
+                let payload = serde_json::to_string(&args.to_string())?;
+                format!(r#"
+import sys
+import json
+sys.path.append("/opt/resolve/Developer/Scripting/Modules")
+
+try:
+    import DaVinciResolveScript as dvr_script
+    resolve = dvr_script.scriptapp("Resolve")
+    if not resolve:
+        print(json.dumps({{"error": "Cannot connect to DaVinci Resolve"}}))
+        sys.exit(1)
+
+    project = resolve.GetProjectManager().GetCurrentProject()
+    timeline = project.GetCurrentTimeline() if project else None
+    if not timeline:
+        print(json.dumps({{"error": "No timeline selected"}}))
+        sys.exit(1)
+
+    note = json.loads({})
+    timeline.DeleteMarkerByCustomData(note["note_id"])
+    result = timeline.AddMarker(note["frame"], note["color"], note["name"], note["text"], 1, note["note_id"])
+    if result:
+        print(json.dumps({{"success": True, "result": "Synced review marker " + note["note_id"]}}))
+    else:
+        print(json.dumps({{"error": "Failed to add review marker"}}))
+        sys.exit(1)
+except Exception as e:
+    print(json.dumps({{"error": str(e)}}))
+    sys.exit(1)
+"#, payload)
             },
             _ => {
                 return Err(ResolveError::not_supported(format!("Real API method: {}", method)));
             }
         };
 
-        // Execute Python script
-        let output = Command::new("python3")
-            .arg("-c")
-            .arg(&python_script)
-            .output()
-            .map_err(|e| {
-                ResolveError::internal(&format!("Failed to execute Python script: {}", e))
-            })?;
+        self.run_resolve_script(method, &python_script)
+    }
 
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            return Err(ResolveError::api_call(
-                method,
-                format!("Python script failed: {}", stderr),
-            ));
+    /// Python command for a script, with any configured scripting module directory on its path
+    fn python_command(&self, script: &str) -> std::process::Command {
+        let mut command = std::process::Command::new("python3");
+        command.arg("-c").arg(script);
+        if let Some(modules) = &self.config.resolve.scripting_modules {
+            command.env("PYTHONPATH", modules);
         }
+        command
+    }
+
+    /// A script for `run_resolve_script`: `definitions` at the top level, then
+    /// `setup` and `operation` inside the connection's `try`, with `request` in
+    /// scope for the operation
+    pub(super) fn resolve_script(
+        definitions: &str,
+        setup: &str,
+        request: &Value,
+        operation: &str,
+    ) -> ResolveResult<String> {
+        // Pass the request as a JSON string literal so no value needs escaping
+        let payload = serde_json::to_string(&request.to_string())?;
+        Ok(format!(
+            "{}{}{}{}\n    request = json.loads({}){}{}",
+            SCRIPT_PRELUDE, definitions, SCRIPT_CONNECT, setup, payload, operation, SCRIPT_EPILOGUE
+        ))
+    }
+
+    /// Run a script against the Resolve scripting API and return its JSON result
+    ///
+    /// Scripts print `{"error": ...}` before exiting non-zero, so stdout is read
+    /// first; the exit status and stderr only explain a script that printed no JSON.
+    fn run_resolve_script(&self, method: &str, script: &str) -> ResolveResult<Value> {
+        let output = self.python_command(script).output().map_err(|e| {
+            ResolveError::internal(&format!("Failed to execute Python script: {}", e))
+        })?;
 
         let stdout = String::from_utf8_lossy(&output.stdout);
-        let json_result: serde_json::Value = serde_json::from_str(&stdout).map_err(|e| {
-            ResolveError::internal(&format!("Failed to parse Python response: {}", e))
-        })?;
+        let json_result: serde_json::Value = match serde_json::from_str(&stdout) {
+            Ok(value) => value,
+            Err(e) if output.status.success() => {
+                return Err(ResolveError::internal(&format!(
+                    "Failed to parse Python response: {}",
+                    e
+                )));
+            }
+            Err(_) => {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                let stderr = stderr.trim();
+                return Err(ResolveError::api_call(
+                    method,
+                    format!(
+                        "Python script failed ({}): {}",
+                        output.status,
+                        if stderr.is_empty() {
+                            "no output"
+                        } else {
+                            stderr
+                        }
+                    ),
+                ));
+            }
+        };
 
-        if let Some(_error) = json_result.get("error") {
+        if let Some(error) = json_result.get("error") {
             return Err(ResolveError::api_call(
                 method,
-                _error.as_str().unwrap_or("Unknown error").to_string(),
+                error.as_str().unwrap_or("Unknown error").to_string(),
             ));
         }
 
@@ -1103,8 +2011,6 @@ except Exception as e:
 
     /// Test Python API connection to DaVinci Resolve
     async fn test_python_api_connection(&self) -> ResolveResult<()> {
-        use std::process::Command;
-
         tracing::debug!("Testing Python API connection to DaVinci Resolve...");
 
         let python_script = r#"
@@ -1133,13 +2039,9 @@ except Exception as e:
     sys.exit(1)
 "#;
 
-        let output = Command::new("python3")
-            .arg("-c")
-            .arg(python_script)
-            .output()
-            .map_err(|e| {
-                ResolveError::internal(&format!("Failed to execute Python test script: {}", e))
-            })?;
+        let output = self.python_command(python_script).output().map_err(|e| {
+            ResolveError::internal(&format!("Failed to execute Python test script: {}", e))
+        })?;
 
         if !output.status.success() {
             let stderr = String::from_utf8_lossy(&output.stderr);
@@ -1170,7 +2072,7 @@ except Exception as e:
         }
     }
 
-    async fn create_project(&self, state: &mut ResolveState, args: Value) -> ResolveResult<Value> {
+    async fn create_project(&self, state: &mut StateView<'_>, args: Value) -> ResolveResult<Value> {
         let name = args["name"]
             .as_str()
             .ok_or_else(|| ResolveError::invalid_parameter("name", "required string"))?;
@@ -1183,9 +2085,10 @@ except Exception as e:
         }
 
         state.projects.push(name.to_string());
-        state.current_project = Some(name.to_string());
+        *state.current_project = Some(name.to_string());
+        state.project_settings.clear();
         state.timelines.clear();
-        state.media_pool = MediaPool::default();
+        *state.media_pool = MediaPool::default();
 
         Ok(serde_json::json!({
             "result": format!("Created project '{}'", name),
@@ -1194,7 +2097,7 @@ except Exception as e:
         }))
     }
 
-    async fn open_project(&self, state: &mut ResolveState, args: Value) -> ResolveResult<Value> {
+    async fn open_project(&self, state: &mut StateView<'_>, args: Value) -> ResolveResult<Value> {
         let name = args["name"]
             .as_str()
             .ok_or_else(|| ResolveError::invalid_parameter("name", "required string"))?;
@@ -1205,18 +2108,24 @@ except Exception as e:
             });
         }
 
-        state.current_project = Some(name.to_string());
+        *state.current_project = Some(name.to_string());
 
         // Simulate loading existing timelines and media
         if !state.timelines.contains_key(name) {
             state.timelines.insert(
                 name.to_string(),
                 Timeline {
+                    id: Uuid::new_v4().to_string(),
                     name: format!("{} Timeline", name),
                     frame_rate: Some("24".to_string()),
+                    start_timecode: None,
                     resolution_width: Some(1920),
                     resolution_height: Some(1080),
                     markers: vec![],
+                    playhead: None,
+                    subtitle_tracks: Vec::new(),
+                    lineage: None,
+                    output: Default::default(),
                 },
             );
         }
@@ -1228,7 +2137,7 @@ except Exception as e:
         }))
     }
 
-    async fn switch_page(&self, state: &mut ResolveState, args: Value) -> ResolveResult<Value> {
+    async fn switch_page(&self, state: &mut StateView<'_>, args: Value) -> ResolveResult<Value> {
         let page = args["page"]
             .as_str()
             .ok_or_else(|| ResolveError::invalid_parameter("page", "required string"))?;
@@ -1246,15 +2155,19 @@ except Exception as e:
             return Err(ResolveError::invalid_parameter("page", "invalid page name"));
         }
 
-        state.current_page = page.to_string();
+        *state.current_page = page.to_string();
 
         Ok(serde_json::json!({
             "result": format!("Switched to {} page", page),
-            "previous_page": state.current_page
+            "previous_page": *state.current_page
         }))
     }
 
-    async fn create_timeline(&self, state: &mut ResolveState, args: Value) -> ResolveResult<Value> {
+    async fn create_timeline(
+        &self,
+        state: &mut StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
         let name = args["name"]
             .as_str()
             .ok_or_else(|| ResolveError::invalid_parameter("name", "required string"))?;
@@ -1263,20 +2176,39 @@ except Exception as e:
             return Err(ResolveError::NotRunning);
         }
 
+        if !state.timelines.contains_key(name) {
+            ensure_capacity(
+                "timelines",
+                state.timelines.len(),
+                1,
+                self.config.limits.max_timelines,
+            )?;
+        }
+
         let timeline = Timeline {
+            id: state.timelines.get(name).map_or_else(
+                || Uuid::new_v4().to_string(),
+                |timeline| timeline.id.clone(),
+            ),
             name: name.to_string(),
             frame_rate: args["frame_rate"].as_str().map(|s| s.to_string()),
+            start_timecode: None,
             resolution_width: args["resolution_width"].as_i64().map(|i| i as i32),
             resolution_height: args["resolution_height"].as_i64().map(|i| i as i32),
             markers: vec![],
+            playhead: None,
+            subtitle_tracks: Vec::new(),
+            lineage: None,
+            output: Default::default(),
         };
 
+        let timeline_id = timeline.id.clone();
         state.timelines.insert(name.to_string(), timeline);
-        state.current_timeline = Some(name.to_string());
+        *state.current_timeline = Some(name.to_string());
 
         Ok(serde_json::json!({
             "result": format!("Created timeline '{}'", name),
-            "timeline_id": Uuid::new_v4().to_string(),
+            "timeline_id": timeline_id,
             "frame_rate": args["frame_rate"],
             "resolution": format!("{}x{}",
                 args["resolution_width"].as_i64().unwrap_or(1920),
@@ -1285,37 +2217,57 @@ except Exception as e:
         }))
     }
 
-    async fn add_marker(&self, state: &mut ResolveState, args: Value) -> ResolveResult<Value> {
-        if state.current_timeline.is_none() {
+    async fn add_marker(&self, state: &mut StateView<'_>, args: Value) -> ResolveResult<Value> {
+        let Some(timeline_name) = state.current_timeline.clone() else {
             return Err(ResolveError::TimelineNotFound {
                 name: "current".to_string(),
             });
+        };
+
+        if args["duration"]
+            .as_i64()
+            .is_some_and(|duration| duration < 1)
+        {
+            return Err(ResolveError::invalid_parameter(
+                "duration",
+                "must be at least 1 frame",
+            ));
         }
 
-        let timeline_name = state.current_timeline.as_ref().unwrap();
-        let timeline = state.timelines.get_mut(timeline_name).ok_or_else(|| {
+        let timeline = state.timelines.get_mut(&timeline_name).ok_or_else(|| {
             ResolveError::TimelineNotFound {
                 name: timeline_name.clone(),
             }
         })?;
 
+        ensure_capacity(
+            &format!("markers on timeline '{}'", timeline_name),
+            timeline.markers.len(),
+            1,
+            self.config.limits.max_markers_per_timeline,
+        )?;
+
         let marker = Marker {
+            id: Uuid::new_v4().to_string(),
             frame: args["frame"].as_i64().map(|i| i as i32),
             color: args["color"].as_str().unwrap_or("Blue").to_string(),
             note: args["note"].as_str().unwrap_or("").to_string(),
+            name: args["name"].as_str().unwrap_or("").to_string(),
+            duration: args["duration"].as_i64().unwrap_or(1),
         };
 
+        let marker_id = marker.id.clone();
         timeline.markers.push(marker);
 
         Ok(serde_json::json!({
             "result": format!("Added {} marker to timeline '{}'",
                 args["color"].as_str().unwrap_or("Blue"), timeline_name),
-            "marker_id": Uuid::new_v4().to_string(),
+            "marker_id": marker_id,
             "total_markers": timeline.markers.len()
         }))
     }
 
-    async fn import_media(&self, state: &mut ResolveState, args: Value) -> ResolveResult<Value> {
+    async fn import_media(&self, state: &mut StateView<'_>, args: Value) -> ResolveResult<Value> {
         let file_path = args["file_path"]
             .as_str()
             .ok_or_else(|| ResolveError::invalid_parameter("file_path", "required string"))?;
@@ -1330,25 +2282,84 @@ except Exception as e:
             .and_then(|name| name.to_str())
             .unwrap_or("unknown_file");
 
+        if !state.media_pool.clips.contains_key(filename) {
+            ensure_capacity(
+                "clips",
+                state.media_pool.clips.len(),
+                1,
+                self.config.limits.max_clips,
+            )?;
+        }
+
+        let metadata = self.import_metadata(&args["metadata"], filename)?;
+
+        // A bin the import names, as a user's default bin may, is created if missing
+        let bin_name = args["bin_name"].as_str();
+        let created_bin = match bin_name {
+            Some(name) if !state.media_pool.bins.contains_key(name) => {
+                state.media_pool.bins.insert(
+                    name.to_string(),
+                    Bin {
+                        id: Uuid::new_v4().to_string(),
+                        name: name.to_string(),
+                        clips: vec![],
+                        color_rules: Vec::new(),
+                    },
+                );
+                true
+            }
+            _ => false,
+        };
         let clip = Clip {
+            id: state
+                .media_pool
+                .clips
+                .get(filename)
+                .map_or_else(|| Uuid::new_v4().to_string(), |clip| clip.id.clone()),
             name: filename.to_string(),
             file_path: file_path.to_string(),
-            bin: None,
+            bin: bin_name.map(str::to_string),
             linked: true,
             proxy_path: None,
+            start_timecode: None,
+            reel_name: None,
+            frame_rate: None,
+            color: Default::default(),
+            metadata: Default::default(),
+            marks: Default::default(),
         };
 
+        let clip_id = clip.id.clone();
         state.media_pool.clips.insert(filename.to_string(), clip);
+        if let Some(bin) = bin_name.and_then(|bin| state.media_pool.bins.get_mut(bin)) {
+            if !bin.clips.iter().any(|name| name == filename) {
+                bin.clips.push(filename.to_string());
+            }
+        }
+        #[cfg(feature = "camera-sidecars")]
+        let sidecar = self.apply_camera_sidecar(state, filename);
+        #[cfg(not(feature = "camera-sidecars"))]
+        let sidecar = Value::Null;
+        for (field, value) in metadata {
+            metadata_schema::set_clip_value(state, filename, &field, value);
+        }
+        let color = Self::apply_bin_color_rules(state, filename);
 
         Ok(serde_json::json!({
             "result": format!("Imported media: {}", filename),
-            "clip_id": Uuid::new_v4().to_string(),
+            "clip_id": clip_id,
+            "bin_name": bin_name,
+            "created_bin": created_bin,
+            "sidecar": sidecar,
+            "missing_required_metadata": self.missing_required_metadata(state, filename),
+            "input_color_space": color.as_ref().and_then(|color| color.input_color_space.clone()),
+            "input_lut": color.and_then(|color| color.input_lut),
             "file_size": "simulated",
             "duration": "00:01:30:00"
         }))
     }
 
-    async fn create_bin(&self, state: &mut ResolveState, args: Value) -> ResolveResult<Value> {
+    async fn create_bin(&self, state: &mut StateView<'_>, args: Value) -> ResolveResult<Value> {
         let name = args["name"]
             .as_str()
             .ok_or_else(|| ResolveError::invalid_parameter("name", "required string"))?;
@@ -1358,51 +2369,32 @@ except Exception as e:
         }
 
         // Check if bin already exists - if so, return success (idempotent operation)
-        if state.media_pool.bins.contains_key(name) {
+        if let Some(bin) = state.media_pool.bins.get(name) {
             return Ok(serde_json::json!({
                 "result": format!("Bin '{}' already exists", name),
-                "bin_id": Uuid::new_v4().to_string(),
+                "bin_id": bin.id,
                 "already_existed": true
             }));
         }
 
         let bin = Bin {
+            id: Uuid::new_v4().to_string(),
             name: name.to_string(),
             clips: vec![],
+            color_rules: Vec::new(),
         };
 
+        let bin_id = bin.id.clone();
         state.media_pool.bins.insert(name.to_string(), bin);
 
         Ok(serde_json::json!({
             "result": format!("Created bin '{}'", name),
-            "bin_id": Uuid::new_v4().to_string(),
+            "bin_id": bin_id,
             "already_existed": false
         }))
     }
 
-    async fn auto_sync_audio(
-        &self,
-        _state: &mut ResolveState,
-        args: Value,
-    ) -> ResolveResult<Value> {
-        let clip_names = args["clip_names"]
-            .as_array()
-            .ok_or_else(|| ResolveError::invalid_parameter("clip_names", "required array"))?;
-
-        let sync_method = args["sync_method"].as_str().unwrap_or("waveform");
-        let clips_found = clip_names.len();
-
-        // Simulate sync processing
-        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
-
-        Ok(serde_json::json!({
-            "result": format!("Synchronized {} clips using {} method", clips_found, sync_method),
-            "sync_id": Uuid::new_v4().to_string(),
-            "processing_time": "1.2s"
-        }))
-    }
-
-    async fn unlink_clips(&self, _state: &mut ResolveState, args: Value) -> ResolveResult<Value> {
+    async fn unlink_clips(&self, _state: &StateView<'_>, args: Value) -> ResolveResult<Value> {
         let clip_names = args["clip_names"]
             .as_array()
             .ok_or_else(|| ResolveError::invalid_parameter("clip_names", "required array"))?;
@@ -1413,27 +2405,49 @@ except Exception as e:
         }))
     }
 
-    async fn relink_clips(&self, _state: &mut ResolveState, args: Value) -> ResolveResult<Value> {
+    async fn relink_clips(&self, state: &mut StateView<'_>, args: Value) -> ResolveResult<Value> {
         let clip_names = args["clip_names"]
             .as_array()
             .ok_or_else(|| ResolveError::invalid_parameter("clip_names", "required array"))?;
 
+        if let Some(folder) = args["folder_path"].as_str() {
+            let names: Vec<String> = clip_names
+                .iter()
+                .filter_map(|name| name.as_str().map(str::to_string))
+                .collect();
+            return self.relink_from_folder(
+                state,
+                &names,
+                folder,
+                args["recursive"].as_bool().unwrap_or(false),
+                args["match_by"].as_str().unwrap_or("name"),
+            );
+        }
+
         Ok(serde_json::json!({
             "result": format!("Relinked {} clips", clip_names.len()),
             "operation_id": Uuid::new_v4().to_string()
         }))
     }
 
-    async fn create_sub_clip(
-        &self,
-        _state: &mut ResolveState,
-        args: Value,
-    ) -> ResolveResult<Value> {
+    async fn create_sub_clip(&self, _state: &StateView<'_>, args: Value) -> ResolveResult<Value> {
         let clip_name = args["clip_name"]
             .as_str()
             .ok_or_else(|| ResolveError::invalid_parameter("clip_name", "required string"))?;
-        let start_frame = args["start_frame"].as_i64().unwrap_or(0) as i32;
-        let end_frame = args["end_frame"].as_i64().unwrap_or(100) as i32;
+        let start_frame = args["start_frame"].as_i64().unwrap_or(0);
+        let end_frame = args["end_frame"].as_i64().unwrap_or(100);
+        if start_frame < 0 {
+            return Err(ResolveError::invalid_parameter(
+                "start_frame",
+                "must not be negative",
+            ));
+        }
+        if end_frame <= start_frame {
+            return Err(ResolveError::invalid_parameter(
+                "end_frame",
+                "must be after start_frame",
+            ));
+        }
 
         let default_sub_clip_name = format!("{}_subclip", clip_name);
         let sub_clip_name = args["sub_clip_name"]
@@ -1448,11 +2462,7 @@ except Exception as e:
         }))
     }
 
-    async fn link_proxy_media(
-        &self,
-        _state: &mut ResolveState,
-        args: Value,
-    ) -> ResolveResult<Value> {
+    async fn link_proxy_media(&self, _state: &StateView<'_>, args: Value) -> ResolveResult<Value> {
         let clip_name = args["clip_name"]
             .as_str()
             .ok_or_else(|| ResolveError::invalid_parameter("clip_name", "required string"))?;
@@ -1465,7 +2475,7 @@ except Exception as e:
 
     async fn unlink_proxy_media(
         &self,
-        _state: &mut ResolveState,
+        _state: &StateView<'_>,
         args: Value,
     ) -> ResolveResult<Value> {
         let clip_name = args["clip_name"]
@@ -1478,7 +2488,7 @@ except Exception as e:
         }))
     }
 
-    async fn replace_clip(&self, _state: &mut ResolveState, args: Value) -> ResolveResult<Value> {
+    async fn replace_clip(&self, _state: &StateView<'_>, args: Value) -> ResolveResult<Value> {
         let clip_name = args["clip_name"]
             .as_str()
             .ok_or_else(|| ResolveError::invalid_parameter("clip_name", "required string"))?;
@@ -1492,45 +2502,44 @@ except Exception as e:
         }))
     }
 
-    async fn delete_timeline(&self, state: &mut ResolveState, args: Value) -> ResolveResult<Value> {
-        let name = args["name"]
+    async fn delete_timeline(
+        &self,
+        state: &mut StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        let reference = args["name"]
             .as_str()
             .ok_or_else(|| ResolveError::invalid_parameter("name", "required string"))?;
-
-        if state.timelines.remove(name).is_none() {
-            return Err(ResolveError::TimelineNotFound {
-                name: name.to_string(),
-            });
-        }
+        let name = &Self::resolve_timeline(state, reference)?;
+        let trash = state
+            .timelines
+            .remove(name)
+            .and_then(|timeline| self.move_to_trash(state, trash::Trashed::Timeline(timeline)));
 
         // Reset current timeline if it was the deleted one
-        if state.current_timeline.as_ref() == Some(&name.to_string()) {
-            state.current_timeline = None;
+        if state.current_timeline.as_ref() == Some(name) {
+            *state.current_timeline = None;
         }
 
         Ok(serde_json::json!({
             "result": format!("Deleted timeline '{}'", name),
             "remaining_timelines": state.timelines.len(),
+            "trash": trash,
             "operation_id": Uuid::new_v4().to_string()
         }))
     }
 
     async fn set_current_timeline(
         &self,
-        state: &mut ResolveState,
+        state: &mut StateView<'_>,
         args: Value,
     ) -> ResolveResult<Value> {
-        let name = args["name"]
+        let reference = args["name"]
             .as_str()
             .ok_or_else(|| ResolveError::invalid_parameter("name", "required string"))?;
+        let name = Self::resolve_timeline(state, reference)?;
 
-        if !state.timelines.contains_key(name) {
-            return Err(ResolveError::TimelineNotFound {
-                name: name.to_string(),
-            });
-        }
-
-        state.current_timeline = Some(name.to_string());
+        *state.current_timeline = Some(name.clone());
 
         Ok(serde_json::json!({
             "result": format!("Set current timeline to '{}'", name),
@@ -1540,7 +2549,7 @@ except Exception as e:
 
     async fn create_empty_timeline(
         &self,
-        state: &mut ResolveState,
+        state: &mut StateView<'_>,
         args: Value,
     ) -> ResolveResult<Value> {
         let name = args["name"]
@@ -1554,7 +2563,7 @@ except Exception as e:
                     // Auto-create a default project in simulation mode
                     let default_project = "Default Project".to_string();
                     state.projects.push(default_project.clone());
-                    state.current_project = Some(default_project);
+                    *state.current_project = Some(default_project);
                     tracing::info!("Auto-created default project for timeline creation");
                 }
                 ConnectionMode::Real => {
@@ -1563,20 +2572,45 @@ except Exception as e:
             }
         }
 
+        if !state.timelines.contains_key(name) {
+            ensure_capacity(
+                "timelines",
+                state.timelines.len(),
+                1,
+                self.config.limits.max_timelines,
+            )?;
+        }
+
         let timeline = Timeline {
+            id: state.timelines.get(name).map_or_else(
+                || Uuid::new_v4().to_string(),
+                |timeline| timeline.id.clone(),
+            ),
             name: name.to_string(),
             frame_rate: args["frame_rate"].as_str().map(|s| s.to_string()),
+            start_timecode: args["start_timecode"].as_str().map(|s| s.to_string()),
             resolution_width: args["resolution_width"].as_i64().map(|i| i as i32),
             resolution_height: args["resolution_height"].as_i64().map(|i| i as i32),
             markers: vec![],
+            playhead: None,
+            subtitle_tracks: Vec::new(),
+            lineage: None,
+            output: Default::default(),
         };
+        if let Some(start) = &timeline.start_timecode {
+            self.timeline_frame_rate(&timeline)?
+                .for_timecode(start)
+                .and_then(|rate| rate.timecode_to_frames(start))
+                .map_err(|e| ResolveError::invalid_parameter("start_timecode", e))?;
+        }
 
+        let timeline_id = timeline.id.clone();
         state.timelines.insert(name.to_string(), timeline);
-        state.current_timeline = Some(name.to_string());
+        *state.current_timeline = Some(name.to_string());
 
         Ok(serde_json::json!({
             "result": format!("Created empty timeline '{}'", name),
-            "timeline_id": Uuid::new_v4().to_string(),
+            "timeline_id": timeline_id,
             "frame_rate": args["frame_rate"],
             "resolution": format!("{}x{}",
                 args["resolution_width"].as_i64().unwrap_or(1920),
@@ -1589,46 +2623,138 @@ except Exception as e:
 
     async fn add_clip_to_timeline(
         &self,
-        state: &mut ResolveState,
+        state: &mut StateView<'_>,
         args: Value,
     ) -> ResolveResult<Value> {
-        let clip_name = args["clip_name"]
+        let clip_reference = args["clip_name"]
             .as_str()
             .ok_or_else(|| ResolveError::invalid_parameter("clip_name", "required string"))?;
+        let timeline_name = Self::resolve_timeline_name(state, &args)?;
+        let clip_name = Self::resolve_clip(state, clip_reference)?;
 
-        let timeline_name = if let Some(name) = args["timeline_name"].as_str() {
-            name.to_string()
-        } else {
-            state
-                .current_timeline
-                .clone()
-                .ok_or_else(|| ResolveError::TimelineNotFound {
-                    name: "current".to_string(),
-                })?
+        let track_index = match args["track_index"].as_i64() {
+            Some(index) if index >= 1 => index as u32,
+            Some(_) => {
+                return Err(ResolveError::invalid_parameter(
+                    "track_index",
+                    "must be 1 or greater",
+                ))
+            }
+            None => 1,
         };
-
-        if !state.timelines.contains_key(&timeline_name) {
-            return Err(ResolveError::TimelineNotFound {
-                name: timeline_name,
-            });
+        let mut placed = self.place_clip(state, &timeline_name, &clip_name, track_index, &args)?;
+        placed["result"] = json!(format!(
+            "Added clip '{}' to timeline '{}' as item '{}'",
+            clip_name,
+            timeline_name,
+            placed["timeline_item_id"].as_str().unwrap_or_default()
+        ));
+        Ok(placed)
+    }
+
+    /// Place a media pool clip on a video track of a timeline. The source
+    /// range is `start_frame` to `end_frame` from `args`, with the clip's
+    /// video marks filling in whatever they leave out; the item goes at
+    /// `record_frame`, or after the last item on the track.
+    fn place_clip(
+        &self,
+        state: &mut StateView<'_>,
+        timeline_name: &str,
+        clip_name: &str,
+        track_index: u32,
+        args: &Value,
+    ) -> ResolveResult<Value> {
+        let clip_id = state.media_pool.clips[clip_name].id.clone();
+        let rate = self.timeline_frame_rate(&state.timelines[timeline_name])?;
+        let marked = state.media_pool.clips[clip_name].marks.video_range();
+        let source_in = args["start_frame"]
+            .as_i64()
+            .or(marked.map(|(start, _)| start))
+            .unwrap_or(0);
+        let source_out = args["end_frame"]
+            .as_i64()
+            .or(marked.map(|(_, end)| end))
+            .unwrap_or(SIMULATED_CLIP_SECONDS * rate.nominal());
+        let used_marks =
+            marked.is_some() && (args["start_frame"].is_null() || args["end_frame"].is_null());
+        if source_in < 0 || source_out <= source_in {
+            return Err(ResolveError::invalid_parameter(
+                "end_frame",
+                "source range must start at 0 or later and end after it starts",
+            ));
         }
 
-        if !state.media_pool.clips.contains_key(clip_name) {
-            return Err(ResolveError::MediaNotFound {
-                name: clip_name.to_string(),
-            });
-        }
+        let on_track: Vec<&ItemPlacement> = state
+            .timeline_items
+            .items
+            .values()
+            .filter(|item| item.timeline_name == timeline_name)
+            .filter_map(|item| item.placement.as_ref())
+            .filter(|placement| placement.track_index == track_index)
+            .collect();
+        let record_in = match args["record_frame"].as_i64() {
+            Some(frame) if frame < 0 => {
+                return Err(ResolveError::invalid_parameter(
+                    "record_frame",
+                    "must be 0 or later",
+                ))
+            }
+            Some(frame) => {
+                let record_out = frame + source_out - source_in;
+                if on_track.iter().any(|placement| {
+                    placement.record_in < record_out && frame < placement.record_out()
+                }) {
+                    return Err(ResolveError::invalid_parameter(
+                        "record_frame",
+                        format!(
+                            "the clip would overlap another item on video track {}",
+                            track_index
+                        ),
+                    ));
+                }
+                frame
+            }
+            None => on_track
+                .iter()
+                .map(|placement| placement.record_out())
+                .max()
+                .unwrap_or(0),
+        };
 
-        Ok(serde_json::json!({
-            "result": format!("Added clip '{}' to timeline '{}'", clip_name, timeline_name),
-            "timeline_item_id": Uuid::new_v4().to_string(),
-            "track": "Video 1"
+        let item_id = Uuid::new_v4().to_string();
+        state.timeline_items.item_counter += 1;
+        state.timeline_items.items.insert(
+            item_id.clone(),
+            TimelineItemState {
+                id: item_id.clone(),
+                timeline_name: timeline_name.to_string(),
+                clip_name: clip_name.to_string(),
+                clip_id: Some(clip_id.clone()),
+                placement: Some(ItemPlacement {
+                    track_index,
+                    record_in,
+                    source_in,
+                    duration: source_out - source_in,
+                }),
+                ..Default::default()
+            },
+        );
+
+        Ok(json!({
+            "timeline_item_id": item_id,
+            "clip_name": clip_name,
+            "clip_id": clip_id,
+            "track": format!("Video {}", track_index),
+            "record_frame": record_in,
+            "source_in": source_in,
+            "duration": source_out - source_in,
+            "used_clip_marks": used_marks
         }))
     }
 
     async fn list_timelines_tool(
         &self,
-        state: &mut ResolveState,
+        state: &StateView<'_>,
         _args: Value,
     ) -> ResolveResult<Value> {
         let timeline_names: Vec<&String> = state.timelines.keys().collect();
@@ -1642,16 +2768,24 @@ except Exception as e:
                 .join(", ")
         };
 
+        let mut timelines: Vec<Value> = state
+            .timelines
+            .iter()
+            .map(|(name, timeline)| json!({"name": name, "timeline_id": timeline.id}))
+            .collect();
+        timelines.sort_by(|a, b| a["name"].as_str().cmp(&b["name"].as_str()));
+
         Ok(serde_json::json!({
             "result": format!("Timelines: {}", timeline_list),
+            "timelines": timelines,
             "count": timeline_names.len(),
-            "current_timeline": state.current_timeline
+            "current_timeline": *state.current_timeline
         }))
     }
 
     async fn get_timeline_tracks(
         &self,
-        state: &mut ResolveState,
+        state: &StateView<'_>,
         args: Value,
     ) -> ResolveResult<Value> {
         let timeline_name = if let Some(name) = args["timeline_name"].as_str() {
@@ -1685,7 +2819,7 @@ except Exception as e:
 
     // ==================== COLOR OPERATIONS (Phase 3 Week 3) ====================
 
-    async fn apply_lut(&self, state: &mut ResolveState, args: Value) -> ResolveResult<Value> {
+    async fn apply_lut(&self, state: &mut StateView<'_>, args: Value) -> ResolveResult<Value> {
         let lut_path = args["lut_path"]
             .as_str()
             .ok_or_else(|| ResolveError::invalid_parameter("lut_path", "required string"))?;
@@ -1694,8 +2828,25 @@ except Exception as e:
             .unwrap_or(state.color_state.current_node_index as i64) as i32;
 
         // Validate LUT exists (check if it's in our available LUTs or is a file path)
+        self.refresh_lut_index(state);
+        let indexed = state
+            .color_state
+            .available_luts
+            .iter()
+            .any(|(key, info)| key == lut_path || info.path == lut_path);
+        let in_lut_folder = self
+            .config
+            .luts
+            .directories
+            .iter()
+            .any(|directory| std::path::Path::new(lut_path).starts_with(directory));
         let lut_name = if lut_path.starts_with('/') {
-            // File path - validate it exists
+            // A file in a LUT folder must be one the index could read
+            if in_lut_folder && !indexed {
+                return Err(ResolveError::FileNotFound {
+                    path: lut_path.to_string(),
+                });
+            }
             std::path::Path::new(lut_path)
                 .file_stem()
                 .and_then(|s| s.to_str())
@@ -1712,9 +2863,9 @@ except Exception as e:
         };
 
         // Apply LUT to current clip
-        if let Some(clip_name) = &state.color_state.current_clip {
-            let grade = state
-                .color_state
+        let color_state = &mut *state.color_state;
+        if let Some(clip_name) = &color_state.current_clip {
+            let grade = color_state
                 .clip_grades
                 .entry(clip_name.clone())
                 .or_default();
@@ -1731,7 +2882,7 @@ except Exception as e:
 
     async fn set_color_wheel_param(
         &self,
-        state: &mut ResolveState,
+        state: &mut StateView<'_>,
         args: Value,
     ) -> ResolveResult<Value> {
         let wheel = args["wheel"]
@@ -1748,24 +2899,33 @@ except Exception as e:
             .unwrap_or(state.color_state.current_node_index as i64) as i32;
 
         // Validate wheel and param
-        let valid_wheels = vec!["lift", "gamma", "gain", "offset"];
-        let valid_params = vec!["red", "green", "blue", "master"];
-
-        if !valid_wheels.contains(&wheel) {
+        if !clip_grades::WHEELS.contains(&wheel) {
             return Err(ResolveError::invalid_parameter(
                 "wheel",
                 "must be lift, gamma, gain, or offset",
             ));
         }
-        if !valid_params.contains(&param) {
+        if !clip_grades::WHEEL_PARAMS.contains(&param) {
             return Err(ResolveError::invalid_parameter(
                 "param",
                 "must be red, green, blue, or master",
             ));
         }
 
-        // Apply to current clip
-        if let Some(clip_name) = &state.color_state.current_clip {
+        // A relative value is added to the current one under the same lock
+        let relative = args["relative"].as_bool().unwrap_or(false);
+
+        // Apply to the named clip or the current clip
+        let clip_name = Self::grade_clip(state, &args)?;
+        if relative && clip_name.is_none() {
+            return Err(ResolveError::invalid_parameter(
+                "clip_name",
+                "no current clip to adjust; name a clip",
+            ));
+        }
+        let mut previous = None;
+        let mut applied = value;
+        if let Some(clip_name) = &clip_name {
             let grade = state
                 .color_state
                 .clip_grades
@@ -1780,17 +2940,41 @@ except Exception as e:
                 _ => unreachable!(),
             };
 
-            match param {
-                "red" => wheel_params.red = value,
-                "green" => wheel_params.green = value,
-                "blue" => wheel_params.blue = value,
-                "master" => wheel_params.master = value,
+            let slot = match param {
+                "red" => &mut wheel_params.red,
+                "green" => &mut wheel_params.green,
+                "blue" => &mut wheel_params.blue,
+                "master" => &mut wheel_params.master,
                 _ => unreachable!(),
+            };
+            if relative {
+                previous = Some(*slot);
+                applied = clip_grades::clamp_wheel_value(*slot + value);
             }
+            *slot = applied;
         }
 
+        if let Some(previous) = previous {
+            return Ok(serde_json::json!({
+                "result": format!(
+                    "Adjusted {} {} by {:+} to {} on node {}",
+                    wheel, param, value, applied, node_index
+                ),
+                "clip_name": clip_name,
+                "wheel": wheel,
+                "param": param,
+                "relative": true,
+                "delta": value,
+                "previous_value": previous,
+                "value": applied,
+                "clamped": applied != previous + value,
+                "node_index": node_index,
+                "operation_id": Uuid::new_v4().to_string()
+            }));
+        }
         Ok(serde_json::json!({
             "result": format!("Set {} {} to {} on node {}", wheel, param, value, node_index),
+            "clip_name": clip_name,
             "wheel": wheel,
             "param": param,
             "value": value,
@@ -1799,7 +2983,7 @@ except Exception as e:
         }))
     }
 
-    async fn add_node(&self, state: &mut ResolveState, args: Value) -> ResolveResult<Value> {
+    async fn add_node(&self, state: &mut StateView<'_>, args: Value) -> ResolveResult<Value> {
         let node_type = args["node_type"].as_str().unwrap_or("serial");
         let label = args["label"].as_str();
 
@@ -1813,9 +2997,9 @@ except Exception as e:
         }
 
         // Add node to current clip
-        if let Some(clip_name) = &state.color_state.current_clip {
-            let grade = state
-                .color_state
+        let color_state = &mut *state.color_state;
+        if let Some(clip_name) = &color_state.current_clip {
+            let grade = color_state
                 .clip_grades
                 .entry(clip_name.clone())
                 .or_default();
@@ -1840,7 +3024,7 @@ except Exception as e:
         }))
     }
 
-    async fn copy_grade(&self, state: &mut ResolveState, args: Value) -> ResolveResult<Value> {
+    async fn copy_grade(&self, state: &mut StateView<'_>, args: Value) -> ResolveResult<Value> {
         let source_clip_name = args["source_clip_name"].as_str();
         let target_clip_name = args["target_clip_name"].as_str();
         let mode = args["mode"].as_str().unwrap_or("full");
@@ -1913,12 +3097,12 @@ except Exception as e:
 
     async fn save_color_preset(
         &self,
-        state: &mut ResolveState,
+        state: &mut StateView<'_>,
         args: Value,
     ) -> ResolveResult<Value> {
         let clip_name = args["clip_name"].as_str();
         let preset_name = args["preset_name"].as_str();
-        let album_name = args["album_name"].as_str().unwrap_or("DaVinci Resolve");
+        let album_name = args["album_name"].as_str().unwrap_or(DEFAULT_PRESET_ALBUM);
 
         // Use current clip if not specified
         let source_clip =
@@ -1945,53 +3129,64 @@ except Exception as e:
             .cloned()
             .unwrap_or_default();
 
-        // Save preset
+        let album = state
+            .color_state
+            .preset_albums
+            .get_mut(album_name)
+            .ok_or_else(|| {
+                ResolveError::invalid_parameter(
+                    "album_name",
+                    format!("album '{}' not found", album_name),
+                )
+            })?;
+
+        // Saving over an existing preset keeps its ID so references stay valid
+        let preset_id = album
+            .presets
+            .get(&preset_name_final)
+            .map(|existing| existing.id.clone())
+            .unwrap_or_else(|| Uuid::new_v4().to_string());
+        let replaced = album.presets.contains_key(&preset_name_final);
+
         let preset = ColorPreset {
+            id: preset_id.clone(),
             name: preset_name_final.clone(),
             album: album_name.to_string(),
             created_at: chrono::Utc::now().to_rfc3339(),
             grade_data: grade,
         };
 
-        state
-            .color_state
-            .color_presets
-            .insert(preset_name_final.clone(), preset);
+        album.presets.insert(preset_name_final.clone(), preset);
 
         Ok(serde_json::json!({
             "result": format!("Saved color preset '{}' from clip '{}' to album '{}'",
                 preset_name_final, source_clip, album_name),
+            "preset_id": preset_id,
             "preset_name": preset_name_final,
             "album": album_name,
             "source_clip": source_clip,
+            "replaced": replaced,
             "operation_id": Uuid::new_v4().to_string()
         }))
     }
 
     async fn apply_color_preset(
         &self,
-        state: &mut ResolveState,
+        state: &mut StateView<'_>,
         args: Value,
     ) -> ResolveResult<Value> {
-        let preset_id = args["preset_id"].as_str();
-        let preset_name = args["preset_name"].as_str();
         let clip_name = args["clip_name"].as_str();
-        let album_name = args["album_name"].as_str().unwrap_or("DaVinci Resolve");
-
-        // Find preset by ID or name
-        let preset = if let Some(id) = preset_id {
-            state.color_state.color_presets.get(id)
-        } else if let Some(name) = preset_name {
-            state.color_state.color_presets.get(name)
-        } else {
-            return Err(ResolveError::invalid_parameter(
-                "preset_id or preset_name",
-                "one is required",
-            ));
-        };
+        let album_name = args["album_name"].as_str().unwrap_or(DEFAULT_PRESET_ALBUM);
 
-        let preset =
-            preset.ok_or_else(|| ResolveError::invalid_parameter("preset", "preset not found"))?;
+        // Find preset by ID or name within the album
+        let preset = state
+            .color_state
+            .find_preset(
+                album_name,
+                args["preset_id"].as_str(),
+                args["preset_name"].as_str(),
+            )?
+            .clone();
 
         // Use current clip if not specified
         let target_clip =
@@ -2012,6 +3207,7 @@ except Exception as e:
         Ok(serde_json::json!({
             "result": format!("Applied color preset '{}' from album '{}' to clip '{}'",
                 preset.name, album_name, target_clip),
+            "preset_id": preset.id,
             "preset_name": preset.name,
             "album": album_name,
             "target_clip": target_clip,
@@ -2021,41 +3217,113 @@ except Exception as e:
 
     async fn delete_color_preset(
         &self,
-        state: &mut ResolveState,
+        state: &mut StateView<'_>,
         args: Value,
     ) -> ResolveResult<Value> {
         let preset_id = args["preset_id"].as_str();
         let preset_name = args["preset_name"].as_str();
-        let album_name = args["album_name"].as_str().unwrap_or("DaVinci Resolve");
+        let album_name = args["album_name"].as_str().unwrap_or(DEFAULT_PRESET_ALBUM);
 
-        // Find preset by ID or name
-        let preset_key = if let Some(id) = preset_id {
-            id.to_string()
-        } else if let Some(name) = preset_name {
-            name.to_string()
-        } else {
+        if preset_id.is_none() && preset_name.is_none() {
             return Err(ResolveError::invalid_parameter(
                 "preset_id or preset_name",
                 "one is required",
             ));
-        };
+        }
 
-        let removed_preset = state
+        let album = state
             .color_state
-            .color_presets
-            .remove(&preset_key)
-            .ok_or_else(|| ResolveError::invalid_parameter("preset", "preset not found"))?;
+            .preset_albums
+            .get_mut(album_name)
+            .ok_or_else(|| {
+                ResolveError::invalid_parameter(
+                    "album_name",
+                    format!("album '{}' not found", album_name),
+                )
+            })?;
 
-        Ok(serde_json::json!({
+        let removed_preset = album
+            .find_preset_key(preset_id, preset_name)
+            .and_then(|key| album.presets.remove(&key))
+            .ok_or_else(|| {
+                ResolveError::invalid_parameter(
+                    "preset",
+                    format!("preset not found in album '{}'", album_name),
+                )
+            })?;
+
+        let remaining_presets = album.presets.len();
+        let mut response = serde_json::json!({
             "result": format!("Deleted color preset '{}' from album '{}'",
                 removed_preset.name, album_name),
+            "preset_id": removed_preset.id,
             "preset_name": removed_preset.name,
             "album": album_name,
+            "remaining_presets": remaining_presets,
+            "operation_id": Uuid::new_v4().to_string()
+        });
+        response["trash"] =
+            json!(self.move_to_trash(state, trash::Trashed::ColorPreset(removed_preset)));
+        Ok(response)
+    }
+
+    async fn list_color_presets(&self, state: &StateView<'_>, args: Value) -> ResolveResult<Value> {
+        let album_filter = args["album_name"].as_str();
+
+        if let Some(album_name) = album_filter {
+            if !state.color_state.preset_albums.contains_key(album_name) {
+                return Err(ResolveError::invalid_parameter(
+                    "album_name",
+                    format!("album '{}' not found", album_name),
+                ));
+            }
+        }
+
+        let mut albums: Vec<&ColorPresetAlbum> = state
+            .color_state
+            .preset_albums
+            .values()
+            .filter(|album| album_filter.is_none_or(|name| album.name == name))
+            .collect();
+        albums.sort_by(|a, b| a.name.cmp(&b.name));
+
+        let mut total_presets = 0;
+        let album_data: Vec<Value> = albums
+            .iter()
+            .map(|album| {
+                let mut presets: Vec<&ColorPreset> = album.presets.values().collect();
+                presets.sort_by(|a, b| a.name.cmp(&b.name));
+                total_presets += presets.len();
+
+                serde_json::json!({
+                    "album_name": album.name,
+                    "created_at": album.created_at,
+                    "preset_count": presets.len(),
+                    "presets": presets
+                        .iter()
+                        .map(|preset| serde_json::json!({
+                            "preset_id": preset.id,
+                            "preset_name": preset.name,
+                            "album": preset.album,
+                            "created_at": preset.created_at,
+                            "node_count": preset.grade_data.node_count,
+                            "applied_luts": preset.grade_data.applied_luts
+                        }))
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+
+        Ok(serde_json::json!({
+            "result": format!("Found {} color presets in {} albums", total_presets, album_data.len()),
+            "albums": album_data,
+            "album_count": album_data.len(),
+            "preset_count": total_presets,
             "operation_id": Uuid::new_v4().to_string()
         }))
     }
 
-    async fn export_lut(&self, state: &mut ResolveState, args: Value) -> ResolveResult<Value> {
+    async fn export_lut(&self, state: &StateView<'_>, args: Value) -> ResolveResult<Value> {
         let clip_name = args["clip_name"].as_str();
         let export_path = args["export_path"].as_str();
         let lut_format = args["lut_format"].as_str().unwrap_or("Cube");
@@ -2106,7 +3374,7 @@ except Exception as e:
 
     async fn set_timeline_item_transform(
         &self,
-        state: &mut ResolveState,
+        state: &mut StateView<'_>,
         args: Value,
     ) -> ResolveResult<Value> {
         let timeline_item_id = args["timeline_item_id"].as_str().ok_or_else(|| {
@@ -2115,62 +3383,22 @@ except Exception as e:
         let property_name = args["property_name"]
             .as_str()
             .ok_or_else(|| ResolveError::invalid_parameter("property_name", "required string"))?;
-        let property_value = args["property_value"]
-            .as_f64()
-            .ok_or_else(|| ResolveError::invalid_parameter("property_value", "required number"))?;
-
-        // Validate property name
-        let valid_properties = vec![
-            "Pan",
-            "Tilt",
-            "ZoomX",
-            "ZoomY",
-            "Rotation",
-            "AnchorPointX",
-            "AnchorPointY",
-            "Pitch",
-            "Yaw",
-        ];
-        if !valid_properties.contains(&property_name) {
-            return Err(ResolveError::invalid_parameter(
-                "property_name",
-                "invalid transform property",
-            ));
-        }
-
-        // Get or create timeline item
-        let timeline_item = state
-            .timeline_items
-            .items
-            .entry(timeline_item_id.to_string())
-            .or_insert_with(|| {
-                state.timeline_items.item_counter += 1;
-                TimelineItemState {
-                    id: timeline_item_id.to_string(),
-                    timeline_name: state.current_timeline.clone().unwrap_or_default(),
-                    clip_name: format!("clip_{}", state.timeline_items.item_counter),
-                    ..Default::default()
-                }
-            });
-
-        // Set transform property
-        match property_name {
-            "Pan" => timeline_item.transform.pan = property_value,
-            "Tilt" => timeline_item.transform.tilt = property_value,
-            "ZoomX" => timeline_item.transform.zoom_x = property_value,
-            "ZoomY" => timeline_item.transform.zoom_y = property_value,
-            "Rotation" => timeline_item.transform.rotation = property_value,
-            "AnchorPointX" => timeline_item.transform.anchor_point_x = property_value,
-            "AnchorPointY" => timeline_item.transform.anchor_point_y = property_value,
-            "Pitch" => timeline_item.transform.pitch = property_value,
-            "Yaw" => timeline_item.transform.yaw = property_value,
-            _ => unreachable!(),
-        }
+        let property = item_properties::lookup(property_name)
+            .filter(|property| property.category == Category::Transform)
+            .ok_or_else(|| {
+                ResolveError::invalid_parameter("property_name", "invalid transform property")
+            })?;
+        let property_value = &args["property_value"];
+        Self::set_item_properties(
+            state,
+            timeline_item_id,
+            &[("property_value", property, property_value)],
+        )?;
 
         Ok(serde_json::json!({
-            "result": format!("Set {} to {} for timeline item '{}'", property_name, property_value, timeline_item_id),
+            "result": format!("Set {} to {} for timeline item '{}'", property.key, property_value, timeline_item_id),
             "timeline_item_id": timeline_item_id,
-            "property_name": property_name,
+            "property_name": property.key,
             "property_value": property_value,
             "operation_id": Uuid::new_v4().to_string()
         }))
@@ -2178,7 +3406,7 @@ except Exception as e:
 
     async fn set_timeline_item_crop(
         &self,
-        state: &mut ResolveState,
+        state: &mut StateView<'_>,
         args: Value,
     ) -> ResolveResult<Value> {
         let timeline_item_id = args["timeline_item_id"].as_str().ok_or_else(|| {
@@ -2187,53 +3415,22 @@ except Exception as e:
         let crop_type = args["crop_type"]
             .as_str()
             .ok_or_else(|| ResolveError::invalid_parameter("crop_type", "required string"))?;
-        let crop_value = args["crop_value"]
-            .as_f64()
-            .ok_or_else(|| ResolveError::invalid_parameter("crop_value", "required number"))?;
-
-        // Validate crop type and value
-        let valid_crop_types = vec!["Left", "Right", "Top", "Bottom"];
-        if !valid_crop_types.contains(&crop_type) {
-            return Err(ResolveError::invalid_parameter(
-                "crop_type",
-                "must be Left, Right, Top, or Bottom",
-            ));
-        }
-        if crop_value < 0.0 || crop_value > 1.0 {
-            return Err(ResolveError::invalid_parameter(
-                "crop_value",
-                "must be between 0.0 and 1.0",
-            ));
-        }
-
-        // Get or create timeline item
-        let timeline_item = state
-            .timeline_items
-            .items
-            .entry(timeline_item_id.to_string())
-            .or_insert_with(|| {
-                state.timeline_items.item_counter += 1;
-                TimelineItemState {
-                    id: timeline_item_id.to_string(),
-                    timeline_name: state.current_timeline.clone().unwrap_or_default(),
-                    clip_name: format!("clip_{}", state.timeline_items.item_counter),
-                    ..Default::default()
-                }
-            });
-
-        // Set crop property
-        match crop_type {
-            "Left" => timeline_item.crop.left = crop_value,
-            "Right" => timeline_item.crop.right = crop_value,
-            "Top" => timeline_item.crop.top = crop_value,
-            "Bottom" => timeline_item.crop.bottom = crop_value,
-            _ => unreachable!(),
-        }
+        let property = item_properties::lookup(crop_type)
+            .filter(|property| property.category == Category::Crop)
+            .ok_or_else(|| {
+                ResolveError::invalid_parameter("crop_type", "must be Left, Right, Top, or Bottom")
+            })?;
+        let crop_value = &args["crop_value"];
+        Self::set_item_properties(
+            state,
+            timeline_item_id,
+            &[("crop_value", property, crop_value)],
+        )?;
 
         Ok(serde_json::json!({
-            "result": format!("Set {} crop to {} for timeline item '{}'", crop_type, crop_value, timeline_item_id),
+            "result": format!("Set {} crop to {} for timeline item '{}'", property.key, crop_value, timeline_item_id),
             "timeline_item_id": timeline_item_id,
-            "crop_type": crop_type,
+            "crop_type": property.key,
             "crop_value": crop_value,
             "operation_id": Uuid::new_v4().to_string()
         }))
@@ -2241,79 +3438,17 @@ except Exception as e:
 
     async fn set_timeline_item_composite(
         &self,
-        state: &mut ResolveState,
+        state: &mut StateView<'_>,
         args: Value,
     ) -> ResolveResult<Value> {
         let timeline_item_id = args["timeline_item_id"].as_str().ok_or_else(|| {
             ResolveError::invalid_parameter("timeline_item_id", "required string")
         })?;
-        let composite_mode = args["composite_mode"].as_str();
-        let opacity = args["opacity"].as_f64();
-
-        // Validate composite mode if provided
-        if let Some(mode) = composite_mode {
-            let valid_modes = vec![
-                "Normal",
-                "Add",
-                "Multiply",
-                "Screen",
-                "Overlay",
-                "SoftLight",
-                "HardLight",
-                "ColorDodge",
-                "ColorBurn",
-                "Darken",
-                "Lighten",
-                "Difference",
-                "Exclusion",
-            ];
-            if !valid_modes.contains(&mode) {
-                return Err(ResolveError::invalid_parameter(
-                    "composite_mode",
-                    "invalid composite mode",
-                ));
-            }
-        }
-
-        // Validate opacity if provided
-        if let Some(opacity_val) = opacity {
-            if opacity_val < 0.0 || opacity_val > 1.0 {
-                return Err(ResolveError::invalid_parameter(
-                    "opacity",
-                    "must be between 0.0 and 1.0",
-                ));
-            }
-        }
-
-        // Get or create timeline item
-        let timeline_item = state
-            .timeline_items
-            .items
-            .entry(timeline_item_id.to_string())
-            .or_insert_with(|| {
-                state.timeline_items.item_counter += 1;
-                TimelineItemState {
-                    id: timeline_item_id.to_string(),
-                    timeline_name: state.current_timeline.clone().unwrap_or_default(),
-                    clip_name: format!("clip_{}", state.timeline_items.item_counter),
-                    composite: CompositeProperties {
-                        mode: "Normal".to_string(),
-                        opacity: 1.0,
-                    },
-                    ..Default::default()
-                }
-            });
-
-        // Set composite properties
-        let mut result_parts = Vec::new();
-        if let Some(mode) = composite_mode {
-            timeline_item.composite.mode = mode.to_string();
-            result_parts.push(format!("composite mode to {}", mode));
-        }
-        if let Some(opacity_val) = opacity {
-            timeline_item.composite.opacity = opacity_val;
-            result_parts.push(format!("opacity to {}", opacity_val));
-        }
+        let changes = item_properties::changes_from_args(
+            &args,
+            &[("composite_mode", "CompositeMode"), ("opacity", "Opacity")],
+        )?;
+        let result_parts = Self::set_item_properties(state, timeline_item_id, &changes)?;
 
         let result_msg = if result_parts.is_empty() {
             "No composite properties changed".to_string()
@@ -2328,73 +3463,25 @@ except Exception as e:
         Ok(serde_json::json!({
             "result": result_msg,
             "timeline_item_id": timeline_item_id,
-            "composite_mode": composite_mode,
-            "opacity": opacity,
+            "composite_mode": args["composite_mode"],
+            "opacity": args["opacity"],
             "operation_id": Uuid::new_v4().to_string()
         }))
     }
 
     async fn set_timeline_item_retime(
         &self,
-        state: &mut ResolveState,
+        state: &mut StateView<'_>,
         args: Value,
     ) -> ResolveResult<Value> {
         let timeline_item_id = args["timeline_item_id"].as_str().ok_or_else(|| {
             ResolveError::invalid_parameter("timeline_item_id", "required string")
         })?;
-        let speed = args["speed"].as_f64();
-        let process = args["process"].as_str();
-
-        // Validate speed if provided
-        if let Some(speed_val) = speed {
-            if speed_val <= 0.0 || speed_val > 10.0 {
-                return Err(ResolveError::invalid_parameter(
-                    "speed",
-                    "must be between 0.0 and 10.0",
-                ));
-            }
-        }
-
-        // Validate process if provided
-        if let Some(process_str) = process {
-            let valid_processes = vec!["NearestFrame", "FrameBlend", "OpticalFlow"];
-            if !valid_processes.contains(&process_str) {
-                return Err(ResolveError::invalid_parameter(
-                    "process",
-                    "must be NearestFrame, FrameBlend, or OpticalFlow",
-                ));
-            }
-        }
-
-        // Get or create timeline item
-        let timeline_item = state
-            .timeline_items
-            .items
-            .entry(timeline_item_id.to_string())
-            .or_insert_with(|| {
-                state.timeline_items.item_counter += 1;
-                TimelineItemState {
-                    id: timeline_item_id.to_string(),
-                    timeline_name: state.current_timeline.clone().unwrap_or_default(),
-                    clip_name: format!("clip_{}", state.timeline_items.item_counter),
-                    retime: RetimeProperties {
-                        speed: 1.0,
-                        process: "NearestFrame".to_string(),
-                    },
-                    ..Default::default()
-                }
-            });
-
-        // Set retime properties
-        let mut result_parts = Vec::new();
-        if let Some(speed_val) = speed {
-            timeline_item.retime.speed = speed_val;
-            result_parts.push(format!("speed to {}x", speed_val));
-        }
-        if let Some(process_str) = process {
-            timeline_item.retime.process = process_str.to_string();
-            result_parts.push(format!("process to {}", process_str));
-        }
+        let changes = item_properties::changes_from_args(
+            &args,
+            &[("speed", "Speed"), ("process", "RetimeProcess")],
+        )?;
+        let result_parts = Self::set_item_properties(state, timeline_item_id, &changes)?;
 
         let result_msg = if result_parts.is_empty() {
             "No retime properties changed".to_string()
@@ -2409,79 +3496,90 @@ except Exception as e:
         Ok(serde_json::json!({
             "result": result_msg,
             "timeline_item_id": timeline_item_id,
-            "speed": speed,
-            "process": process,
+            "speed": args["speed"],
+            "process": args["process"],
             "operation_id": Uuid::new_v4().to_string()
         }))
     }
 
-    async fn set_timeline_item_stabilization(
+    async fn set_item_output_sizing(
         &self,
-        state: &mut ResolveState,
+        state: &mut StateView<'_>,
         args: Value,
     ) -> ResolveResult<Value> {
         let timeline_item_id = args["timeline_item_id"].as_str().ok_or_else(|| {
             ResolveError::invalid_parameter("timeline_item_id", "required string")
         })?;
-        let enabled = args["enabled"].as_bool();
-        let method = args["method"].as_str();
-        let strength = args["strength"].as_f64();
-
-        // Validate method if provided
-        if let Some(method_str) = method {
-            let valid_methods = vec!["Perspective", "Similarity", "Translation"];
-            if !valid_methods.contains(&method_str) {
+        let changes = item_properties::changes_from_args(
+            &args,
+            &[
+                ("scaling", "Scaling"),
+                ("super_scale", "SuperScale"),
+                ("super_scale_factor", "SuperScaleFactor"),
+                ("lens_correction", "LensCorrection"),
+            ],
+        )?;
+
+        // A Super Scale factor only applies while Super Scale is on
+        if !args["super_scale_factor"].is_null() {
+            let super_scale = item_properties::require("super_scale", "SuperScale")?;
+            let enabled = match args["super_scale"].as_bool() {
+                Some(enabled) => enabled,
+                None => state
+                    .timeline_items
+                    .items
+                    .get(timeline_item_id)
+                    .is_some_and(|item| {
+                        item.properties.get(super_scale) == PropertyValue::Bool(true)
+                    }),
+            };
+            if !enabled {
                 return Err(ResolveError::invalid_parameter(
-                    "method",
-                    "must be Perspective, Similarity, or Translation",
+                    "super_scale_factor",
+                    "Super Scale is off; set super_scale to true as well",
                 ));
             }
         }
+        let result_parts = Self::set_item_properties(state, timeline_item_id, &changes)?;
 
-        // Validate strength if provided
-        if let Some(strength_val) = strength {
-            if strength_val < 0.0 || strength_val > 1.0 {
-                return Err(ResolveError::invalid_parameter(
-                    "strength",
-                    "must be between 0.0 and 1.0",
-                ));
-            }
-        }
+        let result_msg = if result_parts.is_empty() {
+            "No sizing properties changed".to_string()
+        } else {
+            format!(
+                "Set {} for timeline item '{}'",
+                result_parts.join(" and "),
+                timeline_item_id
+            )
+        };
 
-        // Get or create timeline item
-        let timeline_item = state
-            .timeline_items
-            .items
-            .entry(timeline_item_id.to_string())
-            .or_insert_with(|| {
-                state.timeline_items.item_counter += 1;
-                TimelineItemState {
-                    id: timeline_item_id.to_string(),
-                    timeline_name: state.current_timeline.clone().unwrap_or_default(),
-                    clip_name: format!("clip_{}", state.timeline_items.item_counter),
-                    stabilization: StabilizationProperties {
-                        enabled: false,
-                        method: "Perspective".to_string(),
-                        strength: 0.5,
-                    },
-                    ..Default::default()
-                }
-            });
+        Ok(serde_json::json!({
+            "result": result_msg,
+            "timeline_item_id": timeline_item_id,
+            "scaling": args["scaling"],
+            "super_scale": args["super_scale"],
+            "super_scale_factor": args["super_scale_factor"],
+            "lens_correction": args["lens_correction"],
+            "operation_id": Uuid::new_v4().to_string()
+        }))
+    }
 
-        // Set stabilization properties
-        let mut result_parts = Vec::new();
-        if let Some(enabled_val) = enabled {
-            timeline_item.stabilization.enabled = enabled_val;
-            result_parts.push(format!("enabled to {}", enabled_val));
-        }
-        if let Some(method_str) = method {
-            timeline_item.stabilization.method = method_str.to_string();
-            result_parts.push(format!("method to {}", method_str));
-        }
-        if let Some(strength_val) = strength {
-            timeline_item.stabilization.strength = strength_val;
-            result_parts.push(format!("strength to {}", strength_val));
-        }
+    async fn set_timeline_item_stabilization(
+        &self,
+        state: &mut StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        let timeline_item_id = args["timeline_item_id"].as_str().ok_or_else(|| {
+            ResolveError::invalid_parameter("timeline_item_id", "required string")
+        })?;
+        let changes = item_properties::changes_from_args(
+            &args,
+            &[
+                ("enabled", "StabilizationEnabled"),
+                ("method", "StabilizationMethod"),
+                ("strength", "Strength"),
+            ],
+        )?;
+        let result_parts = Self::set_item_properties(state, timeline_item_id, &changes)?;
 
         let result_msg = if result_parts.is_empty() {
             "No stabilization properties changed".to_string()
@@ -2496,79 +3594,30 @@ except Exception as e:
         Ok(serde_json::json!({
             "result": result_msg,
             "timeline_item_id": timeline_item_id,
-            "enabled": enabled,
-            "method": method,
-            "strength": strength,
+            "enabled": args["enabled"],
+            "method": args["method"],
+            "strength": args["strength"],
             "operation_id": Uuid::new_v4().to_string()
         }))
     }
 
     async fn set_timeline_item_audio(
         &self,
-        state: &mut ResolveState,
+        state: &mut StateView<'_>,
         args: Value,
     ) -> ResolveResult<Value> {
         let timeline_item_id = args["timeline_item_id"].as_str().ok_or_else(|| {
             ResolveError::invalid_parameter("timeline_item_id", "required string")
         })?;
-        let volume = args["volume"].as_f64();
-        let pan = args["pan"].as_f64();
-        let eq_enabled = args["eq_enabled"].as_bool();
-
-        // Validate volume if provided
-        if let Some(volume_val) = volume {
-            if volume_val < 0.0 || volume_val > 2.0 {
-                return Err(ResolveError::invalid_parameter(
-                    "volume",
-                    "must be between 0.0 and 2.0",
-                ));
-            }
-        }
-
-        // Validate pan if provided
-        if let Some(pan_val) = pan {
-            if pan_val < -1.0 || pan_val > 1.0 {
-                return Err(ResolveError::invalid_parameter(
-                    "pan",
-                    "must be between -1.0 and 1.0",
-                ));
-            }
-        }
-
-        // Get or create timeline item
-        let timeline_item = state
-            .timeline_items
-            .items
-            .entry(timeline_item_id.to_string())
-            .or_insert_with(|| {
-                state.timeline_items.item_counter += 1;
-                TimelineItemState {
-                    id: timeline_item_id.to_string(),
-                    timeline_name: state.current_timeline.clone().unwrap_or_default(),
-                    clip_name: format!("clip_{}", state.timeline_items.item_counter),
-                    audio: AudioProperties {
-                        volume: 1.0,
-                        pan: 0.0,
-                        eq_enabled: false,
-                    },
-                    ..Default::default()
-                }
-            });
-
-        // Set audio properties
-        let mut result_parts = Vec::new();
-        if let Some(volume_val) = volume {
-            timeline_item.audio.volume = volume_val;
-            result_parts.push(format!("volume to {}", volume_val));
-        }
-        if let Some(pan_val) = pan {
-            timeline_item.audio.pan = pan_val;
-            result_parts.push(format!("pan to {}", pan_val));
-        }
-        if let Some(eq_val) = eq_enabled {
-            timeline_item.audio.eq_enabled = eq_val;
-            result_parts.push(format!("EQ enabled to {}", eq_val));
-        }
+        let changes = item_properties::changes_from_args(
+            &args,
+            &[
+                ("volume", "Volume"),
+                ("pan", "AudioPan"),
+                ("eq_enabled", "AudioEQEnabled"),
+            ],
+        )?;
+        let result_parts = Self::set_item_properties(state, timeline_item_id, &changes)?;
 
         let result_msg = if result_parts.is_empty() {
             "No audio properties changed".to_string()
@@ -2583,16 +3632,16 @@ except Exception as e:
         Ok(serde_json::json!({
             "result": result_msg,
             "timeline_item_id": timeline_item_id,
-            "volume": volume,
-            "pan": pan,
-            "eq_enabled": eq_enabled,
+            "volume": args["volume"],
+            "pan": args["pan"],
+            "eq_enabled": args["eq_enabled"],
             "operation_id": Uuid::new_v4().to_string()
         }))
     }
 
     async fn get_timeline_item_properties(
         &self,
-        state: &mut ResolveState,
+        state: &StateView<'_>,
         args: Value,
     ) -> ResolveResult<Value> {
         let timeline_item_id = args["timeline_item_id"].as_str().ok_or_else(|| {
@@ -2613,56 +3662,29 @@ except Exception as e:
             "timeline_item_id": timeline_item_id,
             "timeline_name": timeline_item.timeline_name,
             "clip_name": timeline_item.clip_name,
-            "properties": {
-                "transform": {
-                    "pan": timeline_item.transform.pan,
-                    "tilt": timeline_item.transform.tilt,
-                    "zoom_x": timeline_item.transform.zoom_x,
-                    "zoom_y": timeline_item.transform.zoom_y,
-                    "rotation": timeline_item.transform.rotation,
-                    "anchor_point_x": timeline_item.transform.anchor_point_x,
-                    "anchor_point_y": timeline_item.transform.anchor_point_y,
-                    "pitch": timeline_item.transform.pitch,
-                    "yaw": timeline_item.transform.yaw
-                },
-                "crop": {
-                    "left": timeline_item.crop.left,
-                    "right": timeline_item.crop.right,
-                    "top": timeline_item.crop.top,
-                    "bottom": timeline_item.crop.bottom
-                },
-                "composite": {
-                    "mode": timeline_item.composite.mode,
-                    "opacity": timeline_item.composite.opacity
-                },
-                "retime": {
-                    "speed": timeline_item.retime.speed,
-                    "process": timeline_item.retime.process
-                },
-                "stabilization": {
-                    "enabled": timeline_item.stabilization.enabled,
-                    "method": timeline_item.stabilization.method,
-                    "strength": timeline_item.stabilization.strength
-                },
-                "audio": {
-                    "volume": timeline_item.audio.volume,
-                    "pan": timeline_item.audio.pan,
-                    "eq_enabled": timeline_item.audio.eq_enabled
-                }
-            },
+            "properties": timeline_item.properties.by_category(),
             "operation_id": Uuid::new_v4().to_string()
         }))
     }
 
     async fn reset_timeline_item_properties(
         &self,
-        state: &mut ResolveState,
+        state: &mut StateView<'_>,
         args: Value,
     ) -> ResolveResult<Value> {
         let timeline_item_id = args["timeline_item_id"].as_str().ok_or_else(|| {
             ResolveError::invalid_parameter("timeline_item_id", "required string")
         })?;
         let property_type = args["property_type"].as_str();
+        let category = match property_type {
+            Some(name) => Some(Category::parse(name).ok_or_else(|| {
+                ResolveError::invalid_parameter(
+                    "property_type",
+                    "must be transform, crop, composite, retime, sizing, stabilization, or audio",
+                )
+            })?),
+            None => None,
+        };
 
         // Get timeline item
         let timeline_item = state
@@ -2673,75 +3695,11 @@ except Exception as e:
                 ResolveError::invalid_parameter("timeline_item_id", "timeline item not found")
             })?;
 
-        let mut reset_parts = Vec::new();
-
         // Reset specific property type or all if not specified
-        match property_type {
-            Some("transform") => {
-                timeline_item.transform = TransformProperties::default();
-                reset_parts.push("transform");
-            }
-            Some("crop") => {
-                timeline_item.crop = CropProperties::default();
-                reset_parts.push("crop");
-            }
-            Some("composite") => {
-                timeline_item.composite = CompositeProperties {
-                    mode: "Normal".to_string(),
-                    opacity: 1.0,
-                };
-                reset_parts.push("composite");
-            }
-            Some("retime") => {
-                timeline_item.retime = RetimeProperties {
-                    speed: 1.0,
-                    process: "NearestFrame".to_string(),
-                };
-                reset_parts.push("retime");
-            }
-            Some("stabilization") => {
-                timeline_item.stabilization = StabilizationProperties::default();
-                reset_parts.push("stabilization");
-            }
-            Some("audio") => {
-                timeline_item.audio = AudioProperties {
-                    volume: 1.0,
-                    pan: 0.0,
-                    eq_enabled: false,
-                };
-                reset_parts.push("audio");
-            }
-            Some(_invalid_type) => {
-                return Err(ResolveError::invalid_parameter(
-                    "property_type",
-                    "must be transform, crop, composite, retime, stabilization, or audio",
-                ));
-            }
-            None => {
-                // Reset all properties
-                timeline_item.transform = TransformProperties::default();
-                timeline_item.crop = CropProperties::default();
-                timeline_item.composite = CompositeProperties {
-                    mode: "Normal".to_string(),
-                    opacity: 1.0,
-                };
-                timeline_item.retime = RetimeProperties {
-                    speed: 1.0,
-                    process: "NearestFrame".to_string(),
-                };
-                timeline_item.stabilization = StabilizationProperties::default();
-                timeline_item.audio = AudioProperties {
-                    volume: 1.0,
-                    pan: 0.0,
-                    eq_enabled: false,
-                };
-                reset_parts.push("all properties");
-            }
-        }
-
+        timeline_item.properties.reset(category);
         let result_msg = format!(
             "Reset {} for timeline item '{}'",
-            reset_parts.join(", "),
+            category.map_or("all properties", Category::name),
             timeline_item_id
         );
 
@@ -2755,7 +3713,7 @@ except Exception as e:
 
     // ==================== KEYFRAME ANIMATION OPERATIONS (Phase 4 Week 2) ====================
 
-    async fn add_keyframe(&self, state: &mut ResolveState, args: Value) -> ResolveResult<Value> {
+    async fn add_keyframe(&self, state: &mut StateView<'_>, args: Value) -> ResolveResult<Value> {
         let timeline_item_id = args["timeline_item_id"].as_str().ok_or_else(|| {
             ResolveError::invalid_parameter("timeline_item_id", "required string")
         })?;
@@ -2770,33 +3728,16 @@ except Exception as e:
             .as_f64()
             .ok_or_else(|| ResolveError::invalid_parameter("value", "required number"))?;
 
-        // Validate property name
-        let valid_properties = vec![
-            "Pan",
-            "Tilt",
-            "ZoomX",
-            "ZoomY",
-            "Rotation",
-            "AnchorPointX",
-            "AnchorPointY",
-            "Pitch",
-            "Yaw",
-            "Left",
-            "Right",
-            "Top",
-            "Bottom",
-            "Opacity",
-            "Speed",
-            "Strength",
-            "Volume",
-            "AudioPan",
-        ];
-        if !valid_properties.contains(&property_name) {
+        // Only numeric properties with keyframe support can be animated
+        let property = item_properties::require("property_name", property_name)?;
+        if !property.keyframable {
             return Err(ResolveError::invalid_parameter(
                 "property_name",
-                "must be a valid timeline item property",
+                format!("{} cannot be keyframed", property.key),
             ));
         }
+        property.parse("value", &args["value"])?;
+        let property_name = property.key;
 
         // Validate frame position
         if frame < 0 {
@@ -2806,6 +3747,20 @@ except Exception as e:
             ));
         }
 
+        let existing_keyframes = state
+            .keyframe_state
+            .timeline_item_keyframes
+            .get(timeline_item_id)
+            .map_or(0, |item| {
+                item.property_keyframes.values().map(Vec::len).sum()
+            });
+        ensure_capacity(
+            &format!("keyframes on timeline item '{}'", timeline_item_id),
+            existing_keyframes,
+            1,
+            self.config.limits.max_keyframes_per_item,
+        )?;
+
         // Generate keyframe ID
         state.keyframe_state.keyframe_counter += 1;
         let keyframe_id = state.keyframe_state.keyframe_counter;
@@ -2855,18 +3810,27 @@ except Exception as e:
         }))
     }
 
-    async fn modify_keyframe(&self, state: &mut ResolveState, args: Value) -> ResolveResult<Value> {
+    async fn modify_keyframe(
+        &self,
+        state: &mut StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
         let timeline_item_id = args["timeline_item_id"].as_str().ok_or_else(|| {
             ResolveError::invalid_parameter("timeline_item_id", "required string")
         })?;
         let property_name = args["property_name"]
             .as_str()
+            .map(item_properties::canonical_key)
             .ok_or_else(|| ResolveError::invalid_parameter("property_name", "required string"))?;
         let frame = args["frame"]
             .as_i64()
             .ok_or_else(|| ResolveError::invalid_parameter("frame", "required integer"))?
             as i32;
         let new_value = args["new_value"].as_f64();
+        if !args["new_value"].is_null() {
+            item_properties::require("property_name", property_name)?
+                .parse("new_value", &args["new_value"])?;
+        }
         let new_frame = args["new_frame"].as_i64().map(|f| f as i32);
 
         // Get timeline item keyframes
@@ -2944,12 +3908,17 @@ except Exception as e:
         }))
     }
 
-    async fn delete_keyframe(&self, state: &mut ResolveState, args: Value) -> ResolveResult<Value> {
+    async fn delete_keyframe(
+        &self,
+        state: &mut StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
         let timeline_item_id = args["timeline_item_id"].as_str().ok_or_else(|| {
             ResolveError::invalid_parameter("timeline_item_id", "required string")
         })?;
         let property_name = args["property_name"]
             .as_str()
+            .map(item_properties::canonical_key)
             .ok_or_else(|| ResolveError::invalid_parameter("property_name", "required string"))?;
         let frame = args["frame"]
             .as_i64()
@@ -2999,7 +3968,7 @@ except Exception as e:
 
     async fn set_keyframe_interpolation(
         &self,
-        state: &mut ResolveState,
+        state: &mut StateView<'_>,
         args: Value,
     ) -> ResolveResult<Value> {
         let timeline_item_id = args["timeline_item_id"].as_str().ok_or_else(|| {
@@ -3007,6 +3976,7 @@ except Exception as e:
         })?;
         let property_name = args["property_name"]
             .as_str()
+            .map(item_properties::canonical_key)
             .ok_or_else(|| ResolveError::invalid_parameter("property_name", "required string"))?;
         let frame = args["frame"]
             .as_i64()
@@ -3074,7 +4044,7 @@ except Exception as e:
 
     async fn enable_keyframes(
         &self,
-        state: &mut ResolveState,
+        state: &mut StateView<'_>,
         args: Value,
     ) -> ResolveResult<Value> {
         let timeline_item_id = args["timeline_item_id"].as_str().ok_or_else(|| {
@@ -3123,11 +4093,13 @@ except Exception as e:
         }))
     }
 
-    async fn get_keyframes(&self, state: &mut ResolveState, args: Value) -> ResolveResult<Value> {
+    async fn get_keyframes(&self, state: &StateView<'_>, args: Value) -> ResolveResult<Value> {
         let timeline_item_id = args["timeline_item_id"].as_str().ok_or_else(|| {
             ResolveError::invalid_parameter("timeline_item_id", "required string")
         })?;
-        let property_name = args["property_name"].as_str();
+        let property_name = args["property_name"]
+            .as_str()
+            .map(item_properties::canonical_key);
 
         // Get timeline item keyframes
         let timeline_item_keyframes = state
@@ -3212,19 +4184,37 @@ except Exception as e:
 
     async fn add_to_render_queue(
         &self,
-        state: &mut ResolveState,
+        state: &mut StateView<'_>,
         args: Value,
     ) -> ResolveResult<Value> {
         let preset_name = args["preset_name"]
             .as_str()
             .ok_or_else(|| ResolveError::invalid_parameter("preset_name", "required string"))?;
-        let timeline_name = args["timeline_name"].as_str().unwrap_or_else(|| {
-            state
-                .current_timeline
-                .as_ref()
-                .map(|s| s.as_str())
-                .unwrap_or("Timeline 1")
-        });
+        let timeline_name = args["timeline_name"]
+            .as_str()
+            .map(str::to_string)
+            .unwrap_or_else(|| {
+                state
+                    .current_timeline
+                    .clone()
+                    .unwrap_or_else(|| "Timeline 1".to_string())
+            });
+        self.queue_timeline_render(state, &timeline_name, preset_name, None, None, &args)
+    }
+
+    /// Queue a render of a timeline with a preset. Track and subtitle options
+    /// come from `args`; the output path, given without the format's
+    /// extension, defaults to one under /tmp/renders. `frame_range` limits
+    /// the job to record frames from the timeline start, end exclusive.
+    fn queue_timeline_render(
+        &self,
+        state: &mut StateView<'_>,
+        timeline_name: &str,
+        preset_name: &str,
+        output_stem: Option<String>,
+        frame_range: Option<(i64, i64)>,
+        args: &Value,
+    ) -> ResolveResult<Value> {
         let use_in_out_range = args["use_in_out_range"].as_bool().unwrap_or(false);
 
         // Validate timeline exists
@@ -3245,6 +4235,12 @@ except Exception as e:
                 quality: RenderQuality::High,
                 audio_codec: "AAC".to_string(),
                 audio_bitrate: 192,
+                bit_depth: 8,
+                export_alpha: false,
+                export_video: true,
+                audio_channel_layout: Some(render_formats::DEFAULT_CHANNEL_LAYOUT.to_string()),
+                embed_chapters: false,
+                embed_metadata: false,
                 created_at: chrono::Utc::now(),
             };
             state
@@ -3254,16 +4250,61 @@ except Exception as e:
         }
 
         // Validate preset exists
-        if !state.render_state.render_presets.contains_key(preset_name) {
-            return Err(ResolveError::PresetNotFound {
+        let preset = state
+            .render_state
+            .render_presets
+            .get(preset_name)
+            .ok_or_else(|| ResolveError::PresetNotFound {
                 name: preset_name.to_string(),
-            });
+            })?;
+
+        // Tracks default to the preset's and are checked against its format
+        let format = render_formats::require_format("preset_name", &preset.format)?;
+        let export_video = args["export_video"]
+            .as_bool()
+            .unwrap_or(preset.export_video);
+        let export_audio = args["export_audio"]
+            .as_bool()
+            .unwrap_or(preset.audio_channel_layout.is_some());
+        let audio_channel_layout = if export_audio {
+            let audio_codec = format.require_audio_codec("export_audio", &preset.audio_codec)?;
+            let layout = args["audio_channel_layout"]
+                .as_str()
+                .or(preset.audio_channel_layout.as_deref())
+                .unwrap_or(render_formats::DEFAULT_CHANNEL_LAYOUT);
+            Some(audio_codec.require_channel_layout(layout)?.to_string())
+        } else {
+            None
+        };
+        if !export_video && audio_channel_layout.is_none() {
+            return Err(ResolveError::invalid_parameter(
+                "export_video",
+                "a render job needs video, audio or both",
+            ));
         }
+        let subtitles = subtitles::SubtitleExport::from_args(
+            &state.timelines[timeline_name],
+            args,
+            export_video,
+        )?;
+
+        // The job renders with the timeline's framing as it is now
+        let framing = state.timelines[timeline_name].output.clone();
+        let output = framing.to_json(preset.resolution);
+        let embedding = self.render_embedding(
+            state,
+            timeline_name,
+            frame_range,
+            preset.embed_chapters,
+            preset.embed_metadata,
+        )?;
 
         // Generate job ID and output path
         state.render_state.job_counter += 1;
         let job_id = format!("job_{}", state.render_state.job_counter);
-        let output_path = format!("/tmp/renders/{}_{}.mp4", timeline_name, job_id);
+        let output_stem =
+            output_stem.unwrap_or_else(|| format!("/tmp/renders/{}_{}", timeline_name, job_id));
+        let output_path = format!("{}.{}", output_stem, format.extension);
 
         // Create render job
         let render_job = RenderJob {
@@ -3272,6 +4313,12 @@ except Exception as e:
             preset_name: preset_name.to_string(),
             output_path: output_path.clone(),
             use_in_out_range,
+            frame_range,
+            export_video,
+            audio_channel_layout: audio_channel_layout.clone(),
+            subtitles: subtitles.clone(),
+            framing,
+            embedding: embedding.clone(),
             created_at: chrono::Utc::now(),
             status: RenderJobStatus::Queued,
         };
@@ -3286,12 +4333,19 @@ except Exception as e:
             "preset_name": preset_name,
             "output_path": output_path,
             "use_in_out_range": use_in_out_range,
+            "frame_range": frame_range,
+            "export_video": export_video,
+            "export_audio": export_audio,
+            "audio_channel_layout": audio_channel_layout,
+            "subtitles": subtitles.map(|subtitles| subtitles.to_json(&output_path)),
+            "output": output,
+            "embedding": embedding.as_ref().map(render_metadata::Embedding::to_json),
             "queue_position": state.render_state.render_queue.len(),
             "operation_id": Uuid::new_v4().to_string()
         }))
     }
 
-    async fn start_render(&self, state: &mut ResolveState, _args: Value) -> ResolveResult<Value> {
+    async fn start_render(&self, state: &mut StateView<'_>, _args: Value) -> ResolveResult<Value> {
         if state.render_state.render_queue.is_empty() {
             return Err(ResolveError::invalid_parameter(
                 "render_queue",
@@ -3299,33 +4353,7 @@ except Exception as e:
             ));
         }
 
-        let mut started_jobs = Vec::new();
-        let now = chrono::Utc::now();
-
-        // Process all queued jobs
-        for job in &mut state.render_state.render_queue {
-            if matches!(job.status, RenderJobStatus::Queued) {
-                job.status = RenderJobStatus::Rendering;
-
-                // Create render progress tracking
-                let progress = RenderProgress {
-                    job_id: job.id.clone(),
-                    progress_percent: 0.0,
-                    estimated_time_remaining: Some(std::time::Duration::from_secs(120)),
-                    current_frame: 0,
-                    total_frames: 1000, // Simulated frame count
-                    status_message: "Starting render...".to_string(),
-                    last_update: now,
-                };
-
-                state
-                    .render_state
-                    .active_renders
-                    .insert(job.id.clone(), progress);
-                started_jobs.push(job.id.clone());
-            }
-        }
-
+        let started_jobs = self.begin_renders(state);
         if started_jobs.is_empty() {
             return Err(ResolveError::invalid_parameter(
                 "render_queue",
@@ -3345,7 +4373,7 @@ except Exception as e:
 
     async fn clear_render_queue(
         &self,
-        state: &mut ResolveState,
+        state: &mut StateView<'_>,
         _args: Value,
     ) -> ResolveResult<Value> {
         let queue_size = state.render_state.render_queue.len();
@@ -3369,11 +4397,49 @@ except Exception as e:
         }))
     }
 
-    async fn get_render_status(
+    async fn delete_render_job(
         &self,
-        state: &mut ResolveState,
-        _args: Value,
+        state: &mut StateView<'_>,
+        args: Value,
     ) -> ResolveResult<Value> {
+        let job_id = args["job_id"]
+            .as_str()
+            .ok_or_else(|| ResolveError::invalid_parameter("job_id", "required string"))?;
+        let render_state = &mut *state.render_state;
+        let index = render_state
+            .render_queue
+            .iter()
+            .position(|job| job.id == job_id)
+            .ok_or_else(|| {
+                ResolveError::invalid_parameter(
+                    "job_id",
+                    format!("render job '{}' not found", job_id),
+                )
+            })?;
+        // Resolve refuses to delete a job while it renders
+        if matches!(
+            render_state.render_queue[index].status,
+            RenderJobStatus::Rendering
+        ) {
+            return Err(ResolveError::invalid_parameter(
+                "job_id",
+                format!("render job '{}' is rendering; stop rendering first", job_id),
+            ));
+        }
+        let job = render_state.render_queue.remove(index);
+        render_state.active_renders.remove(job_id);
+
+        Ok(serde_json::json!({
+            "result": format!("Deleted render job '{}'", job_id),
+            "job_id": job_id,
+            "timeline_name": job.timeline_name,
+            "preset_name": job.preset_name,
+            "remaining_jobs": render_state.render_queue.len(),
+            "operation_id": Uuid::new_v4().to_string()
+        }))
+    }
+
+    async fn get_render_status(&self, state: &StateView<'_>, _args: Value) -> ResolveResult<Value> {
         let queue_size = state.render_state.render_queue.len();
         let active_renders = state.render_state.active_renders.len();
         let completed_renders = state.render_state.render_history.len();
@@ -3390,6 +4456,26 @@ except Exception as e:
             }))
             .collect();
 
+        let completed_render_details: Vec<_> = state
+            .render_state
+            .render_history
+            .iter()
+            .map(|result| {
+                serde_json::json!({
+                    "job_id": result.job_id,
+                    "timeline_name": result.timeline_name,
+                    "preset_name": result.preset_name,
+                    "output_path": result.output_path,
+                    "status": format!("{:?}", result.status),
+                    "render_seconds": result.render_duration.as_secs_f64(),
+                    "completed_at": result.completed_at.to_rfc3339(),
+                    "error": result.error_message,
+                    "hooks": result.hooks,
+                    "embedding": result.embedding
+                })
+            })
+            .collect();
+
         // Collect queued job details
         let queued_job_details: Vec<_> = state
             .render_state
@@ -3402,7 +4488,12 @@ except Exception as e:
                     "timeline_name": job.timeline_name,
                     "preset_name": job.preset_name,
                     "output_path": job.output_path,
-                    "use_in_out_range": job.use_in_out_range
+                    "use_in_out_range": job.use_in_out_range,
+                    "export_video": job.export_video,
+                    "export_audio": job.audio_channel_layout.is_some(),
+                    "audio_channel_layout": job.audio_channel_layout,
+                    "subtitles": job.subtitles.as_ref().map(|subtitles| subtitles.to_json(&job.output_path)),
+                    "embedding": job.embedding.as_ref().map(render_metadata::Embedding::to_json)
                 })
             })
             .collect();
@@ -3414,11 +4505,12 @@ except Exception as e:
             "completed_renders": completed_renders,
             "queued_job_details": queued_job_details,
             "active_render_details": active_render_details,
+            "completed_render_details": completed_render_details,
             "operation_id": Uuid::new_v4().to_string()
         }))
     }
 
-    async fn export_project(&self, state: &mut ResolveState, args: Value) -> ResolveResult<Value> {
+    async fn export_project(&self, state: &StateView<'_>, args: Value) -> ResolveResult<Value> {
         let export_path = args["export_path"]
             .as_str()
             .ok_or_else(|| ResolveError::invalid_parameter("export_path", "required string"))?;
@@ -3476,7 +4568,7 @@ except Exception as e:
 
     async fn create_render_preset(
         &self,
-        state: &mut ResolveState,
+        state: &mut StateView<'_>,
         args: Value,
     ) -> ResolveResult<Value> {
         let preset_name = args["preset_name"]
@@ -3489,26 +4581,56 @@ except Exception as e:
             .as_str()
             .ok_or_else(|| ResolveError::invalid_parameter("codec", "required string"))?;
         let resolution = (
-            args["resolution_width"].as_i64().unwrap() as u32,
-            args["resolution_height"].as_i64().unwrap() as u32,
+            args["resolution_width"]
+                .as_u64()
+                .and_then(|width| u32::try_from(width).ok())
+                .ok_or_else(|| {
+                    ResolveError::invalid_parameter("resolution_width", "required integer")
+                })?,
+            args["resolution_height"]
+                .as_u64()
+                .and_then(|height| u32::try_from(height).ok())
+                .ok_or_else(|| {
+                    ResolveError::invalid_parameter("resolution_height", "required integer")
+                })?,
         );
-        let frame_rate = args["frame_rate"].as_f64().unwrap() as f32;
-        let quality = args["quality"].as_u64().unwrap() as u32;
+        let frame_rate = args["frame_rate"]
+            .as_f64()
+            .ok_or_else(|| ResolveError::invalid_parameter("frame_rate", "required number"))?
+            as f32;
+        let quality = args["quality"]
+            .as_u64()
+            .and_then(|quality| u32::try_from(quality).ok())
+            .ok_or_else(|| ResolveError::invalid_parameter("quality", "required integer"))?;
         let audio_codec = args["audio_codec"]
             .as_str()
             .ok_or_else(|| ResolveError::invalid_parameter("audio_codec", "required string"))?;
-        let audio_bitrate = args["audio_bitrate"].as_u64().unwrap() as u32;
-
-        // Validate format
-        let valid_formats = vec!["MP4", "MOV", "MXF"];
-        if !valid_formats.contains(&format) {
-            return Err(ResolveError::invalid_parameter("format", "invalid format"));
-        }
-
-        // Validate codec
-        let valid_codecs = vec!["H.264", "H.265", "ProRes"];
-        if !valid_codecs.contains(&codec) {
-            return Err(ResolveError::invalid_parameter("codec", "invalid codec"));
+        let audio_bitrate = args["audio_bitrate"]
+            .as_u64()
+            .and_then(|bitrate| u32::try_from(bitrate).ok())
+            .ok_or_else(|| ResolveError::invalid_parameter("audio_bitrate", "required integer"))?;
+        // Out-of-range depths saturate so the codec check rejects them
+        let bit_depth = args["bit_depth"]
+            .as_u64()
+            .map(|depth| u8::try_from(depth).unwrap_or(u8::MAX));
+        let export_alpha = args["export_alpha"].as_bool().unwrap_or(false);
+
+        // Validate format and codec against the render format catalog
+        let format = render_formats::require_format("format", format)?;
+        let codec = format.require_codec("codec", codec)?;
+        codec.check(bit_depth, export_alpha)?;
+        let bit_depth = bit_depth.unwrap_or_else(|| codec.default_bit_depth());
+
+        // Audio defaults on for formats that carry it
+        let export_video = args["export_video"].as_bool().unwrap_or(true);
+        let export_audio = args["export_audio"]
+            .as_bool()
+            .unwrap_or_else(|| format.has_audio());
+        if !export_video && !export_audio {
+            return Err(ResolveError::invalid_parameter(
+                "export_video",
+                "a render preset needs video, audio or both",
+            ));
         }
 
         // Validate resolution
@@ -3535,14 +4657,26 @@ except Exception as e:
             ));
         }
 
-        // Validate audio codec
-        let valid_audio_codecs = vec!["AAC", "ProRes"];
-        if !valid_audio_codecs.contains(&audio_codec) {
-            return Err(ResolveError::invalid_parameter(
-                "audio_codec",
-                "invalid audio codec",
-            ));
-        }
+        // Validate audio codec and channel layout
+        let audio = if export_audio {
+            let audio_codec = format.require_audio_codec("audio_codec", audio_codec)?;
+            let layout = args["audio_channel_layout"]
+                .as_str()
+                .unwrap_or(render_formats::DEFAULT_CHANNEL_LAYOUT);
+            Some((
+                audio_codec.name,
+                audio_codec.require_channel_layout(layout)?,
+            ))
+        } else {
+            None
+        };
+        let audio_channel_layout = audio.map(|(_, layout)| layout);
+        let audio_codec = audio.map(|(codec, _)| codec);
+
+        // Chapters and metadata need a container that carries them
+        let embed_chapters = args["embed_chapters"].as_bool().unwrap_or(false);
+        let embed_metadata = args["embed_metadata"].as_bool().unwrap_or(false);
+        render_metadata::check_format(format.name, embed_chapters, embed_metadata)?;
 
         // Validate audio bitrate
         if audio_bitrate < 64000 || audio_bitrate > 192000 {
@@ -3555,13 +4689,19 @@ except Exception as e:
         // Create new render preset
         let render_preset = RenderPreset {
             name: preset_name.to_string(),
-            format: format.to_string(),
-            codec: codec.to_string(),
+            format: format.name.to_string(),
+            codec: codec.name.to_string(),
             resolution,
             frame_rate,
             quality: RenderQuality::Custom(quality),
-            audio_codec: audio_codec.to_string(),
+            audio_codec: audio_codec.unwrap_or_default().to_string(),
             audio_bitrate,
+            bit_depth,
+            export_alpha,
+            export_video,
+            audio_channel_layout: audio_channel_layout.map(str::to_string),
+            embed_chapters,
+            embed_metadata,
             created_at: chrono::Utc::now(),
         };
 
@@ -3574,49 +4714,55 @@ except Exception as e:
         Ok(serde_json::json!({
             "result": format!("Created render preset '{}'", preset_name),
             "preset_name": preset_name,
-            "format": format,
-            "codec": codec,
+            "format": format.name,
+            "codec": codec.name,
             "resolution": format!("{}x{}", resolution.0, resolution.1),
             "frame_rate": frame_rate,
             "quality": quality,
             "audio_codec": audio_codec,
             "audio_bitrate": audio_bitrate,
+            "bit_depth": bit_depth,
+            "export_alpha": export_alpha,
+            "export_video": export_video,
+            "export_audio": export_audio,
+            "audio_channel_layout": audio_channel_layout,
+            "embed_chapters": embed_chapters,
+            "embed_metadata": embed_metadata,
             "operation_id": Uuid::new_v4().to_string()
         }))
     }
 
     // ---- Project Management Operations ----
-    async fn save_project(&self, state: &mut ResolveState, _args: Value) -> ResolveResult<Value> {
-        if state.current_project.is_none() {
+    async fn save_project(&self, state: &StateView<'_>, _args: Value) -> ResolveResult<Value> {
+        let Some(project_name) = state.current_project.as_ref() else {
             return Err(ResolveError::NotRunning);
-        }
-
-        let project_name = state.current_project.as_ref().unwrap();
+        };
 
         // Simulate save operation
         tokio::time::sleep(std::time::Duration::from_millis(200)).await;
 
         Ok(serde_json::json!({
             "result": format!("Saved project '{}'", project_name),
+            "project_name": project_name,
             "operation_id": Uuid::new_v4().to_string(),
             "save_time": chrono::Utc::now().to_rfc3339()
         }))
     }
 
-    async fn close_project(&self, state: &mut ResolveState, _args: Value) -> ResolveResult<Value> {
-        if state.current_project.is_none() {
+    async fn close_project(&self, state: &mut StateView<'_>, _args: Value) -> ResolveResult<Value> {
+        let Some(project_name) = state.current_project.take() else {
             return Err(ResolveError::NotRunning);
-        }
-
-        let project_name = state.current_project.take().unwrap();
+        };
 
         // Reset project state
-        state.current_timeline = None;
+        state.project_settings.clear();
+        *state.current_timeline = None;
         state.timelines.clear();
         state.media_pool.bins.clear();
         state.media_pool.clips.clear();
         state.color_state.current_clip = None;
         state.color_state.clip_grades.clear();
+        state.color_state.grade_history.clear();
         state.timeline_items.items.clear();
         state.keyframe_state.timeline_item_keyframes.clear();
         state.render_state.render_queue.clear();
@@ -3630,7 +4776,7 @@ except Exception as e:
 
     async fn set_project_setting(
         &self,
-        state: &mut ResolveState,
+        state: &mut StateView<'_>,
         args: Value,
     ) -> ResolveResult<Value> {
         if state.current_project.is_none() {
@@ -3641,6 +4787,9 @@ except Exception as e:
             .as_str()
             .ok_or_else(|| ResolveError::invalid_parameter("setting_name", "required string"))?;
         let setting_value = &args["setting_value"];
+        state
+            .project_settings
+            .insert(setting_name.to_string(), setting_value.clone());
 
         Ok(serde_json::json!({
             "result": format!("Set project setting '{}' to {:?}", setting_name, setting_value),
@@ -3653,22 +4802,24 @@ except Exception as e:
     // ---- Audio Transcription Operations ----
     async fn transcribe_audio(
         &self,
-        _state: &mut ResolveState,
+        state: &mut StateView<'_>,
         args: Value,
     ) -> ResolveResult<Value> {
         let clip_name = args["clip_name"]
             .as_str()
             .ok_or_else(|| ResolveError::invalid_parameter("clip_name", "required string"))?;
-        let language = args["language"].as_str().unwrap_or("en-US");
+        let language = self.transcription_language(&args)?;
 
         // Simulate transcription processing
         tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+        let word_count = Self::store_transcript(state, clip_name, language);
 
         Ok(serde_json::json!({
             "result": format!("Started transcription for clip '{}' in language '{}'", clip_name, language),
             "transcription_id": Uuid::new_v4().to_string(),
             "clip_name": clip_name,
             "language": language,
+            "word_count": word_count,
             "estimated_duration": "45s",
             "status": "processing"
         }))
@@ -3676,12 +4827,13 @@ except Exception as e:
 
     async fn clear_transcription(
         &self,
-        _state: &mut ResolveState,
+        state: &mut StateView<'_>,
         args: Value,
     ) -> ResolveResult<Value> {
         let clip_name = args["clip_name"]
             .as_str()
             .ok_or_else(|| ResolveError::invalid_parameter("clip_name", "parameter is required"))?;
+        Self::remove_transcript(state, clip_name);
 
         Ok(serde_json::json!({
             "result": format!("Cleared transcription for clip: {}", clip_name),
@@ -3691,24 +4843,27 @@ except Exception as e:
     }
 
     // ---- NEW: Extended Project Management Operations ----
-    async fn delete_media(&self, state: &mut ResolveState, args: Value) -> ResolveResult<Value> {
+    async fn delete_media(&self, state: &mut StateView<'_>, args: Value) -> ResolveResult<Value> {
         let clip_name = args["clip_name"]
             .as_str()
             .ok_or_else(|| ResolveError::invalid_parameter("clip_name", "parameter is required"))?;
 
         // Remove clip from media pool
-        state.media_pool.clips.remove(clip_name);
+        let trash = state.media_pool.clips.remove(clip_name).and_then(|clip| {
+            self.move_to_trash(state, trash::Trashed::Clip(clip_name.to_string(), clip))
+        });
 
         Ok(serde_json::json!({
             "result": format!("Deleted media clip: {}", clip_name),
             "clip_name": clip_name,
+            "trash": trash,
             "status": "success"
         }))
     }
 
     async fn move_media_to_bin(
         &self,
-        state: &mut ResolveState,
+        state: &mut StateView<'_>,
         args: Value,
     ) -> ResolveResult<Value> {
         let clip_name = args["clip_name"]
@@ -3731,7 +4886,7 @@ except Exception as e:
         }))
     }
 
-    async fn export_folder(&self, _state: &mut ResolveState, args: Value) -> ResolveResult<Value> {
+    async fn export_folder(&self, _state: &StateView<'_>, args: Value) -> ResolveResult<Value> {
         let folder_name = args["folder_name"].as_str().ok_or_else(|| {
             ResolveError::invalid_parameter("folder_name", "parameter is required")
         })?;
@@ -3751,30 +4906,50 @@ except Exception as e:
 
     async fn transcribe_folder_audio(
         &self,
-        _state: &mut ResolveState,
+        state: &mut StateView<'_>,
         args: Value,
     ) -> ResolveResult<Value> {
         let folder_name = args["folder_name"].as_str().ok_or_else(|| {
             ResolveError::invalid_parameter("folder_name", "parameter is required")
         })?;
-        let language = args["language"].as_str().unwrap_or("en-US");
+        let language = self.transcription_language(&args)?;
+        let clip_names = state
+            .media_pool
+            .bins
+            .get(folder_name)
+            .map(|bin| bin.clips.clone())
+            .unwrap_or_default();
+        let transcribed = clip_names
+            .iter()
+            .filter(|clip_name| Self::store_transcript(state, clip_name, language).is_some())
+            .count();
 
         Ok(serde_json::json!({
             "result": format!("Started transcription for all clips in folder '{}' using language '{}'", folder_name, language),
             "folder_name": folder_name,
             "language": language,
+            "transcribed_clips": transcribed,
             "status": "success"
         }))
     }
 
     async fn clear_folder_transcription(
         &self,
-        _state: &mut ResolveState,
+        state: &mut StateView<'_>,
         args: Value,
     ) -> ResolveResult<Value> {
         let folder_name = args["folder_name"].as_str().ok_or_else(|| {
             ResolveError::invalid_parameter("folder_name", "parameter is required")
         })?;
+        let clip_names = state
+            .media_pool
+            .bins
+            .get(folder_name)
+            .map(|bin| bin.clips.clone())
+            .unwrap_or_default();
+        for clip_name in &clip_names {
+            Self::remove_transcript(state, clip_name);
+        }
 
         Ok(serde_json::json!({
             "result": format!("Cleared transcriptions for all clips in folder '{}'", folder_name),
@@ -3784,7 +4959,7 @@ except Exception as e:
     }
 
     // ---- NEW: Cache and Optimization Operations ----
-    async fn set_cache_mode(&self, _state: &mut ResolveState, args: Value) -> ResolveResult<Value> {
+    async fn set_cache_mode(&self, _state: &StateView<'_>, args: Value) -> ResolveResult<Value> {
         let mode = args["mode"]
             .as_str()
             .ok_or_else(|| ResolveError::invalid_parameter("mode", "parameter is required"))?;
@@ -3805,7 +4980,7 @@ except Exception as e:
 
     async fn set_optimized_media_mode(
         &self,
-        _state: &mut ResolveState,
+        _state: &StateView<'_>,
         args: Value,
     ) -> ResolveResult<Value> {
         let mode = args["mode"]
@@ -3826,7 +5001,7 @@ except Exception as e:
         }))
     }
 
-    async fn set_proxy_mode(&self, _state: &mut ResolveState, args: Value) -> ResolveResult<Value> {
+    async fn set_proxy_mode(&self, _state: &StateView<'_>, args: Value) -> ResolveResult<Value> {
         let mode = args["mode"]
             .as_str()
             .ok_or_else(|| ResolveError::invalid_parameter("mode", "parameter is required"))?;
@@ -3845,11 +5020,7 @@ except Exception as e:
         }))
     }
 
-    async fn set_proxy_quality(
-        &self,
-        _state: &mut ResolveState,
-        args: Value,
-    ) -> ResolveResult<Value> {
+    async fn set_proxy_quality(&self, _state: &StateView<'_>, args: Value) -> ResolveResult<Value> {
         let quality = args["quality"]
             .as_str()
             .ok_or_else(|| ResolveError::invalid_parameter("quality", "parameter is required"))?;
@@ -3868,7 +5039,7 @@ except Exception as e:
         }))
     }
 
-    async fn set_cache_path(&self, _state: &mut ResolveState, args: Value) -> ResolveResult<Value> {
+    async fn set_cache_path(&self, _state: &StateView<'_>, args: Value) -> ResolveResult<Value> {
         let path_type = args["path_type"]
             .as_str()
             .ok_or_else(|| ResolveError::invalid_parameter("path_type", "parameter is required"))?;
@@ -3893,7 +5064,7 @@ except Exception as e:
 
     async fn generate_optimized_media(
         &self,
-        _state: &mut ResolveState,
+        _state: &StateView<'_>,
         args: Value,
     ) -> ResolveResult<Value> {
         let clip_names = args["clip_names"].as_array();
@@ -3916,7 +5087,7 @@ except Exception as e:
 
     async fn delete_optimized_media(
         &self,
-        _state: &mut ResolveState,
+        _state: &StateView<'_>,
         args: Value,
     ) -> ResolveResult<Value> {
         let clip_names = args["clip_names"].as_array();
@@ -3937,39 +5108,76 @@ except Exception as e:
     // ---- NEW: Extended Color Operations ----
     async fn create_color_preset_album(
         &self,
-        _state: &mut ResolveState,
+        state: &mut StateView<'_>,
         args: Value,
     ) -> ResolveResult<Value> {
         let album_name = args["album_name"].as_str().ok_or_else(|| {
             ResolveError::invalid_parameter("album_name", "parameter is required")
         })?;
 
+        // Creating an album that already exists is a no-op (idempotent operation)
+        if state.color_state.preset_albums.contains_key(album_name) {
+            return Ok(serde_json::json!({
+                "result": format!("Color preset album '{}' already exists", album_name),
+                "album_name": album_name,
+                "already_existed": true,
+                "status": "success"
+            }));
+        }
+
+        state
+            .color_state
+            .preset_albums
+            .insert(album_name.to_string(), ColorPresetAlbum::new(album_name));
+
         Ok(serde_json::json!({
             "result": format!("Created color preset album '{}'", album_name),
             "album_name": album_name,
+            "already_existed": false,
+            "total_albums": state.color_state.preset_albums.len(),
             "status": "success"
         }))
     }
 
     async fn delete_color_preset_album(
         &self,
-        _state: &mut ResolveState,
+        state: &mut StateView<'_>,
         args: Value,
     ) -> ResolveResult<Value> {
         let album_name = args["album_name"].as_str().ok_or_else(|| {
             ResolveError::invalid_parameter("album_name", "parameter is required")
         })?;
 
+        if album_name == DEFAULT_PRESET_ALBUM {
+            return Err(ResolveError::invalid_parameter(
+                "album_name",
+                "the default album cannot be deleted",
+            ));
+        }
+
+        let removed_album = state
+            .color_state
+            .preset_albums
+            .remove(album_name)
+            .ok_or_else(|| {
+                ResolveError::invalid_parameter(
+                    "album_name",
+                    format!("album '{}' not found", album_name),
+                )
+            })?;
+
         Ok(serde_json::json!({
-            "result": format!("Deleted color preset album '{}'", album_name),
+            "result": format!("Deleted color preset album '{}' ({} presets)",
+                album_name, removed_album.presets.len()),
             "album_name": album_name,
+            "deleted_presets": removed_album.presets.len(),
             "status": "success"
         }))
     }
 
     async fn export_all_power_grade_luts(
         &self,
-        _state: &mut ResolveState,
+        _state: &StateView<'_>,
         args: Value,
     ) -> ResolveResult<Value> {
         let export_dir = args["export_dir"].as_str().ok_or_else(|| {
@@ -3986,7 +5194,7 @@ except Exception as e:
     // ---- NEW: Layout and Interface Management ----
     async fn save_layout_preset(
         &self,
-        _state: &mut ResolveState,
+        _state: &StateView<'_>,
         args: Value,
     ) -> ResolveResult<Value> {
         let preset_name = args["preset_name"].as_str().ok_or_else(|| {
@@ -4002,7 +5210,7 @@ except Exception as e:
 
     async fn load_layout_preset(
         &self,
-        _state: &mut ResolveState,
+        _state: &StateView<'_>,
         args: Value,
     ) -> ResolveResult<Value> {
         let preset_name = args["preset_name"].as_str().ok_or_else(|| {
@@ -4018,7 +5226,7 @@ except Exception as e:
 
     async fn export_layout_preset(
         &self,
-        _state: &mut ResolveState,
+        _state: &StateView<'_>,
         args: Value,
     ) -> ResolveResult<Value> {
         let preset_name = args["preset_name"].as_str().ok_or_else(|| {
@@ -4038,7 +5246,7 @@ except Exception as e:
 
     async fn import_layout_preset(
         &self,
-        _state: &mut ResolveState,
+        _state: &StateView<'_>,
         args: Value,
     ) -> ResolveResult<Value> {
         let import_path = args["import_path"].as_str().ok_or_else(|| {
@@ -4058,7 +5266,7 @@ except Exception as e:
 
     async fn delete_layout_preset(
         &self,
-        _state: &mut ResolveState,
+        _state: &StateView<'_>,
         args: Value,
     ) -> ResolveResult<Value> {
         let preset_name = args["preset_name"].as_str().ok_or_else(|| {
@@ -4073,7 +5281,7 @@ except Exception as e:
     }
 
     // ---- NEW: Application Control ----
-    async fn quit_app(&self, _state: &mut ResolveState, args: Value) -> ResolveResult<Value> {
+    async fn quit_app(&self, _state: &StateView<'_>, args: Value) -> ResolveResult<Value> {
         let force = args["force"].as_bool().unwrap_or(false);
         let save_project = args["save_project"].as_bool().unwrap_or(true);
 
@@ -4093,7 +5301,7 @@ except Exception as e:
         }))
     }
 
-    async fn restart_app(&self, _state: &mut ResolveState, args: Value) -> ResolveResult<Value> {
+    async fn restart_app(&self, _state: &StateView<'_>, args: Value) -> ResolveResult<Value> {
         let wait_seconds = args["wait_seconds"].as_i64().unwrap_or(5);
 
         Ok(serde_json::json!({
@@ -4103,7 +5311,7 @@ except Exception as e:
         }))
     }
 
-    async fn open_settings(&self, _state: &mut ResolveState, _args: Value) -> ResolveResult<Value> {
+    async fn open_settings(&self, _state: &StateView<'_>, _args: Value) -> ResolveResult<Value> {
         Ok(serde_json::json!({
             "result": "Opened Project Settings dialog",
             "status": "success"
@@ -4112,7 +5320,7 @@ except Exception as e:
 
     async fn open_app_preferences(
         &self,
-        _state: &mut ResolveState,
+        _state: &StateView<'_>,
         _args: Value,
     ) -> ResolveResult<Value> {
         Ok(serde_json::json!({
@@ -4124,7 +5332,7 @@ except Exception as e:
     // ---- NEW: Cloud Operations ----
     async fn create_cloud_project(
         &self,
-        _state: &mut ResolveState,
+        _state: &StateView<'_>,
         args: Value,
     ) -> ResolveResult<Value> {
         let project_name = args["project_name"].as_str().ok_or_else(|| {
@@ -4151,7 +5359,7 @@ except Exception as e:
 
     async fn import_cloud_project(
         &self,
-        _state: &mut ResolveState,
+        _state: &StateView<'_>,
         args: Value,
     ) -> ResolveResult<Value> {
         let cloud_id = args["cloud_id"]
@@ -4175,7 +5383,7 @@ except Exception as e:
 
     async fn restore_cloud_project(
         &self,
-        _state: &mut ResolveState,
+        _state: &StateView<'_>,
         args: Value,
     ) -> ResolveResult<Value> {
         let cloud_id = args["cloud_id"]
@@ -4199,7 +5407,7 @@ except Exception as e:
 
     async fn export_project_to_cloud(
         &self,
-        state: &mut ResolveState,
+        state: &StateView<'_>,
         args: Value,
     ) -> ResolveResult<Value> {
         let project_name = args["project_name"].as_str().unwrap_or_else(|| {
@@ -4218,7 +5426,7 @@ except Exception as e:
 
     async fn add_user_to_cloud_project(
         &self,
-        _state: &mut ResolveState,
+        _state: &StateView<'_>,
         args: Value,
     ) -> ResolveResult<Value> {
         let cloud_id = args["cloud_id"]
@@ -4240,7 +5448,7 @@ except Exception as e:
 
     async fn remove_user_from_cloud_project(
         &self,
-        _state: &mut ResolveState,
+        _state: &StateView<'_>,
         args: Value,
     ) -> ResolveResult<Value> {
         let cloud_id = args["cloud_id"]
@@ -4259,7 +5467,7 @@ except Exception as e:
     }
 
     // ---- NEW: Object Inspection ----
-    async fn object_help(&self, _state: &mut ResolveState, args: Value) -> ResolveResult<Value> {
+    async fn object_help(&self, _state: &StateView<'_>, args: Value) -> ResolveResult<Value> {
         let object_type = args["object_type"].as_str().ok_or_else(|| {
             ResolveError::invalid_parameter("object_type", "parameter is required")
         })?;
@@ -4283,7 +5491,7 @@ except Exception as e:
 
     async fn inspect_custom_object(
         &self,
-        _state: &mut ResolveState,
+        _state: &StateView<'_>,
         args: Value,
     ) -> ResolveResult<Value> {
         let object_path = args["object_path"].as_str().ok_or_else(|| {
@@ -4302,7 +5510,7 @@ except Exception as e:
     // ---- NEW: Project Properties ----
     async fn set_project_property(
         &self,
-        _state: &mut ResolveState,
+        _state: &StateView<'_>,
         args: Value,
     ) -> ResolveResult<Value> {
         let property_name = args["property_name"].as_str().ok_or_else(|| {
@@ -4320,7 +5528,7 @@ except Exception as e:
 
     async fn set_timeline_format(
         &self,
-        _state: &mut ResolveState,
+        _state: &StateView<'_>,
         args: Value,
     ) -> ResolveResult<Value> {
         let width = args["width"]
@@ -4345,11 +5553,7 @@ except Exception as e:
     }
 
     // ---- NEW: Timeline Object API ----
-    async fn get_timeline_name(
-        &self,
-        _state: &mut ResolveState,
-        args: Value,
-    ) -> ResolveResult<Value> {
+    async fn get_timeline_name(&self, _state: &StateView<'_>, args: Value) -> ResolveResult<Value> {
         let timeline_name = args["timeline_name"].as_str();
 
         Ok(serde_json::json!({
@@ -4359,11 +5563,7 @@ except Exception as e:
         }))
     }
 
-    async fn set_timeline_name(
-        &self,
-        _state: &mut ResolveState,
-        args: Value,
-    ) -> ResolveResult<Value> {
+    async fn set_timeline_name(&self, _state: &StateView<'_>, args: Value) -> ResolveResult<Value> {
         let timeline_name = args["timeline_name"].as_str().ok_or_else(|| {
             ResolveError::invalid_parameter("timeline_name", "parameter is required")
         })?;
@@ -4381,7 +5581,7 @@ except Exception as e:
 
     async fn get_timeline_frames(
         &self,
-        _state: &mut ResolveState,
+        _state: &StateView<'_>,
         args: Value,
     ) -> ResolveResult<Value> {
         let timeline_name = args["timeline_name"].as_str();
@@ -4396,27 +5596,9 @@ except Exception as e:
         }))
     }
 
-    async fn set_timeline_timecode(
-        &self,
-        _state: &mut ResolveState,
-        args: Value,
-    ) -> ResolveResult<Value> {
-        let timeline_name = args["timeline_name"].as_str();
-        let timecode = args["timecode"]
-            .as_str()
-            .ok_or_else(|| ResolveError::invalid_parameter("timecode", "parameter is required"))?;
-
-        Ok(serde_json::json!({
-            "result": format!("Set timeline timecode to: {}", timecode),
-            "timeline_name": timeline_name,
-            "timecode": timecode,
-            "status": "success"
-        }))
-    }
-
     async fn get_timeline_track_count(
         &self,
-        _state: &mut ResolveState,
+        _state: &StateView<'_>,
         args: Value,
     ) -> ResolveResult<Value> {
         let timeline_name = args["timeline_name"].as_str();
@@ -4442,7 +5624,7 @@ except Exception as e:
 
     async fn get_timeline_items_in_track(
         &self,
-        _state: &mut ResolveState,
+        _state: &StateView<'_>,
         args: Value,
     ) -> ResolveResult<Value> {
         let timeline_name = args["timeline_name"].as_str();
@@ -4468,7 +5650,7 @@ except Exception as e:
 
     async fn add_timeline_marker(
         &self,
-        _state: &mut ResolveState,
+        _state: &StateView<'_>,
         args: Value,
     ) -> ResolveResult<Value> {
         let timeline_name = args["timeline_name"].as_str();
@@ -4492,7 +5674,7 @@ except Exception as e:
 
     async fn get_timeline_markers(
         &self,
-        _state: &mut ResolveState,
+        _state: &StateView<'_>,
         args: Value,
     ) -> ResolveResult<Value> {
         let timeline_name = args["timeline_name"].as_str();
@@ -4510,7 +5692,7 @@ except Exception as e:
 
     async fn delete_timeline_marker(
         &self,
-        _state: &mut ResolveState,
+        _state: &StateView<'_>,
         args: Value,
     ) -> ResolveResult<Value> {
         let timeline_name = args["timeline_name"].as_str();
@@ -4530,7 +5712,7 @@ except Exception as e:
 
     async fn duplicate_timeline(
         &self,
-        _state: &mut ResolveState,
+        _state: &StateView<'_>,
         args: Value,
     ) -> ResolveResult<Value> {
         let source_timeline_name = args["source_timeline_name"].as_str().ok_or_else(|| {
@@ -4550,7 +5732,7 @@ except Exception as e:
 
     async fn create_compound_clip(
         &self,
-        _state: &mut ResolveState,
+        _state: &StateView<'_>,
         args: Value,
     ) -> ResolveResult<Value> {
         let timeline_name = args["timeline_name"].as_str();
@@ -4572,7 +5754,7 @@ except Exception as e:
 
     async fn create_fusion_clip(
         &self,
-        _state: &mut ResolveState,
+        _state: &StateView<'_>,
         args: Value,
     ) -> ResolveResult<Value> {
         let timeline_name = args["timeline_name"].as_str();
@@ -4588,40 +5770,21 @@ except Exception as e:
         }))
     }
 
-    async fn export_timeline(
-        &self,
-        _state: &mut ResolveState,
-        args: Value,
-    ) -> ResolveResult<Value> {
-        let timeline_name = args["timeline_name"].as_str();
-        let file_name = args["file_name"]
-            .as_str()
-            .ok_or_else(|| ResolveError::invalid_parameter("file_name", "parameter is required"))?;
-        let export_type = args["export_type"].as_str().ok_or_else(|| {
-            ResolveError::invalid_parameter("export_type", "parameter is required")
-        })?;
-        let export_subtype = args["export_subtype"].as_str();
-
-        Ok(serde_json::json!({
-            "result": format!("Exported timeline as {} to {}", export_type, file_name),
-            "timeline_name": timeline_name,
-            "file_name": file_name,
-            "export_type": export_type,
-            "export_subtype": export_subtype,
-            "status": "success"
-        }))
+    async fn export_timeline(&self, _state: &StateView<'_>, args: Value) -> ResolveResult<Value> {
+        self.timeline_export(&args)
     }
 
-    async fn insert_generator(
-        &self,
-        _state: &mut ResolveState,
-        args: Value,
-    ) -> ResolveResult<Value> {
+    async fn insert_generator(&self, _state: &StateView<'_>, args: Value) -> ResolveResult<Value> {
         let timeline_name = args["timeline_name"].as_str();
         let generator_name = args["generator_name"].as_str().ok_or_else(|| {
             ResolveError::invalid_parameter("generator_name", "parameter is required")
         })?;
         let generator_type = args["generator_type"].as_str().unwrap_or("standard");
+        let generator_name = if generator_type == "standard" {
+            generators::require_standard("generator_name", generator_name, None)?.name
+        } else {
+            generator_name
+        };
 
         Ok(serde_json::json!({
             "result": format!("Inserted {} generator: {}", generator_type, generator_name),
@@ -4632,7 +5795,7 @@ except Exception as e:
         }))
     }
 
-    async fn insert_title(&self, _state: &mut ResolveState, args: Value) -> ResolveResult<Value> {
+    async fn insert_title(&self, _state: &StateView<'_>, args: Value) -> ResolveResult<Value> {
         let timeline_name = args["timeline_name"].as_str();
         let title_name = args["title_name"].as_str().ok_or_else(|| {
             ResolveError::invalid_parameter("title_name", "parameter is required")
@@ -4648,84 +5811,80 @@ except Exception as e:
         }))
     }
 
-    async fn grab_still(&self, _state: &mut ResolveState, args: Value) -> ResolveResult<Value> {
-        let timeline_name = args["timeline_name"].as_str();
+    async fn grab_still(&self, state: &mut StateView<'_>, args: Value) -> ResolveResult<Value> {
+        let timeline_name = args["timeline_name"]
+            .as_str()
+            .map(|s| s.to_string())
+            .or_else(|| state.current_timeline.clone());
         let still_frame_source = args["still_frame_source"].as_str();
         let grab_all = args["grab_all"].as_bool().unwrap_or(false);
-
-        let action = if grab_all {
-            "Grabbed all stills"
-        } else {
-            "Grabbed current still"
-        };
-
-        Ok(serde_json::json!({
-            "result": action,
-            "timeline_name": timeline_name,
-            "still_frame_source": still_frame_source,
-            "grab_all": grab_all,
-            "status": "success"
-        }))
-    }
-
-    // ---- NEW: TimelineItem Object API ----
-    async fn get_timeline_item_property(
-        &self,
-        _state: &mut ResolveState,
-        args: Value,
-    ) -> ResolveResult<Value> {
-        let timeline_item_id = args["timeline_item_id"].as_str().ok_or_else(|| {
-            ResolveError::invalid_parameter("timeline_item_id", "parameter is required")
-        })?;
-        let property_key = args["property_key"].as_str();
-
-        let properties = if let Some(key) = property_key {
-            serde_json::json!({ key: "property_value" })
+        let album_name = state.gallery.resolve_album_name("album", None)?;
+
+        // Grabbing all takes one still per clip on the timeline
+        let mut source_clips: Vec<Option<String>> = if grab_all {
+            let mut items: Vec<&TimelineItemState> = state
+                .timeline_items
+                .items
+                .values()
+                .filter(|item| Some(&item.timeline_name) == timeline_name.as_ref())
+                .collect();
+            items.sort_by(|a, b| a.id.cmp(&b.id));
+            items
+                .into_iter()
+                .map(|item| Some(item.clip_name.clone()))
+                .collect()
         } else {
-            serde_json::json!({
-                "name": "Timeline Item",
-                "duration": 100,
-                "start": 1001,
-                "end": 1101,
-                "left_offset": 0,
-                "right_offset": 0
-            })
+            Vec::new()
         };
+        if source_clips.is_empty() {
+            source_clips.push(state.color_state.current_clip.clone());
+        }
 
-        Ok(serde_json::json!({
-            "result": "Timeline item property retrieved",
-            "timeline_item_id": timeline_item_id,
-            "property_key": property_key,
-            "properties": properties,
-            "status": "success"
-        }))
-    }
+        let grabbed_at = chrono::Utc::now().to_rfc3339();
+        let mut stills = Vec::with_capacity(source_clips.len());
+        for source_clip in source_clips {
+            let id = state.gallery.next_still_id();
+            stills.push(GalleryStill {
+                label: format!("Still {}", state.gallery.still_counter),
+                id,
+                timeline_name: timeline_name.clone(),
+                source_clip,
+                grabbed_at: grabbed_at.clone(),
+            });
+        }
+        let still_ids: Vec<String> = stills.iter().map(|still| still.id.clone()).collect();
 
-    async fn set_timeline_item_property(
-        &self,
-        _state: &mut ResolveState,
-        args: Value,
-    ) -> ResolveResult<Value> {
-        let timeline_item_id = args["timeline_item_id"].as_str().ok_or_else(|| {
-            ResolveError::invalid_parameter("timeline_item_id", "parameter is required")
-        })?;
-        let property_key = args["property_key"].as_str().ok_or_else(|| {
-            ResolveError::invalid_parameter("property_key", "parameter is required")
-        })?;
-        let property_value = &args["property_value"];
+        let album = state
+            .gallery
+            .album_mut(&album_name)
+            .ok_or_else(|| ResolveError::internal("current still album missing"))?;
+        album.stills.extend(stills);
+
+        let action = if grab_all {
+            format!(
+                "Grabbed {} stills into album '{}'",
+                still_ids.len(),
+                album_name
+            )
+        } else {
+            format!("Grabbed current still into album '{}'", album_name)
+        };
 
         Ok(serde_json::json!({
-            "result": format!("Set property '{}' on timeline item", property_key),
-            "timeline_item_id": timeline_item_id,
-            "property_key": property_key,
-            "property_value": property_value,
+            "result": action,
+            "timeline_name": timeline_name,
+            "still_frame_source": still_frame_source,
+            "grab_all": grab_all,
+            "album": album_name,
+            "still_ids": still_ids,
             "status": "success"
         }))
     }
 
+    // ---- NEW: TimelineItem Object API ----
     async fn get_timeline_item_details(
         &self,
-        _state: &mut ResolveState,
+        _state: &StateView<'_>,
         args: Value,
     ) -> ResolveResult<Value> {
         let timeline_item_id = args["timeline_item_id"].as_str().ok_or_else(|| {
@@ -4753,7 +5912,7 @@ except Exception as e:
 
     async fn add_timeline_item_marker(
         &self,
-        _state: &mut ResolveState,
+        _state: &StateView<'_>,
         args: Value,
     ) -> ResolveResult<Value> {
         let timeline_item_id = args["timeline_item_id"].as_str().ok_or_else(|| {
@@ -4779,7 +5938,7 @@ except Exception as e:
 
     async fn get_timeline_item_markers(
         &self,
-        _state: &mut ResolveState,
+        _state: &StateView<'_>,
         args: Value,
     ) -> ResolveResult<Value> {
         let timeline_item_id = args["timeline_item_id"].as_str().ok_or_else(|| {
@@ -4799,7 +5958,7 @@ except Exception as e:
 
     async fn delete_timeline_item_marker(
         &self,
-        _state: &mut ResolveState,
+        _state: &StateView<'_>,
         args: Value,
     ) -> ResolveResult<Value> {
         let timeline_item_id = args["timeline_item_id"].as_str().ok_or_else(|| {
@@ -4821,7 +5980,7 @@ except Exception as e:
 
     async fn timeline_item_flag(
         &self,
-        _state: &mut ResolveState,
+        _state: &StateView<'_>,
         args: Value,
     ) -> ResolveResult<Value> {
         let timeline_item_id = args["timeline_item_id"].as_str().ok_or_else(|| {
@@ -4829,8 +5988,8 @@ except Exception as e:
         })?;
         let color = args["color"].as_str();
 
-        let action = if color.is_some() {
-            format!("Added {} flag to timeline item", color.unwrap())
+        let action = if let Some(color) = color {
+            format!("Added {} flag to timeline item", color)
         } else {
             "Retrieved flags from timeline item".to_string()
         };
@@ -4846,7 +6005,7 @@ except Exception as e:
 
     async fn timeline_item_color(
         &self,
-        _state: &mut ResolveState,
+        _state: &StateView<'_>,
         args: Value,
     ) -> ResolveResult<Value> {
         let timeline_item_id = args["timeline_item_id"].as_str().ok_or_else(|| {
@@ -4868,7 +6027,7 @@ except Exception as e:
         }))
     }
 
-    async fn fusion_comp(&self, _state: &mut ResolveState, args: Value) -> ResolveResult<Value> {
+    async fn fusion_comp(&self, _state: &StateView<'_>, args: Value) -> ResolveResult<Value> {
         let timeline_item_id = args["timeline_item_id"].as_str().ok_or_else(|| {
             ResolveError::invalid_parameter("timeline_item_id", "parameter is required")
         })?;
@@ -4886,7 +6045,7 @@ except Exception as e:
         }))
     }
 
-    async fn version(&self, _state: &mut ResolveState, args: Value) -> ResolveResult<Value> {
+    async fn version(&self, _state: &StateView<'_>, args: Value) -> ResolveResult<Value> {
         let timeline_item_id = args["timeline_item_id"].as_str().ok_or_else(|| {
             ResolveError::invalid_parameter("timeline_item_id", "parameter is required")
         })?;
@@ -4904,21 +6063,34 @@ except Exception as e:
         }))
     }
 
-    async fn stereo_params(&self, _state: &mut ResolveState, args: Value) -> ResolveResult<Value> {
+    /// Sets the stereo parameters given in `params`, or reads them when there are none
+    async fn stereo_params(&self, state: &mut StateView<'_>, args: Value) -> ResolveResult<Value> {
         let timeline_item_id = args["timeline_item_id"].as_str().ok_or_else(|| {
             ResolveError::invalid_parameter("timeline_item_id", "parameter is required")
         })?;
-        let params = &args["params"];
-
-        Ok(serde_json::json!({
-            "result": "Stereo parameters operation completed",
-            "timeline_item_id": timeline_item_id,
-            "params": params,
-            "status": "success"
-        }))
+        let mut request = match &args["params"] {
+            Value::Object(params) if !params.is_empty() => Value::Object(params.clone()),
+            Value::Null => return self.get_stereo_params(state, args).await,
+            Value::Object(_) => {
+                return self
+                    .get_stereo_params(
+                        state,
+                        serde_json::json!({ "timeline_item_id": timeline_item_id }),
+                    )
+                    .await
+            }
+            _ => {
+                return Err(ResolveError::invalid_parameter(
+                    "params",
+                    "must be an object of stereo parameters",
+                ))
+            }
+        };
+        request["timeline_item_id"] = Value::from(timeline_item_id);
+        self.set_stereo_params(state, request).await
     }
 
-    async fn node_lut(&self, _state: &mut ResolveState, args: Value) -> ResolveResult<Value> {
+    async fn node_lut(&self, _state: &StateView<'_>, args: Value) -> ResolveResult<Value> {
         let timeline_item_id = args["timeline_item_id"].as_str().ok_or_else(|| {
             ResolveError::invalid_parameter("timeline_item_id", "parameter is required")
         })?;
@@ -4927,8 +6099,8 @@ except Exception as e:
         })?;
         let lut_path = args["lut_path"].as_str();
 
-        let action = if lut_path.is_some() {
-            format!("Set LUT on node {} to {}", node_index, lut_path.unwrap())
+        let action = if let Some(lut_path) = lut_path {
+            format!("Set LUT on node {} to {}", node_index, lut_path)
         } else {
             format!("Retrieved LUT from node {}", node_index)
         };
@@ -4942,21 +6114,23 @@ except Exception as e:
         }))
     }
 
-    async fn set_cdl(&self, _state: &mut ResolveState, args: Value) -> ResolveResult<Value> {
+    async fn set_cdl(&self, state: &mut StateView<'_>, args: Value) -> ResolveResult<Value> {
         let timeline_item_id = args["timeline_item_id"].as_str().ok_or_else(|| {
             ResolveError::invalid_parameter("timeline_item_id", "parameter is required")
         })?;
-        let cdl_map = &args["cdl_map"];
+        let cdl = grade_report::Cdl::parse(&args["cdl_map"])?;
+        let cdl_values = cdl.to_json();
+        Self::timeline_item_mut(state, timeline_item_id).cdl = Some(cdl);
 
         Ok(serde_json::json!({
             "result": "CDL parameters set on timeline item",
             "timeline_item_id": timeline_item_id,
-            "cdl_map": cdl_map,
+            "cdl": cdl_values,
             "status": "success"
         }))
     }
 
-    async fn take(&self, _state: &mut ResolveState, args: Value) -> ResolveResult<Value> {
+    async fn take(&self, _state: &StateView<'_>, args: Value) -> ResolveResult<Value> {
         let timeline_item_id = args["timeline_item_id"].as_str().ok_or_else(|| {
             ResolveError::invalid_parameter("timeline_item_id", "parameter is required")
         })?;
@@ -4972,7 +6146,7 @@ except Exception as e:
         }))
     }
 
-    async fn copy_grades(&self, _state: &mut ResolveState, args: Value) -> ResolveResult<Value> {
+    async fn copy_grades(&self, _state: &StateView<'_>, args: Value) -> ResolveResult<Value> {
         let source_timeline_item_id =
             args["source_timeline_item_id"].as_str().ok_or_else(|| {
                 ResolveError::invalid_parameter("source_timeline_item_id", "parameter is required")
@@ -4994,16 +6168,24 @@ except Exception as e:
 
     async fn get_media_pool_item_list(
         &self,
-        state: &mut ResolveState,
-        _args: Value,
+        state: &StateView<'_>,
+        args: Value,
     ) -> ResolveResult<Value> {
-        let clips: Vec<Value> = state
-            .media_pool
-            .clips
-            .iter()
+        // Sorted so pages follow a stable order
+        let mut clips: Vec<(&String, &Clip)> = state.media_pool.clips.iter().collect();
+        clips.sort_by(|a, b| a.0.cmp(b.0));
+
+        let mut response = json!({
+            "success": true,
+            "count": clips.len(),
+            "operation_id": format!("get_media_pool_item_list_{}", chrono::Utc::now().timestamp())
+        });
+        let clips: Vec<Value> = clips
+            .into_iter()
             .map(|(name, clip)| {
                 json!({
                     "name": name,
+                    "clip_id": clip.id,
                     "file_path": clip.file_path,
                     "bin": clip.bin,
                     "linked": clip.linked,
@@ -5011,18 +6193,20 @@ except Exception as e:
                 })
             })
             .collect();
-
-        Ok(json!({
-            "success": true,
-            "clips": clips,
-            "count": clips.len(),
-            "operation_id": format!("get_media_pool_item_list_{}", chrono::Utc::now().timestamp())
-        }))
+        self.paginate(
+            "get_media_pool_item_list",
+            &mut response,
+            "clips",
+            clips,
+            &args,
+        )
+        .await?;
+        Ok(response)
     }
 
     async fn get_media_pool_item_name(
         &self,
-        state: &mut ResolveState,
+        state: &StateView<'_>,
         args: Value,
     ) -> ResolveResult<Value> {
         let clip_name = args["clip_name"].as_str().unwrap_or("default_clip");
@@ -5045,11 +6229,14 @@ except Exception as e:
 
     async fn get_media_pool_item_property(
         &self,
-        state: &mut ResolveState,
+        state: &StateView<'_>,
         args: Value,
     ) -> ResolveResult<Value> {
         let clip_name = args["clip_name"].as_str().unwrap_or("default_clip");
-        let property_name = args["property_name"].as_str().unwrap_or("File Name");
+        let property_name = args["property_key"]
+            .as_str()
+            .or(args["property_name"].as_str())
+            .unwrap_or("File Name");
 
         if let Some(clip) = state.media_pool.clips.get(clip_name) {
             let property_value = match property_name {
@@ -5061,6 +6248,13 @@ except Exception as e:
                     .proxy_path
                     .clone()
                     .unwrap_or_else(|| "None".to_string()),
+                "FPS" => self.clip_frame_rate(clip),
+                "Input Color Space" => clip
+                    .color
+                    .input_color_space
+                    .clone()
+                    .unwrap_or_else(|| "Project".to_string()),
+                "Input LUT" => clip.color.input_lut.clone().unwrap_or_default(),
                 _ => format!("Property '{}' not available", property_name),
             };
 
@@ -5082,18 +6276,48 @@ except Exception as e:
 
     async fn set_media_pool_item_property(
         &self,
-        state: &mut ResolveState,
+        state: &mut StateView<'_>,
         args: Value,
     ) -> ResolveResult<Value> {
         let clip_name = args["clip_name"].as_str().unwrap_or("default_clip");
-        let property_name = args["property_name"].as_str().unwrap_or("Clip Name");
-        let property_value = args["property_value"].as_str().unwrap_or("");
+        let property_name = args["property_key"]
+            .as_str()
+            .or(args["property_name"].as_str())
+            .unwrap_or("Clip Name");
+        let property_value = match &args["property_value"] {
+            Value::Number(number) => number.to_string(),
+            value => value.as_str().unwrap_or("").to_string(),
+        };
+        let property_value = property_value.as_str();
 
         if let Some(clip) = state.media_pool.clips.get_mut(clip_name) {
             match property_name {
                 "Clip Name" => clip.name = property_value.to_string(),
                 "Bin" => clip.bin = Some(property_value.to_string()),
                 "Proxy Path" => clip.proxy_path = Some(property_value.to_string()),
+                "FPS" => {
+                    FrameRate::parse(property_value)
+                        .map_err(|e| ResolveError::invalid_parameter("property_value", e))?;
+                    clip.frame_rate = Some(property_value.to_string());
+                }
+                "Start TC" => {
+                    let rate = self.project_frame_rate()?;
+                    embedded_timecode::timecode_frames(property_value, rate)
+                        .map_err(|e| ResolveError::invalid_parameter("property_value", e))?;
+                    clip.start_timecode = Some(property_value.to_string());
+                }
+                "Reel Name" => {
+                    clip.reel_name =
+                        Some(property_value.to_string()).filter(|reel| !reel.is_empty());
+                }
+                "Input Color Space" => {
+                    clip.color.input_color_space =
+                        Some(property_value.to_string()).filter(|space| !space.is_empty());
+                }
+                "Input LUT" => {
+                    clip.color.input_lut =
+                        Some(property_value.to_string()).filter(|lut| !lut.is_empty());
+                }
                 _ => {
                     return Ok(json!({
                         "success": false,
@@ -5122,7 +6346,7 @@ except Exception as e:
 
     async fn get_media_pool_item_metadata(
         &self,
-        state: &mut ResolveState,
+        state: &StateView<'_>,
         args: Value,
     ) -> ResolveResult<Value> {
         let clip_name = args["clip_name"].as_str().unwrap_or("default_clip");
@@ -5133,11 +6357,15 @@ except Exception as e:
                 "File Name" => clip.file_path.clone(),
                 "Clip Name" => clip.name.clone(),
                 "Duration" => "00:00:10:00".to_string(), // Simulated duration
-                "Frame Rate" => "24".to_string(),
+                "Frame Rate" => self.clip_frame_rate(clip),
+                notes::CLIP_NOTES_METADATA => {
+                    state.notes.clip(clip_name).unwrap_or_default().to_string()
+                }
                 "Resolution" => "1920x1080".to_string(),
                 "Codec" => "H.264".to_string(),
                 "Date Created" => chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string(),
-                _ => format!("Metadata '{}' not available", metadata_type),
+                _ => metadata_schema::clip_value(state, clip_name, metadata_type)
+                    .unwrap_or_else(|| format!("Metadata '{}' not available", metadata_type)),
             };
 
             Ok(json!({
@@ -5158,36 +6386,49 @@ except Exception as e:
 
     async fn set_media_pool_item_metadata(
         &self,
-        state: &mut ResolveState,
+        state: &mut StateView<'_>,
         args: Value,
     ) -> ResolveResult<Value> {
         let clip_name = args["clip_name"].as_str().unwrap_or("default_clip");
         let metadata_type = args["metadata_type"].as_str().unwrap_or("Clip Name");
-        let metadata_value = args["metadata_value"].as_str().unwrap_or("");
+        let metadata_value = match &args["metadata_value"] {
+            Value::Number(number) => number.to_string(),
+            value => value.as_str().unwrap_or("").to_string(),
+        };
 
-        if state.media_pool.clips.contains_key(clip_name) {
-            // In simulation mode, we just acknowledge the metadata change
-            // In real mode, this would actually modify the clip metadata
-            Ok(json!({
-                "success": true,
-                "clip_name": clip_name,
-                "metadata_type": metadata_type,
-                "metadata_value": metadata_value,
-                "message": format!("Set metadata '{}' to '{}' for clip '{}'", metadata_type, metadata_value, clip_name),
-                "operation_id": format!("set_media_pool_item_metadata_{}", chrono::Utc::now().timestamp())
-            }))
-        } else {
-            Ok(json!({
+        if !state.media_pool.clips.contains_key(clip_name) {
+            return Ok(json!({
                 "success": false,
                 "error": format!("Clip '{}' not found in media pool", clip_name),
                 "operation_id": format!("set_media_pool_item_metadata_{}", chrono::Utc::now().timestamp())
-            }))
+            }));
+        }
+        if metadata_schema::READ_ONLY_FIELDS.contains(&metadata_type) {
+            return Ok(json!({
+                "success": false,
+                "error": format!("Metadata '{}' is read-only", metadata_type),
+                "operation_id": format!("set_media_pool_item_metadata_{}", chrono::Utc::now().timestamp())
+            }));
         }
+        self.enforce_metadata_schema(
+            "metadata_value",
+            [(clip_name, metadata_type, metadata_value.as_str())],
+        )?;
+        metadata_schema::set_clip_value(state, clip_name, metadata_type, metadata_value.clone());
+
+        Ok(json!({
+            "success": true,
+            "clip_name": clip_name,
+            "metadata_type": metadata_type,
+            "metadata_value": metadata_value,
+            "message": format!("Set metadata '{}' to '{}' for clip '{}'", metadata_type, metadata_value, clip_name),
+            "operation_id": format!("set_media_pool_item_metadata_{}", chrono::Utc::now().timestamp())
+        }))
     }
 
     async fn get_media_pool_item_markers(
         &self,
-        state: &mut ResolveState,
+        state: &StateView<'_>,
         args: Value,
     ) -> ResolveResult<Value> {
         let clip_name = args["clip_name"].as_str().unwrap_or("default_clip");
@@ -5227,7 +6468,7 @@ except Exception as e:
 
     async fn get_media_pool_item_flag_list(
         &self,
-        state: &mut ResolveState,
+        state: &StateView<'_>,
         args: Value,
     ) -> ResolveResult<Value> {
         let clip_name = args["clip_name"].as_str().unwrap_or("default_clip");
@@ -5257,7 +6498,7 @@ except Exception as e:
 
     async fn get_media_pool_item_clip_color(
         &self,
-        state: &mut ResolveState,
+        state: &StateView<'_>,
         args: Value,
     ) -> ResolveResult<Value> {
         let clip_name = args["clip_name"].as_str().unwrap_or("default_clip");
@@ -5280,7 +6521,7 @@ except Exception as e:
 
     async fn set_media_pool_item_name(
         &self,
-        state: &mut ResolveState,
+        state: &mut StateView<'_>,
         args: Value,
     ) -> ResolveResult<Value> {
         let clip_name = args["clip_name"]
@@ -5310,7 +6551,7 @@ except Exception as e:
 
     async fn add_media_pool_item_marker(
         &self,
-        _state: &mut ResolveState,
+        _state: &StateView<'_>,
         args: Value,
     ) -> ResolveResult<Value> {
         let clip_name = args["clip_name"]
@@ -5335,7 +6576,7 @@ except Exception as e:
 
     async fn add_media_pool_item_flag(
         &self,
-        _state: &mut ResolveState,
+        _state: &StateView<'_>,
         args: Value,
     ) -> ResolveResult<Value> {
         let clip_name = args["clip_name"]
@@ -5354,7 +6595,7 @@ except Exception as e:
 
     async fn set_media_pool_item_clip_color(
         &self,
-        _state: &mut ResolveState,
+        _state: &StateView<'_>,
         args: Value,
     ) -> ResolveResult<Value> {
         let clip_name = args["clip_name"]
@@ -5375,7 +6616,7 @@ except Exception as e:
 
     async fn link_media_pool_item_proxy_media(
         &self,
-        _state: &mut ResolveState,
+        _state: &StateView<'_>,
         args: Value,
     ) -> ResolveResult<Value> {
         let clip_name = args["clip_name"]
@@ -5396,7 +6637,7 @@ except Exception as e:
 
     async fn unlink_media_pool_item_proxy_media(
         &self,
-        _state: &mut ResolveState,
+        _state: &StateView<'_>,
         args: Value,
     ) -> ResolveResult<Value> {
         let clip_name = args["clip_name"]
@@ -5413,31 +6654,34 @@ except Exception as e:
 
     async fn transcribe_media_pool_item_audio(
         &self,
-        _state: &mut ResolveState,
+        state: &mut StateView<'_>,
         args: Value,
     ) -> ResolveResult<Value> {
         let clip_name = args["clip_name"]
             .as_str()
             .ok_or_else(|| ResolveError::invalid_parameter("clip_name", "parameter is required"))?;
-        let language = args["language"].as_str().unwrap_or("en-US");
+        let language = self.transcription_language(&args)?;
+        let word_count = Self::store_transcript(state, clip_name, language);
 
         Ok(json!({
             "success": true,
             "result": format!("Started transcription for clip '{}' in language '{}'", clip_name, language),
             "clip_name": clip_name,
             "language": language,
+            "word_count": word_count,
             "operation_id": format!("transcribe_media_pool_item_audio_{}", chrono::Utc::now().timestamp())
         }))
     }
 
     async fn clear_media_pool_item_transcription(
         &self,
-        _state: &mut ResolveState,
+        state: &mut StateView<'_>,
         args: Value,
     ) -> ResolveResult<Value> {
         let clip_name = args["clip_name"]
             .as_str()
             .ok_or_else(|| ResolveError::invalid_parameter("clip_name", "parameter is required"))?;
+        Self::remove_transcript(state, clip_name);
 
         Ok(json!({
             "success": true,
@@ -5451,7 +6695,7 @@ except Exception as e:
 
     async fn get_fusion_tool_list(
         &self,
-        _state: &mut ResolveState,
+        _state: &StateView<'_>,
         args: Value,
     ) -> ResolveResult<Value> {
         let selected_only = args["selected_only"].as_bool().unwrap_or(false);
@@ -5494,7 +6738,7 @@ except Exception as e:
 
     async fn get_audio_track_count(
         &self,
-        _state: &mut ResolveState,
+        _state: &StateView<'_>,
         _args: Value,
     ) -> ResolveResult<Value> {
         Ok(json!({
@@ -5507,7 +6751,7 @@ except Exception as e:
 
     async fn get_project_timeline_count(
         &self,
-        state: &mut ResolveState,
+        state: &StateView<'_>,
         _args: Value,
     ) -> ResolveResult<Value> {
         let count = state.timelines.len();
@@ -5521,22 +6765,36 @@ except Exception as e:
 
     async fn get_gallery_still_albums(
         &self,
-        _state: &mut ResolveState,
+        state: &StateView<'_>,
         _args: Value,
     ) -> ResolveResult<Value> {
-        let albums = vec!["PowerGrade", "Stills", "LUTs", "Custom"];
+        let current = state.gallery.current_album.as_deref();
+        let albums: Vec<Value> = state
+            .gallery
+            .albums
+            .iter()
+            .map(|album| {
+                json!({
+                    "album_name": album.name,
+                    "album_id": album.id,
+                    "still_count": album.stills.len(),
+                    "is_current": Some(album.name.as_str()) == current
+                })
+            })
+            .collect();
         Ok(json!({
             "success": true,
             "result": "Retrieved gallery still albums",
             "albums": albums,
             "count": albums.len(),
+            "current_album": current,
             "operation_id": format!("get_gallery_still_albums_{}", chrono::Utc::now().timestamp())
         }))
     }
 
     async fn get_media_pool_root_folder(
         &self,
-        _state: &mut ResolveState,
+        _state: &StateView<'_>,
         _args: Value,
     ) -> ResolveResult<Value> {
         Ok(json!({
@@ -5548,11 +6806,7 @@ except Exception as e:
         }))
     }
 
-    async fn add_fusion_tool(
-        &self,
-        _state: &mut ResolveState,
-        args: Value,
-    ) -> ResolveResult<Value> {
+    async fn add_fusion_tool(&self, _state: &StateView<'_>, args: Value) -> ResolveResult<Value> {
         let tool_name = args["tool_name"]
             .as_str()
             .ok_or_else(|| ResolveError::invalid_parameter("tool_name", "parameter is required"))?;
@@ -5571,7 +6825,7 @@ except Exception as e:
 
     async fn get_audio_track_name(
         &self,
-        _state: &mut ResolveState,
+        _state: &StateView<'_>,
         args: Value,
     ) -> ResolveResult<Value> {
         let track_index = args["track_index"].as_i64().ok_or_else(|| {
@@ -5589,7 +6843,7 @@ except Exception as e:
 
     async fn set_audio_track_name(
         &self,
-        _state: &mut ResolveState,
+        _state: &StateView<'_>,
         args: Value,
     ) -> ResolveResult<Value> {
         let track_index = args["track_index"].as_i64().ok_or_else(|| {
@@ -5610,25 +6864,267 @@ except Exception as e:
 
     async fn add_gallery_still_album(
         &self,
-        _state: &mut ResolveState,
+        state: &mut StateView<'_>,
         args: Value,
     ) -> ResolveResult<Value> {
         let album_name = args["album_name"].as_str().ok_or_else(|| {
             ResolveError::invalid_parameter("album_name", "parameter is required")
         })?;
 
+        // Adding an album that already exists is a no-op
+        if let Some(album) = state.gallery.album(album_name) {
+            return Ok(json!({
+                "success": true,
+                "result": format!("Gallery still album '{}' already exists", album_name),
+                "album_name": album_name,
+                "album_id": album.id,
+                "operation_id": format!("add_gallery_still_album_{}", chrono::Utc::now().timestamp())
+            }));
+        }
+
+        let album = GalleryStillAlbum::new(album_name);
+        let album_id = album.id.clone();
+        state.gallery.albums.push(album);
+
         Ok(json!({
             "success": true,
             "result": format!("Added gallery still album '{}'", album_name),
             "album_name": album_name,
-            "album_id": format!("album_{}", chrono::Utc::now().timestamp()),
+            "album_id": album_id,
             "operation_id": format!("add_gallery_still_album_{}", chrono::Utc::now().timestamp())
         }))
     }
 
+    async fn rename_gallery_still_album(
+        &self,
+        state: &mut StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        let album_name = args["album_name"].as_str().ok_or_else(|| {
+            ResolveError::invalid_parameter("album_name", "parameter is required")
+        })?;
+        let new_name = args["new_name"]
+            .as_str()
+            .ok_or_else(|| ResolveError::invalid_parameter("new_name", "parameter is required"))?;
+
+        if new_name.trim().is_empty() {
+            return Err(ResolveError::invalid_parameter(
+                "new_name",
+                "album name cannot be empty",
+            ));
+        }
+        if new_name != album_name && state.gallery.album(new_name).is_some() {
+            return Err(ResolveError::invalid_parameter(
+                "new_name",
+                format!("still album '{}' already exists", new_name),
+            ));
+        }
+
+        let album = state.gallery.album_mut(album_name).ok_or_else(|| {
+            ResolveError::invalid_parameter(
+                "album_name",
+                format!("still album '{}' not found", album_name),
+            )
+        })?;
+        album.name = new_name.to_string();
+        let album_id = album.id.clone();
+
+        if state.gallery.current_album.as_deref() == Some(album_name) {
+            state.gallery.current_album = Some(new_name.to_string());
+        }
+
+        Ok(json!({
+            "success": true,
+            "result": format!("Renamed gallery still album '{}' to '{}'", album_name, new_name),
+            "album_id": album_id,
+            "old_name": album_name,
+            "new_name": new_name,
+            "operation_id": Uuid::new_v4().to_string()
+        }))
+    }
+
+    async fn set_current_still_album(
+        &self,
+        state: &mut StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        let album_name = args["album_name"].as_str().ok_or_else(|| {
+            ResolveError::invalid_parameter("album_name", "parameter is required")
+        })?;
+        let album_name = state
+            .gallery
+            .resolve_album_name("album_name", Some(album_name))?;
+
+        let previous = state.gallery.current_album.replace(album_name.clone());
+
+        Ok(json!({
+            "success": true,
+            "result": format!("Set current still album to '{}'", album_name),
+            "album_name": album_name,
+            "previous_album": previous,
+            "operation_id": Uuid::new_v4().to_string()
+        }))
+    }
+
+    async fn get_current_still_album(
+        &self,
+        state: &StateView<'_>,
+        _args: Value,
+    ) -> ResolveResult<Value> {
+        let album_name = state.gallery.resolve_album_name("album_name", None)?;
+        let album = state
+            .gallery
+            .album(&album_name)
+            .ok_or_else(|| ResolveError::internal("current still album missing"))?;
+
+        Ok(json!({
+            "success": true,
+            "result": format!("Current still album is '{}'", album_name),
+            "album_name": album.name,
+            "album_id": album.id,
+            "still_count": album.stills.len(),
+            "operation_id": Uuid::new_v4().to_string()
+        }))
+    }
+
+    async fn get_album_stills(&self, state: &StateView<'_>, args: Value) -> ResolveResult<Value> {
+        let album_name = state
+            .gallery
+            .resolve_album_name("album_name", args["album_name"].as_str())?;
+        let album = state
+            .gallery
+            .album(&album_name)
+            .ok_or_else(|| ResolveError::internal("still album missing"))?;
+        let stills: Vec<Value> = album
+            .stills
+            .iter()
+            .map(|still| still.to_json(&album.name))
+            .collect();
+
+        Ok(json!({
+            "success": true,
+            "result": format!("Retrieved {} stills from album '{}'", stills.len(), album_name),
+            "album_name": album_name,
+            "stills": stills,
+            "count": stills.len(),
+            "operation_id": Uuid::new_v4().to_string()
+        }))
+    }
+
+    async fn move_gallery_stills(
+        &self,
+        state: &mut StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        self.transfer_gallery_stills(state, args, false)
+    }
+
+    async fn copy_gallery_stills(
+        &self,
+        state: &mut StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        self.transfer_gallery_stills(state, args, true)
+    }
+
+    /// Move or copy stills between albums; either every still is transferred or none is
+    fn transfer_gallery_stills(
+        &self,
+        state: &mut StateView<'_>,
+        args: Value,
+        copy: bool,
+    ) -> ResolveResult<Value> {
+        let still_ids: Vec<String> = args["still_ids"]
+            .as_array()
+            .ok_or_else(|| ResolveError::invalid_parameter("still_ids", "required array"))?
+            .iter()
+            .map(|id| {
+                id.as_str().map(|s| s.to_string()).ok_or_else(|| {
+                    ResolveError::invalid_parameter("still_ids", "still IDs must be strings")
+                })
+            })
+            .collect::<ResolveResult<_>>()?;
+        if still_ids.is_empty() {
+            return Err(ResolveError::invalid_parameter(
+                "still_ids",
+                "at least one still ID is required",
+            ));
+        }
+        let target_album = args["target_album"].as_str().ok_or_else(|| {
+            ResolveError::invalid_parameter("target_album", "parameter is required")
+        })?;
+        let source_album = state
+            .gallery
+            .resolve_album_name("source_album", args["source_album"].as_str())?;
+        let target_album = state
+            .gallery
+            .resolve_album_name("target_album", Some(target_album))?;
+        if !copy && source_album == target_album {
+            return Err(ResolveError::invalid_parameter(
+                "target_album",
+                "source and target albums are the same",
+            ));
+        }
+
+        let source = state
+            .gallery
+            .album(&source_album)
+            .ok_or_else(|| ResolveError::internal("still album missing"))?;
+        // A still named twice is transferred once, so no album holds an ID twice
+        let mut transferred: Vec<GalleryStill> = Vec::with_capacity(still_ids.len());
+        for still_id in &still_ids {
+            if transferred.iter().any(|still| &still.id == still_id) {
+                continue;
+            }
+            let still = source
+                .stills
+                .iter()
+                .find(|still| &still.id == still_id)
+                .ok_or_else(|| {
+                    ResolveError::invalid_parameter(
+                        "still_ids",
+                        format!("still '{}' not found in album '{}'", still_id, source_album),
+                    )
+                })?;
+            transferred.push(still.clone());
+        }
+
+        if copy {
+            for still in transferred.iter_mut() {
+                still.id = state.gallery.next_still_id();
+            }
+        } else if let Some(source) = state.gallery.album_mut(&source_album) {
+            source.stills.retain(|still| !still_ids.contains(&still.id));
+        }
+
+        let new_ids: Vec<String> = transferred.iter().map(|still| still.id.clone()).collect();
+        let target = state
+            .gallery
+            .album_mut(&target_album)
+            .ok_or_else(|| ResolveError::internal("still album missing"))?;
+        target.stills.extend(transferred);
+
+        let verb = if copy { "Copied" } else { "Moved" };
+        Ok(json!({
+            "success": true,
+            "result": format!(
+                "{} {} stills from '{}' to '{}'",
+                verb,
+                new_ids.len(),
+                source_album,
+                target_album
+            ),
+            "source_album": source_album,
+            "target_album": target_album,
+            "still_ids": new_ids,
+            "target_still_count": target.stills.len(),
+            "operation_id": Uuid::new_v4().to_string()
+        }))
+    }
+
     async fn add_media_pool_sub_folder(
         &self,
-        state: &mut ResolveState,
+        state: &mut StateView<'_>,
         args: Value,
     ) -> ResolveResult<Value> {
         let name = args["name"]
@@ -5637,29 +7133,32 @@ except Exception as e:
         let _parent_folder = args["parent_folder"].as_str();
 
         // Check if bin already exists - if so, return success (idempotent operation)
-        if state.media_pool.bins.contains_key(name) {
+        if let Some(bin) = state.media_pool.bins.get(name) {
             return Ok(json!({
                 "success": true,
                 "result": format!("Media pool sub folder '{}' already exists", name),
                 "folder_name": name,
-                "folder_id": format!("folder_{}", chrono::Utc::now().timestamp()),
+                "folder_id": bin.id,
                 "operation_id": format!("add_media_pool_sub_folder_{}", chrono::Utc::now().timestamp()),
                 "already_existed": true
             }));
         }
 
         let bin = Bin {
+            id: Uuid::new_v4().to_string(),
             name: name.to_string(),
             clips: Vec::new(),
+            color_rules: Vec::new(),
         };
 
+        let folder_id = bin.id.clone();
         state.media_pool.bins.insert(name.to_string(), bin);
 
         Ok(json!({
             "success": true,
             "result": format!("Added media pool sub folder '{}'", name),
             "folder_name": name,
-            "folder_id": format!("folder_{}", chrono::Utc::now().timestamp()),
+            "folder_id": folder_id,
             "operation_id": format!("add_media_pool_sub_folder_{}", chrono::Utc::now().timestamp()),
             "already_existed": false
         }))
@@ -5667,24 +7166,52 @@ except Exception as e:
 
     async fn append_to_timeline(
         &self,
-        _state: &mut ResolveState,
+        state: &mut StateView<'_>,
         args: Value,
     ) -> ResolveResult<Value> {
         let clip_info = args["clip_info"]
             .as_array()
             .ok_or_else(|| ResolveError::invalid_parameter("clip_info", "parameter is required"))?;
-        let timeline_name = args["timeline_name"].as_str();
+        // A named timeline must exist; with none named, the current one is used if there is one
+        let timeline_name = match args["timeline_name"].as_str() {
+            Some(_) => Some(Self::resolve_timeline_name(state, &args)?),
+            None => state
+                .current_timeline
+                .clone()
+                .filter(|name| state.timelines.contains_key(name)),
+        };
 
-        let clip_names: Vec<String> = clip_info
-            .iter()
-            .filter_map(|v| v.as_str())
-            .map(|s| s.to_string())
-            .collect();
+        // As with Resolve's AppendToTimeline, clips that cannot be found are left out
+        let mut appended = Vec::new();
+        let mut skipped = Vec::new();
+        for clip_reference in clip_info.iter().filter_map(|v| v.as_str()) {
+            let clip_name = Self::resolve_clip(state, clip_reference).ok();
+            match (&timeline_name, clip_name) {
+                (Some(timeline_name), Some(clip_name)) => {
+                    appended.push(self.place_clip(
+                        state,
+                        timeline_name,
+                        &clip_name,
+                        1,
+                        &Value::Null,
+                    )?);
+                }
+                _ => skipped.push(clip_reference.to_string()),
+            }
+        }
 
         Ok(json!({
             "success": true,
-            "result": format!("Appended {} clips to timeline", clip_names.len()),
-            "clips": clip_names,
+            "result": format!(
+                "Appended {} clips to timeline{}",
+                appended.len(),
+                timeline_name
+                    .as_ref()
+                    .map(|name| format!(" '{}'", name))
+                    .unwrap_or_default()
+            ),
+            "clips": appended,
+            "skipped": skipped,
             "timeline_name": timeline_name,
             "operation_id": format!("append_to_timeline_{}", chrono::Utc::now().timestamp())
         }))
@@ -5692,7 +7219,7 @@ except Exception as e:
 
     async fn get_project_timeline_by_index(
         &self,
-        state: &mut ResolveState,
+        state: &StateView<'_>,
         args: Value,
     ) -> ResolveResult<Value> {
         let timeline_index = args["timeline_index"].as_i64().ok_or_else(|| {
@@ -5700,10 +7227,12 @@ except Exception as e:
         })?;
 
         let timeline_names: Vec<&String> = state.timelines.keys().collect();
-        let index = (timeline_index - 1) as usize; // Convert to 0-based index
+        // Convert to 0-based index; 0 and negative indexes are out of range
+        let timeline_name = usize::try_from(timeline_index - 1)
+            .ok()
+            .and_then(|index| timeline_names.get(index));
 
-        if index < timeline_names.len() {
-            let timeline_name = timeline_names[index];
+        if let Some(timeline_name) = timeline_name {
             Ok(json!({
                 "success": true,
                 "result": format!("Retrieved timeline at index {}", timeline_index),
@@ -5722,20 +7251,20 @@ except Exception as e:
 
     async fn get_project_current_timeline(
         &self,
-        state: &mut ResolveState,
+        state: &StateView<'_>,
         _args: Value,
     ) -> ResolveResult<Value> {
         Ok(json!({
             "success": true,
             "result": "Retrieved current timeline",
-            "current_timeline": state.current_timeline,
+            "current_timeline": *state.current_timeline,
             "operation_id": format!("get_project_current_timeline_{}", chrono::Utc::now().timestamp())
         }))
     }
 
     async fn set_project_current_timeline(
         &self,
-        state: &mut ResolveState,
+        state: &mut StateView<'_>,
         args: Value,
     ) -> ResolveResult<Value> {
         let timeline_name = args["timeline_name"].as_str().ok_or_else(|| {
@@ -5743,7 +7272,7 @@ except Exception as e:
         })?;
 
         if state.timelines.contains_key(timeline_name) {
-            state.current_timeline = Some(timeline_name.to_string());
+            *state.current_timeline = Some(timeline_name.to_string());
             Ok(json!({
                 "success": true,
                 "result": format!("Set current timeline to '{}'", timeline_name),
@@ -5759,29 +7288,25 @@ except Exception as e:
         }
     }
 
-    async fn get_project_name(
-        &self,
-        state: &mut ResolveState,
-        _args: Value,
-    ) -> ResolveResult<Value> {
+    async fn get_project_name(&self, state: &StateView<'_>, _args: Value) -> ResolveResult<Value> {
         Ok(json!({
             "success": true,
             "result": "Retrieved project name",
-            "project_name": state.current_project,
+            "project_name": *state.current_project,
             "operation_id": format!("get_project_name_{}", chrono::Utc::now().timestamp())
         }))
     }
 
     async fn set_project_name(
         &self,
-        state: &mut ResolveState,
+        state: &mut StateView<'_>,
         args: Value,
     ) -> ResolveResult<Value> {
         let project_name = args["project_name"].as_str().ok_or_else(|| {
             ResolveError::invalid_parameter("project_name", "parameter is required")
         })?;
 
-        state.current_project = Some(project_name.to_string());
+        *state.current_project = Some(project_name.to_string());
         Ok(json!({
             "success": true,
             "result": format!("Set project name to '{}'", project_name),
@@ -5792,7 +7317,7 @@ except Exception as e:
 
     async fn get_project_unique_id(
         &self,
-        _state: &mut ResolveState,
+        _state: &StateView<'_>,
         _args: Value,
     ) -> ResolveResult<Value> {
         Ok(json!({
@@ -5805,7 +7330,7 @@ except Exception as e:
 
     async fn get_project_render_job_list(
         &self,
-        state: &mut ResolveState,
+        state: &StateView<'_>,
         _args: Value,
     ) -> ResolveResult<Value> {
         let job_list: Vec<&RenderJob> = state.render_state.render_queue.iter().collect();
@@ -5817,6 +7342,10 @@ except Exception as e:
                 "id": job.id,
                 "timeline_name": job.timeline_name,
                 "preset_name": job.preset_name,
+                "frame_range": job.frame_range,
+                "export_video": job.export_video,
+                "audio_channel_layout": job.audio_channel_layout,
+                "subtitles": job.subtitles.as_ref().map(|subtitles| subtitles.to_json(&job.output_path)),
                 "status": format!("{:?}", job.status)
             })).collect::<Vec<_>>(),
             "operation_id": format!("get_project_render_job_list_{}", chrono::Utc::now().timestamp())
@@ -5825,33 +7354,31 @@ except Exception as e:
 
     async fn start_project_rendering(
         &self,
-        state: &mut ResolveState,
+        state: &mut StateView<'_>,
         args: Value,
     ) -> ResolveResult<Value> {
         let _job_ids = args["job_ids"].as_array();
         let _is_interactive_mode = args["is_interactive_mode"].as_bool().unwrap_or(false);
 
         // Start rendering queued jobs
-        for job in &mut state.render_state.render_queue {
-            if matches!(job.status, RenderJobStatus::Queued) {
-                job.status = RenderJobStatus::Rendering;
-            }
-        }
+        let started_jobs = self.begin_renders(state);
 
         Ok(json!({
             "success": true,
             "result": "Started project rendering",
+            "started_jobs": started_jobs,
             "operation_id": format!("start_project_rendering_{}", chrono::Utc::now().timestamp())
         }))
     }
 
     async fn stop_project_rendering(
         &self,
-        state: &mut ResolveState,
+        state: &mut StateView<'_>,
         _args: Value,
     ) -> ResolveResult<Value> {
         // Stop all rendering jobs
-        for job in &mut state.render_state.render_queue {
+        let render_state = &mut *state.render_state;
+        for job in &mut render_state.render_queue {
             if matches!(job.status, RenderJobStatus::Rendering) {
                 job.status = RenderJobStatus::Queued;
             }
@@ -5866,7 +7393,7 @@ except Exception as e:
 
     async fn is_project_rendering_in_progress(
         &self,
-        state: &mut ResolveState,
+        state: &StateView<'_>,
         _args: Value,
     ) -> ResolveResult<Value> {
         let is_rendering = state
@@ -5885,7 +7412,7 @@ except Exception as e:
 
     async fn get_project_preset_list(
         &self,
-        state: &mut ResolveState,
+        state: &StateView<'_>,
         _args: Value,
     ) -> ResolveResult<Value> {
         let preset_names: Vec<&String> = state.render_state.render_presets.keys().collect();
@@ -5900,7 +7427,7 @@ except Exception as e:
 
     async fn load_project_render_preset(
         &self,
-        _state: &mut ResolveState,
+        _state: &StateView<'_>,
         args: Value,
     ) -> ResolveResult<Value> {
         let preset_name = args["preset_name"].as_str().ok_or_else(|| {
@@ -5917,7 +7444,7 @@ except Exception as e:
 
     async fn save_as_new_project_render_preset(
         &self,
-        state: &mut ResolveState,
+        state: &mut StateView<'_>,
         args: Value,
     ) -> ResolveResult<Value> {
         let preset_name = args["preset_name"].as_str().ok_or_else(|| {
@@ -5933,6 +7460,12 @@ except Exception as e:
             quality: RenderQuality::High,
             audio_codec: "AAC".to_string(),
             audio_bitrate: 320,
+            bit_depth: 8,
+            export_alpha: false,
+            export_video: true,
+            audio_channel_layout: Some(render_formats::DEFAULT_CHANNEL_LAYOUT.to_string()),
+            embed_chapters: false,
+            embed_metadata: false,
             created_at: chrono::Utc::now(),
         };
 
@@ -5951,21 +7484,29 @@ except Exception as e:
 
     async fn get_current_project_render_format_and_codec(
         &self,
-        _state: &mut ResolveState,
+        state: &StateView<'_>,
         _args: Value,
     ) -> ResolveResult<Value> {
+        let (format, codec) = match &state.render_state.format_and_codec {
+            Some((format, codec)) => (format.as_str(), codec.as_str()),
+            None => ("MOV", "H.264"),
+        };
+        let format = render_formats::require_format("format", format)?;
+        let codec = format.require_codec("codec", codec)?;
+
         Ok(json!({
             "success": true,
-            "result": "Retrieved current render format and codec",
-            "format": "QuickTime",
-            "codec": "H.264",
+            "result": format!("Current render format is {} with {}", format.name, codec.name),
+            "format": format.name,
+            "codec": codec.name,
+            "capabilities": format.to_json(),
             "operation_id": format!("get_current_project_render_format_and_codec_{}", chrono::Utc::now().timestamp())
         }))
     }
 
     async fn set_current_project_render_format_and_codec(
         &self,
-        _state: &mut ResolveState,
+        state: &mut StateView<'_>,
         args: Value,
     ) -> ResolveResult<Value> {
         let format = args["format"]
@@ -5974,19 +7515,24 @@ except Exception as e:
         let codec = args["codec"]
             .as_str()
             .ok_or_else(|| ResolveError::invalid_parameter("codec", "parameter is required"))?;
+        let format = render_formats::require_format("format", format)?;
+        let codec = format.require_codec("codec", codec)?;
+
+        state.render_state.format_and_codec =
+            Some((format.name.to_string(), codec.name.to_string()));
 
         Ok(json!({
             "success": true,
-            "result": format!("Set render format to '{}' and codec to '{}'", format, codec),
-            "format": format,
-            "codec": codec,
+            "result": format!("Set render format to '{}' and codec to '{}'", format.name, codec.name),
+            "format": format.name,
+            "codec": codec.name,
             "operation_id": format!("set_current_project_render_format_and_codec_{}", chrono::Utc::now().timestamp())
         }))
     }
 
     async fn get_current_project_render_mode(
         &self,
-        _state: &mut ResolveState,
+        _state: &StateView<'_>,
         _args: Value,
     ) -> ResolveResult<Value> {
         Ok(json!({
@@ -5999,7 +7545,7 @@ except Exception as e:
 
     async fn set_current_project_render_mode(
         &self,
-        _state: &mut ResolveState,
+        _state: &StateView<'_>,
         args: Value,
     ) -> ResolveResult<Value> {
         let render_mode = args["render_mode"].as_str().ok_or_else(|| {
@@ -6016,10 +7562,15 @@ except Exception as e:
 
     async fn get_project_color_groups_list(
         &self,
-        _state: &mut ResolveState,
+        state: &StateView<'_>,
         _args: Value,
     ) -> ResolveResult<Value> {
-        let color_groups = vec!["Group 1", "Group 2", "Group 3"];
+        let color_groups: Vec<Value> = state
+            .color_state
+            .color_groups
+            .iter()
+            .map(|(name, clips)| json!({ "group_name": name, "clips": clips }))
+            .collect();
         Ok(json!({
             "success": true,
             "result": "Retrieved project color groups list",
@@ -6031,29 +7582,57 @@ except Exception as e:
 
     async fn add_project_color_group(
         &self,
-        _state: &mut ResolveState,
+        state: &mut StateView<'_>,
         args: Value,
     ) -> ResolveResult<Value> {
         let group_name = args["group_name"].as_str().ok_or_else(|| {
             ResolveError::invalid_parameter("group_name", "parameter is required")
         })?;
+        if state.color_state.color_groups.contains_key(group_name) {
+            return Err(ResolveError::invalid_parameter(
+                "group_name",
+                format!("color group '{}' already exists", group_name),
+            ));
+        }
+        let clips = args["clip_names"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .map(|clip| {
+                let reference = clip.as_str().ok_or_else(|| {
+                    ResolveError::invalid_parameter("clip_names", "must be a list of strings")
+                })?;
+                Self::resolve_clip(state, reference)
+            })
+            .collect::<ResolveResult<Vec<String>>>()?;
+        state
+            .color_state
+            .color_groups
+            .insert(group_name.to_string(), clips.clone());
 
         Ok(json!({
             "success": true,
             "result": format!("Added project color group '{}'", group_name),
             "group_name": group_name,
+            "clips": clips,
             "operation_id": format!("add_project_color_group_{}", chrono::Utc::now().timestamp())
         }))
     }
 
     async fn delete_project_color_group(
         &self,
-        _state: &mut ResolveState,
+        state: &mut StateView<'_>,
         args: Value,
     ) -> ResolveResult<Value> {
         let group_name = args["group_name"].as_str().ok_or_else(|| {
             ResolveError::invalid_parameter("group_name", "parameter is required")
         })?;
+        if state.color_state.color_groups.remove(group_name).is_none() {
+            return Err(ResolveError::invalid_parameter(
+                "group_name",
+                format!("color group '{}' not found", group_name),
+            ));
+        }
 
         Ok(json!({
             "success": true,
@@ -6062,6 +7641,43 @@ except Exception as e:
             "operation_id": format!("delete_project_color_group_{}", chrono::Utc::now().timestamp())
         }))
     }
+
+    // ---- NEW: Resource Limits ----
+    async fn get_resource_usage(
+        &self,
+        state: &StateView<'_>,
+        _args: Value,
+    ) -> ResolveResult<Value> {
+        let limits = &self.config.limits;
+        let max_markers = state
+            .timelines
+            .values()
+            .map(|timeline| timeline.markers.len())
+            .max()
+            .unwrap_or(0);
+        let max_keyframes = state
+            .keyframe_state
+            .timeline_item_keyframes
+            .values()
+            .map(|item| {
+                item.property_keyframes
+                    .values()
+                    .map(Vec::len)
+                    .sum::<usize>()
+            })
+            .max()
+            .unwrap_or(0);
+        let usage = |used: usize, limit: usize| json!({"used": used, "limit": limit});
+
+        Ok(json!({
+            "result": "Retrieved resource usage",
+            "timelines": usage(state.timelines.len(), limits.max_timelines),
+            "clips": usage(state.media_pool.clips.len(), limits.max_clips),
+            "markers_per_timeline": usage(max_markers, limits.max_markers_per_timeline),
+            "keyframes_per_item": usage(max_keyframes, limits.max_keyframes_per_item),
+            "operation_id": Uuid::new_v4().to_string()
+        }))
+    }
 }
 
 impl ResolveState {
@@ -6088,11 +7704,17 @@ impl ResolveState {
         let resolution_height = args["resolution_height"].as_i64().map(|i| i as i32);
 
         let timeline = Timeline {
+            id: Uuid::new_v4().to_string(),
             name: name.clone(),
             frame_rate,
+            start_timecode: args["start_timecode"].as_str().map(|s| s.to_string()),
             resolution_width,
             resolution_height,
             markers: Vec::new(),
+            playhead: None,
+            subtitle_tracks: Vec::new(),
+            lineage: None,
+            output: Default::default(),
         };
 
         self.timelines.insert(name.clone(), timeline);
@@ -6108,9 +7730,12 @@ impl ResolveState {
         let note = args["note"].as_str().unwrap_or("").to_string();
 
         let marker = Marker {
+            id: Uuid::new_v4().to_string(),
             frame,
             color: color.clone(),
             note: note.clone(),
+            name: String::new(),
+            duration: 1,
         };
 
         if let Some(timeline_name) = &self.current_timeline {
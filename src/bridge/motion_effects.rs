@@ -0,0 +1,436 @@
+//! Noise reduction and motion blur on grade nodes
+//!
+//! Each node of a clip grade carries the controls of the color page's Motion
+//! Effects palette: temporal noise reduction, spatial noise reduction and
+//! motion blur. Every control has the range or choices of its palette
+//! counterpart and reads as the palette default until set, so a node with
+//! nothing set adds no effect.
+//!
+//! Resolve's scripting API does not reach the Motion Effects palette, so in
+//! Real mode these calls fall back to the grade model like the other color
+//! calls without an API equivalent. Responses name each control as the
+//! palette labels it, for applying the values through a PowerGrade or DRX.
+
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+use std::collections::BTreeMap;
+use uuid::Uuid;
+
+use super::{ResolveBridge, StateView};
+use crate::error::{ResolveError, ResolveResult};
+
+/// Group of controls in the Motion Effects palette
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+pub(super) enum Section {
+    TemporalNr,
+    SpatialNr,
+    MotionBlur,
+}
+
+impl Section {
+    const ALL: [Section; 3] = [Section::TemporalNr, Section::SpatialNr, Section::MotionBlur];
+
+    fn name(self) -> &'static str {
+        match self {
+            Section::TemporalNr => "temporal_nr",
+            Section::SpatialNr => "spatial_nr",
+            Section::MotionBlur => "motion_blur",
+        }
+    }
+}
+
+/// Values a control accepts, with the palette default
+enum Values {
+    Integer {
+        min: i64,
+        max: i64,
+        default: i64,
+    },
+    Number {
+        min: f64,
+        max: f64,
+        default: f64,
+    },
+    Choice {
+        choices: &'static [&'static str],
+        default: &'static str,
+    },
+}
+
+/// One control of the Motion Effects palette
+struct Control {
+    section: Section,
+    /// Name in responses and, with the section prefix for noise reduction, in arguments
+    name: &'static str,
+    /// The control as the palette labels it
+    label: &'static str,
+    values: Values,
+}
+
+const MOTION_ESTIMATION: &[&str] = &["none", "faster", "better"];
+const MOTION_RANGE: &[&str] = &["small", "medium", "large"];
+const THRESHOLD: Values = Values::Number {
+    min: 0.0,
+    max: 100.0,
+    default: 0.0,
+};
+
+const CONTROLS: &[Control] = &[
+    Control {
+        section: Section::TemporalNr,
+        name: "frames",
+        label: "Temporal NR > Frames",
+        values: Values::Integer {
+            min: 0,
+            max: 5,
+            default: 0,
+        },
+    },
+    Control {
+        section: Section::TemporalNr,
+        name: "motion_estimation",
+        label: "Temporal NR > Mo. Est. Type",
+        values: Values::Choice {
+            choices: MOTION_ESTIMATION,
+            default: "faster",
+        },
+    },
+    Control {
+        section: Section::TemporalNr,
+        name: "motion_range",
+        label: "Temporal NR > Motion Range",
+        values: Values::Choice {
+            choices: MOTION_RANGE,
+            default: "medium",
+        },
+    },
+    Control {
+        section: Section::TemporalNr,
+        name: "luma_threshold",
+        label: "Temporal Threshold > Luma",
+        values: THRESHOLD,
+    },
+    Control {
+        section: Section::TemporalNr,
+        name: "chroma_threshold",
+        label: "Temporal Threshold > Chroma",
+        values: THRESHOLD,
+    },
+    Control {
+        section: Section::TemporalNr,
+        name: "motion_threshold",
+        label: "Temporal Threshold > Motion",
+        values: Values::Number {
+            min: 0.0,
+            max: 100.0,
+            default: 50.0,
+        },
+    },
+    Control {
+        section: Section::TemporalNr,
+        name: "blend",
+        label: "Temporal Threshold > Blend",
+        values: THRESHOLD,
+    },
+    Control {
+        section: Section::SpatialNr,
+        name: "mode",
+        label: "Spatial NR > Mode",
+        values: Values::Choice {
+            choices: &["faster", "better", "enhanced"],
+            default: "faster",
+        },
+    },
+    Control {
+        section: Section::SpatialNr,
+        name: "radius",
+        label: "Spatial NR > Radius",
+        values: Values::Choice {
+            choices: MOTION_RANGE,
+            default: "medium",
+        },
+    },
+    Control {
+        section: Section::SpatialNr,
+        name: "luma_threshold",
+        label: "Spatial Threshold > Luma",
+        values: THRESHOLD,
+    },
+    Control {
+        section: Section::SpatialNr,
+        name: "chroma_threshold",
+        label: "Spatial Threshold > Chroma",
+        values: THRESHOLD,
+    },
+    Control {
+        section: Section::SpatialNr,
+        name: "blend",
+        label: "Spatial Threshold > Blend",
+        values: THRESHOLD,
+    },
+    Control {
+        section: Section::MotionBlur,
+        name: "motion_estimation",
+        label: "Motion Blur > Mo. Est. Type",
+        values: Values::Choice {
+            choices: &["faster", "better"],
+            default: "faster",
+        },
+    },
+    Control {
+        section: Section::MotionBlur,
+        name: "motion_range",
+        label: "Motion Blur > Motion Range",
+        values: Values::Choice {
+            choices: MOTION_RANGE,
+            default: "medium",
+        },
+    },
+    Control {
+        section: Section::MotionBlur,
+        name: "amount",
+        label: "Motion Blur > Motion Blur",
+        values: THRESHOLD,
+    },
+];
+
+impl Control {
+    /// Argument the control is set with
+    fn arg(&self) -> String {
+        match self.section {
+            Section::TemporalNr => format!("temporal_{}", self.name),
+            Section::SpatialNr => format!("spatial_{}", self.name),
+            Section::MotionBlur => self.name.to_string(),
+        }
+    }
+
+    fn default_value(&self) -> Value {
+        match self.values {
+            Values::Integer { default, .. } => json!(default),
+            Values::Number { default, .. } => json!(default),
+            Values::Choice { default, .. } => json!(default),
+        }
+    }
+
+    fn parse(&self, value: &Value) -> ResolveResult<Value> {
+        let arg = self.arg();
+        match self.values {
+            Values::Integer { min, max, .. } => value
+                .as_i64()
+                .filter(|value| (min..=max).contains(value))
+                .map(|value| json!(value))
+                .ok_or_else(|| {
+                    ResolveError::invalid_parameter(
+                        &arg,
+                        format!("must be a whole number from {} to {}", min, max),
+                    )
+                }),
+            Values::Number { min, max, .. } => value
+                .as_f64()
+                .filter(|value| (min..=max).contains(value))
+                .map(|value| json!(value))
+                .ok_or_else(|| {
+                    ResolveError::invalid_parameter(
+                        &arg,
+                        format!("must be a number from {} to {}", min, max),
+                    )
+                }),
+            Values::Choice { choices, .. } => value
+                .as_str()
+                .map(str::to_ascii_lowercase)
+                .filter(|value| choices.contains(&value.as_str()))
+                .map(|value| json!(value))
+                .ok_or_else(|| {
+                    ResolveError::invalid_parameter(
+                        &arg,
+                        format!("must be one of {}", choices.join(", ")),
+                    )
+                }),
+        }
+    }
+}
+
+/// Motion Effects controls set on one node, by section and control name
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub(super) struct NodeMotionEffects {
+    values: BTreeMap<(Section, &'static str), Value>,
+}
+
+impl NodeMotionEffects {
+    fn get(&self, control: &Control) -> Value {
+        self.values
+            .get(&(control.section, control.name))
+            .cloned()
+            .unwrap_or_else(|| control.default_value())
+    }
+
+    fn number(&self, section: Section, name: &str) -> f64 {
+        CONTROLS
+            .iter()
+            .find(|control| control.section == section && control.name == name)
+            .and_then(|control| self.get(control).as_f64())
+            .unwrap_or(0.0)
+    }
+
+    /// Whether a section changes the picture with these values
+    fn active(&self, section: Section) -> bool {
+        match section {
+            Section::TemporalNr => self.number(section, "frames") > 0.0,
+            Section::SpatialNr => {
+                self.number(section, "luma_threshold") > 0.0
+                    || self.number(section, "chroma_threshold") > 0.0
+            }
+            Section::MotionBlur => self.number(section, "amount") > 0.0,
+        }
+    }
+
+    /// Every control by section, with whether each section is active
+    pub(super) fn to_json(&self) -> Value {
+        let mut sections = Map::new();
+        for section in Section::ALL {
+            let mut fields = Map::new();
+            fields.insert("active".to_string(), json!(self.active(section)));
+            for control in CONTROLS.iter().filter(|control| control.section == section) {
+                fields.insert(control.name.to_string(), self.get(control));
+            }
+            sections.insert(section.name().to_string(), Value::Object(fields));
+        }
+        Value::Object(sections)
+    }
+
+    /// Values set on the node, for hashing a grade
+    pub(super) fn set_values(&self) -> impl Iterator<Item = (&(Section, &'static str), &Value)> {
+        self.values.iter()
+    }
+}
+
+impl ResolveBridge {
+    pub(super) async fn set_noise_reduction(
+        &self,
+        state: &mut StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        self.set_motion_effects(state, args, &[Section::TemporalNr, Section::SpatialNr])
+    }
+
+    pub(super) async fn set_motion_blur(
+        &self,
+        state: &mut StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        self.set_motion_effects(state, args, &[Section::MotionBlur])
+    }
+
+    pub(super) async fn get_motion_effects(
+        &self,
+        state: &mut StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        let clip_name = Self::required_grade_clip(state, &args)?;
+        let node_index = Self::grade_node(state, &args)?;
+        let effects = state
+            .color_state
+            .clip_grades
+            .get(&clip_name)
+            .and_then(|grade| grade.motion_effects.get(&node_index))
+            .cloned()
+            .unwrap_or_default();
+        Ok(json!({
+            "result": format!("Read motion effects of node {} on clip '{}'", node_index, clip_name),
+            "clip_name": clip_name,
+            "node_index": node_index,
+            "motion_effects": effects.to_json(),
+            "operation_id": Uuid::new_v4().to_string()
+        }))
+    }
+
+    /// Node a node-level grade call acts on: `node_index` or the current node
+    pub(super) fn grade_node(state: &StateView<'_>, args: &Value) -> ResolveResult<i32> {
+        match &args["node_index"] {
+            Value::Null => Ok(state.color_state.current_node_index.max(1)),
+            value => value
+                .as_i64()
+                .filter(|index| (1..=i32::MAX as i64).contains(index))
+                .map(|index| index as i32)
+                .ok_or_else(|| ResolveError::invalid_parameter("node_index", "must be 1 or more")),
+        }
+    }
+
+    /// Validate every given control of the sections, then set them together
+    fn set_motion_effects(
+        &self,
+        state: &mut StateView<'_>,
+        args: Value,
+        sections: &[Section],
+    ) -> ResolveResult<Value> {
+        let clip_name = Self::required_grade_clip(state, &args)?;
+        let node_index = Self::grade_node(state, &args)?;
+        let mut changes = Vec::new();
+        for control in CONTROLS
+            .iter()
+            .filter(|control| sections.contains(&control.section))
+        {
+            let value = &args[control.arg().as_str()];
+            if !value.is_null() {
+                changes.push((control, control.parse(value)?));
+            }
+        }
+        if changes.is_empty() {
+            let args: Vec<String> = CONTROLS
+                .iter()
+                .filter(|control| sections.contains(&control.section))
+                .map(Control::arg)
+                .collect();
+            return Err(ResolveError::invalid_parameter(
+                &args[0],
+                format!("set at least one of {}", args.join(", ")),
+            ));
+        }
+
+        let effects = state
+            .color_state
+            .clip_grades
+            .entry(clip_name.clone())
+            .or_default()
+            .motion_effects
+            .entry(node_index)
+            .or_default();
+        let changed: Vec<Value> = changes
+            .into_iter()
+            .map(|(control, value)| {
+                effects
+                    .values
+                    .insert((control.section, control.name), value.clone());
+                json!({
+                    "control": control.arg(),
+                    "resolve_control": control.label,
+                    "value": value
+                })
+            })
+            .collect();
+        let motion_effects = effects.to_json();
+        let sections: Map<String, Value> = sections
+            .iter()
+            .map(|section| {
+                (
+                    section.name().to_string(),
+                    motion_effects[section.name()].clone(),
+                )
+            })
+            .collect();
+
+        Ok(json!({
+            "result": format!(
+                "Set {} motion effect controls on node {} of clip '{}'",
+                changed.len(),
+                node_index,
+                clip_name
+            ),
+            "clip_name": clip_name,
+            "node_index": node_index,
+            "changed": changed,
+            "motion_effects": sections,
+            "operation_id": Uuid::new_v4().to_string()
+        }))
+    }
+}
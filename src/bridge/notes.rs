@@ -0,0 +1,233 @@
+//! Free-text notes on projects, timelines and clips
+//!
+//! Notes let an agent leave context for the editor inside the project itself.
+//! Clip notes are Resolve's "Comments" metadata field and read back through it;
+//! Resolve has no such field for projects and timelines, so those notes live in
+//! the bridge state. An empty note removes it.
+
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use uuid::Uuid;
+
+use super::rename_batch::{rekey, Renames};
+use super::{ResolveBridge, StateView};
+use crate::error::{ResolveError, ResolveResult};
+
+/// Clip metadata field that holds clip notes
+pub(super) const CLIP_NOTES_METADATA: &str = "Comments";
+
+/// Notes by project, timeline and clip name
+#[derive(Debug, Clone, Default, Serialize)]
+pub(super) struct NoteState {
+    projects: BTreeMap<String, String>,
+    timelines: BTreeMap<String, String>,
+    clips: BTreeMap<String, String>,
+}
+
+impl NoteState {
+    /// Notes on a media pool clip, if any
+    pub(super) fn clip(&self, clip_name: &str) -> Option<&str> {
+        self.clips.get(clip_name).map(String::as_str)
+    }
+
+    /// Replace the notes on a clip; empty notes remove them
+    pub(super) fn set_clip(&mut self, clip_name: &str, notes: String) {
+        if notes.is_empty() {
+            self.clips.remove(clip_name);
+        } else {
+            self.clips.insert(clip_name.to_string(), notes);
+        }
+    }
+
+    /// Keep the notes of renamed clips and timelines with them
+    pub(super) fn rename(&mut self, clips: &Renames, timelines: &Renames) {
+        rekey(&mut self.clips, clips);
+        rekey(&mut self.timelines, timelines);
+    }
+}
+
+/// What a notes request applies to
+enum NoteTarget {
+    Project(String),
+    Timeline(String),
+    Clip(String),
+}
+
+impl NoteTarget {
+    /// The named target, or the current project when none is named
+    fn from_args(state: &StateView<'_>, args: &Value) -> ResolveResult<Self> {
+        match (
+            args["project_name"].as_str(),
+            args["timeline_name"].as_str(),
+            args["clip_name"].as_str(),
+        ) {
+            (Some(project), None, None) => {
+                if !state.projects.iter().any(|name| name == project) {
+                    return Err(ResolveError::invalid_parameter(
+                        "project_name",
+                        format!("project '{}' not found", project),
+                    ));
+                }
+                Ok(Self::Project(project.to_string()))
+            }
+            (None, Some(timeline), None) => {
+                if !state.timelines.contains_key(timeline) {
+                    return Err(ResolveError::invalid_parameter(
+                        "timeline_name",
+                        format!("timeline '{}' not found", timeline),
+                    ));
+                }
+                Ok(Self::Timeline(timeline.to_string()))
+            }
+            (None, None, Some(clip_name)) => {
+                Ok(Self::Clip(ResolveBridge::resolve_clip(state, clip_name)?))
+            }
+            (None, None, None) => {
+                state
+                    .current_project
+                    .clone()
+                    .map(Self::Project)
+                    .ok_or_else(|| {
+                        ResolveError::invalid_parameter(
+                            "project_name",
+                            "no project is open; name a project, timeline or clip",
+                        )
+                    })
+            }
+            _ => Err(ResolveError::invalid_parameter(
+                "clip_name",
+                "at most one of project_name, timeline_name or clip_name is allowed",
+            )),
+        }
+    }
+
+    fn notes_mut<'a>(&self, notes: &'a mut NoteState) -> &'a mut BTreeMap<String, String> {
+        match self {
+            Self::Project(_) => &mut notes.projects,
+            Self::Timeline(_) => &mut notes.timelines,
+            Self::Clip(_) => &mut notes.clips,
+        }
+    }
+
+    fn notes<'a>(&self, notes: &'a NoteState) -> Option<&'a String> {
+        match self {
+            Self::Project(name) => notes.projects.get(name),
+            Self::Timeline(name) => notes.timelines.get(name),
+            Self::Clip(name) => notes.clips.get(name),
+        }
+    }
+
+    fn key(&self) -> &str {
+        match self {
+            Self::Project(name) | Self::Timeline(name) | Self::Clip(name) => name,
+        }
+    }
+
+    fn describe(&self) -> String {
+        match self {
+            Self::Project(name) => format!("project '{}'", name),
+            Self::Timeline(name) => format!("timeline '{}'", name),
+            Self::Clip(name) => format!("clip '{}'", name),
+        }
+    }
+
+    fn to_json(&self, notes: Option<&String>) -> Value {
+        let (target_type, field, metadata) = match self {
+            Self::Project(_) => ("project", "project_name", None),
+            Self::Timeline(_) => ("timeline", "timeline_name", None),
+            Self::Clip(_) => ("clip", "clip_name", Some(CLIP_NOTES_METADATA)),
+        };
+        json!({
+            "target_type": target_type,
+            field: self.key(),
+            "notes": notes,
+            "resolve_metadata": metadata
+        })
+    }
+}
+
+impl ResolveBridge {
+    pub(super) async fn set_notes(
+        &self,
+        state: &mut StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        let notes = args["notes"]
+            .as_str()
+            .ok_or_else(|| ResolveError::invalid_parameter("notes", "required string"))?
+            .trim()
+            .to_string();
+        let target = NoteTarget::from_args(state, &args)?;
+
+        let map = target.notes_mut(&mut state.notes);
+        let previous = if notes.is_empty() {
+            map.remove(target.key())
+        } else {
+            map.insert(target.key().to_string(), notes.clone())
+        };
+        let result = if notes.is_empty() {
+            format!("Cleared notes on {}", target.describe())
+        } else {
+            format!("Set notes on {}", target.describe())
+        };
+
+        let mut response = target.to_json(target.notes(&state.notes));
+        response["result"] = json!(result);
+        response["previous_notes"] = json!(previous);
+        response["operation_id"] = json!(Uuid::new_v4().to_string());
+        Ok(response)
+    }
+
+    pub(super) async fn get_notes(
+        &self,
+        state: &StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        let named = ["project_name", "timeline_name", "clip_name"]
+            .iter()
+            .any(|field| !args[*field].is_null());
+        if named || !args["all"].as_bool().unwrap_or(false) {
+            let target = NoteTarget::from_args(state, &args)?;
+            let notes = target.notes(&state.notes);
+            let mut response = target.to_json(notes);
+            response["result"] = json!(match notes {
+                Some(_) => format!("Notes on {}", target.describe()),
+                None => format!("No notes on {}", target.describe()),
+            });
+            response["operation_id"] = json!(Uuid::new_v4().to_string());
+            return Ok(response);
+        }
+
+        // Every note; notes on timelines or clips that have since been removed are left out
+        let timelines: BTreeMap<&String, &String> = state
+            .notes
+            .timelines
+            .iter()
+            .filter(|(name, _)| state.timelines.contains_key(*name))
+            .collect();
+        let clips: BTreeMap<&String, &String> = state
+            .notes
+            .clips
+            .iter()
+            .filter(|(name, _)| state.media_pool.clips.contains_key(*name))
+            .collect();
+        let project = state
+            .current_project
+            .as_ref()
+            .and_then(|name| state.notes.projects.get(name));
+        Ok(json!({
+            "result": format!(
+                "Found notes on {} timelines and {} clips{}",
+                timelines.len(),
+                clips.len(),
+                if project.is_some() { " and the project" } else { "" }
+            ),
+            "project_name": state.current_project.as_ref(),
+            "project_notes": project,
+            "timelines": timelines,
+            "clips": clips,
+            "operation_id": Uuid::new_v4().to_string()
+        }))
+    }
+}
@@ -0,0 +1,254 @@
+//! Sorting clips into bins by rules
+//!
+//! auto_organize_media moves media pool clips into bins chosen from what is
+//! known about each clip: the day it was recorded, its camera, its reel, its
+//! frame size or any metadata field such as Scene. Rules come from the call
+//! or from `[metadata] bin_rules` in the config and are tried in order; the
+//! first that reads a value for a clip names its bin, `Scene {value}` making
+//! `Scene 12`. Bins are created as needed. A clip no rule reads a value for
+//! stays where it is.
+//!
+//! A dry run reports the moves without making them.
+
+use serde_json::{json, Value};
+use std::collections::BTreeSet;
+use std::path::Path;
+use uuid::Uuid;
+
+use super::{metadata_schema, Bin, Clip, ResolveBridge, StateView};
+use crate::config::{BinRule, BinRuleKey};
+use crate::error::{ResolveError, ResolveResult};
+
+/// Frame size the simulation reports for clips, as get_media_pool_item_metadata does
+const SIMULATED_RESOLUTION: &str = "1920x1080";
+
+/// Metadata fields a clip's recording day is read from, in order
+const DATE_FIELDS: [&str; 2] = ["Date Recorded", "Shoot Date"];
+
+/// Metadata fields a clip's camera is read from, in order
+const CAMERA_FIELDS: [&str; 2] = ["Camera #", "Camera Type"];
+
+/// The day part of a recorded date, as YYYY-MM-DD; camera metadata writes
+/// dates like `2026:10:16 09:30:00`
+fn day(date: &str) -> String {
+    date.split([' ', 'T'])
+        .next()
+        .unwrap_or_default()
+        .replace([':', '/', '.'], "-")
+}
+
+/// Day the media file was last written, for clips without a recorded date
+fn file_day(path: &str) -> Option<String> {
+    let modified = std::fs::metadata(Path::new(path)).ok()?.modified().ok()?;
+    let modified: chrono::DateTime<chrono::Utc> = modified.into();
+    Some(modified.format("%Y-%m-%d").to_string())
+}
+
+/// The value `rule` sorts `clip` by, if the clip has one
+fn rule_value(state: &StateView<'_>, clip: &Clip, rule: &BinRule) -> Option<String> {
+    let field = |field: &str| clip.metadata.get(field).cloned();
+    let value = match rule.by {
+        BinRuleKey::Date => DATE_FIELDS
+            .iter()
+            .find_map(|name| field(name))
+            .map(|date| day(&date))
+            .or_else(|| file_day(&clip.file_path)),
+        BinRuleKey::Camera => CAMERA_FIELDS.iter().find_map(|name| field(name)),
+        BinRuleKey::Reel => clip.reel_name.clone(),
+        BinRuleKey::Resolution => {
+            field("Resolution").or_else(|| Some(SIMULATED_RESOLUTION.to_string()))
+        }
+        BinRuleKey::Metadata => metadata_schema::clip_value(
+            state,
+            &clip.name,
+            rule.field.as_deref().unwrap_or_default().trim(),
+        ),
+    };
+    value
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+fn rule_key(key: BinRuleKey) -> &'static str {
+    match key {
+        BinRuleKey::Date => "date",
+        BinRuleKey::Camera => "camera",
+        BinRuleKey::Reel => "reel",
+        BinRuleKey::Resolution => "resolution",
+        BinRuleKey::Metadata => "metadata",
+    }
+}
+
+fn rule_json(rule: &BinRule) -> Value {
+    json!({
+        "by": rule_key(rule.by),
+        "field": rule.field,
+        "bin": rule.bin
+    })
+}
+
+/// Rules given to the call, which may leave out `field` and `bin`
+fn parse_rules(rules: &[Value]) -> ResolveResult<Vec<BinRule>> {
+    rules
+        .iter()
+        .enumerate()
+        .map(|(index, rule)| {
+            let param = format!("rules[{}]", index);
+            let mut rule = rule.clone();
+            if let Some(fields) = rule.as_object_mut() {
+                fields.retain(|_, value| !value.is_null());
+            }
+            let rule: BinRule = serde_json::from_value(rule)
+                .map_err(|e| ResolveError::invalid_parameter(&param, e.to_string()))?;
+            match rule.problem() {
+                Some(problem) => Err(ResolveError::invalid_parameter(&param, problem)),
+                None => Ok(rule),
+            }
+        })
+        .collect()
+}
+
+impl ResolveBridge {
+    pub(super) async fn auto_organize_media(
+        &self,
+        state: &mut StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        if state.current_project.is_none() {
+            return Err(ResolveError::NotRunning);
+        }
+        let (rules, rules_from) = match args["rules"].as_array() {
+            Some(rules) if !rules.is_empty() => (parse_rules(rules)?, "arguments"),
+            _ => (self.config.metadata.bin_rules.clone(), "config"),
+        };
+        if rules.is_empty() {
+            return Err(ResolveError::invalid_parameter(
+                "rules",
+                "no rules given, and none are configured in [metadata] bin_rules",
+            ));
+        }
+        let dry_run = args["dry_run"].as_bool().unwrap_or(false);
+
+        let clip_names: Vec<String> = match args["clip_names"].as_array() {
+            Some(names) => {
+                let names: Vec<String> = names
+                    .iter()
+                    .filter_map(Value::as_str)
+                    .map(str::to_string)
+                    .collect();
+                if let Some(missing) = names
+                    .iter()
+                    .find(|name| !state.media_pool.clips.contains_key(*name))
+                {
+                    return Err(ResolveError::MediaNotFound {
+                        name: missing.clone(),
+                    });
+                }
+                names
+            }
+            None => {
+                let mut names: Vec<String> = state.media_pool.clips.keys().cloned().collect();
+                names.sort();
+                names
+            }
+        };
+
+        let mut moves = Vec::new();
+        let mut unchanged = 0;
+        let mut unmatched = Vec::new();
+        for clip_name in &clip_names {
+            let Some(clip) = state.media_pool.clips.get(clip_name) else {
+                continue;
+            };
+            let found = rules.iter().enumerate().find_map(|(index, rule)| {
+                rule_value(state, clip, rule).map(|value| (index, rule, value))
+            });
+            let Some((index, rule, value)) = found else {
+                unmatched.push(clip_name.clone());
+                continue;
+            };
+            let bin = rule.bin.replace("{value}", &value).trim().to_string();
+            if clip.bin.as_deref() == Some(bin.as_str()) {
+                unchanged += 1;
+                continue;
+            }
+            moves.push((
+                clip_name.clone(),
+                clip.bin.clone(),
+                bin,
+                index,
+                rule.by,
+                value,
+            ));
+        }
+
+        let existing: BTreeSet<&String> = state.media_pool.bins.keys().collect();
+        let created: Vec<String> = moves
+            .iter()
+            .map(|(_, _, bin, ..)| bin)
+            .filter(|bin| !existing.contains(bin))
+            .cloned()
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+
+        if !dry_run {
+            let media_pool = &mut *state.media_pool;
+            for bin in &created {
+                media_pool.bins.insert(
+                    bin.clone(),
+                    Bin {
+                        id: Uuid::new_v4().to_string(),
+                        name: bin.clone(),
+                        clips: Vec::new(),
+                        color_rules: Vec::new(),
+                    },
+                );
+            }
+            for (clip_name, from, to, ..) in &moves {
+                if let Some(old) = from.as_ref().and_then(|from| media_pool.bins.get_mut(from)) {
+                    old.clips.retain(|name| name != clip_name);
+                }
+                if let Some(new) = media_pool.bins.get_mut(to) {
+                    new.clips.push(clip_name.clone());
+                }
+                if let Some(clip) = media_pool.clips.get_mut(clip_name) {
+                    clip.bin = Some(to.clone());
+                }
+            }
+        }
+
+        let moves: Vec<Value> = moves
+            .into_iter()
+            .map(|(clip_name, from, to, index, by, value)| {
+                json!({
+                    "clip_name": clip_name,
+                    "from_bin": from,
+                    "to_bin": to,
+                    "rule": index + 1,
+                    "by": rule_key(by),
+                    "value": value
+                })
+            })
+            .collect();
+        Ok(json!({
+            "result": format!(
+                "{} {} of {} clips into bins, creating {} bins; {} were already in place and {} matched no rule",
+                if dry_run { "Would move" } else { "Moved" },
+                moves.len(),
+                clip_names.len(),
+                created.len(),
+                unchanged,
+                unmatched.len()
+            ),
+            "dry_run": dry_run,
+            "rules_from": rules_from,
+            "rules": rules.iter().map(rule_json).collect::<Vec<_>>(),
+            "moves": moves,
+            "created_bins": created,
+            "unchanged": unchanged,
+            "unmatched_clips": unmatched,
+            "operation_id": Uuid::new_v4().to_string()
+        }))
+    }
+}
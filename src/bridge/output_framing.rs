@@ -0,0 +1,218 @@
+//! Output blanking and reframing
+//!
+//! A timeline's output can be blanked to a wider aspect ratio, the 2.39:1 or
+//! 1.85:1 bars of a cinema delivery, and reframed to a vertical or square
+//! frame for social media. Both are timeline settings: render jobs queued from
+//! the timeline take the framing it has at that moment, reframing the preset's
+//! resolution and blanking inside the reframed picture, and previews are made
+//! at the reframed aspect ratio.
+
+use serde::Serialize;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use super::{ResolveBridge, StateView, Timeline};
+use crate::error::{ResolveError, ResolveResult};
+
+/// Blanking presets by name
+const BLANKING_PRESETS: [(&str, f64); 2] = [("2.39:1", 2.39), ("1.85:1", 1.85)];
+
+/// Narrowest and widest custom blanking ratios accepted
+const CUSTOM_RATIO_RANGE: (f64, f64) = (0.25, 4.0);
+
+/// Reframe presets by name, as width and height of the frame
+const REFRAME_PRESETS: [(&str, (u32, u32)); 2] = [("vertical", (9, 16)), ("square", (1, 1))];
+
+/// Blanking and reframing of a timeline's output
+#[derive(Debug, Clone, Default, Serialize)]
+pub(super) struct OutputFraming {
+    /// Blanking preset name, or the ratio as "2.00:1" when custom, and the
+    /// aspect ratio it leaves visible
+    blanking: Option<(String, f64)>,
+    /// Reframe preset name
+    reframe: Option<&'static str>,
+}
+
+/// Round down to an even number of pixels, as codecs need
+fn even(pixels: f64) -> u32 {
+    ((pixels / 2.0).floor() as u32 * 2).max(2)
+}
+
+/// Size of a source frame of `width` by `height` reframed to `preset`, which
+/// keeps the source's shorter side
+pub(super) fn reframed_size(preset: &str, (width, height): (u32, u32)) -> (u32, u32) {
+    let Some((across, down)) = REFRAME_PRESETS
+        .iter()
+        .find(|(name, _)| *name == preset)
+        .map(|(_, frame)| *frame)
+    else {
+        return (width, height);
+    };
+    let short = f64::from(width.min(height));
+    if across <= down {
+        (
+            even(short),
+            even(short * f64::from(down) / f64::from(across)),
+        )
+    } else {
+        (
+            even(short * f64::from(across) / f64::from(down)),
+            even(short),
+        )
+    }
+}
+
+impl OutputFraming {
+    /// Size of the output for a source frame of `width` by `height`
+    pub(super) fn output_size(&self, source: (u32, u32)) -> (u32, u32) {
+        match self.reframe {
+            Some(preset) => reframed_size(preset, source),
+            None => source,
+        }
+    }
+
+    /// Output size and blanking bars for a source frame of `width` by `height`
+    pub(super) fn to_json(&self, source: (u32, u32)) -> Value {
+        let (width, height) = self.output_size(source);
+        let blanking = self.blanking.as_ref().map(|(name, ratio)| {
+            let aspect = f64::from(width) / f64::from(height);
+            // Wider blanking bars the top and bottom, narrower the sides
+            let (visible_width, visible_height) = if *ratio >= aspect {
+                (width, even(f64::from(width) / ratio).min(height))
+            } else {
+                (even(f64::from(height) * ratio).min(width), height)
+            };
+            json!({
+                "preset": name,
+                "aspect_ratio": ratio,
+                "visible_width": visible_width,
+                "visible_height": visible_height,
+                "bar_top_bottom": (height - visible_height) / 2,
+                "bar_left_right": (width - visible_width) / 2
+            })
+        });
+        json!({
+            "reframe": self.reframe,
+            "width": width,
+            "height": height,
+            "blanking": blanking
+        })
+    }
+}
+
+/// Frame size of a timeline, 1920x1080 when not set
+pub(super) fn timeline_size(timeline: &Timeline) -> (u32, u32) {
+    match (timeline.resolution_width, timeline.resolution_height) {
+        (Some(width), Some(height)) if width > 0 && height > 0 => (width as u32, height as u32),
+        _ => (1920, 1080),
+    }
+}
+
+/// Blanking asked for by a set_output_blanking call; None clears it
+fn blanking_argument(args: &Value) -> ResolveResult<Option<(String, f64)>> {
+    let aspect = args["aspect"]
+        .as_str()
+        .ok_or_else(|| ResolveError::invalid_parameter("aspect", "required string"))?;
+    match aspect {
+        "none" => Ok(None),
+        "custom" => {
+            let ratio = args["custom_ratio"].as_f64().ok_or_else(|| {
+                ResolveError::invalid_parameter("custom_ratio", "required number for 'custom'")
+            })?;
+            let (narrowest, widest) = CUSTOM_RATIO_RANGE;
+            if !(narrowest..=widest).contains(&ratio) {
+                return Err(ResolveError::invalid_parameter(
+                    "custom_ratio",
+                    format!("must be between {} and {}", narrowest, widest),
+                ));
+            }
+            Ok(Some((format!("{:.2}:1", ratio), ratio)))
+        }
+        _ => BLANKING_PRESETS
+            .iter()
+            .find(|(name, _)| *name == aspect)
+            .map(|(name, ratio)| Some((name.to_string(), *ratio)))
+            .ok_or_else(|| {
+                ResolveError::invalid_parameter(
+                    "aspect",
+                    "must be '2.39:1', '1.85:1', 'custom' or 'none'",
+                )
+            }),
+    }
+}
+
+impl ResolveBridge {
+    /// Response of the output framing tools
+    fn output_framing_response(
+        state: &StateView<'_>,
+        timeline_name: &str,
+        result: String,
+    ) -> Value {
+        let timeline = &state.timelines[timeline_name];
+        let (width, height) = timeline_size(timeline);
+        json!({
+            "result": result,
+            "timeline_name": timeline_name,
+            "timeline_resolution": { "width": width, "height": height },
+            "output": timeline.output.to_json((width, height)),
+            "operation_id": Uuid::new_v4().to_string()
+        })
+    }
+
+    pub(super) async fn set_output_blanking(
+        &self,
+        state: &mut StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        let blanking = blanking_argument(&args)?;
+        let timeline_name = Self::resolve_timeline_name(state, &args)?;
+        let result = match &blanking {
+            Some((name, _)) => format!(
+                "Set output blanking of timeline '{}' to {}",
+                timeline_name, name
+            ),
+            None => format!("Removed output blanking of timeline '{}'", timeline_name),
+        };
+        if let Some(timeline) = state.timelines.get_mut(&timeline_name) {
+            timeline.output.blanking = blanking;
+        }
+        Ok(Self::output_framing_response(state, &timeline_name, result))
+    }
+
+    pub(super) async fn set_output_reframe(
+        &self,
+        state: &mut StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        let preset = args["preset"]
+            .as_str()
+            .ok_or_else(|| ResolveError::invalid_parameter("preset", "required string"))?;
+        let reframe = match preset {
+            "none" => None,
+            _ => Some(
+                REFRAME_PRESETS
+                    .iter()
+                    .map(|(name, _)| *name)
+                    .find(|name| *name == preset)
+                    .ok_or_else(|| {
+                        ResolveError::invalid_parameter(
+                            "preset",
+                            "must be 'vertical', 'square' or 'none'",
+                        )
+                    })?,
+            ),
+        };
+        let timeline_name = Self::resolve_timeline_name(state, &args)?;
+        let result = match reframe {
+            Some(name) => format!(
+                "Reframed the output of timeline '{}' to {}",
+                timeline_name, name
+            ),
+            None => format!("Removed output reframing of timeline '{}'", timeline_name),
+        };
+        if let Some(timeline) = state.timelines.get_mut(&timeline_name) {
+            timeline.output.reframe = reframe;
+        }
+        Ok(Self::output_framing_response(state, &timeline_name, result))
+    }
+}
@@ -0,0 +1,229 @@
+//! Paged results for large list responses
+//!
+//! Tools that can return thousands of items send the first page inline and
+//! write the remaining items to a temporary file, one JSON value per line. The
+//! response carries a cursor that `get_result_page` follows to read later pages
+//! straight from that file, so the full list is never serialized as a single
+//! JSON value. Stored results expire once unused for
+//! `Config::pagination.cursor_ttl_seconds`.
+
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Seek, SeekFrom, Write};
+use std::path::PathBuf;
+use uuid::Uuid;
+
+use super::{ResolveBridge, StateView};
+use crate::error::{ResolveError, ResolveResult};
+
+/// Results stored behind cursors, by result ID
+#[derive(Debug, Default)]
+pub(super) struct PageStore {
+    results: HashMap<String, StoredResult>,
+}
+
+#[derive(Debug)]
+struct StoredResult {
+    /// Tool that produced the result
+    tool: String,
+    /// Response field the items belong in
+    field: String,
+    path: PathBuf,
+    /// Byte offset of each stored item's line
+    offsets: Vec<u64>,
+    /// Items sent inline with the original response
+    inline_count: usize,
+    page_size: usize,
+    last_used: chrono::DateTime<chrono::Utc>,
+}
+
+impl StoredResult {
+    fn total_count(&self) -> usize {
+        self.inline_count + self.offsets.len()
+    }
+
+    /// Items from `start` (an index into the stored items) up to `count` of them
+    fn read(&self, start: usize, count: usize) -> Result<Vec<Value>, String> {
+        let Some(&offset) = self.offsets.get(start) else {
+            return Ok(Vec::new());
+        };
+        let mut reader = BufReader::new(File::open(&self.path).map_err(|e| e.to_string())?);
+        reader
+            .seek(SeekFrom::Start(offset))
+            .map_err(|e| e.to_string())?;
+        reader
+            .lines()
+            .take(count.min(self.offsets.len() - start))
+            .map(|line| {
+                let line = line.map_err(|e| e.to_string())?;
+                serde_json::from_str(&line).map_err(|e| e.to_string())
+            })
+            .collect()
+    }
+}
+
+/// Cursor pointing at the stored item with index `position`
+fn cursor(result_id: &str, position: usize) -> String {
+    format!("{}:{}", result_id, position)
+}
+
+impl ResolveBridge {
+    /// Requested page size, or the configured default
+    pub(super) fn page_size(&self, args: &Value) -> ResolveResult<usize> {
+        let config = &self.config.pagination;
+        match args["page_size"].as_u64() {
+            Some(size) if size == 0 || size as usize > config.max_page_size => {
+                Err(ResolveError::invalid_parameter(
+                    "page_size",
+                    format!("must be between 1 and {}", config.max_page_size),
+                ))
+            }
+            Some(size) => Ok(size as usize),
+            None => Ok(config.default_page_size),
+        }
+    }
+
+    /// Drop results whose cursors have not been used within the TTL
+    fn expire_results(&self, store: &mut PageStore) {
+        let ttl = chrono::Duration::seconds(self.config.pagination.cursor_ttl_seconds as i64);
+        let now = chrono::Utc::now();
+        store.results.retain(|_, result| {
+            let keep = now - result.last_used <= ttl;
+            if !keep {
+                if let Err(e) = std::fs::remove_file(&result.path) {
+                    tracing::warn!(
+                        "Could not remove stored page {}: {}",
+                        result.path.display(),
+                        e
+                    );
+                }
+            }
+            keep
+        });
+    }
+
+    /// Put the first page of `items` in `response[field]` and store the rest behind a cursor
+    pub(super) async fn paginate<I>(
+        &self,
+        tool: &str,
+        response: &mut Value,
+        field: &str,
+        items: I,
+        args: &Value,
+    ) -> ResolveResult<()>
+    where
+        I: IntoIterator<Item = Value>,
+    {
+        let page_size = self.page_size(args)?;
+        let mut items = items.into_iter().peekable();
+        let first_page: Vec<Value> = items.by_ref().take(page_size).collect();
+        let inline_count = first_page.len();
+        response[field] = Value::Array(first_page);
+        if items.peek().is_none() {
+            response["total_count"] = json!(inline_count);
+            response["next_cursor"] = Value::Null;
+            return Ok(());
+        }
+
+        // Each remaining item is serialized on its own straight to the file
+        let result_id = Uuid::new_v4().to_string();
+        let directory = self
+            .config
+            .pagination
+            .directory
+            .clone()
+            .unwrap_or_else(|| std::env::temp_dir().join("davinci-mcp-pages"));
+        let path = directory.join(format!("{}.jsonl", result_id));
+        let write_failed = |e: std::io::Error| {
+            ResolveError::internal(format!(
+                "could not store result pages in '{}': {}",
+                directory.display(),
+                e
+            ))
+        };
+        std::fs::create_dir_all(&directory).map_err(write_failed)?;
+        let mut writer = BufWriter::new(File::create(&path).map_err(write_failed)?);
+        let mut offsets = Vec::new();
+        let mut offset = 0;
+        for item in items {
+            let line = serde_json::to_vec(&item)?;
+            writer.write_all(&line).map_err(write_failed)?;
+            writer.write_all(b"\n").map_err(write_failed)?;
+            offsets.push(offset);
+            offset += line.len() as u64 + 1;
+        }
+        writer.flush().map_err(write_failed)?;
+
+        response["total_count"] = json!(inline_count + offsets.len());
+        response["next_cursor"] = json!(cursor(&result_id, 0));
+        let mut store = self.pages.lock().await;
+        self.expire_results(&mut store);
+        store.results.insert(
+            result_id,
+            StoredResult {
+                tool: tool.to_string(),
+                field: field.to_string(),
+                path,
+                offsets,
+                inline_count,
+                page_size,
+                last_used: chrono::Utc::now(),
+            },
+        );
+        Ok(())
+    }
+
+    pub(super) async fn get_result_page(
+        &self,
+        _state: &StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        let cursor_arg = args["cursor"]
+            .as_str()
+            .ok_or_else(|| ResolveError::invalid_parameter("cursor", "required string"))?;
+        let invalid = || {
+            ResolveError::invalid_parameter(
+                "cursor",
+                format!("'{}' is not a valid or unexpired cursor", cursor_arg),
+            )
+        };
+        let (result_id, position) = cursor_arg.rsplit_once(':').ok_or_else(invalid)?;
+        let position: usize = position.parse().map_err(|_| invalid())?;
+
+        let mut store = self.pages.lock().await;
+        self.expire_results(&mut store);
+        let result = store.results.get_mut(result_id).ok_or_else(invalid)?;
+        if position > result.offsets.len() {
+            return Err(invalid());
+        }
+        let page_size = match args["page_size"].as_u64() {
+            Some(_) => self.page_size(&args)?,
+            None => result.page_size,
+        };
+        let items = result
+            .read(position, page_size)
+            .map_err(|e| ResolveError::internal(format!("could not read stored page: {}", e)))?;
+        result.last_used = chrono::Utc::now();
+
+        let next = position + items.len();
+        let first = result.inline_count + position;
+        let mut response = json!({
+            "result": format!(
+                "Returned items {} to {} of {} from {}",
+                first + 1,
+                first + items.len(),
+                result.total_count(),
+                result.tool
+            ),
+            "tool": result.tool,
+            "field": result.field,
+            "offset": first,
+            "total_count": result.total_count(),
+            "next_cursor": (next < result.offsets.len()).then(|| cursor(result_id, next)),
+            "operation_id": Uuid::new_v4().to_string()
+        });
+        response[result.field.as_str()] = Value::Array(items);
+        Ok(response)
+    }
+}
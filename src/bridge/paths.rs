@@ -0,0 +1,131 @@
+//! File path checks for tools that touch the filesystem
+//!
+//! Every path argument of an API call is checked before the call runs, so
+//! import, export, LUT, layout and any later tools share one set of rules.
+//! Registered tools declare their path arguments in the tool registry; the
+//! hand-written ones are matched by argument name.
+//! Relative paths and `..` components are refused, and the path is
+//! canonicalized through its longest existing ancestor, so a symlink inside an
+//! allowed root cannot lead outside it. The result must lie below one of the
+//! `[sandbox]` roots. Nothing is checked when no roots are configured.
+
+use serde_json::Value;
+use std::path::{Component, Path, PathBuf};
+
+use crate::config::SandboxConfig;
+use crate::error::{ResolveError, ResolveResult};
+use crate::tools::registry;
+
+/// Arguments that hold file or folder paths in any hand-written API call
+const PATH_ARGUMENTS: &[&str] = &[
+    "file_path",
+    "path",
+    "paths",
+    "media_paths",
+    "folder_path",
+    "lut_path",
+    "drx_path",
+    "export_path",
+    "export_dir",
+    "import_path",
+    "reference_path",
+    "output_path",
+    "output_directory",
+    "destination_folder",
+    "replacement_path",
+    "proxy_file_path",
+    "proxy_media_file_path",
+];
+
+/// Arguments that hold paths only in particular hand-written API calls
+const METHOD_PATH_ARGUMENTS: &[(&str, &str)] = &[
+    ("export_timeline", "file_name"),
+    ("describe_frame", "image_path"),
+];
+
+/// Absolute path with symlinks resolved; the part that does not exist yet is
+/// appended as given
+fn canonicalize(path: &Path) -> PathBuf {
+    path.ancestors()
+        .find_map(|ancestor| {
+            let canonical = std::fs::canonicalize(ancestor).ok()?;
+            let rest = path.strip_prefix(ancestor).ok()?;
+            Some(canonical.join(rest))
+        })
+        .unwrap_or_else(|| path.to_path_buf())
+}
+
+/// Check that a path lies inside the sandbox, returning its canonical form
+pub(super) fn check_path(sandbox: &SandboxConfig, path: &str) -> ResolveResult<PathBuf> {
+    let given = Path::new(path);
+    if sandbox.allowed_roots.is_empty() {
+        return Ok(given.to_path_buf());
+    }
+    if !given.is_absolute() {
+        return Err(ResolveError::path_not_allowed(
+            path,
+            "must be an absolute path",
+        ));
+    }
+    if given
+        .components()
+        .any(|component| component == Component::ParentDir)
+    {
+        return Err(ResolveError::path_not_allowed(
+            path,
+            "must not contain '..'",
+        ));
+    }
+    let canonical = canonicalize(given);
+    if sandbox
+        .allowed_roots
+        .iter()
+        .any(|root| canonical.starts_with(canonicalize(root)))
+    {
+        Ok(canonical)
+    } else {
+        Err(ResolveError::path_not_allowed(
+            path,
+            "outside the allowed directories",
+        ))
+    }
+}
+
+/// Check every path argument of an API call
+pub(super) fn check_arguments(
+    sandbox: &SandboxConfig,
+    method: &str,
+    args: &Value,
+) -> ResolveResult<()> {
+    if sandbox.allowed_roots.is_empty() {
+        return Ok(());
+    }
+    let Some(fields) = args.as_object() else {
+        return Ok(());
+    };
+    let declared = registry::find(method).map(|spec| spec.paths);
+    for (key, value) in fields {
+        let holds_path = match declared {
+            Some(paths) => paths.contains(&key.as_str()),
+            None => {
+                PATH_ARGUMENTS.contains(&key.as_str())
+                    || METHOD_PATH_ARGUMENTS.contains(&(method, key.as_str()))
+            }
+        };
+        if !holds_path {
+            continue;
+        }
+        match value {
+            Value::String(path) => {
+                check_path(sandbox, path)?;
+            }
+            Value::Array(paths) => {
+                for path in paths.iter().filter_map(Value::as_str) {
+                    check_path(sandbox, path)?;
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,497 @@
+//! Power Windows on grade nodes
+//!
+//! A node of a clip grade can hold any number of windows that limit the node
+//! to part of the frame, as on the color page's Window palette: circles,
+//! linear (rectangular) windows, polygons and gradients. Positions and sizes
+//! are fractions of the frame, so a window fits any resolution. Windows are
+//! numbered from 1 on each node in the order they were added.
+//!
+//! Tracking a window moves it with a subject on one timeline item: its center
+//! is keyframed on the item at every tracked frame, like the windows of
+//! apply_privacy_blur. Every window shows up in get_clip_grade and in grade
+//! reports, so secondaries can be checked without opening the color page.
+
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use super::{
+    ensure_capacity, InterpolationType, Keyframe, KeyframeModes, ResolveBridge, StateView,
+    TimelineItemKeyframes,
+};
+use crate::error::{ResolveError, ResolveResult};
+
+/// Fewest points a polygon window can have
+const MIN_POLYGON_POINTS: usize = 3;
+
+/// Shape of a Power Window
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+pub(super) enum WindowShape {
+    Circle,
+    Linear,
+    Polygon,
+    Gradient,
+}
+
+impl WindowShape {
+    fn parse(value: &str) -> ResolveResult<Self> {
+        match value.to_ascii_lowercase().as_str() {
+            "circle" => Ok(WindowShape::Circle),
+            "linear" => Ok(WindowShape::Linear),
+            "polygon" => Ok(WindowShape::Polygon),
+            "gradient" => Ok(WindowShape::Gradient),
+            _ => Err(ResolveError::invalid_parameter(
+                "shape",
+                "must be 'circle', 'linear', 'polygon' or 'gradient'",
+            )),
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            WindowShape::Circle => "circle",
+            WindowShape::Linear => "linear",
+            WindowShape::Polygon => "polygon",
+            WindowShape::Gradient => "gradient",
+        }
+    }
+}
+
+/// A window on a grade node
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub(super) struct PowerWindow {
+    pub(super) shape: WindowShape,
+    /// Center, or the pivot of a gradient, in fractions of the frame
+    pub(super) center_x: f64,
+    pub(super) center_y: f64,
+    /// Size of a circle or linear window, in fractions of the frame
+    pub(super) width: f64,
+    pub(super) height: f64,
+    /// Rotation in degrees; the direction of a gradient
+    pub(super) rotation: f64,
+    /// Edge softness, 0 to 100
+    pub(super) softness: f64,
+    /// Grade outside the window instead of inside
+    pub(super) invert: bool,
+    /// Corners of a polygon, in fractions of the frame
+    pub(super) points: Vec<(f64, f64)>,
+    /// Item whose keyframes move the window, once tracked
+    pub(super) tracked_item: Option<String>,
+}
+
+impl PowerWindow {
+    fn new(shape: WindowShape) -> Self {
+        Self {
+            shape,
+            center_x: 0.5,
+            center_y: 0.5,
+            width: 0.5,
+            height: 0.5,
+            rotation: 0.0,
+            softness: 0.0,
+            invert: false,
+            points: Vec::new(),
+            tracked_item: None,
+        }
+    }
+
+    /// Change the fields given in `args`, checking each against the shape
+    fn update(&mut self, args: &Value) -> ResolveResult<()> {
+        let number = |param: &str, min: f64, max: f64| -> ResolveResult<Option<f64>> {
+            match &args[param] {
+                Value::Null => Ok(None),
+                value => value
+                    .as_f64()
+                    .filter(|value| (min..=max).contains(value))
+                    .map(Some)
+                    .ok_or_else(|| {
+                        ResolveError::invalid_parameter(
+                            param,
+                            format!("must be a number from {} to {}", min, max),
+                        )
+                    }),
+            }
+        };
+        let sized = matches!(self.shape, WindowShape::Circle | WindowShape::Linear);
+        for param in ["width", "height"] {
+            if !sized && !args[param].is_null() {
+                return Err(ResolveError::invalid_parameter(
+                    param,
+                    format!("a {} window has no size", self.shape.as_str()),
+                ));
+            }
+        }
+        if self.shape != WindowShape::Polygon && !args["points"].is_null() {
+            return Err(ResolveError::invalid_parameter(
+                "points",
+                "only polygon windows have points",
+            ));
+        }
+
+        if let Some(value) = number("center_x", 0.0, 1.0)? {
+            self.center_x = value;
+        }
+        if let Some(value) = number("center_y", 0.0, 1.0)? {
+            self.center_y = value;
+        }
+        for (param, slot) in [("width", &mut self.width), ("height", &mut self.height)] {
+            if let Some(value) = number(param, 0.0, 1.0)? {
+                if value == 0.0 {
+                    return Err(ResolveError::invalid_parameter(
+                        param,
+                        "must be more than 0",
+                    ));
+                }
+                *slot = value;
+            }
+        }
+        if let Some(value) = number("rotation", -180.0, 180.0)? {
+            self.rotation = value;
+        }
+        if let Some(value) = number("softness", 0.0, 100.0)? {
+            self.softness = value;
+        }
+        if let Some(invert) = args["invert"].as_bool() {
+            self.invert = invert;
+        }
+        if let Some(points) = args["points"].as_array() {
+            self.points = points
+                .iter()
+                .map(|point| {
+                    let coordinate = |axis: &str| {
+                        point[axis]
+                            .as_f64()
+                            .filter(|value| (0.0..=1.0).contains(value))
+                    };
+                    coordinate("x").zip(coordinate("y")).ok_or_else(|| {
+                        ResolveError::invalid_parameter(
+                            "points",
+                            "each point needs x and y from 0 to 1",
+                        )
+                    })
+                })
+                .collect::<ResolveResult<_>>()?;
+        }
+        if self.shape == WindowShape::Polygon && self.points.len() < MIN_POLYGON_POINTS {
+            return Err(ResolveError::invalid_parameter(
+                "points",
+                format!(
+                    "a polygon window needs at least {} points",
+                    MIN_POLYGON_POINTS
+                ),
+            ));
+        }
+        Ok(())
+    }
+
+    pub(super) fn to_json(&self) -> Value {
+        let mut window = json!({
+            "shape": self.shape.as_str(),
+            "center_x": self.center_x,
+            "center_y": self.center_y,
+            "rotation": self.rotation,
+            "softness": self.softness,
+            "invert": self.invert,
+            "tracked_item": self.tracked_item
+        });
+        match self.shape {
+            WindowShape::Circle | WindowShape::Linear => {
+                window["width"] = json!(self.width);
+                window["height"] = json!(self.height);
+            }
+            WindowShape::Polygon => {
+                window["points"] = self
+                    .points
+                    .iter()
+                    .map(|(x, y)| json!({ "x": x, "y": y }))
+                    .collect();
+            }
+            WindowShape::Gradient => {}
+        }
+        window
+    }
+}
+
+/// Keyframed property of a tracked window's center
+fn center_property(node_index: i32, window_index: usize, axis: &str) -> String {
+    format!(
+        "Node {} Window {} Center {}",
+        node_index, window_index, axis
+    )
+}
+
+/// Window index of a call, checked against the node's windows
+fn window_index(args: &Value, count: usize) -> ResolveResult<usize> {
+    args["window_index"]
+        .as_u64()
+        .map(|index| index as usize)
+        .filter(|index| (1..=count).contains(index))
+        .ok_or_else(|| {
+            ResolveError::invalid_parameter(
+                "window_index",
+                match count {
+                    0 => "the node has no windows".to_string(),
+                    count => format!("must be a window of the node, 1 to {}", count),
+                },
+            )
+        })
+}
+
+impl ResolveBridge {
+    /// Response of the Power Window tools
+    fn power_window_response(
+        result: String,
+        clip_name: &str,
+        node_index: i32,
+        window_index: usize,
+        window: &PowerWindow,
+    ) -> Value {
+        json!({
+            "result": result,
+            "clip_name": clip_name,
+            "node_index": node_index,
+            "window_index": window_index,
+            "window": window.to_json(),
+            "operation_id": Uuid::new_v4().to_string()
+        })
+    }
+
+    pub(super) async fn add_power_window(
+        &self,
+        state: &mut StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        let shape = WindowShape::parse(
+            args["shape"]
+                .as_str()
+                .ok_or_else(|| ResolveError::invalid_parameter("shape", "required string"))?,
+        )?;
+        let clip_name = Self::required_grade_clip(state, &args)?;
+        let node_index = Self::grade_node(state, &args)?;
+        let mut window = PowerWindow::new(shape);
+        window.update(&args)?;
+
+        let windows = state
+            .color_state
+            .clip_grades
+            .entry(clip_name.clone())
+            .or_default()
+            .power_windows
+            .entry(node_index)
+            .or_default();
+        windows.push(window);
+        let window_index = windows.len();
+        Ok(Self::power_window_response(
+            format!(
+                "Added {} window {} to node {} of clip '{}'",
+                shape.as_str(),
+                window_index,
+                node_index,
+                clip_name
+            ),
+            &clip_name,
+            node_index,
+            window_index,
+            &windows[window_index - 1],
+        ))
+    }
+
+    pub(super) async fn modify_power_window(
+        &self,
+        state: &mut StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        let clip_name = Self::required_grade_clip(state, &args)?;
+        let node_index = Self::grade_node(state, &args)?;
+        let count = state
+            .color_state
+            .clip_grades
+            .get(&clip_name)
+            .and_then(|grade| grade.power_windows.get(&node_index))
+            .map_or(0, Vec::len);
+        let window_index = window_index(&args, count)?;
+        let window = &mut state
+            .color_state
+            .clip_grades
+            .get_mut(&clip_name)
+            .and_then(|grade| grade.power_windows.get_mut(&node_index))
+            .expect("window_index checked the node has windows")[window_index - 1];
+
+        // Changes apply together or not at all
+        let mut updated = window.clone();
+        updated.update(&args)?;
+        *window = updated;
+        Ok(Self::power_window_response(
+            format!(
+                "Modified window {} on node {} of clip '{}'",
+                window_index, node_index, clip_name
+            ),
+            &clip_name,
+            node_index,
+            window_index,
+            window,
+        ))
+    }
+
+    pub(super) async fn track_power_window(
+        &self,
+        state: &mut StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        let timeline_item_id = args["timeline_item_id"].as_str().ok_or_else(|| {
+            ResolveError::invalid_parameter("timeline_item_id", "parameter is required")
+        })?;
+        let item = state
+            .timeline_items
+            .items
+            .get(timeline_item_id)
+            .ok_or_else(|| {
+                ResolveError::invalid_parameter("timeline_item_id", "timeline item not found")
+            })?;
+        let clip_name = item.clip_name.clone();
+        let duration = item.placement.as_ref().map(|placement| placement.duration);
+        let node_index = Self::grade_node(state, &args)?;
+        let count = state
+            .color_state
+            .clip_grades
+            .get(&clip_name)
+            .and_then(|grade| grade.power_windows.get(&node_index))
+            .map_or(0, Vec::len);
+        let window_index = window_index(&args, count)?;
+
+        let positions = args["positions"]
+            .as_array()
+            .filter(|positions| !positions.is_empty())
+            .ok_or_else(|| {
+                ResolveError::invalid_parameter("positions", "give at least one tracked position")
+            })?;
+        let mut track = Vec::with_capacity(positions.len());
+        for position in positions {
+            let frame = position["frame"]
+                .as_i64()
+                .filter(|frame| *frame >= 0 && duration.is_none_or(|duration| *frame < duration))
+                .ok_or_else(|| {
+                    ResolveError::invalid_parameter(
+                        "positions",
+                        match duration {
+                            Some(duration) => {
+                                format!("frames must be frames of the item, 0 to {}", duration - 1)
+                            }
+                            None => "frames must be 0 or more".to_string(),
+                        },
+                    )
+                })?;
+            let coordinate = |axis: &str| {
+                position[axis]
+                    .as_f64()
+                    .filter(|value| (0.0..=1.0).contains(value))
+                    .ok_or_else(|| {
+                        ResolveError::invalid_parameter(
+                            "positions",
+                            format!("{} at frame {} must be from 0 to 1", axis, frame),
+                        )
+                    })
+            };
+            track.push((
+                frame as i32,
+                coordinate("center_x")?,
+                coordinate("center_y")?,
+            ));
+        }
+        track.sort_by_key(|(frame, _, _)| *frame);
+        if let Some(pair) = track.windows(2).find(|pair| pair[0].0 == pair[1].0) {
+            return Err(ResolveError::invalid_parameter(
+                "positions",
+                format!("frame {} is tracked twice", pair[0].0),
+            ));
+        }
+
+        // Tracking again replaces the window's earlier track
+        let properties = [
+            center_property(node_index, window_index, "X"),
+            center_property(node_index, window_index, "Y"),
+        ];
+        let existing = state
+            .keyframe_state
+            .timeline_item_keyframes
+            .get(timeline_item_id)
+            .map_or(0, |item| {
+                item.property_keyframes
+                    .iter()
+                    .filter(|(property, _)| !properties.contains(property))
+                    .map(|(_, keyframes)| keyframes.len())
+                    .sum()
+            });
+        ensure_capacity(
+            &format!("keyframes on timeline item '{}'", timeline_item_id),
+            existing,
+            track.len() * 2,
+            self.config.limits.max_keyframes_per_item,
+        )?;
+
+        let keyframe_state = &mut *state.keyframe_state;
+        let item_keyframes = keyframe_state
+            .timeline_item_keyframes
+            .entry(timeline_item_id.to_string())
+            .or_insert_with(|| TimelineItemKeyframes {
+                timeline_item_id: timeline_item_id.to_string(),
+                property_keyframes: HashMap::new(),
+                keyframe_modes: KeyframeModes::default(),
+            });
+        let created_at = chrono::Utc::now().to_rfc3339();
+        let centers: [Vec<(i32, f64)>; 2] = [
+            track.iter().map(|&(frame, x, _)| (frame, x)).collect(),
+            track.iter().map(|&(frame, _, y)| (frame, y)).collect(),
+        ];
+        for (property, values) in properties.iter().zip(centers) {
+            let keyframes: Vec<Keyframe> = values
+                .into_iter()
+                .map(|(frame, value)| {
+                    keyframe_state.keyframe_counter += 1;
+                    Keyframe {
+                        id: keyframe_state.keyframe_counter,
+                        frame,
+                        value,
+                        interpolation: InterpolationType::Linear,
+                        created_at: created_at.clone(),
+                    }
+                })
+                .collect();
+            item_keyframes
+                .property_keyframes
+                .insert(property.clone(), keyframes);
+        }
+
+        let window = &mut state
+            .color_state
+            .clip_grades
+            .get_mut(&clip_name)
+            .and_then(|grade| grade.power_windows.get_mut(&node_index))
+            .expect("window_index checked the node has windows")[window_index - 1];
+        let (_, first_x, first_y) = track[0];
+        window.center_x = first_x;
+        window.center_y = first_y;
+        window.tracked_item = Some(timeline_item_id.to_string());
+        let mut response = Self::power_window_response(
+            format!(
+                "Tracked window {} on node {} of clip '{}' over {} frames of item '{}'",
+                window_index,
+                node_index,
+                clip_name,
+                track.len(),
+                timeline_item_id
+            ),
+            &clip_name,
+            node_index,
+            window_index,
+            window,
+        );
+        response["timeline_item_id"] = json!(timeline_item_id);
+        response["tracked_frames"] = json!(track.len());
+        response["first_frame"] = json!(track[0].0);
+        response["last_frame"] = json!(track[track.len() - 1].0);
+        response["keyframed_properties"] = json!(properties);
+        Ok(response)
+    }
+}
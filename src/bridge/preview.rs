@@ -0,0 +1,427 @@
+//! Animated previews for quick review
+//!
+//! A preview is a small GIF or 480p MP4 of a timeline or clip range, made to
+//! be dropped into a chat thread where an agent and a reviewer go back and
+//! forth on a cut. It is rendered by ffmpeg as a background job: each stretch
+//! of the range is cut from the media of the topmost item there, or filled
+//! with black for gaps, audio-only clips and offline media, scaled into the
+//! preview frame, and the stretches are joined. Previews carry picture only.
+//!
+//! Without ffmpeg the job still completes, reporting the stretches it would
+//! have rendered and that no file was written.
+
+use serde_json::{json, Value};
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+use super::delivery_qc::MediaKind;
+use super::{ffmpeg, output_framing, ResolveBridge, StateView, SIMULATED_CLIP_SECONDS};
+use crate::error::{ResolveError, ResolveResult};
+use crate::timecode::FrameRate;
+
+/// Longest range a preview covers
+const MAX_PREVIEW_SECONDS: f64 = 120.0;
+
+const GIF_WIDTH: u32 = 320;
+const GIF_FPS: f64 = 10.0;
+const MP4_HEIGHT: u32 = 480;
+const MP4_MAX_FPS: f64 = 30.0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum PreviewFormat {
+    Gif,
+    Mp4,
+}
+
+impl PreviewFormat {
+    fn parse(format: &str) -> Option<Self> {
+        match format.to_ascii_lowercase().as_str() {
+            "gif" => Some(Self::Gif),
+            "mp4" => Some(Self::Mp4),
+            _ => None,
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Gif => "gif",
+            Self::Mp4 => "mp4",
+        }
+    }
+
+    /// Frame size for a picture of `aspect` width over height, kept even
+    fn size(self, aspect: f64) -> (u32, u32) {
+        let even = |value: f64| ((value / 2.0).round() as u32).max(1) * 2;
+        match self {
+            Self::Gif => (GIF_WIDTH, even(f64::from(GIF_WIDTH) / aspect)),
+            Self::Mp4 => (even(f64::from(MP4_HEIGHT) * aspect), MP4_HEIGHT),
+        }
+    }
+
+    fn fps(self, source_fps: f64) -> f64 {
+        match self {
+            Self::Gif => GIF_FPS.min(source_fps),
+            Self::Mp4 => MP4_MAX_FPS.min(source_fps),
+        }
+    }
+}
+
+/// One stretch of the preview, cut from one clip or left black
+struct PreviewPiece {
+    /// Clip shown; None for gaps
+    clip_name: Option<String>,
+    /// Media to cut from; None for gaps and offline media
+    source: Option<String>,
+    /// First frame of the stretch within the range
+    frame: i64,
+    frames: i64,
+    /// Source time the stretch starts at
+    source_seconds: f64,
+}
+
+impl PreviewPiece {
+    fn to_json(&self, rate: FrameRate) -> Value {
+        json!({
+            "clip_name": self.clip_name,
+            "start_frame": self.frame,
+            "frames": self.frames,
+            "seconds": self.frames as f64 / rate.fps(),
+            "source_seconds": self.source_seconds,
+            "offline": self.clip_name.is_some() && self.source.is_none()
+        })
+    }
+}
+
+/// Everything the preview job needs, detached from the state
+struct Preview {
+    format: PreviewFormat,
+    size: (u32, u32),
+    fps: f64,
+    rate: FrameRate,
+    pieces: Vec<PreviewPiece>,
+    output: PathBuf,
+    target: Value,
+}
+
+impl Preview {
+    fn report(&self) -> Value {
+        let frames: i64 = self.pieces.iter().map(|piece| piece.frames).sum();
+        json!({
+            "target": self.target,
+            "format": self.format.as_str(),
+            "width": self.size.0,
+            "height": self.size.1,
+            "fps": self.fps,
+            "duration_seconds": frames as f64 / self.rate.fps(),
+            "pieces": self
+                .pieces
+                .iter()
+                .map(|piece| piece.to_json(self.rate))
+                .collect::<Vec<_>>(),
+            "output_path": self.output.display().to_string()
+        })
+    }
+
+    fn run(self) -> Result<Value, String> {
+        let mut report = self.report();
+        if !ffmpeg::available() {
+            report["rendered"] = json!(false);
+            report["reason"] = json!("ffmpeg is not available; no file was written");
+            return Ok(report);
+        }
+
+        let work = std::env::temp_dir().join(format!("davinci_mcp_preview_{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&work).map_err(|e| e.to_string())?;
+        let rendered = self.render(&work);
+        let _ = std::fs::remove_dir_all(&work);
+        rendered?;
+
+        let size = std::fs::metadata(&self.output)
+            .map_err(|e| e.to_string())?
+            .len();
+        report["rendered"] = json!(true);
+        report["size_bytes"] = json!(size);
+        Ok(report)
+    }
+
+    /// Render each piece into `work`, then join them into the output
+    fn render(&self, work: &Path) -> Result<(), String> {
+        let mut list = String::new();
+        for (index, piece) in self.pieces.iter().enumerate() {
+            let part = work.join(format!("part_{:04}.mp4", index));
+            ffmpeg::preview_piece(
+                piece.source.as_deref().map(Path::new),
+                piece
+                    .source
+                    .as_deref()
+                    .is_some_and(|source| MediaKind::of(source) == MediaKind::Still),
+                piece.source_seconds,
+                piece.frames as f64 / self.rate.fps(),
+                self.size,
+                self.fps,
+                &part,
+            )?;
+            let part = part.display().to_string().replace('\'', "'\\''");
+            list.push_str(&format!("file '{}'\n", part));
+        }
+        let list_path = work.join("pieces.txt");
+        std::fs::write(&list_path, list).map_err(|e| e.to_string())?;
+        if let Some(parent) = self.output.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        ffmpeg::join_preview(&list_path, self.format == PreviewFormat::Gif, &self.output)
+    }
+}
+
+/// Frame range from `start_frame` and `end_frame`, defaulting to `0..default_end`
+fn frame_range(args: &Value, default_end: i64, rate: FrameRate) -> ResolveResult<(i64, i64)> {
+    let start = args["start_frame"].as_i64().unwrap_or(0);
+    let end = args["end_frame"].as_i64().unwrap_or(default_end);
+    if start < 0 || end <= start {
+        return Err(ResolveError::invalid_parameter(
+            "end_frame",
+            "range must start at 0 or later and end after it starts",
+        ));
+    }
+    if (end - start) as f64 / rate.fps() > MAX_PREVIEW_SECONDS {
+        return Err(ResolveError::invalid_parameter(
+            "end_frame",
+            format!(
+                "previews cover at most {} seconds; give a shorter range",
+                MAX_PREVIEW_SECONDS
+            ),
+        ));
+    }
+    Ok((start, end))
+}
+
+/// File name stem made of the characters of `name` safe everywhere
+fn file_stem(name: &str) -> String {
+    let stem: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if stem.is_empty() {
+        "preview".to_string()
+    } else {
+        stem
+    }
+}
+
+impl ResolveBridge {
+    pub(super) async fn generate_preview(
+        &self,
+        state: &mut StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        let output_path = args["output_path"].as_str();
+        let extension_format = output_path
+            .and_then(|path| Path::new(path).extension())
+            .and_then(|ext| PreviewFormat::parse(&ext.to_string_lossy()));
+        let format = match args["format"].as_str() {
+            Some(format) => {
+                let format = PreviewFormat::parse(format).ok_or_else(|| {
+                    ResolveError::invalid_parameter("format", "must be 'gif' or 'mp4'")
+                })?;
+                if extension_format.is_some_and(|extension| extension != format) {
+                    return Err(ResolveError::invalid_parameter(
+                        "output_path",
+                        format!("must end in .{} to match the format", format.as_str()),
+                    ));
+                }
+                format
+            }
+            None => extension_format.unwrap_or(PreviewFormat::Gif),
+        };
+
+        let (rate, aspect, pieces, target, name) = match args["clip_name"].as_str() {
+            Some(reference) => {
+                if args["timeline_name"].is_string() {
+                    return Err(ResolveError::invalid_parameter(
+                        "clip_name",
+                        "give either clip_name or timeline_name, not both",
+                    ));
+                }
+                let clip_name = Self::resolve_clip(state, reference)?;
+                let clip = &state.media_pool.clips[&clip_name];
+                if MediaKind::of(&clip.file_path) == MediaKind::Audio {
+                    return Err(ResolveError::invalid_parameter(
+                        "clip_name",
+                        format!("clip '{}' has no picture", clip_name),
+                    ));
+                }
+                let rate = FrameRate::parse(&self.clip_frame_rate(clip))
+                    .map_err(|e| ResolveError::invalid_parameter("frame_rate", e))?;
+                let (start, end) =
+                    frame_range(&args, SIMULATED_CLIP_SECONDS * rate.nominal(), rate)?;
+                let pieces = vec![PreviewPiece {
+                    clip_name: Some(clip_name.clone()),
+                    source: Path::new(&clip.file_path)
+                        .is_file()
+                        .then(|| clip.file_path.clone()),
+                    frame: 0,
+                    frames: end - start,
+                    source_seconds: start as f64 / rate.fps(),
+                }];
+                let target =
+                    json!({ "clip_name": clip_name, "start_frame": start, "end_frame": end });
+                (rate, 16.0 / 9.0, pieces, target, clip_name)
+            }
+            None => {
+                let timeline_name = Self::resolve_timeline_name(state, &args)?;
+                let timeline = &state.timelines[&timeline_name];
+                let rate = self.timeline_frame_rate(timeline)?;
+                let (width, height) = timeline
+                    .output
+                    .output_size(output_framing::timeline_size(timeline));
+                let aspect = f64::from(width) / f64::from(height);
+                let mut items: Vec<_> = state
+                    .timeline_items
+                    .items
+                    .values()
+                    .filter(|item| item.timeline_name == timeline_name)
+                    .filter_map(|item| Some((item, item.placement.as_ref()?)))
+                    .collect();
+                let timeline_end = items
+                    .iter()
+                    .map(|(_, placement)| placement.record_out())
+                    .max()
+                    .unwrap_or(0);
+                // Audio-only clips leave the picture to the tracks below
+                items.retain(|(item, _)| {
+                    state
+                        .media_pool
+                        .clips
+                        .get(&item.clip_name)
+                        .is_none_or(|clip| MediaKind::of(&clip.file_path) != MediaKind::Audio)
+                });
+                if timeline_end == 0 {
+                    return Err(ResolveError::invalid_parameter(
+                        "timeline_name",
+                        format!("timeline '{}' has nothing to preview", timeline_name),
+                    ));
+                }
+                let (start, end) = frame_range(&args, timeline_end, rate)?;
+
+                // The topmost item shows between each pair of edit points
+                let mut cuts = vec![start, end];
+                for (_, placement) in &items {
+                    cuts.extend([placement.record_in, placement.record_out()]);
+                }
+                cuts.retain(|frame| (start..=end).contains(frame));
+                cuts.sort_unstable();
+                cuts.dedup();
+                let mut pieces: Vec<PreviewPiece> = Vec::new();
+                for pair in cuts.windows(2) {
+                    let (from, to) = (pair[0], pair[1]);
+                    let shown = items
+                        .iter()
+                        .filter(|(_, placement)| {
+                            placement.record_in <= from && from < placement.record_out()
+                        })
+                        .max_by_key(|(_, placement)| placement.track_index);
+                    let piece = match shown {
+                        Some((item, placement)) => {
+                            let file = state
+                                .media_pool
+                                .clips
+                                .get(&item.clip_name)
+                                .filter(|_| item.generator.is_none())
+                                .map(|clip| clip.file_path.clone())
+                                .filter(|path| Path::new(path).is_file());
+                            PreviewPiece {
+                                clip_name: Some(item.clip_name.clone()),
+                                source: file,
+                                frame: from - start,
+                                frames: to - from,
+                                source_seconds: (placement.source_in + from - placement.record_in)
+                                    as f64
+                                    / rate.fps(),
+                            }
+                        }
+                        None => PreviewPiece {
+                            clip_name: None,
+                            source: None,
+                            frame: from - start,
+                            frames: to - from,
+                            source_seconds: 0.0,
+                        },
+                    };
+                    // Stretches continuing the same source join up
+                    match pieces.last_mut() {
+                        Some(last)
+                            if last.clip_name == piece.clip_name
+                                && last.source == piece.source
+                                && (piece.source.is_none()
+                                    || (last.source_seconds + last.frames as f64 / rate.fps()
+                                        - piece.source_seconds)
+                                        .abs()
+                                        < 0.5 / rate.fps()) =>
+                        {
+                            last.frames += piece.frames;
+                        }
+                        _ => pieces.push(piece),
+                    }
+                }
+                let target = json!({
+                    "timeline_name": timeline_name,
+                    "start_frame": start,
+                    "end_frame": end
+                });
+                (rate, aspect, pieces, target, timeline_name)
+            }
+        };
+
+        let output = match output_path {
+            Some(path) => PathBuf::from(path),
+            None => self
+                .config
+                .cache
+                .directory
+                .as_ref()
+                .map_or_else(
+                    || std::env::temp_dir().join("davinci_mcp_previews"),
+                    |directory| directory.join("previews"),
+                )
+                .join(format!(
+                    "{}_{}.{}",
+                    file_stem(&name),
+                    &Uuid::new_v4().simple().to_string()[..8],
+                    format.as_str()
+                )),
+        };
+        let preview = Preview {
+            format,
+            size: format.size(aspect),
+            fps: format.fps(rate.fps()),
+            rate,
+            pieces,
+            output,
+            target,
+        };
+        let planned = preview.report();
+        let description = format!("{} preview of '{}'", format.as_str().to_uppercase(), name);
+        let job_id = self.spawn_job(state, "preview", description.clone(), move || preview.run());
+
+        Ok(json!({
+            "result": format!("Started {} as job '{}'", description, job_id),
+            "job_id": job_id,
+            "status": "running",
+            "target": planned["target"],
+            "format": planned["format"],
+            "width": planned["width"],
+            "height": planned["height"],
+            "fps": planned["fps"],
+            "duration_seconds": planned["duration_seconds"],
+            "piece_count": planned["pieces"].as_array().map_or(0, Vec::len),
+            "output_path": planned["output_path"],
+            "operation_id": Uuid::new_v4().to_string()
+        }))
+    }
+}
@@ -0,0 +1,347 @@
+//! Privacy blur
+//!
+//! Blurs faces, plates or other regions a detector found, or that were drawn
+//! by hand, in one call. Each region becomes a blur node on the grade of the
+//! item's clip, holding a window of the region's shape, and the window
+//! follows the region through keyframes on the timeline item: one keyframe
+//! per tracked frame for each of the window's center and size.
+//!
+//! Regions are given the way detectors report them, as boxes with their top
+//! left corner, width and height in fractions of the frame, at frames counted
+//! from the item's first frame. Every region is checked before anything is
+//! applied, so a bad region leaves the grades and keyframes as they were.
+
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use super::{
+    ensure_capacity, InterpolationType, Keyframe, KeyframeModes, ResolveBridge, StateView,
+    TimelineItemKeyframes,
+};
+use crate::error::{ResolveError, ResolveResult};
+
+const DEFAULT_BLUR_STRENGTH: f64 = 60.0;
+const DEFAULT_SOFTNESS: f64 = 15.0;
+
+/// Window shape of a privacy mask
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+pub(super) enum MaskShape {
+    Ellipse,
+    Rectangle,
+}
+
+impl MaskShape {
+    fn as_str(self) -> &'static str {
+        match self {
+            MaskShape::Ellipse => "ellipse",
+            MaskShape::Rectangle => "rectangle",
+        }
+    }
+}
+
+/// A blur window on a grade node, tracked on one timeline item
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub(super) struct PrivacyMask {
+    pub(super) label: String,
+    pub(super) shape: MaskShape,
+    /// Blur radius, 0 to 100
+    pub(super) blur_strength: f64,
+    /// Window edge softness, 0 to 100
+    pub(super) softness: f64,
+    /// Item whose keyframes move the window
+    pub(super) timeline_item_id: String,
+}
+
+impl PrivacyMask {
+    pub(super) fn to_json(&self) -> Value {
+        json!({
+            "label": self.label,
+            "shape": self.shape.as_str(),
+            "blur_strength": self.blur_strength,
+            "softness": self.softness,
+            "timeline_item_id": self.timeline_item_id
+        })
+    }
+}
+
+/// Window position at one frame, in fractions of the frame
+struct TrackPoint {
+    frame: i32,
+    center_x: f64,
+    center_y: f64,
+    width: f64,
+    height: f64,
+}
+
+/// A region checked and ready to apply
+struct Region {
+    timeline_item_id: String,
+    clip_name: String,
+    label: String,
+    shape: MaskShape,
+    track: Vec<TrackPoint>,
+}
+
+/// A keyframed window field and its value at a tracked frame
+type WindowField = (&'static str, fn(&TrackPoint) -> f64);
+
+const WINDOW_FIELDS: [WindowField; 4] = [
+    ("Center X", |point| point.center_x),
+    ("Center Y", |point| point.center_y),
+    ("Width", |point| point.width),
+    ("Height", |point| point.height),
+];
+
+/// Keyframed property of a node's window
+fn window_property(node_index: i32, field: &str) -> String {
+    format!("Node {} Window {}", node_index, field)
+}
+
+/// A number from 0 to 100, or the default
+fn percent(args: &Value, param: &str, default: f64) -> ResolveResult<f64> {
+    match &args[param] {
+        Value::Null => Ok(default),
+        value => value
+            .as_f64()
+            .filter(|value| (0.0..=100.0).contains(value))
+            .ok_or_else(|| {
+                ResolveError::invalid_parameter(param, "must be a number from 0 to 100")
+            }),
+    }
+}
+
+impl ResolveBridge {
+    pub(super) async fn apply_privacy_blur(
+        &self,
+        state: &mut StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        let regions = args["regions"]
+            .as_array()
+            .filter(|regions| !regions.is_empty())
+            .ok_or_else(|| {
+                ResolveError::invalid_parameter("regions", "give at least one region to blur")
+            })?;
+        let blur_strength = percent(&args, "blur_strength", DEFAULT_BLUR_STRENGTH)?;
+        let softness = percent(&args, "softness", DEFAULT_SOFTNESS)?;
+        let regions = regions
+            .iter()
+            .enumerate()
+            .map(|(index, region)| Self::privacy_region(state, index, region))
+            .collect::<ResolveResult<Vec<Region>>>()?;
+
+        // Each window keyframes its center and size at every tracked frame
+        let mut added: HashMap<&str, usize> = HashMap::new();
+        for region in &regions {
+            *added.entry(region.timeline_item_id.as_str()).or_default() += region.track.len() * 4;
+        }
+        for (timeline_item_id, additional) in &added {
+            let existing = state
+                .keyframe_state
+                .timeline_item_keyframes
+                .get(*timeline_item_id)
+                .map_or(0, |item| {
+                    item.property_keyframes.values().map(Vec::len).sum()
+                });
+            ensure_capacity(
+                &format!("keyframes on timeline item '{}'", timeline_item_id),
+                existing,
+                *additional,
+                self.config.limits.max_keyframes_per_item,
+            )?;
+        }
+
+        let mut applied = Vec::with_capacity(regions.len());
+        for region in &regions {
+            let grade = state
+                .color_state
+                .clip_grades
+                .entry(region.clip_name.clone())
+                .or_default();
+            grade.node_count += 1;
+            let node_index = grade.node_count;
+            grade
+                .node_labels
+                .insert(node_index, format!("Privacy Blur: {}", region.label));
+            grade.privacy_masks.insert(
+                node_index,
+                PrivacyMask {
+                    label: region.label.clone(),
+                    shape: region.shape,
+                    blur_strength,
+                    softness,
+                    timeline_item_id: region.timeline_item_id.clone(),
+                },
+            );
+
+            let keyframe_state = &mut *state.keyframe_state;
+            let item_keyframes = keyframe_state
+                .timeline_item_keyframes
+                .entry(region.timeline_item_id.clone())
+                .or_insert_with(|| TimelineItemKeyframes {
+                    timeline_item_id: region.timeline_item_id.clone(),
+                    property_keyframes: HashMap::new(),
+                    keyframe_modes: KeyframeModes::default(),
+                });
+            let created_at = chrono::Utc::now().to_rfc3339();
+            for (field, value) in WINDOW_FIELDS {
+                let keyframes: Vec<Keyframe> = region
+                    .track
+                    .iter()
+                    .map(|point| {
+                        keyframe_state.keyframe_counter += 1;
+                        Keyframe {
+                            id: keyframe_state.keyframe_counter,
+                            frame: point.frame,
+                            value: value(point),
+                            interpolation: InterpolationType::Linear,
+                            created_at: created_at.clone(),
+                        }
+                    })
+                    .collect();
+                item_keyframes
+                    .property_keyframes
+                    .insert(window_property(node_index, field), keyframes);
+            }
+
+            let (first, last) = (&region.track[0], &region.track[region.track.len() - 1]);
+            applied.push(json!({
+                "label": region.label,
+                "timeline_item_id": region.timeline_item_id,
+                "clip_name": region.clip_name,
+                "node_index": node_index,
+                "shape": region.shape.as_str(),
+                "tracked_frames": region.track.len(),
+                "first_frame": first.frame,
+                "last_frame": last.frame,
+                "keyframed_properties": WINDOW_FIELDS
+                    .iter()
+                    .map(|(field, _)| window_property(node_index, field))
+                    .collect::<Vec<_>>()
+            }));
+        }
+
+        Ok(json!({
+            "result": format!(
+                "Blurred {} regions on {} timeline items",
+                applied.len(),
+                added.len()
+            ),
+            "regions": applied,
+            "blur_strength": blur_strength,
+            "softness": softness,
+            "operation_id": Uuid::new_v4().to_string()
+        }))
+    }
+
+    /// Check one region of apply_privacy_blur against its item
+    fn privacy_region(
+        state: &StateView<'_>,
+        index: usize,
+        region: &Value,
+    ) -> ResolveResult<Region> {
+        let param = |field: &str| format!("regions[{}].{}", index, field);
+        let timeline_item_id = region["timeline_item_id"].as_str().ok_or_else(|| {
+            ResolveError::invalid_parameter(param("timeline_item_id"), "required string")
+        })?;
+        let item = state
+            .timeline_items
+            .items
+            .get(timeline_item_id)
+            .ok_or_else(|| {
+                ResolveError::invalid_parameter(
+                    param("timeline_item_id"),
+                    format!("no timeline item '{}'", timeline_item_id),
+                )
+            })?;
+        let label = region["label"]
+            .as_str()
+            .map(str::to_string)
+            .unwrap_or_else(|| format!("Region {}", index + 1));
+        let shape = match region["shape"].as_str().unwrap_or("ellipse") {
+            "ellipse" => MaskShape::Ellipse,
+            "rectangle" => MaskShape::Rectangle,
+            _ => {
+                return Err(ResolveError::invalid_parameter(
+                    param("shape"),
+                    "must be 'ellipse' or 'rectangle'",
+                ))
+            }
+        };
+
+        let boxes = region["boxes"]
+            .as_array()
+            .filter(|boxes| !boxes.is_empty())
+            .ok_or_else(|| {
+                ResolveError::invalid_parameter(param("boxes"), "give at least one tracked box")
+            })?;
+        let duration = item.placement.as_ref().map(|placement| placement.duration);
+        let mut track = Vec::with_capacity(boxes.len());
+        for tracked in boxes {
+            let frame = tracked["frame"]
+                .as_i64()
+                .filter(|frame| *frame >= 0 && duration.is_none_or(|duration| *frame < duration))
+                .ok_or_else(|| {
+                    ResolveError::invalid_parameter(
+                        param("boxes.frame"),
+                        match duration {
+                            Some(duration) => {
+                                format!("must be a frame of the item, 0 to {}", duration - 1)
+                            }
+                            None => "must be a frame of the item, 0 or more".to_string(),
+                        },
+                    )
+                })?;
+            let fraction = |field: &str| {
+                tracked[field]
+                    .as_f64()
+                    .filter(|value| (0.0..=1.0).contains(value))
+                    .ok_or_else(|| {
+                        ResolveError::invalid_parameter(
+                            param(&format!("boxes.{}", field)),
+                            "must be a fraction of the frame from 0 to 1",
+                        )
+                    })
+            };
+            let (x, y, width, height) = (
+                fraction("x")?,
+                fraction("y")?,
+                fraction("width")?,
+                fraction("height")?,
+            );
+            if width == 0.0 || height == 0.0 || x + width > 1.0 || y + height > 1.0 {
+                return Err(ResolveError::invalid_parameter(
+                    param("boxes"),
+                    format!(
+                        "box at frame {} must have a size and fit in the frame",
+                        frame
+                    ),
+                ));
+            }
+            track.push(TrackPoint {
+                frame: frame as i32,
+                center_x: x + width / 2.0,
+                center_y: y + height / 2.0,
+                width,
+                height,
+            });
+        }
+        track.sort_by_key(|point| point.frame);
+        if let Some(pair) = track.windows(2).find(|pair| pair[0].frame == pair[1].frame) {
+            return Err(ResolveError::invalid_parameter(
+                param("boxes"),
+                format!("frame {} is tracked twice", pair[0].frame),
+            ));
+        }
+
+        Ok(Region {
+            timeline_item_id: timeline_item_id.to_string(),
+            clip_name: item.clip_name.clone(),
+            label,
+            shape,
+            track,
+        })
+    }
+}
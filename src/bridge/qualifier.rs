@@ -0,0 +1,435 @@
+//! HSL qualifiers on grade nodes
+//!
+//! Each node of a clip grade carries the controls of the color page's
+//! Qualifier palette in HSL mode: a hue range around a center, saturation and
+//! luminance ranges between a low and a high level, each with its softness,
+//! and the Matte Finesse controls that clean up the key. Every control has the
+//! range of its palette counterpart and reads as the palette default until
+//! set, so a node with nothing set keys the whole frame.
+//!
+//! Resolve's scripting API does not reach the Qualifier palette, so in Real
+//! mode these calls fall back to the grade model like the Motion Effects
+//! calls. Responses name each control as the palette labels it, for applying
+//! the key through a PowerGrade or DRX.
+
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+use std::collections::BTreeMap;
+use uuid::Uuid;
+
+use super::{ResolveBridge, StateView};
+use crate::error::{ResolveError, ResolveResult};
+
+/// Group of controls in the Qualifier palette
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+pub(super) enum Section {
+    Hue,
+    Saturation,
+    Luminance,
+    MatteFinesse,
+}
+
+impl Section {
+    const ALL: [Section; 4] = [
+        Section::Hue,
+        Section::Saturation,
+        Section::Luminance,
+        Section::MatteFinesse,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            Section::Hue => "hue",
+            Section::Saturation => "saturation",
+            Section::Luminance => "luminance",
+            Section::MatteFinesse => "matte_finesse",
+        }
+    }
+
+    /// Prefix of the section's arguments
+    fn prefix(self) -> &'static str {
+        match self {
+            Section::Hue => "hue_",
+            Section::Saturation => "sat_",
+            Section::Luminance => "lum_",
+            Section::MatteFinesse => "",
+        }
+    }
+}
+
+/// Values a control accepts, with the palette default
+enum Values {
+    Number { min: f64, max: f64, default: f64 },
+    Switch { default: bool },
+}
+
+/// One control of the Qualifier palette
+struct Control {
+    section: Section,
+    /// Name in responses and, with the section prefix, in arguments
+    name: &'static str,
+    /// The control as the palette labels it
+    label: &'static str,
+    values: Values,
+}
+
+const ENABLED: Values = Values::Switch { default: true };
+const LEVEL_LOW: Values = Values::Number {
+    min: 0.0,
+    max: 100.0,
+    default: 0.0,
+};
+const LEVEL_HIGH: Values = Values::Number {
+    min: 0.0,
+    max: 100.0,
+    default: 100.0,
+};
+const SOFTNESS: Values = Values::Number {
+    min: 0.0,
+    max: 50.0,
+    default: 0.0,
+};
+const FINESSE: Values = Values::Number {
+    min: 0.0,
+    max: 100.0,
+    default: 0.0,
+};
+
+const CONTROLS: &[Control] = &[
+    Control {
+        section: Section::Hue,
+        name: "enabled",
+        label: "Hue",
+        values: ENABLED,
+    },
+    Control {
+        section: Section::Hue,
+        name: "center",
+        label: "Hue > Center",
+        values: Values::Number {
+            min: 0.0,
+            max: 100.0,
+            default: 50.0,
+        },
+    },
+    Control {
+        section: Section::Hue,
+        name: "width",
+        label: "Hue > Width",
+        values: LEVEL_HIGH,
+    },
+    Control {
+        section: Section::Hue,
+        name: "softness",
+        label: "Hue > Soft",
+        values: SOFTNESS,
+    },
+    Control {
+        section: Section::Hue,
+        name: "symmetry",
+        label: "Hue > Sym",
+        values: Values::Number {
+            min: 0.0,
+            max: 100.0,
+            default: 50.0,
+        },
+    },
+    Control {
+        section: Section::Saturation,
+        name: "enabled",
+        label: "Sat",
+        values: ENABLED,
+    },
+    Control {
+        section: Section::Saturation,
+        name: "low",
+        label: "Sat > Low",
+        values: LEVEL_LOW,
+    },
+    Control {
+        section: Section::Saturation,
+        name: "high",
+        label: "Sat > High",
+        values: LEVEL_HIGH,
+    },
+    Control {
+        section: Section::Saturation,
+        name: "low_softness",
+        label: "Sat > L. Soft",
+        values: SOFTNESS,
+    },
+    Control {
+        section: Section::Saturation,
+        name: "high_softness",
+        label: "Sat > H. Soft",
+        values: SOFTNESS,
+    },
+    Control {
+        section: Section::Luminance,
+        name: "enabled",
+        label: "Lum",
+        values: ENABLED,
+    },
+    Control {
+        section: Section::Luminance,
+        name: "low",
+        label: "Lum > Low",
+        values: LEVEL_LOW,
+    },
+    Control {
+        section: Section::Luminance,
+        name: "high",
+        label: "Lum > High",
+        values: LEVEL_HIGH,
+    },
+    Control {
+        section: Section::Luminance,
+        name: "low_softness",
+        label: "Lum > L. Soft",
+        values: SOFTNESS,
+    },
+    Control {
+        section: Section::Luminance,
+        name: "high_softness",
+        label: "Lum > H. Soft",
+        values: SOFTNESS,
+    },
+    Control {
+        section: Section::MatteFinesse,
+        name: "denoise",
+        label: "Matte Finesse > Denoise",
+        values: FINESSE,
+    },
+    Control {
+        section: Section::MatteFinesse,
+        name: "clean_black",
+        label: "Matte Finesse > Clean Black",
+        values: FINESSE,
+    },
+    Control {
+        section: Section::MatteFinesse,
+        name: "clean_white",
+        label: "Matte Finesse > Clean White",
+        values: FINESSE,
+    },
+    Control {
+        section: Section::MatteFinesse,
+        name: "blur_radius",
+        label: "Matte Finesse > Blur Radius",
+        values: FINESSE,
+    },
+    Control {
+        section: Section::MatteFinesse,
+        name: "invert",
+        label: "Qualifier > Invert",
+        values: Values::Switch { default: false },
+    },
+];
+
+impl Control {
+    /// Argument the control is set with
+    fn arg(&self) -> String {
+        format!("{}{}", self.section.prefix(), self.name)
+    }
+
+    fn default_value(&self) -> Value {
+        match self.values {
+            Values::Number { default, .. } => json!(default),
+            Values::Switch { default } => json!(default),
+        }
+    }
+
+    fn parse(&self, value: &Value) -> ResolveResult<Value> {
+        let arg = self.arg();
+        match self.values {
+            Values::Number { min, max, .. } => value
+                .as_f64()
+                .filter(|value| (min..=max).contains(value))
+                .map(|value| json!(value))
+                .ok_or_else(|| {
+                    ResolveError::invalid_parameter(
+                        &arg,
+                        format!("must be a number from {} to {}", min, max),
+                    )
+                }),
+            Values::Switch { .. } => value
+                .as_bool()
+                .map(|value| json!(value))
+                .ok_or_else(|| ResolveError::invalid_parameter(&arg, "must be true or false")),
+        }
+    }
+}
+
+/// Qualifier controls set on one node, by section and control name
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub(super) struct NodeQualifier {
+    values: BTreeMap<(Section, &'static str), Value>,
+}
+
+impl NodeQualifier {
+    fn get(&self, control: &Control) -> Value {
+        self.values
+            .get(&(control.section, control.name))
+            .cloned()
+            .unwrap_or_else(|| control.default_value())
+    }
+
+    fn value(&self, section: Section, name: &str) -> Value {
+        CONTROLS
+            .iter()
+            .find(|control| control.section == section && control.name == name)
+            .map(|control| self.get(control))
+            .unwrap_or(Value::Null)
+    }
+
+    fn number(&self, section: Section, name: &str) -> f64 {
+        self.value(section, name).as_f64().unwrap_or(0.0)
+    }
+
+    /// Whether a range narrows the key with these values
+    fn active(&self, section: Section) -> bool {
+        let enabled = self.value(section, "enabled").as_bool().unwrap_or(false);
+        match section {
+            Section::Hue => enabled && self.number(section, "width") < 100.0,
+            Section::Saturation | Section::Luminance => {
+                enabled
+                    && (self.number(section, "low") > 0.0 || self.number(section, "high") < 100.0)
+            }
+            Section::MatteFinesse => CONTROLS
+                .iter()
+                .filter(|control| control.section == section)
+                .any(|control| self.get(control) != control.default_value()),
+        }
+    }
+
+    /// Every control by section, with whether each section is active and
+    /// whether the node keys part of the frame
+    pub(super) fn to_json(&self) -> Value {
+        let mut sections = Map::new();
+        for section in Section::ALL {
+            let mut fields = Map::new();
+            fields.insert("active".to_string(), json!(self.active(section)));
+            for control in CONTROLS.iter().filter(|control| control.section == section) {
+                fields.insert(control.name.to_string(), self.get(control));
+            }
+            sections.insert(section.name().to_string(), Value::Object(fields));
+        }
+        let keying = [Section::Hue, Section::Saturation, Section::Luminance]
+            .into_iter()
+            .any(|section| self.active(section));
+        sections.insert("keying".to_string(), json!(keying));
+        Value::Object(sections)
+    }
+
+    /// Values set on the node, for hashing a grade
+    pub(super) fn set_values(&self) -> impl Iterator<Item = (&(Section, &'static str), &Value)> {
+        self.values.iter()
+    }
+}
+
+impl ResolveBridge {
+    /// Validate every given qualifier control, then set them together
+    pub(super) async fn set_qualifier(
+        &self,
+        state: &mut StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        let clip_name = Self::required_grade_clip(state, &args)?;
+        let node_index = Self::grade_node(state, &args)?;
+        let mut changes = Vec::new();
+        for control in CONTROLS {
+            let value = &args[control.arg().as_str()];
+            if !value.is_null() {
+                changes.push((control, control.parse(value)?));
+            }
+        }
+        if changes.is_empty() {
+            let args: Vec<String> = CONTROLS.iter().map(Control::arg).collect();
+            return Err(ResolveError::invalid_parameter(
+                &args[0],
+                format!("set at least one of {}", args.join(", ")),
+            ));
+        }
+
+        let mut qualifier = state
+            .color_state
+            .clip_grades
+            .get(&clip_name)
+            .and_then(|grade| grade.qualifiers.get(&node_index))
+            .cloned()
+            .unwrap_or_default();
+        for (control, value) in &changes {
+            qualifier
+                .values
+                .insert((control.section, control.name), value.clone());
+        }
+        for section in [Section::Saturation, Section::Luminance] {
+            if qualifier.number(section, "low") > qualifier.number(section, "high") {
+                return Err(ResolveError::invalid_parameter(
+                    format!("{}low", section.prefix()),
+                    format!("{} low must not be above its high", section.name()),
+                ));
+            }
+        }
+
+        let changed: Vec<Value> = changes
+            .into_iter()
+            .map(|(control, value)| {
+                json!({
+                    "control": control.arg(),
+                    "resolve_control": control.label,
+                    "value": value
+                })
+            })
+            .collect();
+        let response = qualifier.to_json();
+        state
+            .color_state
+            .clip_grades
+            .entry(clip_name.clone())
+            .or_default()
+            .qualifiers
+            .insert(node_index, qualifier);
+        Ok(json!({
+            "result": format!(
+                "Set {} qualifier controls on node {} of clip '{}'",
+                changed.len(),
+                node_index,
+                clip_name
+            ),
+            "clip_name": clip_name,
+            "node_index": node_index,
+            "changed": changed,
+            "qualifier": response,
+            "operation_id": Uuid::new_v4().to_string()
+        }))
+    }
+
+    pub(super) async fn get_qualifier(
+        &self,
+        state: &mut StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        let clip_name = Self::required_grade_clip(state, &args)?;
+        let node_index = Self::grade_node(state, &args)?;
+        let qualifier = state
+            .color_state
+            .clip_grades
+            .get(&clip_name)
+            .and_then(|grade| grade.qualifiers.get(&node_index))
+            .cloned()
+            .unwrap_or_default();
+        let resolve_controls: Map<String, Value> = CONTROLS
+            .iter()
+            .map(|control| (control.arg(), json!(control.label)))
+            .collect();
+        Ok(json!({
+            "result": format!("Read qualifier of node {} on clip '{}'", node_index, clip_name),
+            "clip_name": clip_name,
+            "node_index": node_index,
+            "qualifier": qualifier.to_json(),
+            "resolve_controls": resolve_controls,
+            "operation_id": Uuid::new_v4().to_string()
+        }))
+    }
+}
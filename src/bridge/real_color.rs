@@ -0,0 +1,302 @@
+//! Color page operations against the running Resolve
+//!
+//! In Real mode apply_lut, set_color_wheel_param, adjust_color_wheel_param
+//! and copy_grade run as Python scripts. Each first selects its target the
+//! way a colorist would: a named clip is found on the current timeline by its
+//! item or media pool name and the playhead moved onto it on the color page,
+//! and with no clip named the current video item is used.
+//!
+//! The scripting API has no color wheels, so wheel changes are sent as the
+//! clip's CDL: gain becomes the slope, gamma the power and offset the offset.
+//! Resolve cannot read a CDL back, so the channels a call leaves alone come
+//! from the wheel values the bridge last sent for the clip, which are kept in
+//! its grade record. Lift has no CDL equivalent, and nodes cannot be added
+//! through the API at all; those calls fall back to the simulation.
+
+use serde_json::{json, Value};
+
+use super::clip_grades::{self, clamp_wheel_value};
+use super::locking::{Domain, LockPlan};
+use super::{ClipGrade, ColorWheelParams, ResolveBridge, SCRIPT_TIMELINE};
+use crate::error::{ResolveError, ResolveResult};
+
+/// Methods with a real color page path
+pub(super) const METHODS: [&str; 5] = [
+    "apply_lut",
+    "set_color_wheel_param",
+    "adjust_color_wheel_param",
+    "add_node",
+    "copy_grade",
+];
+
+/// Defines `select_item` and `find_item`
+const DEFINITIONS: &str = r#"
+def timecode(timeline, frame):
+    rate = int(round(float(timeline.GetSetting("timelineFrameRate") or 24)))
+    return "%02d:%02d:%02d:%02d" % (
+        frame // (rate * 3600), frame // (rate * 60) % 60, frame // rate % 60, frame % rate)
+
+def find_item(timeline, clip_name):
+    for index in range(1, timeline.GetTrackCount("video") + 1):
+        for item in timeline.GetItemListInTrack("video", index) or []:
+            media = item.GetMediaPoolItem()
+            if clip_name in (item.GetName(), media.GetName() if media else None):
+                return item
+    return None
+
+def select_item(resolve, timeline, clip_name):
+    resolve.OpenPage("color")
+    if not clip_name:
+        return timeline.GetCurrentVideoItem()
+    item = find_item(timeline, clip_name)
+    if item:
+        timeline.SetCurrentTimecode(timecode(timeline, item.GetStart()))
+    return item
+
+def clip_name_of(item):
+    media = item.GetMediaPoolItem()
+    return media.GetName() if media else item.GetName()
+"#;
+
+/// Selects `request["clip_name"]` as `item`
+const SELECT_TARGET: &str = r#"
+    item = select_item(resolve, timeline, request["clip_name"])
+    if not item:
+        fail("Clip not found on the current timeline: " + str(request["clip_name"] or "no current clip"))
+"#;
+
+const APPLY_LUT: &str = r#"
+    if request["node_index"] > item.GetNumNodes():
+        fail("The clip has no node %d" % request["node_index"])
+    if not item.SetLUT(request["node_index"], request["lut_path"]):
+        fail("Resolve could not apply " + request["lut_path"])
+    print(json.dumps({
+        "success": True,
+        "clip_name": clip_name_of(item),
+        "timeline_item_id": item.GetUniqueId()
+    }))
+"#;
+
+const SET_CDL: &str = r#"
+    if not item.SetCDL(request["cdl"]):
+        fail("Resolve could not set the CDL")
+    print(json.dumps({
+        "success": True,
+        "clip_name": clip_name_of(item),
+        "timeline_item_id": item.GetUniqueId()
+    }))
+"#;
+
+const COPY_GRADE: &str = r#"
+    source = item
+    target = find_item(timeline, request["target_clip_name"]) if request["target_clip_name"] else source
+    if not target:
+        fail("Clip not found on the current timeline: " + request["target_clip_name"])
+    if target.GetUniqueId() == source.GetUniqueId():
+        fail("The source and target clip are the same")
+    if not source.CopyGrades([target]):
+        fail("Resolve could not copy the grade")
+    print(json.dumps({
+        "success": True,
+        "clip_name": clip_name_of(source),
+        "target_clip_name": clip_name_of(target),
+        "timeline_item_id": source.GetUniqueId(),
+        "target_timeline_item_id": target.GetUniqueId()
+    }))
+"#;
+
+/// A script running `operation` on the target of `request`
+fn script(request: &Value, operation: &str) -> ResolveResult<String> {
+    ResolveBridge::resolve_script(
+        DEFINITIONS,
+        SCRIPT_TIMELINE,
+        request,
+        &format!("{}{}", SELECT_TARGET, operation),
+    )
+}
+
+/// The wheel of a grade by name, to change
+fn wheel_mut<'g>(grade: &'g mut ClipGrade, wheel: &str) -> Option<&'g mut ColorWheelParams> {
+    match wheel {
+        "lift" => Some(&mut grade.lift),
+        "gamma" => Some(&mut grade.gamma),
+        "gain" => Some(&mut grade.gain),
+        "offset" => Some(&mut grade.offset),
+        _ => None,
+    }
+}
+
+fn param_mut<'w>(wheel: &'w mut ColorWheelParams, param: &str) -> Option<&'w mut f64> {
+    match param {
+        "red" => Some(&mut wheel.red),
+        "green" => Some(&mut wheel.green),
+        "blue" => Some(&mut wheel.blue),
+        "master" => Some(&mut wheel.master),
+        _ => None,
+    }
+}
+
+/// SetCDL values of a grade's gain, gamma and offset wheels
+fn cdl(grade: &ClipGrade, node_index: i64) -> Value {
+    let channels = |wheel: &ColorWheelParams, value: &dyn Fn(f64) -> f64| {
+        [wheel.red, wheel.green, wheel.blue]
+            .iter()
+            .map(|channel| format!("{:.4}", value(channel + wheel.master)))
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+    json!({
+        "NodeIndex": node_index.to_string(),
+        "Slope": channels(&grade.gain, &|gain| (1.0 + gain).max(0.0)),
+        "Offset": channels(&grade.offset, &|offset| offset),
+        "Power": channels(&grade.gamma, &|gamma| (1.0 - gamma).max(0.01)),
+        "Saturation": "1.0"
+    })
+}
+
+impl ResolveBridge {
+    /// Run a color page operation in the running Resolve
+    pub(super) async fn real_color_operation(
+        &self,
+        method: &str,
+        args: &Value,
+    ) -> ResolveResult<Value> {
+        let node_index = args["node_index"].as_i64().unwrap_or(1);
+        if node_index < 1 {
+            return Err(ResolveError::invalid_parameter(
+                "node_index",
+                "nodes are numbered from 1",
+            ));
+        }
+        match method {
+            "apply_lut" => {
+                let lut_path = args["lut_path"].as_str().ok_or_else(|| {
+                    ResolveError::invalid_parameter("lut_path", "required string")
+                })?;
+                let request = json!({
+                    "clip_name": args["clip_name"],
+                    "lut_path": lut_path,
+                    "node_index": node_index
+                });
+                let mut output = self.run_resolve_script(method, &script(&request, APPLY_LUT)?)?;
+                output["result"] = json!(format!(
+                    "Applied LUT '{}' to node {} of clip '{}'",
+                    lut_path,
+                    node_index,
+                    output["clip_name"].as_str().unwrap_or_default()
+                ));
+                output["lut_path"] = json!(lut_path);
+                output["node_index"] = json!(node_index);
+                Ok(output)
+            }
+            "set_color_wheel_param" | "adjust_color_wheel_param" => {
+                self.real_color_wheel(method, args, node_index).await
+            }
+            "copy_grade" => {
+                let mode = args["mode"].as_str().unwrap_or("full");
+                if mode == "current_node" {
+                    return Err(ResolveError::not_supported(
+                        "copying a single node: Resolve's API copies whole grades",
+                    ));
+                }
+                let request = json!({
+                    "clip_name": args["source_clip_name"],
+                    "target_clip_name": args["target_clip_name"]
+                });
+                let mut output = self.run_resolve_script(method, &script(&request, COPY_GRADE)?)?;
+                output["result"] = json!(format!(
+                    "Copied the grade from '{}' to '{}'",
+                    output["clip_name"].as_str().unwrap_or_default(),
+                    output["target_clip_name"].as_str().unwrap_or_default()
+                ));
+                output["mode"] = json!(mode);
+                Ok(output)
+            }
+            _ => Err(ResolveError::not_supported(format!(
+                "{} in Resolve's scripting API",
+                method
+            ))),
+        }
+    }
+
+    /// Set one wheel parameter through the CDL of the target clip
+    async fn real_color_wheel(
+        &self,
+        method: &str,
+        args: &Value,
+        node_index: i64,
+    ) -> ResolveResult<Value> {
+        let wheel = args["wheel"]
+            .as_str()
+            .ok_or_else(|| ResolveError::invalid_parameter("wheel", "required string"))?;
+        let param = args["param"]
+            .as_str()
+            .ok_or_else(|| ResolveError::invalid_parameter("param", "required string"))?;
+        if !clip_grades::WHEELS.contains(&wheel) || !clip_grades::WHEEL_PARAMS.contains(&param) {
+            return Err(ResolveError::invalid_parameter(
+                "wheel",
+                "must be lift, gamma, gain or offset, with red, green, blue or master",
+            ));
+        }
+        if wheel == "lift" {
+            return Err(ResolveError::not_supported(
+                "lift in Resolve's scripting API, which only sets a CDL",
+            ));
+        }
+        let (value, relative) = match method {
+            "adjust_color_wheel_param" => (args["delta"].as_f64(), true),
+            _ => (
+                args["value"].as_f64(),
+                args["relative"].as_bool().unwrap_or(false),
+            ),
+        };
+        let value =
+            value.ok_or_else(|| ResolveError::invalid_parameter("value", "required number"))?;
+
+        // Held across the script so relative changes to one clip line up
+        let mut state = self
+            .state
+            .lock(LockPlan::write(&[Domain::Color]).reading(&[]))
+            .await;
+        let known_clip = args["clip_name"]
+            .as_str()
+            .map(str::to_string)
+            .or_else(|| state.color_state.current_clip.clone());
+        let mut grade = known_clip
+            .as_ref()
+            .and_then(|clip| state.color_state.clip_grades.get(clip))
+            .cloned()
+            .unwrap_or_default();
+        let Some(slot) = wheel_mut(&mut grade, wheel).and_then(|wheel| param_mut(wheel, param))
+        else {
+            return Err(ResolveError::invalid_parameter(
+                "param",
+                "unknown wheel parameter",
+            ));
+        };
+        let previous = *slot;
+        *slot = clamp_wheel_value(if relative { previous + value } else { value });
+        let applied = *slot;
+
+        let cdl = cdl(&grade, node_index);
+        let request = json!({ "clip_name": args["clip_name"], "cdl": cdl });
+        let mut output = self.run_resolve_script(method, &script(&request, SET_CDL)?)?;
+        let clip_name = output["clip_name"].as_str().unwrap_or_default().to_string();
+        state
+            .color_state
+            .clip_grades
+            .insert(clip_name.clone(), grade);
+
+        output["result"] = json!(format!(
+            "Set {} {} to {} on node {} of clip '{}' through its CDL",
+            wheel, param, applied, node_index, clip_name
+        ));
+        output["wheel"] = json!(wheel);
+        output["param"] = json!(param);
+        output["previous_value"] = json!(previous);
+        output["value"] = json!(applied);
+        output["node_index"] = json!(node_index);
+        output["cdl"] = cdl;
+        Ok(output)
+    }
+}
@@ -0,0 +1,273 @@
+//! Media pool operations against the running Resolve
+//!
+//! In Real mode import_media, create_bin, add_media_pool_sub_folder,
+//! create_sub_clip and move_media_to_bin run as Python scripts, so ingest
+//! works outside the simulation. Bins are media pool folders, found by name
+//! anywhere below the root folder; clips are found by clip name, then by
+//! file name.
+//!
+//! Media is imported with MediaStorage.AddItemListToMediaPool into the bin
+//! made current first, and metadata given to the import is checked against
+//! the active metadata schema before Resolve sees it. The API has no subclip
+//! call, so a subclip is the source file added again with a frame range and
+//! renamed, in the source clip's bin.
+
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use super::{ResolveBridge, SCRIPT_PROJECT};
+use crate::error::{ResolveError, ResolveResult};
+
+/// Methods with a real media pool path
+pub(super) const METHODS: [&str; 5] = [
+    "import_media",
+    "create_bin",
+    "add_media_pool_sub_folder",
+    "create_sub_clip",
+    "move_media_to_bin",
+];
+
+/// Defines the folder and clip lookups
+const DEFINITIONS: &str = r#"
+def folders(folder):
+    yield folder
+    for sub_folder in folder.GetSubFolderList() or []:
+        yield from folders(sub_folder)
+
+def find_folder(media_pool, name):
+    return next((folder for folder in folders(media_pool.GetRootFolder()) if folder.GetName() == name), None)
+
+def find_clip(media_pool, name):
+    for folder in folders(media_pool.GetRootFolder()):
+        for clip in folder.GetClipList() or []:
+            if name in (clip.GetName(), clip.GetClipProperty("File Name")):
+                return clip, folder
+    return None, None
+"#;
+
+/// Finds the current project's media pool as `media_pool`
+const SETUP: &str = r#"
+    media_pool = project.GetMediaPool()
+"#;
+
+const IMPORT: &str = r#"
+    created_bin = False
+    if request["bin_name"]:
+        folder = find_folder(media_pool, request["bin_name"])
+        if not folder:
+            folder = media_pool.AddSubFolder(media_pool.GetRootFolder(), request["bin_name"])
+            created_bin = True
+        if not folder or not media_pool.SetCurrentFolder(folder):
+            fail("Could not open bin " + request["bin_name"])
+    items = resolve.GetMediaStorage().AddItemListToMediaPool([request["file_path"]])
+    if not items:
+        fail("Resolve could not import " + request["file_path"])
+    item = items[0]
+    for field, value in request["metadata"].items():
+        if not item.SetMetadata(field, value):
+            fail("Resolve refused metadata field " + field)
+    print(json.dumps({
+        "success": True,
+        "clip_id": item.GetMediaId(),
+        "clip_name": item.GetName(),
+        "created_bin": created_bin,
+        "duration": item.GetClipProperty("Duration")
+    }))
+"#;
+
+const ADD_FOLDER: &str = r#"
+    parent = media_pool.GetRootFolder()
+    if request["parent_folder"]:
+        parent = find_folder(media_pool, request["parent_folder"])
+        if not parent:
+            fail("Bin not found: " + request["parent_folder"])
+    folder = find_folder(media_pool, request["name"])
+    existed = folder is not None
+    if not folder:
+        folder = media_pool.AddSubFolder(parent, request["name"])
+        if not folder:
+            fail("Resolve could not add bin " + request["name"])
+    print(json.dumps({"success": True, "folder_id": folder.GetUniqueId(), "already_existed": existed}))
+"#;
+
+const SUB_CLIP: &str = r#"
+    clip, folder = find_clip(media_pool, request["clip_name"])
+    if not clip:
+        fail("Clip not found: " + request["clip_name"])
+    media_pool.SetCurrentFolder(folder)
+    items = resolve.GetMediaStorage().AddItemListToMediaPool([{
+        "media": clip.GetClipProperty("File Path"),
+        "startFrame": request["start_frame"],
+        "endFrame": request["end_frame"] - 1
+    }])
+    if not items:
+        fail("Resolve could not add the subclip")
+    sub_clip = items[0]
+    sub_clip.SetClipProperty("Clip Name", request["sub_clip_name"])
+    print(json.dumps({"success": True, "subclip_id": sub_clip.GetMediaId(), "bin_name": folder.GetName()}))
+"#;
+
+const MOVE: &str = r#"
+    clip, source = find_clip(media_pool, request["clip_name"])
+    if not clip:
+        fail("Clip not found: " + request["clip_name"])
+    folder = find_folder(media_pool, request["bin_name"])
+    if not folder:
+        fail("Bin not found: " + request["bin_name"])
+    if not media_pool.MoveClips([clip], folder):
+        fail("Resolve could not move " + request["clip_name"])
+    print(json.dumps({"success": True, "clip_id": clip.GetMediaId(), "from_bin": source.GetName()}))
+"#;
+
+/// A script running `operation` with `request` in scope
+fn script(request: &Value, operation: &str) -> ResolveResult<String> {
+    ResolveBridge::resolve_script(
+        DEFINITIONS,
+        &format!("{}{}", SCRIPT_PROJECT, SETUP),
+        request,
+        operation,
+    )
+}
+
+fn required<'a>(args: &'a Value, name: &str) -> ResolveResult<&'a str> {
+    args[name]
+        .as_str()
+        .ok_or_else(|| ResolveError::invalid_parameter(name, "required string"))
+}
+
+impl ResolveBridge {
+    /// Run a media pool operation in the running Resolve
+    pub(super) async fn real_media_pool_operation(
+        &self,
+        method: &str,
+        args: &Value,
+    ) -> ResolveResult<Value> {
+        match method {
+            "import_media" => {
+                let file_path = required(args, "file_path")?;
+                let clip_name = std::path::Path::new(file_path)
+                    .file_name()
+                    .and_then(|name| name.to_str())
+                    .unwrap_or(file_path);
+                let metadata = self.import_metadata(&args["metadata"], clip_name)?;
+                let missing: Vec<&String> = self
+                    .active_metadata_schema()
+                    .map(|(_, schema)| {
+                        schema
+                            .fields
+                            .iter()
+                            .filter(|(field, rule)| rule.required && !metadata.contains_key(*field))
+                            .map(|(field, _)| field)
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                let request = json!({
+                    "file_path": file_path,
+                    "bin_name": args["bin_name"],
+                    "metadata": metadata
+                });
+                let output = self.run_resolve_script(method, &script(&request, IMPORT)?)?;
+                Ok(json!({
+                    "result": format!("Imported media: {}", output["clip_name"].as_str().unwrap_or(clip_name)),
+                    "clip_id": output["clip_id"],
+                    "bin_name": args["bin_name"],
+                    "created_bin": output["created_bin"].as_bool().unwrap_or(false),
+                    "sidecar": Value::Null,
+                    "missing_required_metadata": missing,
+                    "input_color_space": Value::Null,
+                    "input_lut": Value::Null,
+                    "file_size": Value::Null,
+                    "duration": output["duration"]
+                }))
+            }
+            "create_bin" | "add_media_pool_sub_folder" => {
+                let name = required(args, "name")?;
+                let request = json!({
+                    "name": name,
+                    "parent_folder": args["parent_folder"]
+                });
+                let output = self.run_resolve_script(method, &script(&request, ADD_FOLDER)?)?;
+                let existed = output["already_existed"].as_bool().unwrap_or(false);
+                Ok(if method == "create_bin" {
+                    json!({
+                        "result": if existed {
+                            format!("Bin '{}' already exists", name)
+                        } else {
+                            format!("Created bin '{}'", name)
+                        },
+                        "bin_id": output["folder_id"],
+                        "already_existed": existed
+                    })
+                } else {
+                    json!({
+                        "success": true,
+                        "result": if existed {
+                            format!("Media pool sub folder '{}' already exists", name)
+                        } else {
+                            format!("Added media pool sub folder '{}'", name)
+                        },
+                        "folder_name": name,
+                        "folder_id": output["folder_id"],
+                        "operation_id": format!("add_media_pool_sub_folder_{}", chrono::Utc::now().timestamp()),
+                        "already_existed": existed
+                    })
+                })
+            }
+            "create_sub_clip" => {
+                let clip_name = required(args, "clip_name")?;
+                let start_frame = args["start_frame"].as_i64().unwrap_or(0);
+                let end_frame = args["end_frame"].as_i64().unwrap_or(100);
+                if start_frame < 0 {
+                    return Err(ResolveError::invalid_parameter(
+                        "start_frame",
+                        "must not be negative",
+                    ));
+                }
+                if end_frame <= start_frame {
+                    return Err(ResolveError::invalid_parameter(
+                        "end_frame",
+                        "must be after start_frame",
+                    ));
+                }
+                let sub_clip_name = args["sub_clip_name"]
+                    .as_str()
+                    .map_or_else(|| format!("{}_subclip", clip_name), str::to_string);
+                let request = json!({
+                    "clip_name": clip_name,
+                    "sub_clip_name": sub_clip_name,
+                    "start_frame": start_frame,
+                    "end_frame": end_frame
+                });
+                let output = self.run_resolve_script(method, &script(&request, SUB_CLIP)?)?;
+                Ok(json!({
+                    "result": format!(
+                        "Created subclip '{}' from '{}' (frames {}-{})",
+                        sub_clip_name, clip_name, start_frame, end_frame
+                    ),
+                    "subclip_id": output["subclip_id"],
+                    "bin_name": output["bin_name"],
+                    "duration_frames": end_frame - start_frame
+                }))
+            }
+            "move_media_to_bin" => {
+                let clip_name = required(args, "clip_name")?;
+                let bin_name = required(args, "bin_name")?;
+                let request = json!({ "clip_name": clip_name, "bin_name": bin_name });
+                let output = self.run_resolve_script(method, &script(&request, MOVE)?)?;
+                Ok(json!({
+                    "result": format!("Moved clip '{}' to bin '{}'", clip_name, bin_name),
+                    "clip_name": clip_name,
+                    "clip_id": output["clip_id"],
+                    "bin_name": bin_name,
+                    "from_bin": output["from_bin"],
+                    "status": "success",
+                    "operation_id": Uuid::new_v4().to_string()
+                }))
+            }
+            _ => Err(ResolveError::not_supported(format!(
+                "Real API method: {}",
+                method
+            ))),
+        }
+    }
+}
@@ -0,0 +1,392 @@
+//! Render queue control against the running Resolve
+//!
+//! In Real mode the render queue tools run as Python scripts: jobs are added
+//! with LoadRenderPreset and AddRenderJob, started and stopped with
+//! StartRendering and StopRendering and deleted with DeleteRenderJob or
+//! DeleteAllRenderJobs. Every script ends by reading the queue back with
+//! GetRenderJobList and GetRenderJobStatus, and that list replaces the
+//! bridge's render queue the way the periodic state sync does. Responses are
+//! built from it with the same fields as in simulation, so a caller cannot
+//! tell which mode answered.
+//!
+//! Subtitle and channel layout options have no render setting in the
+//! scripting API; jobs asking for them fall back to the simulation.
+
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use super::locking::{Domain, LockPlan};
+use super::state_sync::{mirror_render_jobs, RenderJobSnapshot};
+use super::{RenderJobStatus, ResolveBridge, SCRIPT_PROJECT};
+use crate::error::{ResolveError, ResolveResult};
+
+/// Methods with a real render queue path
+pub(super) const METHODS: [&str; 9] = [
+    "add_to_render_queue",
+    "start_render",
+    "start_project_rendering",
+    "stop_project_rendering",
+    "clear_render_queue",
+    "delete_render_job",
+    "get_render_status",
+    "get_project_render_job_list",
+    "is_project_rendering_in_progress",
+];
+
+/// Defines `render_jobs`, the queue as the bridge models it
+const RENDER_JOBS: &str = r#"
+def render_jobs(project):
+    starts = {}
+    for index in range(1, project.GetTimelineCount() + 1):
+        timeline = project.GetTimelineByIndex(index)
+        if timeline:
+            starts[timeline.GetName()] = timeline.GetStartFrame()
+    jobs = []
+    for job in project.GetRenderJobList() or []:
+        status = project.GetRenderJobStatus(job["JobId"]) or {}
+        start = starts.get(job.get("TimelineName"), 0)
+        frame_range = None
+        if job.get("MarkIn") is not None and job.get("MarkOut") is not None:
+            frame_range = [job["MarkIn"] - start, job["MarkOut"] - start + 1]
+        jobs.append({
+            "job_id": job["JobId"],
+            "timeline_name": job.get("TimelineName", ""),
+            "preset_name": job.get("PresetName", ""),
+            "output_path": os.path.join(job.get("TargetDir", ""), job.get("OutputFilename", "")),
+            "frame_range": frame_range,
+            "export_video": job.get("IsExportVideo", True),
+            "export_audio": job.get("IsExportAudio", True),
+            "status": status.get("JobStatus", "Ready"),
+            "completion_percent": status.get("CompletionPercentage", 0)
+        })
+    return jobs
+"#;
+
+/// Starts the output each operation adds to
+const OUTPUT: &str = r#"
+    output = {"success": True}
+"#;
+
+/// Prints the output with the queue as it is afterwards
+const REPORT: &str = r#"
+    output["render_jobs"] = render_jobs(project)
+    output["is_rendering"] = bool(project.IsRenderingInProgress())
+    print(json.dumps(output))
+"#;
+
+const ADD_JOB: &str = r#"
+    if request["timeline_name"]:
+        timeline = None
+        for index in range(1, project.GetTimelineCount() + 1):
+            candidate = project.GetTimelineByIndex(index)
+            if candidate and candidate.GetName() == request["timeline_name"]:
+                timeline = candidate
+        if not timeline:
+            fail("Timeline not found: " + request["timeline_name"])
+        project.SetCurrentTimeline(timeline)
+    elif not project.GetCurrentTimeline():
+        fail("No timeline selected")
+    if not project.LoadRenderPreset(request["preset_name"]):
+        fail("Render preset not found: " + request["preset_name"])
+    settings = {}
+    if not request["use_in_out_range"]:
+        settings["SelectAllFrames"] = True
+    if request["export_video"] is not None:
+        settings["ExportVideo"] = request["export_video"]
+    if request["export_audio"] is not None:
+        settings["ExportAudio"] = request["export_audio"]
+    if settings and not project.SetRenderSettings(settings):
+        fail("Resolve refused the render settings")
+    job_id = project.AddRenderJob()
+    if not job_id:
+        fail("Resolve could not add the render job")
+    output["job_id"] = job_id
+"#;
+
+const START: &str = r#"
+    ready = [job["job_id"] for job in render_jobs(project) if job["status"] == "Ready"]
+    if request["job_ids"] is not None:
+        ready = [job_id for job_id in ready if job_id in request["job_ids"]]
+    if ready:
+        if not project.StartRendering(ready, request["interactive"]):
+            fail("Resolve could not start rendering")
+    output["started_jobs"] = ready
+"#;
+
+const STOP: &str = r#"
+    output["stopped_jobs"] = [job["job_id"] for job in render_jobs(project) if job["status"] == "Rendering"]
+    project.StopRendering()
+"#;
+
+const CLEAR: &str = r#"
+    jobs = render_jobs(project)
+    output["cleared_jobs"] = len(jobs)
+    output["stopped_jobs"] = [job["job_id"] for job in jobs if job["status"] == "Rendering"]
+    if project.IsRenderingInProgress():
+        project.StopRendering()
+    if jobs and not project.DeleteAllRenderJobs():
+        fail("Resolve could not delete the render jobs")
+"#;
+
+const DELETE: &str = r#"
+    job = next((job for job in render_jobs(project) if job["job_id"] == request["job_id"]), None)
+    if not job:
+        fail("Render job not found: " + request["job_id"])
+    if job["status"] == "Rendering":
+        fail("Render job " + request["job_id"] + " is rendering; stop rendering first")
+    if not project.DeleteRenderJob(request["job_id"]):
+        fail("Resolve could not delete render job " + request["job_id"])
+    output["deleted_job"] = job
+"#;
+
+/// A script running `operation` with `request` in scope
+fn script(request: &Value, operation: &str) -> ResolveResult<String> {
+    ResolveBridge::resolve_script(
+        RENDER_JOBS,
+        &format!("{}{}", SCRIPT_PROJECT, OUTPUT),
+        request,
+        &format!("{}{}", operation, REPORT),
+    )
+}
+
+/// A job as get_render_status lists a queued one
+fn queued_details(job: &RenderJobSnapshot) -> Value {
+    json!({
+        "job_id": job.job_id,
+        "timeline_name": job.timeline_name,
+        "preset_name": job.preset_name,
+        "output_path": job.output_path,
+        "use_in_out_range": job.frame_range.is_some(),
+        "export_video": job.export_video,
+        "export_audio": job.export_audio,
+        "audio_channel_layout": Value::Null,
+        "subtitles": Value::Null,
+        "embedding": Value::Null
+    })
+}
+
+/// A job as get_render_status lists one that is rendering
+fn active_details(job: &RenderJobSnapshot) -> Value {
+    let total_frames = job
+        .frame_range
+        .map_or(0, |(start, end)| u32::try_from(end - start).unwrap_or(0));
+    let progress = job.completion_percent.clamp(0.0, 100.0);
+    json!({
+        "job_id": job.job_id,
+        "progress_percent": progress,
+        "current_frame": (total_frames as f32 * progress / 100.0) as u32,
+        "total_frames": total_frames,
+        "status_message": format!("Rendering in Resolve, {:.0}% done", progress),
+        "estimated_time_remaining_seconds": Value::Null
+    })
+}
+
+/// A job as get_render_status lists a finished one
+fn completed_details(job: &RenderJobSnapshot) -> Value {
+    json!({
+        "job_id": job.job_id,
+        "timeline_name": job.timeline_name,
+        "preset_name": job.preset_name,
+        "output_path": job.output_path,
+        "status": format!("{:?}", job.status()),
+        "render_seconds": Value::Null,
+        "completed_at": Value::Null,
+        "error": Value::Null,
+        "hooks": [],
+        "embedding": Value::Null
+    })
+}
+
+/// get_render_status of a real queue
+fn render_status(jobs: &[RenderJobSnapshot]) -> Value {
+    let with_status = |wanted: fn(&RenderJobStatus) -> bool| {
+        jobs.iter()
+            .filter(move |job| wanted(&job.status()))
+            .collect::<Vec<_>>()
+    };
+    let queued = with_status(|status| matches!(status, RenderJobStatus::Queued));
+    let active = with_status(|status| matches!(status, RenderJobStatus::Rendering));
+    let completed = with_status(|status| {
+        matches!(
+            status,
+            RenderJobStatus::Completed | RenderJobStatus::Failed | RenderJobStatus::Cancelled
+        )
+    });
+    json!({
+        "result": format!(
+            "Render status: {} queued, {} active, {} completed",
+            queued.len(),
+            active.len(),
+            completed.len()
+        ),
+        "queued_jobs": queued.len(),
+        "active_renders": active.len(),
+        "completed_renders": completed.len(),
+        "queued_job_details": queued.iter().map(|job| queued_details(job)).collect::<Vec<_>>(),
+        "active_render_details": active.iter().map(|job| active_details(job)).collect::<Vec<_>>(),
+        "completed_render_details": completed.iter().map(|job| completed_details(job)).collect::<Vec<_>>(),
+        "operation_id": Uuid::new_v4().to_string()
+    })
+}
+
+impl ResolveBridge {
+    /// Run a render queue operation in the running Resolve
+    pub(super) async fn real_render_operation(
+        &self,
+        method: &str,
+        args: &Value,
+    ) -> ResolveResult<Value> {
+        let (request, operation) = match method {
+            "add_to_render_queue" => {
+                if args["subtitle_export"]
+                    .as_str()
+                    .is_some_and(|export| export != "none")
+                    || !args["audio_channel_layout"].is_null()
+                {
+                    return Err(ResolveError::not_supported(
+                        "subtitle and channel layout options in Resolve's render settings API",
+                    ));
+                }
+                let preset_name = args["preset_name"].as_str().ok_or_else(|| {
+                    ResolveError::invalid_parameter("preset_name", "required string")
+                })?;
+                let request = json!({
+                    "preset_name": preset_name,
+                    "timeline_name": args["timeline_name"],
+                    "use_in_out_range": args["use_in_out_range"].as_bool().unwrap_or(false),
+                    "export_video": args["export_video"],
+                    "export_audio": args["export_audio"]
+                });
+                (request, ADD_JOB)
+            }
+            "start_render" | "start_project_rendering" => {
+                let request = json!({
+                    "job_ids": args["job_ids"],
+                    "interactive": args["is_interactive_mode"].as_bool().unwrap_or(false)
+                });
+                (request, START)
+            }
+            "stop_project_rendering" => (json!({}), STOP),
+            "clear_render_queue" => (json!({}), CLEAR),
+            "delete_render_job" => {
+                let job_id = args["job_id"]
+                    .as_str()
+                    .ok_or_else(|| ResolveError::invalid_parameter("job_id", "required string"))?;
+                (json!({ "job_id": job_id }), DELETE)
+            }
+            _ => (json!({}), ""),
+        };
+        let output = self.run_resolve_script(method, &script(&request, operation)?)?;
+        let jobs: Vec<RenderJobSnapshot> = serde_json::from_value(output["render_jobs"].clone())
+            .map_err(|e| ResolveError::internal(format!("Unexpected render job list: {}", e)))?;
+
+        let mut state = self
+            .state
+            .lock(LockPlan::write(&[Domain::Render]).reading(&[]))
+            .await;
+        mirror_render_jobs(&mut state.render_state, &jobs);
+        let rendering = jobs
+            .iter()
+            .filter(|job| matches!(job.status(), RenderJobStatus::Rendering))
+            .count();
+
+        let response = match method {
+            "add_to_render_queue" => {
+                let job_id = output["job_id"].as_str().unwrap_or_default();
+                let position = jobs.iter().position(|job| job.job_id == job_id);
+                let job = position.map(|index| &jobs[index]).ok_or_else(|| {
+                    ResolveError::api_call(method, "the new job is not in the render queue")
+                })?;
+                json!({
+                    "result": format!(
+                        "Added timeline '{}' to render queue with preset '{}'",
+                        job.timeline_name, job.preset_name
+                    ),
+                    "job_id": job.job_id,
+                    "timeline_name": job.timeline_name,
+                    "preset_name": job.preset_name,
+                    "output_path": job.output_path,
+                    "use_in_out_range": request["use_in_out_range"],
+                    "export_video": job.export_video,
+                    "export_audio": job.export_audio,
+                    "audio_channel_layout": Value::Null,
+                    "subtitles": Value::Null,
+                    "output": Value::Null,
+                    "embedding": Value::Null,
+                    "queue_position": position.map_or(jobs.len(), |index| index + 1),
+                    "operation_id": Uuid::new_v4().to_string()
+                })
+            }
+            "start_render" => {
+                let started = output["started_jobs"]
+                    .as_array()
+                    .cloned()
+                    .unwrap_or_default();
+                if started.is_empty() {
+                    return Err(ResolveError::invalid_parameter(
+                        "render_queue",
+                        if jobs.is_empty() {
+                            "no jobs in queue"
+                        } else {
+                            "no queued jobs to start"
+                        },
+                    ));
+                }
+                json!({
+                    "result": format!("Started {} render jobs", started.len()),
+                    "started_jobs": started,
+                    "total_active_renders": rendering,
+                    "operation_id": Uuid::new_v4().to_string()
+                })
+            }
+            "start_project_rendering" => json!({
+                "success": true,
+                "result": "Started project rendering",
+                "started_jobs": output["started_jobs"],
+                "operation_id": format!("start_project_rendering_{}", chrono::Utc::now().timestamp())
+            }),
+            "stop_project_rendering" => json!({
+                "success": true,
+                "result": "Stopped project rendering",
+                "stopped_jobs": output["stopped_jobs"],
+                "operation_id": format!("stop_project_rendering_{}", chrono::Utc::now().timestamp())
+            }),
+            "clear_render_queue" => {
+                let cleared = output["cleared_jobs"].as_u64().unwrap_or(0);
+                let stopped = output["stopped_jobs"].as_array().map_or(0, Vec::len);
+                json!({
+                    "result": format!(
+                        "Cleared render queue ({} jobs) and stopped {} active renders",
+                        cleared, stopped
+                    ),
+                    "cleared_queue_jobs": cleared,
+                    "stopped_active_renders": stopped,
+                    "operation_id": Uuid::new_v4().to_string()
+                })
+            }
+            "delete_render_job" => {
+                let deleted = &output["deleted_job"];
+                json!({
+                    "result": format!("Deleted render job '{}'", request["job_id"].as_str().unwrap_or_default()),
+                    "job_id": request["job_id"],
+                    "timeline_name": deleted["timeline_name"],
+                    "preset_name": deleted["preset_name"],
+                    "remaining_jobs": jobs.len(),
+                    "operation_id": Uuid::new_v4().to_string()
+                })
+            }
+            "get_render_status" => render_status(&jobs),
+            "get_project_render_job_list" => {
+                self.get_project_render_job_list(&state, args.clone())
+                    .await?
+            }
+            _ => json!({
+                "success": true,
+                "result": "Checked project rendering status",
+                "is_rendering": output["is_rendering"],
+                "operation_id": format!("is_project_rendering_in_progress_{}", chrono::Utc::now().timestamp())
+            }),
+        };
+        Ok(response)
+    }
+}
@@ -0,0 +1,49 @@
+//! Dispatch of registered tools
+//!
+//! The entries in [`crate::tools::registry`] expand here into the lock plan
+//! and the handler call of each registered tool. A tool's handler is the
+//! bridge method named after it, taking the state and its arguments.
+
+use serde_json::Value;
+
+use super::{Domain, LockPlan, ResolveBridge, StateView};
+use crate::error::ResolveResult;
+use crate::tools::registry::registered_tools;
+
+macro_rules! registered_dispatch {
+    ($($name:ident {
+        category: $category:literal,
+        description: $description:literal,
+        request: $request:ident,
+        writes: [$($domain:ident),*],
+        paths: [$($path:literal),*],
+    })*) => {
+        impl ResolveBridge {
+            /// Lock plan of a registered tool, or None for other methods
+            pub(super) fn registered_lock_plan(method: &str) -> Option<LockPlan> {
+                $(
+                    if method == stringify!($name) {
+                        return Some(LockPlan::write(&[$(Domain::$domain),*]));
+                    }
+                )*
+                None
+            }
+
+            /// Run a registered tool's handler, or None for other methods
+            pub(super) async fn dispatch_registered(
+                &self,
+                method: &str,
+                state: &mut StateView<'_>,
+                args: Value,
+            ) -> Option<ResolveResult<Value>> {
+                $(
+                    if method == stringify!($name) {
+                        return Some(self.$name(state, args).await);
+                    }
+                )*
+                None
+            }
+        }
+    };
+}
+registered_tools!(registered_dispatch);
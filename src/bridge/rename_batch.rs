@@ -0,0 +1,359 @@
+//! Project-wide find and replace
+//!
+//! rename_batch applies a regular expression to the names of every clip, bin
+//! or timeline, or to a clip metadata field, and replaces what it matches. A
+//! dry run reports what would change without changing it. Names must stay
+//! unique, so a batch that would give two entities the same name, rename one
+//! to a name that is kept by another, or leave a name empty is refused as a
+//! whole, with every collision listed.
+//!
+//! Renamed entities keep their IDs, and whatever refers to them by name
+//! follows: bins and timeline items their clips, clips their bin, render jobs
+//! their timeline, and grades, color groups, transcripts, tags and notes the
+//! clip or timeline they belong to. Metadata values need not be unique, but a
+//! metadata batch whose new values break the active metadata schema is
+//! refused as a whole; fields Resolve fills in itself cannot be edited.
+//!
+//! Resolve's scripting API has no batch rename, so in Real mode the batch
+//! applies to the simulated state only.
+
+use regex::{Regex, RegexBuilder};
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, BTreeSet};
+use uuid::Uuid;
+
+use super::metadata_schema::{self, READ_ONLY_FIELDS};
+use super::{ResolveBridge, StateView};
+use crate::error::{ResolveError, ResolveResult};
+
+/// What a batch can rename
+const TARGETS: [&str; 4] = ["clips", "bins", "timelines", "metadata"];
+
+/// New names by old name
+pub(super) type Renames = BTreeMap<String, String>;
+
+/// Move the entries of renamed keys to their new keys
+pub(super) fn rekey<M, V>(map: &mut M, renames: &Renames)
+where
+    M: Default + IntoIterator<Item = (String, V)> + FromIterator<(String, V)>,
+{
+    if renames.is_empty() {
+        return;
+    }
+    *map = std::mem::take(map)
+        .into_iter()
+        .map(|(key, value)| match renames.get(&key) {
+            Some(new_key) => (new_key.clone(), value),
+            None => (key, value),
+        })
+        .collect();
+}
+
+/// Rename a name in place if the batch renames it
+fn follow(name: &mut String, renames: &Renames) {
+    if let Some(new_name) = renames.get(name.as_str()) {
+        *name = new_name.clone();
+    }
+}
+
+/// The `find` argument as a regular expression
+fn parse_find(args: &Value) -> ResolveResult<Regex> {
+    let find = args["find"]
+        .as_str()
+        .ok_or_else(|| ResolveError::invalid_parameter("find", "required string"))?;
+    if find.is_empty() {
+        return Err(ResolveError::invalid_parameter("find", "cannot be empty"));
+    }
+    RegexBuilder::new(find)
+        .case_insensitive(args["case_insensitive"].as_bool().unwrap_or(false))
+        .build()
+        .map_err(|e| {
+            ResolveError::invalid_parameter("find", format!("invalid regular expression: {}", e))
+        })
+}
+
+/// Names the batch changes, by old name
+fn plan_renames(names: &BTreeSet<&str>, find: &Regex, replace: &str) -> Renames {
+    names
+        .iter()
+        .filter_map(|&name| {
+            let new_name = find.replace_all(name, replace);
+            (new_name != name).then(|| (name.to_string(), new_name.into_owned()))
+        })
+        .collect()
+}
+
+/// Every way the renames would break unique names among `existing`
+fn collisions(existing: &BTreeSet<&str>, renames: &Renames) -> Vec<Value> {
+    let mut by_new_name: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+    for (old_name, new_name) in renames {
+        by_new_name
+            .entry(new_name.as_str())
+            .or_default()
+            .push(old_name.as_str());
+    }
+    by_new_name
+        .into_iter()
+        .filter_map(|(new_name, old_names)| {
+            let reason = if new_name.trim().is_empty() {
+                "the new name is empty"
+            } else if old_names.len() > 1 {
+                "several names would be renamed to it"
+            } else if existing.contains(new_name) && !renames.contains_key(new_name) {
+                "the name is kept by an entity that is not renamed"
+            } else {
+                return None;
+            };
+            Some(json!({
+                "name": new_name,
+                "from": old_names,
+                "reason": reason
+            }))
+        })
+        .collect()
+}
+
+impl ResolveBridge {
+    pub(super) async fn rename_batch(
+        &self,
+        state: &mut StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        let target = args["target"]
+            .as_str()
+            .ok_or_else(|| ResolveError::invalid_parameter("target", "required string"))?;
+        if !TARGETS.contains(&target) {
+            return Err(ResolveError::invalid_parameter(
+                "target",
+                format!(
+                    "unknown target '{}'; use one of {}",
+                    target,
+                    TARGETS.join(", ")
+                ),
+            ));
+        }
+        let find = parse_find(&args)?;
+        let replace = args["replace"]
+            .as_str()
+            .ok_or_else(|| ResolveError::invalid_parameter("replace", "required string"))?;
+        let dry_run = args["dry_run"].as_bool().unwrap_or(false);
+
+        if target == "metadata" {
+            return self.replace_metadata(state, &args, &find, replace, dry_run);
+        }
+
+        let (renames, collisions) = {
+            let names: BTreeSet<&str> = match target {
+                "clips" => state.media_pool.clips.keys().map(String::as_str).collect(),
+                "bins" => state.media_pool.bins.keys().map(String::as_str).collect(),
+                _ => state.timelines.keys().map(String::as_str).collect(),
+            };
+            let renames = plan_renames(&names, &find, replace);
+            let collisions = collisions(&names, &renames);
+            (renames, collisions)
+        };
+        if !dry_run && !collisions.is_empty() {
+            let details: Vec<String> = collisions
+                .iter()
+                .map(|collision| {
+                    format!(
+                        "'{}' from {} ({})",
+                        collision["name"].as_str().unwrap_or_default(),
+                        collision["from"],
+                        collision["reason"].as_str().unwrap_or_default()
+                    )
+                })
+                .collect();
+            return Err(ResolveError::invalid_parameter(
+                "replace",
+                format!(
+                    "{} new names collide, so nothing was renamed: {}",
+                    collisions.len(),
+                    details.join("; ")
+                ),
+            ));
+        }
+
+        let renamed: Vec<Value> = renames
+            .iter()
+            .map(|(old_name, new_name)| {
+                let id = match target {
+                    "clips" => state.media_pool.clips.get(old_name).map(|clip| &clip.id),
+                    "bins" => state.media_pool.bins.get(old_name).map(|bin| &bin.id),
+                    _ => state.timelines.get(old_name).map(|timeline| &timeline.id),
+                };
+                json!({ "id": id, "from": old_name, "to": new_name })
+            })
+            .collect();
+        if !dry_run {
+            match target {
+                "clips" => Self::rename_clips(state, &renames),
+                "bins" => Self::rename_bins(state, &renames),
+                _ => Self::rename_timelines(state, &renames),
+            }
+        }
+
+        let result = match (dry_run, collisions.is_empty()) {
+            (false, _) => format!("Renamed {} {}", renamed.len(), target),
+            (true, true) => format!("Would rename {} {}", renamed.len(), target),
+            (true, false) => format!(
+                "Would rename {} {}, but {} new names collide",
+                renamed.len(),
+                target,
+                collisions.len()
+            ),
+        };
+        Ok(json!({
+            "result": result,
+            "target": target,
+            "dry_run": dry_run,
+            "renamed": renamed,
+            "collisions": collisions,
+            "operation_id": Uuid::new_v4().to_string()
+        }))
+    }
+
+    /// Find and replace in one metadata field of every clip
+    fn replace_metadata(
+        &self,
+        state: &mut StateView<'_>,
+        args: &Value,
+        find: &Regex,
+        replace: &str,
+        dry_run: bool,
+    ) -> ResolveResult<Value> {
+        let field = args["metadata_field"].as_str().ok_or_else(|| {
+            ResolveError::invalid_parameter("metadata_field", "required for the metadata target")
+        })?;
+        if READ_ONLY_FIELDS.contains(&field) {
+            return Err(ResolveError::invalid_parameter(
+                "metadata_field",
+                format!("'{}' is read-only", field),
+            ));
+        }
+
+        let view: &StateView<'_> = state;
+        let mut clip_names: Vec<&String> = view.media_pool.clips.keys().collect();
+        clip_names.sort();
+        let changes: Vec<(String, String, String)> = clip_names
+            .into_iter()
+            .filter_map(|clip_name| {
+                let value = metadata_schema::clip_value(view, clip_name, field)?;
+                let new_value = find.replace_all(&value, replace).into_owned();
+                (new_value != value).then(|| (clip_name.clone(), value, new_value))
+            })
+            .collect();
+
+        let changed: Vec<Value> = changes
+            .iter()
+            .map(|(clip_name, old_value, new_value)| {
+                json!({
+                    "clip": clip_name,
+                    "id": state.media_pool.clips[clip_name].id,
+                    "from": old_value,
+                    "to": new_value
+                })
+            })
+            .collect();
+        let schema_error = match self.enforce_metadata_schema(
+            "replace",
+            changes
+                .iter()
+                .map(|(clip_name, _, new_value)| (clip_name.as_str(), field, new_value.as_str())),
+        ) {
+            Err(e) if !dry_run => return Err(e),
+            checked => checked.err().map(|e| e.to_string()),
+        };
+        if !dry_run {
+            for (clip_name, _, new_value) in changes {
+                metadata_schema::set_clip_value(state, &clip_name, field, new_value);
+            }
+        }
+
+        Ok(json!({
+            "result": format!(
+                "{} '{}' on {} clips",
+                if dry_run { "Would change" } else { "Changed" },
+                field,
+                changed.len()
+            ),
+            "target": "metadata",
+            "metadata_field": field,
+            "dry_run": dry_run,
+            "renamed": changed,
+            "collisions": [],
+            "schema_error": schema_error,
+            "operation_id": Uuid::new_v4().to_string()
+        }))
+    }
+
+    /// Rename clips and everything that refers to them by name
+    fn rename_clips(state: &mut StateView<'_>, renames: &Renames) {
+        for clip in state.media_pool.clips.values_mut() {
+            follow(&mut clip.name, renames);
+        }
+        rekey(&mut state.media_pool.clips, renames);
+        for bin in state.media_pool.bins.values_mut() {
+            for clip_name in &mut bin.clips {
+                follow(clip_name, renames);
+            }
+        }
+        for synced in state.media_pool.synced_clips.values_mut() {
+            synced.rename_clips(renames);
+        }
+        rekey(&mut state.media_pool.synced_clips, renames);
+        state.transcripts.rename_clips(renames);
+
+        for item in state.timeline_items.items.values_mut() {
+            follow(&mut item.clip_name, renames);
+        }
+
+        let color = &mut *state.color_state;
+        rekey(&mut color.clip_grades, renames);
+        if let Some(current_clip) = &mut color.current_clip {
+            follow(current_clip, renames);
+        }
+        for members in color.color_groups.values_mut() {
+            for clip_name in members {
+                follow(clip_name, renames);
+            }
+        }
+
+        state.tags.rename_clips(renames);
+        state.notes.rename(renames, &Renames::new());
+    }
+
+    /// Rename bins and the bin each of their clips is filed in
+    fn rename_bins(state: &mut StateView<'_>, renames: &Renames) {
+        let media_pool = &mut *state.media_pool;
+        for bin in media_pool.bins.values_mut() {
+            follow(&mut bin.name, renames);
+        }
+        rekey(&mut media_pool.bins, renames);
+        for bin in media_pool
+            .clips
+            .values_mut()
+            .filter_map(|clip| clip.bin.as_mut())
+        {
+            follow(bin, renames);
+        }
+    }
+
+    /// Rename timelines and everything that refers to them by name
+    fn rename_timelines(state: &mut StateView<'_>, renames: &Renames) {
+        for timeline in state.timelines.values_mut() {
+            follow(&mut timeline.name, renames);
+        }
+        rekey(&mut *state.timelines, renames);
+        if let Some(current_timeline) = &mut *state.current_timeline {
+            follow(current_timeline, renames);
+        }
+        for item in state.timeline_items.items.values_mut() {
+            follow(&mut item.timeline_name, renames);
+        }
+        for job in &mut state.render_state.render_queue {
+            follow(&mut job.timeline_name, renames);
+        }
+        state.notes.rename(&Renames::new(), renames);
+    }
+}
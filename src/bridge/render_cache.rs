@@ -0,0 +1,177 @@
+//! Per-item render cache flags
+//!
+//! Resolve keeps two render cache flags on each timeline item, one for its
+//! color output and one for its Fusion output. `On` forces the item into the
+//! render cache, `Off` keeps it out and `Auto` leaves the decision to the
+//! project's render cache mode. Forcing heavy shots on ahead of a review
+//! session means they play back in real time when the session starts.
+
+use serde::Serialize;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use super::{ResolveBridge, StateView};
+use crate::error::{ResolveError, ResolveResult};
+
+/// One render cache flag of a timeline item
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub(super) enum CacheFlag {
+    #[default]
+    Auto,
+    On,
+    Off,
+}
+
+impl CacheFlag {
+    fn parse(param: &str, value: &str) -> ResolveResult<Self> {
+        match value.to_ascii_lowercase().as_str() {
+            "auto" => Ok(CacheFlag::Auto),
+            "on" => Ok(CacheFlag::On),
+            "off" => Ok(CacheFlag::Off),
+            _ => Err(ResolveError::invalid_parameter(
+                param,
+                "must be 'on', 'off' or 'auto'",
+            )),
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            CacheFlag::Auto => "auto",
+            CacheFlag::On => "on",
+            CacheFlag::Off => "off",
+        }
+    }
+}
+
+/// Render cache flags of a timeline item
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub(super) struct ItemRenderCache {
+    pub(super) color_output: CacheFlag,
+    pub(super) fusion_output: CacheFlag,
+}
+
+impl ItemRenderCache {
+    /// Whether a flag forces the item into the cache
+    fn forced(&self) -> bool {
+        self.color_output == CacheFlag::On || self.fusion_output == CacheFlag::On
+    }
+
+    fn to_json(self) -> Value {
+        json!({
+            "color_output": self.color_output.as_str(),
+            "fusion_output": self.fusion_output.as_str()
+        })
+    }
+}
+
+impl ResolveBridge {
+    pub(super) async fn set_item_render_cache(
+        &self,
+        state: &mut StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        let timeline_item_id = args["timeline_item_id"].as_str().ok_or_else(|| {
+            ResolveError::invalid_parameter("timeline_item_id", "parameter is required")
+        })?;
+        let flag = |param: &str| {
+            args[param]
+                .as_str()
+                .map(|value| CacheFlag::parse(param, value))
+                .transpose()
+        };
+        let (color_output, fusion_output) = (flag("color_output")?, flag("fusion_output")?);
+        if color_output.is_none() && fusion_output.is_none() {
+            return Err(ResolveError::invalid_parameter(
+                "color_output",
+                "give color_output, fusion_output or both",
+            ));
+        }
+
+        let item = Self::timeline_item_mut(state, timeline_item_id);
+        if let Some(flag) = color_output {
+            item.render_cache.color_output = flag;
+        }
+        if let Some(flag) = fusion_output {
+            item.render_cache.fusion_output = flag;
+        }
+        let render_cache = item.render_cache;
+        Ok(json!({
+            "result": format!(
+                "Set render cache of item '{}' to color {}, Fusion {}",
+                timeline_item_id,
+                render_cache.color_output.as_str(),
+                render_cache.fusion_output.as_str()
+            ),
+            "timeline_item_id": timeline_item_id,
+            "clip_name": item.clip_name,
+            "render_cache": render_cache.to_json(),
+            "operation_id": Uuid::new_v4().to_string()
+        }))
+    }
+
+    pub(super) async fn get_render_cache_status(
+        &self,
+        state: &mut StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        let timeline_name = Self::resolve_timeline_name(state, &args)?;
+        let mut items: Vec<_> = state
+            .timeline_items
+            .items
+            .values()
+            .filter(|item| item.timeline_name == timeline_name)
+            .collect();
+        items.sort_by_key(|item| {
+            (
+                item.placement.as_ref().map_or(i64::MAX, |p| p.record_in),
+                item.id.clone(),
+            )
+        });
+
+        let mut forced_frames = 0;
+        let (mut forced, mut excluded, mut auto) = (0, 0, 0);
+        let listed: Vec<Value> = items
+            .iter()
+            .map(|item| {
+                let cache = item.render_cache;
+                let duration = item.placement.as_ref().map(|p| p.duration);
+                if cache.forced() {
+                    forced += 1;
+                    forced_frames += duration.unwrap_or(0);
+                } else if cache.color_output == CacheFlag::Off
+                    && cache.fusion_output == CacheFlag::Off
+                {
+                    excluded += 1;
+                } else {
+                    auto += 1;
+                }
+                json!({
+                    "timeline_item_id": item.id,
+                    "clip_name": item.clip_name,
+                    "track": item.placement.as_ref().map(|p| format!("V{}", p.track_index)),
+                    "record_in": item.placement.as_ref().map(|p| p.record_in),
+                    "duration": duration,
+                    "render_cache": cache.to_json(),
+                    "forced": cache.forced()
+                })
+            })
+            .collect();
+
+        Ok(json!({
+            "result": format!(
+                "{} of {} items on '{}' are forced into the render cache",
+                forced,
+                listed.len(),
+                timeline_name
+            ),
+            "timeline_name": timeline_name,
+            "items": listed,
+            "forced_items": forced,
+            "forced_frames": forced_frames,
+            "excluded_items": excluded,
+            "auto_items": auto,
+            "operation_id": Uuid::new_v4().to_string()
+        }))
+    }
+}
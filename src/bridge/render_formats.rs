@@ -0,0 +1,363 @@
+//! Render format and codec capability catalog
+//!
+//! Lists the containers Resolve renders to with the codecs each one accepts,
+//! the bit depths of every codec and whether it can carry alpha. Render preset
+//! and render format calls validate against this table, and list_render_formats
+//! exposes it so agents can pick a valid combination up front. Names match
+//! case-insensitively and also answer to Resolve's own format keys.
+//!
+//! Audio codecs list the channel layouts they can carry, so audio-only
+//! mixdowns can be checked before they are queued.
+
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use super::{ResolveBridge, StateView};
+use crate::error::{ResolveError, ResolveResult};
+
+#[derive(Debug)]
+pub(super) struct RenderCodec {
+    pub(super) name: &'static str,
+    /// Other names accepted for the codec
+    aliases: &'static [&'static str],
+    bit_depths: &'static [u8],
+    alpha: bool,
+}
+
+#[derive(Debug)]
+pub(super) struct RenderFormat {
+    /// Name used by render presets
+    pub(super) name: &'static str,
+    /// Resolve's format key, as used by SetCurrentRenderFormatAndCodec
+    resolve_format: &'static str,
+    /// Other names accepted for the format, such as Resolve's description
+    aliases: &'static [&'static str],
+    pub(super) extension: &'static str,
+    /// Whether each frame renders to its own file
+    image_sequence: bool,
+    codecs: &'static [RenderCodec],
+    audio_codecs: &'static [AudioCodec],
+}
+
+#[derive(Debug)]
+pub(super) struct AudioCodec {
+    pub(super) name: &'static str,
+    channel_layouts: &'static [&'static str],
+}
+
+/// Channel layout used when none is requested
+pub(super) const DEFAULT_CHANNEL_LAYOUT: &str = "Stereo";
+
+const AAC: AudioCodec = AudioCodec {
+    name: "AAC",
+    channel_layouts: &["Mono", "Stereo", "5.1"],
+};
+
+const LINEAR_PCM: AudioCodec = AudioCodec {
+    name: "Linear PCM",
+    channel_layouts: &["Mono", "Stereo", "5.1", "7.1"],
+};
+
+const fn codec(
+    name: &'static str,
+    aliases: &'static [&'static str],
+    bit_depths: &'static [u8],
+    alpha: bool,
+) -> RenderCodec {
+    RenderCodec {
+        name,
+        aliases,
+        bit_depths,
+        alpha,
+    }
+}
+
+/// Every render format, in the order they are listed
+pub(super) const RENDER_FORMATS: &[RenderFormat] = &[
+    RenderFormat {
+        name: "MOV",
+        resolve_format: "mov",
+        aliases: &["QuickTime"],
+        extension: "mov",
+        image_sequence: false,
+        codecs: &[
+            codec("H.264", &["H264"], &[8, 10], false),
+            codec("H.265", &["H265", "HEVC"], &[8, 10], false),
+            codec("ProRes 422 Proxy", &[], &[10], false),
+            codec("ProRes 422 LT", &[], &[10], false),
+            codec("ProRes 422", &["ProRes"], &[10], false),
+            codec("ProRes 422 HQ", &[], &[10], false),
+            codec("ProRes 4444", &[], &[12], true),
+            codec("ProRes 4444 XQ", &[], &[12], true),
+            codec("DNxHR HQX", &[], &[10], false),
+            codec("DNxHR 444", &[], &[12], false),
+        ],
+        audio_codecs: &[AAC, LINEAR_PCM],
+    },
+    RenderFormat {
+        name: "MP4",
+        resolve_format: "mp4",
+        aliases: &[],
+        extension: "mp4",
+        image_sequence: false,
+        codecs: &[
+            codec("H.264", &["H264"], &[8, 10], false),
+            codec("H.265", &["H265", "HEVC"], &[8, 10], false),
+        ],
+        audio_codecs: &[AAC],
+    },
+    RenderFormat {
+        name: "MXF",
+        resolve_format: "mxf_op1a",
+        aliases: &["MXF OP1A"],
+        extension: "mxf",
+        image_sequence: false,
+        codecs: &[
+            codec("DNxHD", &[], &[8, 10], false),
+            codec("DNxHR HQX", &[], &[10], false),
+            codec("DNxHR 444", &[], &[12], false),
+            codec("ProRes 422 HQ", &[], &[10], false),
+            codec("ProRes 4444", &[], &[12], true),
+        ],
+        audio_codecs: &[LINEAR_PCM],
+    },
+    RenderFormat {
+        name: "EXR",
+        resolve_format: "exr",
+        aliases: &["OpenEXR"],
+        extension: "exr",
+        image_sequence: true,
+        codecs: &[
+            codec("RGB Half", &[], &[16], true),
+            codec("RGB Float", &[], &[32], true),
+        ],
+        audio_codecs: &[],
+    },
+    RenderFormat {
+        name: "DPX",
+        resolve_format: "dpx",
+        aliases: &[],
+        extension: "dpx",
+        image_sequence: true,
+        codecs: &[codec("RGB", &[], &[10, 12, 16], false)],
+        audio_codecs: &[],
+    },
+    RenderFormat {
+        name: "TIFF",
+        resolve_format: "tif",
+        aliases: &["TIF"],
+        extension: "tif",
+        image_sequence: true,
+        codecs: &[codec("RGB", &[], &[8, 16], true)],
+        audio_codecs: &[],
+    },
+];
+
+fn names_match(name: &str, primary: &str, aliases: &[&str]) -> bool {
+    primary.eq_ignore_ascii_case(name)
+        || aliases.iter().any(|alias| alias.eq_ignore_ascii_case(name))
+}
+
+/// Render format with this name, alias or Resolve key, or an error naming `param`
+pub(super) fn require_format(param: &str, name: &str) -> ResolveResult<&'static RenderFormat> {
+    RENDER_FORMATS
+        .iter()
+        .find(|format| {
+            format.resolve_format.eq_ignore_ascii_case(name)
+                || names_match(name, format.name, format.aliases)
+        })
+        .ok_or_else(|| {
+            let names: Vec<&str> = RENDER_FORMATS.iter().map(|format| format.name).collect();
+            ResolveError::invalid_parameter(
+                param,
+                format!(
+                    "unknown render format '{}'; expected one of: {}",
+                    name,
+                    names.join(", ")
+                ),
+            )
+        })
+}
+
+impl RenderCodec {
+    /// Check a requested bit depth and alpha channel against the codec
+    pub(super) fn check(&self, bit_depth: Option<u8>, alpha: bool) -> ResolveResult<()> {
+        if let Some(depth) = bit_depth.filter(|depth| !self.bit_depths.contains(depth)) {
+            let depths: Vec<String> = self.bit_depths.iter().map(u8::to_string).collect();
+            return Err(ResolveError::invalid_parameter(
+                "bit_depth",
+                format!(
+                    "{} renders at {} bits, not {}",
+                    self.name,
+                    depths.join(" or "),
+                    depth
+                ),
+            ));
+        }
+        if alpha && !self.alpha {
+            return Err(ResolveError::invalid_parameter(
+                "export_alpha",
+                format!("{} cannot carry an alpha channel", self.name),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Bit depth used when none is requested
+    pub(super) fn default_bit_depth(&self) -> u8 {
+        self.bit_depths[0]
+    }
+
+    fn to_json(&self) -> Value {
+        json!({
+            "name": self.name,
+            "aliases": self.aliases,
+            "bit_depths": self.bit_depths,
+            "alpha": self.alpha
+        })
+    }
+}
+
+impl RenderFormat {
+    /// Codec this format accepts, or an error listing the ones it does
+    pub(super) fn require_codec(
+        &'static self,
+        param: &str,
+        name: &str,
+    ) -> ResolveResult<&'static RenderCodec> {
+        self.codecs
+            .iter()
+            .find(|codec| names_match(name, codec.name, codec.aliases))
+            .ok_or_else(|| {
+                let names: Vec<&str> = self.codecs.iter().map(|codec| codec.name).collect();
+                ResolveError::invalid_parameter(
+                    param,
+                    format!(
+                        "{} does not support codec '{}'; expected one of: {}",
+                        self.name,
+                        name,
+                        names.join(", ")
+                    ),
+                )
+            })
+    }
+
+    /// Audio codec this format accepts; image sequences carry no audio
+    pub(super) fn require_audio_codec(
+        &'static self,
+        param: &str,
+        name: &str,
+    ) -> ResolveResult<&'static AudioCodec> {
+        self.audio_codecs
+            .iter()
+            .find(|codec| codec.name.eq_ignore_ascii_case(name))
+            .ok_or_else(|| {
+                ResolveError::invalid_parameter(
+                    param,
+                    if self.audio_codecs.is_empty() {
+                        format!("{} has no audio", self.name)
+                    } else {
+                        format!(
+                            "{} does not support audio codec '{}'; expected one of: {}",
+                            self.name,
+                            name,
+                            self.audio_codec_names().join(", ")
+                        )
+                    },
+                )
+            })
+    }
+
+    pub(super) fn to_json(&self) -> Value {
+        json!({
+            "name": self.name,
+            "resolve_format": self.resolve_format,
+            "aliases": self.aliases,
+            "extension": self.extension,
+            "image_sequence": self.image_sequence,
+            "codecs": self.codecs.iter().map(RenderCodec::to_json).collect::<Vec<_>>(),
+            "audio_codecs": self.audio_codecs.iter().map(AudioCodec::to_json).collect::<Vec<_>>()
+        })
+    }
+
+    /// Whether the format can carry audio at all
+    pub(super) fn has_audio(&self) -> bool {
+        !self.audio_codecs.is_empty()
+    }
+
+    fn audio_codec_names(&self) -> Vec<&'static str> {
+        self.audio_codecs.iter().map(|codec| codec.name).collect()
+    }
+}
+
+impl AudioCodec {
+    /// Channel layout this codec carries, in its catalog spelling
+    pub(super) fn require_channel_layout(&self, layout: &str) -> ResolveResult<&'static str> {
+        self.channel_layouts
+            .iter()
+            .find(|entry| entry.eq_ignore_ascii_case(layout))
+            .copied()
+            .ok_or_else(|| {
+                ResolveError::invalid_parameter(
+                    "audio_channel_layout",
+                    format!(
+                        "{} does not carry '{}'; expected one of: {}",
+                        self.name,
+                        layout,
+                        self.channel_layouts.join(", ")
+                    ),
+                )
+            })
+    }
+
+    fn to_json(&self) -> Value {
+        json!({
+            "name": self.name,
+            "channel_layouts": self.channel_layouts
+        })
+    }
+}
+
+impl ResolveBridge {
+    pub(super) async fn list_render_formats(
+        &self,
+        _state: &StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        let only_format = match args["format"].as_str() {
+            Some(name) => Some(require_format("format", name)?),
+            None => None,
+        };
+        let codec = args["codec"].as_str();
+        let alpha = args["alpha"].as_bool().unwrap_or(false);
+
+        // Filters narrow the codecs listed; formats left without codecs are dropped
+        let formats: Vec<Value> = RENDER_FORMATS
+            .iter()
+            .filter(|format| only_format.is_none_or(|only| format.name == only.name))
+            .filter_map(|format| {
+                let codecs: Vec<Value> = format
+                    .codecs
+                    .iter()
+                    .filter(|entry| {
+                        codec.is_none_or(|name| names_match(name, entry.name, entry.aliases))
+                    })
+                    .filter(|entry| !alpha || entry.alpha)
+                    .map(RenderCodec::to_json)
+                    .collect();
+                (!codecs.is_empty()).then(|| {
+                    let mut entry = format.to_json();
+                    entry["codecs"] = json!(codecs);
+                    entry
+                })
+            })
+            .collect();
+
+        Ok(json!({
+            "result": format!("Listed {} render formats", formats.len()),
+            "formats": formats,
+            "count": formats.len(),
+            "operation_id": Uuid::new_v4().to_string()
+        }))
+    }
+}
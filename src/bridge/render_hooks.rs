@@ -0,0 +1,399 @@
+//! Simulated render progress and post-render hooks
+//!
+//! Started render jobs advance in a background task until they complete, at
+//! which point they move to the render history and the `[hooks]` actions from
+//! the config run on a blocking thread. Copy and move hooks first wait for the
+//! output file to appear and stop growing, since Resolve may still be writing
+//! it when the job reports completion. Chapters and metadata the job embeds
+//! are written into the output before the completion event and the hooks.
+//! Each hook's outcome is stored with the job's history entry. A job only
+//! starts rendering once the render weight fits in the shared CPU capacity.
+
+use serde_json::{json, Value};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use super::concurrency::Workload;
+use super::events;
+use super::{RenderJob, RenderJobStatus, RenderProgress, RenderResult, ResolveBridge, StateView};
+use crate::config::PostRenderHook;
+
+/// Frames a simulated render gets through per second
+const SIMULATED_RENDER_FPS: f64 = 2_400.0;
+/// How often simulated progress is updated
+const PROGRESS_TICK: Duration = Duration::from_millis(50);
+/// How often the output watchdog checks the file
+const WATCH_INTERVAL: Duration = Duration::from_millis(100);
+
+impl ResolveBridge {
+    /// Mark queued jobs as rendering and advance each one in the background;
+    /// returns the started job IDs
+    pub(super) fn begin_renders(&self, state: &mut StateView<'_>) -> Vec<String> {
+        let now = chrono::Utc::now();
+        let mut started = Vec::new();
+        let render_state = &mut *state.render_state;
+        for job in &mut render_state.render_queue {
+            if !matches!(job.status, RenderJobStatus::Queued) {
+                continue;
+            }
+            let total_frames = match job.frame_range {
+                Some((start, end)) => end - start,
+                None => state
+                    .timeline_items
+                    .items
+                    .values()
+                    .filter(|item| item.timeline_name == job.timeline_name)
+                    .filter_map(|item| item.placement.as_ref())
+                    .map(|placement| placement.record_out())
+                    .max()
+                    .unwrap_or(0),
+            }
+            .max(1) as u32;
+
+            job.status = RenderJobStatus::Rendering;
+            render_state.active_renders.insert(
+                job.id.clone(),
+                RenderProgress {
+                    job_id: job.id.clone(),
+                    progress_percent: 0.0,
+                    estimated_time_remaining: Some(Duration::from_secs_f64(
+                        f64::from(total_frames) / SIMULATED_RENDER_FPS,
+                    )),
+                    current_frame: 0,
+                    total_frames,
+                    status_message: "Starting render...".to_string(),
+                    last_update: now,
+                },
+            );
+            self.advance_render(job.id.clone());
+            started.push(job.id.clone());
+        }
+        started
+    }
+
+    /// Advance one job's progress until it completes, is stopped or is cleared
+    fn advance_render(&self, job_id: String) {
+        let shared = Arc::clone(&self.state);
+        let config = Arc::clone(&self.config);
+        let cpu = Arc::clone(&self.cpu);
+        tokio::spawn(async move {
+            let acquire = cpu.acquire(Workload::Render, format!("render {}", job_id));
+            tokio::pin!(acquire);
+            let permit = loop {
+                tokio::select! {
+                    permit = &mut acquire => break permit,
+                    _ = tokio::time::sleep(PROGRESS_TICK) => {
+                        let mut render_state = shared.render_state.write().await;
+                        if !is_rendering(&render_state.render_queue, &job_id) {
+                            render_state.active_renders.remove(&job_id);
+                            return;
+                        }
+                        if let Some(progress) = render_state.active_renders.get_mut(&job_id) {
+                            progress.status_message = "Waiting for CPU capacity".to_string();
+                            progress.last_update = chrono::Utc::now();
+                        }
+                    }
+                }
+            };
+
+            let started = Instant::now();
+            let (mut payload, embedding) = loop {
+                tokio::time::sleep(PROGRESS_TICK).await;
+                let mut render_state = shared.render_state.write().await;
+                let render_state = &mut *render_state;
+                // Stopping puts the job back in the queue; clearing removes it
+                let Some(job) = render_state
+                    .render_queue
+                    .iter_mut()
+                    .find(|job| job.id == job_id)
+                    .filter(|job| matches!(job.status, RenderJobStatus::Rendering))
+                else {
+                    render_state.active_renders.remove(&job_id);
+                    return;
+                };
+                let Some(progress) = render_state.active_renders.get_mut(&job_id) else {
+                    return;
+                };
+
+                let elapsed = started.elapsed().as_secs_f64();
+                let frame = (elapsed * SIMULATED_RENDER_FPS) as u32;
+                if frame < progress.total_frames {
+                    progress.current_frame = frame;
+                    progress.progress_percent = frame as f32 / progress.total_frames as f32 * 100.0;
+                    progress.estimated_time_remaining = Some(Duration::from_secs_f64(
+                        f64::from(progress.total_frames - frame) / SIMULATED_RENDER_FPS,
+                    ));
+                    progress.status_message =
+                        format!("Rendering frame {} of {}", frame, progress.total_frames);
+                    progress.last_update = chrono::Utc::now();
+                    continue;
+                }
+
+                job.status = RenderJobStatus::Completed;
+                render_state.active_renders.remove(&job_id);
+                let completed_at = chrono::Utc::now();
+                render_state.render_history.push(RenderResult {
+                    job_id: job.id.clone(),
+                    timeline_name: job.timeline_name.clone(),
+                    preset_name: job.preset_name.clone(),
+                    output_path: job.output_path.clone(),
+                    render_duration: started.elapsed(),
+                    status: RenderJobStatus::Completed,
+                    completed_at,
+                    error_message: None,
+                    hooks: Vec::new(),
+                    embedding: None,
+                });
+                tracing::info!("Render job {} completed", job_id);
+                let payload = json!({
+                    "job_id": job.id,
+                    "timeline_name": job.timeline_name,
+                    "preset_name": job.preset_name,
+                    "output_path": job.output_path,
+                    "status": "completed",
+                    "completed_at": completed_at.to_rfc3339(),
+                    "render_seconds": started.elapsed().as_secs_f64()
+                });
+                break (payload, job.embedding.clone());
+            };
+            drop(permit);
+
+            // Chapters and metadata go in before anything sees the output
+            if let Some(embedding) = embedding {
+                let output_path = payload["output_path"]
+                    .as_str()
+                    .unwrap_or_default()
+                    .to_string();
+                let outcome = tokio::task::spawn_blocking(move || embedding.embed(&output_path))
+                    .await
+                    .unwrap_or_else(|e| json!({ "status": "failed", "error": e.to_string() }));
+                payload["embedding"] = outcome.clone();
+                let mut render_state = shared.render_state.write().await;
+                if let Some(result) = render_state
+                    .render_history
+                    .iter_mut()
+                    .rev()
+                    .find(|result| result.job_id == job_id)
+                {
+                    result.embedding = Some(outcome);
+                }
+            }
+
+            events::publish(&config, events::RENDER_COMPLETED, payload.clone());
+            let hooks = config.hooks.post_render.clone();
+            if hooks.is_empty() {
+                return;
+            }
+            let timeout = Duration::from_secs(config.hooks.timeout_seconds);
+            let (outcomes, output_path) =
+                tokio::task::spawn_blocking(move || run_hooks(&hooks, payload, timeout))
+                    .await
+                    .unwrap_or_else(|e| {
+                        (
+                            vec![json!({ "status": "failed", "error": e.to_string() })],
+                            None,
+                        )
+                    });
+            let mut render_state = shared.render_state.write().await;
+            if let Some(result) = render_state
+                .render_history
+                .iter_mut()
+                .rev()
+                .find(|result| result.job_id == job_id)
+            {
+                result.hooks = outcomes;
+                // Later tools look for the output where a move hook left it
+                if let Some(output_path) = output_path {
+                    result.output_path = output_path;
+                }
+            }
+        });
+    }
+}
+
+/// Whether the job is still in the queue and marked as rendering
+fn is_rendering(queue: &[RenderJob], job_id: &str) -> bool {
+    queue
+        .iter()
+        .any(|job| job.id == job_id && matches!(job.status, RenderJobStatus::Rendering))
+}
+
+/// Run each hook in order and describe how it went, along with where the
+/// output ended up if a hook moved it
+fn run_hooks(
+    hooks: &[PostRenderHook],
+    mut payload: Value,
+    timeout: Duration,
+) -> (Vec<Value>, Option<String>) {
+    let mut output_ready: Option<Result<(), String>> = None;
+    let mut moved = None;
+    let outcomes = hooks
+        .iter()
+        .map(|hook| {
+            let output = PathBuf::from(payload["output_path"].as_str().unwrap_or_default());
+            let (action, outcome) = match hook {
+                PostRenderHook::Copy { destination } | PostRenderHook::Move { destination } => {
+                    let moving = matches!(hook, PostRenderHook::Move { .. });
+                    let outcome = output_ready
+                        .get_or_insert_with(|| wait_for_output(&output, timeout))
+                        .clone()
+                        .and_then(|_| transfer(&output, destination, moving));
+                    if let (true, Ok(moved_to)) = (moving, &outcome) {
+                        payload["output_path"] = json!(moved_to);
+                        moved = Some(moved_to.clone());
+                    }
+                    let action = if moving { "move" } else { "copy" };
+                    (
+                        action,
+                        outcome.map(|path| format!("{} to {}", action, path)),
+                    )
+                }
+                PostRenderHook::Command { command } => {
+                    ("command", run_command(command, &payload, timeout))
+                }
+                PostRenderHook::Webhook { url } => {
+                    ("webhook", post_webhook(url, &payload, timeout))
+                }
+            };
+            match outcome {
+                Ok(detail) => json!({ "action": action, "status": "succeeded", "detail": detail }),
+                Err(error) => {
+                    tracing::warn!("Post-render {} hook failed: {}", action, error);
+                    json!({ "action": action, "status": "failed", "error": error })
+                }
+            }
+        })
+        .collect();
+    (outcomes, moved)
+}
+
+/// Wait until the output file exists and its size holds steady between checks
+fn wait_for_output(output: &Path, timeout: Duration) -> Result<(), String> {
+    let deadline = Instant::now() + timeout;
+    let mut last_size = None;
+    loop {
+        let size = std::fs::metadata(output)
+            .ok()
+            .map(|metadata| metadata.len());
+        if size.is_some() && size == last_size {
+            return Ok(());
+        }
+        if Instant::now() >= deadline {
+            return Err(match size {
+                Some(_) => format!("output file {} was still growing", output.display()),
+                None => format!("output file {} did not appear", output.display()),
+            });
+        }
+        last_size = size;
+        std::thread::sleep(WATCH_INTERVAL);
+    }
+}
+
+/// Copy or move the output into a directory, returning the new path
+fn transfer(output: &Path, destination: &Path, moving: bool) -> Result<String, String> {
+    let file_name = output
+        .file_name()
+        .ok_or_else(|| format!("output path {} has no file name", output.display()))?;
+    let target = destination.join(file_name);
+    std::fs::create_dir_all(destination)
+        .and_then(|_| {
+            // Renames fail across filesystems, so fall back to copy and delete
+            if moving && std::fs::rename(output, &target).is_ok() {
+                return Ok(());
+            }
+            std::fs::copy(output, &target)?;
+            if moving {
+                std::fs::remove_file(output)?;
+            }
+            Ok(())
+        })
+        .map_err(|e| format!("failed to write {}: {}", target.display(), e))?;
+    Ok(target.display().to_string())
+}
+
+/// Run a shell command with the job in its environment
+fn run_command(command: &str, payload: &Value, timeout: Duration) -> Result<String, String> {
+    let mut shell = if cfg!(windows) {
+        let mut shell = Command::new("cmd");
+        shell.arg("/C");
+        shell
+    } else {
+        let mut shell = Command::new("sh");
+        shell.arg("-c");
+        shell
+    };
+    shell.arg(command);
+    for (name, key) in [
+        ("DAVINCI_RENDER_JOB_ID", "job_id"),
+        ("DAVINCI_RENDER_TIMELINE", "timeline_name"),
+        ("DAVINCI_RENDER_PRESET", "preset_name"),
+        ("DAVINCI_RENDER_OUTPUT", "output_path"),
+    ] {
+        shell.env(name, payload[key].as_str().unwrap_or_default());
+    }
+    shell.env("DAVINCI_RENDER_JOB", payload.to_string());
+    run_with_timeout(shell, None, timeout).map(|_| format!("ran '{}'", command))
+}
+
+/// POST the job to a URL with curl
+fn post_webhook(url: &str, payload: &Value, timeout: Duration) -> Result<String, String> {
+    events::post_json(url, &payload.to_string(), &[], timeout)
+        .map(|_| format!("posted job to {}", url))
+}
+
+/// Run a process to completion, killing it once `timeout` passes
+pub(super) fn run_with_timeout(
+    mut command: Command,
+    input: Option<&[u8]>,
+    timeout: Duration,
+) -> Result<(), String> {
+    let mut child = command
+        .stdin(if input.is_some() {
+            Stdio::piped()
+        } else {
+            Stdio::null()
+        })
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("failed to start: {}", e))?;
+    // Drained while the process runs, so a chatty process cannot fill the pipe and stall
+    let stderr = child.stderr.take().map(|mut pipe| {
+        std::thread::spawn(move || {
+            let mut stderr = String::new();
+            let _ = pipe.read_to_string(&mut stderr);
+            stderr
+        })
+    });
+    if let (Some(input), Some(mut stdin)) = (input, child.stdin.take()) {
+        stdin
+            .write_all(input)
+            .map_err(|e| format!("failed to send input: {}", e))?;
+    }
+
+    let deadline = Instant::now() + timeout;
+    let status = loop {
+        match child.try_wait().map_err(|e| e.to_string())? {
+            Some(status) => break status,
+            None if Instant::now() >= deadline => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(format!("timed out after {} seconds", timeout.as_secs()));
+            }
+            None => std::thread::sleep(WATCH_INTERVAL),
+        }
+    };
+    if status.success() {
+        return Ok(());
+    }
+    let stderr = stderr
+        .and_then(|reader| reader.join().ok())
+        .unwrap_or_default();
+    Err(match stderr.trim() {
+        "" => format!("exited with {}", status),
+        message => format!("exited with {}: {}", status, message),
+    })
+}
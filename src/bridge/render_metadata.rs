@@ -0,0 +1,221 @@
+//! Chapters and metadata embedded in rendered files
+//!
+//! A render preset for MP4 or MOV output can embed chapters, one per timeline
+//! marker in the rendered range and titled with the marker's note, and the
+//! title, author and copyright set as project settings. The title falls back
+//! to the project name, and the author is written as the artist tag players
+//! show. What a job embeds is worked out when it is queued, from the markers
+//! and settings of that moment.
+//!
+//! Once the render completes, ffmpeg stream-copies the output together with
+//! an FFMETADATA file into a new file that replaces it, before any post-render
+//! hook runs. When ffmpeg or the output file is missing, the job's history
+//! entry records that nothing was embedded and why.
+
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use super::{ffmpeg, ResolveBridge, StateView};
+use crate::error::{ResolveError, ResolveResult};
+
+/// Render formats whose containers carry chapters and metadata
+const EMBED_FORMATS: [&str; 2] = ["MP4", "MOV"];
+
+/// Project settings embedded as metadata, by the metadata key they fill
+const METADATA_SETTINGS: [(&str, &str); 3] = [
+    ("title", "title"),
+    ("artist", "author"),
+    ("copyright", "copyright"),
+];
+
+/// A chapter of the rendered file, in milliseconds from its start
+#[derive(Debug, Clone, Serialize)]
+struct Chapter {
+    start_ms: u64,
+    end_ms: u64,
+    title: String,
+}
+
+/// Chapters and metadata a render job embeds in its output
+#[derive(Debug, Clone, Default, Serialize)]
+pub(super) struct Embedding {
+    /// None when the preset does not embed chapters
+    chapters: Option<Vec<Chapter>>,
+    /// None when the preset does not embed metadata
+    metadata: Option<BTreeMap<&'static str, String>>,
+}
+
+/// Check that a preset embeds only into a format that carries it
+pub(super) fn check_format(format: &str, chapters: bool, metadata: bool) -> ResolveResult<()> {
+    if (chapters || metadata) && !EMBED_FORMATS.contains(&format) {
+        return Err(ResolveError::invalid_parameter(
+            if chapters {
+                "embed_chapters"
+            } else {
+                "embed_metadata"
+            },
+            format!(
+                "{} output cannot carry chapters or metadata; use MP4 or MOV",
+                format
+            ),
+        ));
+    }
+    Ok(())
+}
+
+/// Escape a value for an FFMETADATA file
+fn escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        if matches!(c, '=' | ';' | '#' | '\\' | '\n') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+impl Embedding {
+    pub(super) fn to_json(&self) -> Value {
+        json!({
+            "chapters": self.chapters,
+            "metadata": self.metadata
+        })
+    }
+
+    /// The chapters and metadata as an FFMETADATA file
+    fn ffmetadata(&self) -> String {
+        let mut text = String::from(";FFMETADATA1\n");
+        for (key, value) in self.metadata.iter().flatten() {
+            text.push_str(&format!("{}={}\n", key, escape(value)));
+        }
+        for chapter in self.chapters.iter().flatten() {
+            text.push_str(&format!(
+                "[CHAPTER]\nTIMEBASE=1/1000\nSTART={}\nEND={}\ntitle={}\n",
+                chapter.start_ms,
+                chapter.end_ms,
+                escape(&chapter.title)
+            ));
+        }
+        text
+    }
+
+    /// Write the chapters and metadata into a rendered file; returns the
+    /// outcome stored with the job's history entry
+    pub(super) fn embed(&self, output_path: &str) -> Value {
+        let output = Path::new(output_path);
+        let skipped = |reason: &str| json!({ "status": "skipped", "reason": reason });
+        if !output.is_file() {
+            return skipped("output file not found");
+        }
+        if !ffmpeg::available() {
+            return skipped("ffmpeg is not available");
+        }
+        let Some(file_name) = output.file_name().and_then(|name| name.to_str()) else {
+            return skipped("output path has no file name");
+        };
+        // Hidden files next to the output, keeping its extension for the muxer
+        let sibling = |suffix: &str| -> PathBuf {
+            output.with_file_name(format!(".{}{}", file_name, suffix))
+        };
+        let (metadata_path, embedded_path) = (sibling(".ffmeta"), sibling(""));
+
+        let result = std::fs::write(&metadata_path, self.ffmetadata())
+            .map_err(|e| e.to_string())
+            .and_then(|()| ffmpeg::embed_metadata(output, &metadata_path, &embedded_path))
+            .and_then(|()| std::fs::rename(&embedded_path, output).map_err(|e| e.to_string()));
+        let _ = std::fs::remove_file(&metadata_path);
+        match result {
+            Ok(()) => json!({
+                "status": "embedded",
+                "chapters": self.chapters.as_ref().map(Vec::len),
+                "metadata": self.metadata.as_ref().map(|metadata| metadata.keys().collect::<Vec<_>>())
+            }),
+            Err(error) => {
+                let _ = std::fs::remove_file(&embedded_path);
+                json!({ "status": "failed", "error": error })
+            }
+        }
+    }
+}
+
+impl ResolveBridge {
+    /// What a job rendering `frame_range` of a timeline embeds, or None when
+    /// its preset embeds nothing
+    pub(super) fn render_embedding(
+        &self,
+        state: &StateView<'_>,
+        timeline_name: &str,
+        frame_range: Option<(i64, i64)>,
+        chapters: bool,
+        metadata: bool,
+    ) -> ResolveResult<Option<Embedding>> {
+        if !chapters && !metadata {
+            return Ok(None);
+        }
+        let timeline = &state.timelines[timeline_name];
+
+        let chapters = if chapters {
+            let fps = self.timeline_frame_rate(timeline)?.fps();
+            let (start, end) = frame_range.unwrap_or_else(|| {
+                let end = state
+                    .timeline_items
+                    .items
+                    .values()
+                    .filter(|item| item.timeline_name == timeline_name)
+                    .filter_map(|item| item.placement.as_ref())
+                    .map(|placement| placement.record_out())
+                    .max()
+                    .unwrap_or(0);
+                (0, end)
+            });
+            let mut markers: Vec<(i64, &str)> = timeline
+                .markers
+                .iter()
+                .filter_map(|marker| Some((i64::from(marker.frame?), marker.note.as_str())))
+                .filter(|(frame, _)| (start..end).contains(frame))
+                .collect();
+            markers.sort_by_key(|(frame, _)| *frame);
+            markers.dedup_by_key(|(frame, _)| *frame);
+            let millis = |frame: i64| ((frame - start) as f64 / fps * 1000.0).round() as u64;
+            let chapters = markers
+                .iter()
+                .enumerate()
+                .map(|(index, (frame, note))| {
+                    let next = markers.get(index + 1).map_or(end, |(next, _)| *next);
+                    Chapter {
+                        start_ms: millis(*frame),
+                        end_ms: millis(next),
+                        title: match note.trim() {
+                            "" => format!("Chapter {}", index + 1),
+                            note => note.to_string(),
+                        },
+                    }
+                })
+                .collect();
+            Some(chapters)
+        } else {
+            None
+        };
+
+        let metadata = metadata.then(|| {
+            let mut metadata: BTreeMap<&'static str, String> = METADATA_SETTINGS
+                .iter()
+                .filter_map(|(key, setting)| {
+                    let value = state.project_settings.get(*setting)?.as_str()?.trim();
+                    (!value.is_empty()).then(|| (*key, value.to_string()))
+                })
+                .collect();
+            if !metadata.contains_key("title") {
+                if let Some(project) = state.current_project.as_ref() {
+                    metadata.insert("title", project.clone());
+                }
+            }
+            metadata
+        });
+
+        Ok(Some(Embedding { chapters, metadata }))
+    }
+}
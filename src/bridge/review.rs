@@ -0,0 +1,404 @@
+//! Review and approval notes
+//!
+//! Notes attach to a timeline item or a record timecode and move through
+//! todo, in-progress and approved. Simulation state keeps the full note; in
+//! Real mode timecode notes are mirrored as timeline markers whose custom data
+//! is the note ID.
+
+use serde_json::{json, Map, Value};
+use uuid::Uuid;
+
+use super::markers::timeline_start;
+use super::{ConnectionMode, ResolveBridge, StateView};
+use crate::error::{ResolveError, ResolveResult};
+
+/// Review notes by creation order
+#[derive(Debug, Clone, Default)]
+pub(super) struct ReviewState {
+    notes: Vec<ReviewNote>,
+    /// Counter used to generate note IDs
+    pub(super) note_counter: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ReviewStatus {
+    Todo,
+    InProgress,
+    Approved,
+}
+
+impl ReviewStatus {
+    fn parse(status: &str) -> ResolveResult<Self> {
+        match status
+            .to_ascii_lowercase()
+            .replace(['-', ' '], "_")
+            .as_str()
+        {
+            "todo" => Ok(Self::Todo),
+            "in_progress" => Ok(Self::InProgress),
+            "approved" => Ok(Self::Approved),
+            _ => Err(ResolveError::invalid_parameter(
+                "status",
+                "must be 'todo', 'in_progress' or 'approved'",
+            )),
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Todo => "todo",
+            Self::InProgress => "in_progress",
+            Self::Approved => "approved",
+        }
+    }
+
+    /// Marker color used for notes with this status in Real mode
+    fn marker_color(&self) -> &'static str {
+        match self {
+            Self::Todo => "Red",
+            Self::InProgress => "Yellow",
+            Self::Approved => "Green",
+        }
+    }
+
+    /// Approved notes must be reopened to todo before work restarts on them
+    fn can_transition_to(&self, next: Self) -> bool {
+        *self != next && !(*self == Self::Approved && next == Self::InProgress)
+    }
+}
+
+#[derive(Debug, Clone)]
+enum ReviewTarget {
+    TimelineItem(String),
+    Timecode { timecode: String, frame: i64 },
+}
+
+#[derive(Debug, Clone)]
+struct ReviewNote {
+    /// Unique note ID
+    id: String,
+    timeline_name: String,
+    target: ReviewTarget,
+    text: String,
+    author: Option<String>,
+    status: ReviewStatus,
+    created_at: String,
+    updated_at: String,
+    /// Status changes as (from, to, time, comment)
+    history: Vec<(ReviewStatus, ReviewStatus, String, Option<String>)>,
+}
+
+impl ReviewState {
+    /// Every note as list_review_notes shows it, by note ID
+    pub(super) fn notes_json(&self) -> Map<String, Value> {
+        self.notes
+            .iter()
+            .map(|note| (note.id.clone(), note.to_json()))
+            .collect()
+    }
+}
+
+impl ReviewNote {
+    fn to_json(&self) -> Value {
+        let (timeline_item_id, timecode, frame) = match &self.target {
+            ReviewTarget::TimelineItem(id) => (Some(id.as_str()), None, None),
+            ReviewTarget::Timecode { timecode, frame } => {
+                (None, Some(timecode.as_str()), Some(*frame))
+            }
+        };
+        let history: Vec<Value> = self
+            .history
+            .iter()
+            .map(|(from, to, at, comment)| {
+                json!({
+                    "from": from.as_str(),
+                    "to": to.as_str(),
+                    "at": at,
+                    "comment": comment
+                })
+            })
+            .collect();
+        json!({
+            "note_id": self.id,
+            "timeline_name": self.timeline_name,
+            "timeline_item_id": timeline_item_id,
+            "timecode": timecode,
+            "frame": frame,
+            "text": self.text,
+            "author": self.author,
+            "status": self.status.as_str(),
+            "created_at": self.created_at,
+            "updated_at": self.updated_at,
+            "history": history
+        })
+    }
+}
+
+/// A note on a record timecode brought in from a review platform
+pub(super) struct ImportedNote {
+    pub(super) timeline_name: String,
+    pub(super) timecode: String,
+    pub(super) frame: i64,
+    pub(super) text: String,
+    pub(super) author: Option<String>,
+    /// Marked done on the platform; the note starts approved
+    pub(super) completed: bool,
+}
+
+impl ResolveBridge {
+    /// Mirror a timecode note as a timeline marker when connected to Resolve
+    async fn sync_review_marker(&self, note: &ReviewNote) {
+        if self.mode != ConnectionMode::Real {
+            return;
+        }
+        let ReviewTarget::Timecode { frame, .. } = note.target else {
+            return;
+        };
+        let payload = json!({
+            "note_id": note.id,
+            "frame": frame,
+            "color": note.status.marker_color(),
+            "name": format!("Review: {}", note.status.as_str()),
+            "text": note.text
+        });
+        if let Err(e) = self.call_real_api("sync_review_marker", &payload).await {
+            tracing::warn!("Failed to sync review marker for {}: {}", note.id, e);
+        }
+    }
+
+    pub(super) async fn add_review_note(
+        &self,
+        state: &mut StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        let text = args["text"]
+            .as_str()
+            .ok_or_else(|| ResolveError::invalid_parameter("text", "required string"))?;
+        if text.trim().is_empty() {
+            return Err(ResolveError::invalid_parameter("text", "cannot be empty"));
+        }
+        let status = match args["status"].as_str() {
+            Some(status) => ReviewStatus::parse(status)?,
+            None => ReviewStatus::Todo,
+        };
+
+        let (timeline_name, target) =
+            match (args["timeline_item_id"].as_str(), args["timecode"].as_str()) {
+                (Some(item_id), None) => {
+                    // Items keep the timeline they were created on
+                    let item = state.timeline_items.items.get(item_id).ok_or_else(|| {
+                        ResolveError::invalid_parameter(
+                            "timeline_item_id",
+                            "timeline item not found",
+                        )
+                    })?;
+                    let timeline_name = if item.timeline_name.is_empty() {
+                        Self::resolve_timeline_name(state, &args)?
+                    } else {
+                        item.timeline_name.clone()
+                    };
+                    (
+                        timeline_name,
+                        ReviewTarget::TimelineItem(item_id.to_string()),
+                    )
+                }
+                (None, Some(timecode)) => {
+                    let timeline_name = Self::resolve_timeline_name(state, &args)?;
+                    let timeline = &state.timelines[&timeline_name];
+                    let start = timeline_start(timeline, &args);
+                    let rate = self.timeline_frame_rate(timeline)?;
+                    let start_frame = rate
+                        .for_timecode(start)
+                        .and_then(|rate| rate.timecode_to_frames(start))
+                        .map_err(|e| {
+                            ResolveError::invalid_parameter("timeline_start_timecode", e)
+                        })?;
+                    let frame = rate
+                        .for_timecode(timecode)
+                        .and_then(|rate| rate.timecode_to_frames(timecode))
+                        .map_err(|e| ResolveError::invalid_parameter("timecode", e))?
+                        - start_frame;
+                    if frame < 0 {
+                        return Err(ResolveError::invalid_parameter(
+                            "timecode",
+                            format!("{} is before the timeline start {}", timecode, start),
+                        ));
+                    }
+                    (
+                        timeline_name,
+                        ReviewTarget::Timecode {
+                            timecode: timecode.to_string(),
+                            frame,
+                        },
+                    )
+                }
+                _ => {
+                    return Err(ResolveError::invalid_parameter(
+                        "timeline_item_id",
+                        "exactly one of timeline_item_id or timecode is required",
+                    ))
+                }
+            };
+
+        state.review.note_counter += 1;
+        let now = chrono::Utc::now().to_rfc3339();
+        let note = ReviewNote {
+            id: format!("note_{:04}", state.review.note_counter),
+            timeline_name,
+            target,
+            text: text.to_string(),
+            author: args["author"].as_str().map(|author| author.to_string()),
+            status,
+            created_at: now.clone(),
+            updated_at: now,
+            history: Vec::new(),
+        };
+        self.sync_review_marker(&note).await;
+        let response = note.to_json();
+        state.review.notes.push(note);
+
+        Ok(json!({
+            "result": format!("Added review note '{}'", response["note_id"].as_str().unwrap_or_default()),
+            "note": response,
+            "operation_id": Uuid::new_v4().to_string()
+        }))
+    }
+
+    /// Add a note imported from a review platform, returning its ID
+    pub(super) async fn add_imported_note(
+        &self,
+        state: &mut StateView<'_>,
+        imported: ImportedNote,
+    ) -> String {
+        state.review.note_counter += 1;
+        let now = chrono::Utc::now().to_rfc3339();
+        let note = ReviewNote {
+            id: format!("note_{:04}", state.review.note_counter),
+            timeline_name: imported.timeline_name,
+            target: ReviewTarget::Timecode {
+                timecode: imported.timecode,
+                frame: imported.frame,
+            },
+            text: imported.text,
+            author: imported.author,
+            status: if imported.completed {
+                ReviewStatus::Approved
+            } else {
+                ReviewStatus::Todo
+            },
+            created_at: now.clone(),
+            updated_at: now,
+            history: Vec::new(),
+        };
+        self.sync_review_marker(&note).await;
+        let id = note.id.clone();
+        state.review.notes.push(note);
+        id
+    }
+
+    pub(super) async fn list_review_notes(
+        &self,
+        state: &StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        let status = args["status"]
+            .as_str()
+            .map(ReviewStatus::parse)
+            .transpose()?;
+        let timeline_name = args["timeline_name"].as_str();
+        let timeline_item_id = args["timeline_item_id"].as_str();
+        let author = args["author"].as_str();
+
+        let notes: Vec<Value> = state
+            .review
+            .notes
+            .iter()
+            .filter(|note| status.is_none_or(|status| note.status == status))
+            .filter(|note| timeline_name.is_none_or(|name| note.timeline_name == name))
+            .filter(|note| {
+                timeline_item_id.is_none_or(
+                    |id| matches!(&note.target, ReviewTarget::TimelineItem(item) if item == id),
+                )
+            })
+            .filter(|note| author.is_none_or(|author| note.author.as_deref() == Some(author)))
+            .map(ReviewNote::to_json)
+            .collect();
+
+        let count_with = |status: ReviewStatus| {
+            state
+                .review
+                .notes
+                .iter()
+                .filter(|note| note.status == status)
+                .count()
+        };
+
+        Ok(json!({
+            "result": format!("Found {} review notes", notes.len()),
+            "notes": notes,
+            "count": notes.len(),
+            "summary": {
+                "todo": count_with(ReviewStatus::Todo),
+                "in_progress": count_with(ReviewStatus::InProgress),
+                "approved": count_with(ReviewStatus::Approved)
+            },
+            "operation_id": Uuid::new_v4().to_string()
+        }))
+    }
+
+    pub(super) async fn resolve_note(
+        &self,
+        state: &mut StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        let note_id = args["note_id"]
+            .as_str()
+            .ok_or_else(|| ResolveError::invalid_parameter("note_id", "required string"))?;
+        let next = match args["status"].as_str() {
+            Some(status) => ReviewStatus::parse(status)?,
+            None => ReviewStatus::Approved,
+        };
+        let comment = args["comment"].as_str().map(|comment| comment.to_string());
+
+        let note = state
+            .review
+            .notes
+            .iter_mut()
+            .find(|note| note.id == note_id)
+            .ok_or_else(|| {
+                ResolveError::invalid_parameter(
+                    "note_id",
+                    format!("review note '{}' not found", note_id),
+                )
+            })?;
+        let previous = note.status;
+        if !previous.can_transition_to(next) {
+            return Err(ResolveError::invalid_parameter(
+                "status",
+                format!(
+                    "cannot move note from '{}' to '{}'",
+                    previous.as_str(),
+                    next.as_str()
+                ),
+            ));
+        }
+
+        let now = chrono::Utc::now().to_rfc3339();
+        note.history.push((previous, next, now.clone(), comment));
+        note.status = next;
+        note.updated_at = now;
+        let note = note.clone();
+        self.sync_review_marker(&note).await;
+
+        Ok(json!({
+            "result": format!(
+                "Moved review note '{}' from '{}' to '{}'",
+                note_id,
+                previous.as_str(),
+                next.as_str()
+            ),
+            "note": note.to_json(),
+            "operation_id": Uuid::new_v4().to_string()
+        }))
+    }
+}
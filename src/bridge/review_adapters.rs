@@ -0,0 +1,412 @@
+//! Comment import from review platforms
+//!
+//! Adapters read the comment exports of review platforms and place each
+//! comment on a timeline, as a marker or as a review note. Frame.io exports
+//! CSV with the timecode its player showed; Vimeo review exports JSON with
+//! times in seconds. Both count from the first frame of the uploaded file, so
+//! each adapter has `[review_import]` settings for where that file sits on the
+//! timeline, which a call may override.
+//!
+//! Every comment is placed before any is added. Comments without text, marked
+//! done on the platform, landing before the timeline start or, as markers, on
+//! a frame that already has one are skipped and listed with the reason.
+
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use super::markers::{marker_color, parse_csv, timeline_start};
+use super::review::ImportedNote;
+use super::{ensure_capacity, Marker, ResolveBridge, StateView};
+use crate::config::{ReviewAdapterConfig, ReviewImportTarget};
+use crate::error::{ResolveError, ResolveResult};
+
+/// Marker color of comments marked done on the platform
+const COMPLETED_COLOR: &str = "Green";
+
+/// Review platform an export comes from
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Adapter {
+    FrameIo,
+    Vimeo,
+}
+
+impl Adapter {
+    fn parse(name: &str) -> ResolveResult<Self> {
+        match name
+            .to_ascii_lowercase()
+            .replace(['.', '_', '-', ' '], "")
+            .as_str()
+        {
+            "frameio" => Ok(Adapter::FrameIo),
+            "vimeo" => Ok(Adapter::Vimeo),
+            _ => Err(ResolveError::invalid_parameter(
+                "adapter",
+                "must be 'frameio' or 'vimeo'",
+            )),
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Adapter::FrameIo => "frameio",
+            Adapter::Vimeo => "vimeo",
+        }
+    }
+
+    /// What an entry of the export is called in errors
+    fn entry_name(self) -> &'static str {
+        match self {
+            Adapter::FrameIo => "line",
+            Adapter::Vimeo => "comment",
+        }
+    }
+
+    fn read(self, data: &str) -> ResolveResult<Vec<Comment>> {
+        match self {
+            Adapter::FrameIo => read_frameio_csv(data),
+            Adapter::Vimeo => read_vimeo_json(data),
+        }
+    }
+}
+
+/// Where a comment sits in the review file
+enum Position {
+    Timecode(String),
+    Seconds(f64),
+}
+
+/// A comment read from an export, before it is placed on the timeline
+struct Comment {
+    /// Line or comment number, for errors and skipped comments
+    entry: usize,
+    position: Position,
+    text: String,
+    author: Option<String>,
+    completed: bool,
+}
+
+/// Whether a CSV flag column reads as set
+fn flag_set(value: &str) -> bool {
+    matches!(
+        value.trim().to_ascii_lowercase().as_str(),
+        "true" | "yes" | "1" | "x" | "done" | "completed" | "resolved"
+    )
+}
+
+fn read_frameio_csv(data: &str) -> ResolveResult<Vec<Comment>> {
+    let mut rows = parse_csv(data).into_iter();
+    let header: Vec<String> = rows
+        .next()
+        .ok_or_else(|| ResolveError::invalid_parameter("data", "CSV has no header row"))?
+        .iter()
+        .map(|name| name.trim().to_ascii_lowercase())
+        .collect();
+    let column = |names: &[&str]| {
+        header
+            .iter()
+            .position(|name| names.contains(&name.as_str()))
+    };
+    let timecode_column = column(&["timecode", "timestamp", "tc"]).ok_or_else(|| {
+        ResolveError::invalid_parameter("data", "Frame.io CSV needs a 'Timecode' column")
+    })?;
+    let text_column = column(&["comment", "comments", "text"]).ok_or_else(|| {
+        ResolveError::invalid_parameter("data", "Frame.io CSV needs a 'Comment' column")
+    })?;
+    let author_column = column(&["commenter", "name", "author", "user"]);
+    let completed_column = column(&["completed", "status", "resolved"]);
+
+    rows.enumerate()
+        .map(|(index, row)| {
+            let field = |column: Option<usize>| {
+                column
+                    .and_then(|column| row.get(column))
+                    .map(|value| value.trim().to_string())
+                    .unwrap_or_default()
+            };
+            // Header is line 1
+            let line = index + 2;
+            // Range comments keep their in point
+            let timecode = field(Some(timecode_column))
+                .split('-')
+                .next()
+                .unwrap_or_default()
+                .trim()
+                .to_string();
+            if timecode.is_empty() {
+                return Err(ResolveError::invalid_parameter(
+                    "data",
+                    format!("line {}: comment has no timecode", line),
+                ));
+            }
+            let author = field(author_column);
+            Ok(Comment {
+                entry: line,
+                position: Position::Timecode(timecode),
+                text: field(Some(text_column)),
+                author: Some(author).filter(|author| !author.is_empty()),
+                completed: flag_set(&field(completed_column)),
+            })
+        })
+        .collect()
+}
+
+fn read_vimeo_json(data: &str) -> ResolveResult<Vec<Comment>> {
+    let value: Value = serde_json::from_str(data)
+        .map_err(|e| ResolveError::invalid_parameter("data", format!("invalid JSON: {}", e)))?;
+    let entries = value
+        .as_array()
+        .or_else(|| value["data"].as_array())
+        .or_else(|| value["comments"].as_array())
+        .ok_or_else(|| {
+            ResolveError::invalid_parameter(
+                "data",
+                "JSON must be an array of comments or an object with a 'data' or 'comments' array",
+            )
+        })?;
+
+    entries
+        .iter()
+        .enumerate()
+        .map(|(index, entry)| {
+            let seconds = entry["timecode"]
+                .as_f64()
+                .or_else(|| entry["time"].as_f64())
+                .filter(|seconds| seconds.is_finite() && *seconds >= 0.0)
+                .ok_or_else(|| {
+                    ResolveError::invalid_parameter(
+                        "data",
+                        format!(
+                            "comment {} needs a 'timecode' of 0 or more seconds",
+                            index + 1
+                        ),
+                    )
+                })?;
+            Ok(Comment {
+                entry: index + 1,
+                position: Position::Seconds(seconds),
+                text: entry["text"]
+                    .as_str()
+                    .or_else(|| entry["comment"].as_str())
+                    .unwrap_or_default()
+                    .trim()
+                    .to_string(),
+                author: entry["user"]["name"]
+                    .as_str()
+                    .or_else(|| entry["author"].as_str())
+                    .map(str::to_string),
+                completed: entry["resolved"]
+                    .as_bool()
+                    .or_else(|| entry["is_resolved"].as_bool())
+                    .unwrap_or(false),
+            })
+        })
+        .collect()
+}
+
+impl ResolveBridge {
+    pub(super) async fn import_review_comments(
+        &self,
+        state: &mut StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        let adapter = Adapter::parse(
+            args["adapter"]
+                .as_str()
+                .ok_or_else(|| ResolveError::invalid_parameter("adapter", "required string"))?,
+        )?;
+        let data = args["data"]
+            .as_str()
+            .ok_or_else(|| ResolveError::invalid_parameter("data", "required string"))?;
+        let settings: &ReviewAdapterConfig = match adapter {
+            Adapter::FrameIo => &self.config.review_import.frameio,
+            Adapter::Vimeo => &self.config.review_import.vimeo,
+        };
+        let target = match args["target"].as_str() {
+            None => settings.target,
+            Some("markers") => ReviewImportTarget::Markers,
+            Some("notes") => ReviewImportTarget::Notes,
+            Some(_) => {
+                return Err(ResolveError::invalid_parameter(
+                    "target",
+                    "must be 'markers' or 'notes'",
+                ))
+            }
+        };
+        let file_start = args["file_start_timecode"]
+            .as_str()
+            .unwrap_or(&settings.file_start_timecode);
+        let offset_frames = match &args["offset_frames"] {
+            Value::Null => settings.offset_frames,
+            value => value.as_i64().ok_or_else(|| {
+                ResolveError::invalid_parameter("offset_frames", "must be a whole number")
+            })?,
+        };
+        let include_completed = args["include_completed"]
+            .as_bool()
+            .unwrap_or(settings.include_completed);
+        let color = marker_color(&settings.marker_color).ok_or_else(|| {
+            ResolveError::invalid_parameter(
+                "marker_color",
+                format!(
+                    "review_import.{}.marker_color '{}' is not a Resolve marker color",
+                    adapter.as_str(),
+                    settings.marker_color
+                ),
+            )
+        })?;
+        let timeline_name = Self::resolve_timeline_name(state, &args)?;
+        let comments = adapter.read(data)?;
+
+        let timeline = &state.timelines[&timeline_name];
+        let timeline_start = timeline_start(timeline, &args);
+        let rate = self
+            .timeline_frame_rate(timeline)?
+            .for_timecode(timeline_start)
+            .map_err(|e| ResolveError::invalid_parameter("timeline_start_timecode", e))?;
+        let frames_at = |timecode: &str| rate.for_timecode(timecode)?.timecode_to_frames(timecode);
+        let start_frame = frames_at(timeline_start)
+            .map_err(|e| ResolveError::invalid_parameter("timeline_start_timecode", e))?;
+        let file_start_frame = frames_at(file_start)
+            .map_err(|e| ResolveError::invalid_parameter("file_start_timecode", e))?;
+
+        // Place every comment before adding any of them
+        let mut placed: Vec<(Comment, i32)> = Vec::with_capacity(comments.len());
+        let mut skipped = Vec::new();
+        for comment in comments {
+            let skip = |reason: &str| json!({ "entry": comment.entry, "reason": reason });
+            if comment.text.is_empty() {
+                skipped.push(skip("comment has no text"));
+                continue;
+            }
+            if comment.completed && !include_completed {
+                skipped.push(skip("marked done on the platform"));
+                continue;
+            }
+            let entry_error = |reason: String| {
+                ResolveError::invalid_parameter(
+                    "data",
+                    format!("{} {}: {}", adapter.entry_name(), comment.entry, reason),
+                )
+            };
+            let file_frame = match &comment.position {
+                Position::Timecode(timecode) => frames_at(timecode)
+                    .map_err(entry_error)?
+                    .saturating_sub(file_start_frame),
+                Position::Seconds(seconds) => (seconds * rate.fps()).round() as i64,
+            };
+            let frame = file_frame.saturating_add(offset_frames);
+            if frame < 0 {
+                skipped.push(skip("before the timeline start"));
+                continue;
+            }
+            let frame = i32::try_from(frame)
+                .map_err(|_| entry_error("is past the end of any timeline".to_string()))?;
+            // Resolve keeps one marker per frame
+            let taken = target == ReviewImportTarget::Markers
+                && (timeline.markers.iter().any(|m| m.frame == Some(frame))
+                    || placed.iter().any(|(_, placed)| *placed == frame));
+            if taken {
+                skipped.push(skip("frame already has a marker"));
+                continue;
+            }
+            placed.push((comment, frame));
+        }
+        let marker_count = timeline.markers.len();
+        let record_timecode = |frame: i32| rate.frames_to_timecode(start_frame + i64::from(frame));
+
+        let mut imported = Vec::with_capacity(placed.len());
+        match target {
+            ReviewImportTarget::Markers => {
+                ensure_capacity(
+                    &format!("markers on timeline '{}'", timeline_name),
+                    marker_count,
+                    placed.len(),
+                    self.config.limits.max_markers_per_timeline,
+                )?;
+                let mut markers = Vec::with_capacity(placed.len());
+                for (comment, frame) in &placed {
+                    let marker = Marker {
+                        id: Uuid::new_v4().to_string(),
+                        frame: Some(*frame),
+                        color: if comment.completed {
+                            COMPLETED_COLOR
+                        } else {
+                            color
+                        }
+                        .to_string(),
+                        note: match &comment.author {
+                            Some(author) => format!("{}: {}", author, comment.text),
+                            None => comment.text.clone(),
+                        },
+                        name: String::new(),
+                        duration: 1,
+                    };
+                    imported.push(json!({
+                        "entry": comment.entry,
+                        "frame": frame,
+                        "timecode": record_timecode(*frame),
+                        "author": comment.author,
+                        "marker_id": marker.id
+                    }));
+                    markers.push(marker);
+                }
+                state
+                    .timelines
+                    .get_mut(&timeline_name)
+                    .ok_or_else(|| ResolveError::internal("timeline missing"))?
+                    .markers
+                    .extend(markers);
+            }
+            ReviewImportTarget::Notes => {
+                for (comment, frame) in placed {
+                    let timecode = record_timecode(frame);
+                    let (entry, author) = (comment.entry, comment.author.clone());
+                    let note_id = self
+                        .add_imported_note(
+                            state,
+                            ImportedNote {
+                                timeline_name: timeline_name.clone(),
+                                timecode: timecode.clone(),
+                                frame: i64::from(frame),
+                                text: comment.text,
+                                author: comment.author,
+                                completed: comment.completed,
+                            },
+                        )
+                        .await;
+                    imported.push(json!({
+                        "entry": entry,
+                        "frame": frame,
+                        "timecode": timecode,
+                        "author": author,
+                        "note_id": note_id
+                    }));
+                }
+            }
+        }
+
+        let (target_name, target_label) = match target {
+            ReviewImportTarget::Markers => ("markers", "markers"),
+            ReviewImportTarget::Notes => ("notes", "review notes"),
+        };
+        Ok(json!({
+            "result": format!(
+                "Imported {} {} comments into timeline '{}' as {}",
+                imported.len(),
+                adapter.as_str(),
+                timeline_name,
+                target_label
+            ),
+            "adapter": adapter.as_str(),
+            "target": target_name,
+            "timeline_name": timeline_name,
+            "frame_rate": rate.to_string(),
+            "file_start_timecode": file_start,
+            "offset_frames": offset_frames,
+            "imported_count": imported.len(),
+            "imported": imported,
+            "skipped": skipped,
+            "operation_id": Uuid::new_v4().to_string()
+        }))
+    }
+}
@@ -0,0 +1,217 @@
+//! Scope statistics for a single clip frame
+//!
+//! The frame is decoded with ffmpeg at a reduced size and summarized as a
+//! luma histogram, an RGB parade, vectorscope chroma figures and percentile
+//! statistics. Levels are reported from 0.0 to 1.0 using Rec. 709 weights.
+//! Offline media, or any media when ffmpeg is missing, is summarized from a
+//! simulated gradient frame instead.
+
+use serde_json::{json, Value};
+use std::path::Path;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::ffmpeg;
+use super::{ClipGrade, ResolveBridge, StateView};
+use crate::error::{ResolveError, ResolveResult};
+use crate::timecode::FrameRate;
+
+/// Size frames are scaled to before analysis
+pub(super) const ANALYSIS_WIDTH: u32 = 320;
+pub(super) const ANALYSIS_HEIGHT: u32 = 180;
+
+const DEFAULT_HISTOGRAM_BINS: u64 = 32;
+
+/// Vertical bands the parade is averaged over, left to right
+const PARADE_BANDS: usize = 8;
+
+/// Percentiles reported for luma and each channel
+const PERCENTILES: [u32; 5] = [1, 5, 50, 95, 99];
+
+/// Gradient frame used when the media cannot be decoded
+fn simulated_frame(frame: i64) -> Vec<u8> {
+    let (width, height) = (ANALYSIS_WIDTH as usize, ANALYSIS_HEIGHT as usize);
+    let blue = (frame.rem_euclid(51) * 5) as u8;
+    let mut pixels = Vec::with_capacity(width * height * 3);
+    for y in 0..height {
+        for x in 0..width {
+            pixels.push((x * 255 / (width - 1)) as u8);
+            pixels.push((y * 255 / (height - 1)) as u8);
+            pixels.push(blue);
+        }
+    }
+    pixels
+}
+
+/// Rec. 709 luma of 0.0-1.0 RGB levels
+pub(super) fn rec709_luma(r: f64, g: f64, b: f64) -> f64 {
+    0.2126 * r + 0.7152 * g + 0.0722 * b
+}
+
+pub(super) fn round(value: f64) -> f64 {
+    (value * 10_000.0).round() / 10_000.0
+}
+
+/// Mean, extremes and percentiles of a set of levels
+fn level_stats(values: &mut [f64]) -> Value {
+    values.sort_by(f64::total_cmp);
+    let count = values.len().max(1);
+    let percentile = |p: u32| values[(p as usize * (count - 1) + 50) / 100];
+    let mut stats = json!({
+        "min": round(values[0]),
+        "max": round(values[count - 1]),
+        "mean": round(values.iter().sum::<f64>() / count as f64)
+    });
+    for p in PERCENTILES {
+        stats[format!("p{}", p)] = json!(round(percentile(p)));
+    }
+    stats
+}
+
+impl ResolveBridge {
+    /// RGB24 pixels of a media frame at analysis size, and whether they were
+    /// "decoded", "simulated" or taken from the frame "cache"
+    pub(super) fn analysis_frame(
+        &self,
+        file_path: &str,
+        frame: i64,
+        rate: FrameRate,
+        grade: Option<&ClipGrade>,
+    ) -> (Arc<Vec<u8>>, &'static str) {
+        self.frame_cache
+            .get_or_decode(file_path, frame, rate, grade, || {
+                let media = Path::new(file_path);
+                if media.is_file() && ffmpeg::available() {
+                    match ffmpeg::rgb_frame(
+                        media,
+                        frame as f64 / rate.fps(),
+                        ANALYSIS_WIDTH,
+                        ANALYSIS_HEIGHT,
+                    ) {
+                        Ok(pixels) => return (pixels, "decoded"),
+                        Err(e) => tracing::warn!("Failed to decode frame of {}: {}", file_path, e),
+                    }
+                }
+                (simulated_frame(frame), "simulated")
+            })
+    }
+
+    pub(super) async fn get_frame_scopes(
+        &self,
+        state: &StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        let clip_reference = args["clip_name"]
+            .as_str()
+            .ok_or_else(|| ResolveError::invalid_parameter("clip_name", "required string"))?;
+        let frame = args["frame"].as_i64().unwrap_or(0);
+        if frame < 0 {
+            return Err(ResolveError::invalid_parameter(
+                "frame",
+                "must be 0 or greater",
+            ));
+        }
+        let bins = args["histogram_bins"]
+            .as_u64()
+            .unwrap_or(DEFAULT_HISTOGRAM_BINS);
+        if !(1..=256).contains(&bins) {
+            return Err(ResolveError::invalid_parameter(
+                "histogram_bins",
+                "must be between 1 and 256",
+            ));
+        }
+        let clip_name = &Self::resolve_clip(state, clip_reference)?;
+        let clip = &state.media_pool.clips[clip_name];
+        let rate = self.project_frame_rate()?;
+        let (pixels, source) = self.analysis_frame(
+            &clip.file_path,
+            frame,
+            rate,
+            state.color_state.clip_grades.get(clip_name),
+        );
+
+        let width = ANALYSIS_WIDTH as usize;
+        let pixel_count = pixels.len() / 3;
+        let mut luma = Vec::with_capacity(pixel_count);
+        let mut channels: [Vec<f64>; 3] = Default::default();
+        let mut parade = [[0.0f64; PARADE_BANDS]; 3];
+        let mut band_counts = [0usize; PARADE_BANDS];
+        let mut histogram = vec![0u64; bins as usize];
+        let (mut cb_sum, mut cr_sum, mut saturation_sum, mut saturation_max) =
+            (0.0, 0.0, 0.0, 0.0f64);
+
+        for (index, rgb) in pixels.chunks_exact(3).enumerate() {
+            let [r, g, b] = [rgb[0], rgb[1], rgb[2]].map(|level| f64::from(level) / 255.0);
+            let y = rec709_luma(r, g, b);
+            let cb = (b - y) / 1.8556;
+            let cr = (r - y) / 1.5748;
+            let saturation = (cb * cb + cr * cr).sqrt();
+            cb_sum += cb;
+            cr_sum += cr;
+            saturation_sum += saturation;
+            saturation_max = saturation_max.max(saturation);
+
+            let bin = ((y * bins as f64) as usize).min(bins as usize - 1);
+            histogram[bin] += 1;
+            luma.push(y);
+
+            let band = (index % width) * PARADE_BANDS / width;
+            band_counts[band] += 1;
+            for (channel, level) in [r, g, b].into_iter().enumerate() {
+                channels[channel].push(level);
+                parade[channel][band] += level;
+            }
+        }
+
+        let count = pixel_count.max(1) as f64;
+        let clipped = |test: fn(f64) -> bool| {
+            round(luma.iter().filter(|&&y| test(y)).count() as f64 / count * 100.0)
+        };
+        let clipped_black = clipped(|y| y <= 1.0 / 255.0);
+        let clipped_white = clipped(|y| y >= 254.0 / 255.0);
+        let (mean_cb, mean_cr) = (cb_sum / count, cr_sum / count);
+        let parade_json = |channel: usize| {
+            parade[channel]
+                .iter()
+                .zip(band_counts)
+                .map(|(sum, n)| round(sum / n.max(1) as f64))
+                .collect::<Vec<_>>()
+        };
+        let [red, green, blue] = &mut channels;
+
+        Ok(json!({
+            "result": format!("Computed scopes for frame {} of clip '{}'", frame, clip_name),
+            "clip_name": clip_name,
+            "frame": frame,
+            "timecode": rate.frames_to_timecode(frame),
+            "source": source,
+            "analysis_resolution": [ANALYSIS_WIDTH, ANALYSIS_HEIGHT],
+            "luma": level_stats(&mut luma),
+            "luma_clipped_black_percent": clipped_black,
+            "luma_clipped_white_percent": clipped_white,
+            "histogram": {
+                "bins": bins,
+                "counts": histogram
+            },
+            "rgb": {
+                "red": level_stats(red),
+                "green": level_stats(green),
+                "blue": level_stats(blue)
+            },
+            "parade": {
+                "bands": PARADE_BANDS,
+                "red": parade_json(0),
+                "green": parade_json(1),
+                "blue": parade_json(2)
+            },
+            "vectorscope": {
+                "mean_cb": round(mean_cb),
+                "mean_cr": round(mean_cr),
+                "mean_hue_degrees": round(mean_cr.atan2(mean_cb).to_degrees().rem_euclid(360.0)),
+                "mean_saturation": round(saturation_sum / count),
+                "max_saturation": round(saturation_max)
+            },
+            "operation_id": Uuid::new_v4().to_string()
+        }))
+    }
+}
@@ -0,0 +1,294 @@
+//! Share links to rendered outputs
+//!
+//! get_share_link hands out a URL for a completed render job's output file,
+//! so review bots and chat integrations can fetch it without access to the
+//! filesystem. The first link starts a small HTTP server on the `[share]`
+//! address; it answers GET and HEAD for `/share/<token>/<file name>` and
+//! nothing else, so only files a link was made for can be fetched. Tokens are
+//! random, expire after `share.link_ttl_hours` or less, and are gone when the
+//! server restarts.
+//!
+//! Outputs are shared from where the job wrote them, so the file must exist:
+//! in simulation mode that is only the case when something else put it
+//! there. With `share.root` set, only outputs inside it can be shared.
+
+use chrono::{DateTime, Utc};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+use super::{RenderJobStatus, ResolveBridge, StateView};
+use crate::error::{ResolveError, ResolveResult};
+
+/// Most a request line and headers may take up
+const MAX_REQUEST_HEAD_BYTES: u64 = 16 * 1024;
+
+/// A file a link was made for
+#[derive(Debug)]
+struct SharedFile {
+    path: PathBuf,
+    expires_at: DateTime<Utc>,
+}
+
+/// The share server once started, and its links by token
+#[derive(Debug, Default)]
+pub(super) struct ShareState {
+    address: Option<SocketAddr>,
+    links: HashMap<String, SharedFile>,
+}
+
+/// Content type of a shared file, by extension
+fn content_type(path: &Path) -> &'static str {
+    let extension = path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    match extension.as_str() {
+        "mp4" | "m4v" => "video/mp4",
+        "mov" => "video/quicktime",
+        "mxf" => "application/mxf",
+        "webm" => "video/webm",
+        "gif" => "image/gif",
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "wav" => "audio/wav",
+        "mp3" => "audio/mpeg",
+        "srt" => "application/x-subrip",
+        _ => "application/octet-stream",
+    }
+}
+
+/// Percent-encode a file name for the last segment of a link
+fn encode_segment(name: &str) -> String {
+    name.bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
+
+/// Accept connections until the process exits
+async fn accept_loop(listener: TcpListener, share: Arc<Mutex<ShareState>>) {
+    loop {
+        match listener.accept().await {
+            Ok((stream, _)) => {
+                let share = Arc::clone(&share);
+                tokio::spawn(async move {
+                    if let Err(e) = serve(stream, share).await {
+                        tracing::debug!("Share request failed: {}", e);
+                    }
+                });
+            }
+            Err(e) => tracing::warn!("Share server could not accept a connection: {}", e),
+        }
+    }
+}
+
+/// Answer one request and close the connection
+async fn serve(stream: TcpStream, share: Arc<Mutex<ShareState>>) -> std::io::Result<()> {
+    let (reader, mut writer) = stream.into_split();
+    let mut reader = BufReader::new(reader.take(MAX_REQUEST_HEAD_BYTES));
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line).await?;
+    // Headers are read and ignored
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header).await? == 0 || header.trim().is_empty() {
+            break;
+        }
+    }
+
+    let mut parts = request_line.split_whitespace();
+    let (method, target) = (
+        parts.next().unwrap_or_default(),
+        parts.next().unwrap_or_default(),
+    );
+    if method != "GET" && method != "HEAD" {
+        return respond(&mut writer, "405 Method Not Allowed").await;
+    }
+    let Some(token) = target
+        .strip_prefix("/share/")
+        .and_then(|rest| rest.split('/').next())
+    else {
+        return respond(&mut writer, "404 Not Found").await;
+    };
+    let path = {
+        let share = share.lock().await;
+        match share.links.get(token) {
+            Some(file) if file.expires_at > Utc::now() => file.path.clone(),
+            Some(_) => return respond(&mut writer, "410 Gone").await,
+            None => return respond(&mut writer, "404 Not Found").await,
+        }
+    };
+
+    let Ok(mut file) = tokio::fs::File::open(&path).await else {
+        return respond(&mut writer, "404 Not Found").await;
+    };
+    let length = file.metadata().await?.len();
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().replace('"', ""))
+        .unwrap_or_default();
+    let head = format!(
+        "HTTP/1.1 200 OK\r\nContent-Type: {}\r\nContent-Length: {}\r\nContent-Disposition: inline; filename=\"{}\"\r\nConnection: close\r\n\r\n",
+        content_type(&path),
+        length,
+        file_name
+    );
+    writer.write_all(head.as_bytes()).await?;
+    if method == "GET" {
+        tokio::io::copy(&mut file, &mut writer).await?;
+    }
+    writer.shutdown().await
+}
+
+/// Answer with a status and no body
+async fn respond(
+    writer: &mut tokio::net::tcp::OwnedWriteHalf,
+    status: &str,
+) -> std::io::Result<()> {
+    let response = format!(
+        "HTTP/1.1 {}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+        status
+    );
+    writer.write_all(response.as_bytes()).await?;
+    writer.shutdown().await
+}
+
+impl ResolveBridge {
+    /// Address of the share server, starting it on first use
+    async fn share_address(&self, share: &mut ShareState) -> ResolveResult<SocketAddr> {
+        if let Some(address) = share.address {
+            return Ok(address);
+        }
+        let settings = &self.config.share;
+        let listener = TcpListener::bind((settings.bind_address.as_str(), settings.port))
+            .await
+            .map_err(|e| {
+                ResolveError::internal(format!(
+                    "Share server cannot listen on {}:{}: {}",
+                    settings.bind_address, settings.port, e
+                ))
+            })?;
+        let address = listener
+            .local_addr()
+            .map_err(|e| ResolveError::internal(e.to_string()))?;
+        tokio::spawn(accept_loop(listener, Arc::clone(&self.share)));
+        tracing::info!("Serving share links on http://{}", address);
+        share.address = Some(address);
+        Ok(address)
+    }
+
+    pub(super) async fn get_share_link(
+        &self,
+        state: &mut StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        let settings = &self.config.share;
+        if !settings.enabled {
+            return Err(ResolveError::not_supported(
+                "get_share_link while share links are off; enable them in the [share] config",
+            ));
+        }
+        let job_id = args["job_id"]
+            .as_str()
+            .ok_or_else(|| ResolveError::invalid_parameter("job_id", "required string"))?;
+        let expires_in_hours = match args["expires_in_hours"].as_u64() {
+            Some(0) => {
+                return Err(ResolveError::invalid_parameter(
+                    "expires_in_hours",
+                    "must be greater than zero",
+                ))
+            }
+            Some(hours) => hours.min(settings.link_ttl_hours),
+            None => settings.link_ttl_hours,
+        };
+
+        let render_state = &state.render_state;
+        let Some(result) = render_state
+            .render_history
+            .iter()
+            .rev()
+            .find(|result| result.job_id == job_id)
+            .filter(|result| matches!(result.status, RenderJobStatus::Completed))
+        else {
+            let queued = render_state.render_queue.iter().any(|job| job.id == job_id);
+            return Err(ResolveError::invalid_parameter(
+                "job_id",
+                if queued {
+                    format!("render job '{}' has not completed", job_id)
+                } else {
+                    format!("render job '{}' not found", job_id)
+                },
+            ));
+        };
+        let output = PathBuf::from(&result.output_path);
+        let metadata = std::fs::metadata(&output)
+            .ok()
+            .filter(|metadata| metadata.is_file());
+        let Some(metadata) = metadata else {
+            return Err(ResolveError::FileNotFound {
+                path: result.output_path.clone(),
+            });
+        };
+        let output = std::fs::canonicalize(&output).unwrap_or(output);
+        if let Some(root) = &settings.root {
+            let root = std::fs::canonicalize(root).unwrap_or_else(|_| root.clone());
+            if !output.starts_with(&root) {
+                return Err(ResolveError::path_not_allowed(
+                    output.display().to_string(),
+                    format!("outside the share root {}", root.display()),
+                ));
+            }
+        }
+
+        let mut share = self.share.lock().await;
+        let address = self.share_address(&mut share).await?;
+        let now = Utc::now();
+        share.links.retain(|_, file| file.expires_at > now);
+        let token = Uuid::new_v4().simple().to_string();
+        let expires_at = now + chrono::Duration::hours(expires_in_hours as i64);
+        let file_name = output
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let base = settings
+            .public_url
+            .clone()
+            .unwrap_or_else(|| format!("http://{}", address));
+        let url = format!(
+            "{}/share/{}/{}",
+            base.trim_end_matches('/'),
+            token,
+            encode_segment(&file_name)
+        );
+        share.links.insert(
+            token,
+            SharedFile {
+                path: output,
+                expires_at,
+            },
+        );
+
+        Ok(json!({
+            "result": format!("Share link for the output of render job '{}'", job_id),
+            "url": url,
+            "job_id": job_id,
+            "timeline_name": result.timeline_name,
+            "file_name": file_name,
+            "file_size": metadata.len(),
+            "expires_at": expires_at.to_rfc3339(),
+            "active_links": share.links.len()
+        }))
+    }
+}
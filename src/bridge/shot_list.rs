@@ -0,0 +1,118 @@
+//! Shot list generation for VFX and editorial handoffs
+
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use super::markers::{csv_field, timeline_start};
+use super::{ResolveBridge, StateView};
+use crate::error::{ResolveError, ResolveResult};
+
+impl ResolveBridge {
+    pub(super) async fn generate_shot_list(
+        &self,
+        state: &StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        let format = args["format"]
+            .as_str()
+            .unwrap_or("json")
+            .to_ascii_lowercase();
+        if format != "json" && format != "csv" {
+            return Err(ResolveError::invalid_parameter(
+                "format",
+                "must be 'json' or 'csv'",
+            ));
+        }
+        let timeline_name = Self::resolve_timeline_name(state, &args)?;
+        let timeline = &state.timelines[&timeline_name];
+        let start = timeline_start(timeline, &args);
+        let rate = self
+            .timeline_frame_rate(timeline)?
+            .for_timecode(start)
+            .map_err(|e| ResolveError::invalid_parameter("timeline_start_timecode", e))?;
+        let start_frame = rate
+            .timecode_to_frames(start)
+            .map_err(|e| ResolveError::invalid_parameter("timeline_start_timecode", e))?;
+
+        let mut items: Vec<_> = state
+            .timeline_items
+            .items
+            .values()
+            .filter(|item| item.timeline_name == timeline_name)
+            .filter_map(|item| item.placement.as_ref().map(|placement| (item, placement)))
+            .collect();
+        items.sort_by_key(|(item, placement)| {
+            (placement.record_in, placement.track_index, &item.id)
+        });
+
+        let shots: Vec<Value> = items
+            .iter()
+            .enumerate()
+            .map(|(index, (item, placement))| {
+                let notes: Vec<&str> = timeline
+                    .markers
+                    .iter()
+                    .filter(|marker| {
+                        marker.frame.is_some_and(|frame| {
+                            (placement.record_in..placement.record_out()).contains(&i64::from(frame))
+                        })
+                    })
+                    .map(|marker| marker.note.as_str())
+                    .filter(|note| !note.is_empty())
+                    .collect();
+                json!({
+                    "shot": index + 1,
+                    "timeline_item_id": item.id,
+                    "clip_name": item.clip_name,
+                    "file_path": state.media_pool.clips.get(&item.clip_name).map(|clip| clip.file_path.as_str()),
+                    "track": format!("V{}", placement.track_index),
+                    "source_in": rate.frames_to_timecode(placement.source_in),
+                    "source_out": rate.frames_to_timecode(placement.source_out()),
+                    "record_in": rate.frames_to_timecode(start_frame + placement.record_in),
+                    "record_out": rate.frames_to_timecode(start_frame + placement.record_out()),
+                    "duration_frames": placement.duration,
+                    "duration": rate.frames_to_timecode(placement.duration),
+                    "notes": notes
+                })
+            })
+            .collect();
+
+        let content = if format == "csv" {
+            let mut csv = String::from(
+                "Shot,Clip Name,Track,Source In,Source Out,Record In,Record Out,Duration,Notes\n",
+            );
+            for shot in &shots {
+                let notes: Vec<&str> = shot["notes"]
+                    .as_array()
+                    .map(|notes| notes.iter().filter_map(Value::as_str).collect())
+                    .unwrap_or_default();
+                let field = |key: &str| shot[key].as_str().unwrap_or_default().to_string();
+                csv.push_str(&format!(
+                    "{},{},{},{},{},{},{},{},{}\n",
+                    shot["shot"],
+                    csv_field(&field("clip_name")),
+                    field("track"),
+                    field("source_in"),
+                    field("source_out"),
+                    field("record_in"),
+                    field("record_out"),
+                    shot["duration_frames"],
+                    csv_field(&notes.join("; "))
+                ));
+            }
+            Some(csv)
+        } else {
+            None
+        };
+
+        Ok(json!({
+            "result": format!("Generated shot list of {} shots for timeline '{}'", shots.len(), timeline_name),
+            "timeline_name": timeline_name,
+            "frame_rate": rate.to_string(),
+            "shot_count": shots.len(),
+            "shots": shots,
+            "content": content,
+            "operation_id": Uuid::new_v4().to_string()
+        }))
+    }
+}
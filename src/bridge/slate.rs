@@ -0,0 +1,293 @@
+//! Slates and countdowns at the head of a timeline for broadcast delivery
+//!
+//! The slate is a title item holding the template fields, followed by a
+//! countdown generator that runs into the first frame of program. When the
+//! program starts too early to fit them, everything on the timeline, markers
+//! and playhead included, moves later to make room. Bars and tone at the head
+//! stay where they are, ahead of the slate.
+
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use super::item_lookup::timeline_clock;
+use super::{ItemPlacement, ResolveBridge, StateView, TimelineItemState};
+use crate::error::{ResolveError, ResolveResult};
+
+/// Longest leader element the tools lay down, in seconds
+const MAX_SECONDS: f64 = 3600.0;
+
+/// Seconds from the arguments, or the configured default
+pub(super) fn seconds(args: &Value, key: &str, default: f64, minimum: f64) -> ResolveResult<f64> {
+    match &args[key] {
+        Value::Null => Ok(default),
+        value => value
+            .as_f64()
+            .filter(|seconds| (minimum..=MAX_SECONDS).contains(seconds))
+            .ok_or_else(|| {
+                ResolveError::invalid_parameter(
+                    key,
+                    format!(
+                        "must be a number of seconds from {} to {}",
+                        minimum, MAX_SECONDS
+                    ),
+                )
+            }),
+    }
+}
+
+/// Video track from the arguments, V1 by default
+pub(super) fn track_index(args: &Value) -> ResolveResult<u32> {
+    match args["track_index"].as_i64() {
+        Some(index) if index >= 1 => Ok(index as u32),
+        Some(_) => Err(ResolveError::invalid_parameter(
+            "track_index",
+            "must be 1 or greater",
+        )),
+        None => Ok(1),
+    }
+}
+
+/// Whether an item is a generator of this type, such as "slate" or "bars"
+pub(super) fn is_generated(item: &TimelineItemState, generator_type: &str) -> bool {
+    item.generator
+        .as_ref()
+        .is_some_and(|generator| generator["type"] == generator_type)
+}
+
+/// Move everything on a timeline from frame `from` onwards later, markers and
+/// playhead included
+pub(super) fn ripple_later(state: &mut StateView<'_>, timeline_name: &str, from: i64, shift: i64) {
+    if shift <= 0 {
+        return;
+    }
+    for item in state.timeline_items.items.values_mut() {
+        if item.timeline_name != timeline_name {
+            continue;
+        }
+        if let Some(placement) = item
+            .placement
+            .as_mut()
+            .filter(|placement| placement.record_in >= from)
+        {
+            placement.record_in += shift;
+        }
+    }
+    let timeline = state
+        .timelines
+        .get_mut(timeline_name)
+        .expect("resolved timeline exists");
+    for marker in &mut timeline.markers {
+        if let Some(frame) = marker
+            .frame
+            .as_mut()
+            .filter(|frame| i64::from(**frame) >= from)
+        {
+            *frame += shift as i32;
+        }
+    }
+    if let Some(playhead) = timeline
+        .playhead
+        .as_mut()
+        .filter(|playhead| **playhead >= from)
+    {
+        *playhead += shift;
+    }
+}
+
+impl ResolveBridge {
+    pub(super) async fn add_slate(
+        &self,
+        state: &mut StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        let settings = &self.config.slate;
+        let project_name = state
+            .current_project
+            .clone()
+            .ok_or(ResolveError::NotRunning)?;
+        let timeline_name = Self::resolve_timeline_name(state, &args)?;
+        let (rate, start_frame) = timeline_clock(self, state, &timeline_name, &args)?;
+        let slate_seconds = seconds(&args, "slate_seconds", settings.slate_seconds, 0.1)?;
+        let countdown_seconds =
+            seconds(&args, "countdown_seconds", settings.countdown_seconds, 0.0)?;
+        let track_index = track_index(&args)?;
+
+        let on_timeline = |item: &&TimelineItemState| item.timeline_name == timeline_name;
+        if state
+            .timeline_items
+            .items
+            .values()
+            .filter(on_timeline)
+            .any(|item| is_generated(item, "slate"))
+        {
+            return Err(ResolveError::invalid_parameter(
+                "timeline_name",
+                format!("timeline '{}' already has a slate", timeline_name),
+            ));
+        }
+
+        // The program as it stands, before anything moves; bars and tone stay
+        // ahead of the slate
+        let (bars, program): (Vec<&TimelineItemState>, Vec<&TimelineItemState>) = state
+            .timeline_items
+            .items
+            .values()
+            .filter(on_timeline)
+            .partition(|item| is_generated(item, "bars"));
+        let leader_out = bars
+            .iter()
+            .filter_map(|item| item.placement.as_ref())
+            .map(ItemPlacement::record_out)
+            .max()
+            .unwrap_or(0);
+        let placements: Vec<&ItemPlacement> = program
+            .iter()
+            .filter_map(|item| item.placement.as_ref())
+            .collect();
+        let program_in = placements
+            .iter()
+            .map(|placement| placement.record_in)
+            .min()
+            .unwrap_or(leader_out);
+        let program_out = placements
+            .iter()
+            .map(|placement| placement.record_out())
+            .max()
+            .unwrap_or(0);
+        let trt = rate.frames_to_timecode(program_out - program_in);
+
+        // Template fields: call values, then timeline values, then configured ones
+        let overrides = match &args["fields"] {
+            Value::Null => serde_json::Map::new(),
+            Value::Object(fields) => fields.clone(),
+            _ => {
+                return Err(ResolveError::invalid_parameter(
+                    "fields",
+                    "must be an object of field names and values",
+                ))
+            }
+        };
+        let mut order: Vec<String> = match &args["field_order"] {
+            Value::Null => settings.fields.clone(),
+            Value::Array(names) => names
+                .iter()
+                .map(|name| {
+                    name.as_str().map(str::to_string).ok_or_else(|| {
+                        ResolveError::invalid_parameter("field_order", "must be a list of strings")
+                    })
+                })
+                .collect::<ResolveResult<_>>()?,
+            _ => {
+                return Err(ResolveError::invalid_parameter(
+                    "field_order",
+                    "must be a list of strings",
+                ))
+            }
+        };
+        for name in overrides.keys() {
+            if !order.contains(name) {
+                order.push(name.clone());
+            }
+        }
+        let fields = order
+            .iter()
+            .map(|name| {
+                let value = match overrides.get(name) {
+                    Some(Value::String(value)) => Some(value.clone()),
+                    Some(Value::Number(value)) => Some(value.to_string()),
+                    Some(_) => {
+                        return Err(ResolveError::invalid_parameter(
+                            "fields",
+                            format!("value of '{}' must be text", name),
+                        ))
+                    }
+                    None => match name.to_ascii_lowercase().as_str() {
+                        "project" => Some(project_name.clone()),
+                        "timeline" => Some(timeline_name.clone()),
+                        "date" => Some(chrono::Local::now().format("%Y-%m-%d").to_string()),
+                        "trt" => Some(trt.clone()),
+                        _ => settings.values.get(name).cloned(),
+                    },
+                };
+                let value = value.ok_or_else(|| {
+                    ResolveError::invalid_parameter(
+                        "fields",
+                        format!("no value for slate field '{}'", name),
+                    )
+                })?;
+                Ok(json!({ "name": name, "value": value }))
+            })
+            .collect::<ResolveResult<Vec<Value>>>()?;
+
+        // Make room at the head when the program starts too early
+        let fps = rate.fps();
+        let slate_frames = ((slate_seconds * fps).round() as i64).max(1);
+        let countdown_frames = (countdown_seconds * fps).round() as i64;
+        let head = slate_frames + countdown_frames;
+        let shift = (leader_out + head - program_in).max(0);
+        ripple_later(state, &timeline_name, leader_out, shift);
+        let program_start = program_in + shift;
+
+        let mut insert = |name: &str, record_in: i64, duration: i64, generator: Value| {
+            let item_id = Uuid::new_v4().to_string();
+            state.timeline_items.item_counter += 1;
+            state.timeline_items.items.insert(
+                item_id.clone(),
+                TimelineItemState {
+                    id: item_id.clone(),
+                    timeline_name: timeline_name.clone(),
+                    clip_name: name.to_string(),
+                    placement: Some(ItemPlacement {
+                        track_index,
+                        record_in,
+                        source_in: 0,
+                        duration,
+                    }),
+                    generator: Some(generator),
+                    ..Default::default()
+                },
+            );
+            json!({
+                "timeline_item_id": item_id,
+                "track": format!("V{}", track_index),
+                "record_in": rate.frames_to_timecode(start_frame + record_in),
+                "record_out": rate.frames_to_timecode(start_frame + record_in + duration)
+            })
+        };
+        let mut slate = insert(
+            "Slate",
+            program_start - head,
+            slate_frames,
+            json!({ "type": "slate", "fields": fields }),
+        );
+        slate["fields"] = json!(fields);
+        let countdown = (countdown_frames > 0).then(|| {
+            insert(
+                "Countdown",
+                program_start - countdown_frames,
+                countdown_frames,
+                json!({ "type": "countdown", "seconds": countdown_seconds }),
+            )
+        });
+
+        Ok(json!({
+            "result": format!(
+                "Added a {} second slate{} to timeline '{}'",
+                slate_seconds,
+                if countdown.is_some() {
+                    format!(" and {} second countdown", countdown_seconds)
+                } else {
+                    String::new()
+                },
+                timeline_name
+            ),
+            "timeline_name": timeline_name,
+            "slate": slate,
+            "countdown": countdown,
+            "program_start": rate.frames_to_timecode(start_frame + program_start),
+            "trt": trt,
+            "shifted_frames": shift,
+            "operation_id": Uuid::new_v4().to_string()
+        }))
+    }
+}
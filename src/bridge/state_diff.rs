@@ -0,0 +1,247 @@
+//! Structured "what changed" reports for mutating calls
+//!
+//! A tool call made with `return_diff` records what it changed so the caller
+//! can check the effect without reading the state back. Before a mutating
+//! call runs, the entities of every domain it may write are captured as JSON,
+//! keyed by entity kind and ID; afterwards they are captured again and the
+//! two compared. An entity only in the second capture was added, one only in
+//! the first was removed, and one in both with different JSON was modified,
+//! reported field by field with dotted paths such as `placement.record_in`.
+//!
+//! Calls made while serving the tool call, such as the steps of an action,
+//! record into the same report, each change naming the call that made it.
+
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+use std::cell::RefCell;
+use std::future::Future;
+
+use super::locking::{Domain, LockPlan, StateView};
+use super::ResolveBridge;
+
+tokio::task_local! {
+    /// Changes recorded so far for the tool call being served
+    static CHANGES: RefCell<Vec<Value>>;
+}
+
+/// Entities of one kind, by ID
+type Collection = (&'static str, Map<String, Value>);
+
+/// Entities keyed by ID, from a map or struct that serializes to an object
+fn entities<T: Serialize + ?Sized>(collection: &T) -> Map<String, Value> {
+    match serde_json::to_value(collection) {
+        Ok(Value::Object(map)) => map,
+        _ => Map::new(),
+    }
+}
+
+/// A single entity that is not part of any collection, such as the open project
+fn singleton(value: Value) -> Map<String, Value> {
+    let mut map = Map::new();
+    map.insert("current".to_string(), value);
+    map
+}
+
+/// Entities a call writing `domain` may change
+fn collections(state: &StateView<'_>, domain: Domain) -> Vec<Collection> {
+    match domain {
+        Domain::Project => vec![(
+            "project",
+            singleton(json!({
+                "name": *state.current_project,
+                "page": *state.current_page,
+                "projects": *state.projects
+            })),
+        )],
+        Domain::MediaPool => vec![
+            ("bin", entities(&state.media_pool.bins)),
+            ("clip", entities(&state.media_pool.clips)),
+            ("synced_clip", entities(&state.media_pool.synced_clips)),
+        ],
+        Domain::Timelines => vec![
+            ("timeline", entities(&*state.timelines)),
+            ("timeline_item", entities(&state.timeline_items.items)),
+            (
+                "keyframes",
+                entities(&state.keyframe_state.timeline_item_keyframes),
+            ),
+            (
+                "current_timeline",
+                singleton(json!({ "timeline_name": *state.current_timeline })),
+            ),
+        ],
+        Domain::Color => vec![
+            ("clip_grade", entities(&state.color_state.clip_grades)),
+            (
+                "color_preset_album",
+                entities(&state.color_state.preset_albums),
+            ),
+            ("color_group", entities(&state.color_state.color_groups)),
+            ("lut", entities(&state.color_state.available_luts)),
+            (
+                "still_album",
+                state
+                    .gallery
+                    .albums
+                    .iter()
+                    .map(|album| (album.name.clone(), json!(album)))
+                    .collect(),
+            ),
+            (
+                "color_page",
+                singleton(json!({
+                    "current_clip": state.color_state.current_clip,
+                    "current_node_index": state.color_state.current_node_index,
+                    "current_still_album": state.gallery.current_album
+                })),
+            ),
+        ],
+        Domain::Render => vec![
+            (
+                "render_job",
+                state
+                    .render_state
+                    .render_queue
+                    .iter()
+                    .map(|job| (job.id.clone(), json!(job)))
+                    .collect(),
+            ),
+            (
+                "render_preset",
+                entities(&state.render_state.render_presets),
+            ),
+            (
+                "render_result",
+                state
+                    .render_state
+                    .render_history
+                    .iter()
+                    .map(|result| (result.job_id.clone(), json!(result)))
+                    .collect(),
+            ),
+            (
+                "render_settings",
+                singleton(json!({
+                    "format_and_codec": state.render_state.format_and_codec
+                })),
+            ),
+        ],
+        Domain::Review => vec![
+            ("review_note", state.review.notes_json()),
+            ("tags", entities(&*state.tags)),
+            ("notes", entities(&*state.notes)),
+        ],
+        Domain::Jobs => vec![("job", state.jobs.summaries())],
+    }
+}
+
+/// Changed fields between two versions of an entity, as dotted paths
+pub(super) fn diff_fields(path: &str, before: &Value, after: &Value, fields: &mut Vec<Value>) {
+    match (before, after) {
+        (Value::Object(before), Value::Object(after)) => {
+            let mut keys: Vec<&String> = before.keys().chain(after.keys()).collect();
+            keys.sort();
+            keys.dedup();
+            for key in keys {
+                let field = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}.{}", path, key)
+                };
+                diff_fields(
+                    &field,
+                    before.get(key).unwrap_or(&Value::Null),
+                    after.get(key).unwrap_or(&Value::Null),
+                    fields,
+                );
+            }
+        }
+        _ if before != after => fields.push(json!({
+            "field": path,
+            "before": before,
+            "after": after
+        })),
+        _ => {}
+    }
+}
+
+/// Entities of the domains a call may write, captured before or after it
+pub(super) struct Snapshot {
+    collections: Vec<Collection>,
+}
+
+impl Snapshot {
+    /// Capture every domain `plan` may write
+    pub(super) fn capture(state: &StateView<'_>, plan: LockPlan) -> Self {
+        Self {
+            collections: plan
+                .writes()
+                .iter()
+                .flat_map(|&domain| collections(state, domain))
+                .collect(),
+        }
+    }
+
+    /// Capture the state before a call, if the tool call being served asked
+    /// for its changes and the call may change anything
+    pub(super) fn before(state: &StateView<'_>, plan: LockPlan) -> Option<Self> {
+        let requested = CHANGES.try_with(|_| ()).is_ok();
+        (requested && !plan.is_read_only()).then(|| Self::capture(state, plan))
+    }
+
+    /// Record what `method` changed since this capture
+    pub(super) fn record(self, state: &StateView<'_>, plan: LockPlan, method: &str) {
+        let changes = self.changes(state, plan, method);
+        let _ = CHANGES.try_with(|recorded| recorded.borrow_mut().extend(changes));
+    }
+
+    /// What `method` changed since this capture, one entry per entity
+    pub(super) fn changes(self, state: &StateView<'_>, plan: LockPlan, method: &str) -> Vec<Value> {
+        let after = Self::capture(state, plan);
+        let mut changes = Vec::new();
+        for ((kind, before), (_, after)) in self.collections.into_iter().zip(after.collections) {
+            let mut ids: Vec<&String> = before.keys().chain(after.keys()).collect();
+            ids.sort();
+            ids.dedup();
+            for id in ids {
+                let change = match (before.get(id), after.get(id)) {
+                    (None, Some(added)) => json!({ "change": "added", "after": added }),
+                    (Some(removed), None) => json!({ "change": "removed", "before": removed }),
+                    (Some(old), Some(new)) if old != new => {
+                        let mut fields = Vec::new();
+                        diff_fields("", old, new, &mut fields);
+                        json!({ "change": "modified", "fields": fields })
+                    }
+                    _ => continue,
+                };
+                let mut entry = json!({ "tool": method, "entity": kind, "id": id });
+                if let (Some(entry), Value::Object(change)) = (entry.as_object_mut(), change) {
+                    entry.extend(change);
+                }
+                changes.push(entry);
+            }
+        }
+        changes
+    }
+}
+
+impl ResolveBridge {
+    /// Run a tool call, collecting the changes of every call it makes to the
+    /// bridge, in the order they were made
+    pub async fn with_changes<F: Future>(work: F) -> (F::Output, Vec<Value>) {
+        CHANGES
+            .scope(RefCell::new(Vec::new()), async {
+                let output = work.await;
+                (output, CHANGES.with(|recorded| recorded.take()))
+            })
+            .await
+    }
+
+    /// Whether a tool call can change the state, and so report its changes
+    pub fn is_mutating(method: &str) -> bool {
+        matches!(
+            method,
+            "run_action" | "bootstrap_session" | "describe_frame"
+        ) || !Self::lock_plan(method).is_read_only()
+    }
+}
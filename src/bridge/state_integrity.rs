@@ -0,0 +1,461 @@
+//! State integrity checks
+//!
+//! Many tools reach across parts of the state: items name their timeline and
+//! clip, keyframes their item, grades and color groups their clips, render
+//! jobs their timeline. Deleting a timeline or a clip leaves those references
+//! behind. verify_state_integrity walks every such reference and reports the
+//! ones that lead nowhere, along with markers outside their timeline, and can
+//! repair them: orphans are removed, an item whose clip was renamed is pointed
+//! at it again through the clip ID, and a current timeline or clip that no
+//! longer exists is cleared.
+//!
+//! Items whose clip is gone cannot be repaired here, since removing them would
+//! change the cut; they are reported for relinking. A backup can be checked
+//! before it is restored, without repairing it.
+
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, HashMap};
+use uuid::Uuid;
+
+use super::{
+    ColorState, KeyframeState, MediaPool, RenderState, ResolveBridge, StateView, Timeline,
+    TimelineItemsState,
+};
+use crate::error::{ResolveError, ResolveResult};
+
+/// The parts of the state that reference each other
+pub(super) struct Tables<'a> {
+    pub(super) timelines: &'a HashMap<String, Timeline>,
+    pub(super) current_timeline: &'a Option<String>,
+    pub(super) media_pool: &'a MediaPool,
+    pub(super) timeline_items: &'a TimelineItemsState,
+    pub(super) keyframe_state: &'a KeyframeState,
+    pub(super) color_state: &'a ColorState,
+    pub(super) render_state: &'a RenderState,
+}
+
+impl<'a> Tables<'a> {
+    fn of(state: &'a StateView<'_>) -> Self {
+        Self {
+            timelines: &state.timelines,
+            current_timeline: &state.current_timeline,
+            media_pool: &state.media_pool,
+            timeline_items: &state.timeline_items,
+            keyframe_state: &state.keyframe_state,
+            color_state: &state.color_state,
+            render_state: &state.render_state,
+        }
+    }
+}
+
+/// How a problem is repaired
+enum Repair {
+    RemoveItem(String),
+    /// Point an item at the clip that now has its clip ID
+    RelinkItem {
+        item_id: String,
+        clip_name: String,
+    },
+    RemoveKeyframes(String),
+    RemoveMarker {
+        timeline: String,
+        marker_id: String,
+    },
+    RemoveBinEntry {
+        bin: String,
+        clip_name: String,
+    },
+    ClearClipBin(String),
+    ClearCurrentTimeline,
+    ClearCurrentClip,
+    RemoveClipGrade(String),
+    RemoveGroupMember {
+        group: String,
+        clip_name: String,
+    },
+    RemoveRenderJob(String),
+}
+
+/// A reference that leads nowhere
+struct Problem {
+    check: &'static str,
+    entity: &'static str,
+    id: String,
+    detail: String,
+    /// None when the problem can only be reported
+    repair: Option<Repair>,
+}
+
+impl Problem {
+    fn new(
+        check: &'static str,
+        entity: &'static str,
+        id: impl Into<String>,
+        detail: String,
+        repair: Option<Repair>,
+    ) -> Self {
+        Self {
+            check,
+            entity,
+            id: id.into(),
+            detail,
+            repair,
+        }
+    }
+
+    fn to_json(&self, repaired: bool) -> Value {
+        json!({
+            "check": self.check,
+            "entity": self.entity,
+            "id": self.id,
+            "detail": self.detail,
+            "repairable": self.repair.is_some(),
+            "repaired": repaired
+        })
+    }
+}
+
+/// Every broken reference in `tables`, in a stable order
+fn check(tables: &Tables<'_>) -> Vec<Problem> {
+    let mut problems = Vec::new();
+    let clips = &tables.media_pool.clips;
+
+    // Timeline items and the timeline and clip each names
+    let mut items: Vec<_> = tables.timeline_items.items.values().collect();
+    items.sort_by(|a, b| a.id.cmp(&b.id));
+    for item in &items {
+        if !tables.timelines.contains_key(&item.timeline_name) {
+            problems.push(Problem::new(
+                "item_timeline",
+                "timeline_item",
+                &item.id,
+                format!("timeline '{}' does not exist", item.timeline_name),
+                Some(Repair::RemoveItem(item.id.clone())),
+            ));
+            continue;
+        }
+        // Items only known by ID have no clip to check
+        if item.placement.is_none()
+            || item.generator.is_some()
+            || clips.contains_key(&item.clip_name)
+        {
+            continue;
+        }
+        let renamed = item.clip_id.as_ref().and_then(|clip_id| {
+            clips
+                .values()
+                .find(|clip| clip.id == *clip_id)
+                .map(|clip| clip.name.clone())
+        });
+        let detail = match &renamed {
+            Some(name) => format!("clip '{}' was renamed to '{}'", item.clip_name, name),
+            None => format!(
+                "clip '{}' is not in the media pool; relink it",
+                item.clip_name
+            ),
+        };
+        problems.push(Problem::new(
+            "item_clip",
+            "timeline_item",
+            &item.id,
+            detail,
+            renamed.map(|clip_name| Repair::RelinkItem {
+                item_id: item.id.clone(),
+                clip_name,
+            }),
+        ));
+    }
+
+    // Keyframes of items that do not exist
+    let mut keyframed: Vec<&String> = tables
+        .keyframe_state
+        .timeline_item_keyframes
+        .keys()
+        .filter(|id| !tables.timeline_items.items.contains_key(*id))
+        .collect();
+    keyframed.sort();
+    for id in keyframed {
+        let count: usize = tables.keyframe_state.timeline_item_keyframes[id]
+            .property_keyframes
+            .values()
+            .map(Vec::len)
+            .sum();
+        problems.push(Problem::new(
+            "keyframe_item",
+            "keyframes",
+            id,
+            format!("{} keyframes belong to an item that does not exist", count),
+            Some(Repair::RemoveKeyframes(id.clone())),
+        ));
+    }
+
+    // Markers before the start or past the end of their timeline
+    let mut timelines: Vec<_> = tables.timelines.iter().collect();
+    timelines.sort_by_key(|(name, _)| *name);
+    for (name, timeline) in timelines {
+        let end = tables
+            .timeline_items
+            .items
+            .values()
+            .filter(|item| item.timeline_name == *name)
+            .filter_map(|item| item.placement.as_ref())
+            .map(|placement| placement.record_out())
+            .max();
+        for marker in &timeline.markers {
+            let Some(frame) = marker.frame else {
+                continue;
+            };
+            let frame = i64::from(frame);
+            // An empty timeline has no end to be past
+            let outside = frame < 0 || end.is_some_and(|end| frame >= end);
+            if outside {
+                problems.push(Problem::new(
+                    "marker_bounds",
+                    "marker",
+                    &marker.id,
+                    match end {
+                        Some(end) if frame >= 0 => format!(
+                            "frame {} is past the end of timeline '{}' at frame {}",
+                            frame, name, end
+                        ),
+                        _ => format!("frame {} is before the start of timeline '{}'", frame, name),
+                    },
+                    Some(Repair::RemoveMarker {
+                        timeline: name.clone(),
+                        marker_id: marker.id.clone(),
+                    }),
+                ));
+            }
+        }
+    }
+
+    // Bins listing clips that do not exist, and clips filed in bins that do not
+    let mut bins: Vec<_> = tables.media_pool.bins.iter().collect();
+    bins.sort_by_key(|(name, _)| *name);
+    for (bin, contents) in bins {
+        for clip_name in contents
+            .clips
+            .iter()
+            .filter(|name| !clips.contains_key(*name))
+        {
+            problems.push(Problem::new(
+                "bin_clip",
+                "bin",
+                &contents.id,
+                format!(
+                    "bin '{}' lists clip '{}', which does not exist",
+                    bin, clip_name
+                ),
+                Some(Repair::RemoveBinEntry {
+                    bin: bin.clone(),
+                    clip_name: clip_name.clone(),
+                }),
+            ));
+        }
+    }
+    let mut filed: Vec<_> = clips
+        .values()
+        .filter_map(|clip| Some((clip, clip.bin.as_ref()?)))
+        .filter(|(_, bin)| !tables.media_pool.bins.contains_key(*bin))
+        .collect();
+    filed.sort_by(|a, b| a.0.name.cmp(&b.0.name));
+    for (clip, bin) in filed {
+        problems.push(Problem::new(
+            "clip_bin",
+            "clip",
+            &clip.id,
+            format!(
+                "clip '{}' is filed in bin '{}', which does not exist",
+                clip.name, bin
+            ),
+            Some(Repair::ClearClipBin(clip.name.clone())),
+        ));
+    }
+
+    // Current timeline and clip
+    if let Some(name) = tables.current_timeline {
+        if !tables.timelines.contains_key(name) {
+            problems.push(Problem::new(
+                "current_timeline",
+                "current_timeline",
+                name,
+                format!("current timeline '{}' does not exist", name),
+                Some(Repair::ClearCurrentTimeline),
+            ));
+        }
+    }
+    let color = tables.color_state;
+    if let Some(name) = &color.current_clip {
+        if !clips.contains_key(name) {
+            problems.push(Problem::new(
+                "current_clip",
+                "color_page",
+                name,
+                format!("clip '{}' current on the color page does not exist", name),
+                Some(Repair::ClearCurrentClip),
+            ));
+        }
+    }
+
+    // Grades and color groups of clips that do not exist
+    let mut grades: Vec<&String> = color
+        .clip_grades
+        .keys()
+        .filter(|name| !clips.contains_key(*name))
+        .collect();
+    grades.sort();
+    for name in grades {
+        problems.push(Problem::new(
+            "grade_clip",
+            "clip_grade",
+            name,
+            format!("grade of clip '{}', which does not exist", name),
+            Some(Repair::RemoveClipGrade(name.clone())),
+        ));
+    }
+    for (group, members) in &color.color_groups {
+        for clip_name in members.iter().filter(|name| !clips.contains_key(*name)) {
+            problems.push(Problem::new(
+                "group_clip",
+                "color_group",
+                group,
+                format!(
+                    "color group '{}' lists clip '{}', which does not exist",
+                    group, clip_name
+                ),
+                Some(Repair::RemoveGroupMember {
+                    group: group.clone(),
+                    clip_name: clip_name.clone(),
+                }),
+            ));
+        }
+    }
+
+    // Render jobs of timelines that do not exist
+    for job in &tables.render_state.render_queue {
+        if !tables.timelines.contains_key(&job.timeline_name) {
+            problems.push(Problem::new(
+                "render_job_timeline",
+                "render_job",
+                &job.id,
+                format!("timeline '{}' does not exist", job.timeline_name),
+                Some(Repair::RemoveRenderJob(job.id.clone())),
+            ));
+        }
+    }
+    problems
+}
+
+/// Apply one repair to the state
+fn repair(state: &mut StateView<'_>, repair: &Repair) {
+    match repair {
+        Repair::RemoveItem(id) => {
+            state.timeline_items.items.remove(id);
+            state.keyframe_state.timeline_item_keyframes.remove(id);
+        }
+        Repair::RelinkItem { item_id, clip_name } => {
+            if let Some(item) = state.timeline_items.items.get_mut(item_id) {
+                item.clip_name = clip_name.clone();
+            }
+        }
+        Repair::RemoveKeyframes(id) => {
+            state.keyframe_state.timeline_item_keyframes.remove(id);
+        }
+        Repair::RemoveMarker {
+            timeline,
+            marker_id,
+        } => {
+            if let Some(timeline) = state.timelines.get_mut(timeline) {
+                timeline.markers.retain(|marker| marker.id != *marker_id);
+            }
+        }
+        Repair::RemoveBinEntry { bin, clip_name } => {
+            if let Some(bin) = state.media_pool.bins.get_mut(bin) {
+                bin.clips.retain(|name| name != clip_name);
+            }
+        }
+        Repair::ClearClipBin(clip_name) => {
+            if let Some(clip) = state.media_pool.clips.get_mut(clip_name) {
+                clip.bin = None;
+            }
+        }
+        Repair::ClearCurrentTimeline => *state.current_timeline = None,
+        Repair::ClearCurrentClip => state.color_state.current_clip = None,
+        Repair::RemoveClipGrade(clip_name) => {
+            state.color_state.clip_grades.remove(clip_name);
+        }
+        Repair::RemoveGroupMember { group, clip_name } => {
+            if let Some(members) = state.color_state.color_groups.get_mut(group) {
+                members.retain(|name| name != clip_name);
+            }
+        }
+        Repair::RemoveRenderJob(id) => {
+            state.render_state.render_queue.retain(|job| job.id != *id);
+        }
+    }
+}
+
+impl ResolveBridge {
+    pub(super) async fn verify_state_integrity(
+        &self,
+        state: &mut StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        let repair_requested = args["repair"].as_bool().unwrap_or(false);
+        let backup_id = args["backup_id"].as_str();
+        if backup_id.is_some() && repair_requested {
+            return Err(ResolveError::invalid_parameter(
+                "repair",
+                "backups are checked as they are; restore the backup to repair it",
+            ));
+        }
+
+        let problems = match backup_id {
+            Some(backup_id) => {
+                let tables = state.backups.tables(backup_id).ok_or_else(|| {
+                    ResolveError::invalid_parameter(
+                        "backup_id",
+                        format!("backup '{}' not found", backup_id),
+                    )
+                })?;
+                check(&tables)
+            }
+            None => check(&Tables::of(state)),
+        };
+        let mut repaired = 0;
+        let mut report = Vec::with_capacity(problems.len());
+        for problem in &problems {
+            let fixed = match &problem.repair {
+                Some(fix) if repair_requested => {
+                    repair(state, fix);
+                    true
+                }
+                _ => false,
+            };
+            if fixed {
+                repaired += 1;
+            }
+            report.push(problem.to_json(fixed));
+        }
+        let mut by_check: BTreeMap<&str, usize> = BTreeMap::new();
+        for problem in &problems {
+            *by_check.entry(problem.check).or_default() += 1;
+        }
+
+        Ok(json!({
+            "result": match (problems.len(), repair_requested) {
+                (0, _) => "No integrity problems found".to_string(),
+                (found, true) => format!("Found {} integrity problems and repaired {}", found, repaired),
+                (found, false) => format!("Found {} integrity problems", found),
+            },
+            "backup_id": backup_id,
+            "healthy": problems.is_empty(),
+            "problem_count": problems.len(),
+            "by_check": by_check,
+            "repaired": repaired,
+            "unrepaired": problems.len() - repaired,
+            "problems": report,
+            "operation_id": Uuid::new_v4().to_string()
+        }))
+    }
+}
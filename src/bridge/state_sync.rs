@@ -0,0 +1,421 @@
+//! Mirroring real Resolve state into the simulation state
+//!
+//! In Real mode a background task snapshots the open project every
+//! `state_sync.interval_seconds`: its name, its timelines with their markers,
+//! and the render queue with each job's progress. The snapshot replaces those
+//! parts of the state, so when a call to Resolve fails because the Python
+//! bridge is busy, the simulation it falls back to answers from the real
+//! project as of the last sync rather than from invented state.
+//!
+//! The snapshot script runs without holding any lock; only applying it takes
+//! the project, timeline and render locks. Parts of the state Resolve does not
+//! report, such as subtitle tracks, output framing or the chapters a job
+//! embeds, are kept for the timelines and jobs that are still there.
+
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::time::Duration;
+use uuid::Uuid;
+
+use super::{
+    render_formats, ConnectionMode, Domain, LockPlan, Marker, RenderJob, RenderJobStatus,
+    RenderProgress, RenderState, ResolveBridge, StateView, Timeline,
+};
+use crate::error::{ResolveError, ResolveResult};
+
+/// Domains a snapshot replaces
+const SYNC_LOCKS: LockPlan = LockPlan::write(&[Domain::Project, Domain::Timelines, Domain::Render]);
+
+/// Prints the parts of the open project that are mirrored
+const SNAPSHOT_SCRIPT: &str = r#"
+    snapshot = {"success": True, "project": None, "current_timeline": None, "timelines": [], "render_jobs": []}
+    project = resolve.GetProjectManager().GetCurrentProject()
+    if project:
+        snapshot["project"] = project.GetName()
+        current = project.GetCurrentTimeline()
+        snapshot["current_timeline"] = current.GetName() if current else None
+
+        starts = {}
+        for index in range(1, project.GetTimelineCount() + 1):
+            timeline = project.GetTimelineByIndex(index)
+            if not timeline:
+                continue
+            markers = []
+            for frame, marker in sorted((timeline.GetMarkers() or {}).items()):
+                markers.append({
+                    "frame": int(frame),
+                    "color": marker.get("color", "Blue"),
+                    "note": marker.get("note") or marker.get("name", ""),
+                    "name": marker.get("name", ""),
+                    "duration": int(marker.get("duration") or 1)
+                })
+            width = timeline.GetSetting("timelineResolutionWidth")
+            height = timeline.GetSetting("timelineResolutionHeight")
+            starts[timeline.GetName()] = timeline.GetStartFrame()
+            snapshot["timelines"].append({
+                "id": timeline.GetUniqueId(),
+                "name": timeline.GetName(),
+                "frame_rate": timeline.GetSetting("timelineFrameRate") or None,
+                "resolution_width": int(width) if width else None,
+                "resolution_height": int(height) if height else None,
+                "markers": markers
+            })
+
+        for job in project.GetRenderJobList() or []:
+            status = project.GetRenderJobStatus(job["JobId"]) or {}
+            start = starts.get(job.get("TimelineName"), 0)
+            frame_range = None
+            if job.get("MarkIn") is not None and job.get("MarkOut") is not None:
+                frame_range = [job["MarkIn"] - start, job["MarkOut"] - start + 1]
+            snapshot["render_jobs"].append({
+                "job_id": job["JobId"],
+                "timeline_name": job.get("TimelineName", ""),
+                "preset_name": job.get("PresetName", ""),
+                "output_path": os.path.join(job.get("TargetDir", ""), job.get("OutputFilename", "")),
+                "frame_range": frame_range,
+                "export_video": job.get("IsExportVideo", True),
+                "export_audio": job.get("IsExportAudio", True),
+                "status": status.get("JobStatus", "Ready"),
+                "completion_percent": status.get("CompletionPercentage", 0)
+            })
+
+    print(json.dumps(snapshot))
+"#;
+
+/// The mirrored parts of the real project, as the snapshot script prints them
+#[derive(Debug, Deserialize)]
+struct Snapshot {
+    project: Option<String>,
+    current_timeline: Option<String>,
+    timelines: Vec<TimelineSnapshot>,
+    render_jobs: Vec<RenderJobSnapshot>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TimelineSnapshot {
+    id: String,
+    name: String,
+    frame_rate: Option<String>,
+    resolution_width: Option<i32>,
+    resolution_height: Option<i32>,
+    markers: Vec<MarkerSnapshot>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MarkerSnapshot {
+    /// Frame relative to the timeline start, as GetMarkers keys them
+    frame: i32,
+    color: String,
+    note: String,
+    #[serde(default)]
+    name: String,
+    #[serde(default = "single_frame")]
+    duration: i64,
+}
+
+fn single_frame() -> i64 {
+    1
+}
+
+#[derive(Debug, Deserialize)]
+pub(super) struct RenderJobSnapshot {
+    pub(super) job_id: String,
+    pub(super) timeline_name: String,
+    pub(super) preset_name: String,
+    pub(super) output_path: String,
+    /// Record frames relative to the timeline start, end exclusive
+    pub(super) frame_range: Option<(i64, i64)>,
+    pub(super) export_video: bool,
+    pub(super) export_audio: bool,
+    /// Resolve's JobStatus: Ready, Rendering, Complete, Failed or Cancelled
+    pub(super) status: String,
+    pub(super) completion_percent: f32,
+}
+
+impl RenderJobSnapshot {
+    pub(super) fn status(&self) -> RenderJobStatus {
+        match self.status.as_str() {
+            "Rendering" => RenderJobStatus::Rendering,
+            "Complete" => RenderJobStatus::Completed,
+            "Failed" => RenderJobStatus::Failed,
+            "Cancelled" => RenderJobStatus::Cancelled,
+            _ => RenderJobStatus::Queued,
+        }
+    }
+}
+
+/// Outcome of the syncs so far
+#[derive(Debug, Default)]
+pub(super) struct SyncStatus {
+    /// Whether the periodic task has been started
+    running: bool,
+    syncs: u64,
+    failures: u64,
+    last_synced_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Error of the last attempt; None when it succeeded
+    last_error: Option<String>,
+    /// What the last successful sync mirrored
+    last_summary: Value,
+}
+
+impl SyncStatus {
+    fn to_json(&self, mode: &ConnectionMode, interval_seconds: u64) -> Value {
+        json!({
+            "enabled": *mode == ConnectionMode::Real,
+            "periodic": self.running,
+            "interval_seconds": interval_seconds,
+            "syncs": self.syncs,
+            "failures": self.failures,
+            "last_synced_at": self.last_synced_at.map(|at| at.to_rfc3339()),
+            "seconds_since_sync": self
+                .last_synced_at
+                .map(|at| (chrono::Utc::now() - at).num_milliseconds() as f64 / 1000.0),
+            "last_error": self.last_error,
+            "last_summary": self.last_summary
+        })
+    }
+}
+
+impl Snapshot {
+    /// Replace the mirrored parts of the state; returns what was mirrored
+    fn apply(self, state: &mut StateView<'_>) -> Value {
+        if *state.current_project != self.project {
+            state.project_settings.clear();
+        }
+        if let Some(project) = &self.project {
+            if !state.projects.contains(project) {
+                state.projects.push(project.clone());
+            }
+        }
+        *state.current_project = self.project.clone();
+
+        let mut timelines = HashMap::with_capacity(self.timelines.len());
+        let mut marker_count = 0;
+        for real in self.timelines {
+            let mut timeline = state
+                .timelines
+                .remove(&real.name)
+                .unwrap_or_else(|| Timeline {
+                    id: real.id.clone(),
+                    name: real.name.clone(),
+                    frame_rate: None,
+                    start_timecode: None,
+                    resolution_width: None,
+                    resolution_height: None,
+                    markers: Vec::new(),
+                    playhead: None,
+                    subtitle_tracks: Vec::new(),
+                    lineage: None,
+                    output: Default::default(),
+                });
+            timeline.frame_rate = real.frame_rate;
+            timeline.resolution_width = real.resolution_width;
+            timeline.resolution_height = real.resolution_height;
+            // Resolve holds one marker per frame, so a marker keeps its ID by frame
+            let markers = real
+                .markers
+                .into_iter()
+                .map(|marker| Marker {
+                    id: timeline
+                        .markers
+                        .iter()
+                        .find(|old| old.frame == Some(marker.frame))
+                        .map_or_else(|| Uuid::new_v4().to_string(), |old| old.id.clone()),
+                    frame: Some(marker.frame),
+                    color: marker.color,
+                    note: marker.note,
+                    name: marker.name,
+                    duration: marker.duration,
+                })
+                .collect();
+            timeline.markers = markers;
+            marker_count += timeline.markers.len();
+            timelines.insert(real.name, timeline);
+        }
+        let removed_timelines = state.timelines.len();
+        *state.timelines = timelines;
+        *state.current_timeline = self
+            .current_timeline
+            .filter(|name| state.timelines.contains_key(name));
+
+        let removed_render_jobs = mirror_render_jobs(&mut state.render_state, &self.render_jobs);
+
+        json!({
+            "project": self.project,
+            "timelines": state.timelines.len(),
+            "markers": marker_count,
+            "render_jobs": self.render_jobs.len(),
+            "removed_timelines": removed_timelines,
+            "removed_render_jobs": removed_render_jobs
+        })
+    }
+}
+
+/// Replace the render queue with Resolve's, keeping what Resolve does not
+/// report for the jobs that are still there; returns how many local jobs
+/// were dropped
+pub(super) fn mirror_render_jobs(
+    render_state: &mut RenderState,
+    jobs: &[RenderJobSnapshot],
+) -> usize {
+    let mut local: HashMap<String, RenderJob> = render_state
+        .render_queue
+        .drain(..)
+        .map(|job| (job.id.clone(), job))
+        .collect();
+    let now = chrono::Utc::now();
+    for real in jobs {
+        let status = real.status();
+        let old = local.remove(&real.job_id);
+        let audio_channel_layout = real.export_audio.then(|| {
+            old.as_ref()
+                .and_then(|job| job.audio_channel_layout.clone())
+                .unwrap_or_else(|| render_formats::DEFAULT_CHANNEL_LAYOUT.to_string())
+        });
+        if matches!(status, RenderJobStatus::Rendering) {
+            let total_frames = real
+                .frame_range
+                .map_or(0, |(start, end)| u32::try_from(end - start).unwrap_or(0));
+            let progress = real.completion_percent.clamp(0.0, 100.0);
+            render_state.active_renders.insert(
+                real.job_id.clone(),
+                RenderProgress {
+                    job_id: real.job_id.clone(),
+                    progress_percent: progress,
+                    estimated_time_remaining: None,
+                    current_frame: (total_frames as f32 * progress / 100.0) as u32,
+                    total_frames,
+                    status_message: format!("Rendering in Resolve, {:.0}% done", progress),
+                    last_update: now,
+                },
+            );
+        }
+        render_state.render_queue.push(RenderJob {
+            id: real.job_id.clone(),
+            timeline_name: real.timeline_name.clone(),
+            preset_name: real.preset_name.clone(),
+            output_path: real.output_path.clone(),
+            use_in_out_range: old.as_ref().is_some_and(|job| job.use_in_out_range),
+            frame_range: real.frame_range,
+            export_video: real.export_video,
+            audio_channel_layout,
+            subtitles: old.as_ref().and_then(|job| job.subtitles.clone()),
+            framing: old
+                .as_ref()
+                .map(|job| job.framing.clone())
+                .unwrap_or_default(),
+            embedding: old.as_ref().and_then(|job| job.embedding.clone()),
+            created_at: old.as_ref().map_or(now, |job| job.created_at),
+            status,
+        });
+    }
+    let rendering: Vec<&str> = render_state
+        .render_queue
+        .iter()
+        .filter(|job| matches!(job.status, RenderJobStatus::Rendering))
+        .map(|job| job.id.as_str())
+        .collect();
+    render_state
+        .active_renders
+        .retain(|job_id, _| rendering.contains(&job_id.as_str()));
+    local.len()
+}
+
+impl ResolveBridge {
+    /// Start syncing periodically, once, when Real mode connects
+    pub(super) async fn start_state_sync(&self) {
+        let interval_seconds = self.config.state_sync.interval_seconds;
+        if interval_seconds == 0 {
+            return;
+        }
+        {
+            let mut status = self.state_sync.lock().await;
+            if status.running {
+                return;
+            }
+            status.running = true;
+        }
+        let bridge = self.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_secs(interval_seconds));
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                interval.tick().await;
+                if let Err(e) = bridge.sync_once().await {
+                    tracing::warn!("Syncing real Resolve state failed: {}", e);
+                }
+            }
+        });
+        tracing::info!(
+            "Mirroring real Resolve state every {} seconds",
+            interval_seconds
+        );
+    }
+
+    /// Snapshot the real project and apply it, recording the outcome
+    async fn sync_once(&self) -> ResolveResult<Value> {
+        let bridge = self.clone();
+        let snapshot = tokio::task::spawn_blocking(move || {
+            let script = Self::resolve_script("", "", &Value::Null, SNAPSHOT_SCRIPT)?;
+            bridge.run_resolve_script("sync_real_state", &script)
+        })
+        .await
+        .map_err(|e| ResolveError::internal(e.to_string()))
+        .and_then(|output| output)
+        .and_then(|output| {
+            serde_json::from_value::<Snapshot>(output)
+                .map_err(|e| ResolveError::internal(format!("Unreadable state snapshot: {}", e)))
+        });
+
+        let result = match snapshot {
+            Ok(snapshot) => {
+                let mut state = self.state.lock(SYNC_LOCKS).await;
+                Ok(snapshot.apply(&mut state))
+            }
+            Err(e) => Err(e),
+        };
+        let mut status = self.state_sync.lock().await;
+        match &result {
+            Ok(summary) => {
+                status.syncs += 1;
+                status.last_synced_at = Some(chrono::Utc::now());
+                status.last_error = None;
+                status.last_summary = summary.clone();
+            }
+            Err(e) => {
+                status.failures += 1;
+                status.last_error = Some(e.to_string());
+            }
+        }
+        result
+    }
+
+    /// Sync now instead of waiting for the next periodic snapshot; takes no
+    /// lock while Resolve is being read
+    pub(super) async fn sync_real_state(&self, _args: Value) -> ResolveResult<Value> {
+        if self.mode != ConnectionMode::Real {
+            return Err(ResolveError::not_supported(
+                "sync_real_state outside Real mode; there is no real project to mirror",
+            ));
+        }
+        let summary = self.sync_once().await?;
+        Ok(json!({
+            "result": format!(
+                "Mirrored {} timelines, {} markers and {} render jobs from the real project",
+                summary["timelines"], summary["markers"], summary["render_jobs"]
+            ),
+            "mirrored": summary,
+            "sync": self.state_sync_status().await,
+            "operation_id": Uuid::new_v4().to_string()
+        }))
+    }
+
+    /// How syncing has gone, for get_bridge_stats
+    pub(super) async fn state_sync_status(&self) -> Value {
+        self.state_sync
+            .lock()
+            .await
+            .to_json(&self.mode, self.config.state_sync.interval_seconds)
+    }
+}
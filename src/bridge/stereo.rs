@@ -0,0 +1,303 @@
+//! Stereoscopic 3D parameters of timeline items
+//!
+//! Each item of a stereo timeline carries the parameters Resolve exposes
+//! through GetStereoConvergenceValues and the floating window methods: the
+//! convergence of the two eyes, and a floating window on each eye that masks
+//! its edges so objects in front of the screen are not cut off by the frame.
+//! Convergence and windows are keyframed by frame of the item, the way the
+//! real API reports them. The eye the item is seen with and whether its eyes
+//! are swapped apply to the whole item.
+
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+use std::collections::BTreeMap;
+use uuid::Uuid;
+
+use super::{ResolveBridge, StateView};
+use crate::error::{ResolveError, ResolveResult};
+
+/// Convergence range, in pixels of horizontal offset between the eyes
+const CONVERGENCE_RANGE: (f64, f64) = (-100.0, 100.0);
+
+/// Widest a floating window edge can be, as a fraction of the frame
+const MAX_WINDOW_EDGE: f64 = 0.5;
+
+/// Eye an item is seen with
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub(super) enum Eye {
+    #[default]
+    Both,
+    Left,
+    Right,
+}
+
+impl Eye {
+    fn parse(value: &str) -> ResolveResult<Self> {
+        match value.to_ascii_lowercase().as_str() {
+            "both" => Ok(Eye::Both),
+            "left" => Ok(Eye::Left),
+            "right" => Ok(Eye::Right),
+            _ => Err(ResolveError::invalid_parameter(
+                "eye",
+                "must be 'both', 'left' or 'right'",
+            )),
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Eye::Both => "both",
+            Eye::Left => "left",
+            Eye::Right => "right",
+        }
+    }
+}
+
+/// Floating window of one eye, each edge as a fraction of the frame
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub(super) struct FloatingWindow {
+    left: f64,
+    right: f64,
+    top: f64,
+    bottom: f64,
+}
+
+impl FloatingWindow {
+    /// This window with the edges given in `value` changed
+    fn updated(mut self, param: &str, value: &Value) -> ResolveResult<Self> {
+        let Some(edges) = value.as_object() else {
+            return Err(ResolveError::invalid_parameter(
+                param,
+                "must be an object of left, right, top and bottom edges",
+            ));
+        };
+        for (edge, width) in edges.iter().filter(|(_, width)| !width.is_null()) {
+            let slot = match edge.as_str() {
+                "left" => &mut self.left,
+                "right" => &mut self.right,
+                "top" => &mut self.top,
+                "bottom" => &mut self.bottom,
+                _ => {
+                    return Err(ResolveError::invalid_parameter(
+                        param,
+                        format!("unknown edge '{}'; use left, right, top or bottom", edge),
+                    ))
+                }
+            };
+            *slot = width
+                .as_f64()
+                .filter(|width| (0.0..=MAX_WINDOW_EDGE).contains(width))
+                .ok_or_else(|| {
+                    ResolveError::invalid_parameter(
+                        param,
+                        format!("{} must be a number from 0 to {}", edge, MAX_WINDOW_EDGE),
+                    )
+                })?;
+        }
+        if self.left + self.right >= 1.0 || self.top + self.bottom >= 1.0 {
+            return Err(ResolveError::invalid_parameter(
+                param,
+                "opposite edges would cover the whole frame",
+            ));
+        }
+        Ok(self)
+    }
+}
+
+/// Stereo 3D parameters of a timeline item
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub(super) struct StereoParams {
+    /// Convergence by frame of the item
+    convergence: BTreeMap<i64, f64>,
+    /// Left eye floating window by frame of the item
+    left_window: BTreeMap<i64, FloatingWindow>,
+    /// Right eye floating window by frame of the item
+    right_window: BTreeMap<i64, FloatingWindow>,
+    eye: Eye,
+    swap_eyes: bool,
+}
+
+impl Default for StereoParams {
+    fn default() -> Self {
+        Self {
+            convergence: BTreeMap::from([(0, 0.0)]),
+            left_window: BTreeMap::from([(0, FloatingWindow::default())]),
+            right_window: BTreeMap::from([(0, FloatingWindow::default())]),
+            eye: Eye::default(),
+            swap_eyes: false,
+        }
+    }
+}
+
+/// Value in effect at `frame`: the last keyframe at or before it, else the first
+fn at_frame<T: Copy>(keyframes: &BTreeMap<i64, T>, frame: i64) -> Option<T> {
+    keyframes
+        .range(..=frame)
+        .next_back()
+        .or_else(|| keyframes.iter().next())
+        .map(|(_, value)| *value)
+}
+
+/// Keyframes as an object keyed by frame, as the real API returns them
+fn keyed_by_frame<T: Serialize>(keyframes: &BTreeMap<i64, T>) -> Value {
+    let map: Map<String, Value> = keyframes
+        .iter()
+        .map(|(frame, value)| (frame.to_string(), json!(value)))
+        .collect();
+    Value::Object(map)
+}
+
+impl StereoParams {
+    fn to_json(&self) -> Value {
+        json!({
+            "convergence_values": keyed_by_frame(&self.convergence),
+            "left_floating_window": keyed_by_frame(&self.left_window),
+            "right_floating_window": keyed_by_frame(&self.right_window),
+            "eye": self.eye.as_str(),
+            "swap_eyes": self.swap_eyes
+        })
+    }
+}
+
+impl ResolveBridge {
+    pub(super) async fn set_stereo_params(
+        &self,
+        state: &mut StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        let timeline_item_id = args["timeline_item_id"].as_str().ok_or_else(|| {
+            ResolveError::invalid_parameter("timeline_item_id", "parameter is required")
+        })?;
+        let frame = match &args["frame"] {
+            Value::Null => 0,
+            value => value.as_i64().filter(|frame| *frame >= 0).ok_or_else(|| {
+                ResolveError::invalid_parameter("frame", "must be a non-negative integer")
+            })?,
+        };
+        let convergence = match &args["convergence"] {
+            Value::Null => None,
+            value => {
+                let (lowest, highest) = CONVERGENCE_RANGE;
+                Some(
+                    value
+                        .as_f64()
+                        .filter(|convergence| (lowest..=highest).contains(convergence))
+                        .ok_or_else(|| {
+                            ResolveError::invalid_parameter(
+                                "convergence",
+                                format!("must be a number from {} to {}", lowest, highest),
+                            )
+                        })?,
+                )
+            }
+        };
+        let eye = args["eye"].as_str().map(Eye::parse).transpose()?;
+        let swap_eyes = args["swap_eyes"].as_bool();
+        let (left_window, right_window) = (&args["left_window"], &args["right_window"]);
+        if convergence.is_none()
+            && eye.is_none()
+            && swap_eyes.is_none()
+            && left_window.is_null()
+            && right_window.is_null()
+        {
+            return Err(ResolveError::invalid_parameter(
+                "convergence",
+                "give convergence, left_window, right_window, eye or swap_eyes",
+            ));
+        }
+        if let Some(duration) = state
+            .timeline_items
+            .items
+            .get(timeline_item_id)
+            .and_then(|item| item.placement.as_ref())
+            .map(|placement| placement.duration)
+        {
+            if frame >= duration {
+                return Err(ResolveError::invalid_parameter(
+                    "frame",
+                    format!(
+                        "frame {} is past the end of '{}', which is {} frames long",
+                        frame, timeline_item_id, duration
+                    ),
+                ));
+            }
+        }
+
+        // Validate everything before changing anything
+        let mut stereo = state
+            .timeline_items
+            .items
+            .get(timeline_item_id)
+            .and_then(|item| item.stereo.clone())
+            .unwrap_or_default();
+        if let Some(convergence) = convergence {
+            stereo.convergence.insert(frame, convergence);
+        }
+        for (param, value, keyframes) in [
+            ("left_window", left_window, &mut stereo.left_window),
+            ("right_window", right_window, &mut stereo.right_window),
+        ] {
+            if !value.is_null() {
+                let window = at_frame(keyframes, frame)
+                    .unwrap_or_default()
+                    .updated(param, value)?;
+                keyframes.insert(frame, window);
+            }
+        }
+        if let Some(eye) = eye {
+            stereo.eye = eye;
+        }
+        if let Some(swap_eyes) = swap_eyes {
+            stereo.swap_eyes = swap_eyes;
+        }
+
+        let item = Self::timeline_item_mut(state, timeline_item_id);
+        let response = stereo.to_json();
+        item.stereo = Some(stereo);
+        Ok(json!({
+            "result": format!(
+                "Set stereo parameters of item '{}' at frame {}",
+                timeline_item_id, frame
+            ),
+            "timeline_item_id": timeline_item_id,
+            "clip_name": item.clip_name,
+            "frame": frame,
+            "stereo": response,
+            "operation_id": Uuid::new_v4().to_string()
+        }))
+    }
+
+    pub(super) async fn get_stereo_params(
+        &self,
+        state: &mut StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        let timeline_item_id = args["timeline_item_id"].as_str().ok_or_else(|| {
+            ResolveError::invalid_parameter("timeline_item_id", "parameter is required")
+        })?;
+        let item = state
+            .timeline_items
+            .items
+            .get(timeline_item_id)
+            .ok_or_else(|| {
+                ResolveError::invalid_parameter("timeline_item_id", "timeline item not found")
+            })?;
+        let stereo = item.stereo.clone().unwrap_or_default();
+        let mut response = json!({
+            "result": format!("Retrieved stereo parameters of item '{}'", timeline_item_id),
+            "timeline_item_id": timeline_item_id,
+            "clip_name": item.clip_name,
+            "stereo": stereo.to_json(),
+            "operation_id": Uuid::new_v4().to_string()
+        });
+        if let Some(frame) = args["frame"].as_i64() {
+            response["frame"] = json!(frame);
+            response["at_frame"] = json!({
+                "convergence": at_frame(&stereo.convergence, frame),
+                "left_floating_window": at_frame(&stereo.left_window, frame),
+                "right_floating_window": at_frame(&stereo.right_window, frame)
+            });
+        }
+        Ok(response)
+    }
+}
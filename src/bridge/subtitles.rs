@@ -0,0 +1,592 @@
+//! Subtitle tracks and cues
+//!
+//! A timeline's subtitle tracks hold cues with record in and out timecodes,
+//! text and a style. Cues on one track cannot overlap, as in Resolve. New cues
+//! take the track's style, with any font, size, position or color given on the
+//! call applied on top. Render jobs either burn a track into the picture or
+//! write it next to the render as a subtitle file.
+
+use serde::Serialize;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use super::languages;
+use super::markers::timeline_start;
+use super::{ResolveBridge, StateView, Timeline};
+use crate::error::{ResolveError, ResolveResult};
+use crate::timecode::FrameRate;
+
+/// Range of a subtitle font size, in points
+const FONT_SIZE_RANGE: (u64, u64) = (8, 200);
+
+#[derive(Debug, Clone, Serialize)]
+pub(super) struct SubtitleTrack {
+    name: String,
+    /// BCP-47 tag of the subtitle language
+    language: Option<&'static str>,
+    /// Style new cues start from
+    style: CueStyle,
+    /// Cues in record order
+    cues: Vec<SubtitleCue>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct SubtitleCue {
+    id: String,
+    /// Record frames relative to the timeline start; the out frame is exclusive
+    in_frame: i64,
+    out_frame: i64,
+    text: String,
+    style: CueStyle,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+enum CuePosition {
+    Bottom,
+    Center,
+    Top,
+}
+
+impl CuePosition {
+    fn parse(position: &str) -> ResolveResult<Self> {
+        match position.to_ascii_lowercase().as_str() {
+            "bottom" => Ok(Self::Bottom),
+            "center" => Ok(Self::Center),
+            "top" => Ok(Self::Top),
+            _ => Err(ResolveError::invalid_parameter(
+                "position",
+                "must be bottom, center or top",
+            )),
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Bottom => "bottom",
+            Self::Center => "center",
+            Self::Top => "top",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct CueStyle {
+    font: String,
+    /// Font size in points
+    size: u32,
+    position: CuePosition,
+    /// Text color as #RRGGBB
+    color: String,
+}
+
+impl Default for CueStyle {
+    fn default() -> Self {
+        Self {
+            font: "Open Sans".to_string(),
+            size: 42,
+            position: CuePosition::Bottom,
+            color: "#FFFFFF".to_string(),
+        }
+    }
+}
+
+impl CueStyle {
+    /// This style with the font, size, position and color given in `args`
+    fn with_args(&self, args: &Value) -> ResolveResult<Self> {
+        let mut style = self.clone();
+        if let Some(font) = args["font"].as_str() {
+            if font.trim().is_empty() {
+                return Err(ResolveError::invalid_parameter("font", "cannot be empty"));
+            }
+            style.font = font.trim().to_string();
+        }
+        if !args["size"].is_null() {
+            let size = args["size"]
+                .as_u64()
+                .filter(|size| (FONT_SIZE_RANGE.0..=FONT_SIZE_RANGE.1).contains(size))
+                .ok_or_else(|| {
+                    ResolveError::invalid_parameter(
+                        "size",
+                        format!(
+                            "must be a whole number from {} to {}",
+                            FONT_SIZE_RANGE.0, FONT_SIZE_RANGE.1
+                        ),
+                    )
+                })?;
+            style.size = size as u32;
+        }
+        if let Some(position) = args["position"].as_str() {
+            style.position = CuePosition::parse(position)?;
+        }
+        if let Some(color) = args["color"].as_str() {
+            let hex = color.strip_prefix('#').unwrap_or(color);
+            if hex.len() != 6 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+                return Err(ResolveError::invalid_parameter(
+                    "color",
+                    "must be a hex color such as #FFFFFF",
+                ));
+            }
+            style.color = format!("#{}", hex.to_ascii_uppercase());
+        }
+        Ok(style)
+    }
+
+    fn to_json(&self) -> Value {
+        json!({
+            "font": self.font,
+            "size": self.size,
+            "position": self.position.as_str(),
+            "color": self.color
+        })
+    }
+}
+
+/// How a render job delivers subtitles
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub(super) enum SubtitleExport {
+    /// Drawn into the picture
+    BurnIn { track_index: usize },
+    /// Written next to the render in `format` (srt or webvtt)
+    SeparateFile {
+        track_index: usize,
+        format: &'static str,
+    },
+}
+
+impl SubtitleExport {
+    /// Subtitle options for a render job; None when subtitles are not exported
+    pub(super) fn from_args(
+        timeline: &Timeline,
+        args: &Value,
+        export_video: bool,
+    ) -> ResolveResult<Option<Self>> {
+        let mode = args["subtitle_export"].as_str().unwrap_or("none");
+        let track_index = args["subtitle_track_index"].as_u64().unwrap_or(1) as usize;
+        let export = match mode {
+            "none" => return Ok(None),
+            "burn_in" if !export_video => {
+                return Err(ResolveError::invalid_parameter(
+                    "subtitle_export",
+                    "burned-in subtitles need the video track",
+                ))
+            }
+            "burn_in" => Self::BurnIn { track_index },
+            "separate_file" => {
+                let format = match args["subtitle_format"].as_str().unwrap_or("srt") {
+                    "srt" => "srt",
+                    "webvtt" => "webvtt",
+                    _ => {
+                        return Err(ResolveError::invalid_parameter(
+                            "subtitle_format",
+                            "must be srt or webvtt",
+                        ))
+                    }
+                };
+                Self::SeparateFile {
+                    track_index,
+                    format,
+                }
+            }
+            _ => {
+                return Err(ResolveError::invalid_parameter(
+                    "subtitle_export",
+                    "must be none, burn_in or separate_file",
+                ))
+            }
+        };
+
+        let track = track_at(timeline, "subtitle_track_index", track_index)?;
+        if track.cues.is_empty() {
+            return Err(ResolveError::invalid_parameter(
+                "subtitle_track_index",
+                format!("subtitle track {} has no cues to export", track_index),
+            ));
+        }
+        Ok(Some(export))
+    }
+
+    /// Response fields for a job rendering to `output_path`
+    pub(super) fn to_json(&self, output_path: &str) -> Value {
+        match self {
+            Self::BurnIn { track_index } => json!({
+                "mode": "burn_in",
+                "track_index": track_index
+            }),
+            Self::SeparateFile {
+                track_index,
+                format,
+            } => {
+                let extension = if *format == "webvtt" { "vtt" } else { "srt" };
+                let stem = output_path
+                    .rsplit_once('.')
+                    .map_or(output_path, |(stem, _)| stem);
+                json!({
+                    "mode": "separate_file",
+                    "track_index": track_index,
+                    "format": format,
+                    "path": format!("{}.{}", stem, extension)
+                })
+            }
+        }
+    }
+}
+
+/// Subtitle track by 1-based index
+fn track_at<'a>(
+    timeline: &'a Timeline,
+    param: &str,
+    track_index: usize,
+) -> ResolveResult<&'a SubtitleTrack> {
+    track_index
+        .checked_sub(1)
+        .and_then(|index| timeline.subtitle_tracks.get(index))
+        .ok_or_else(|| no_track(param, timeline, track_index))
+}
+
+fn no_track(param: &str, timeline: &Timeline, track_index: usize) -> ResolveError {
+    ResolveError::invalid_parameter(
+        param,
+        format!(
+            "timeline '{}' has no subtitle track {} ({} tracks)",
+            timeline.name,
+            track_index,
+            timeline.subtitle_tracks.len()
+        ),
+    )
+}
+
+/// Frame of a record timecode relative to the timeline start
+fn frame_at(rate: FrameRate, start_frame: i64, param: &str, timecode: &str) -> ResolveResult<i64> {
+    let frame = rate
+        .for_timecode(timecode)
+        .and_then(|rate| rate.timecode_to_frames(timecode))
+        .map_err(|e| ResolveError::invalid_parameter(param, e))?
+        - start_frame;
+    if frame < 0 {
+        return Err(ResolveError::invalid_parameter(
+            param,
+            format!("{} is before the timeline start", timecode),
+        ));
+    }
+    Ok(frame)
+}
+
+/// Cue text, which cannot be blank
+fn cue_text(text: &str) -> ResolveResult<String> {
+    if text.trim().is_empty() {
+        return Err(ResolveError::invalid_parameter("text", "cannot be empty"));
+    }
+    Ok(text.trim().to_string())
+}
+
+impl SubtitleTrack {
+    fn to_json(&self, track_index: usize, rate: FrameRate, start_frame: i64) -> Value {
+        json!({
+            "track_index": track_index,
+            "name": self.name,
+            "language": self.language,
+            "style": self.style.to_json(),
+            "cue_count": self.cues.len(),
+            "cues": self
+                .cues
+                .iter()
+                .map(|cue| cue.to_json(track_index, rate, start_frame))
+                .collect::<Vec<_>>()
+        })
+    }
+
+    /// Cue that would overlap `in_frame..out_frame`, ignoring the cue being changed
+    fn overlapping(&self, in_frame: i64, out_frame: i64, except: &str) -> Option<&SubtitleCue> {
+        self.cues
+            .iter()
+            .find(|cue| cue.id != except && cue.in_frame < out_frame && in_frame < cue.out_frame)
+    }
+
+    fn sort_cues(&mut self) {
+        self.cues.sort_by_key(|cue| cue.in_frame);
+    }
+}
+
+impl SubtitleCue {
+    fn to_json(&self, track_index: usize, rate: FrameRate, start_frame: i64) -> Value {
+        json!({
+            "cue_id": self.id,
+            "track_index": track_index,
+            "in_timecode": rate.frames_to_timecode(start_frame + self.in_frame),
+            "out_timecode": rate.frames_to_timecode(start_frame + self.out_frame),
+            "duration_frames": self.out_frame - self.in_frame,
+            "text": self.text,
+            "style": self.style.to_json()
+        })
+    }
+}
+
+impl ResolveBridge {
+    /// Timeline a subtitle call targets, its frame rate and the frame of its start timecode
+    fn subtitle_timeline(
+        &self,
+        state: &StateView<'_>,
+        args: &Value,
+    ) -> ResolveResult<(String, FrameRate, i64)> {
+        let timeline_name = Self::resolve_timeline_name(state, args)?;
+        let timeline = &state.timelines[&timeline_name];
+        let rate = self.timeline_frame_rate(timeline)?;
+        let start = timeline_start(timeline, args);
+        let start_frame = frame_at(rate, 0, "timeline_start_timecode", start)?;
+        Ok((timeline_name, rate, start_frame))
+    }
+
+    /// Cue in and out frames from `in_timecode` and `out_timecode`, defaulting to `current`
+    fn cue_range(
+        args: &Value,
+        rate: FrameRate,
+        start_frame: i64,
+        current: Option<(i64, i64)>,
+    ) -> ResolveResult<(i64, i64)> {
+        let frame = |param: &str, fallback: Option<i64>| match args[param].as_str() {
+            Some(timecode) => frame_at(rate, start_frame, param, timecode),
+            None => {
+                fallback.ok_or_else(|| ResolveError::invalid_parameter(param, "required timecode"))
+            }
+        };
+        let in_frame = frame("in_timecode", current.map(|(in_frame, _)| in_frame))?;
+        let out_frame = frame("out_timecode", current.map(|(_, out_frame)| out_frame))?;
+        if out_frame <= in_frame {
+            return Err(ResolveError::invalid_parameter(
+                "out_timecode",
+                "must be after in_timecode",
+            ));
+        }
+        Ok((in_frame, out_frame))
+    }
+
+    /// Track and position of a cue on a timeline
+    fn find_cue(timeline: &Timeline, cue_id: &str) -> ResolveResult<(usize, usize)> {
+        timeline
+            .subtitle_tracks
+            .iter()
+            .enumerate()
+            .find_map(|(track, subtitle_track)| {
+                subtitle_track
+                    .cues
+                    .iter()
+                    .position(|cue| cue.id == cue_id)
+                    .map(|cue| (track, cue))
+            })
+            .ok_or_else(|| {
+                ResolveError::invalid_parameter(
+                    "cue_id",
+                    format!("no cue '{}' on timeline '{}'", cue_id, timeline.name),
+                )
+            })
+    }
+
+    fn overlap_error(cue: &SubtitleCue, rate: FrameRate, start_frame: i64) -> ResolveError {
+        ResolveError::invalid_parameter(
+            "in_timecode",
+            format!(
+                "overlaps cue '{}' from {} to {}",
+                cue.id,
+                rate.frames_to_timecode(start_frame + cue.in_frame),
+                rate.frames_to_timecode(start_frame + cue.out_frame)
+            ),
+        )
+    }
+
+    pub(super) async fn add_subtitle_track(
+        &self,
+        state: &mut StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        let (timeline_name, rate, start_frame) = self.subtitle_timeline(state, &args)?;
+        let language = match args["language"].as_str() {
+            Some(language) => Some(languages::require_known_language(language)?.code),
+            None => None,
+        };
+        let style = CueStyle::default().with_args(&args)?;
+
+        let timeline = state.timelines.get_mut(&timeline_name).ok_or_else(|| {
+            ResolveError::TimelineNotFound {
+                name: timeline_name.clone(),
+            }
+        })?;
+        let track_index = timeline.subtitle_tracks.len() + 1;
+        let track = SubtitleTrack {
+            name: args["name"]
+                .as_str()
+                .map_or_else(|| format!("Subtitle {}", track_index), str::to_string),
+            language,
+            style,
+            cues: Vec::new(),
+        };
+        let mut response = track.to_json(track_index, rate, start_frame);
+        timeline.subtitle_tracks.push(track);
+
+        response["result"] = json!(format!(
+            "Added subtitle track {} to timeline '{}'",
+            track_index, timeline_name
+        ));
+        response["timeline_name"] = json!(timeline_name);
+        response["operation_id"] = json!(Uuid::new_v4().to_string());
+        Ok(response)
+    }
+
+    pub(super) async fn add_subtitle_cue(
+        &self,
+        state: &mut StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        let text = cue_text(
+            args["text"]
+                .as_str()
+                .ok_or_else(|| ResolveError::invalid_parameter("text", "required string"))?,
+        )?;
+        let track_index = args["track_index"].as_u64().unwrap_or(1) as usize;
+        let (timeline_name, rate, start_frame) = self.subtitle_timeline(state, &args)?;
+        let (in_frame, out_frame) = Self::cue_range(&args, rate, start_frame, None)?;
+
+        let timeline = state.timelines.get_mut(&timeline_name).ok_or_else(|| {
+            ResolveError::TimelineNotFound {
+                name: timeline_name.clone(),
+            }
+        })?;
+        let track = track_index
+            .checked_sub(1)
+            .filter(|index| *index < timeline.subtitle_tracks.len())
+            .ok_or_else(|| no_track("track_index", timeline, track_index))?;
+        let track = &mut timeline.subtitle_tracks[track];
+        if let Some(cue) = track.overlapping(in_frame, out_frame, "") {
+            return Err(Self::overlap_error(cue, rate, start_frame));
+        }
+        let cue = SubtitleCue {
+            id: Uuid::new_v4().to_string(),
+            in_frame,
+            out_frame,
+            text,
+            style: track.style.with_args(&args)?,
+        };
+        let mut response = cue.to_json(track_index, rate, start_frame);
+        track.cues.push(cue);
+        track.sort_cues();
+
+        response["result"] = json!(format!(
+            "Added subtitle cue to track {} of timeline '{}'",
+            track_index, timeline_name
+        ));
+        response["timeline_name"] = json!(timeline_name);
+        response["operation_id"] = json!(Uuid::new_v4().to_string());
+        Ok(response)
+    }
+
+    pub(super) async fn update_subtitle_cue(
+        &self,
+        state: &mut StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        let cue_id = args["cue_id"]
+            .as_str()
+            .ok_or_else(|| ResolveError::invalid_parameter("cue_id", "required string"))?;
+        let (timeline_name, rate, start_frame) = self.subtitle_timeline(state, &args)?;
+        let timeline = state.timelines.get_mut(&timeline_name).ok_or_else(|| {
+            ResolveError::TimelineNotFound {
+                name: timeline_name.clone(),
+            }
+        })?;
+        let (track_index, position) = Self::find_cue(timeline, cue_id)?;
+        let track = &mut timeline.subtitle_tracks[track_index];
+
+        // Validate every change before applying any of them
+        let cue = &track.cues[position];
+        let (in_frame, out_frame) = Self::cue_range(
+            &args,
+            rate,
+            start_frame,
+            Some((cue.in_frame, cue.out_frame)),
+        )?;
+        let text = match args["text"].as_str() {
+            Some(text) => cue_text(text)?,
+            None => cue.text.clone(),
+        };
+        let style = cue.style.with_args(&args)?;
+        if let Some(other) = track.overlapping(in_frame, out_frame, cue_id) {
+            return Err(Self::overlap_error(other, rate, start_frame));
+        }
+
+        let cue = &mut track.cues[position];
+        cue.in_frame = in_frame;
+        cue.out_frame = out_frame;
+        cue.text = text;
+        cue.style = style;
+        let mut response = cue.to_json(track_index + 1, rate, start_frame);
+        track.sort_cues();
+
+        response["result"] = json!(format!("Updated subtitle cue '{}'", cue_id));
+        response["timeline_name"] = json!(timeline_name);
+        response["operation_id"] = json!(Uuid::new_v4().to_string());
+        Ok(response)
+    }
+
+    pub(super) async fn delete_subtitle_cue(
+        &self,
+        state: &mut StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        let cue_id = args["cue_id"]
+            .as_str()
+            .ok_or_else(|| ResolveError::invalid_parameter("cue_id", "required string"))?;
+        let (timeline_name, rate, start_frame) = self.subtitle_timeline(state, &args)?;
+        let timeline = state.timelines.get_mut(&timeline_name).ok_or_else(|| {
+            ResolveError::TimelineNotFound {
+                name: timeline_name.clone(),
+            }
+        })?;
+        let (track, position) = Self::find_cue(timeline, cue_id)?;
+        let cue = timeline.subtitle_tracks[track].cues.remove(position);
+
+        let mut response = cue.to_json(track + 1, rate, start_frame);
+        response["result"] = json!(format!("Deleted subtitle cue '{}'", cue_id));
+        response["timeline_name"] = json!(timeline_name);
+        response["remaining_cues"] = json!(timeline.subtitle_tracks[track].cues.len());
+        response["operation_id"] = json!(Uuid::new_v4().to_string());
+        Ok(response)
+    }
+
+    pub(super) async fn list_subtitle_cues(
+        &self,
+        state: &StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        let (timeline_name, rate, start_frame) = self.subtitle_timeline(state, &args)?;
+        let timeline = &state.timelines[&timeline_name];
+        let only_track = args["track_index"].as_u64().map(|index| index as usize);
+        if let Some(track_index) = only_track {
+            track_at(timeline, "track_index", track_index)?;
+        }
+
+        let tracks: Vec<Value> = timeline
+            .subtitle_tracks
+            .iter()
+            .enumerate()
+            .map(|(index, track)| (index + 1, track))
+            .filter(|(track_index, _)| only_track.is_none_or(|only| only == *track_index))
+            .map(|(track_index, track)| track.to_json(track_index, rate, start_frame))
+            .collect();
+        let cue_count: usize = tracks
+            .iter()
+            .map(|track| track["cue_count"].as_u64().unwrap_or(0) as usize)
+            .sum();
+
+        Ok(json!({
+            "result": format!(
+                "Found {} subtitle cues on {} tracks of timeline '{}'",
+                cue_count,
+                tracks.len(),
+                timeline_name
+            ),
+            "timeline_name": timeline_name,
+            "tracks": tracks,
+            "cue_count": cue_count,
+            "operation_id": Uuid::new_v4().to_string()
+        }))
+    }
+}
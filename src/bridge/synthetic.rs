@@ -0,0 +1,116 @@
+//! Large synthetic states for benchmarks
+//!
+//! Only built with the `bench` feature. Seeding writes straight into the
+//! state, skipping per-call validation and the resource limits, so a project
+//! with thousands of clips and timelines is ready in milliseconds.
+
+use uuid::Uuid;
+
+use super::{
+    Bin, Clip, ItemPlacement, LockPlan, MediaPool, ResolveBridge, Timeline, TimelineItemState,
+};
+
+/// Clips per synthetic bin
+const CLIPS_PER_BIN: usize = 100;
+
+/// Items laid end to end on each synthetic timeline
+const ITEMS_PER_TIMELINE: usize = 10;
+
+/// Length of each synthetic timeline item, in frames
+const ITEM_FRAMES: i64 = 240;
+
+impl ResolveBridge {
+    /// Replace the state with a project named `Synthetic` holding `clips` media
+    /// pool clips in bins of 100 and `timelines` timelines of ten items each,
+    /// drawn from the clips in turn. The last timeline is made current.
+    pub async fn seed_synthetic_state(&self, clips: usize, timelines: usize) {
+        let mut state = self.state.lock(LockPlan::ALL).await;
+
+        let mut media_pool = MediaPool {
+            bins: Default::default(),
+            clips: Default::default(),
+            synced_clips: Default::default(),
+        };
+        let clip_names: Vec<String> = (0..clips).map(|n| format!("clip_{:05}.mov", n)).collect();
+        for (n, name) in clip_names.iter().enumerate() {
+            let bin_name = format!("Bin {:03}", n / CLIPS_PER_BIN);
+            media_pool
+                .bins
+                .entry(bin_name.clone())
+                .or_insert_with(|| Bin {
+                    id: Uuid::new_v4().to_string(),
+                    name: bin_name.clone(),
+                    clips: Vec::new(),
+                    color_rules: Vec::new(),
+                })
+                .clips
+                .push(name.clone());
+            media_pool.clips.insert(
+                name.clone(),
+                Clip {
+                    id: Uuid::new_v4().to_string(),
+                    name: name.clone(),
+                    file_path: format!("/synthetic/media/{}", name),
+                    bin: Some(bin_name),
+                    linked: true,
+                    proxy_path: None,
+                    start_timecode: None,
+                    reel_name: None,
+                    frame_rate: None,
+                    color: Default::default(),
+                    metadata: Default::default(),
+                    marks: Default::default(),
+                },
+            );
+        }
+
+        state.projects.push("Synthetic".to_string());
+        *state.current_project = Some("Synthetic".to_string());
+        *state.media_pool = media_pool;
+        state.timelines.clear();
+        state.timeline_items.items.clear();
+
+        let mut clip_cycle = clip_names.iter().cycle();
+        for t in 0..timelines {
+            let timeline_name = format!("Timeline {:04}", t + 1);
+            state.timelines.insert(
+                timeline_name.clone(),
+                Timeline {
+                    id: Uuid::new_v4().to_string(),
+                    name: timeline_name.clone(),
+                    frame_rate: None,
+                    start_timecode: None,
+                    resolution_width: None,
+                    resolution_height: None,
+                    markers: Vec::new(),
+                    playhead: None,
+                    subtitle_tracks: Vec::new(),
+                    lineage: None,
+                    output: Default::default(),
+                },
+            );
+            for i in 0..ITEMS_PER_TIMELINE {
+                let Some(clip_name) = clip_cycle.next() else {
+                    break;
+                };
+                let item_id = Uuid::new_v4().to_string();
+                state.timeline_items.item_counter += 1;
+                let item = TimelineItemState {
+                    id: item_id.clone(),
+                    timeline_name: timeline_name.clone(),
+                    clip_name: clip_name.clone(),
+                    clip_id: Some(state.media_pool.clips[clip_name].id.clone()),
+                    placement: Some(ItemPlacement {
+                        track_index: 1,
+                        record_in: i as i64 * ITEM_FRAMES,
+                        source_in: 0,
+                        duration: ITEM_FRAMES,
+                    }),
+                    ..Default::default()
+                };
+                state.timeline_items.items.insert(item_id, item);
+            }
+            *state.current_timeline = Some(timeline_name);
+        }
+    }
+}
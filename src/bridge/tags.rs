@@ -0,0 +1,249 @@
+//! User-defined tags on clips and timeline items
+//!
+//! Resolve only offers a single clip color, so tags live in the bridge state.
+//! Tags are trimmed and lowercased so `B-Roll` and `b-roll` are one tag.
+
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, BTreeSet};
+use uuid::Uuid;
+
+use super::rename_batch::{rekey, Renames};
+use super::{ResolveBridge, StateView};
+use crate::error::{ResolveError, ResolveResult};
+
+/// Tags by clip name and by timeline item ID
+#[derive(Debug, Clone, Default, Serialize)]
+pub(super) struct TagState {
+    clips: BTreeMap<String, BTreeSet<String>>,
+    timeline_items: BTreeMap<String, BTreeSet<String>>,
+}
+
+impl TagState {
+    /// Keep the tags of renamed clips with them
+    pub(super) fn rename_clips(&mut self, renames: &Renames) {
+        rekey(&mut self.clips, renames);
+    }
+}
+
+/// What a tag request applies to
+enum TagTarget {
+    Clip(String),
+    TimelineItem(String),
+}
+
+impl TagTarget {
+    fn from_args(state: &StateView<'_>, args: &Value) -> ResolveResult<Self> {
+        match (
+            args["clip_name"].as_str(),
+            args["timeline_item_id"].as_str(),
+        ) {
+            (Some(clip_name), None) => {
+                Ok(Self::Clip(ResolveBridge::resolve_clip(state, clip_name)?))
+            }
+            (None, Some(item_id)) => {
+                if !state.timeline_items.items.contains_key(item_id) {
+                    return Err(ResolveError::invalid_parameter(
+                        "timeline_item_id",
+                        format!("timeline item '{}' not found", item_id),
+                    ));
+                }
+                Ok(Self::TimelineItem(item_id.to_string()))
+            }
+            _ => Err(ResolveError::invalid_parameter(
+                "clip_name",
+                "exactly one of clip_name or timeline_item_id is required",
+            )),
+        }
+    }
+
+    fn tags_mut<'a>(&self, tags: &'a mut TagState) -> &'a mut BTreeMap<String, BTreeSet<String>> {
+        match self {
+            Self::Clip(_) => &mut tags.clips,
+            Self::TimelineItem(_) => &mut tags.timeline_items,
+        }
+    }
+
+    fn key(&self) -> &str {
+        match self {
+            Self::Clip(name) | Self::TimelineItem(name) => name,
+        }
+    }
+
+    fn describe(&self) -> String {
+        match self {
+            Self::Clip(name) => format!("clip '{}'", name),
+            Self::TimelineItem(id) => format!("timeline item '{}'", id),
+        }
+    }
+}
+
+/// Read and normalize the `tags` argument
+fn parse_tags(args: &Value) -> ResolveResult<Option<BTreeSet<String>>> {
+    let Some(tags) = args["tags"].as_array() else {
+        return Ok(None);
+    };
+    let tags = tags
+        .iter()
+        .map(|tag| {
+            tag.as_str()
+                .map(|tag| tag.trim().to_lowercase())
+                .filter(|tag| !tag.is_empty())
+                .ok_or_else(|| {
+                    ResolveError::invalid_parameter("tags", "tags must be non-empty strings")
+                })
+        })
+        .collect::<ResolveResult<BTreeSet<String>>>()?;
+    if tags.is_empty() {
+        return Err(ResolveError::invalid_parameter(
+            "tags",
+            "at least one tag is required",
+        ));
+    }
+    Ok(Some(tags))
+}
+
+impl ResolveBridge {
+    pub(super) async fn tag_clip(
+        &self,
+        state: &mut StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        let tags = parse_tags(&args)?
+            .ok_or_else(|| ResolveError::invalid_parameter("tags", "required array"))?;
+        let target = TagTarget::from_args(state, &args)?;
+
+        let current = target
+            .tags_mut(&mut state.tags)
+            .entry(target.key().to_string())
+            .or_default();
+        let added: Vec<String> = tags
+            .into_iter()
+            .filter(|tag| current.insert(tag.clone()))
+            .collect();
+
+        Ok(json!({
+            "result": format!("Added {} tags to {}", added.len(), target.describe()),
+            "added": added,
+            "tags": current.iter().collect::<Vec<_>>(),
+            "operation_id": Uuid::new_v4().to_string()
+        }))
+    }
+
+    pub(super) async fn untag_clip(
+        &self,
+        state: &mut StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        // Without a tag list every tag is removed
+        let tags = parse_tags(&args)?;
+        let target = TagTarget::from_args(state, &args)?;
+
+        let map = target.tags_mut(&mut state.tags);
+        let current = map.remove(target.key()).unwrap_or_default();
+        let (removed, kept): (BTreeSet<String>, BTreeSet<String>) = current
+            .into_iter()
+            .partition(|tag| tags.as_ref().is_none_or(|tags| tags.contains(tag)));
+        if !kept.is_empty() {
+            map.insert(target.key().to_string(), kept.clone());
+        }
+
+        Ok(json!({
+            "result": format!("Removed {} tags from {}", removed.len(), target.describe()),
+            "removed": removed,
+            "tags": kept,
+            "operation_id": Uuid::new_v4().to_string()
+        }))
+    }
+
+    pub(super) async fn find_by_tag(
+        &self,
+        state: &StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        let tags = parse_tags(&args)?
+            .ok_or_else(|| ResolveError::invalid_parameter("tags", "required array"))?;
+        let match_all = match args["match"].as_str().unwrap_or("any") {
+            "any" => false,
+            "all" => true,
+            _ => {
+                return Err(ResolveError::invalid_parameter(
+                    "match",
+                    "must be 'any' or 'all'",
+                ))
+            }
+        };
+        let (clips_wanted, items_wanted) = match args["target_type"].as_str().unwrap_or("all") {
+            "all" => (true, true),
+            "clip" => (true, false),
+            "timeline_item" => (false, true),
+            _ => {
+                return Err(ResolveError::invalid_parameter(
+                    "target_type",
+                    "must be 'clip', 'timeline_item' or 'all'",
+                ))
+            }
+        };
+        let matches = |tagged: &BTreeSet<String>| {
+            if match_all {
+                tags.is_subset(tagged)
+            } else {
+                !tags.is_disjoint(tagged)
+            }
+        };
+
+        // Tags on clips or items that have since been removed are ignored
+        let clips: Vec<Value> = if clips_wanted {
+            state
+                .tags
+                .clips
+                .iter()
+                .filter(|(name, tagged)| {
+                    state.media_pool.clips.contains_key(*name) && matches(tagged)
+                })
+                .map(|(name, tagged)| {
+                    json!({
+                        "clip_name": name,
+                        "file_path": state.media_pool.clips[name].file_path,
+                        "tags": tagged
+                    })
+                })
+                .collect()
+        } else {
+            Vec::new()
+        };
+        let timeline_items: Vec<Value> = if items_wanted {
+            state
+                .tags
+                .timeline_items
+                .iter()
+                .filter_map(|(id, tagged)| {
+                    let item = state.timeline_items.items.get(id)?;
+                    matches(tagged).then(|| {
+                        json!({
+                            "timeline_item_id": id,
+                            "timeline_name": item.timeline_name,
+                            "clip_name": item.clip_name,
+                            "tags": tagged
+                        })
+                    })
+                })
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        Ok(json!({
+            "result": format!(
+                "Found {} clips and {} timeline items tagged {}",
+                clips.len(),
+                timeline_items.len(),
+                tags.iter().cloned().collect::<Vec<_>>().join(if match_all { " and " } else { " or " })
+            ),
+            "clips": clips,
+            "timeline_items": timeline_items,
+            "count": clips.len() + timeline_items.len(),
+            "operation_id": Uuid::new_v4().to_string()
+        }))
+    }
+}
@@ -0,0 +1,259 @@
+//! Timeline export formats
+//!
+//! export_timeline takes a format and an optional subtype by our own names,
+//! checked against a compatibility table before anything runs, so a subtype
+//! that does not belong to its format is refused in both modes alike. Each
+//! row names the `resolve.EXPORT_*` constants Timeline.Export wants; FCPXML
+//! versions are separate export types in Resolve but subtypes here.
+//!
+//! AAF exports either link the existing media or carry new media. EDLs can
+//! carry CDL or SDL data, or list only the clips missing from the media pool.
+//! Formats without a subtype refuse one.
+
+use serde_json::{json, Value};
+
+use super::{ResolveBridge, SCRIPT_PROJECT};
+use crate::error::{ResolveError, ResolveResult};
+
+/// One export type and subtype Resolve can write
+struct ExportFormat {
+    export_type: &'static str,
+    /// Subtype name and its accepted aliases; None for formats without one
+    subtype: Option<&'static [&'static str]>,
+    /// Used when no subtype is given
+    default: bool,
+    resolve_type: &'static str,
+    resolve_subtype: Option<&'static str>,
+    extension: &'static str,
+}
+
+const fn format(
+    export_type: &'static str,
+    subtype: Option<&'static [&'static str]>,
+    default: bool,
+    resolve_type: &'static str,
+    resolve_subtype: Option<&'static str>,
+    extension: &'static str,
+) -> ExportFormat {
+    ExportFormat {
+        export_type,
+        subtype,
+        default,
+        resolve_type,
+        resolve_subtype,
+        extension,
+    }
+}
+
+/// Every supported combination, the default subtype of each type first
+const FORMATS: &[ExportFormat] = &[
+    format(
+        "AAF",
+        Some(&["linked", "existing"]),
+        true,
+        "EXPORT_AAF",
+        Some("EXPORT_AAF_EXISTING"),
+        "aaf",
+    ),
+    format(
+        "AAF",
+        Some(&["embedded", "new"]),
+        false,
+        "EXPORT_AAF",
+        Some("EXPORT_AAF_NEW"),
+        "aaf",
+    ),
+    format(
+        "EDL",
+        Some(&["none"]),
+        true,
+        "EXPORT_EDL",
+        Some("EXPORT_NONE"),
+        "edl",
+    ),
+    format(
+        "EDL",
+        Some(&["cdl"]),
+        false,
+        "EXPORT_EDL",
+        Some("EXPORT_CDL"),
+        "edl",
+    ),
+    format(
+        "EDL",
+        Some(&["sdl"]),
+        false,
+        "EXPORT_EDL",
+        Some("EXPORT_SDL"),
+        "edl",
+    ),
+    format(
+        "EDL",
+        Some(&["missing_clips"]),
+        false,
+        "EXPORT_EDL",
+        Some("EXPORT_MISSING_CLIPS"),
+        "edl",
+    ),
+    format("XML", None, true, "EXPORT_FCP_7_XML", None, "xml"),
+    format(
+        "FCPXML",
+        Some(&["1.10"]),
+        true,
+        "EXPORT_FCPXML_1_10",
+        None,
+        "fcpxml",
+    ),
+    format(
+        "FCPXML",
+        Some(&["1.9"]),
+        false,
+        "EXPORT_FCPXML_1_9",
+        None,
+        "fcpxml",
+    ),
+    format(
+        "FCPXML",
+        Some(&["1.8"]),
+        false,
+        "EXPORT_FCPXML_1_8",
+        None,
+        "fcpxml",
+    ),
+    format("DRT", None, true, "EXPORT_DRT", None, "drt"),
+    format("OTIO", None, true, "EXPORT_OTIO", None, "otio"),
+    format("CSV", None, true, "EXPORT_TEXT_CSV", None, "csv"),
+    format("TAB", None, true, "EXPORT_TEXT_TAB", None, "txt"),
+];
+
+/// Supported subtypes of an export type, for error messages
+fn subtypes_of(export_type: &str) -> Vec<&'static str> {
+    FORMATS
+        .iter()
+        .filter(|format| format.export_type == export_type)
+        .filter_map(|format| format.subtype.map(|names| names[0]))
+        .collect()
+}
+
+/// The table row for a type and subtype, matched case-insensitively
+fn lookup(export_type: &str, subtype: Option<&str>) -> ResolveResult<&'static ExportFormat> {
+    let mut rows = FORMATS
+        .iter()
+        .filter(|format| format.export_type.eq_ignore_ascii_case(export_type))
+        .peekable();
+    let Some(first) = rows.peek() else {
+        let mut types: Vec<&str> = FORMATS.iter().map(|format| format.export_type).collect();
+        types.dedup();
+        return Err(ResolveError::invalid_parameter(
+            "export_type",
+            format!("must be one of {}", types.join(", ")),
+        ));
+    };
+    let export_type = first.export_type;
+    let Some(subtype) = subtype else {
+        return Ok(rows
+            .find(|format| format.default)
+            .expect("every export type has a default row"));
+    };
+    if first.subtype.is_none() {
+        return Err(ResolveError::invalid_parameter(
+            "export_subtype",
+            format!("{} exports take no subtype", export_type),
+        ));
+    }
+    rows.find(|format| {
+        format
+            .subtype
+            .is_some_and(|names| names.iter().any(|name| name.eq_ignore_ascii_case(subtype)))
+    })
+    .ok_or_else(|| {
+        ResolveError::invalid_parameter(
+            "export_subtype",
+            format!(
+                "{} exports support {}",
+                export_type,
+                subtypes_of(export_type).join(", ")
+            ),
+        )
+    })
+}
+
+/// Exports the timeline named in `request`, or the current one
+const EXPORT: &str = r#"
+    timeline = project.GetCurrentTimeline()
+    if request["timeline_name"]:
+        timelines = (project.GetTimelineByIndex(index) for index in range(1, project.GetTimelineCount() + 1))
+        timeline = next((found for found in timelines if found.GetName() == request["timeline_name"]), None)
+    if not timeline:
+        fail("Timeline not found: " + str(request["timeline_name"] or "no current timeline"))
+
+    for constant in filter(None, [request["resolve_type"], request["resolve_subtype"]]):
+        if not hasattr(resolve, constant):
+            fail("This version of Resolve cannot export " + constant)
+    arguments = [request["file_name"], getattr(resolve, request["resolve_type"])]
+    if request["resolve_subtype"]:
+        arguments.append(getattr(resolve, request["resolve_subtype"]))
+    if not timeline.Export(*arguments):
+        fail("Resolve could not export the timeline to " + request["file_name"])
+    print(json.dumps({"success": True, "timeline_name": timeline.GetName()}))
+"#;
+
+/// Response of an export, in both modes
+fn exported(
+    format: &ExportFormat,
+    timeline_name: Option<&str>,
+    file_name: &str,
+) -> ResolveResult<Value> {
+    let subtype = format.subtype.map(|names| names[0]);
+    Ok(json!({
+        "result": match subtype {
+            Some(subtype) => format!(
+                "Exported timeline as {} ({}) to {}",
+                format.export_type, subtype, file_name
+            ),
+            None => format!("Exported timeline as {} to {}", format.export_type, file_name),
+        },
+        "timeline_name": timeline_name,
+        "file_name": file_name,
+        "export_type": format.export_type,
+        "export_subtype": subtype,
+        "resolve_export_type": format.resolve_type,
+        "resolve_export_subtype": format.resolve_subtype,
+        "expected_extension": format.extension,
+        "status": "success"
+    }))
+}
+
+impl ResolveBridge {
+    /// Check an export against the compatibility table and describe it
+    pub(super) fn timeline_export(&self, args: &Value) -> ResolveResult<Value> {
+        let file_name = args["file_name"]
+            .as_str()
+            .ok_or_else(|| ResolveError::invalid_parameter("file_name", "parameter is required"))?;
+        let export_type = args["export_type"].as_str().ok_or_else(|| {
+            ResolveError::invalid_parameter("export_type", "parameter is required")
+        })?;
+        let format = lookup(export_type, args["export_subtype"].as_str())?;
+        exported(format, args["timeline_name"].as_str(), file_name)
+    }
+
+    /// Export a timeline from the running Resolve
+    pub(super) fn real_export_timeline(&self, args: &Value) -> ResolveResult<Value> {
+        let file_name = args["file_name"]
+            .as_str()
+            .ok_or_else(|| ResolveError::invalid_parameter("file_name", "parameter is required"))?;
+        let export_type = args["export_type"].as_str().ok_or_else(|| {
+            ResolveError::invalid_parameter("export_type", "parameter is required")
+        })?;
+        let format = lookup(export_type, args["export_subtype"].as_str())?;
+        let request = json!({
+            "timeline_name": args["timeline_name"],
+            "file_name": file_name,
+            "resolve_type": format.resolve_type,
+            "resolve_subtype": format.resolve_subtype
+        });
+        let script = Self::resolve_script("", SCRIPT_PROJECT, &request, EXPORT)?;
+        let output = self.run_resolve_script("export_timeline", &script)?;
+        exported(format, output["timeline_name"].as_str(), file_name)
+    }
+}
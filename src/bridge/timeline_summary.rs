@@ -0,0 +1,189 @@
+//! Runtime, track and gap summary of a timeline
+//!
+//! The sanity check editors run before delivery: how long the timeline runs,
+//! what sits on each video track, how many media pool clips it uses, which of
+//! them run at a different frame rate than the timeline, and where tracks have
+//! holes. Gaps are found between items on each track; stretches that no track
+//! covers render as black and are also reported for the timeline as a whole.
+
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use uuid::Uuid;
+
+use super::item_lookup::timeline_clock;
+use super::{Clip, ItemPlacement, ResolveBridge, StateView};
+use crate::error::ResolveResult;
+use crate::timecode::FrameRate;
+
+/// Frames between placements that nothing covers, given sorted by record in
+fn gaps<'a>(placements: impl IntoIterator<Item = &'a ItemPlacement>) -> Vec<(i64, i64)> {
+    let mut gaps = Vec::new();
+    let mut reach: Option<i64> = None;
+    for placement in placements {
+        if let Some(end) = reach.filter(|end| placement.record_in > *end) {
+            gaps.push((end, placement.record_in));
+        }
+        reach = Some(reach.map_or(placement.record_out(), |end| {
+            end.max(placement.record_out())
+        }));
+    }
+    gaps
+}
+
+impl ResolveBridge {
+    /// Frame rate of a clip's media, falling back to the project frame rate
+    pub(super) fn clip_frame_rate(&self, clip: &Clip) -> String {
+        clip.frame_rate
+            .clone()
+            .unwrap_or_else(|| self.config.resolve.default_project.frame_rate.clone())
+    }
+
+    pub(super) async fn get_timeline_summary(
+        &self,
+        state: &StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        let timeline_name = Self::resolve_timeline_name(state, &args)?;
+        let (rate, start_frame) = timeline_clock(self, state, &timeline_name, &args)?;
+        let timeline_rate = state.timelines[&timeline_name]
+            .frame_rate
+            .clone()
+            .unwrap_or_else(|| self.config.resolve.default_project.frame_rate.clone());
+        let timecode = |frame: i64| rate.frames_to_timecode(start_frame + frame);
+        let describe_gaps = |gaps: &[(i64, i64)]| {
+            gaps.iter()
+                .map(|&(start, end)| {
+                    json!({
+                        "start": timecode(start),
+                        "end": timecode(end),
+                        "duration": rate.frames_to_timecode(end - start),
+                        "frames": end - start
+                    })
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let items: Vec<_> = state
+            .timeline_items
+            .items
+            .values()
+            .filter(|item| item.timeline_name == timeline_name)
+            .collect();
+        let mut placements: Vec<&ItemPlacement> = items
+            .iter()
+            .filter_map(|item| item.placement.as_ref())
+            .collect();
+        placements.sort_by_key(|placement| (placement.record_in, placement.track_index));
+        let end = placements
+            .iter()
+            .map(|placement| placement.record_out())
+            .max()
+            .unwrap_or(0);
+
+        let mut by_track: BTreeMap<u32, Vec<&ItemPlacement>> = BTreeMap::new();
+        for placement in &placements {
+            by_track
+                .entry(placement.track_index)
+                .or_default()
+                .push(placement);
+        }
+        let tracks: Vec<Value> = by_track
+            .iter()
+            .map(|(track_index, placements)| {
+                let track_gaps = gaps(placements.iter().copied());
+                let track_out = placements
+                    .iter()
+                    .map(|placement| placement.record_out())
+                    .max()
+                    .unwrap_or(0);
+                json!({
+                    "track": format!("V{}", track_index),
+                    "track_type": "video",
+                    "item_count": placements.len(),
+                    "record_in": timecode(placements[0].record_in),
+                    "record_out": timecode(track_out),
+                    "gap_count": track_gaps.len(),
+                    "gaps": describe_gaps(&track_gaps)
+                })
+            })
+            .collect();
+        let timeline_gaps = gaps(placements.iter().copied());
+
+        // Clips from the media pool; generators and offline items have none
+        let mut clips: BTreeMap<&str, &Clip> = BTreeMap::new();
+        let mut generator_items = 0;
+        let mut offline_items = 0;
+        for item in &items {
+            if item.generator.is_some() {
+                generator_items += 1;
+            } else if let Some(clip) = state.media_pool.clips.get(&item.clip_name) {
+                clips.insert(&item.clip_name, clip);
+            } else {
+                offline_items += 1;
+            }
+        }
+        let mixed_frame_rates: Vec<Value> = clips
+            .iter()
+            .filter_map(|(name, clip)| {
+                let clip_rate = self.clip_frame_rate(clip);
+                let differs = FrameRate::parse(&clip_rate)
+                    .is_ok_and(|clip_fps| (clip_fps.fps() - rate.fps()).abs() > 0.001);
+                differs.then(|| json!({ "clip_name": name, "frame_rate": clip_rate }))
+            })
+            .collect();
+
+        let mut warnings: Vec<String> = mixed_frame_rates
+            .iter()
+            .map(|clip| {
+                format!(
+                    "Clip '{}' is {} fps on a {} fps timeline",
+                    clip["clip_name"].as_str().unwrap_or_default(),
+                    clip["frame_rate"].as_str().unwrap_or_default(),
+                    timeline_rate
+                )
+            })
+            .collect();
+        if !timeline_gaps.is_empty() {
+            let frames: i64 = timeline_gaps.iter().map(|(start, end)| end - start).sum();
+            warnings.push(format!(
+                "{} gap(s) totalling {} with no picture on any track",
+                timeline_gaps.len(),
+                rate.frames_to_timecode(frames)
+            ));
+        }
+        if offline_items > 0 {
+            warnings.push(format!(
+                "{} item(s) use clips that are not in the media pool",
+                offline_items
+            ));
+        }
+
+        Ok(json!({
+            "result": format!(
+                "Timeline '{}' runs {} with {} items on {} tracks and {} warning(s)",
+                timeline_name,
+                rate.frames_to_timecode(end),
+                items.len(),
+                tracks.len(),
+                warnings.len()
+            ),
+            "timeline_name": timeline_name,
+            "frame_rate": timeline_rate,
+            "start": timecode(0),
+            "end": timecode(end),
+            "total_runtime": rate.frames_to_timecode(end),
+            "total_frames": end,
+            "item_count": items.len(),
+            "unplaced_items": items.len() - placements.len(),
+            "generator_items": generator_items,
+            "offline_items": offline_items,
+            "used_clip_count": clips.len(),
+            "used_clips": clips.keys().collect::<Vec<_>>(),
+            "tracks": tracks,
+            "gaps": describe_gaps(&timeline_gaps),
+            "mixed_frame_rates": mixed_frame_rates,
+            "warnings": warnings,
+            "operation_id": Uuid::new_v4().to_string()
+        }))
+    }
+}
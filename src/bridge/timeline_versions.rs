@@ -0,0 +1,320 @@
+//! Timeline versions
+//!
+//! Editors version a cut by duplicating its timeline under the next version
+//! suffix, `Trailer v001` becoming `Trailer v002`, and keep working on the
+//! copy. Each version records the timeline it came from, who made it and why,
+//! so the versions of a cut form a tree that can be walked back to the first.
+//!
+//! The suffix is a `v` and a number at the end of the name, after a space, an
+//! underscore or a dash; its separator and padding carry over to the next
+//! version. A timeline without one counts as version 1. The next number is
+//! one past the highest taken by any timeline of the same name, so versioning
+//! an older cut never collides with a newer one.
+
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use super::{ensure_capacity, ResolveBridge, StateView, Timeline};
+use crate::error::{ResolveError, ResolveResult};
+
+/// Padding of version numbers on timelines named without one
+const DEFAULT_PADDING: usize = 3;
+
+/// Where a timeline version came from
+#[derive(Debug, Clone, Serialize)]
+pub(super) struct Lineage {
+    /// ID of the timeline this version was made from
+    parent_id: String,
+    /// Name of that timeline when this version was made
+    parent_name: String,
+    version: u32,
+    author: Option<String>,
+    note: Option<String>,
+    created_at: String,
+}
+
+/// A timeline name split at its version suffix
+struct VersionName<'a> {
+    base: &'a str,
+    /// Separator before the `v`, kept for the next version
+    separator: &'a str,
+    version: Option<u32>,
+    padding: usize,
+}
+
+impl<'a> VersionName<'a> {
+    fn parse(name: &'a str) -> Self {
+        let unversioned = Self {
+            base: name,
+            separator: "_",
+            version: None,
+            padding: DEFAULT_PADDING,
+        };
+        let digits = name.len() - name.trim_end_matches(|c: char| c.is_ascii_digit()).len();
+        let before_digits = &name[..name.len() - digits];
+        let Some(before_v) = before_digits
+            .strip_suffix('v')
+            .or_else(|| before_digits.strip_suffix('V'))
+        else {
+            return unversioned;
+        };
+        let Some(separator) = [" ", "_", "-"]
+            .into_iter()
+            .find(|separator| before_v.ends_with(separator))
+        else {
+            return unversioned;
+        };
+        let base = &before_v[..before_v.len() - separator.len()];
+        match name[name.len() - digits..].parse() {
+            Ok(version) if digits > 0 && !base.trim().is_empty() => Self {
+                base,
+                separator,
+                version: Some(version),
+                padding: digits,
+            },
+            _ => unversioned,
+        }
+    }
+
+    /// Version of the timeline, counting an unversioned name as 1
+    fn number(&self) -> u32 {
+        self.version.unwrap_or(1)
+    }
+
+    fn with_version(&self, version: u32) -> String {
+        format!(
+            "{}{}v{:0width$}",
+            self.base,
+            self.separator,
+            version,
+            width = self.padding
+        )
+    }
+}
+
+/// Version number of a timeline, from its lineage or its name
+fn version_of(timeline: &Timeline) -> u32 {
+    timeline.lineage.as_ref().map_or_else(
+        || VersionName::parse(&timeline.name).number(),
+        |lineage| lineage.version,
+    )
+}
+
+/// Current name of the timeline `timeline` was versioned from, if it still exists
+pub(super) fn parent_timeline(state: &StateView<'_>, timeline: &Timeline) -> Option<String> {
+    let lineage = timeline.lineage.as_ref()?;
+    state
+        .timelines
+        .iter()
+        .find(|(_, parent)| parent.id == lineage.parent_id)
+        .map(|(name, _)| name.clone())
+}
+
+/// Give timeline `to` its own copy of every item on timeline `from` and the
+/// item's keyframes, returning the ID of each item and of its copy
+pub(super) fn copy_timeline_items(
+    state: &mut StateView<'_>,
+    from: &str,
+    to: &str,
+) -> Vec<(String, String)> {
+    let mut items: Vec<_> = state
+        .timeline_items
+        .items
+        .values()
+        .filter(|item| item.timeline_name == from)
+        .cloned()
+        .collect();
+    items.sort_by(|a, b| a.id.cmp(&b.id));
+    let mut copies = Vec::with_capacity(items.len());
+    for mut item in items {
+        let parent_item_id = std::mem::replace(&mut item.id, Uuid::new_v4().to_string());
+        item.timeline_name = to.to_string();
+        let keyframe_state = &mut *state.keyframe_state;
+        if let Some(mut keyframes) = keyframe_state
+            .timeline_item_keyframes
+            .get(&parent_item_id)
+            .cloned()
+        {
+            keyframes.timeline_item_id = item.id.clone();
+            for keyframe in keyframes.property_keyframes.values_mut().flatten() {
+                keyframe_state.keyframe_counter += 1;
+                keyframe.id = keyframe_state.keyframe_counter;
+            }
+            keyframe_state
+                .timeline_item_keyframes
+                .insert(item.id.clone(), keyframes);
+        }
+        copies.push((parent_item_id, item.id.clone()));
+        state.timeline_items.item_counter += 1;
+        state.timeline_items.items.insert(item.id.clone(), item);
+    }
+    copies
+}
+
+impl ResolveBridge {
+    pub(super) async fn create_timeline_version(
+        &self,
+        state: &mut StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        let parent_name = Self::resolve_timeline_name(state, &args)?;
+        let name = VersionName::parse(&parent_name);
+        let same_base = |timeline: &Timeline| {
+            let other = VersionName::parse(&timeline.name);
+            other.base == name.base && (other.version.is_some() || timeline.name == name.base)
+        };
+        let version = state
+            .timelines
+            .values()
+            .filter(|timeline| same_base(timeline))
+            .map(version_of)
+            .max()
+            .unwrap_or(1)
+            .checked_add(1)
+            .ok_or_else(|| ResolveError::invalid_parameter("timeline_name", "no version left"))?;
+        let new_name = name.with_version(version);
+        if state.timelines.contains_key(&new_name) {
+            return Err(ResolveError::invalid_parameter(
+                "timeline_name",
+                format!("timeline '{}' already exists", new_name),
+            ));
+        }
+        ensure_capacity(
+            "timelines",
+            state.timelines.len(),
+            1,
+            self.config.limits.max_timelines,
+        )?;
+
+        let parent = &state.timelines[&parent_name];
+        let lineage = Lineage {
+            parent_id: parent.id.clone(),
+            parent_name: parent_name.clone(),
+            version,
+            author: args["author"].as_str().map(str::to_string),
+            note: args["note"].as_str().map(str::to_string),
+            created_at: chrono::Utc::now().to_rfc3339(),
+        };
+        let mut timeline = parent.clone();
+        timeline.id = Uuid::new_v4().to_string();
+        timeline.name = new_name.clone();
+        timeline.lineage = Some(lineage.clone());
+        let timeline_id = timeline.id.clone();
+        state.timelines.insert(new_name.clone(), timeline);
+
+        let item_count = copy_timeline_items(state, &parent_name, &new_name).len();
+        *state.current_timeline = Some(new_name.clone());
+
+        Ok(json!({
+            "result": format!(
+                "Created '{}' from '{}' with {} items",
+                new_name, parent_name, item_count
+            ),
+            "timeline_name": new_name,
+            "timeline_id": timeline_id,
+            "version": version,
+            "parent_timeline": parent_name,
+            "parent_timeline_id": lineage.parent_id,
+            "author": lineage.author,
+            "note": lineage.note,
+            "created_at": lineage.created_at,
+            "item_count": item_count,
+            "operation_id": Uuid::new_v4().to_string()
+        }))
+    }
+
+    pub(super) async fn list_timeline_versions(
+        &self,
+        state: &mut StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        let timeline_name = Self::resolve_timeline_name(state, &args)?;
+        let by_id: HashMap<&str, &Timeline> = state
+            .timelines
+            .values()
+            .map(|timeline| (timeline.id.as_str(), timeline))
+            .collect();
+        let parent_of = |timeline: &Timeline| {
+            timeline
+                .lineage
+                .as_ref()
+                .and_then(|lineage| by_id.get(lineage.parent_id.as_str()).copied())
+        };
+
+        // Walk up to the first version; a deleted parent ends the walk
+        let mut root = &state.timelines[&timeline_name];
+        let mut seen = vec![root.id.as_str()];
+        while let Some(parent) = parent_of(root) {
+            if seen.contains(&parent.id.as_str()) {
+                break;
+            }
+            seen.push(parent.id.as_str());
+            root = parent;
+        }
+
+        let mut children: HashMap<&str, Vec<&Timeline>> = HashMap::new();
+        for timeline in state.timelines.values() {
+            if let Some(parent) = parent_of(timeline) {
+                children
+                    .entry(parent.id.as_str())
+                    .or_default()
+                    .push(timeline);
+            }
+        }
+        for versions in children.values_mut() {
+            versions.sort_by_key(|timeline| (version_of(timeline), timeline.name.clone()));
+        }
+
+        fn node(
+            timeline: &Timeline,
+            children: &HashMap<&str, Vec<&Timeline>>,
+            versions: &mut Vec<Value>,
+            depth: usize,
+        ) -> Value {
+            let lineage = timeline.lineage.as_ref();
+            let mut entry = json!({
+                "timeline_name": timeline.name,
+                "timeline_id": timeline.id,
+                "version": version_of(timeline),
+                "parent_timeline": lineage.map(|lineage| &lineage.parent_name),
+                "author": lineage.and_then(|lineage| lineage.author.as_ref()),
+                "note": lineage.and_then(|lineage| lineage.note.as_ref()),
+                "created_at": lineage.map(|lineage| &lineage.created_at),
+                "depth": depth
+            });
+            versions.push(entry.clone());
+            let branches: Vec<Value> = children
+                .get(timeline.id.as_str())
+                .into_iter()
+                .flatten()
+                .map(|child| node(child, children, versions, depth + 1))
+                .collect();
+            entry["children"] = json!(branches);
+            entry
+        }
+        let mut versions = Vec::new();
+        let tree = node(root, &children, &mut versions, 0);
+        let latest = versions
+            .iter()
+            .max_by_key(|version| version["version"].as_u64())
+            .map(|version| version["timeline_name"].clone());
+
+        Ok(json!({
+            "result": format!(
+                "'{}' has {} versions starting from '{}'",
+                timeline_name,
+                versions.len(),
+                root.name
+            ),
+            "timeline_name": timeline_name,
+            "root_timeline": root.name,
+            "latest_timeline": latest,
+            "version_count": versions.len(),
+            "versions": versions,
+            "tree": tree,
+            "operation_id": Uuid::new_v4().to_string()
+        }))
+    }
+}
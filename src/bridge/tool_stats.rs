@@ -0,0 +1,112 @@
+//! Tool usage statistics
+//!
+//! Next to the audit log, every tool call is counted by tool from the moment
+//! the server starts: how often it ran, how often it failed and how long it
+//! took. Operators read the summary through the `resolve://stats/tools`
+//! resource to see which tools agents rely on and which ones fail them.
+//!
+//! Medians are taken over the most recent calls of each tool, so a server
+//! that runs for weeks keeps a bounded number of latency samples.
+
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, VecDeque};
+use std::time::Duration;
+
+use super::ResolveBridge;
+
+/// Latency samples kept per tool for the median
+const LATENCY_SAMPLES: usize = 1024;
+
+/// Calls of one tool
+#[derive(Debug, Default)]
+struct ToolUsage {
+    calls: u64,
+    errors: u64,
+    /// Most recent latencies in milliseconds, oldest first
+    latencies_ms: VecDeque<f64>,
+    last_called_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl ToolUsage {
+    fn median_latency_ms(&self) -> Option<f64> {
+        let mut samples: Vec<f64> = self.latencies_ms.iter().copied().collect();
+        if samples.is_empty() {
+            return None;
+        }
+        samples.sort_by(f64::total_cmp);
+        let middle = samples.len() / 2;
+        Some(if samples.len().is_multiple_of(2) {
+            (samples[middle - 1] + samples[middle]) / 2.0
+        } else {
+            samples[middle]
+        })
+    }
+
+    fn to_json(&self, tool: &str) -> Value {
+        json!({
+            "tool": tool,
+            "calls": self.calls,
+            "errors": self.errors,
+            "error_rate": self.errors as f64 / self.calls as f64,
+            "median_latency_ms": self.median_latency_ms(),
+            "last_called_at": self.last_called_at.map(|at| at.to_rfc3339())
+        })
+    }
+}
+
+/// Calls of every tool since the server started
+#[derive(Debug)]
+pub(super) struct ToolStats {
+    started_at: chrono::DateTime<chrono::Utc>,
+    tools: BTreeMap<String, ToolUsage>,
+}
+
+impl Default for ToolStats {
+    fn default() -> Self {
+        Self {
+            started_at: chrono::Utc::now(),
+            tools: BTreeMap::new(),
+        }
+    }
+}
+
+impl ResolveBridge {
+    /// Count a finished tool call in the usage statistics
+    pub async fn record_tool_call(&self, tool: &str, succeeded: bool, latency: Duration) {
+        let mut store = self.identity.lock().await;
+        let usage = store.tool_stats.tools.entry(tool.to_string()).or_default();
+        usage.calls += 1;
+        if !succeeded {
+            usage.errors += 1;
+        }
+        usage.latencies_ms.push_back(latency.as_secs_f64() * 1000.0);
+        if usage.latencies_ms.len() > LATENCY_SAMPLES {
+            usage.latencies_ms.pop_front();
+        }
+        usage.last_called_at = Some(chrono::Utc::now());
+    }
+
+    /// Usage of each tool called since startup, most called first
+    pub async fn tool_stats(&self) -> Value {
+        let store = self.identity.lock().await;
+        let stats = &store.tool_stats;
+        let mut tools: Vec<(&String, &ToolUsage)> = stats.tools.iter().collect();
+        tools.sort_by(|a, b| b.1.calls.cmp(&a.1.calls).then_with(|| a.0.cmp(b.0)));
+        let calls: u64 = tools.iter().map(|(_, usage)| usage.calls).sum();
+        let errors: u64 = tools.iter().map(|(_, usage)| usage.errors).sum();
+        let now = chrono::Utc::now();
+
+        json!({
+            "since": stats.started_at.to_rfc3339(),
+            "uptime_seconds": (now - stats.started_at).num_seconds(),
+            "total_calls": calls,
+            "total_errors": errors,
+            "error_rate": if calls == 0 { 0.0 } else { errors as f64 / calls as f64 },
+            "latency_samples_per_tool": LATENCY_SAMPLES,
+            "tools": tools
+                .iter()
+                .map(|(tool, usage)| usage.to_json(tool))
+                .collect::<Vec<_>>()
+        })
+    }
+}
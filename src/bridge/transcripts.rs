@@ -0,0 +1,285 @@
+//! Word-level transcripts and transcript search
+//!
+//! Transcribing a media pool clip stores its words with timings and a
+//! confidence score. Simulation mode produces a fixed sample transcript so
+//! search results are predictable.
+
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use uuid::Uuid;
+
+use super::markers::marker_color;
+use super::rename_batch::{rekey, Renames};
+use super::{ensure_capacity, Marker, ResolveBridge, StateView};
+use crate::error::{ResolveError, ResolveResult};
+use crate::timecode::FrameRate;
+
+/// Words spoken in every simulated transcript
+const SIMULATED_TRANSCRIPT: &str = "Welcome back to the show. Today we are looking at the new \
+    color pipeline and how the grade holds up in the final render. Let's start with the \
+    opening shot.";
+
+/// Words shown either side of a match
+const CONTEXT_WORDS: usize = 4;
+
+/// Transcripts by clip name
+#[derive(Debug, Clone, Default)]
+pub(super) struct TranscriptState {
+    transcripts: BTreeMap<String, Transcript>,
+}
+
+impl TranscriptState {
+    /// Keep the transcripts of renamed clips with them
+    pub(super) fn rename_clips(&mut self, renames: &Renames) {
+        rekey(&mut self.transcripts, renames);
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Transcript {
+    language: String,
+    words: Vec<TranscriptWord>,
+}
+
+#[derive(Debug, Clone)]
+struct TranscriptWord {
+    text: String,
+    /// Start and end within the clip, in seconds
+    start: f64,
+    end: f64,
+    /// Recognition confidence from 0.0 to 1.0
+    confidence: f64,
+}
+
+impl Transcript {
+    /// Sample transcript with evenly paced words and varying confidence
+    fn simulated(language: &str) -> Self {
+        let mut start = 0.5;
+        let words = SIMULATED_TRANSCRIPT
+            .split_whitespace()
+            .enumerate()
+            .map(|(index, text)| {
+                let end = start + 0.25 + 0.05 * text.len() as f64;
+                let word = TranscriptWord {
+                    text: text.to_string(),
+                    start,
+                    end,
+                    confidence: 0.99 - (index * 7 % 20) as f64 / 100.0,
+                };
+                start = end + 0.1;
+                word
+            })
+            .collect();
+        Self {
+            language: language.to_string(),
+            words,
+        }
+    }
+}
+
+/// Lowercase a word and drop punctuation so "Grade," matches "grade"
+fn normalize_word(word: &str) -> String {
+    word.chars()
+        .filter(|c| c.is_alphanumeric() || *c == '\'')
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+fn seconds_to_frames(seconds: f64, rate: FrameRate) -> i64 {
+    (seconds * rate.fps()).round() as i64
+}
+
+impl ResolveBridge {
+    /// Store a transcript for a clip if it is in the media pool; returns the word count
+    pub(super) fn store_transcript(
+        state: &mut StateView<'_>,
+        clip_name: &str,
+        language: &str,
+    ) -> Option<usize> {
+        if !state.media_pool.clips.contains_key(clip_name) {
+            return None;
+        }
+        let transcript = Transcript::simulated(language);
+        let word_count = transcript.words.len();
+        state
+            .transcripts
+            .transcripts
+            .insert(clip_name.to_string(), transcript);
+        Some(word_count)
+    }
+
+    /// Remove a clip's transcript; returns whether one existed
+    pub(super) fn remove_transcript(state: &mut StateView<'_>, clip_name: &str) -> bool {
+        state.transcripts.transcripts.remove(clip_name).is_some()
+    }
+
+    pub(super) async fn search_transcript(
+        &self,
+        state: &mut StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        let query = args["query"]
+            .as_str()
+            .ok_or_else(|| ResolveError::invalid_parameter("query", "required string"))?;
+        let phrase: Vec<String> = query
+            .split_whitespace()
+            .map(normalize_word)
+            .filter(|word| !word.is_empty())
+            .collect();
+        if phrase.is_empty() {
+            return Err(ResolveError::invalid_parameter(
+                "query",
+                "must contain at least one word",
+            ));
+        }
+        let clip_filter = match args["clip_name"].as_str() {
+            Some(clip) => Some(Self::resolve_clip(state, clip)?),
+            None => None,
+        };
+        let min_confidence = args["min_confidence"].as_f64().unwrap_or(0.0);
+        // Checked up front so a bad page size cannot fail after markers are added
+        self.page_size(&args)?;
+        let create_markers = args["create_markers"].as_bool().unwrap_or(false);
+
+        // Markers go on a timeline, whose rate is also used for match timecodes
+        let timeline_name = if create_markers {
+            Some(Self::resolve_timeline_name(state, &args)?)
+        } else {
+            None
+        };
+        let rate = match &timeline_name {
+            Some(name) => self.timeline_frame_rate(&state.timelines[name])?,
+            None => self.project_frame_rate()?,
+        };
+        let color = match args["marker_color"].as_str() {
+            Some(color) => marker_color(color).ok_or_else(|| {
+                ResolveError::invalid_parameter("marker_color", "unknown marker color")
+            })?,
+            None => "Purple",
+        };
+
+        let mut matches = Vec::new();
+        for (clip_name, transcript) in &state.transcripts.transcripts {
+            if clip_filter
+                .as_ref()
+                .is_some_and(|filter| filter != clip_name)
+            {
+                continue;
+            }
+            let normalized: Vec<String> = transcript
+                .words
+                .iter()
+                .map(|word| normalize_word(&word.text))
+                .collect();
+            for start in 0..normalized.len().saturating_sub(phrase.len() - 1) {
+                if normalized[start..start + phrase.len()] != phrase[..] {
+                    continue;
+                }
+                let words = &transcript.words[start..start + phrase.len()];
+                let confidence = words
+                    .iter()
+                    .map(|word| word.confidence)
+                    .fold(f64::INFINITY, f64::min);
+                if confidence < min_confidence {
+                    continue;
+                }
+                let context_start = start.saturating_sub(CONTEXT_WORDS);
+                let context_end = (start + phrase.len() + CONTEXT_WORDS).min(normalized.len());
+                let context: Vec<&str> = transcript.words[context_start..context_end]
+                    .iter()
+                    .map(|word| word.text.as_str())
+                    .collect();
+                let in_frame = seconds_to_frames(words[0].start, rate);
+                let out_frame = seconds_to_frames(words[words.len() - 1].end, rate);
+                matches.push((
+                    clip_name.clone(),
+                    in_frame,
+                    json!({
+                        "clip_name": clip_name,
+                        "language": transcript.language,
+                        "text": words.iter().map(|word| word.text.as_str()).collect::<Vec<_>>().join(" "),
+                        "context": context.join(" "),
+                        "in_frame": in_frame,
+                        "out_frame": out_frame,
+                        "in_timecode": rate.frames_to_timecode(in_frame),
+                        "out_timecode": rate.frames_to_timecode(out_frame),
+                        "confidence": (confidence * 100.0).round() / 100.0
+                    }),
+                ));
+            }
+        }
+
+        // Place a marker wherever a matched source frame is used on the timeline
+        let mut markers_created = 0;
+        if let Some(timeline_name) = &timeline_name {
+            let mut markers: Vec<Marker> = Vec::new();
+            let timeline = &state.timelines[timeline_name];
+            for (clip_name, in_frame, _) in &matches {
+                let record_frames = state
+                    .timeline_items
+                    .items
+                    .values()
+                    .filter(|item| {
+                        item.timeline_name == *timeline_name && item.clip_name == *clip_name
+                    })
+                    .filter_map(|item| item.placement.as_ref())
+                    .filter(|placement| {
+                        (placement.source_in..placement.source_out()).contains(in_frame)
+                    })
+                    .map(|placement| placement.record_in + in_frame - placement.source_in);
+                for frame in record_frames {
+                    let Ok(frame) = i32::try_from(frame) else {
+                        continue;
+                    };
+                    let taken = timeline.markers.iter().any(|m| m.frame == Some(frame))
+                        || markers.iter().any(|m| m.frame == Some(frame));
+                    if !taken {
+                        markers.push(Marker {
+                            id: Uuid::new_v4().to_string(),
+                            frame: Some(frame),
+                            color: color.to_string(),
+                            note: format!("Transcript: \"{}\"", query.trim()),
+                            name: String::new(),
+                            duration: 1,
+                        });
+                    }
+                }
+            }
+            ensure_capacity(
+                &format!("markers on timeline '{}'", timeline_name),
+                timeline.markers.len(),
+                markers.len(),
+                self.config.limits.max_markers_per_timeline,
+            )?;
+            markers_created = markers.len();
+            if let Some(timeline) = state.timelines.get_mut(timeline_name) {
+                timeline.markers.extend(markers);
+            }
+        }
+
+        let mut response = json!({
+            "result": format!(
+                "Found {} matches for '{}' in {} transcripts",
+                matches.len(),
+                query.trim(),
+                state.transcripts.transcripts.len()
+            ),
+            "query": query,
+            "frame_rate": rate.to_string(),
+            "count": matches.len(),
+            "timeline_name": timeline_name,
+            "markers_created": markers_created,
+            "operation_id": Uuid::new_v4().to_string()
+        });
+        let matches = matches.into_iter().map(|(_, _, found)| found);
+        self.paginate(
+            "search_transcript",
+            &mut response,
+            "matches",
+            matches,
+            &args,
+        )
+        .await?;
+        Ok(response)
+    }
+}
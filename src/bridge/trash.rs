@@ -0,0 +1,304 @@
+//! Trash for deleted media, timelines and color presets
+//!
+//! delete_media, delete_timeline and delete_color_preset move what they delete
+//! into the trash instead of dropping it. Each entry can be restored for
+//! `Config::trash.retention_hours` after its deletion and is purged once that
+//! has passed; a retention of 0 makes deletions permanent again. An entry is
+//! restored into the project it was deleted from, under its old name and ID,
+//! as long as nothing else has taken the name since.
+//!
+//! In Real mode deletions that reach Resolve are permanent there, so only
+//! entities deleted from the simulated state are in the trash.
+
+use chrono::{DateTime, Utc};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use super::{
+    ensure_capacity, Clip, ColorPreset, ColorPresetAlbum, ResolveBridge, StateView, Timeline,
+};
+use crate::error::{ResolveError, ResolveResult};
+
+/// Kinds of entity the trash holds, by the name tools use for them
+const KINDS: [&str; 3] = ["media", "timeline", "color_preset"];
+
+/// A deleted entity, with what it takes to put it back
+#[derive(Debug, Clone)]
+pub(super) enum Trashed {
+    /// A clip with the media pool name it was deleted under
+    Clip(String, Clip),
+    Timeline(Timeline),
+    /// The preset's album is kept in the preset
+    ColorPreset(ColorPreset),
+}
+
+impl Trashed {
+    fn kind(&self) -> &'static str {
+        match self {
+            Trashed::Clip(..) => "media",
+            Trashed::Timeline(_) => "timeline",
+            Trashed::ColorPreset(_) => "color_preset",
+        }
+    }
+
+    fn name(&self) -> &str {
+        match self {
+            Trashed::Clip(name, _) => name,
+            Trashed::Timeline(timeline) => &timeline.name,
+            Trashed::ColorPreset(preset) => &preset.name,
+        }
+    }
+
+    /// ID of the entity itself, kept when it is restored
+    fn entity_id(&self) -> &str {
+        match self {
+            Trashed::Clip(_, clip) => &clip.id,
+            Trashed::Timeline(timeline) => &timeline.id,
+            Trashed::ColorPreset(preset) => &preset.id,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct TrashEntry {
+    /// Unique trash entry ID
+    id: String,
+    /// Project the entity was deleted from
+    project: Option<String>,
+    deleted_at: DateTime<Utc>,
+    expires_at: DateTime<Utc>,
+    item: Trashed,
+}
+
+impl TrashEntry {
+    fn to_json(&self) -> Value {
+        let mut entry = json!({
+            "trash_id": self.id,
+            "kind": self.item.kind(),
+            "name": self.item.name(),
+            "id": self.item.entity_id(),
+            "project": self.project,
+            "deleted_at": self.deleted_at.to_rfc3339(),
+            "expires_at": self.expires_at.to_rfc3339()
+        });
+        match &self.item {
+            Trashed::Clip(_, clip) => {
+                entry["file_path"] = json!(clip.file_path);
+                entry["bin"] = json!(clip.bin);
+            }
+            Trashed::Timeline(timeline) => {
+                entry["marker_count"] = json!(timeline.markers.len());
+            }
+            Trashed::ColorPreset(preset) => {
+                entry["album"] = json!(preset.album);
+            }
+        }
+        entry
+    }
+}
+
+/// Deleted entities that can still be restored, oldest first
+#[derive(Debug, Default)]
+pub(super) struct TrashState {
+    entries: Vec<TrashEntry>,
+}
+
+impl TrashState {
+    /// Drop the entries whose retention has passed; returns how many
+    fn purge_expired(&mut self, now: DateTime<Utc>) -> usize {
+        let before = self.entries.len();
+        self.entries.retain(|entry| entry.expires_at > now);
+        before - self.entries.len()
+    }
+}
+
+/// Check an optional kind filter
+fn parse_kind(args: &Value) -> ResolveResult<Option<&str>> {
+    match args["kind"].as_str() {
+        None => Ok(None),
+        Some(kind) if KINDS.contains(&kind) => Ok(Some(kind)),
+        Some(kind) => Err(ResolveError::invalid_parameter(
+            "kind",
+            format!("unknown kind '{}'; use one of {}", kind, KINDS.join(", ")),
+        )),
+    }
+}
+
+impl ResolveBridge {
+    /// Move a deleted entity into the trash; returns the fields a delete
+    /// response adds, or None when deletions are permanent
+    pub(super) fn move_to_trash(&self, state: &mut StateView<'_>, item: Trashed) -> Option<Value> {
+        let retention_hours = self.config.trash.retention_hours;
+        if retention_hours == 0 {
+            return None;
+        }
+        let now = Utc::now();
+        state.trash.purge_expired(now);
+        let entry = TrashEntry {
+            id: Uuid::new_v4().to_string(),
+            project: state.current_project.clone(),
+            deleted_at: now,
+            expires_at: now + chrono::Duration::hours(retention_hours as i64),
+            item,
+        };
+        let response = json!({
+            "trash_id": entry.id,
+            "expires_at": entry.expires_at.to_rfc3339()
+        });
+        state.trash.entries.push(entry);
+        Some(response)
+    }
+
+    pub(super) async fn list_trash(
+        &self,
+        state: &mut StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        let kind = parse_kind(&args)?;
+        let now = Utc::now();
+        // Expired entries are purged on the next change; until then they are hidden
+        let entries: Vec<Value> = state
+            .trash
+            .entries
+            .iter()
+            .rev()
+            .filter(|entry| entry.expires_at > now)
+            .filter(|entry| kind.is_none_or(|kind| entry.item.kind() == kind))
+            .map(TrashEntry::to_json)
+            .collect();
+        Ok(json!({
+            "result": format!("{} deleted items can be restored", entries.len()),
+            "entries": entries,
+            "retention_hours": self.config.trash.retention_hours
+        }))
+    }
+
+    pub(super) async fn restore_from_trash(
+        &self,
+        state: &mut StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        let trash_id = args["trash_id"]
+            .as_str()
+            .ok_or_else(|| ResolveError::invalid_parameter("trash_id", "required string"))?;
+        state.trash.purge_expired(Utc::now());
+        let position = state
+            .trash
+            .entries
+            .iter()
+            .position(|entry| entry.id == trash_id)
+            .ok_or_else(|| {
+                ResolveError::invalid_parameter(
+                    "trash_id",
+                    format!("'{}' is not in the trash or has expired", trash_id),
+                )
+            })?;
+
+        let entry = &state.trash.entries[position];
+        if entry.project != *state.current_project {
+            return Err(ResolveError::invalid_parameter(
+                "trash_id",
+                format!(
+                    "'{}' was deleted from project '{}'; open it to restore",
+                    entry.item.name(),
+                    entry.project.as_deref().unwrap_or("none")
+                ),
+            ));
+        }
+        let taken = |what: &str, name: &str| {
+            ResolveError::invalid_parameter(
+                "trash_id",
+                format!(
+                    "a {} named '{}' already exists; rename or delete it first",
+                    what, name
+                ),
+            )
+        };
+        match &entry.item {
+            Trashed::Clip(name, _) => {
+                if state.media_pool.clips.contains_key(name) {
+                    return Err(taken("clip", name));
+                }
+            }
+            Trashed::Timeline(timeline) => {
+                if state.timelines.contains_key(&timeline.name) {
+                    return Err(taken("timeline", &timeline.name));
+                }
+                ensure_capacity(
+                    "timelines",
+                    state.timelines.len(),
+                    1,
+                    self.config.limits.max_timelines,
+                )?;
+            }
+            Trashed::ColorPreset(preset) => {
+                let exists = state
+                    .color_state
+                    .preset_albums
+                    .get(&preset.album)
+                    .is_some_and(|album| album.presets.contains_key(&preset.name));
+                if exists {
+                    return Err(taken("color preset", &preset.name));
+                }
+            }
+        }
+
+        let entry = state.trash.entries.remove(position);
+        let kind = entry.item.kind();
+        let mut response = json!({
+            "result": format!(
+                "Restored {} '{}' from the trash",
+                kind.replace('_', " "),
+                entry.item.name()
+            ),
+            "kind": kind,
+            "name": entry.item.name(),
+            "id": entry.item.entity_id(),
+            "trash_id": entry.id,
+            "operation_id": Uuid::new_v4().to_string()
+        });
+        match entry.item {
+            Trashed::Clip(name, clip) => {
+                response["bin"] = json!(clip.bin);
+                state.media_pool.clips.insert(name, clip);
+            }
+            Trashed::Timeline(timeline) => {
+                state.timelines.insert(timeline.name.clone(), timeline);
+            }
+            Trashed::ColorPreset(preset) => {
+                response["album"] = json!(preset.album);
+                let album = preset.album.clone();
+                state
+                    .color_state
+                    .preset_albums
+                    .entry(album.clone())
+                    .or_insert_with(|| ColorPresetAlbum::new(&album))
+                    .presets
+                    .insert(preset.name.clone(), preset);
+            }
+        }
+        Ok(response)
+    }
+
+    pub(super) async fn empty_trash(
+        &self,
+        state: &mut StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        let kind = parse_kind(&args)?;
+        let expired = state.trash.purge_expired(Utc::now());
+        let before = state.trash.entries.len();
+        state
+            .trash
+            .entries
+            .retain(|entry| kind.is_some_and(|kind| entry.item.kind() != kind));
+        let removed = before - state.trash.entries.len();
+        Ok(json!({
+            "result": format!("Permanently deleted {} items from the trash", removed),
+            "removed": removed,
+            "expired": expired,
+            "remaining": state.trash.entries.len(),
+            "operation_id": Uuid::new_v4().to_string()
+        }))
+    }
+}
@@ -0,0 +1,294 @@
+//! VFX plate export
+//!
+//! Each plate is a render job over one shot's record range plus handles,
+//! named `SHOW_SEQ_SHOT_VERSION` (for example `ABC_010_0040_v001`). Shots come
+//! from timeline items or from the ranges between markers of one color.
+
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use super::markers::{marker_color, timeline_start};
+use super::{RenderJob, RenderJobStatus, ResolveBridge, StateView};
+use crate::error::{ResolveError, ResolveResult};
+
+/// Handle length used when the request does not give one, in frames
+const DEFAULT_PLATE_HANDLES: i64 = 8;
+
+/// Output folder used when the request does not give one
+const DEFAULT_PLATE_DIRECTORY: &str = "/tmp/renders/vfx_plates";
+
+/// Plate formats as (format, render preset, file extension, image sequence)
+const PLATE_FORMATS: &[(&str, &str, &str, bool)] = &[
+    ("exr", "VFX Plate EXR", "exr", true),
+    ("dpx", "VFX Plate DPX", "dpx", true),
+    ("prores", "VFX Plate ProRes 4444", "mov", false),
+];
+
+/// A shot to export, in record frames relative to the timeline start
+struct PlateShot {
+    record_in: i64,
+    record_out: i64,
+    timeline_item_id: Option<String>,
+    clip_name: Option<String>,
+    /// Source frames used by the item, for item-based shots
+    source_range: Option<(i64, i64)>,
+}
+
+/// Keep a plate name segment to characters that are safe in file names
+fn name_segment(param: &str, value: &str) -> ResolveResult<String> {
+    let segment = value.trim().to_ascii_uppercase();
+    if segment.is_empty()
+        || !segment
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-')
+    {
+        return Err(ResolveError::invalid_parameter(
+            param,
+            "must be letters, digits or '-'",
+        ));
+    }
+    Ok(segment)
+}
+
+impl ResolveBridge {
+    pub(super) async fn export_vfx_plates(
+        &self,
+        state: &mut StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        let show = name_segment(
+            "show",
+            args["show"]
+                .as_str()
+                .ok_or_else(|| ResolveError::invalid_parameter("show", "required string"))?,
+        )?;
+        let sequence = name_segment(
+            "sequence",
+            args["sequence"]
+                .as_str()
+                .ok_or_else(|| ResolveError::invalid_parameter("sequence", "required string"))?,
+        )?;
+        let version = args["version"].as_u64().unwrap_or(1);
+        let shot_start = args["shot_start"].as_u64().unwrap_or(10);
+        let shot_increment = args["shot_increment"].as_u64().unwrap_or(10);
+        if version == 0 || shot_increment == 0 {
+            return Err(ResolveError::invalid_parameter(
+                "version",
+                "version and shot_increment must be 1 or greater",
+            ));
+        }
+        let handles = match args["handles"].as_i64() {
+            Some(handles) if handles >= 0 => handles,
+            Some(_) => {
+                return Err(ResolveError::invalid_parameter(
+                    "handles",
+                    "must be 0 or greater",
+                ))
+            }
+            None => DEFAULT_PLATE_HANDLES,
+        };
+        let format = args["format"]
+            .as_str()
+            .unwrap_or("exr")
+            .to_ascii_lowercase();
+        let &(_, preset_name, extension, image_sequence) = PLATE_FORMATS
+            .iter()
+            .find(|(name, ..)| *name == format)
+            .ok_or_else(|| {
+                ResolveError::invalid_parameter("format", "must be 'exr', 'dpx' or 'prores'")
+            })?;
+        let output_directory = args["output_directory"]
+            .as_str()
+            .unwrap_or(DEFAULT_PLATE_DIRECTORY)
+            .trim_end_matches('/');
+
+        let timeline_name = Self::resolve_timeline_name(state, &args)?;
+        let timeline = &state.timelines[&timeline_name];
+        let start = timeline_start(timeline, &args);
+        let rate = self
+            .timeline_frame_rate(timeline)?
+            .for_timecode(start)
+            .map_err(|e| ResolveError::invalid_parameter("timeline_start_timecode", e))?;
+        let start_frame = rate
+            .timecode_to_frames(start)
+            .map_err(|e| ResolveError::invalid_parameter("timeline_start_timecode", e))?;
+
+        let placed: Vec<_> = state
+            .timeline_items
+            .items
+            .values()
+            .filter(|item| item.timeline_name == timeline_name)
+            .filter_map(|item| item.placement.as_ref().map(|placement| (item, placement)))
+            .collect();
+        let timeline_end = placed
+            .iter()
+            .map(|(_, placement)| placement.record_out())
+            .max()
+            .unwrap_or(0);
+
+        let mut shots: Vec<PlateShot> = match (
+            args["timeline_item_ids"].as_array(),
+            args["marker_color"].as_str(),
+        ) {
+            (Some(_), Some(_)) => {
+                return Err(ResolveError::invalid_parameter(
+                    "timeline_item_ids",
+                    "use either timeline_item_ids or marker_color, not both",
+                ))
+            }
+            (None, Some(color)) => {
+                let color = marker_color(color).ok_or_else(|| {
+                    ResolveError::invalid_parameter("marker_color", "unknown marker color")
+                })?;
+                // Each marker starts a shot that runs to the next marker of the color
+                let mut frames: Vec<i64> = timeline
+                    .markers
+                    .iter()
+                    .filter(|marker| marker.color.eq_ignore_ascii_case(color))
+                    .filter_map(|marker| marker.frame.map(i64::from))
+                    .collect();
+                frames.sort_unstable();
+                frames.dedup();
+                let mut ends: Vec<i64> = frames.iter().skip(1).copied().collect();
+                ends.push(timeline_end);
+                frames
+                    .into_iter()
+                    .zip(ends)
+                    .filter(|(record_in, record_out)| record_in < record_out)
+                    .map(|(record_in, record_out)| PlateShot {
+                        record_in,
+                        record_out,
+                        timeline_item_id: None,
+                        clip_name: None,
+                        source_range: None,
+                    })
+                    .collect()
+            }
+            (ids, None) => {
+                let ids: Option<Vec<&str>> = ids
+                    .map(|ids| {
+                        ids.iter()
+                            .map(|id| {
+                                id.as_str().ok_or_else(|| {
+                                    ResolveError::invalid_parameter(
+                                        "timeline_item_ids",
+                                        "timeline item IDs must be strings",
+                                    )
+                                })
+                            })
+                            .collect::<ResolveResult<_>>()
+                    })
+                    .transpose()?;
+                if let Some(ids) = &ids {
+                    if let Some(missing) = ids
+                        .iter()
+                        .find(|id| !placed.iter().any(|(item, _)| item.id == **id))
+                    {
+                        return Err(ResolveError::invalid_parameter(
+                            "timeline_item_ids",
+                            format!(
+                                "timeline item '{}' is not on timeline '{}'",
+                                missing, timeline_name
+                            ),
+                        ));
+                    }
+                }
+                placed
+                    .iter()
+                    .filter(|(item, _)| {
+                        ids.as_ref()
+                            .is_none_or(|ids| ids.contains(&item.id.as_str()))
+                    })
+                    .map(|(item, placement)| PlateShot {
+                        record_in: placement.record_in,
+                        record_out: placement.record_out(),
+                        timeline_item_id: Some(item.id.clone()),
+                        clip_name: Some(item.clip_name.clone()),
+                        source_range: Some((placement.source_in, placement.source_out())),
+                    })
+                    .collect()
+            }
+        };
+        if shots.is_empty() {
+            return Err(ResolveError::invalid_parameter(
+                "timeline_item_ids",
+                format!("no shots to export on timeline '{}'", timeline_name),
+            ));
+        }
+        shots.sort_by(|a, b| {
+            (a.record_in, &a.timeline_item_id).cmp(&(b.record_in, &b.timeline_item_id))
+        });
+
+        let created_at = chrono::Utc::now();
+        let mut plates = Vec::with_capacity(shots.len());
+        for (index, shot) in shots.iter().enumerate() {
+            let shot_number = shot_start + shot_increment * index as u64;
+            let plate_name = format!("{}_{}_{:04}_v{:03}", show, sequence, shot_number, version);
+            let output_path = if image_sequence {
+                format!(
+                    "{}/{}/{}.%08d.{}",
+                    output_directory, plate_name, plate_name, extension
+                )
+            } else {
+                format!("{}/{}.{}", output_directory, plate_name, extension)
+            };
+            // Timeline frames before the start cannot be rendered
+            let render_in = (shot.record_in - handles).max(0);
+            let render_out = shot.record_out + handles;
+
+            state.render_state.job_counter += 1;
+            let job_id = format!("job_{}", state.render_state.job_counter);
+            state.render_state.render_queue.push(RenderJob {
+                id: job_id.clone(),
+                timeline_name: timeline_name.clone(),
+                preset_name: preset_name.to_string(),
+                output_path: output_path.clone(),
+                use_in_out_range: true,
+                frame_range: Some((render_in, render_out)),
+                // Plates are picture only
+                export_video: true,
+                audio_channel_layout: None,
+                subtitles: None,
+                // Plates go to VFX at full frame, without blanking or reframing
+                framing: Default::default(),
+                embedding: None,
+                created_at,
+                status: RenderJobStatus::Queued,
+            });
+
+            plates.push(json!({
+                "plate_name": plate_name,
+                "shot": format!("{:04}", shot_number),
+                "job_id": job_id,
+                "timeline_item_id": shot.timeline_item_id,
+                "clip_name": shot.clip_name,
+                "output_path": output_path,
+                "record_in": rate.frames_to_timecode(start_frame + render_in),
+                "record_out": rate.frames_to_timecode(start_frame + render_out),
+                "source_in": shot.source_range.map(|(source_in, _)| {
+                    rate.frames_to_timecode((source_in - (shot.record_in - render_in)).max(0))
+                }),
+                "source_out": shot.source_range.map(|(_, source_out)| {
+                    rate.frames_to_timecode(source_out + handles)
+                }),
+                "frame_count": render_out - render_in
+            }));
+        }
+
+        Ok(json!({
+            "result": format!(
+                "Queued {} VFX plates from timeline '{}'",
+                plates.len(),
+                timeline_name
+            ),
+            "timeline_name": timeline_name,
+            "format": format,
+            "preset_name": preset_name,
+            "handles": handles,
+            "plates": plates,
+            "plate_count": plates.len(),
+            "queue_size": state.render_state.render_queue.len(),
+            "operation_id": Uuid::new_v4().to_string()
+        }))
+    }
+}
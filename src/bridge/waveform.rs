@@ -0,0 +1,244 @@
+//! Audio waveform peaks for clips and timeline ranges
+//!
+//! Each media file is decoded once with ffmpeg into min/max peaks for short
+//! blocks, which are cached on disk when `Config::cache` has a directory.
+//! Requests then reduce the blocks to the number of buckets asked for. Media
+//! that is offline, or any media when ffmpeg is missing, gets a simulated
+//! waveform instead.
+
+use serde_json::{json, Value};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+use super::ffmpeg;
+use super::{ResolveBridge, StateView, SIMULATED_CLIP_SECONDS};
+use crate::error::{ResolveError, ResolveResult};
+
+/// Peak blocks per second of audio kept in the cache
+pub(super) const BLOCKS_PER_SECOND: u32 = 100;
+
+const DEFAULT_BUCKETS: u64 = 200;
+const MAX_BUCKETS: u64 = 10_000;
+
+/// Where a clip's peaks came from
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) enum PeakSource {
+    Decoded,
+    Cache,
+    Simulated,
+}
+
+impl PeakSource {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Decoded => "decoded",
+            Self::Cache => "cache",
+            Self::Simulated => "simulated",
+        }
+    }
+}
+
+/// Deterministic stand-in peaks for media that cannot be decoded
+pub(super) fn simulated_peaks() -> Vec<(f32, f32)> {
+    (0..SIMULATED_CLIP_SECONDS as u32 * BLOCKS_PER_SECOND)
+        .map(|block| {
+            let t = block as f32 / BLOCKS_PER_SECOND as f32;
+            let level = 0.5 * (t * 0.7).sin().abs() * (0.6 + 0.4 * (t * 3.1).sin());
+            (-level, level)
+        })
+        .collect()
+}
+
+/// A source range of one clip laid onto the requested frame range
+struct Segment {
+    clip_name: String,
+    /// Requested frame the segment starts at
+    frame: i64,
+    /// Source frame the segment starts at
+    source_in: i64,
+    duration: i64,
+}
+
+impl ResolveBridge {
+    /// Cache file for a media file's peaks; changes when the file does
+    fn peak_cache_path(&self, media: &Path) -> Option<PathBuf> {
+        let directory = self.config.cache.directory.as_ref()?;
+        let metadata = std::fs::metadata(media).ok()?;
+        let mut hasher = DefaultHasher::new();
+        media.hash(&mut hasher);
+        metadata.len().hash(&mut hasher);
+        metadata.modified().ok()?.hash(&mut hasher);
+        Some(
+            directory
+                .join("waveforms")
+                .join(format!("{:016x}.json", hasher.finish())),
+        )
+    }
+
+    /// Peaks for a media file, from the cache, ffmpeg or simulation
+    pub(super) fn media_peaks(&self, file_path: &str) -> (Vec<(f32, f32)>, PeakSource) {
+        let media = Path::new(file_path);
+        if !media.is_file() || !ffmpeg::available() {
+            return (simulated_peaks(), PeakSource::Simulated);
+        }
+
+        let cache_path = self.peak_cache_path(media);
+        if let Some(cached) = cache_path
+            .as_ref()
+            .and_then(|path| std::fs::read(path).ok())
+            .and_then(|data| serde_json::from_slice::<Vec<(f32, f32)>>(&data).ok())
+        {
+            return (cached, PeakSource::Cache);
+        }
+
+        match ffmpeg::audio_peaks(media, BLOCKS_PER_SECOND) {
+            Ok(peaks) => {
+                if let Some(path) = &cache_path {
+                    let written = path
+                        .parent()
+                        .map_or(Ok(()), std::fs::create_dir_all)
+                        .and_then(|_| std::fs::write(path, serde_json::to_vec(&peaks)?));
+                    if let Err(e) = written {
+                        tracing::warn!("Failed to cache waveform for {}: {}", file_path, e);
+                    }
+                }
+                (peaks, PeakSource::Decoded)
+            }
+            Err(e) => {
+                tracing::warn!("Failed to decode audio of {}: {}", file_path, e);
+                (simulated_peaks(), PeakSource::Simulated)
+            }
+        }
+    }
+
+    pub(super) async fn get_audio_waveform(
+        &self,
+        state: &StateView<'_>,
+        args: Value,
+    ) -> ResolveResult<Value> {
+        let buckets = args["buckets"].as_u64().unwrap_or(DEFAULT_BUCKETS);
+        if buckets == 0 || buckets > MAX_BUCKETS {
+            return Err(ResolveError::invalid_parameter(
+                "buckets",
+                format!("must be between 1 and {}", MAX_BUCKETS),
+            ));
+        }
+
+        // Frames are source frames for a clip and record frames for a timeline
+        let (rate, segments, default_end, timeline_name) = match args["clip_name"].as_str() {
+            Some(clip_reference) => {
+                let clip_name = Self::resolve_clip(state, clip_reference)?;
+                let rate = self.project_frame_rate()?;
+                let end = SIMULATED_CLIP_SECONDS * rate.nominal();
+                // The whole clip; decoded media ends where its audio does
+                let segments = vec![Segment {
+                    clip_name,
+                    frame: 0,
+                    source_in: 0,
+                    duration: i64::MAX / 2,
+                }];
+                (rate, segments, end, None)
+            }
+            None => {
+                let timeline_name = Self::resolve_timeline_name(state, &args)?;
+                let rate = self.timeline_frame_rate(&state.timelines[&timeline_name])?;
+                let segments: Vec<Segment> = state
+                    .timeline_items
+                    .items
+                    .values()
+                    .filter(|item| item.timeline_name == timeline_name)
+                    .filter_map(|item| {
+                        item.placement.as_ref().map(|placement| Segment {
+                            clip_name: item.clip_name.clone(),
+                            frame: placement.record_in,
+                            source_in: placement.source_in,
+                            duration: placement.duration,
+                        })
+                    })
+                    .collect();
+                let end = segments
+                    .iter()
+                    .map(|segment| segment.frame + segment.duration)
+                    .max()
+                    .unwrap_or(0);
+                (rate, segments, end, Some(timeline_name))
+            }
+        };
+
+        let start_frame = args["start_frame"].as_i64().unwrap_or(0);
+        let end_frame = args["end_frame"].as_i64().unwrap_or(default_end);
+        if start_frame < 0 || end_frame <= start_frame {
+            return Err(ResolveError::invalid_parameter(
+                "end_frame",
+                "range must start at 0 or later and end after it starts",
+            ));
+        }
+
+        let fps = rate.fps();
+        let bucket_frames = (end_frame - start_frame) as f64 / buckets as f64;
+        let mut peaks: Vec<Option<(f32, f32)>> = vec![None; buckets as usize];
+        let mut sources = serde_json::Map::new();
+        for segment in &segments {
+            let from = segment.frame.max(start_frame);
+            let to = (segment.frame + segment.duration).min(end_frame);
+            if from >= to {
+                continue;
+            }
+            let Some(clip) = state.media_pool.clips.get(&segment.clip_name) else {
+                continue;
+            };
+            let (blocks, source) = self.media_peaks(&clip.file_path);
+            sources.insert(segment.clip_name.clone(), json!(source.as_str()));
+
+            // Blocks covering the part of the segment inside the range
+            let source_from = segment.source_in + (from - segment.frame);
+            let source_to = segment.source_in + (to - segment.frame);
+            let first = (source_from as f64 / fps * BLOCKS_PER_SECOND as f64).floor() as usize;
+            let last = (source_to as f64 / fps * BLOCKS_PER_SECOND as f64).ceil() as usize;
+            for (index, &(min, max)) in blocks.iter().enumerate().take(last).skip(first) {
+                let source_frame = index as f64 / BLOCKS_PER_SECOND as f64 * fps;
+                let frame = segment.frame as f64 + source_frame - segment.source_in as f64;
+                let bucket = ((frame - start_frame as f64) / bucket_frames).floor();
+                if bucket < 0.0 || bucket >= buckets as f64 {
+                    continue;
+                }
+                let peak = peaks[bucket as usize].get_or_insert((0.0, 0.0));
+                peak.0 = peak.0.min(min);
+                peak.1 = peak.1.max(max);
+            }
+        }
+
+        // Buckets without audio are silent
+        let round = |value: f32| (value * 10_000.0).round() / 10_000.0;
+        let peaks: Vec<[f32; 2]> = peaks
+            .into_iter()
+            .map(|peak| {
+                let (min, max) = peak.unwrap_or((0.0, 0.0));
+                [round(min), round(max)]
+            })
+            .collect();
+
+        let mut response = json!({
+            "result": format!(
+                "Computed {} waveform buckets for frames {} to {}",
+                peaks.len(),
+                start_frame,
+                end_frame
+            ),
+            "clip_name": args["clip_name"],
+            "timeline_name": timeline_name,
+            "frame_rate": rate.to_string(),
+            "start_frame": start_frame,
+            "end_frame": end_frame,
+            "bucket_frames": bucket_frames,
+            "sources": sources,
+            "operation_id": Uuid::new_v4().to_string()
+        });
+        let peaks = peaks.into_iter().map(|peak| json!(peak));
+        self.paginate("get_audio_waveform", &mut response, "peaks", peaks, &args)
+            .await?;
+        Ok(response)
+    }
+}
@@ -1,6 +1,11 @@
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 
+mod secrets;
+pub(crate) use secrets::redacted_arguments;
+pub use secrets::Secret;
+
 /// Configuration for the DaVinci Resolve MCP server
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
@@ -10,6 +15,76 @@ pub struct Config {
     pub logging: LoggingConfig,
     /// DaVinci Resolve specific settings
     pub resolve: ResolveConfig,
+    /// Media storage browsing settings
+    #[serde(default)]
+    pub media_storage: MediaStorageConfig,
+    /// Directories tools may read and write files in
+    #[serde(default)]
+    pub sandbox: SandboxConfig,
+    /// Limits on how much state the server will hold
+    #[serde(default)]
+    pub limits: LimitsConfig,
+    /// Project backup settings
+    #[serde(default)]
+    pub backup: BackupConfig,
+    /// How long deleted media, timelines and color presets can be restored
+    #[serde(default)]
+    pub trash: TrashConfig,
+    /// Local HTTP server for share links to rendered outputs
+    #[serde(default)]
+    pub share: ShareConfig,
+    /// Media analysis cache settings
+    #[serde(default)]
+    pub cache: CacheConfig,
+    /// Paging of large list responses
+    #[serde(default)]
+    pub pagination: PaginationConfig,
+    /// Actions run when render jobs complete
+    #[serde(default)]
+    pub hooks: HooksConfig,
+    /// External webhooks that receive server events
+    #[serde(default)]
+    pub webhooks: WebhooksConfig,
+    /// How much CPU-heavy work may run at once
+    #[serde(default)]
+    pub concurrency: ConcurrencyConfig,
+    /// Recording real Resolve responses and answering from them
+    #[serde(default)]
+    pub fixtures: FixturesConfig,
+    /// Mirroring real Resolve state into the server's state in Real mode
+    #[serde(default)]
+    pub state_sync: StateSyncConfig,
+    /// Default slate template for add_slate
+    #[serde(default)]
+    pub slate: SlateConfig,
+    /// How comments exported from review platforms are imported, per adapter
+    #[serde(default)]
+    pub review_import: ReviewImportConfig,
+    /// LUT folders indexed for list_luts and apply_lut
+    #[serde(default)]
+    pub luts: LutConfig,
+    /// Metadata schemas enforced on clip metadata edits and imports
+    #[serde(default)]
+    pub metadata: MetadataConfig,
+    /// Named sequences of API calls run with run_action, by action name
+    #[serde(default)]
+    pub actions: BTreeMap<String, ActionConfig>,
+    /// Editors sharing the server, by user name
+    #[serde(default)]
+    pub users: BTreeMap<String, UserConfig>,
+    /// Language of tool descriptions and result messages
+    #[serde(default)]
+    pub locale: LocaleConfig,
+    /// Project, timeline and page sessions start from
+    #[serde(default)]
+    pub bootstrap: BootstrapConfig,
+    /// Versions of clip grades kept for get_grade_history and revert_grade_to
+    #[serde(default)]
+    pub grade_history: GradeHistoryConfig,
+    /// Sets of renders queued together per timeline with queue_delivery_package,
+    /// by package name
+    #[serde(default)]
+    pub delivery_packages: BTreeMap<String, DeliveryPackageConfig>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -42,6 +117,10 @@ pub struct ResolveConfig {
     pub retry_attempts: u32,
     /// Default project settings
     pub default_project: DefaultProjectConfig,
+    /// Directory holding the DaVinciResolveScript module, searched before
+    /// Resolve's install location (e.g. a fixture module for tests)
+    #[serde(default)]
+    pub scripting_modules: Option<PathBuf>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -56,12 +135,734 @@ pub struct DefaultProjectConfig {
     pub color_space: String,
 }
 
+/// Media storage locations the server may browse and import from,
+/// like the Media Storage locations in Resolve's preferences
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MediaStorageConfig {
+    /// Absolute paths that may be browsed; nothing is browsable when empty
+    pub allowed_paths: Vec<PathBuf>,
+}
+
+/// Directories that file paths given to tools must lie in, so the server can
+/// run in shared environments. Paths are not restricted when the list is empty.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SandboxConfig {
+    /// Absolute root directories; everything below them is allowed
+    pub allowed_roots: Vec<PathBuf>,
+}
+
+/// Upper bounds on state growth, so a runaway client cannot exhaust memory
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LimitsConfig {
+    /// Maximum number of timelines
+    pub max_timelines: usize,
+    /// Maximum number of clips in the media pool
+    pub max_clips: usize,
+    /// Maximum number of keyframes on a single timeline item
+    pub max_keyframes_per_item: usize,
+    /// Maximum number of markers on a single timeline
+    pub max_markers_per_timeline: usize,
+    /// Maximum number of audit log entries; the oldest are dropped
+    pub max_audit_entries: usize,
+}
+
+impl Default for LimitsConfig {
+    fn default() -> Self {
+        Self {
+            max_timelines: 500,
+            max_clips: 10_000,
+            max_keyframes_per_item: 1_000,
+            max_markers_per_timeline: 1_000,
+            max_audit_entries: 1_000,
+        }
+    }
+}
+
+/// Project backup rotation settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BackupConfig {
+    /// Number of most recent backups to keep; older ones are dropped
+    pub max_backups: usize,
+    /// Directory for backup files (backups stay in memory only when None)
+    pub directory: Option<PathBuf>,
+}
+
+impl Default for BackupConfig {
+    fn default() -> Self {
+        Self {
+            max_backups: 10,
+            directory: None,
+        }
+    }
+}
+
+/// Trash that deleted media, timelines and color presets are moved into
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TrashConfig {
+    /// Hours a deleted entity can be restored for; 0 deletes permanently
+    pub retention_hours: u64,
+}
+
+impl Default for TrashConfig {
+    fn default() -> Self {
+        Self {
+            retention_hours: 72,
+        }
+    }
+}
+
+/// Local HTTP server that share links to rendered outputs point at. It is
+/// started by the first get_share_link call and serves only linked files.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ShareConfig {
+    /// Whether rendered outputs can be shared; off by default
+    pub enabled: bool,
+    /// Address the server listens on
+    pub bind_address: String,
+    /// Port the server listens on; 0 picks a free one
+    pub port: u16,
+    /// Directory outputs must lie in to be shared; any output can be when None
+    pub root: Option<PathBuf>,
+    /// Start of every link, for a server reached through a proxy or by
+    /// another host name; defaults to `http://<bind_address>:<port>`
+    pub public_url: Option<String>,
+    /// Hours a link works for unless the call asks for less
+    pub link_ttl_hours: u64,
+}
+
+impl Default for ShareConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            bind_address: "127.0.0.1".to_string(),
+            port: 8787,
+            root: None,
+            public_url: None,
+            link_ttl_hours: 24,
+        }
+    }
+}
+
+/// Caches for media analysis: waveform peaks on disk and decoded frames in memory
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CacheConfig {
+    /// Cache directory (results are recomputed on every request when None)
+    pub directory: Option<PathBuf>,
+    /// Memory kept for decoded still frames, in megabytes; 0 disables the frame cache
+    pub max_frame_cache_mb: u64,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            directory: None,
+            max_frame_cache_mb: 256,
+        }
+    }
+}
+
+/// How large list responses are split into pages
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PaginationConfig {
+    /// Items returned per page when a request does not ask for a page size
+    pub default_page_size: usize,
+    /// Largest page size a request may ask for
+    pub max_page_size: usize,
+    /// Seconds a cursor stays valid after it was last used
+    pub cursor_ttl_seconds: u64,
+    /// Directory for stored pages (the system temp directory when None)
+    pub directory: Option<PathBuf>,
+}
+
+impl Default for PaginationConfig {
+    fn default() -> Self {
+        Self {
+            default_page_size: 500,
+            max_page_size: 10_000,
+            cursor_ttl_seconds: 900,
+            directory: None,
+        }
+    }
+}
+
+/// Actions run after each render job completes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HooksConfig {
+    /// Hooks run in order for every completed render job
+    pub post_render: Vec<PostRenderHook>,
+    /// Seconds to wait for a render's output file to appear and stop growing,
+    /// and for each command or webhook to finish
+    pub timeout_seconds: u64,
+}
+
+impl Default for HooksConfig {
+    fn default() -> Self {
+        Self {
+            post_render: Vec::new(),
+            timeout_seconds: 30,
+        }
+    }
+}
+
+/// One post-render action, such as `{ action = "copy", destination = "/mnt/deliveries" }`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum PostRenderHook {
+    /// Copy the output file into a directory
+    Copy { destination: PathBuf },
+    /// Move the output file into a directory; later hooks see the new path
+    Move { destination: PathBuf },
+    /// Run a shell command with the job in `DAVINCI_RENDER_*` environment variables
+    Command { command: String },
+    /// POST the job as JSON to a URL, sent with curl
+    Webhook { url: String },
+}
+
+/// Webhook endpoints that receive JSON events such as `render.completed`,
+/// `transcription.completed` and `project.saved`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WebhooksConfig {
+    /// Endpoints events are posted to
+    pub endpoints: Vec<WebhookEndpoint>,
+    /// Delivery attempts per event and endpoint, including the first
+    pub max_attempts: u32,
+    /// Delay before the first retry in milliseconds, doubled for each retry after it
+    pub initial_backoff_ms: u64,
+    /// Seconds to wait for each delivery attempt
+    pub timeout_seconds: u64,
+}
+
+impl Default for WebhooksConfig {
+    fn default() -> Self {
+        Self {
+            endpoints: Vec::new(),
+            max_attempts: 5,
+            initial_backoff_ms: 1_000,
+            timeout_seconds: 10,
+        }
+    }
+}
+
+/// Weighted limits on CPU-heavy work, so one client cannot saturate the
+/// workstation Resolve runs on. Work waits in line until its weight fits.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ConcurrencyConfig {
+    /// Total weight of work that may run at once
+    pub max_weight: u32,
+    /// Weight of each render job
+    pub render_weight: u32,
+    /// Weight of each proxy or optimized media generation
+    pub proxy_weight: u32,
+    /// Weight of each transcription
+    pub transcription_weight: u32,
+    /// Weight of each background analysis job, such as delivery QC
+    pub analysis_weight: u32,
+}
+
+impl Default for ConcurrencyConfig {
+    fn default() -> Self {
+        Self {
+            max_weight: 4,
+            render_weight: 2,
+            proxy_weight: 2,
+            transcription_weight: 1,
+            analysis_weight: 1,
+        }
+    }
+}
+
+/// Fixtures of real Resolve API responses. Recording appends each successful
+/// real-mode call to the fixture file; playback answers calls that match a
+/// recorded method and arguments from the file before simulating them.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FixturesConfig {
+    pub mode: FixtureMode,
+    /// JSON lines file the fixtures are written to or read from
+    pub path: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FixtureMode {
+    #[default]
+    Off,
+    Record,
+    Playback,
+}
+
+/// Periodic snapshots of the real project in Real mode. Each one copies the
+/// project name, timelines, markers and render queue into the server's state,
+/// which read tools fall back to when a call to Resolve fails.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StateSyncConfig {
+    /// Seconds between snapshots; 0 syncs only when sync_real_state is called
+    pub interval_seconds: u64,
+}
+
+impl Default for StateSyncConfig {
+    fn default() -> Self {
+        Self {
+            interval_seconds: 10,
+        }
+    }
+}
+
+/// Slate template for add_slate. Project, Timeline, Date and TRT are filled
+/// in from the timeline; other fields, such as the audio configuration, take
+/// their value from `values` or from the call.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SlateConfig {
+    /// Fields shown on the slate, in order
+    pub fields: Vec<String>,
+    /// Fixed values for fields such as Client or Agency
+    pub values: BTreeMap<String, String>,
+    /// How long the slate is held, in seconds
+    pub slate_seconds: f64,
+    /// Length of the countdown between slate and program, in seconds; none when 0
+    pub countdown_seconds: f64,
+}
+
+impl Default for SlateConfig {
+    fn default() -> Self {
+        Self {
+            fields: ["Project", "Timeline", "Date", "TRT", "Audio"]
+                .map(String::from)
+                .to_vec(),
+            values: BTreeMap::from([("Audio".to_string(), "Stereo".to_string())]),
+            slate_seconds: 10.0,
+            countdown_seconds: 10.0,
+        }
+    }
+}
+
+/// LUT folders, like the LUT folders listed in Resolve's project settings.
+/// Their .cube, .3dl, .vlt, .ilut and .olut files are indexed, subfolders
+/// included, and applied by their path relative to the folder.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LutConfig {
+    /// Absolute folder paths, searched in order; the first folder holding a
+    /// relative path wins
+    pub directories: Vec<PathBuf>,
+    /// Rescan before the index is used whenever a folder gained, lost or
+    /// renamed files since the last scan
+    pub watch: bool,
+    /// What apply_lut does when a LUT expects another input color space than
+    /// the node it is applied to receives
+    pub color_space_check: LutColorSpaceCheck,
+    /// Color spaces of LUTs whose names do not say, by the name apply_lut
+    /// takes, the file name or the full path
+    pub color_spaces: BTreeMap<String, LutColorSpaces>,
+}
+
+impl Default for LutConfig {
+    fn default() -> Self {
+        Self {
+            directories: Vec::new(),
+            watch: true,
+            color_space_check: LutColorSpaceCheck::default(),
+            color_spaces: BTreeMap::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LutColorSpaceCheck {
+    Off,
+    /// Apply the LUT and warn
+    #[default]
+    Warn,
+    /// Refuse the LUT unless the call allows the mismatch
+    Block,
+}
+
+/// The color space a LUT converts from, and the one it converts to
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LutColorSpaces {
+    pub input: String,
+    #[serde(default)]
+    pub output: Option<String>,
+}
+
+/// Metadata schemas, like Resolve's metadata presets with rules attached.
+/// The active schema is enforced when clip metadata is edited or imported;
+/// validate_metadata checks the media pool against any of them.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MetadataConfig {
+    /// Schema enforced on edits and imports; nothing is enforced when None
+    pub schema: Option<String>,
+    /// Schemas by name
+    pub schemas: BTreeMap<String, MetadataSchema>,
+    /// Rules auto_organize_media sorts clips into bins by, tried in order
+    pub bin_rules: Vec<BinRule>,
+}
+
+/// A bin chosen for a clip from one of its properties
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BinRule {
+    /// Property the bin is chosen by
+    pub by: BinRuleKey,
+    /// Metadata field read when `by` is `metadata`, such as `Scene`
+    #[serde(default)]
+    pub field: Option<String>,
+    /// Bin name, with `{value}` standing for the value read, such as
+    /// `Scene {value}`; the value alone by default
+    #[serde(default = "default_bin_template")]
+    pub bin: String,
+}
+
+fn default_bin_template() -> String {
+    "{value}".to_string()
+}
+
+impl BinRule {
+    /// Why the rule cannot sort clips, if it cannot
+    pub fn problem(&self) -> Option<String> {
+        if self.bin.trim().is_empty() {
+            return Some("has an empty bin name".to_string());
+        }
+        let field = self.field.as_deref().map(str::trim).unwrap_or_default();
+        (self.by == BinRuleKey::Metadata && field.is_empty())
+            .then(|| "sorts by metadata but names no field".to_string())
+    }
+}
+
+/// Clip properties bins can be chosen by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BinRuleKey {
+    /// Day the clip was recorded, as YYYY-MM-DD
+    Date,
+    /// Camera #, or the camera model when there is none
+    Camera,
+    /// Reel Name
+    Reel,
+    /// Frame size, such as 3840x2160
+    Resolution,
+    /// Any metadata field, named by the rule
+    Metadata,
+}
+
+/// Rules for the metadata fields of every clip
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MetadataSchema {
+    /// What the schema is for, listed with validate_metadata reports
+    pub description: String,
+    /// Rules by metadata field, such as `Scene` or `Camera #`
+    pub fields: BTreeMap<String, MetadataFieldRule>,
+}
+
+/// What values a metadata field may hold
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MetadataFieldRule {
+    /// Every clip must have a value
+    pub required: bool,
+    /// Regular expression a value must match as a whole, such as `\d+[A-Z]?`
+    pub pattern: Option<String>,
+    /// Values the field may hold; any when empty
+    pub allowed_values: Vec<String>,
+}
+
+/// Comment import from review platforms with import_review_comments. Each
+/// adapter places comments on the timeline with its own settings.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReviewImportConfig {
+    /// Frame.io comment CSV exports
+    pub frameio: ReviewAdapterConfig,
+    /// Vimeo review comment JSON exports
+    pub vimeo: ReviewAdapterConfig,
+}
+
+impl Default for ReviewImportConfig {
+    fn default() -> Self {
+        Self {
+            frameio: ReviewAdapterConfig {
+                marker_color: "Purple".to_string(),
+                ..ReviewAdapterConfig::default()
+            },
+            vimeo: ReviewAdapterConfig {
+                marker_color: "Cyan".to_string(),
+                ..ReviewAdapterConfig::default()
+            },
+        }
+    }
+}
+
+/// Where one review platform's comments land on the timeline
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReviewAdapterConfig {
+    /// Whether comments become timeline markers or review notes
+    pub target: ReviewImportTarget,
+    /// Timecode the platform shows on the review file's first frame
+    pub file_start_timecode: String,
+    /// Frames added to each comment's position in the review file to place it
+    /// on the timeline; negative when the file opens with a slate or leader
+    pub offset_frames: i64,
+    /// Color of imported markers
+    pub marker_color: String,
+    /// Whether comments marked done on the platform are imported
+    pub include_completed: bool,
+}
+
+impl Default for ReviewAdapterConfig {
+    fn default() -> Self {
+        Self {
+            target: ReviewImportTarget::default(),
+            file_start_timecode: "00:00:00:00".to_string(),
+            offset_frames: 0,
+            marker_color: "Blue".to_string(),
+            include_completed: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReviewImportTarget {
+    #[default]
+    Markers,
+    Notes,
+}
+
+/// One webhook receiver
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookEndpoint {
+    /// http:// or https:// URL events are posted to
+    pub url: String,
+    /// Event types to send; every event when empty
+    #[serde(default)]
+    pub events: Vec<String>,
+    /// Shared secret for the `X-DaVinci-Signature` HMAC-SHA256 header;
+    /// requests are unsigned when None
+    #[serde(default)]
+    pub secret: Option<Secret>,
+}
+
+/// A named sequence of API calls, such as `prep_dailies`, run with run_action
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ActionConfig {
+    /// What the action does, listed with the available actions
+    #[serde(default)]
+    pub description: String,
+    /// Projects the action can run in; every project when empty
+    #[serde(default)]
+    pub projects: Vec<String>,
+    /// Parameters the caller must give, written `{{name}}` in step arguments
+    #[serde(default)]
+    pub parameters: Vec<String>,
+    /// Calls made in order; the action stops at the first one that fails
+    pub steps: Vec<ActionStep>,
+}
+
+/// An editor sharing the server, recognised by the MCP client they connect
+/// with or by a token given to `identify`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UserConfig {
+    /// Secret the user identifies with; they can only be recognised by client when None
+    pub token: Option<Secret>,
+    /// MCP client names (clientInfo.name) that identify as this user
+    pub clients: Vec<String>,
+    /// Bin media is imported into when no bin is named
+    pub default_bin: Option<String>,
+    /// Render preset queued when no preset is named
+    pub default_render_preset: Option<String>,
+    /// Locale of tool descriptions and messages when the client asks for none
+    pub locale: Option<String>,
+}
+
+/// Locales tool descriptions and result messages are shown in. English is
+/// built in; other locales are catalogs in `directory`, one JSON file per
+/// locale named after its tag, such as `de.json` or `pt-BR.json`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LocaleConfig {
+    /// Locale used when neither the client nor its user asks for one
+    pub default: String,
+    /// Directory of community locale catalogs; only English when None
+    pub directory: Option<PathBuf>,
+}
+
+impl Default for LocaleConfig {
+    fn default() -> Self {
+        Self {
+            default: "en".to_string(),
+            directory: None,
+        }
+    }
+}
+
+/// Where sessions start. The project, timeline and page set here are opened
+/// when the server starts, in either mode; bootstrap_session applies them
+/// again, or applies one of the named profiles instead.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BootstrapConfig {
+    /// What the server opens at startup; nothing is opened when all unset
+    #[serde(flatten)]
+    pub startup: BootstrapProfile,
+    /// Profiles bootstrap_session can apply, by name
+    pub profiles: BTreeMap<String, BootstrapProfile>,
+}
+
+/// A known state to start a session from
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BootstrapProfile {
+    /// What the profile is for, listed by bootstrap_session
+    pub description: String,
+    /// Project opened; the open project is kept when None
+    pub project: Option<String>,
+    /// Timeline made current, by name; the current timeline is kept when None
+    pub timeline: Option<String>,
+    /// Page switched to, such as `edit` or `color`; the page is kept when None
+    pub page: Option<String>,
+}
+
+impl BootstrapProfile {
+    /// Whether applying the profile changes nothing
+    pub fn is_empty(&self) -> bool {
+        self.project.is_none() && self.timeline.is_none() && self.page.is_none()
+    }
+}
+
+/// Grading history. While it is on, every call that changes a clip's grade
+/// keeps the grade it leaves as a new version of that clip's grade, which
+/// revert_grade_to can go back to. set_grade_history turns it on and off
+/// while the server runs.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GradeHistoryConfig {
+    /// Keep versions from the start; off until set_grade_history turns it on
+    pub enabled: bool,
+    /// Grab a gallery still of each version into the Grade History album
+    pub thumbnails: bool,
+    /// Versions kept per clip; the oldest are dropped first
+    pub max_versions: usize,
+}
+
+impl Default for GradeHistoryConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            thumbnails: false,
+            max_versions: 100,
+        }
+    }
+}
+
+/// Renders made of each timeline in a delivery, such as a ProRes master, an
+/// H.264 review copy and audio stems
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DeliveryPackageConfig {
+    /// What the package is for, listed with its outputs
+    pub description: String,
+    /// Outputs queued for each timeline, in queue order
+    pub outputs: Vec<DeliveryOutputConfig>,
+}
+
+/// One render in a delivery package
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeliveryOutputConfig {
+    /// Name added to the output's file name, such as `master` or `review`
+    pub name: String,
+    /// Render preset the output is rendered with
+    pub preset: String,
+    /// Render the video track; the preset's setting when None
+    #[serde(default)]
+    pub export_video: Option<bool>,
+    /// Render the audio track; the preset's setting when None
+    #[serde(default)]
+    pub export_audio: Option<bool>,
+    /// Audio channel layout; the preset's when None
+    #[serde(default)]
+    pub audio_channel_layout: Option<String>,
+}
+
+impl DeliveryPackageConfig {
+    /// Why the package cannot be queued, if it cannot
+    pub fn problem(&self) -> Option<String> {
+        if self.outputs.is_empty() {
+            return Some("has no outputs".to_string());
+        }
+        let mut names = std::collections::BTreeSet::new();
+        for output in &self.outputs {
+            let name = output.name.trim();
+            if name.is_empty() || name.contains(['/', '\\']) {
+                return Some(format!(
+                    "has an output named '{}'; names must be non-empty and hold no slashes",
+                    output.name
+                ));
+            }
+            if !names.insert(name) {
+                return Some(format!("has two outputs named '{}'", name));
+            }
+            if output.preset.trim().is_empty() {
+                return Some(format!("output '{}' names no preset", name));
+            }
+        }
+        None
+    }
+}
+
+/// One API call in an action
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActionStep {
+    /// API method, as with submit_job
+    pub method: String,
+    /// Arguments for the call, with `{{name}}` placeholders for parameters
+    #[serde(default)]
+    pub arguments: serde_json::Value,
+}
+
 impl Default for Config {
     fn default() -> Self {
         Self {
             server: ServerConfig::default(),
             logging: LoggingConfig::default(),
             resolve: ResolveConfig::default(),
+            media_storage: MediaStorageConfig::default(),
+            sandbox: SandboxConfig::default(),
+            limits: LimitsConfig::default(),
+            backup: BackupConfig::default(),
+            trash: TrashConfig::default(),
+            share: ShareConfig::default(),
+            cache: CacheConfig::default(),
+            pagination: PaginationConfig::default(),
+            hooks: HooksConfig::default(),
+            webhooks: WebhooksConfig::default(),
+            concurrency: ConcurrencyConfig::default(),
+            fixtures: FixturesConfig::default(),
+            state_sync: StateSyncConfig::default(),
+            slate: SlateConfig::default(),
+            review_import: ReviewImportConfig::default(),
+            luts: LutConfig::default(),
+            metadata: MetadataConfig::default(),
+            actions: BTreeMap::new(),
+            users: BTreeMap::new(),
+            locale: LocaleConfig::default(),
+            bootstrap: BootstrapConfig::default(),
+            grade_history: GradeHistoryConfig::default(),
+            delivery_packages: BTreeMap::new(),
         }
     }
 }
@@ -93,6 +894,7 @@ impl Default for ResolveConfig {
             connection_timeout: 10,
             retry_attempts: 3,
             default_project: DefaultProjectConfig::default(),
+            scripting_modules: None,
         }
     }
 }
@@ -153,6 +955,288 @@ impl Config {
             ));
         }
 
+        // Validate resource limits
+        let limits = [
+            ("max_timelines", self.limits.max_timelines),
+            ("max_clips", self.limits.max_clips),
+            ("max_keyframes_per_item", self.limits.max_keyframes_per_item),
+            (
+                "max_markers_per_timeline",
+                self.limits.max_markers_per_timeline,
+            ),
+        ];
+        if let Some((name, _)) = limits.iter().find(|(_, limit)| *limit == 0) {
+            return Err(format!("Limit {} must be greater than zero", name));
+        }
+
+        // Validate backup rotation
+        if self.backup.max_backups == 0 {
+            return Err("Backup rotation must keep at least one backup".to_string());
+        }
+
+        // Validate share links
+        if self.share.link_ttl_hours == 0 {
+            return Err("Share link lifetime must be greater than zero".to_string());
+        }
+        if let Some(root) = self.share.root.as_ref().filter(|root| !root.is_absolute()) {
+            return Err(format!("Share root must be absolute: {}", root.display()));
+        }
+        if let Some(url) = self
+            .share
+            .public_url
+            .as_ref()
+            .filter(|url| !url.starts_with("http://") && !url.starts_with("https://"))
+        {
+            return Err(format!(
+                "Share public URL must start with http:// or https://: {}",
+                url
+            ));
+        }
+
+        // Validate post-render hooks
+        if self.hooks.timeout_seconds == 0 {
+            return Err("Hook timeout must be greater than zero".to_string());
+        }
+        for hook in &self.hooks.post_render {
+            match hook {
+                PostRenderHook::Copy { destination } | PostRenderHook::Move { destination }
+                    if !destination.is_absolute() =>
+                {
+                    return Err(format!(
+                        "Post-render hook destination must be absolute: {}",
+                        destination.display()
+                    ));
+                }
+                PostRenderHook::Command { command } if command.trim().is_empty() => {
+                    return Err("Post-render hook command must not be empty".to_string());
+                }
+                PostRenderHook::Webhook { url }
+                    if !url.starts_with("http://") && !url.starts_with("https://") =>
+                {
+                    return Err(format!(
+                        "Post-render webhook URL must start with http:// or https://: {}",
+                        url
+                    ));
+                }
+                _ => {}
+            }
+        }
+
+        // Validate webhook endpoints
+        if self.webhooks.max_attempts == 0 || self.webhooks.timeout_seconds == 0 {
+            return Err("Webhook attempts and timeout must be greater than zero".to_string());
+        }
+        if let Some(endpoint) = self.webhooks.endpoints.iter().find(|endpoint| {
+            !endpoint.url.starts_with("http://") && !endpoint.url.starts_with("https://")
+        }) {
+            return Err(format!(
+                "Webhook URL must start with http:// or https://: {}",
+                endpoint.url
+            ));
+        }
+        for endpoint in &self.webhooks.endpoints {
+            if let Some(secret) = &endpoint.secret {
+                secret.reveal().map_err(|e| {
+                    format!("Webhook secret for {} cannot be read: {}", endpoint.url, e)
+                })?;
+            }
+        }
+
+        // Validate concurrency weights
+        let weights = [
+            ("render_weight", self.concurrency.render_weight),
+            ("proxy_weight", self.concurrency.proxy_weight),
+            (
+                "transcription_weight",
+                self.concurrency.transcription_weight,
+            ),
+            ("analysis_weight", self.concurrency.analysis_weight),
+        ];
+        if let Some((name, _)) = weights
+            .iter()
+            .find(|(_, weight)| *weight == 0 || *weight > self.concurrency.max_weight)
+        {
+            return Err(format!(
+                "Concurrency {} must be between 1 and max_weight ({})",
+                name, self.concurrency.max_weight
+            ));
+        }
+
+        // Validate fixtures
+        match (self.fixtures.mode, &self.fixtures.path) {
+            (FixtureMode::Off, _) => {}
+            (_, None) => return Err("Fixture recording and playback need a path".to_string()),
+            (FixtureMode::Playback, Some(path)) if !path.is_file() => {
+                return Err(format!("Fixture file not found: {}", path.display()));
+            }
+            _ => {}
+        }
+
+        // Validate slate timing
+        let timing_valid = self.slate.slate_seconds > 0.0 && self.slate.countdown_seconds >= 0.0;
+        if !timing_valid {
+            return Err(
+                "Slate must last longer than zero seconds and countdown zero or more".to_string(),
+            );
+        }
+
+        // Validate LUT folders
+        if let Some(directory) = self
+            .luts
+            .directories
+            .iter()
+            .find(|directory| !directory.is_absolute())
+        {
+            return Err(format!(
+                "LUT directory must be absolute: {}",
+                directory.display()
+            ));
+        }
+        if let Some(lut) = self
+            .luts
+            .color_spaces
+            .iter()
+            .find_map(|(lut, spaces)| spaces.input.trim().is_empty().then_some(lut))
+        {
+            return Err(format!("LUT {} needs an input color space", lut));
+        }
+
+        // Validate metadata schemas
+        if let Some(schema) = self
+            .metadata
+            .schema
+            .as_ref()
+            .filter(|schema| !self.metadata.schemas.contains_key(*schema))
+        {
+            return Err(format!(
+                "Active metadata schema '{}' is not defined",
+                schema
+            ));
+        }
+        for (name, schema) in &self.metadata.schemas {
+            for (field, rule) in &schema.fields {
+                if let Some(pattern) = &rule.pattern {
+                    regex::Regex::new(pattern).map_err(|e| {
+                        format!(
+                            "Metadata schema '{}' has an invalid pattern for '{}': {}",
+                            name, field, e
+                        )
+                    })?;
+                }
+            }
+        }
+
+        for (index, rule) in self.metadata.bin_rules.iter().enumerate() {
+            if let Some(problem) = rule.problem() {
+                return Err(format!("Bin rule {} {}", index + 1, problem));
+            }
+        }
+
+        // Validate actions
+        for (name, action) in &self.actions {
+            if action.steps.is_empty() {
+                return Err(format!("Action '{}' must have at least one step", name));
+            }
+            if let Some(step) = action
+                .steps
+                .iter()
+                .find(|step| step.method.trim().is_empty() || step.method == "run_action")
+            {
+                return Err(format!(
+                    "Action '{}' has an invalid step method '{}'; actions cannot run other actions",
+                    name, step.method
+                ));
+            }
+        }
+
+        // Validate bootstrap profiles
+        let pages = [
+            "media",
+            "cut",
+            "edit",
+            "fusion",
+            "color",
+            "fairlight",
+            "deliver",
+        ];
+        let profiles = std::iter::once(("startup", &self.bootstrap.startup)).chain(
+            self.bootstrap
+                .profiles
+                .iter()
+                .map(|(name, profile)| (name.as_str(), profile)),
+        );
+        for (name, profile) in profiles {
+            if let Some(page) = profile
+                .page
+                .as_ref()
+                .filter(|page| !pages.contains(&page.as_str()))
+            {
+                return Err(format!(
+                    "Bootstrap profile '{}' has an invalid page '{}'",
+                    name, page
+                ));
+            }
+            if name != "startup" && profile.is_empty() {
+                return Err(format!(
+                    "Bootstrap profile '{}' must set a project, timeline or page",
+                    name
+                ));
+            }
+        }
+
+        if self.grade_history.max_versions == 0 {
+            return Err("Grade history must keep at least one version per clip".to_string());
+        }
+
+        for (name, package) in &self.delivery_packages {
+            if let Some(problem) = package.problem() {
+                return Err(format!("Delivery package '{}' {}", name, problem));
+            }
+        }
+
+        // Validate users; a token or client must pick out one user
+        let mut tokens = std::collections::BTreeSet::new();
+        let mut clients = std::collections::BTreeSet::new();
+        for (name, user) in &self.users {
+            if let Some(token) = &user.token {
+                let token = token
+                    .reveal()
+                    .map_err(|e| format!("Token of user '{}' cannot be read: {}", name, e))?;
+                if token.trim().is_empty() || !tokens.insert(token) {
+                    return Err(format!("User '{}' needs a non-empty, unique token", name));
+                }
+            }
+            if let Some(client) = user.clients.iter().find(|client| !clients.insert(*client)) {
+                return Err(format!(
+                    "Client '{}' of user '{}' already identifies another user",
+                    client, name
+                ));
+            }
+        }
+
+        // Validate sandbox roots
+        if let Some(root) = self
+            .sandbox
+            .allowed_roots
+            .iter()
+            .find(|root| !root.is_absolute())
+        {
+            return Err(format!("Sandbox root must be absolute: {}", root.display()));
+        }
+
+        // Validate media storage locations
+        if let Some(path) = self
+            .media_storage
+            .allowed_paths
+            .iter()
+            .find(|path| !path.is_absolute())
+        {
+            return Err(format!(
+                "Media storage path must be absolute: {}",
+                path.display()
+            ));
+        }
+
         Ok(())
     }
 }
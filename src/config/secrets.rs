@@ -0,0 +1,182 @@
+//! Secrets in the configuration
+//!
+//! Webhook signing keys and user tokens are [`Secret`]s, written in the
+//! config as a reference to where the value is kept: `env:NAME` reads an
+//! environment variable, `keyring:service/account` an entry in the OS
+//! keychain (macOS Keychain, Windows Credential Manager or the Linux kernel
+//! keyring) and `file:/path` a file only its owner may read. Any other string
+//! is the secret itself, as older configs have it.
+//!
+//! Values are read when they are used, through [`Secret::reveal`]. Debug
+//! output and serialized configs show the reference and never a value, so
+//! logging or dumping a config cannot leak what it protects.
+
+use serde::{Deserialize, Serialize, Serializer};
+use std::fmt;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+/// What an inline secret shows in place of its value
+const REDACTED: &str = "[redacted]";
+
+/// Words in an argument name that mark its value as secret
+const SECRET_ARGUMENTS: [&str; 4] = ["token", "password", "secret", "api_key"];
+
+/// Tool arguments with the values of secret-looking keys, such as the token
+/// given to identify, replaced, for logging
+pub(crate) fn redacted_arguments(args: &serde_json::Value) -> serde_json::Value {
+    use serde_json::Value;
+
+    match args {
+        Value::Object(fields) => Value::Object(
+            fields
+                .iter()
+                .map(|(key, value)| {
+                    let key_lower = key.to_ascii_lowercase();
+                    let value = if SECRET_ARGUMENTS.iter().any(|word| key_lower.contains(word)) {
+                        Value::String(REDACTED.to_string())
+                    } else {
+                        redacted_arguments(value)
+                    };
+                    (key.clone(), value)
+                })
+                .collect(),
+        ),
+        Value::Array(values) => Value::Array(values.iter().map(redacted_arguments).collect()),
+        value => value.clone(),
+    }
+}
+
+/// A secret and where it is read from
+#[derive(Clone, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "String")]
+#[non_exhaustive]
+pub enum Secret {
+    /// The value itself, written in the config
+    Inline(String),
+    /// An environment variable
+    Env(String),
+    /// An entry in the OS keychain
+    Keyring { service: String, account: String },
+    /// A file readable and writable by its owner alone (mode 0600 or stricter)
+    File(PathBuf),
+}
+
+impl Secret {
+    /// Read the value; errors name the reference, never the value
+    pub fn reveal(&self) -> Result<String, String> {
+        let value = match self {
+            Secret::Inline(value) => value.clone(),
+            Secret::Env(name) => std::env::var(name)
+                .map_err(|_| format!("environment variable {} is not set", name))?,
+            Secret::Keyring { service, account } => keyring::Entry::new(service, account)
+                .and_then(|entry| entry.get_password())
+                .map_err(|e| format!("cannot read {} from the OS keychain: {}", self, e))?,
+            Secret::File(path) => {
+                check_owner_only(path)?;
+                let value = std::fs::read_to_string(path)
+                    .map_err(|e| format!("cannot read {}: {}", path.display(), e))?;
+                value.trim_end_matches(['\r', '\n']).to_string()
+            }
+        };
+        if value.is_empty() {
+            return Err(format!("{} is empty", self));
+        }
+        Ok(value)
+    }
+}
+
+/// Refuse secret files other users can read or write
+#[cfg(unix)]
+fn check_owner_only(path: &std::path::Path) -> Result<(), String> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let mode = std::fs::metadata(path)
+        .map_err(|e| format!("cannot read {}: {}", path.display(), e))?
+        .permissions()
+        .mode();
+    if mode & 0o077 != 0 {
+        return Err(format!(
+            "{} is open to other users (mode {:o}); restrict it with chmod 600",
+            path.display(),
+            mode & 0o777
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn check_owner_only(_path: &std::path::Path) -> Result<(), String> {
+    Ok(())
+}
+
+impl FromStr for Secret {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        if let Some(name) = value.strip_prefix("env:") {
+            if name.is_empty() {
+                return Err("env: secret needs a variable name".to_string());
+            }
+            Ok(Secret::Env(name.to_string()))
+        } else if let Some(entry) = value.strip_prefix("keyring:") {
+            match entry.split_once('/') {
+                Some((service, account)) if !service.is_empty() && !account.is_empty() => {
+                    Ok(Secret::Keyring {
+                        service: service.to_string(),
+                        account: account.to_string(),
+                    })
+                }
+                _ => Err(format!(
+                    "keyring secret must be keyring:service/account, not '{}'",
+                    value
+                )),
+            }
+        } else if let Some(path) = value.strip_prefix("file:") {
+            let path = PathBuf::from(path);
+            if !path.is_absolute() {
+                return Err(format!(
+                    "secret file path must be absolute: {}",
+                    path.display()
+                ));
+            }
+            Ok(Secret::File(path))
+        } else {
+            Ok(Secret::Inline(value.to_string()))
+        }
+    }
+}
+
+impl TryFrom<String> for Secret {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+/// The reference as written in the config, with inline values redacted
+impl fmt::Display for Secret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Secret::Inline(_) => f.write_str(REDACTED),
+            Secret::Env(name) => write!(f, "env:{}", name),
+            Secret::Keyring { service, account } => write!(f, "keyring:{}/{}", service, account),
+            Secret::File(path) => write!(f, "file:{}", path.display()),
+        }
+    }
+}
+
+impl fmt::Debug for Secret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Secret")
+            .field(&format_args!("{}", self))
+            .finish()
+    }
+}
+
+impl Serialize for Secret {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
@@ -2,6 +2,7 @@ use thiserror::Error;
 
 /// Comprehensive error types for DaVinci Resolve MCP operations
 #[derive(Error, Debug)]
+#[non_exhaustive]
 pub enum ResolveError {
     #[error("DaVinci Resolve is not running")]
     NotRunning,
@@ -51,9 +52,15 @@ pub enum ResolveError {
     #[error("Permission denied: {operation}")]
     PermissionDenied { operation: String },
 
+    #[error("Path not allowed: {path} - {reason}")]
+    PathNotAllowed { path: String, reason: String },
+
     #[error("Timeout during operation: {operation}")]
     Timeout { operation: String },
 
+    #[error("Resource limit exceeded: {resource} (limit {limit})")]
+    ResourceLimitExceeded { resource: String, limit: usize },
+
     #[error("Internal error: {message}")]
     Internal { message: String },
 }
@@ -82,12 +89,82 @@ impl ResolveError {
         }
     }
 
+    /// Create a new resource limit exceeded error
+    pub fn resource_limit_exceeded(resource: impl Into<String>, limit: usize) -> Self {
+        Self::ResourceLimitExceeded {
+            resource: resource.into(),
+            limit,
+        }
+    }
+
+    /// Create a new path not allowed error
+    pub fn path_not_allowed(path: impl Into<String>, reason: impl Into<String>) -> Self {
+        Self::PathNotAllowed {
+            path: path.into(),
+            reason: reason.into(),
+        }
+    }
+
     /// Create a new internal error
     pub fn internal(message: impl Into<String>) -> Self {
         Self::Internal {
             message: message.into(),
         }
     }
+
+    /// Broad kind of the error, for callers that branch on what went wrong
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            Self::NotRunning => ErrorKind::NotRunning,
+            Self::ProjectNotFound { .. }
+            | Self::TimelineNotFound { .. }
+            | Self::MediaNotFound { .. }
+            | Self::BinNotFound { .. }
+            | Self::PresetNotFound { .. }
+            | Self::RenderNotFound { .. }
+            | Self::ToolNotFound { .. }
+            | Self::FileNotFound { .. } => ErrorKind::NotFound,
+            Self::InvalidTimelineItemId { .. }
+            | Self::InvalidNodeIndex { .. }
+            | Self::Serialization(_)
+            | Self::InvalidParameter { .. } => ErrorKind::InvalidArgument,
+            Self::ApiCall { .. } => ErrorKind::ApiCall,
+            Self::NotSupported { .. } => ErrorKind::NotSupported,
+            Self::PermissionDenied { .. } | Self::PathNotAllowed { .. } => {
+                ErrorKind::PermissionDenied
+            }
+            Self::Timeout { .. } => ErrorKind::Timeout,
+            Self::ResourceLimitExceeded { .. } => ErrorKind::LimitExceeded,
+            Self::Internal { .. } => ErrorKind::Internal,
+        }
+    }
+}
+
+/// What kind of failure a [`ResolveError`] is
+///
+/// Part of the stable API: variants of [`ResolveError`] may be split or
+/// reworded between releases, but each keeps mapping to the same kind.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// DaVinci Resolve or the project the call needs is not open
+    NotRunning,
+    /// A named project, timeline, clip, preset, job, tool or file does not exist
+    NotFound,
+    /// An argument is missing, of the wrong type or out of range
+    InvalidArgument,
+    /// DaVinci Resolve rejected the call
+    ApiCall,
+    /// The call is not available in this mode or build
+    NotSupported,
+    /// The call may not touch what it asked for
+    PermissionDenied,
+    /// The call took too long
+    Timeout,
+    /// The call would exceed a configured limit
+    LimitExceeded,
+    /// A bug in the server
+    Internal,
 }
 
 /// Result type alias for DaVinci Resolve operations
@@ -129,7 +206,13 @@ impl From<ResolveError> for rmcp::Error {
             ResolveError::PermissionDenied { .. } => {
                 rmcp::Error::internal_error(err.to_string(), None)
             }
+            ResolveError::PathNotAllowed { .. } => {
+                rmcp::Error::invalid_params(err.to_string(), None)
+            }
             ResolveError::Timeout { .. } => rmcp::Error::internal_error(err.to_string(), None),
+            ResolveError::ResourceLimitExceeded { .. } => {
+                rmcp::Error::invalid_request(err.to_string(), None)
+            }
             _ => rmcp::Error::internal_error(err.to_string(), None),
         }
     }
@@ -0,0 +1,239 @@
+//! Localized tool descriptions and result messages
+//!
+//! English is built in: descriptions and messages are written in English and
+//! shown as they are. Other locales are community catalogs, one JSON file per
+//! locale in the `[locale]` directory, named after its tag (`de.json`,
+//! `pt-BR.json`):
+//!
+//! ```json
+//! {
+//!   "tools": { "create_project": "Ein neues DaVinci Resolve-Projekt erstellen" },
+//!   "messages": {
+//!     "Successfully created project '{}'": "Projekt '{}' erfolgreich erstellt",
+//!     "Added timeline '{}' to render queue with preset '{}'":
+//!       "Timeline '{0}' mit Preset '{1}' in die Renderwarteschlange gestellt"
+//!   }
+//! }
+//! ```
+//!
+//! A message template matches a whole English message, each `{}` standing for
+//! a value such as a name or a count. The translation places the values with
+//! `{}` in order or `{0}`, `{1}` by position, for languages that put them in a
+//! different order. Anything a catalog leaves out stays in English, so a
+//! catalog can be shipped before it is complete.
+
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::config::LocaleConfig;
+
+/// Locale of the descriptions and messages as written
+pub const DEFAULT_LOCALE: &str = "en";
+
+/// One community locale, as its JSON file holds it
+#[derive(Debug, Default, Deserialize)]
+struct CatalogFile {
+    /// Tool descriptions by tool name
+    #[serde(default)]
+    tools: BTreeMap<String, String>,
+    /// Translations by English message template
+    #[serde(default)]
+    messages: BTreeMap<String, String>,
+}
+
+/// An English message template and its translation
+#[derive(Debug)]
+struct MessagePattern {
+    /// Text around the placeholders; one more than there are placeholders
+    literals: Vec<String>,
+    translation: String,
+}
+
+impl MessagePattern {
+    fn new(template: &str, translation: String) -> Self {
+        Self {
+            literals: template.split("{}").map(str::to_string).collect(),
+            translation,
+        }
+    }
+
+    /// Values in the placeholders if the message matches the whole template
+    fn capture<'a>(&self, message: &'a str) -> Option<Vec<&'a str>> {
+        let (first, rest) = self.literals.split_first()?;
+        let mut remaining = message.strip_prefix(first.as_str())?;
+        let Some((last, middle)) = rest.split_last() else {
+            return remaining.is_empty().then(Vec::new);
+        };
+        let mut values = Vec::with_capacity(rest.len());
+        for literal in middle {
+            let end = remaining.find(literal.as_str())?;
+            values.push(&remaining[..end]);
+            remaining = &remaining[end + literal.len()..];
+        }
+        values.push(remaining.strip_suffix(last.as_str())?);
+        Some(values)
+    }
+
+    /// The translation with the captured values in place
+    fn fill(&self, values: &[&str]) -> String {
+        let mut filled = String::with_capacity(self.translation.len());
+        let mut rest = self.translation.as_str();
+        let mut next = 0;
+        while let Some(open) = rest.find('{') {
+            filled.push_str(&rest[..open]);
+            rest = &rest[open..];
+            let placeholder = rest
+                .find('}')
+                .map(|close| (&rest[1..close], close))
+                .filter(|(inside, _)| inside.chars().all(|c| c.is_ascii_digit()));
+            match placeholder {
+                Some((inside, close)) => {
+                    let index = if inside.is_empty() {
+                        next += 1;
+                        next - 1
+                    } else {
+                        inside.parse().unwrap_or(usize::MAX)
+                    };
+                    filled.push_str(values.get(index).copied().unwrap_or_default());
+                    rest = &rest[close + 1..];
+                }
+                None => {
+                    filled.push('{');
+                    rest = &rest[1..];
+                }
+            }
+        }
+        filled.push_str(rest);
+        filled
+    }
+}
+
+/// Translations of one locale
+#[derive(Debug, Default)]
+pub struct Catalog {
+    tools: BTreeMap<String, String>,
+    /// Exact messages, looked up before the templates with placeholders
+    exact: BTreeMap<String, String>,
+    /// Templates with placeholders, most specific first
+    patterns: Vec<MessagePattern>,
+}
+
+impl Catalog {
+    fn from_file(file: CatalogFile) -> Self {
+        let mut catalog = Catalog {
+            tools: file.tools,
+            ..Catalog::default()
+        };
+        for (template, translation) in file.messages {
+            if template.contains("{}") {
+                catalog
+                    .patterns
+                    .push(MessagePattern::new(&template, translation));
+            } else {
+                catalog.exact.insert(template, translation);
+            }
+        }
+        // Longer literal text is the more specific template
+        catalog.patterns.sort_by_key(|pattern| {
+            std::cmp::Reverse(pattern.literals.iter().map(String::len).sum::<usize>())
+        });
+        catalog
+    }
+
+    /// Translated description of a tool
+    pub fn tool_description(&self, tool: &str) -> Option<&str> {
+        self.tools.get(tool).map(String::as_str)
+    }
+
+    /// Translation of a message, or None when the catalog has none
+    pub fn message(&self, message: &str) -> Option<String> {
+        if let Some(translation) = self.exact.get(message) {
+            return Some(translation.clone());
+        }
+        self.patterns
+            .iter()
+            .find_map(|pattern| pattern.capture(message).map(|values| pattern.fill(&values)))
+    }
+}
+
+/// Every locale the server can show, English and the community catalogs
+#[derive(Debug, Default)]
+pub struct Locales {
+    catalogs: BTreeMap<String, Catalog>,
+}
+
+impl Locales {
+    /// Load the community catalogs from the configured directory. Files that
+    /// cannot be read are skipped with a warning, leaving their locale out.
+    pub fn load(config: &LocaleConfig) -> Self {
+        let mut locales = Locales::default();
+        let Some(directory) = &config.directory else {
+            return locales;
+        };
+        let entries = match std::fs::read_dir(directory) {
+            Ok(entries) => entries,
+            Err(e) => {
+                tracing::warn!(
+                    "Cannot read locale directory {}: {}",
+                    directory.display(),
+                    e
+                );
+                return locales;
+            }
+        };
+        for path in entries.flatten().map(|entry| entry.path()) {
+            if path.extension().and_then(|extension| extension.to_str()) != Some("json") {
+                continue;
+            }
+            match Self::read_catalog(&path) {
+                Ok((tag, catalog)) => {
+                    locales.catalogs.insert(tag, catalog);
+                }
+                Err(e) => tracing::warn!("Skipping locale file {}: {}", path.display(), e),
+            }
+        }
+        locales
+    }
+
+    fn read_catalog(path: &Path) -> Result<(String, Catalog), String> {
+        let tag = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .ok_or("file name is not a locale tag")?
+            .to_string();
+        let text = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+        let file: CatalogFile = serde_json::from_str(&text).map_err(|e| e.to_string())?;
+        Ok((tag, Catalog::from_file(file)))
+    }
+
+    /// Tags of the locales that can be selected, English first
+    pub fn available(&self) -> Vec<&str> {
+        std::iter::once(DEFAULT_LOCALE)
+            .chain(self.catalogs.keys().map(String::as_str))
+            .collect()
+    }
+
+    /// The locale to use for a requested tag: the same tag, then its language
+    /// without the region (`de` for `de-AT`), compared without case; None
+    /// when neither is available
+    pub fn resolve(&self, requested: &str) -> Option<String> {
+        let requested = requested.trim().replace('_', "-");
+        let language = requested.split('-').next().unwrap_or_default();
+        let available = self.available();
+        for wanted in [requested.as_str(), language] {
+            if let Some(tag) = available
+                .iter()
+                .find(|tag| tag.eq_ignore_ascii_case(wanted))
+            {
+                return Some(tag.to_string());
+            }
+        }
+        None
+    }
+
+    /// Catalog of a locale; None for English, which needs no translation
+    pub fn catalog(&self, locale: &str) -> Option<&Catalog> {
+        self.catalogs.get(locale)
+    }
+}
@@ -1,8 +1,11 @@
+pub mod api;
 pub mod bridge;
 pub mod config;
 pub mod error;
+pub mod i18n;
 pub mod native;
 pub mod server;
+pub mod timecode;
 pub mod tools;
 
 pub use config::Config;
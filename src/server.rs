@@ -1,31 +1,43 @@
 use crate::{
-    bridge::{ConnectionMode, ResolveBridge},
+    bridge::{ConnectionMode, ResolveBridge, Sampler, SamplingReply, SamplingRequest},
     config::Config,
-    error::ResolveError,
+    error::{ResolveError, ResolveResult},
+    i18n::{Locales, DEFAULT_LOCALE},
     tools::handle_tool_call,
+    tools::registry::{self, ToolSpec},
 };
 use rmcp::{
     model::{
-        CallToolRequestMethod, CallToolRequestParam, CallToolResult, ClientNotification,
-        ClientRequest, Content, ErrorData, Implementation, InitializeResult, ListToolsResult,
-        ProtocolVersion, ServerCapabilities, ServerResult, Tool,
+        AnnotateAble, CallToolRequestMethod, CallToolRequestParam, CallToolResult,
+        ClientNotification, ClientRequest, Content, CreateMessageRequestParam, ErrorData,
+        Implementation, InitializeResult, ListResourcesResult, ListToolsResult, ProtocolVersion,
+        RawResource, ReadResourceResult, Resource, ResourceContents, Role, SamplingMessage,
+        ServerCapabilities, ServerResult, Tool,
     },
-    service::{RequestContext, RoleServer},
+    service::{Peer, RequestContext, RoleServer},
     Service,
 };
 use serde_json::{json, Value};
+use std::future::Future;
+use std::pin::Pin;
 use std::sync::{Arc, RwLock};
 
+/// Resource summarizing calls, errors and latency of each tool
+pub const TOOL_STATS_URI: &str = "resolve://stats/tools";
+
 /// Main DaVinci Resolve MCP Server
 #[derive(Debug)]
 pub struct DaVinciResolveServer {
     /// Configuration
-    #[allow(dead_code)]
     config: Arc<Config>,
     /// Python bridge to DaVinci Resolve
     bridge: Arc<ResolveBridge>,
     /// Server initialized flag
     initialized: Arc<RwLock<bool>>,
+    /// Community locale catalogs
+    locales: Arc<Locales>,
+    /// Locale tool descriptions and messages are shown in for this client
+    locale: Arc<RwLock<String>>,
 }
 
 impl DaVinciResolveServer {
@@ -48,11 +60,23 @@ impl DaVinciResolveServer {
 
     /// Create a new server instance with specific connection mode and configuration
     pub fn with_mode_and_config(mode: ConnectionMode, config: Config) -> Self {
-        let bridge = Arc::new(ResolveBridge::new(mode));
+        let config = Arc::new(config);
+        let bridge = Arc::new(ResolveBridge::with_config(mode, config.clone()));
+        let locales = Locales::load(&config.locale);
+        let locale = locales.resolve(&config.locale.default).unwrap_or_else(|| {
+            tracing::warn!(
+                "Locale '{}' is not available; using {}",
+                config.locale.default,
+                DEFAULT_LOCALE
+            );
+            DEFAULT_LOCALE.to_string()
+        });
         Self {
-            config: Arc::new(config),
+            config,
             bridge,
             initialized: Arc::new(RwLock::new(false)),
+            locales: Arc::new(locales),
+            locale: Arc::new(RwLock::new(locale)),
         }
     }
 
@@ -69,6 +93,11 @@ impl DaVinciResolveServer {
         Ok(())
     }
 
+    /// The bridge the server calls
+    pub fn bridge(&self) -> Arc<ResolveBridge> {
+        self.bridge.clone()
+    }
+
     /// Handle MCP tool calls by routing to the centralized handler
     pub async fn handle_tool_call(
         &self,
@@ -82,12 +111,82 @@ impl DaVinciResolveServer {
         };
 
         // Use the centralized tool handler
-        handle_tool_call(name, args, self.bridge.clone()).await
+        let response = handle_tool_call(name, args, self.bridge.clone()).await?;
+        Ok(self.localize_response(response))
+    }
+
+    /// Select the locale for this client: the one it asked for, else its
+    /// user's, else the configured default, skipping any that is not
+    /// available. Returns the locale selected.
+    pub async fn select_locale(&self, requested: Option<&str>) -> String {
+        let candidates = [
+            requested.map(str::to_string),
+            self.bridge.user_locale().await,
+            Some(self.config.locale.default.clone()),
+        ];
+        let locale = candidates
+            .into_iter()
+            .flatten()
+            .find_map(|tag| self.locales.resolve(&tag))
+            .unwrap_or_else(|| DEFAULT_LOCALE.to_string());
+        *self.locale.write().unwrap() = locale.clone();
+        locale
+    }
+
+    /// Locale tool descriptions and messages are shown in
+    pub fn locale(&self) -> String {
+        self.locale.read().unwrap().clone()
+    }
+
+    /// Tags of the locales a client can ask for, English first
+    pub fn available_locales(&self) -> Vec<String> {
+        self.locales
+            .available()
+            .into_iter()
+            .map(str::to_string)
+            .collect()
+    }
+
+    /// A message in the client's locale, or as it is when there is no translation
+    pub fn localize(&self, message: &str) -> String {
+        self.locales
+            .catalog(&self.locale())
+            .and_then(|catalog| catalog.message(message))
+            .unwrap_or_else(|| message.to_string())
+    }
+
+    /// A tool response with its result message localized; JSON responses keep
+    /// every other field as it is
+    fn localize_response(&self, response: String) -> String {
+        let locale = self.locale();
+        let Some(catalog) = self.locales.catalog(&locale) else {
+            return response;
+        };
+        match serde_json::from_str::<Value>(&response) {
+            Ok(Value::Object(mut object)) => {
+                let Some(translated) = object
+                    .get("result")
+                    .and_then(Value::as_str)
+                    .and_then(|result| catalog.message(result))
+                else {
+                    return response;
+                };
+                object.insert("result".to_string(), Value::String(translated));
+                let object = Value::Object(object);
+                if response.contains('\n') {
+                    serde_json::to_string_pretty(&object).unwrap_or(response)
+                } else {
+                    object.to_string()
+                }
+            }
+            Ok(_) => response,
+            Err(_) => catalog.message(&response).unwrap_or(response),
+        }
     }
 
     /// Get list of all available tools with comprehensive schemas
-    fn get_tools(&self) -> Vec<Tool> {
-        vec![
+    pub fn get_tools(&self) -> Vec<Tool> {
+        let mut tools = vec![
             // ==================== PHASE 1 & 2 TOOLS ====================
             // Project Management
             Tool::new(
@@ -181,6 +280,17 @@ impl DaVinciResolveServer {
                             "type": "string",
                             "description": "Text note to add to the marker",
                             "default": ""
+                        },
+                        "name": {
+                            "type": "string",
+                            "description": "Marker name",
+                            "default": ""
+                        },
+                        "duration": {
+                            "type": "integer",
+                            "minimum": 1,
+                            "description": "Frames the marker spans",
+                            "default": 1
                         }
                     },
                     "required": ["color", "note"]
@@ -197,6 +307,10 @@ impl DaVinciResolveServer {
                         "file_path": {
                             "type": "string",
                             "description": "Path to the media file to import"
+                        },
+                        "bin_name": {
+                            "type": "string",
+                            "description": "Bin to import into (uses your default bin if not specified)"
                         }
                     },
                     "required": ["file_path"]
@@ -221,7 +335,7 @@ impl DaVinciResolveServer {
             ),
             Tool::new(
                 "auto_sync_audio",
-                "Sync audio between clips with customizable settings",
+                "Sync picture clips to sound recorder audio by timecode or waveform, creating synced clips and reporting each pair's offset and drift",
                 Arc::new(json!({
                     "type": "object",
                     "properties": {
@@ -243,7 +357,7 @@ impl DaVinciResolveServer {
                         },
                         "target_bin": {
                             "type": "string",
-                            "description": "Optional bin to move synchronized clips to"
+                            "description": "Optional bin to put the synced clips in, created if missing"
                         }
                     },
                     "required": ["clip_names"]
@@ -288,6 +402,12 @@ impl DaVinciResolveServer {
                             "type": "boolean",
                             "description": "Whether to search the folder path recursively",
                             "default": false
+                        },
+                        "match_by": {
+                            "type": "string",
+                            "description": "How folder files are matched to clips - 'name' or 'timecode' (embedded start timecode and reel)",
+                            "enum": ["name", "timecode"],
+                            "default": "name"
                         }
                     },
                     "required": ["clip_names"]
@@ -377,13 +497,13 @@ impl DaVinciResolveServer {
             // Timeline Enhancement Tools (Phase 3 Week 2)
             Tool::new(
                 "delete_timeline",
-                "Delete a timeline by name",
+                "Delete a timeline by name, moving it to the trash where restore_from_trash can bring it back",
                 Arc::new(json!({
                     "type": "object",
                     "properties": {
                         "name": {
                             "type": "string",
-                            "description": "The name of the timeline to delete"
+                            "description": "The ID or name of the timeline to delete"
                         }
                     },
                     "required": ["name"]
@@ -397,7 +517,7 @@ impl DaVinciResolveServer {
                     "properties": {
                         "name": {
                             "type": "string",
-                            "description": "The name of the timeline to set as current"
+                            "description": "The ID or name of the timeline to set as current"
                         }
                     },
                     "required": ["name"]
@@ -449,11 +569,29 @@ impl DaVinciResolveServer {
                     "properties": {
                         "clip_name": {
                             "type": "string",
-                            "description": "Name of the clip in the media pool"
+                            "description": "ID or name of the clip in the media pool"
                         },
                         "timeline_name": {
                             "type": "string",
-                            "description": "Optional timeline to target (uses current if not specified)"
+                            "description": "Optional timeline ID or name to target (uses current if not specified)"
+                        },
+                        "track_index": {
+                            "type": "integer",
+                            "description": "Video track to append to",
+                            "default": 1
+                        },
+                        "start_frame": {
+                            "type": "integer",
+                            "description": "First source frame to use (defaults to the clip's in point, else 0)"
+                        },
+                        "end_frame": {
+                            "type": "integer",
+                            "description": "Source frame to end before (defaults to the frame after the clip's out point, else the end of the clip)"
+                        },
+                        "record_frame": {
+                            "type": "integer",
+                            "minimum": 0,
+                            "description": "Timeline frame to place the clip at, from the timeline start (appends after the last item on the track if not specified)"
                         }
                     },
                     "required": ["clip_name"]
@@ -487,7 +625,7 @@ impl DaVinciResolveServer {
 
             Tool::new(
                 "apply_lut",
-                "Apply a LUT to a node in the color page",
+                "Apply a LUT to a node in the color page, checking that the LUT's input color space matches what the node receives",
                 Arc::new(json!({
                     "type": "object",
                     "properties": {
@@ -498,6 +636,10 @@ impl DaVinciResolveServer {
                         "node_index": {
                             "type": "integer",
                             "description": "Index of the node to apply the LUT to (uses current node if None)"
+                        },
+                        "allow_color_space_mismatch": {
+                            "type": "boolean",
+                            "description": "Apply the LUT even if it expects another input color space than the node receives (default false)"
                         }
                     },
                     "required": ["lut_path"]
@@ -526,6 +668,15 @@ impl DaVinciResolveServer {
                         "node_index": {
                             "type": "integer",
                             "description": "Index of the node to set parameter for (uses current node if None)"
+                        },
+                        "clip_name": {
+                            "type": "string",
+                            "description": "Clip to grade (uses current clip if None)"
+                        },
+                        "relative": {
+                            "type": "boolean",
+                            "description": "Add the value to the current one instead of replacing it, clamped to -1.0..1.0",
+                            "default": false
                         }
                     },
                     "required": ["wheel", "param", "value"]
@@ -627,7 +778,7 @@ impl DaVinciResolveServer {
             ),
             Tool::new(
                 "delete_color_preset",
-                "Delete a color preset",
+                "Delete a color preset, moving it to the trash where restore_from_trash can bring it back",
                 Arc::new(json!({
                     "type": "object",
                     "properties": {
@@ -648,6 +799,20 @@ impl DaVinciResolveServer {
                     "additionalProperties": false
                 }).as_object().unwrap().clone()),
             ),
+            Tool::new(
+                "list_color_presets",
+                "List color presets grouped by album, optionally filtered to one album",
+                Arc::new(json!({
+                    "type": "object",
+                    "properties": {
+                        "album_name": {
+                            "type": "string",
+                            "description": "Optional album to list presets from (lists all albums if omitted)"
+                        }
+                    },
+                    "additionalProperties": false
+                }).as_object().unwrap().clone()),
+            ),
             Tool::new(
                 "export_lut",
                 "Export a LUT from the current clip's grade",
@@ -783,6 +948,41 @@ impl DaVinciResolveServer {
                     "additionalProperties": false
                 }).as_object().unwrap().clone()),
             ),
+            Tool::new(
+                "set_item_output_sizing",
+                "Set how a timeline item fills the output frame (crop, fit, fill or stretch), its Super Scale upscaling and its lens correction",
+                Arc::new(json!({
+                    "type": "object",
+                    "properties": {
+                        "timeline_item_id": {
+                            "type": "string",
+                            "description": "The ID of the timeline item to modify"
+                        },
+                        "scaling": {
+                            "type": "string",
+                            "description": "Optional scaling mode; Fill crops to fill the frame, Fit letterboxes, Stretch distorts",
+                            "enum": ["UseProject", "Crop", "Fit", "Fill", "Stretch"]
+                        },
+                        "super_scale": {
+                            "type": "boolean",
+                            "description": "Optional boolean to enable/disable Super Scale upscaling"
+                        },
+                        "super_scale_factor": {
+                            "type": "string",
+                            "description": "Optional Super Scale factor; needs Super Scale enabled",
+                            "enum": ["2x", "3x", "4x"]
+                        },
+                        "lens_correction": {
+                            "type": "number",
+                            "description": "Optional lens distortion correction",
+                            "minimum": -1.0,
+                            "maximum": 1.0
+                        }
+                    },
+                    "required": ["timeline_item_id"],
+                    "additionalProperties": false
+                }).as_object().unwrap().clone()),
+            ),
             Tool::new(
                 "set_timeline_item_stabilization",
                 "Set stabilization properties for a timeline item",
@@ -872,7 +1072,7 @@ impl DaVinciResolveServer {
                         "property_type": {
                             "type": "string",
                             "description": "Optional property type to reset. If None, resets all properties",
-                            "enum": ["transform", "crop", "composite", "retime", "stabilization", "audio"]
+                            "enum": ["transform", "crop", "composite", "retime", "sizing", "stabilization", "audio"]
                         }
                     },
                     "required": ["timeline_item_id"],
@@ -1043,7 +1243,7 @@ impl DaVinciResolveServer {
                     "properties": {
                         "preset_name": {
                             "type": "string",
-                            "description": "Name of the render preset to use"
+                            "description": "Name of the render preset to use (uses your default preset if not specified)"
                         },
                         "timeline_name": {
                             "type": "string",
@@ -1053,9 +1253,36 @@ impl DaVinciResolveServer {
                             "type": "boolean",
                             "description": "Whether to render only the in/out range instead of entire timeline",
                             "default": false
+                        },
+                        "export_video": {
+                            "type": "boolean",
+                            "description": "Render the video track (uses the preset's setting if not specified)"
+                        },
+                        "export_audio": {
+                            "type": "boolean",
+                            "description": "Render the audio track (uses the preset's setting if not specified); false queues a video-only render"
+                        },
+                        "audio_channel_layout": {
+                            "type": "string",
+                            "description": "Audio channel layout, which the preset's audio codec must carry (uses the preset's if not specified)",
+                            "enum": ["Mono", "Stereo", "5.1", "7.1"]
+                        },
+                        "subtitle_export": {
+                            "type": "string",
+                            "description": "Burn a subtitle track into the picture or write it next to the render (default none)",
+                            "enum": ["none", "burn_in", "separate_file"]
+                        },
+                        "subtitle_format": {
+                            "type": "string",
+                            "description": "Subtitle file format when exporting a separate file (default srt)",
+                            "enum": ["srt", "webvtt"]
+                        },
+                        "subtitle_track_index": {
+                            "type": "integer",
+                            "description": "Subtitle track to export, starting at 1 (default 1)",
+                            "minimum": 1
                         }
                     },
-                    "required": ["preset_name"],
                     "additionalProperties": false
                 }).as_object().unwrap().clone()),
             ),
@@ -1123,12 +1350,11 @@ impl DaVinciResolveServer {
                         "format": {
                             "type": "string",
                             "description": "Output format",
-                            "enum": ["MP4", "MOV", "MXF"]
+                            "enum": ["MOV", "MP4", "MXF", "EXR", "DPX", "TIFF"]
                         },
                         "codec": {
                             "type": "string",
-                            "description": "Video codec",
-                            "enum": ["H.264", "H.265", "ProRes"]
+                            "description": "Video codec supported by the format, such as H.264, H.265 or ProRes 422 HQ (see list_render_formats)"
                         },
                         "resolution_width": {
                             "type": "integer",
@@ -1155,7 +1381,7 @@ impl DaVinciResolveServer {
                         "audio_codec": {
                             "type": "string",
                             "description": "Audio codec",
-                            "enum": ["AAC", "ProRes"],
+                            "enum": ["AAC", "Linear PCM"],
                             "default": "AAC"
                         },
                         "audio_bitrate": {
@@ -1164,6 +1390,40 @@ impl DaVinciResolveServer {
                             "minimum": 64,
                             "maximum": 192,
                             "default": 192
+                        },
+                        "bit_depth": {
+                            "type": "integer",
+                            "description": "Video bit depth (uses the codec's lowest if not specified)",
+                            "enum": [8, 10, 12, 16, 32]
+                        },
+                        "export_alpha": {
+                            "type": "boolean",
+                            "description": "Render the alpha channel; the codec must support alpha",
+                            "default": false
+                        },
+                        "export_video": {
+                            "type": "boolean",
+                            "description": "Render the video track; false makes an audio-only mixdown preset",
+                            "default": true
+                        },
+                        "export_audio": {
+                            "type": "boolean",
+                            "description": "Render the audio track (on if the format carries audio when not specified)"
+                        },
+                        "audio_channel_layout": {
+                            "type": "string",
+                            "description": "Audio channel layout, which the audio codec must carry",
+                            "enum": ["Mono", "Stereo", "5.1", "7.1"]
+                        },
+                        "embed_chapters": {
+                            "type": "boolean",
+                            "description": "Embed timeline markers as chapters, titled with their notes (MP4 and MOV only)",
+                            "default": false
+                        },
+                        "embed_metadata": {
+                            "type": "boolean",
+                            "description": "Embed the title, author and copyright project settings (MP4 and MOV only)",
+                            "default": false
                         }
                     },
                     "required": ["preset_name", "format", "codec", "resolution_width", "resolution_height", "frame_rate", "quality"],
@@ -1222,7 +1482,7 @@ impl DaVinciResolveServer {
                         },
                         "language": {
                             "type": "string",
-                            "description": "Language code for transcription (default: en-US)",
+                            "description": "Language for transcription, as a BCP-47 code or name such as en-US or english (default: en-US; see list_supported_languages)",
                             "default": "en-US"
                         }
                     },
@@ -1249,7 +1509,7 @@ impl DaVinciResolveServer {
             // ==================== EXTENDED PROJECT MANAGEMENT OPERATIONS ====================
             Tool::new(
                 "delete_media",
-                "Delete a media clip from the media pool by name",
+                "Delete a media clip from the media pool by name, moving it to the trash where restore_from_trash can bring it back",
                 Arc::new(json!({
                     "type": "object",
                     "properties": {
@@ -1317,7 +1577,7 @@ impl DaVinciResolveServer {
                         },
                         "language": {
                             "type": "string",
-                            "description": "Language code for transcription (default: en-US)",
+                            "description": "Language for transcription, as a BCP-47 code or name such as en-US or english (default: en-US; see list_supported_languages)",
                             "default": "en-US"
                         }
                     },
@@ -1896,7 +2156,7 @@ impl DaVinciResolveServer {
             ),
             Tool::new(
                 "set_timeline_timecode",
-                "Set timeline timecode",
+                "Move the timeline playhead to a timecode",
                 Arc::new(json!({
                     "type": "object",
                     "properties": {
@@ -1907,6 +2167,11 @@ impl DaVinciResolveServer {
                         "timecode": {
                             "type": "string",
                             "description": "Timecode to set"
+                        },
+                        "timeline_start_timecode": {
+                            "type": "string",
+                            "description": "Record timecode of the timeline's first frame",
+                            "default": "01:00:00:00"
                         }
                     },
                     "required": ["timecode"],
@@ -2121,11 +2386,11 @@ impl DaVinciResolveServer {
                         "export_type": {
                             "type": "string",
                             "description": "Export type",
-                            "enum": ["AAF", "EDL", "XML", "FCPXML", "DRT", "ADL", "OTIO"]
+                            "enum": ["AAF", "EDL", "XML", "FCPXML", "DRT", "OTIO", "CSV", "TAB"]
                         },
                         "export_subtype": {
                             "type": "string",
-                            "description": "Export subtype"
+                            "description": "Export subtype: linked or embedded for AAF; none, cdl, sdl or missing_clips for EDL; 1.8, 1.9 or 1.10 for FCPXML"
                         }
                     },
                     "required": ["file_name", "export_type"],
@@ -2209,42 +2474,73 @@ impl DaVinciResolveServer {
             // ==================== TIMELINE ITEM OBJECT API ====================
             Tool::new(
                 "get_timeline_item_property",
-                "Get timeline item property",
+                "Get a timeline item property, or all of them, locating the item by ID or track position",
                 Arc::new(json!({
                     "type": "object",
                     "properties": {
                         "timeline_item_id": {
                             "type": "string",
-                            "description": "Timeline item ID"
+                            "description": "Timeline item ID (or give track_index and item_index)"
+                        },
+                        "track_type": {
+                            "type": "string",
+                            "enum": ["video", "audio", "subtitle"],
+                            "description": "Track type when locating by position",
+                            "default": "video"
+                        },
+                        "track_index": {
+                            "type": "integer",
+                            "minimum": 1,
+                            "description": "Track number when locating by position"
+                        },
+                        "item_index": {
+                            "type": "integer",
+                            "minimum": 1,
+                            "description": "Item position on the track, starting at 1"
                         },
                         "property_key": {
                             "type": "string",
-                            "description": "Property key (optional - returns all if not specified)"
+                            "description": "Property key or Resolve alias (returns all properties if not specified)"
                         }
                     },
-                    "required": ["timeline_item_id"],
                     "additionalProperties": false
                 }).as_object().unwrap().clone()),
             ),
             Tool::new(
                 "set_timeline_item_property",
-                "Set timeline item property",
+                "Set a timeline item property, validated against its type and range",
                 Arc::new(json!({
                     "type": "object",
                     "properties": {
                         "timeline_item_id": {
                             "type": "string",
-                            "description": "Timeline item ID"
+                            "description": "Timeline item ID (or give track_index and item_index)"
+                        },
+                        "track_type": {
+                            "type": "string",
+                            "enum": ["video", "audio", "subtitle"],
+                            "description": "Track type when locating by position",
+                            "default": "video"
+                        },
+                        "track_index": {
+                            "type": "integer",
+                            "minimum": 1,
+                            "description": "Track number when locating by position"
+                        },
+                        "item_index": {
+                            "type": "integer",
+                            "minimum": 1,
+                            "description": "Item position on the track, starting at 1"
                         },
                         "property_key": {
                             "type": "string",
-                            "description": "Property key"
+                            "description": "Property key or Resolve alias, e.g. 'ZoomX' or 'CropLeft' (see list_timeline_item_properties)"
                         },
                         "property_value": {
-                            "description": "Property value"
+                            "description": "Value of the property's type, within its range"
                         }
                     },
-                    "required": ["timeline_item_id", "property_key", "property_value"],
+                    "required": ["property_key", "property_value"],
                     "additionalProperties": false
                 }).as_object().unwrap().clone()),
             ),
@@ -2445,7 +2741,7 @@ impl DaVinciResolveServer {
             ),
             Tool::new(
                 "stereo_params",
-                "Manage stereo parameters",
+                "Set a timeline item's stereo 3D parameters, or get them when params is omitted",
                 Arc::new(json!({
                     "type": "object",
                     "properties": {
@@ -2454,7 +2750,7 @@ impl DaVinciResolveServer {
                             "description": "Timeline item ID"
                         },
                         "params": {
-                            "description": "Stereo parameters"
+                            "description": "Stereo parameters to set: convergence, frame, left_window, right_window, eye and swap_eyes, as for set_stereo_params"
                         }
                     },
                     "required": ["timeline_item_id"],
@@ -2495,7 +2791,8 @@ impl DaVinciResolveServer {
                             "description": "Timeline item ID"
                         },
                         "cdl_map": {
-                            "description": "CDL parameters"
+                            "type": "object",
+                            "description": "CDL values keyed NodeIndex, Slope, Offset, Power and Saturation, as in Resolve's SetCDL"
                         }
                     },
                     "required": ["timeline_item_id", "cdl_map"],
@@ -2589,8 +2886,8 @@ impl DaVinciResolveServer {
                 }).as_object().unwrap().clone()),
             ),
             Tool::new(
-                "get_gallery_still_albums",
-                "Get the list of still albums in the gallery",
+                "get_resource_usage",
+                "Report how much of each configured resource limit is in use",
                 Arc::new(json!({
                     "type": "object",
                     "properties": {},
@@ -2598,91 +2895,1708 @@ impl DaVinciResolveServer {
                 }).as_object().unwrap().clone()),
             ),
             Tool::new(
-                "get_fusion_tool_list",
-                "Get the list of tools in Fusion page",
+                "import_markers",
+                "Import timeline markers from a CSV or JSON marker list with timecodes, colors and notes",
                 Arc::new(json!({
                     "type": "object",
                     "properties": {
-                        "selected_only": {
-                            "type": "boolean",
-                            "description": "Whether to get only selected tools",
-                            "default": false
+                        "format": {
+                            "type": "string",
+                            "enum": ["csv", "json"],
+                            "description": "Marker list format"
                         },
-                        "tool_type": {
+                        "data": {
                             "type": "string",
-                            "description": "Optional tool type filter"
+                            "description": "Marker list contents; CSV needs a Timecode column and may have Color and Note columns"
+                        },
+                        "timeline_name": {
+                            "type": "string",
+                            "description": "Timeline to add markers to (uses current if None)"
+                        },
+                        "timeline_start_timecode": {
+                            "type": "string",
+                            "description": "Record timecode of the timeline's first frame",
+                            "default": "01:00:00:00"
                         }
                     },
+                    "required": ["format", "data"],
                     "additionalProperties": false
                 }).as_object().unwrap().clone()),
             ),
             Tool::new(
-                "get_audio_track_count",
-                "Get the number of audio tracks in the current timeline",
+                "export_markers",
+                "Export timeline markers as CSV, JSON or EDL locators",
+                Arc::new(json!({
+                    "type": "object",
+                    "properties": {
+                        "format": {
+                            "type": "string",
+                            "enum": ["csv", "json", "edl"],
+                            "description": "Export format"
+                        },
+                        "timeline_name": {
+                            "type": "string",
+                            "description": "Timeline to export markers from (uses current if None)"
+                        },
+                        "timeline_start_timecode": {
+                            "type": "string",
+                            "description": "Record timecode of the timeline's first frame",
+                            "default": "01:00:00:00"
+                        }
+                    },
+                    "required": ["format"],
+                    "additionalProperties": false
+                }).as_object().unwrap().clone()),
+            ),
+            Tool::new(
+                "identify",
+                "Identify as a user from the server's [users] config so audit entries and review notes record who made changes and that user's default bin and render preset apply",
+                Arc::new(json!({
+                    "type": "object",
+                    "properties": {
+                        "token": {
+                            "type": "string",
+                            "description": "Token of a configured user"
+                        }
+                    },
+                    "required": ["token"],
+                    "additionalProperties": false
+                }).as_object().unwrap().clone()),
+            ),
+            Tool::new(
+                "get_current_user",
+                "Show who calls are made as, how they were identified and their default settings",
                 Arc::new(json!({
                     "type": "object",
                     "properties": {},
                     "additionalProperties": false
                 }).as_object().unwrap().clone()),
             ),
-        ]
-    }
-}
-
-impl Service<RoleServer> for DaVinciResolveServer {
-    async fn handle_request(
-        &self,
-        request: ClientRequest,
-        _context: RequestContext<RoleServer>,
-    ) -> Result<ServerResult, ErrorData> {
-        match request {
-            ClientRequest::InitializeRequest(_) => {
-                // Handle initialization
-                let info = self.get_info();
-                Ok(ServerResult::InitializeResult(info))
-            }
-            ClientRequest::ListToolsRequest(_) => {
-                let tools = self.get_tools();
-                Ok(ServerResult::ListToolsResult(ListToolsResult {
-                    tools,
-                    next_cursor: None,
-                }))
-            }
-            ClientRequest::CallToolRequest(call_tool_request) => {
-                // Extract the actual parameters from the request
-                let CallToolRequestParam { name, arguments } = call_tool_request.params;
-
-                match self.handle_tool_call(&name, arguments).await {
-                    Ok(content) => Ok(ServerResult::CallToolResult(CallToolResult {
-                        content: vec![Content::text(content)],
-                        is_error: Some(false),
-                    })),
-                    Err(e) => Ok(ServerResult::CallToolResult(CallToolResult {
-                        content: vec![Content::text(format!("Error: {}", e))],
-                        is_error: Some(true),
-                    })),
-                }
-            }
-            _ => {
-                // Create a proper method not found error
-                Err(ErrorData::method_not_found::<CallToolRequestMethod>())
-            }
-        }
-    }
-
-    async fn handle_notification(
-        &self,
-        _notification: ClientNotification,
-    ) -> Result<(), ErrorData> {
-        // Handle notifications if needed
-        Ok(())
-    }
-
-    fn get_info(&self) -> InitializeResult {
-        InitializeResult {
-            protocol_version: ProtocolVersion::LATEST,
-            capabilities: ServerCapabilities {
-                tools: Some(rmcp::model::ToolsCapability {
+            Tool::new(
+                "get_audit_log",
+                "List recent changes and cloud operations with the user who made each one, oldest first",
+                Arc::new(json!({
+                    "type": "object",
+                    "properties": {
+                        "user": {
+                            "type": "string",
+                            "description": "Only entries made by this user"
+                        },
+                        "method": {
+                            "type": "string",
+                            "description": "Only entries for this tool"
+                        },
+                        "limit": {
+                            "type": "integer",
+                            "description": "Maximum number of most recent entries",
+                            "minimum": 1,
+                            "default": 100
+                        }
+                    },
+                    "additionalProperties": false
+                }).as_object().unwrap().clone()),
+            ),
+            Tool::new(
+                "list_render_formats",
+                "List render formats with the codecs each supports, their bit depths and alpha support, to pick a valid format and codec before rendering",
+                Arc::new(json!({
+                    "type": "object",
+                    "properties": {
+                        "format": {
+                            "type": "string",
+                            "description": "Only list this format, by name or Resolve format key (lists all if not specified)"
+                        },
+                        "codec": {
+                            "type": "string",
+                            "description": "Only list formats that support this codec"
+                        },
+                        "alpha": {
+                            "type": "boolean",
+                            "description": "Only list codecs that can carry an alpha channel",
+                            "default": false
+                        }
+                    },
+                    "additionalProperties": false
+                }).as_object().unwrap().clone()),
+            ),
+            Tool::new(
+                "get_color_wheel_param",
+                "Read a color wheel parameter, or a whole wheel, of a clip's grade so it can be adjusted relative to its current value",
+                Arc::new(json!({
+                    "type": "object",
+                    "properties": {
+                        "wheel": {
+                            "type": "string",
+                            "description": "Which color wheel to read",
+                            "enum": ["lift", "gamma", "gain", "offset"]
+                        },
+                        "param": {
+                            "type": "string",
+                            "description": "Which parameter to read (reads the whole wheel if not specified)",
+                            "enum": ["red", "green", "blue", "master"]
+                        },
+                        "node_index": {
+                            "type": "integer",
+                            "description": "Index of the node to read from (uses current node if None)"
+                        },
+                        "clip_name": {
+                            "type": "string",
+                            "description": "Clip to read (uses current clip if None)"
+                        }
+                    },
+                    "required": ["wheel"],
+                    "additionalProperties": false
+                }).as_object().unwrap().clone()),
+            ),
+            Tool::new(
+                "adjust_color_wheel_param",
+                "Nudge a color wheel parameter by an amount relative to its current value, such as warming gain red by 0.05; the result is clamped to -1.0..1.0",
+                Arc::new(json!({
+                    "type": "object",
+                    "properties": {
+                        "wheel": {
+                            "type": "string",
+                            "description": "Which color wheel to adjust",
+                            "enum": ["lift", "gamma", "gain", "offset"]
+                        },
+                        "param": {
+                            "type": "string",
+                            "description": "Which parameter to adjust",
+                            "enum": ["red", "green", "blue", "master"]
+                        },
+                        "delta": {
+                            "type": "number",
+                            "description": "Amount to add to the current value; negative values subtract"
+                        },
+                        "node_index": {
+                            "type": "integer",
+                            "description": "Index of the node to adjust (uses current node if None)"
+                        },
+                        "clip_name": {
+                            "type": "string",
+                            "description": "Clip to grade (uses current clip if None)"
+                        }
+                    },
+                    "required": ["wheel", "param", "delta"],
+                    "additionalProperties": false
+                }).as_object().unwrap().clone()),
+            ),
+            Tool::new(
+                "get_clip_grade",
+                "Read a clip's full grade: lift, gamma, gain and offset wheels, applied LUTs, node count and node labels",
+                Arc::new(json!({
+                    "type": "object",
+                    "properties": {
+                        "clip_name": {
+                            "type": "string",
+                            "description": "Clip to read (uses current clip if None)"
+                        }
+                    },
+                    "additionalProperties": false
+                }).as_object().unwrap().clone()),
+            ),
+            Tool::new(
+                "set_notes",
+                "Leave free-text notes on the current project, a named project, a timeline or a media pool clip so the editor finds the context inside the project; clip notes are the clip's Comments metadata",
+                Arc::new(json!({
+                    "type": "object",
+                    "properties": {
+                        "notes": {
+                            "type": "string",
+                            "description": "Notes text; an empty string clears the notes"
+                        },
+                        "project_name": {
+                            "type": "string",
+                            "description": "Project to annotate (uses current if nothing is named)"
+                        },
+                        "timeline_name": {
+                            "type": "string",
+                            "description": "Timeline to annotate"
+                        },
+                        "clip_name": {
+                            "type": "string",
+                            "description": "Media pool clip to annotate"
+                        }
+                    },
+                    "required": ["notes"],
+                    "additionalProperties": false
+                }).as_object().unwrap().clone()),
+            ),
+            Tool::new(
+                "get_notes",
+                "Read the notes on a project, timeline or media pool clip, or every note in the project",
+                Arc::new(json!({
+                    "type": "object",
+                    "properties": {
+                        "project_name": {
+                            "type": "string",
+                            "description": "Project to read notes from (uses current if nothing is named)"
+                        },
+                        "timeline_name": {
+                            "type": "string",
+                            "description": "Timeline to read notes from"
+                        },
+                        "clip_name": {
+                            "type": "string",
+                            "description": "Media pool clip to read notes from"
+                        },
+                        "all": {
+                            "type": "boolean",
+                            "description": "List every note in the project when nothing is named",
+                            "default": false
+                        }
+                    },
+                    "additionalProperties": false
+                }).as_object().unwrap().clone()),
+            ),
+            Tool::new(
+                "run_action",
+                "Run a named action configured by the administrator, such as prep_dailies or archive_project, as one call; lists the actions available in the current project when no action is given",
+                Arc::new(json!({
+                    "type": "object",
+                    "properties": {
+                        "action": {
+                            "type": "string",
+                            "description": "Name of the configured action (lists the available actions if not specified)"
+                        },
+                        "parameters": {
+                            "type": "object",
+                            "description": "Values for the action's parameters, filling its {{name}} placeholders"
+                        }
+                    },
+                    "additionalProperties": false
+                }).as_object().unwrap().clone()),
+            ),
+            Tool::new(
+                "bootstrap_session",
+                "Bring the session to a known state by applying a bootstrap profile from the config: open its project, make its timeline current and switch to its page; applies the startup profile when no profile is given",
+                Arc::new(json!({
+                    "type": "object",
+                    "properties": {
+                        "profile": {
+                            "type": "string",
+                            "description": "Name of the configured bootstrap profile (applies the startup profile if not specified)"
+                        }
+                    },
+                    "additionalProperties": false
+                }).as_object().unwrap().clone()),
+            ),
+            Tool::new(
+                "describe_frame",
+                "Ask the client's model, through MCP sampling, to describe a frame of a clip and tag it with keywords, and store the answer as the clip's Description and Keywords metadata; the frame is cut from the clip's media with ffmpeg unless an image is given. Needs a client that supports sampling",
+                Arc::new(json!({
+                    "type": "object",
+                    "properties": {
+                        "clip_name": {
+                            "type": "string",
+                            "description": "Media pool clip to describe"
+                        },
+                        "frame": {
+                            "type": "integer",
+                            "minimum": 0,
+                            "description": "Frame of the clip's media to describe, from its first frame",
+                            "default": 0
+                        },
+                        "image_path": {
+                            "type": "string",
+                            "description": "PNG, JPEG, WebP or GIF image to describe instead of a frame cut from the clip's media, such as an exported still"
+                        },
+                        "prompt": {
+                            "type": "string",
+                            "description": "What to pay attention to, added to the question asked"
+                        },
+                        "write_metadata": {
+                            "type": "boolean",
+                            "description": "Store the description and keywords as the clip's Description and Keywords metadata",
+                            "default": true
+                        }
+                    },
+                    "required": ["clip_name"],
+                    "additionalProperties": false
+                }).as_object().unwrap().clone()),
+            ),
+            Tool::new(
+                "get_timeline_summary",
+                "Summarize a timeline before delivery: total runtime, items per track, clips used, clips at a different frame rate and gaps between items",
+                Arc::new(json!({
+                    "type": "object",
+                    "properties": {
+                        "timeline_name": {
+                            "type": "string",
+                            "description": "Timeline ID or name (uses current if None)"
+                        },
+                        "timeline_start_timecode": {
+                            "type": "string",
+                            "description": "Record timecode of the timeline's first frame",
+                            "default": "01:00:00:00"
+                        }
+                    },
+                    "additionalProperties": false
+                }).as_object().unwrap().clone()),
+            ),
+            Tool::new(
+                "insert_bars_and_tone",
+                "Insert SMPTE colour bars and a 1 kHz reference tone of a given length at the head of a timeline, ahead of any slate",
+                Arc::new(json!({
+                    "type": "object",
+                    "properties": {
+                        "timeline_name": {
+                            "type": "string",
+                            "description": "Timeline ID or name (uses current if None)"
+                        },
+                        "duration_seconds": {
+                            "type": "number",
+                            "description": "Length of the bars and tone, in seconds",
+                            "default": 60
+                        },
+                        "pattern": {
+                            "type": "string",
+                            "description": "Colour bars generator",
+                            "enum": ["SMPTE Color Bar", "EBU Color Bar"],
+                            "default": "SMPTE Color Bar"
+                        },
+                        "tone_frequency_hz": {
+                            "type": "number",
+                            "minimum": 20,
+                            "maximum": 20000,
+                            "description": "Tone frequency in Hz",
+                            "default": 1000
+                        },
+                        "tone_level_dbfs": {
+                            "type": "number",
+                            "maximum": 0,
+                            "description": "Tone level in dBFS",
+                            "default": -20
+                        },
+                        "track_index": {
+                            "type": "integer",
+                            "minimum": 1,
+                            "description": "Video track for the bars",
+                            "default": 1
+                        },
+                        "audio_track_index": {
+                            "type": "integer",
+                            "minimum": 1,
+                            "description": "Audio track for the tone",
+                            "default": 1
+                        },
+                        "channels": {
+                            "type": "integer",
+                            "minimum": 1,
+                            "maximum": 16,
+                            "description": "Number of tone channels",
+                            "default": 2
+                        },
+                        "timeline_start_timecode": {
+                            "type": "string",
+                            "description": "Record timecode of the timeline's first frame",
+                            "default": "01:00:00:00"
+                        }
+                    },
+                    "additionalProperties": false
+                }).as_object().unwrap().clone()),
+            ),
+            Tool::new(
+                "add_slate",
+                "Add a broadcast slate (project, timeline, date, TRT, audio and custom fields) and countdown at the head of a timeline",
+                Arc::new(json!({
+                    "type": "object",
+                    "properties": {
+                        "timeline_name": {
+                            "type": "string",
+                            "description": "Timeline ID or name (uses current if None)"
+                        },
+                        "fields": {
+                            "type": "object",
+                            "additionalProperties": { "type": "string" },
+                            "description": "Slate field values, overriding or adding to the template (e.g. Client, Agency, Audio)"
+                        },
+                        "field_order": {
+                            "type": "array",
+                            "items": { "type": "string" },
+                            "description": "Fields shown on the slate, in order (configured template if None)"
+                        },
+                        "slate_seconds": {
+                            "type": "number",
+                            "description": "How long the slate is held, in seconds",
+                            "default": 10
+                        },
+                        "countdown_seconds": {
+                            "type": "number",
+                            "description": "Length of the countdown after the slate, in seconds; 0 for none",
+                            "default": 10
+                        },
+                        "track_index": {
+                            "type": "integer",
+                            "minimum": 1,
+                            "description": "Video track for the slate and countdown",
+                            "default": 1
+                        },
+                        "timeline_start_timecode": {
+                            "type": "string",
+                            "description": "Record timecode of the timeline's first frame",
+                            "default": "01:00:00:00"
+                        }
+                    },
+                    "additionalProperties": false
+                }).as_object().unwrap().clone()),
+            ),
+            Tool::new(
+                "get_bridge_stats",
+                "Report bridge activity, how much of the CPU capacity for renders, proxies, transcription and analysis is in use, and how mirroring real Resolve state is going",
+                Arc::new(json!({
+                    "type": "object",
+                    "properties": {},
+                    "additionalProperties": false
+                }).as_object().unwrap().clone()),
+            ),
+            Tool::new(
+                "sync_real_state",
+                "In Real mode, copy the project name, timelines, markers and render queue of the open Resolve project into the server's state now, rather than at the next periodic sync",
+                Arc::new(json!({
+                    "type": "object",
+                    "properties": {},
+                    "additionalProperties": false
+                }).as_object().unwrap().clone()),
+            ),
+            Tool::new(
+                "submit_job",
+                "Queue an API call as a background job that starts once the jobs it depends on have completed",
+                Arc::new(json!({
+                    "type": "object",
+                    "properties": {
+                        "method": {
+                            "type": "string",
+                            "description": "API method the job runs, such as transcribe_audio or start_render"
+                        },
+                        "arguments": {
+                            "type": "object",
+                            "description": "Arguments passed to the method"
+                        },
+                        "depends_on": {
+                            "type": "array",
+                            "items": { "type": "string" },
+                            "description": "IDs of jobs that must complete before this one starts"
+                        },
+                        "description": {
+                            "type": "string",
+                            "description": "Description shown in job listings"
+                        }
+                    },
+                    "required": ["method"],
+                    "additionalProperties": false
+                }).as_object().unwrap().clone()),
+            ),
+            Tool::new(
+                "get_job_graph",
+                "Get background jobs as a dependency graph with their status, edges and run order",
+                Arc::new(json!({
+                    "type": "object",
+                    "properties": {
+                        "job_id": {
+                            "type": "string",
+                            "description": "Only show this job with its prerequisites and dependents (all jobs if None)"
+                        }
+                    },
+                    "additionalProperties": false
+                }).as_object().unwrap().clone()),
+            ),
+            Tool::new(
+                "generate_grade_report",
+                "Generate a JSON or HTML report of every timeline item's grade: wheels, LUTs, CDL, nodes and stills",
+                Arc::new(json!({
+                    "type": "object",
+                    "properties": {
+                        "timeline_name": {
+                            "type": "string",
+                            "description": "Timeline ID or name (uses current if None)"
+                        },
+                        "format": {
+                            "type": "string",
+                            "enum": ["json", "html"],
+                            "description": "Report format",
+                            "default": "json"
+                        },
+                        "output_path": {
+                            "type": "string",
+                            "description": "Also write the report to this file"
+                        },
+                        "timeline_start_timecode": {
+                            "type": "string",
+                            "description": "Record timecode of the timeline's first frame",
+                            "default": "01:00:00:00"
+                        }
+                    },
+                    "additionalProperties": false
+                }).as_object().unwrap().clone()),
+            ),
+            Tool::new(
+                "apply_color_preset_batch",
+                "Apply a color preset or another clip's grade to a list of clips, a bin, a color group or every clip in a timeline",
+                Arc::new(json!({
+                    "type": "object",
+                    "properties": {
+                        "preset_id": {
+                            "type": "string",
+                            "description": "ID of the preset to apply (if known)"
+                        },
+                        "preset_name": {
+                            "type": "string",
+                            "description": "Name of the preset to apply (searches in album)"
+                        },
+                        "album_name": {
+                            "type": "string",
+                            "description": "Album containing the preset",
+                            "default": "DaVinci Resolve"
+                        },
+                        "source_clip": {
+                            "type": "string",
+                            "description": "Copy this clip's grade instead of applying a preset"
+                        },
+                        "clip_names": {
+                            "type": "array",
+                            "items": { "type": "string" },
+                            "minItems": 1,
+                            "description": "Apply to these clip IDs or names"
+                        },
+                        "bin_name": {
+                            "type": "string",
+                            "description": "Apply to every clip in this bin"
+                        },
+                        "color_group": {
+                            "type": "string",
+                            "description": "Apply to every clip in this project color group"
+                        },
+                        "timeline_name": {
+                            "type": "string",
+                            "description": "Apply to every clip used in this timeline"
+                        }
+                    },
+                    "additionalProperties": false
+                }).as_object().unwrap().clone()),
+            ),
+            Tool::new(
+                "find_timeline_item",
+                "Find timeline items and their IDs by track and position, by source clip, or by timecode",
+                Arc::new(json!({
+                    "type": "object",
+                    "properties": {
+                        "timeline_name": {
+                            "type": "string",
+                            "description": "Timeline ID or name (uses current if None)"
+                        },
+                        "track_index": {
+                            "type": "integer",
+                            "minimum": 1,
+                            "description": "Find items on this video track"
+                        },
+                        "item_index": {
+                            "type": "integer",
+                            "minimum": 1,
+                            "description": "With track_index, only the item at this position on the track"
+                        },
+                        "track_type": {
+                            "type": "string",
+                            "enum": ["video", "audio", "subtitle"],
+                            "description": "Track type",
+                            "default": "video"
+                        },
+                        "clip_name": {
+                            "type": "string",
+                            "description": "Find items using this media pool clip ID or name"
+                        },
+                        "timecode": {
+                            "type": "string",
+                            "description": "Find items under this record timecode"
+                        },
+                        "at_playhead": {
+                            "type": "boolean",
+                            "description": "Find items under the playhead set with set_timeline_timecode"
+                        },
+                        "timeline_start_timecode": {
+                            "type": "string",
+                            "description": "Record timecode of the timeline's first frame",
+                            "default": "01:00:00:00"
+                        }
+                    },
+                    "additionalProperties": false
+                }).as_object().unwrap().clone()),
+            ),
+            Tool::new(
+                "list_timeline_item_properties",
+                "List timeline item properties with their category, type, range, default and aliases",
+                Arc::new(json!({
+                    "type": "object",
+                    "properties": {
+                        "category": {
+                            "type": "string",
+                            "enum": ["transform", "crop", "composite", "retime", "sizing", "stabilization", "audio"],
+                            "description": "Only list properties in this category"
+                        }
+                    },
+                    "additionalProperties": false
+                }).as_object().unwrap().clone()),
+            ),
+            Tool::new(
+                "get_result_page",
+                "Fetch the next page of a large list response using its next_cursor",
+                Arc::new(json!({
+                    "type": "object",
+                    "properties": {
+                        "cursor": {
+                            "type": "string",
+                            "description": "Cursor from a previous response's next_cursor"
+                        },
+                        "page_size": {
+                            "type": "integer",
+                            "minimum": 1,
+                            "description": "Items to return (defaults to the page size of the original request)"
+                        }
+                    },
+                    "required": ["cursor"],
+                    "additionalProperties": false
+                }).as_object().unwrap().clone()),
+            ),
+            Tool::new(
+                "get_media_pool_item_list",
+                "List media pool clips with their IDs, paths and bins, a page at a time",
+                Arc::new(json!({
+                    "type": "object",
+                    "properties": {
+                        "page_size": {
+                            "type": "integer",
+                            "minimum": 1,
+                            "description": "Clips per page; the rest are fetched with get_result_page",
+                            "default": 500
+                        }
+                    },
+                    "additionalProperties": false
+                }).as_object().unwrap().clone()),
+            ),
+            Tool::new(
+                "find_clip_usage",
+                "List every timeline and item where a media pool clip is used, with track and frame ranges",
+                Arc::new(json!({
+                    "type": "object",
+                    "properties": {
+                        "clip_name": {
+                            "type": "string",
+                            "description": "Media pool clip to look up"
+                        }
+                    },
+                    "required": ["clip_name"],
+                    "additionalProperties": false
+                }).as_object().unwrap().clone()),
+            ),
+            Tool::new(
+                "list_unused_clips",
+                "List media pool clips that are not used on any timeline",
+                Arc::new(json!({
+                    "type": "object",
+                    "properties": {
+                        "bin_name": {
+                            "type": "string",
+                            "description": "Only list clips in this bin"
+                        }
+                    },
+                    "additionalProperties": false
+                }).as_object().unwrap().clone()),
+            ),
+            Tool::new(
+                "read_embedded_timecode",
+                "Read embedded start timecode and reel name from clip media and store them on the clips",
+                Arc::new(json!({
+                    "type": "object",
+                    "properties": {
+                        "clip_names": {
+                            "type": "array",
+                            "items": {"type": "string"},
+                            "description": "Clips to read (every media pool clip if omitted)"
+                        }
+                    },
+                    "additionalProperties": false
+                }).as_object().unwrap().clone()),
+            ),
+            Tool::new(
+                "run_delivery_qc",
+                "Start a background job detecting black frames, frozen frames and silent audio in a rendered file or timeline",
+                Arc::new(json!({
+                    "type": "object",
+                    "properties": {
+                        "file_path": {
+                            "type": "string",
+                            "description": "Rendered file to check, inside a media storage location"
+                        },
+                        "timeline_name": {
+                            "type": "string",
+                            "description": "Timeline to check when no file is given (uses current if omitted)"
+                        },
+                        "min_black_seconds": {
+                            "type": "number",
+                            "exclusiveMinimum": 0,
+                            "description": "Shortest black stretch to report, in seconds",
+                            "default": 0.5
+                        },
+                        "min_freeze_seconds": {
+                            "type": "number",
+                            "exclusiveMinimum": 0,
+                            "description": "Shortest frozen stretch to report, in seconds",
+                            "default": 2.0
+                        },
+                        "min_silence_seconds": {
+                            "type": "number",
+                            "exclusiveMinimum": 0,
+                            "description": "Shortest silent stretch to report, in seconds",
+                            "default": 2.0
+                        },
+                        "silence_threshold_db": {
+                            "type": "number",
+                            "exclusiveMaximum": 0,
+                            "description": "Peak level below which audio is silent, in dBFS",
+                            "default": -60
+                        },
+                        "timeline_start_timecode": {
+                            "type": "string",
+                            "description": "Record timecode of the timeline's first frame",
+                            "default": "01:00:00:00"
+                        }
+                    },
+                    "additionalProperties": false
+                }).as_object().unwrap().clone()),
+            ),
+            Tool::new(
+                "get_background_job",
+                "Get the status of a background job and its report once finished",
+                Arc::new(json!({
+                    "type": "object",
+                    "properties": {
+                        "job_id": {
+                            "type": "string",
+                            "description": "ID of the background job"
+                        }
+                    },
+                    "required": ["job_id"],
+                    "additionalProperties": false
+                }).as_object().unwrap().clone()),
+            ),
+            Tool::new(
+                "list_background_jobs",
+                "List background jobs and their status",
+                Arc::new(json!({
+                    "type": "object",
+                    "properties": {
+                        "kind": {
+                            "type": "string",
+                            "description": "Only list jobs of this kind, such as delivery_qc"
+                        }
+                    },
+                    "additionalProperties": false
+                }).as_object().unwrap().clone()),
+            ),
+            Tool::new(
+                "run_color_qc",
+                "Sample timeline frames and flag illegal broadcast levels, clipped highlights or shadows and sudden luminance jumps",
+                Arc::new(json!({
+                    "type": "object",
+                    "properties": {
+                        "timeline_name": {
+                            "type": "string",
+                            "description": "Timeline to check (uses current if omitted)"
+                        },
+                        "interval_frames": {
+                            "type": "integer",
+                            "minimum": 1,
+                            "description": "Frames between samples (defaults to one second)"
+                        },
+                        "illegal_percent_threshold": {
+                            "type": "number",
+                            "minimum": 0,
+                            "description": "Flag a sample when more than this percent of pixels are outside 16-235",
+                            "default": 1.0
+                        },
+                        "clipped_percent_threshold": {
+                            "type": "number",
+                            "minimum": 0,
+                            "description": "Flag a sample when more than this percent of pixels are clipped black or white",
+                            "default": 2.0
+                        },
+                        "luma_jump_threshold": {
+                            "type": "number",
+                            "minimum": 0,
+                            "maximum": 1,
+                            "description": "Flag a change in mean luma (0.0-1.0) between samples larger than this",
+                            "default": 0.25
+                        },
+                        "create_markers": {
+                            "type": "boolean",
+                            "description": "Add a warning marker at each flagged frame",
+                            "default": false
+                        },
+                        "marker_color": {
+                            "type": "string",
+                            "description": "Color of created markers",
+                            "default": "Red"
+                        },
+                        "timeline_start_timecode": {
+                            "type": "string",
+                            "description": "Record timecode of the timeline's first frame",
+                            "default": "01:00:00:00"
+                        }
+                    },
+                    "additionalProperties": false
+                }).as_object().unwrap().clone()),
+            ),
+            Tool::new(
+                "get_frame_scopes",
+                "Get luma histogram, RGB parade, vectorscope and percentile statistics for a clip frame",
+                Arc::new(json!({
+                    "type": "object",
+                    "properties": {
+                        "clip_name": {
+                            "type": "string",
+                            "description": "Clip to analyze"
+                        },
+                        "frame": {
+                            "type": "integer",
+                            "minimum": 0,
+                            "description": "Source frame to analyze",
+                            "default": 0
+                        },
+                        "histogram_bins": {
+                            "type": "integer",
+                            "minimum": 1,
+                            "maximum": 256,
+                            "description": "Number of luma histogram bins",
+                            "default": 32
+                        }
+                    },
+                    "required": ["clip_name"],
+                    "additionalProperties": false
+                }).as_object().unwrap().clone()),
+            ),
+            Tool::new(
+                "get_audio_waveform",
+                "Get downsampled min/max audio peaks for a clip or a timeline range",
+                Arc::new(json!({
+                    "type": "object",
+                    "properties": {
+                        "clip_name": {
+                            "type": "string",
+                            "description": "Clip to analyze; the timeline is used if omitted"
+                        },
+                        "timeline_name": {
+                            "type": "string",
+                            "description": "Timeline to analyze when no clip is given (uses current if None)"
+                        },
+                        "start_frame": {
+                            "type": "integer",
+                            "minimum": 0,
+                            "description": "First frame of the range (source frame for a clip, record frame for a timeline)"
+                        },
+                        "end_frame": {
+                            "type": "integer",
+                            "minimum": 1,
+                            "description": "Frame the range ends before (defaults to the clip or timeline end)"
+                        },
+                        "buckets": {
+                            "type": "integer",
+                            "minimum": 1,
+                            "maximum": 10000,
+                            "description": "Number of min/max peak buckets to return",
+                            "default": 200
+                        },
+                        "page_size": {
+                            "type": "integer",
+                            "minimum": 1,
+                            "description": "Buckets per page; the rest are fetched with get_result_page",
+                            "default": 500
+                        }
+                    },
+                    "additionalProperties": false
+                }).as_object().unwrap().clone()),
+            ),
+            Tool::new(
+                "search_transcript",
+                "Search clip transcripts for a phrase, returning clip, in/out timecodes and confidence, optionally marking matches on a timeline",
+                Arc::new(json!({
+                    "type": "object",
+                    "properties": {
+                        "query": {
+                            "type": "string",
+                            "description": "Word or phrase to search for; case and punctuation are ignored"
+                        },
+                        "clip_name": {
+                            "type": "string",
+                            "description": "Only search this clip's transcript"
+                        },
+                        "min_confidence": {
+                            "type": "number",
+                            "minimum": 0.0,
+                            "maximum": 1.0,
+                            "description": "Skip matches whose lowest word confidence is below this"
+                        },
+                        "create_markers": {
+                            "type": "boolean",
+                            "description": "Add a marker where each match is used on the timeline",
+                            "default": false
+                        },
+                        "timeline_name": {
+                            "type": "string",
+                            "description": "Timeline to add markers to (uses current if None)"
+                        },
+                        "marker_color": {
+                            "type": "string",
+                            "description": "Color of created markers",
+                            "default": "Purple"
+                        },
+                        "page_size": {
+                            "type": "integer",
+                            "minimum": 1,
+                            "description": "Matches per page; the rest are fetched with get_result_page",
+                            "default": 500
+                        }
+                    },
+                    "required": ["query"],
+                    "additionalProperties": false
+                }).as_object().unwrap().clone()),
+            ),
+            Tool::new(
+                "tag_clip",
+                "Add user-defined tags to a media pool clip or a timeline item",
+                Arc::new(json!({
+                    "type": "object",
+                    "properties": {
+                        "clip_name": {
+                            "type": "string",
+                            "description": "Media pool clip to tag (set this or timeline_item_id)"
+                        },
+                        "timeline_item_id": {
+                            "type": "string",
+                            "description": "Timeline item to tag (set this or clip_name)"
+                        },
+                        "tags": {
+                            "type": "array",
+                            "items": {"type": "string"},
+                            "minItems": 1,
+                            "description": "Tags to add; tags are case-insensitive"
+                        }
+                    },
+                    "required": ["tags"],
+                    "additionalProperties": false
+                }).as_object().unwrap().clone()),
+            ),
+            Tool::new(
+                "untag_clip",
+                "Remove tags from a media pool clip or a timeline item",
+                Arc::new(json!({
+                    "type": "object",
+                    "properties": {
+                        "clip_name": {
+                            "type": "string",
+                            "description": "Media pool clip to untag (set this or timeline_item_id)"
+                        },
+                        "timeline_item_id": {
+                            "type": "string",
+                            "description": "Timeline item to untag (set this or clip_name)"
+                        },
+                        "tags": {
+                            "type": "array",
+                            "items": {"type": "string"},
+                            "minItems": 1,
+                            "description": "Tags to remove (removes all tags if omitted)"
+                        }
+                    },
+                    "additionalProperties": false
+                }).as_object().unwrap().clone()),
+            ),
+            Tool::new(
+                "find_by_tag",
+                "Find clips and timeline items carrying any or all of the given tags",
+                Arc::new(json!({
+                    "type": "object",
+                    "properties": {
+                        "tags": {
+                            "type": "array",
+                            "items": {"type": "string"},
+                            "minItems": 1,
+                            "description": "Tags to search for"
+                        },
+                        "match": {
+                            "type": "string",
+                            "enum": ["any", "all"],
+                            "description": "Match any of the tags or all of them",
+                            "default": "any"
+                        },
+                        "target_type": {
+                            "type": "string",
+                            "enum": ["clip", "timeline_item", "all"],
+                            "description": "What to search",
+                            "default": "all"
+                        }
+                    },
+                    "required": ["tags"],
+                    "additionalProperties": false
+                }).as_object().unwrap().clone()),
+            ),
+            Tool::new(
+                "export_vfx_plates",
+                "Queue VFX plate renders with handles for timeline items or marker ranges, named SHOW_SEQ_SHOT_VERSION",
+                Arc::new(json!({
+                    "type": "object",
+                    "properties": {
+                        "show": {
+                            "type": "string",
+                            "description": "Show code, the first part of plate names"
+                        },
+                        "sequence": {
+                            "type": "string",
+                            "description": "Sequence code, the second part of plate names"
+                        },
+                        "timeline_name": {
+                            "type": "string",
+                            "description": "Timeline to export from (uses current if None)"
+                        },
+                        "timeline_item_ids": {
+                            "type": "array",
+                            "items": {"type": "string"},
+                            "description": "Timeline items to export (all items if omitted)"
+                        },
+                        "marker_color": {
+                            "type": "string",
+                            "description": "Export the ranges between markers of this color instead of timeline items"
+                        },
+                        "handles": {
+                            "type": "integer",
+                            "minimum": 0,
+                            "description": "Frames of handle added before and after each shot",
+                            "default": 8
+                        },
+                        "format": {
+                            "type": "string",
+                            "enum": ["exr", "dpx", "prores"],
+                            "description": "Plate format; exr and dpx render image sequences",
+                            "default": "exr"
+                        },
+                        "version": {
+                            "type": "integer",
+                            "minimum": 1,
+                            "description": "Plate version number",
+                            "default": 1
+                        },
+                        "shot_start": {
+                            "type": "integer",
+                            "minimum": 0,
+                            "description": "Number of the first shot",
+                            "default": 10
+                        },
+                        "shot_increment": {
+                            "type": "integer",
+                            "minimum": 1,
+                            "description": "Step between shot numbers",
+                            "default": 10
+                        },
+                        "output_directory": {
+                            "type": "string",
+                            "description": "Folder plates are rendered into",
+                            "default": "/tmp/renders/vfx_plates"
+                        },
+                        "timeline_start_timecode": {
+                            "type": "string",
+                            "description": "Record timecode of the timeline's first frame",
+                            "default": "01:00:00:00"
+                        }
+                    },
+                    "required": ["show", "sequence"],
+                    "additionalProperties": false
+                }).as_object().unwrap().clone()),
+            ),
+            Tool::new(
+                "consolidate_media",
+                "Compute the source ranges a timeline uses (pull list) and optionally trim them into a folder and relink a duplicate timeline",
+                Arc::new(json!({
+                    "type": "object",
+                    "properties": {
+                        "timeline_name": {
+                            "type": "string",
+                            "description": "Timeline to consolidate (uses current if None)"
+                        },
+                        "handles": {
+                            "type": "integer",
+                            "minimum": 0,
+                            "description": "Frames of handle added before and after each used range",
+                            "default": 24
+                        },
+                        "destination_folder": {
+                            "type": "string",
+                            "description": "Media storage folder to trim the ranges into; only the pull list is returned if omitted"
+                        },
+                        "new_timeline_name": {
+                            "type": "string",
+                            "description": "Name of the relinked duplicate timeline (default '<timeline> (Consolidated)')"
+                        }
+                    },
+                    "additionalProperties": false
+                }).as_object().unwrap().clone()),
+            ),
+            Tool::new(
+                "generate_shot_list",
+                "Generate a shot list of a timeline with source and record timecodes, durations and marker notes",
+                Arc::new(json!({
+                    "type": "object",
+                    "properties": {
+                        "timeline_name": {
+                            "type": "string",
+                            "description": "Timeline to walk (uses current if None)"
+                        },
+                        "format": {
+                            "type": "string",
+                            "enum": ["json", "csv"],
+                            "description": "Output format; csv also returns the CSV text",
+                            "default": "json"
+                        },
+                        "timeline_start_timecode": {
+                            "type": "string",
+                            "description": "Record timecode of the timeline's first frame",
+                            "default": "01:00:00:00"
+                        }
+                    },
+                    "additionalProperties": false
+                }).as_object().unwrap().clone()),
+            ),
+            Tool::new(
+                "add_review_note",
+                "Attach a review note with a status to a timeline item or a timecode",
+                Arc::new(json!({
+                    "type": "object",
+                    "properties": {
+                        "text": {
+                            "type": "string",
+                            "description": "Note text"
+                        },
+                        "timeline_item_id": {
+                            "type": "string",
+                            "description": "Timeline item the note is about (set this or timecode)"
+                        },
+                        "timecode": {
+                            "type": "string",
+                            "description": "Record timecode the note is about (set this or timeline_item_id)"
+                        },
+                        "timeline_name": {
+                            "type": "string",
+                            "description": "Timeline the note belongs to (uses current if None)"
+                        },
+                        "timeline_start_timecode": {
+                            "type": "string",
+                            "description": "Record timecode of the timeline's first frame",
+                            "default": "01:00:00:00"
+                        },
+                        "status": {
+                            "type": "string",
+                            "enum": ["todo", "in_progress", "approved"],
+                            "description": "Initial status",
+                            "default": "todo"
+                        },
+                        "author": {
+                            "type": "string",
+                            "description": "Who wrote the note (uses the current user if not specified)"
+                        }
+                    },
+                    "required": ["text"],
+                    "additionalProperties": false
+                }).as_object().unwrap().clone()),
+            ),
+            Tool::new(
+                "list_review_notes",
+                "List review notes, optionally filtered by status, timeline, item or author",
+                Arc::new(json!({
+                    "type": "object",
+                    "properties": {
+                        "status": {
+                            "type": "string",
+                            "enum": ["todo", "in_progress", "approved"],
+                            "description": "Only notes with this status"
+                        },
+                        "timeline_name": {
+                            "type": "string",
+                            "description": "Only notes on this timeline"
+                        },
+                        "timeline_item_id": {
+                            "type": "string",
+                            "description": "Only notes on this timeline item"
+                        },
+                        "author": {
+                            "type": "string",
+                            "description": "Only notes by this author"
+                        }
+                    },
+                    "additionalProperties": false
+                }).as_object().unwrap().clone()),
+            ),
+            Tool::new(
+                "resolve_note",
+                "Move a review note to a new status, approving it by default",
+                Arc::new(json!({
+                    "type": "object",
+                    "properties": {
+                        "note_id": {
+                            "type": "string",
+                            "description": "ID of the note to update"
+                        },
+                        "status": {
+                            "type": "string",
+                            "enum": ["todo", "in_progress", "approved"],
+                            "description": "New status",
+                            "default": "approved"
+                        },
+                        "comment": {
+                            "type": "string",
+                            "description": "Comment recorded with the status change"
+                        }
+                    },
+                    "required": ["note_id"],
+                    "additionalProperties": false
+                }).as_object().unwrap().clone()),
+            ),
+            Tool::new(
+                "create_backup",
+                "Create a restore point of the current project, rotating out the oldest backups",
+                Arc::new(json!({
+                    "type": "object",
+                    "properties": {
+                        "name": {
+                            "type": "string",
+                            "description": "Name for the restore point (defaults to project name and time)"
+                        },
+                        "write_file": {
+                            "type": "boolean",
+                            "description": "Also write a timestamped summary of the backup to the configured directory; the file cannot be restored from",
+                            "default": false
+                        }
+                    },
+                    "additionalProperties": false
+                }).as_object().unwrap().clone()),
+            ),
+            Tool::new(
+                "restore_backup",
+                "Restore the project to a previously created backup",
+                Arc::new(json!({
+                    "type": "object",
+                    "properties": {
+                        "backup_id": {
+                            "type": "string",
+                            "description": "ID of the backup to restore"
+                        }
+                    },
+                    "required": ["backup_id"],
+                    "additionalProperties": false
+                }).as_object().unwrap().clone()),
+            ),
+            Tool::new(
+                "list_backups",
+                "List available project restore points, newest first",
+                Arc::new(json!({
+                    "type": "object",
+                    "properties": {},
+                    "additionalProperties": false
+                }).as_object().unwrap().clone()),
+            ),
+            Tool::new(
+                "get_mounted_volumes",
+                "List the media storage locations available for browsing",
+                Arc::new(json!({
+                    "type": "object",
+                    "properties": {},
+                    "additionalProperties": false
+                }).as_object().unwrap().clone()),
+            ),
+            Tool::new(
+                "browse_folder",
+                "List the subfolders and files of a folder inside a media storage location",
+                Arc::new(json!({
+                    "type": "object",
+                    "properties": {
+                        "path": {
+                            "type": "string",
+                            "description": "Absolute path of the folder to browse"
+                        }
+                    },
+                    "required": ["path"],
+                    "additionalProperties": false
+                }).as_object().unwrap().clone()),
+            ),
+            Tool::new(
+                "add_clips_to_media_pool_from_paths",
+                "Add media files, or the media inside folders, from media storage to the media pool",
+                Arc::new(json!({
+                    "type": "object",
+                    "properties": {
+                        "paths": {
+                            "type": "array",
+                            "items": {"type": "string"},
+                            "description": "Absolute paths of media files or folders to add"
+                        },
+                        "bin_name": {
+                            "type": "string",
+                            "description": "Bin to add the clips to (uses the master bin if None)"
+                        }
+                    },
+                    "required": ["paths"],
+                    "additionalProperties": false
+                }).as_object().unwrap().clone()),
+            ),
+            Tool::new(
+                "get_gallery_still_albums",
+                "Get the list of still albums in the gallery",
+                Arc::new(json!({
+                    "type": "object",
+                    "properties": {},
+                    "additionalProperties": false
+                }).as_object().unwrap().clone()),
+            ),
+            Tool::new(
+                "add_gallery_still_album",
+                "Add a still album to the gallery",
+                Arc::new(json!({
+                    "type": "object",
+                    "properties": {
+                        "album_name": {
+                            "type": "string",
+                            "description": "Name for the new album"
+                        }
+                    },
+                    "required": ["album_name"],
+                    "additionalProperties": false
+                }).as_object().unwrap().clone()),
+            ),
+            Tool::new(
+                "rename_gallery_still_album",
+                "Rename a still album in the gallery",
+                Arc::new(json!({
+                    "type": "object",
+                    "properties": {
+                        "album_name": {
+                            "type": "string",
+                            "description": "Current name of the album"
+                        },
+                        "new_name": {
+                            "type": "string",
+                            "description": "New name for the album"
+                        }
+                    },
+                    "required": ["album_name", "new_name"],
+                    "additionalProperties": false
+                }).as_object().unwrap().clone()),
+            ),
+            Tool::new(
+                "set_current_still_album",
+                "Set the current still album that grabbed stills are added to",
+                Arc::new(json!({
+                    "type": "object",
+                    "properties": {
+                        "album_name": {
+                            "type": "string",
+                            "description": "Name of the album to make current"
+                        }
+                    },
+                    "required": ["album_name"],
+                    "additionalProperties": false
+                }).as_object().unwrap().clone()),
+            ),
+            Tool::new(
+                "get_current_still_album",
+                "Get the current still album in the gallery",
+                Arc::new(json!({
+                    "type": "object",
+                    "properties": {},
+                    "additionalProperties": false
+                }).as_object().unwrap().clone()),
+            ),
+            Tool::new(
+                "get_album_stills",
+                "Get the stills held in a gallery still album",
+                Arc::new(json!({
+                    "type": "object",
+                    "properties": {
+                        "album_name": {
+                            "type": "string",
+                            "description": "Album to list stills from (uses current album if None)"
+                        }
+                    },
+                    "additionalProperties": false
+                }).as_object().unwrap().clone()),
+            ),
+            Tool::new(
+                "move_gallery_stills",
+                "Move stills from one gallery still album to another",
+                Arc::new(json!({
+                    "type": "object",
+                    "properties": {
+                        "still_ids": {
+                            "type": "array",
+                            "items": {"type": "string"},
+                            "description": "IDs of the stills to transfer"
+                        },
+                        "source_album": {
+                            "type": "string",
+                            "description": "Album holding the stills (uses current album if None)"
+                        },
+                        "target_album": {
+                            "type": "string",
+                            "description": "Album receiving the stills"
+                        }
+                    },
+                    "required": ["still_ids", "target_album"],
+                    "additionalProperties": false
+                }).as_object().unwrap().clone()),
+            ),
+            Tool::new(
+                "copy_gallery_stills",
+                "Copy stills from one gallery still album to another",
+                Arc::new(json!({
+                    "type": "object",
+                    "properties": {
+                        "still_ids": {
+                            "type": "array",
+                            "items": {"type": "string"},
+                            "description": "IDs of the stills to transfer"
+                        },
+                        "source_album": {
+                            "type": "string",
+                            "description": "Album holding the stills (uses current album if None)"
+                        },
+                        "target_album": {
+                            "type": "string",
+                            "description": "Album receiving the stills"
+                        }
+                    },
+                    "required": ["still_ids", "target_album"],
+                    "additionalProperties": false
+                }).as_object().unwrap().clone()),
+            ),
+            Tool::new(
+                "get_fusion_tool_list",
+                "Get the list of tools in Fusion page",
+                Arc::new(json!({
+                    "type": "object",
+                    "properties": {
+                        "selected_only": {
+                            "type": "boolean",
+                            "description": "Whether to get only selected tools",
+                            "default": false
+                        },
+                        "tool_type": {
+                            "type": "string",
+                            "description": "Optional tool type filter"
+                        }
+                    },
+                    "additionalProperties": false
+                }).as_object().unwrap().clone()),
+            ),
+            Tool::new(
+                "get_audio_track_count",
+                "Get the number of audio tracks in the current timeline",
+                Arc::new(json!({
+                    "type": "object",
+                    "properties": {},
+                    "additionalProperties": false
+                }).as_object().unwrap().clone()),
+            ),
+        ];
+        for tool in tools.iter_mut() {
+            if ResolveBridge::is_mutating(&tool.name) {
+                registry::add_return_diff(Arc::make_mut(&mut tool.input_schema));
+            }
+        }
+        tools.extend(registry::TOOLS.iter().map(ToolSpec::to_tool));
+        if let Some(catalog) = self.locales.catalog(&self.locale()) {
+            for tool in tools.iter_mut() {
+                if let Some(description) = catalog.tool_description(&tool.name) {
+                    *tool = Tool::new(
+                        tool.name.clone(),
+                        description.to_string(),
+                        tool.input_schema.clone(),
+                    );
+                }
+            }
+        }
+        tools
+    }
+
+    /// Get list of the resources the server exposes
+    pub fn get_resources(&self) -> Vec<Resource> {
+        let mut stats = RawResource::new(TOOL_STATS_URI, "Tool usage statistics");
+        stats.description = Some(
+            "Invocation count, error rate and median latency of each tool since the server started"
+                .to_string(),
+        );
+        stats.mime_type = Some("application/json".to_string());
+        vec![stats.no_annotation()]
+    }
+
+    /// Read a resource as JSON text, or `None` if there is no resource at `uri`
+    pub async fn read_resource(&self, uri: &str) -> Option<String> {
+        match uri {
+            TOOL_STATS_URI => Some(
+                serde_json::to_string_pretty(&self.bridge.tool_stats().await).unwrap_or_default(),
+            ),
+            _ => None,
+        }
+    }
+}
+
+/// Sampling through the connected client, for describe_frame
+#[derive(Debug)]
+struct PeerSampler(Peer<RoleServer>);
+
+impl Sampler for PeerSampler {
+    fn create_message(
+        &self,
+        request: SamplingRequest,
+    ) -> Pin<Box<dyn Future<Output = ResolveResult<SamplingReply>> + Send + '_>> {
+        Box::pin(async move {
+            let result = self
+                .0
+                .create_message(CreateMessageRequestParam {
+                    messages: vec![
+                        SamplingMessage {
+                            role: Role::User,
+                            content: Content::image(request.image_base64, request.mime_type),
+                        },
+                        SamplingMessage {
+                            role: Role::User,
+                            content: Content::text(request.prompt),
+                        },
+                    ],
+                    model_preferences: None,
+                    system_prompt: Some(request.system_prompt),
+                    include_context: None,
+                    temperature: None,
+                    max_tokens: request.max_tokens,
+                    stop_sequences: None,
+                    metadata: None,
+                })
+                .await
+                .map_err(|e| ResolveError::api_call("describe_frame", e.to_string()))?;
+            let text = result
+                .message
+                .content
+                .as_text()
+                .map(|text| text.text.clone())
+                .ok_or_else(|| {
+                    ResolveError::api_call(
+                        "describe_frame",
+                        "the client's model answered without text",
+                    )
+                })?;
+            Ok(SamplingReply {
+                text,
+                model: Some(result.model),
+            })
+        })
+    }
+}
+
+impl Service<RoleServer> for DaVinciResolveServer {
+    async fn handle_request(
+        &self,
+        request: ClientRequest,
+        context: RequestContext<RoleServer>,
+    ) -> Result<ServerResult, ErrorData> {
+        match request {
+            ClientRequest::InitializeRequest(initialize_request) => {
+                // The client's name identifies the user until they identify by token
+                let params = initialize_request.params;
+                self.bridge.identify_client(&params.client_info.name).await;
+                // Clients ask for a locale with {"experimental": {"locale": {"tag": "de"}}}
+                let requested = params
+                    .capabilities
+                    .experimental
+                    .as_ref()
+                    .and_then(|experimental| experimental.get("locale"))
+                    .and_then(|locale| locale.get("tag"))
+                    .and_then(Value::as_str);
+                self.select_locale(requested).await;
+                // describe_frame asks the client's model, if the client lets us
+                let sampler = params
+                    .capabilities
+                    .sampling
+                    .is_some()
+                    .then(|| Arc::new(PeerSampler(context.peer.clone())) as Arc<dyn Sampler>);
+                self.bridge.set_sampler(sampler).await;
+                let info = self.get_info();
+                Ok(ServerResult::InitializeResult(info))
+            }
+            ClientRequest::ListToolsRequest(_) => {
+                let tools = self.get_tools();
+                Ok(ServerResult::ListToolsResult(ListToolsResult {
+                    tools,
+                    next_cursor: None,
+                }))
+            }
+            ClientRequest::ListResourcesRequest(_) => {
+                Ok(ServerResult::ListResourcesResult(ListResourcesResult {
+                    resources: self.get_resources(),
+                    next_cursor: None,
+                }))
+            }
+            ClientRequest::ReadResourceRequest(read_resource_request) => {
+                let uri = read_resource_request.params.uri;
+                match self.read_resource(&uri).await {
+                    Some(text) => Ok(ServerResult::ReadResourceResult(ReadResourceResult {
+                        contents: vec![ResourceContents::TextResourceContents {
+                            uri,
+                            mime_type: Some("application/json".to_string()),
+                            text,
+                        }],
+                    })),
+                    None => Err(ErrorData::resource_not_found(
+                        format!("No resource at {}", uri),
+                        None,
+                    )),
+                }
+            }
+            ClientRequest::CallToolRequest(call_tool_request) => {
+                // Extract the actual parameters from the request
+                let CallToolRequestParam { name, arguments } = call_tool_request.params;
+
+                match self.handle_tool_call(&name, arguments).await {
+                    Ok(content) => Ok(ServerResult::CallToolResult(CallToolResult {
+                        content: vec![Content::text(content)],
+                        is_error: Some(false),
+                    })),
+                    Err(e) => Ok(ServerResult::CallToolResult(CallToolResult {
+                        content: vec![Content::text(format!(
+                            "Error: {}",
+                            self.localize(&e.to_string())
+                        ))],
+                        is_error: Some(true),
+                    })),
+                }
+            }
+            _ => {
+                // Create a proper method not found error
+                Err(ErrorData::method_not_found::<CallToolRequestMethod>())
+            }
+        }
+    }
+
+    async fn handle_notification(
+        &self,
+        _notification: ClientNotification,
+    ) -> Result<(), ErrorData> {
+        // Handle notifications if needed
+        Ok(())
+    }
+
+    fn get_info(&self) -> InitializeResult {
+        InitializeResult {
+            protocol_version: ProtocolVersion::LATEST,
+            capabilities: ServerCapabilities {
+                tools: Some(rmcp::model::ToolsCapability {
+                    list_changed: None,
+                }),
+                resources: Some(rmcp::model::ResourcesCapability {
+                    subscribe: None,
                     list_changed: None,
                 }),
                 ..Default::default()
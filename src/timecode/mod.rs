@@ -0,0 +1,159 @@
+//! SMPTE timecode parsing and formatting
+//!
+//! Timecodes are `HH:MM:SS:FF`, with `;` before the frame field marking
+//! drop-frame timecode at 29.97 and 59.94 fps.
+
+use std::fmt;
+
+/// Frame rate used to convert between timecodes and frame counts
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FrameRate {
+    fps: f64,
+    drop_frame: bool,
+}
+
+impl FrameRate {
+    /// Create a non-drop-frame rate
+    pub fn new(fps: f64) -> Result<Self, String> {
+        if !fps.is_finite() || fps <= 0.0 {
+            return Err(format!("Invalid frame rate: {}", fps));
+        }
+        Ok(Self {
+            fps,
+            drop_frame: false,
+        })
+    }
+
+    /// Parse a frame rate such as `24`, `23.976`, `29.97 DF` or `59.94DF`
+    pub fn parse(rate: &str) -> Result<Self, String> {
+        let trimmed = rate.trim();
+        let upper = trimmed.to_ascii_uppercase();
+        let (number, drop_frame) = match upper.strip_suffix("DF") {
+            Some(number) => (number.trim(), true),
+            None => (trimmed, false),
+        };
+        let fps: f64 = number
+            .parse()
+            .map_err(|_| format!("Invalid frame rate: {}", rate))?;
+        Self::new(fps)?.with_drop_frame(drop_frame)
+    }
+
+    /// Switch drop-frame counting on or off; only 29.97 and 59.94 fps can drop frames
+    pub fn with_drop_frame(mut self, drop_frame: bool) -> Result<Self, String> {
+        if drop_frame && self.dropped_per_minute() == 0 {
+            return Err(format!(
+                "Drop-frame timecode is not defined at {} fps",
+                self.fps
+            ));
+        }
+        self.drop_frame = drop_frame;
+        Ok(self)
+    }
+
+    /// Rate to read `timecode` at: drop-frame when its frame separator is `;`
+    pub fn for_timecode(self, timecode: &str) -> Result<Self, String> {
+        if timecode.contains(';') {
+            self.with_drop_frame(true)
+        } else {
+            Ok(self)
+        }
+    }
+
+    /// Actual frames per second
+    pub fn fps(&self) -> f64 {
+        self.fps
+    }
+
+    /// Whole frames counted per timecode second (30 for 29.97)
+    pub fn nominal(&self) -> i64 {
+        self.fps.round() as i64
+    }
+
+    pub fn is_drop_frame(&self) -> bool {
+        self.drop_frame
+    }
+
+    /// Frame numbers skipped at the start of each minute in drop-frame counting
+    fn dropped_per_minute(&self) -> i64 {
+        if (self.fps - 29.97).abs() < 0.01 {
+            2
+        } else if (self.fps - 59.94).abs() < 0.01 {
+            4
+        } else {
+            0
+        }
+    }
+
+    /// Convert a timecode to a frame count from 00:00:00:00
+    pub fn timecode_to_frames(&self, timecode: &str) -> Result<i64, String> {
+        let timecode = timecode.trim();
+        let invalid = || format!("Invalid timecode: {}", timecode);
+        let fields: Vec<&str> = timecode.split([':', ';', '.']).collect();
+        if fields.len() != 4 || fields.iter().any(|field| field.is_empty()) {
+            return Err(invalid());
+        }
+        let mut values = [0i64; 4];
+        for (value, field) in values.iter_mut().zip(&fields) {
+            if !field.chars().all(|c| c.is_ascii_digit()) {
+                return Err(invalid());
+            }
+            *value = field.parse().map_err(|_| invalid())?;
+        }
+        let [hours, minutes, seconds, frames] = values;
+        let nominal = self.nominal();
+        if minutes > 59 || seconds > 59 || frames >= nominal {
+            return Err(invalid());
+        }
+
+        let total_minutes = hours * 60 + minutes;
+        let mut count = (total_minutes * 60 + seconds) * nominal + frames;
+        if self.drop_frame {
+            let dropped = self.dropped_per_minute();
+            if seconds == 0 && minutes % 10 != 0 && frames < dropped {
+                return Err(format!(
+                    "{} does not exist in drop-frame timecode",
+                    timecode
+                ));
+            }
+            count -= dropped * (total_minutes - total_minutes / 10);
+        }
+        Ok(count)
+    }
+
+    /// Convert a frame count from 00:00:00:00 to a timecode
+    pub fn frames_to_timecode(&self, frame: i64) -> String {
+        let nominal = self.nominal();
+        let mut frame = frame.max(0);
+        if self.drop_frame {
+            let dropped = self.dropped_per_minute();
+            let per_ten_minutes = nominal * 600 - dropped * 9;
+            let per_minute = nominal * 60 - dropped;
+            let tens = frame / per_ten_minutes;
+            let remainder = frame % per_ten_minutes;
+            frame += dropped * 9 * tens;
+            if remainder > dropped {
+                frame += dropped * ((remainder - dropped) / per_minute);
+            }
+        }
+
+        let frames = frame % nominal;
+        let seconds = (frame / nominal) % 60;
+        let minutes = (frame / (nominal * 60)) % 60;
+        let hours = frame / (nominal * 3600);
+        let separator = if self.drop_frame { ';' } else { ':' };
+        format!(
+            "{:02}:{:02}:{:02}{}{:02}",
+            hours, minutes, seconds, separator, frames
+        )
+    }
+}
+
+impl fmt::Display for FrameRate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.fps)?;
+        if self.drop_frame {
+            write!(f, " DF")?;
+        }
+        Ok(())
+    }
+}
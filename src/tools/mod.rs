@@ -5,6 +5,10 @@ use std::sync::Arc;
 use crate::bridge::ResolveBridge;
 use crate::error::ResolveResult;
 
+pub mod registry;
+
+pub use registry::NoArgumentsRequest;
+
 // Helper function for default color value
 fn default_color() -> String {
     "Blue".to_string()
@@ -56,6 +60,12 @@ pub struct CreateTimelineRequest {
 pub struct ImportMediaRequest {
     #[schemars(description = "Path to the media file to import")]
     pub file_path: String,
+    #[schemars(description = "Bin to import into (uses your default bin if None)")]
+    pub bin_name: Option<String>,
+    #[schemars(
+        description = "Metadata fields to set on the clip, such as Scene or Take; checked against the active metadata schema"
+    )]
+    pub metadata: Option<std::collections::BTreeMap<String, String>>,
 }
 
 #[derive(Debug, Deserialize, JsonSchema)]
@@ -71,6 +81,10 @@ pub struct AddMarkerRequest {
     pub color: String,
     #[schemars(description = "Text note to add to the marker")]
     pub note: String,
+    #[schemars(description = "Marker name")]
+    pub name: Option<String>,
+    #[schemars(description = "Frames the marker spans (default 1)")]
+    pub duration: Option<i64>,
 }
 
 // ---- Phase 3 Week 1: Media Operations Request Types ----
@@ -90,7 +104,7 @@ pub struct AutoSyncAudioRequest {
     #[schemars(description = "Whether to append the audio or replace it")]
     #[serde(default)]
     pub append_mode: bool,
-    #[schemars(description = "Optional bin to move synchronized clips to")]
+    #[schemars(description = "Optional bin to put the synced clips in, created if missing")]
     pub target_bin: Option<String>,
 }
 
@@ -111,6 +125,10 @@ pub struct RelinkClipsRequest {
     #[schemars(description = "Whether to search the folder path recursively")]
     #[serde(default)]
     pub recursive: bool,
+    #[schemars(
+        description = "How folder files are matched to clips - 'name' or 'timecode' (embedded start timecode and reel)"
+    )]
+    pub match_by: Option<String>,
 }
 
 #[derive(Debug, Deserialize, JsonSchema)]
@@ -188,6 +206,20 @@ pub struct AddClipToTimelineRequest {
     pub clip_name: String,
     #[schemars(description = "Optional timeline to target (uses current if not specified)")]
     pub timeline_name: Option<String>,
+    #[schemars(description = "Video track to append to (default 1)")]
+    pub track_index: Option<i32>,
+    #[schemars(
+        description = "First source frame to use (defaults to the clip's in point, else 0)"
+    )]
+    pub start_frame: Option<i64>,
+    #[schemars(
+        description = "Source frame to end before (defaults to the frame after the clip's out point, else the end of the clip)"
+    )]
+    pub end_frame: Option<i64>,
+    #[schemars(
+        description = "Timeline frame to place the clip at, from the timeline start (appends after the last item on the track if None)"
+    )]
+    pub record_frame: Option<i64>,
 }
 
 #[derive(Debug, Deserialize, JsonSchema)]
@@ -203,6 +235,10 @@ pub struct ApplyLutRequest {
     pub lut_path: String,
     #[schemars(description = "Index of the node to apply the LUT to (uses current node if None)")]
     pub node_index: Option<i32>,
+    #[schemars(
+        description = "Apply the LUT even if it expects another input color space than the node receives (default false)"
+    )]
+    pub allow_color_space_mismatch: Option<bool>,
 }
 
 #[derive(Debug, Deserialize, JsonSchema)]
@@ -215,6 +251,12 @@ pub struct SetColorWheelParamRequest {
     pub value: f64,
     #[schemars(description = "Index of the node to set parameter for (uses current node if None)")]
     pub node_index: Option<i32>,
+    #[schemars(description = "Clip to grade (uses current clip if None)")]
+    pub clip_name: Option<String>,
+    #[schemars(
+        description = "Add the value to the current one instead of replacing it, clamped to -1.0..1.0"
+    )]
+    pub relative: Option<bool>,
 }
 
 #[derive(Debug, Deserialize, JsonSchema)]
@@ -278,6 +320,12 @@ pub struct DeleteColorPresetRequest {
     pub album_name: String,
 }
 
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ListColorPresetsRequest {
+    #[schemars(description = "Optional album to list presets from (lists all albums if None)")]
+    pub album_name: Option<String>,
+}
+
 #[derive(Debug, Deserialize, JsonSchema)]
 pub struct ExportLutRequest {
     #[schemars(description = "Name of the clip to export grade from (uses current clip if None)")]
@@ -337,6 +385,22 @@ pub struct SetTimelineItemRetimeRequest {
     pub process: Option<String>,
 }
 
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct SetItemOutputSizingRequest {
+    #[schemars(description = "The ID of the timeline item to modify")]
+    pub timeline_item_id: String,
+    #[schemars(
+        description = "Optional scaling mode. Options: 'UseProject', 'Crop', 'Fit', 'Fill', 'Stretch'"
+    )]
+    pub scaling: Option<String>,
+    #[schemars(description = "Optional boolean to enable/disable Super Scale upscaling")]
+    pub super_scale: Option<bool>,
+    #[schemars(description = "Optional Super Scale factor. Options: '2x', '3x', '4x'")]
+    pub super_scale_factor: Option<String>,
+    #[schemars(description = "Optional lens distortion correction (-1.0 to 1.0)")]
+    pub lens_correction: Option<f64>,
+}
+
 #[derive(Debug, Deserialize, JsonSchema)]
 pub struct SetTimelineItemStabilizationRequest {
     #[schemars(description = "The ID of the timeline item to modify")]
@@ -376,7 +440,7 @@ pub struct ResetTimelineItemPropertiesRequest {
     #[schemars(description = "The ID of the timeline item to reset")]
     pub timeline_item_id: String,
     #[schemars(
-        description = "Optional property type to reset. Options: 'transform', 'crop', 'composite', 'retime', 'stabilization', 'audio'. If None, resets all properties"
+        description = "Optional property type to reset. Options: 'transform', 'crop', 'composite', 'retime', 'sizing', 'stabilization', 'audio'. If None, resets all properties"
     )]
     pub property_type: Option<String>,
 }
@@ -454,13 +518,33 @@ pub struct GetKeyframesRequest {
 // ---- Render and Delivery Operations (Phase 4 Week 3) ----
 #[derive(Debug, Deserialize, JsonSchema)]
 pub struct AddToRenderQueueRequest {
-    #[schemars(description = "Name of the render preset to use")]
-    pub preset_name: String,
+    #[schemars(
+        description = "Name of the render preset to use (uses your default preset if None)"
+    )]
+    pub preset_name: Option<String>,
     #[schemars(description = "Name of the timeline to render (uses current if None)")]
     pub timeline_name: Option<String>,
     #[schemars(description = "Whether to render only the in/out range instead of entire timeline")]
     #[serde(default)]
     pub use_in_out_range: bool,
+    #[schemars(description = "Render the video track (uses the preset's setting if None)")]
+    pub export_video: Option<bool>,
+    #[schemars(description = "Render the audio track (uses the preset's setting if None)")]
+    pub export_audio: Option<bool>,
+    #[schemars(
+        description = "Audio channel layout ('Mono', 'Stereo', '5.1', '7.1'; uses the preset's if None)"
+    )]
+    pub audio_channel_layout: Option<String>,
+    #[schemars(
+        description = "Subtitle delivery ('none', 'burn_in' or 'separate_file'; default 'none')"
+    )]
+    pub subtitle_export: Option<String>,
+    #[schemars(
+        description = "Subtitle file format for separate_file ('srt' or 'webvtt'; default 'srt')"
+    )]
+    pub subtitle_format: Option<String>,
+    #[schemars(description = "Subtitle track to export, starting at 1 (default 1)")]
+    pub subtitle_track_index: Option<u32>,
 }
 
 #[derive(Debug, Deserialize, JsonSchema)]
@@ -499,7 +583,9 @@ pub struct SetProjectSettingRequest {
 pub struct TranscribeAudioRequest {
     #[schemars(description = "Name of the clip to transcribe")]
     pub clip_name: String,
-    #[schemars(description = "Language code for transcription (default: en-US)")]
+    #[schemars(
+        description = "Language for transcription, as a BCP-47 code or name such as en-US or english (default: en-US; see list_supported_languages)"
+    )]
     #[serde(default = "default_language")]
     pub language: String,
 }
@@ -531,9 +617,9 @@ pub struct ExportProjectRequest {
 pub struct CreateRenderPresetRequest {
     #[schemars(description = "Name for the new render preset")]
     pub preset_name: String,
-    #[schemars(description = "Output format (MP4, MOV, MXF, etc.)")]
+    #[schemars(description = "Output format (MP4, MOV, MXF, EXR, etc.; see list_render_formats)")]
     pub format: String,
-    #[schemars(description = "Video codec (H.264, H.265, ProRes, etc.)")]
+    #[schemars(description = "Video codec supported by the format (see list_render_formats)")]
     pub codec: String,
     #[schemars(description = "Output width in pixels")]
     pub resolution_width: u32,
@@ -549,6 +635,30 @@ pub struct CreateRenderPresetRequest {
     #[schemars(description = "Audio bitrate in bps (e.g., 192000 for 192kbps)")]
     #[serde(default = "default_audio_bitrate")]
     pub audio_bitrate: u32,
+    #[schemars(description = "Video bit depth (uses the codec's lowest if None)")]
+    pub bit_depth: Option<u8>,
+    #[schemars(description = "Render the alpha channel; the codec must support alpha")]
+    #[serde(default)]
+    pub export_alpha: bool,
+    #[schemars(description = "Render the video track")]
+    #[serde(default = "default_export_video")]
+    pub export_video: bool,
+    #[schemars(description = "Render the audio track (on if the format carries audio when None)")]
+    pub export_audio: Option<bool>,
+    #[schemars(
+        description = "Audio channel layout ('Mono', 'Stereo', '5.1', '7.1'; Stereo if None)"
+    )]
+    pub audio_channel_layout: Option<String>,
+    #[schemars(
+        description = "Embed timeline markers as chapters, titled with their notes (MP4 and MOV only)"
+    )]
+    #[serde(default)]
+    pub embed_chapters: bool,
+    #[schemars(
+        description = "Embed the title, author and copyright project settings (MP4 and MOV only)"
+    )]
+    #[serde(default)]
+    pub embed_metadata: bool,
 }
 
 // Helper functions for color operations defaults
@@ -588,6 +698,10 @@ fn default_audio_bitrate() -> u32 {
     192000
 }
 
+fn default_export_video() -> bool {
+    true
+}
+
 // ---- NEW: Extended Project Management Operations ----
 #[derive(Debug, Deserialize, JsonSchema)]
 pub struct DeleteMediaRequest {
@@ -620,7 +734,9 @@ pub struct ExportFolderRequest {
 pub struct TranscribeFolderAudioRequest {
     #[schemars(description = "Name of the folder containing clips to transcribe")]
     pub folder_name: String,
-    #[schemars(description = "Language code for transcription (default: en-US)")]
+    #[schemars(
+        description = "Language for transcription, as a BCP-47 code or name such as en-US or english (default: en-US; see list_supported_languages)"
+    )]
     #[serde(default = "default_language")]
     pub language: String,
 }
@@ -903,6 +1019,10 @@ pub struct SetTimelineTimecodeRequest {
     pub timeline_name: Option<String>,
     #[schemars(description = "Timecode to set")]
     pub timecode: String,
+    #[schemars(
+        description = "Record timecode of the timeline's first frame (default 01:00:00:00)"
+    )]
+    pub timeline_start_timecode: Option<String>,
 }
 
 #[derive(Debug, Deserialize, JsonSchema)]
@@ -996,9 +1116,11 @@ pub struct ExportTimelineRequest {
     pub timeline_name: Option<String>,
     #[schemars(description = "Export file name")]
     pub file_name: String,
-    #[schemars(description = "Export type (AAF, EDL, XML, FCPXML, DRT, ADL, OTIO)")]
+    #[schemars(description = "Export type (AAF, EDL, XML, FCPXML, DRT, OTIO, CSV, TAB)")]
     pub export_type: String,
-    #[schemars(description = "Export subtype")]
+    #[schemars(
+        description = "Export subtype: linked or embedded for AAF; none, cdl, sdl or missing_clips for EDL; 1.8, 1.9 or 1.10 for FCPXML"
+    )]
     pub export_subtype: Option<String>,
 }
 
@@ -1038,19 +1160,35 @@ pub struct GrabStillRequest {
 // ---- NEW: TimelineItem Object API ----
 #[derive(Debug, Deserialize, JsonSchema)]
 pub struct GetTimelineItemPropertyRequest {
-    #[schemars(description = "Timeline item ID")]
-    pub timeline_item_id: String,
-    #[schemars(description = "Property key")]
+    #[schemars(description = "Timeline item ID (or give track_index and item_index)")]
+    pub timeline_item_id: Option<String>,
+    #[schemars(
+        description = "Track type when locating by position: video (default), audio or subtitle"
+    )]
+    pub track_type: Option<String>,
+    #[schemars(description = "Track number, starting at 1, when locating by position")]
+    pub track_index: Option<u32>,
+    #[schemars(description = "Item position on the track, starting at 1")]
+    pub item_index: Option<u32>,
+    #[schemars(description = "Property key or Resolve alias (returns all properties if omitted)")]
     pub property_key: Option<String>,
 }
 
 #[derive(Debug, Deserialize, JsonSchema)]
 pub struct SetTimelineItemPropertyRequest {
-    #[schemars(description = "Timeline item ID")]
-    pub timeline_item_id: String,
-    #[schemars(description = "Property key")]
+    #[schemars(description = "Timeline item ID (or give track_index and item_index)")]
+    pub timeline_item_id: Option<String>,
+    #[schemars(
+        description = "Track type when locating by position: video (default), audio or subtitle"
+    )]
+    pub track_type: Option<String>,
+    #[schemars(description = "Track number, starting at 1, when locating by position")]
+    pub track_index: Option<u32>,
+    #[schemars(description = "Item position on the track, starting at 1")]
+    pub item_index: Option<u32>,
+    #[schemars(description = "Property key or Resolve alias, e.g. 'ZoomX' or 'CropLeft'")]
     pub property_key: String,
-    #[schemars(description = "Property value")]
+    #[schemars(description = "Value of the property's type, within its range")]
     pub property_value: serde_json::Value,
 }
 
@@ -1146,7 +1284,9 @@ pub struct VersionRequest {
 pub struct StereoParamsRequest {
     #[schemars(description = "Timeline item ID")]
     pub timeline_item_id: String,
-    #[schemars(description = "Stereo parameters")]
+    #[schemars(
+        description = "Stereo parameters to set, as for set_stereo_params (gets them if None)"
+    )]
     pub params: Option<serde_json::Value>,
 }
 
@@ -1164,7 +1304,9 @@ pub struct NodeLUTRequest {
 pub struct SetCDLRequest {
     #[schemars(description = "Timeline item ID")]
     pub timeline_item_id: String,
-    #[schemars(description = "CDL parameters")]
+    #[schemars(
+        description = "CDL values keyed NodeIndex, Slope, Offset, Power and Saturation, as in Resolve's SetCDL"
+    )]
     pub cdl_map: serde_json::Value,
 }
 
@@ -1368,7 +1510,9 @@ pub struct UnlinkMediaPoolItemProxyMediaRequest {
 pub struct TranscribeMediaPoolItemAudioRequest {
     #[schemars(description = "Name of the clip")]
     pub clip_name: String,
-    #[schemars(description = "Language code for transcription")]
+    #[schemars(
+        description = "Language for transcription, as a BCP-47 code or name such as en-US or english (default: en-US; see list_supported_languages)"
+    )]
     #[serde(default = "default_language")]
     pub language: String,
 }
@@ -1414,1894 +1558,4773 @@ pub struct AddGalleryStillAlbumRequest {
 }
 
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
-pub struct AddMediaPoolSubFolderRequest {
-    #[schemars(description = "Name for the new folder")]
-    pub name: String,
-    #[schemars(description = "Optional parent folder")]
-    pub parent_folder: Option<String>,
+pub struct RenameGalleryStillAlbumRequest {
+    #[schemars(description = "Current name of the album")]
+    pub album_name: String,
+    #[schemars(description = "New name for the album")]
+    pub new_name: String,
 }
 
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
-pub struct GetProjectTimelineByIndexRequest {
-    #[schemars(description = "Timeline index")]
-    pub timeline_index: i32,
+pub struct SetCurrentStillAlbumRequest {
+    #[schemars(description = "Name of the album to make current")]
+    pub album_name: String,
 }
 
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
-pub struct GetProjectCurrentTimelineRequest {
+pub struct GetCurrentStillAlbumRequest {
     // No additional parameters needed
 }
 
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
-pub struct SetProjectCurrentTimelineRequest {
-    #[schemars(description = "Timeline name to set as current")]
-    pub timeline_name: String,
+pub struct GetAlbumStillsRequest {
+    #[schemars(description = "Album to list stills from (uses current album if None)")]
+    pub album_name: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
-pub struct GetProjectNameRequest {
+pub struct TransferGalleryStillsRequest {
+    #[schemars(description = "IDs of the stills to transfer")]
+    pub still_ids: Vec<String>,
+    #[schemars(description = "Album holding the stills (uses current album if None)")]
+    pub source_album: Option<String>,
+    #[schemars(description = "Album receiving the stills")]
+    pub target_album: String,
+}
+
+// ---- NEW: Resource Limits ----
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct GetResourceUsageRequest {
     // No additional parameters needed
 }
 
+// ---- NEW: Marker Import/Export ----
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
-pub struct SetProjectNameRequest {
-    #[schemars(description = "New project name")]
-    pub project_name: String,
+pub struct ImportMarkersRequest {
+    #[schemars(description = "Marker list format (csv, json)")]
+    pub format: String,
+    #[schemars(
+        description = "Marker list contents; CSV needs a Timecode column and may have Color and Note columns"
+    )]
+    pub data: String,
+    #[schemars(description = "Timeline to add markers to (uses current if None)")]
+    pub timeline_name: Option<String>,
+    #[schemars(
+        description = "Record timecode of the timeline's first frame (default 01:00:00:00)"
+    )]
+    pub timeline_start_timecode: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
-pub struct GetProjectUniqueIdRequest {
-    // No additional parameters needed
+pub struct ExportMarkersRequest {
+    #[schemars(description = "Export format (csv, json, edl)")]
+    pub format: String,
+    #[schemars(description = "Timeline to export markers from (uses current if None)")]
+    pub timeline_name: Option<String>,
+    #[schemars(
+        description = "Record timecode of the timeline's first frame (default 01:00:00:00)"
+    )]
+    pub timeline_start_timecode: Option<String>,
+}
+
+// ---- NEW: Clip Marks ----
+fn clip_mark_type_schema(_: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+    registry::string_enum(&["all", "video", "audio"])
 }
 
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
-pub struct GetProjectRenderJobListRequest {
-    // No additional parameters needed
+pub struct SetClipInOutRequest {
+    #[schemars(description = "Media pool clip, by name or ID")]
+    pub clip_name: String,
+    #[schemars(description = "First marked frame of the clip's media, from 0")]
+    pub in_frame: i64,
+    #[schemars(description = "Last marked frame, included in the range")]
+    pub out_frame: i64,
+    #[serde(default)]
+    #[schemars(
+        description = "Marks to set: 'all' (default), 'video' or 'audio'; add_clip_to_timeline uses the video marks",
+        schema_with = "clip_mark_type_schema"
+    )]
+    pub mark_type: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
-pub struct StartProjectRenderingRequest {
-    // No additional parameters needed
+pub struct GetClipInOutRequest {
+    #[schemars(description = "Media pool clip, by name or ID")]
+    pub clip_name: String,
 }
 
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
-pub struct StopProjectRenderingRequest {
-    // No additional parameters needed
+pub struct ClearClipInOutRequest {
+    #[schemars(description = "Media pool clip, by name or ID")]
+    pub clip_name: String,
+    #[serde(default)]
+    #[schemars(
+        description = "Marks to clear: 'all' (default), 'video' or 'audio'",
+        schema_with = "clip_mark_type_schema"
+    )]
+    pub mark_type: Option<String>,
 }
 
+// ---- NEW: Item Trimming ----
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
-pub struct IsProjectRenderingInProgressRequest {
-    // No additional parameters needed
+pub struct TrimItemHeadRequest {
+    #[schemars(description = "Timeline item ID")]
+    pub timeline_item_id: String,
+    #[schemars(
+        description = "Frames to move the item's start: positive trims frames off the head, negative extends it into earlier media"
+    )]
+    pub frames: i64,
 }
 
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
-pub struct GetProjectPresetListRequest {
-    // No additional parameters needed
+pub struct TrimItemTailRequest {
+    #[schemars(description = "Timeline item ID")]
+    pub timeline_item_id: String,
+    #[schemars(
+        description = "Frames to move the item's end: positive extends it into later media, negative trims frames off the tail"
+    )]
+    pub frames: i64,
 }
 
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
-pub struct LoadProjectRenderPresetRequest {
-    #[schemars(description = "Name of the preset to load")]
-    pub preset_name: String,
+pub struct SlipItemRequest {
+    #[schemars(description = "Timeline item ID")]
+    pub timeline_item_id: String,
+    #[schemars(
+        description = "Frames to move the source range the item shows: positive shows later media, negative earlier"
+    )]
+    pub frames: i64,
 }
 
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
-pub struct SaveAsNewProjectRenderPresetRequest {
-    #[schemars(description = "Name for the new preset")]
+pub struct SlideItemRequest {
+    #[schemars(description = "Timeline item ID")]
+    pub timeline_item_id: String,
+    #[schemars(
+        description = "Frames to move the item along its track: positive later, negative earlier"
+    )]
+    pub frames: i64,
+}
+
+// ---- NEW: Marker Range Renders ----
+fn marker_range_source_schema(_: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+    registry::string_enum(&["duration", "pairs"])
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct RenderMarkerRangesRequest {
+    #[schemars(description = "Timeline to render, by name or ID (uses current if None)")]
+    pub timeline_name: Option<String>,
+    #[schemars(description = "Render preset every job uses")]
     pub preset_name: String,
+    #[serde(default)]
+    #[schemars(
+        description = "'duration' (default) renders the frames each duration marker spans; 'pairs' takes markers of each color two at a time in frame order as in and out points, the out marker's frame rendered last",
+        schema_with = "marker_range_source_schema"
+    )]
+    pub source: Option<String>,
+    #[schemars(description = "Only use markers of this color")]
+    pub color: Option<String>,
+    #[schemars(
+        description = "Output folder template with {project} and {timeline} placeholders (default '/tmp/renders/{project}/{timeline}'); each file is named after its marker"
+    )]
+    pub output_directory: Option<String>,
+    #[schemars(description = "Render the video track (uses the preset's setting if None)")]
+    pub export_video: Option<bool>,
+    #[schemars(description = "Render the audio track (uses the preset's setting if None)")]
+    pub export_audio: Option<bool>,
+    #[schemars(
+        description = "Audio channel layout ('Mono', 'Stereo', '5.1', '7.1'; uses the preset's if None)"
+    )]
+    pub audio_channel_layout: Option<String>,
 }
 
+// ---- NEW: Delivery Packages ----
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
-pub struct GetCurrentProjectRenderFormatAndCodecRequest {
-    // No additional parameters needed
+pub struct DeliveryOutput {
+    #[schemars(description = "Name added to the output's file name, such as 'master' or 'review'")]
+    pub name: String,
+    #[schemars(description = "Render preset the output is rendered with")]
+    pub preset: String,
+    #[schemars(description = "Render the video track (uses the preset's setting if None)")]
+    pub export_video: Option<bool>,
+    #[schemars(description = "Render the audio track (uses the preset's setting if None)")]
+    pub export_audio: Option<bool>,
+    #[schemars(
+        description = "Audio channel layout ('Mono', 'Stereo', '5.1', '7.1'; uses the preset's if None)"
+    )]
+    pub audio_channel_layout: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
-pub struct SetCurrentProjectRenderFormatAndCodecRequest {
-    #[schemars(description = "Render format")]
-    pub format: String,
-    #[schemars(description = "Render codec")]
-    pub codec: String,
+pub struct QueueDeliveryPackageRequest {
+    #[schemars(description = "Delivery package configured under [delivery_packages]")]
+    pub package: Option<String>,
+    #[schemars(description = "Outputs to queue instead of a configured package")]
+    pub outputs: Option<Vec<DeliveryOutput>>,
+    #[schemars(
+        description = "Timelines to deliver, by name or ID, in queue order (uses current if None)"
+    )]
+    pub timeline_names: Option<Vec<String>>,
+    #[schemars(
+        description = "Output folder template with {project}, {timeline} and {package} placeholders (default '/tmp/renders/{project}/{timeline}'); files are named '<timeline>_<output>'"
+    )]
+    pub output_directory: Option<String>,
+    #[schemars(description = "Whether to render only each timeline's in/out range")]
+    #[serde(default)]
+    pub use_in_out_range: bool,
+    #[schemars(
+        description = "Write '<timeline>_manifest.json' listing the outputs next to them (default true)"
+    )]
+    pub write_manifest: Option<bool>,
+}
+
+// ---- NEW: Media Organization ----
+fn bin_rule_key_schema(_: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+    registry::string_enum(&["date", "camera", "reel", "resolution", "metadata"])
 }
 
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
-pub struct GetCurrentProjectRenderModeRequest {
-    // No additional parameters needed
+pub struct OrganizeBinRule {
+    #[schemars(
+        description = "What the bin is chosen by: 'date' recorded, 'camera', 'reel', 'resolution' or a 'metadata' field",
+        schema_with = "bin_rule_key_schema"
+    )]
+    pub by: String,
+    #[schemars(description = "Metadata field read when sorting by 'metadata', such as 'Scene'")]
+    pub field: Option<String>,
+    #[schemars(
+        description = "Bin name with {value} standing for the value read, such as 'Scene {value}' (default: the value alone)"
+    )]
+    pub bin: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
-pub struct SetCurrentProjectRenderModeRequest {
-    #[schemars(description = "Render mode")]
-    pub render_mode: String,
+pub struct AutoOrganizeMediaRequest {
+    #[schemars(
+        description = "Rules tried in order; the first that reads a value for a clip picks its bin (default: the rules in the config)"
+    )]
+    pub rules: Option<Vec<OrganizeBinRule>>,
+    #[schemars(description = "Clips to sort (default: every clip in the media pool)")]
+    pub clip_names: Option<Vec<String>>,
+    #[schemars(description = "Report the moves without making them (default false)")]
+    pub dry_run: Option<bool>,
 }
 
+// ---- NEW: Grade History ----
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
-pub struct GetProjectColorGroupsListRequest {
-    // No additional parameters needed
+pub struct SetGradeHistoryRequest {
+    #[schemars(description = "Keep a version of each clip grade a call changes")]
+    pub enabled: bool,
+    #[schemars(
+        description = "Grab a gallery still of each version into the Grade History album (unchanged if None)"
+    )]
+    pub thumbnails: Option<bool>,
 }
 
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
-pub struct AddProjectColorGroupRequest {
-    #[schemars(description = "Name for the new color group")]
-    pub group_name: String,
+pub struct GetGradeHistoryRequest {
+    #[schemars(
+        description = "Clip whose versions to list (lists the clips with versions if None)"
+    )]
+    pub clip_name: Option<String>,
+    #[schemars(description = "Include each version's full grade (default false)")]
+    pub include_grades: Option<bool>,
 }
 
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
-pub struct DeleteProjectColorGroupRequest {
-    #[schemars(description = "Name of the color group to delete")]
-    pub group_name: String,
+pub struct RevertGradeToRequest {
+    #[schemars(description = "Clip whose grade to revert")]
+    pub clip_name: String,
+    #[schemars(
+        description = "Version to go back to, as get_grade_history numbers them; 0 is the grade before the first recorded change"
+    )]
+    pub version: u64,
 }
 
+// ---- NEW: Render Job Deletion ----
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
-pub struct AppendToTimelineRequest {
-    #[schemars(description = "List of clip names to append")]
-    pub clip_info: Vec<String>,
-    #[schemars(description = "Optional timeline name (uses current if not specified)")]
-    pub timeline_name: Option<String>,
+pub struct DeleteRenderJobRequest {
+    #[schemars(description = "ID of the render job to delete, as the render queue lists it")]
+    pub job_id: String,
 }
 
-// ============================================
-// TOOL IMPLEMENTATIONS
-// ============================================
+// ---- NEW: Grading Sessions ----
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct StartGradingSessionRequest {
+    #[schemars(description = "Name of the session (defaults to its start date and time)")]
+    pub name: Option<String>,
+    #[schemars(description = "Timeline being graded (defaults to the current timeline)")]
+    pub timeline_name: Option<String>,
+    #[schemars(description = "Notes to keep with the session, such as the client or the look")]
+    pub notes: Option<String>,
+}
 
-#[derive(Debug)]
-pub struct ProjectTools {
-    bridge: Arc<ResolveBridge>,
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct EndGradingSessionRequest {
+    #[schemars(description = "ID of the open session, to make sure the right one is ended")]
+    pub session_id: Option<String>,
+    #[schemars(description = "Notes to add to the session")]
+    pub notes: Option<String>,
+    #[schemars(description = "JSON file to write the session artifact to")]
+    pub output_path: Option<String>,
 }
 
-impl ProjectTools {
-    pub fn new(bridge: Arc<ResolveBridge>) -> Self {
-        Self { bridge }
-    }
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct GetGradingSessionRequest {
+    #[schemars(description = "ID of an ended session (lists every session if None)")]
+    pub session_id: Option<String>,
+    #[schemars(description = "Name of an ended session; the latest one of that name")]
+    pub name: Option<String>,
+}
 
-    pub async fn create_project(&self, req: CreateProjectRequest) -> ResolveResult<String> {
-        let args = serde_json::json!({
-            "name": req.name
-        });
+// ---- NEW: ALE ----
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ExportAleRequest {
+    #[schemars(
+        description = "Clips to export (every clip in the bin, or the media pool, if None)"
+    )]
+    pub clip_names: Option<Vec<String>>,
+    #[schemars(description = "Only export the clips in this bin")]
+    pub bin_name: Option<String>,
+    #[schemars(description = "File to write the ALE to; the content is returned either way")]
+    pub output_path: Option<String>,
+}
 
-        self.bridge.call_api("create_project", args).await?;
-        Ok(format!("Successfully created project '{}'", req.name))
-    }
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ImportAleRequest {
+    #[schemars(description = "Contents of the ALE file")]
+    pub data: String,
+    #[schemars(description = "Report the matches without changing any metadata")]
+    pub dry_run: Option<bool>,
+}
 
-    pub async fn open_project(&self, req: OpenProjectRequest) -> ResolveResult<String> {
-        let args = serde_json::json!({
-            "name": req.name
-        });
+// ---- NEW: Metadata Schemas ----
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ValidateMetadataRequest {
+    #[schemars(
+        description = "Metadata schema from the [metadata] config to check against (defaults to the active schema)"
+    )]
+    pub schema: Option<String>,
+    #[schemars(description = "Only check the clips in this bin")]
+    pub bin_name: Option<String>,
+}
 
-        self.bridge.call_api("open_project", args).await?;
-        Ok(format!("Successfully opened project '{}'", req.name))
-    }
+// ---- NEW: A/V Offset ----
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct DetectAvOffsetRequest {
+    #[schemars(description = "Render file to check")]
+    pub file_path: String,
+    #[schemars(
+        description = "Earlier render to compare against; leave out to compare against a timeline"
+    )]
+    pub reference_path: Option<String>,
+    #[schemars(
+        description = "Timeline the render was made from (defaults to the current timeline when no reference_path is given)"
+    )]
+    pub timeline_name: Option<String>,
+    #[schemars(description = "Furthest apart the versions are searched for (default 10 seconds)")]
+    pub max_offset_seconds: Option<f64>,
+}
 
-    pub async fn switch_page(&self, req: SwitchPageRequest) -> ResolveResult<String> {
-        let args = serde_json::json!({
-            "page": req.page
-        });
+// ---- NEW: Share Links ----
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct GetShareLinkRequest {
+    #[schemars(description = "Completed render job whose output file to share")]
+    pub job_id: String,
+    #[schemars(
+        description = "Hours the link works for, at most the configured share.link_ttl_hours (defaults to it)"
+    )]
+    pub expires_in_hours: Option<u64>,
+}
 
-        self.bridge.call_api("switch_page", args).await?;
-        Ok(format!("Successfully switched to {} page", req.page))
-    }
+// ---- NEW: Rename Batch ----
+fn rename_target_schema(_: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+    registry::string_enum(&["clips", "bins", "timelines", "metadata"])
 }
 
-#[derive(Debug)]
-pub struct TimelineTools {
-    bridge: Arc<ResolveBridge>,
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct RenameBatchRequest {
+    #[schemars(
+        description = "What to find and replace in: clip, bin or timeline names, or the clip metadata field named by metadata_field",
+        schema_with = "rename_target_schema"
+    )]
+    pub target: String,
+    #[schemars(description = "Regular expression matched against every name or value")]
+    pub find: String,
+    #[schemars(description = "Replacement for each match; $1 or ${name} insert capture groups")]
+    pub replace: String,
+    #[schemars(
+        description = "Clip metadata field to edit for the metadata target, such as 'Comments', 'Reel Name' or 'Scene'"
+    )]
+    pub metadata_field: Option<String>,
+    #[schemars(description = "Match letters regardless of case (default: false)")]
+    pub case_insensitive: Option<bool>,
+    #[schemars(
+        description = "Report the renames and collisions without renaming anything (default: false)"
+    )]
+    pub dry_run: Option<bool>,
 }
 
-impl TimelineTools {
-    pub fn new(bridge: Arc<ResolveBridge>) -> Self {
-        Self { bridge }
-    }
+// ---- NEW: Trash ----
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ListTrashRequest {
+    #[schemars(description = "Only list entries of this kind: media, timeline or color_preset")]
+    pub kind: Option<String>,
+}
 
-    pub async fn create_timeline(&self, req: CreateTimelineRequest) -> ResolveResult<String> {
-        let args = serde_json::json!({
-            "name": req.name,
-            "frame_rate": req.frame_rate,
-            "resolution_width": req.resolution_width,
-            "resolution_height": req.resolution_height,
-        });
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct RestoreFromTrashRequest {
+    #[schemars(
+        description = "Trash entry to restore, as list_trash or the delete call returned it"
+    )]
+    pub trash_id: String,
+}
 
-        self.bridge.call_api("create_timeline", args).await?;
-        Ok(format!("Successfully created timeline '{}'", req.name))
-    }
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct EmptyTrashRequest {
+    #[schemars(
+        description = "Only delete entries of this kind: media, timeline or color_preset (every entry if None)"
+    )]
+    pub kind: Option<String>,
+}
 
-    pub async fn add_marker(&self, req: AddMarkerRequest) -> ResolveResult<String> {
-        let args = serde_json::json!({
-            "frame": req.frame,
-            "color": req.color,
-            "note": req.note,
-        });
+// ---- NEW: Qualifier ----
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SetQualifierRequest {
+    #[schemars(description = "Clip to grade (uses the current clip if None)")]
+    pub clip_name: Option<String>,
+    #[schemars(description = "Node to set, starting at 1 (uses the current node if None)")]
+    #[schemars(range(min = 1))]
+    pub node_index: Option<u32>,
+    #[schemars(description = "Key on hue")]
+    pub hue_enabled: Option<bool>,
+    #[schemars(
+        description = "Center of the hue range (0-100; default 50)",
+        range(min = 0.0, max = 100.0)
+    )]
+    pub hue_center: Option<f64>,
+    #[schemars(
+        description = "Width of the hue range (0-100; default 100, every hue)",
+        range(min = 0.0, max = 100.0)
+    )]
+    pub hue_width: Option<f64>,
+    #[schemars(
+        description = "Softness of the hue range edges (0-50)",
+        range(min = 0.0, max = 50.0)
+    )]
+    pub hue_softness: Option<f64>,
+    #[schemars(
+        description = "Balance of the hue softness between its edges (0-100; default 50)",
+        range(min = 0.0, max = 100.0)
+    )]
+    pub hue_symmetry: Option<f64>,
+    #[schemars(description = "Key on saturation")]
+    pub sat_enabled: Option<bool>,
+    #[schemars(
+        description = "Lowest saturation keyed (0-100; default 0)",
+        range(min = 0.0, max = 100.0)
+    )]
+    pub sat_low: Option<f64>,
+    #[schemars(
+        description = "Highest saturation keyed (0-100; default 100)",
+        range(min = 0.0, max = 100.0)
+    )]
+    pub sat_high: Option<f64>,
+    #[schemars(
+        description = "Softness below the lowest saturation (0-50)",
+        range(min = 0.0, max = 50.0)
+    )]
+    pub sat_low_softness: Option<f64>,
+    #[schemars(
+        description = "Softness above the highest saturation (0-50)",
+        range(min = 0.0, max = 50.0)
+    )]
+    pub sat_high_softness: Option<f64>,
+    #[schemars(description = "Key on luminance")]
+    pub lum_enabled: Option<bool>,
+    #[schemars(
+        description = "Lowest luminance keyed (0-100; default 0)",
+        range(min = 0.0, max = 100.0)
+    )]
+    pub lum_low: Option<f64>,
+    #[schemars(
+        description = "Highest luminance keyed (0-100; default 100)",
+        range(min = 0.0, max = 100.0)
+    )]
+    pub lum_high: Option<f64>,
+    #[schemars(
+        description = "Softness below the lowest luminance (0-50)",
+        range(min = 0.0, max = 50.0)
+    )]
+    pub lum_low_softness: Option<f64>,
+    #[schemars(
+        description = "Softness above the highest luminance (0-50)",
+        range(min = 0.0, max = 50.0)
+    )]
+    pub lum_high_softness: Option<f64>,
+    #[schemars(description = "Matte denoise (0-100)", range(min = 0.0, max = 100.0))]
+    pub denoise: Option<f64>,
+    #[schemars(
+        description = "Matte clean black, removing specks from the unkeyed area (0-100)",
+        range(min = 0.0, max = 100.0)
+    )]
+    pub clean_black: Option<f64>,
+    #[schemars(
+        description = "Matte clean white, filling holes in the keyed area (0-100)",
+        range(min = 0.0, max = 100.0)
+    )]
+    pub clean_white: Option<f64>,
+    #[schemars(
+        description = "Matte blur radius (0-100)",
+        range(min = 0.0, max = 100.0)
+    )]
+    pub blur_radius: Option<f64>,
+    #[schemars(description = "Grade outside the key instead of inside")]
+    pub invert: Option<bool>,
+}
 
-        self.bridge.call_api("add_marker", args).await?;
-        let frame_info = req
-            .frame
-            .map(|f| format!(" at frame {}", f))
-            .unwrap_or_default();
-        Ok(format!(
-            "Successfully added {} marker{} with note: '{}'",
-            req.color, frame_info, req.note
-        ))
-    }
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct GetQualifierRequest {
+    #[schemars(description = "Clip to read (uses the current clip if None)")]
+    pub clip_name: Option<String>,
+    #[schemars(description = "Node to read, starting at 1 (uses the current node if None)")]
+    #[schemars(range(min = 1))]
+    pub node_index: Option<u32>,
 }
 
-#[derive(Debug)]
-pub struct MediaTools {
-    bridge: Arc<ResolveBridge>,
+// ---- NEW: Power Windows ----
+fn window_shape_schema(_: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+    registry::string_enum(&["circle", "linear", "polygon", "gradient"])
 }
 
-impl MediaTools {
-    pub fn new(bridge: Arc<ResolveBridge>) -> Self {
-        Self { bridge }
-    }
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct WindowPoint {
+    #[schemars(
+        description = "Horizontal position as a fraction of the frame width (0-1)",
+        range(min = 0.0, max = 1.0)
+    )]
+    pub x: f64,
+    #[schemars(
+        description = "Vertical position as a fraction of the frame height (0-1)",
+        range(min = 0.0, max = 1.0)
+    )]
+    pub y: f64,
+}
 
-    pub async fn import_media(&self, req: ImportMediaRequest) -> ResolveResult<String> {
-        let args = serde_json::json!({
-            "file_path": req.file_path
-        });
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct AddPowerWindowRequest {
+    #[schemars(description = "Window shape", schema_with = "window_shape_schema")]
+    pub shape: String,
+    #[schemars(description = "Clip to grade (uses the current clip if None)")]
+    pub clip_name: Option<String>,
+    #[schemars(
+        description = "Node to add the window to, starting at 1 (uses the current node if None)"
+    )]
+    #[schemars(range(min = 1))]
+    pub node_index: Option<u32>,
+    #[schemars(
+        description = "Center, or the pivot of a gradient, as a fraction of the frame width (0-1)",
+        range(min = 0.0, max = 1.0)
+    )]
+    pub center_x: Option<f64>,
+    #[schemars(
+        description = "Center, or the pivot of a gradient, as a fraction of the frame height (0-1)",
+        range(min = 0.0, max = 1.0)
+    )]
+    pub center_y: Option<f64>,
+    #[schemars(
+        description = "Width of a circle or linear window as a fraction of the frame width (0-1)",
+        range(min = 0.0, max = 1.0)
+    )]
+    pub width: Option<f64>,
+    #[schemars(
+        description = "Height of a circle or linear window as a fraction of the frame height (0-1)",
+        range(min = 0.0, max = 1.0)
+    )]
+    pub height: Option<f64>,
+    #[schemars(
+        description = "Rotation in degrees; the direction of a gradient (-180 to 180)",
+        range(min = -180.0, max = 180.0)
+    )]
+    pub rotation: Option<f64>,
+    #[schemars(description = "Edge softness (0-100)", range(min = 0.0, max = 100.0))]
+    pub softness: Option<f64>,
+    #[schemars(description = "Grade outside the window instead of inside")]
+    pub invert: Option<bool>,
+    #[schemars(description = "Corners of a polygon window, at least three")]
+    pub points: Option<Vec<WindowPoint>>,
+}
 
-        self.bridge.call_api("import_media", args).await?;
-        Ok(format!("Successfully imported media: {}", req.file_path))
-    }
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ModifyPowerWindowRequest {
+    #[schemars(description = "Clip to grade (uses the current clip if None)")]
+    pub clip_name: Option<String>,
+    #[schemars(
+        description = "Node holding the window, starting at 1 (uses the current node if None)"
+    )]
+    #[schemars(range(min = 1))]
+    pub node_index: Option<u32>,
+    #[schemars(description = "Window to change, numbered from 1 in the order added to the node")]
+    #[schemars(range(min = 1))]
+    pub window_index: u32,
+    #[schemars(
+        description = "Center, or the pivot of a gradient, as a fraction of the frame width (0-1)",
+        range(min = 0.0, max = 1.0)
+    )]
+    pub center_x: Option<f64>,
+    #[schemars(
+        description = "Center, or the pivot of a gradient, as a fraction of the frame height (0-1)",
+        range(min = 0.0, max = 1.0)
+    )]
+    pub center_y: Option<f64>,
+    #[schemars(
+        description = "Width of a circle or linear window as a fraction of the frame width (0-1)",
+        range(min = 0.0, max = 1.0)
+    )]
+    pub width: Option<f64>,
+    #[schemars(
+        description = "Height of a circle or linear window as a fraction of the frame height (0-1)",
+        range(min = 0.0, max = 1.0)
+    )]
+    pub height: Option<f64>,
+    #[schemars(
+        description = "Rotation in degrees; the direction of a gradient (-180 to 180)",
+        range(min = -180.0, max = 180.0)
+    )]
+    pub rotation: Option<f64>,
+    #[schemars(description = "Edge softness (0-100)", range(min = 0.0, max = 100.0))]
+    pub softness: Option<f64>,
+    #[schemars(description = "Grade outside the window instead of inside")]
+    pub invert: Option<bool>,
+    #[schemars(description = "Corners of a polygon window, at least three")]
+    pub points: Option<Vec<WindowPoint>>,
+}
 
-    // ---- Phase 3 Week 1: New Media Operations ----
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct WindowTrackPosition {
+    #[schemars(description = "Frame of the timeline item, counted from its first frame")]
+    #[schemars(range(min = 0))]
+    pub frame: i64,
+    #[schemars(
+        description = "Window center as a fraction of the frame width (0-1)",
+        range(min = 0.0, max = 1.0)
+    )]
+    pub center_x: f64,
+    #[schemars(
+        description = "Window center as a fraction of the frame height (0-1)",
+        range(min = 0.0, max = 1.0)
+    )]
+    pub center_y: f64,
+}
 
-    pub async fn create_bin(&self, req: CreateBinRequest) -> ResolveResult<String> {
-        let args = serde_json::json!({
-            "name": req.name
-        });
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct TrackPowerWindowRequest {
+    #[schemars(description = "Timeline item to track on; its clip's grade holds the window")]
+    pub timeline_item_id: String,
+    #[schemars(
+        description = "Node holding the window, starting at 1 (uses the current node if None)"
+    )]
+    #[schemars(range(min = 1))]
+    pub node_index: Option<u32>,
+    #[schemars(description = "Window to track, numbered from 1 in the order added to the node")]
+    #[schemars(range(min = 1))]
+    pub window_index: u32,
+    #[schemars(description = "Tracked window centers; tracking again replaces the earlier track")]
+    pub positions: Vec<WindowTrackPosition>,
+}
 
-        self.bridge.call_api("create_bin", args).await?;
-        Ok(format!("Successfully created bin '{}'", req.name))
-    }
+// ---- NEW: Stereo 3D ----
+fn stereo_eye_schema(_: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+    registry::string_enum(&["both", "left", "right"])
+}
 
-    pub async fn auto_sync_audio(&self, req: AutoSyncAudioRequest) -> ResolveResult<String> {
-        let args = serde_json::json!({
-            "clip_names": req.clip_names,
-            "sync_method": req.sync_method,
-            "append_mode": req.append_mode,
-            "target_bin": req.target_bin
-        });
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct FloatingWindow {
+    #[schemars(description = "Left edge as a fraction of the frame width")]
+    #[schemars(range(min = 0.0, max = 0.5))]
+    pub left: Option<f64>,
+    #[schemars(description = "Right edge as a fraction of the frame width")]
+    #[schemars(range(min = 0.0, max = 0.5))]
+    pub right: Option<f64>,
+    #[schemars(description = "Top edge as a fraction of the frame height")]
+    #[schemars(range(min = 0.0, max = 0.5))]
+    pub top: Option<f64>,
+    #[schemars(description = "Bottom edge as a fraction of the frame height")]
+    #[schemars(range(min = 0.0, max = 0.5))]
+    pub bottom: Option<f64>,
+}
 
-        self.bridge.call_api("auto_sync_audio", args).await?;
-        Ok(format!(
-            "Successfully synchronized {} clips using {} method",
-            req.clip_names.len(),
-            req.sync_method
-        ))
-    }
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SetStereoParamsRequest {
+    #[schemars(description = "Timeline item to set the parameters of")]
+    pub timeline_item_id: String,
+    #[schemars(
+        description = "Frame of the item to keyframe convergence and windows at, counted from its first frame (default: 0)"
+    )]
+    #[schemars(range(min = 0))]
+    pub frame: Option<i64>,
+    #[schemars(description = "Horizontal offset between the eyes in pixels")]
+    #[schemars(range(min = -100.0, max = 100.0))]
+    pub convergence: Option<f64>,
+    #[schemars(description = "Left eye floating window; edges not given keep their value")]
+    pub left_window: Option<FloatingWindow>,
+    #[schemars(description = "Right eye floating window; edges not given keep their value")]
+    pub right_window: Option<FloatingWindow>,
+    #[serde(default)]
+    #[schemars(
+        description = "Eye the item is seen with",
+        schema_with = "stereo_eye_schema"
+    )]
+    pub eye: Option<String>,
+    #[schemars(description = "Swap the left and right eyes")]
+    pub swap_eyes: Option<bool>,
+}
 
-    pub async fn unlink_clips(&self, req: UnlinkClipsRequest) -> ResolveResult<String> {
-        let args = serde_json::json!({
-            "clip_names": req.clip_names
-        });
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct GetStereoParamsRequest {
+    #[schemars(description = "Timeline item to read the parameters of")]
+    pub timeline_item_id: String,
+    #[schemars(description = "Also report the values in effect at this frame of the item")]
+    #[schemars(range(min = 0))]
+    pub frame: Option<i64>,
+}
 
-        self.bridge.call_api("unlink_clips", args).await?;
-        Ok(format!(
-            "Successfully unlinked {} clips",
-            req.clip_names.len()
-        ))
-    }
+// ---- NEW: State Integrity ----
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct VerifyStateIntegrityRequest {
+    #[schemars(
+        description = "Repair what can be repaired: remove orphans, relink items to renamed clips and clear a missing current timeline or clip (default: false)"
+    )]
+    pub repair: Option<bool>,
+    #[schemars(description = "Check this backup instead of the current state, without repairing")]
+    pub backup_id: Option<String>,
+}
 
-    pub async fn relink_clips(&self, req: RelinkClipsRequest) -> ResolveResult<String> {
-        let args = serde_json::json!({
-            "clip_names": req.clip_names,
-            "media_paths": req.media_paths,
-            "folder_path": req.folder_path,
-            "recursive": req.recursive
-        });
+// ---- NEW: Grade Trace ----
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct TraceGradesRequest {
+    #[schemars(
+        description = "New cut to bring the grades to, by name or ID (defaults to the current timeline)"
+    )]
+    pub timeline_name: Option<String>,
+    #[schemars(
+        description = "Old version holding the grades, by name or ID (defaults to the timeline the new cut was versioned from)"
+    )]
+    pub source_timeline_name: Option<String>,
+    #[schemars(description = "Report the matches without copying any grade (default: false)")]
+    pub dry_run: Option<bool>,
+}
 
-        self.bridge.call_api("relink_clips", args).await?;
-        Ok(format!(
-            "Successfully relinked {} clips",
-            req.clip_names.len()
-        ))
-    }
+// ---- NEW: Auto Reframe ----
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SubjectPosition {
+    #[schemars(description = "Frame of the timeline item, counted from its first frame")]
+    #[schemars(range(min = 0))]
+    pub frame: i64,
+    #[schemars(
+        description = "Horizontal center of the subject as a fraction of the frame width (0-1)",
+        range(min = 0.0, max = 1.0)
+    )]
+    pub x: f64,
+    #[schemars(
+        description = "Detector confidence (0-1, default 1)",
+        range(min = 0.0, max = 1.0)
+    )]
+    pub confidence: Option<f64>,
+}
 
-    pub async fn create_sub_clip(&self, req: CreateSubClipRequest) -> ResolveResult<String> {
-        let args = serde_json::json!({
-            "clip_name": req.clip_name,
-            "start_frame": req.start_frame,
-            "end_frame": req.end_frame,
-            "sub_clip_name": req.sub_clip_name,
-            "bin_name": req.bin_name
-        });
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ReframeSubject {
+    #[schemars(description = "Timeline item on the source timeline")]
+    pub timeline_item_id: String,
+    #[schemars(description = "Subject positions found by the detector")]
+    pub positions: Vec<SubjectPosition>,
+}
 
-        self.bridge.call_api("create_sub_clip", args).await?;
-        let sub_name = req
-            .sub_clip_name
-            .unwrap_or_else(|| format!("{}_subclip", req.clip_name));
-        Ok(format!(
-            "Successfully created subclip '{}' from '{}' (frames {}-{})",
-            sub_name, req.clip_name, req.start_frame, req.end_frame
-        ))
-    }
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct AutoReframeTimelineRequest {
+    #[schemars(
+        description = "Timeline to reframe, by name or ID (defaults to the current timeline)"
+    )]
+    pub timeline_name: Option<String>,
+    #[schemars(description = "Name of the vertical copy (default: '<timeline> 9x16')")]
+    pub new_timeline_name: Option<String>,
+    #[schemars(
+        description = "Subject positions per timeline item from an external detector; items left out are held at the center"
+    )]
+    pub subjects: Option<Vec<ReframeSubject>>,
+    #[schemars(
+        description = "Confidence below which an item is listed as needing review (default: 0.5)"
+    )]
+    pub min_confidence: Option<f64>,
+}
 
-    pub async fn link_proxy_media(&self, req: LinkProxyMediaRequest) -> ResolveResult<String> {
-        let args = serde_json::json!({
-            "clip_name": req.clip_name,
-            "proxy_file_path": req.proxy_file_path
-        });
+// ---- NEW: Output Framing ----
+fn blanking_aspect_schema(_: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+    registry::string_enum(&["2.39:1", "1.85:1", "custom", "none"])
+}
 
-        self.bridge.call_api("link_proxy_media", args).await?;
-        Ok(format!(
-            "Successfully linked proxy media for clip '{}'",
-            req.clip_name
-        ))
-    }
+fn reframe_preset_schema(_: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+    registry::string_enum(&["vertical", "square", "none"])
+}
 
-    pub async fn unlink_proxy_media(&self, req: UnlinkProxyMediaRequest) -> ResolveResult<String> {
-        let args = serde_json::json!({
-            "clip_name": req.clip_name
-        });
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SetOutputBlankingRequest {
+    #[schemars(
+        description = "Aspect ratio left visible: '2.39:1', '1.85:1', 'custom' (with custom_ratio) or 'none' to remove blanking",
+        schema_with = "blanking_aspect_schema"
+    )]
+    pub aspect: String,
+    #[schemars(description = "Width over height for 'custom', such as 2.0 (0.25 to 4)")]
+    pub custom_ratio: Option<f64>,
+    #[schemars(
+        description = "Timeline to blank, by name or ID (defaults to the current timeline)"
+    )]
+    pub timeline_name: Option<String>,
+}
 
-        self.bridge.call_api("unlink_proxy_media", args).await?;
-        Ok(format!(
-            "Successfully unlinked proxy media for clip '{}'",
-            req.clip_name
-        ))
-    }
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SetOutputReframeRequest {
+    #[schemars(
+        description = "Output frame: 'vertical' (9:16), 'square' (1:1) or 'none' for the timeline's own frame",
+        schema_with = "reframe_preset_schema"
+    )]
+    pub preset: String,
+    #[schemars(
+        description = "Timeline to reframe, by name or ID (defaults to the current timeline)"
+    )]
+    pub timeline_name: Option<String>,
+}
 
-    pub async fn replace_clip(&self, req: ReplaceClipRequest) -> ResolveResult<String> {
-        let args = serde_json::json!({
-            "clip_name": req.clip_name,
-            "replacement_path": req.replacement_path
-        });
+// ---- NEW: LUT Library ----
+fn lut_format_schema(_: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+    registry::string_enum(&["Cube", "3dl", "Panasonic", "Davinci"])
+}
 
-        self.bridge.call_api("replace_clip", args).await?;
-        Ok(format!(
-            "Successfully replaced clip '{}' with '{}'",
-            req.clip_name, req.replacement_path
-        ))
-    }
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ListLutsRequest {
+    #[serde(default)]
+    #[schemars(
+        description = "Only LUTs of this format: 'Cube', '3dl', 'Panasonic' or 'Davinci'",
+        schema_with = "lut_format_schema"
+    )]
+    pub format: Option<String>,
+    #[schemars(description = "Only LUTs whose path or title contains this text")]
+    pub search: Option<String>,
 }
 
-// ============================================
-// TOOL ROUTING FUNCTION
-// ============================================
+// ---- NEW: DRX Grades ----
+fn drx_grade_mode_schema(_: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+    registry::string_enum(&["no_keyframes", "source_timecode", "start_frames"])
+}
 
-pub async fn handle_tool_call(
-    tool_name: &str,
-    args: serde_json::Value,
-    bridge: Arc<ResolveBridge>,
-) -> ResolveResult<String> {
-    let project_tools = ProjectTools::new(bridge.clone());
-    let timeline_tools = TimelineTools::new(bridge.clone());
-    let media_tools = MediaTools::new(bridge.clone());
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ApplyDrxFileRequest {
+    #[schemars(description = "Path of the .drx grade file, as exported from the gallery")]
+    pub drx_path: String,
+    #[schemars(description = "Timeline items to grade (default: every item on the timeline)")]
+    pub timeline_item_ids: Option<Vec<String>>,
+    #[schemars(
+        description = "Timeline the items are on, by name or ID (defaults to the current timeline)"
+    )]
+    pub timeline_name: Option<String>,
+    #[serde(default)]
+    #[schemars(
+        description = "How the file's keyframes line up: 'no_keyframes' (default) drops them, 'source_timecode' aligns them to source timecode, 'start_frames' to each item's first frame",
+        schema_with = "drx_grade_mode_schema"
+    )]
+    pub grade_mode: Option<String>,
+}
+
+// ---- NEW: Preview ----
+fn preview_format_schema(_: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+    registry::string_enum(&["gif", "mp4"])
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct GeneratePreviewRequest {
+    #[schemars(
+        description = "Timeline to preview, by name or ID (defaults to the current timeline unless clip_name is given)"
+    )]
+    pub timeline_name: Option<String>,
+    #[schemars(description = "Media pool clip to preview instead of a timeline")]
+    pub clip_name: Option<String>,
+    #[schemars(
+        description = "First frame of the range: a timeline frame for timelines, a source frame for clips (default 0)"
+    )]
+    pub start_frame: Option<i64>,
+    #[schemars(
+        description = "Frame the range ends before (default: the end of the timeline or clip). Previews cover at most 120 seconds"
+    )]
+    pub end_frame: Option<i64>,
+    #[serde(default)]
+    #[schemars(
+        description = "'gif' (default) for a 320 pixel wide GIF at 10 fps, or 'mp4' for a 480p MP4",
+        schema_with = "preview_format_schema"
+    )]
+    pub format: Option<String>,
+    #[schemars(
+        description = "File to write (default: a new file in the cache directory's previews folder)"
+    )]
+    pub output_path: Option<String>,
+}
+
+// ---- NEW: Bin Color Rules ----
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct BinColorRule {
+    #[schemars(
+        description = "Glob over the clip's file name, such as '*.ari' or 'A0*', matched without regard to case (default: every file)"
+    )]
+    pub file_pattern: Option<String>,
+    #[schemars(description = "Input color space, such as 'ARRI LogC3' or 'Rec.709'")]
+    pub input_color_space: Option<String>,
+    #[schemars(description = "Input LUT, as a LUT file path or the name of a known LUT")]
+    pub input_lut: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SetBinColorRulesRequest {
+    #[schemars(description = "Bin whose imported clips get the color settings")]
+    pub bin_name: String,
+    #[schemars(
+        description = "Rules tried in order; the first matching a clip's file name sets its input color space and LUT. An empty list clears the bin's rules"
+    )]
+    pub rules: Vec<BinColorRule>,
+    #[schemars(description = "Also apply the rules to clips already in the bin (default false)")]
+    pub apply_to_existing: Option<bool>,
+}
+
+// ---- NEW: Frame Rate Advisor ----
+fn frame_rate_strategy_schema(_: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+    registry::string_enum(&["auto", "conform", "retime"])
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct AnalyzeFrameRateMismatchesRequest {
+    #[schemars(
+        description = "Timeline to check, by name or ID (defaults to the current timeline)"
+    )]
+    pub timeline_name: Option<String>,
+    #[serde(default)]
+    #[schemars(
+        description = "How mismatched clips should play: 'auto' (default) picks per clip, 'conform' plays every frame at the timeline rate, 'retime' keeps real time speed",
+        schema_with = "frame_rate_strategy_schema"
+    )]
+    pub strategy: Option<String>,
+    #[schemars(
+        description = "Read the frame rate of clips without an FPS property from their media with ffprobe (default true)"
+    )]
+    pub probe: Option<bool>,
+    #[schemars(
+        description = "Apply the suggestions: set the FPS of conformed clips and the retime process of retimed items (default false)"
+    )]
+    pub apply: Option<bool>,
+}
+
+// ---- NEW: Timeline Versions ----
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct CreateTimelineVersionRequest {
+    #[schemars(
+        description = "Timeline to version, by name or ID (defaults to the current timeline)"
+    )]
+    pub timeline_name: Option<String>,
+    #[schemars(description = "Who made the version (defaults to the connected user)")]
+    pub author: Option<String>,
+    #[schemars(description = "What changed in this version")]
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ListTimelineVersionsRequest {
+    #[schemars(
+        description = "Any version of the timeline, by name or ID (defaults to the current timeline)"
+    )]
+    pub timeline_name: Option<String>,
+}
+
+// ---- NEW: Archive Manifest ----
+fn archive_manifest_format_schema(
+    _: &mut schemars::gen::SchemaGenerator,
+) -> schemars::schema::Schema {
+    registry::string_enum(&["json", "csv"])
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct GenerateArchiveManifestRequest {
+    #[serde(default)]
+    #[schemars(
+        description = "Manifest format ('json', default, or 'csv')",
+        schema_with = "archive_manifest_format_schema"
+    )]
+    pub format: Option<String>,
+    #[schemars(
+        description = "Where the archive is mounted when restoring, such as the LTFS tape (default /archive/<project>)"
+    )]
+    pub archive_root: Option<String>,
+    #[schemars(description = "Also archive proxy media linked to clips (default true)")]
+    pub include_proxies: Option<bool>,
+    #[schemars(
+        description = "File to write the manifest to; the restore script is written beside it as <name>.restore.sh"
+    )]
+    pub output_path: Option<String>,
+}
+
+// ---- NEW: Review Comment Import ----
+fn review_adapter_schema(_: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+    registry::string_enum(&["frameio", "vimeo"])
+}
+
+fn review_import_target_schema(_: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+    registry::string_enum(&["markers", "notes"])
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ImportReviewCommentsRequest {
+    #[schemars(
+        description = "Platform the export comes from: 'frameio' (comment CSV) or 'vimeo' (review JSON)",
+        schema_with = "review_adapter_schema"
+    )]
+    pub adapter: String,
+    #[schemars(description = "Contents of the exported comment file")]
+    pub data: String,
+    #[serde(default)]
+    #[schemars(
+        description = "Add comments as timeline 'markers' or review 'notes' (default from the adapter's config)",
+        schema_with = "review_import_target_schema"
+    )]
+    pub target: Option<String>,
+    #[schemars(description = "Timeline to add comments to (uses current if None)")]
+    pub timeline_name: Option<String>,
+    #[schemars(
+        description = "Record timecode of the timeline's first frame (default 01:00:00:00)"
+    )]
+    pub timeline_start_timecode: Option<String>,
+    #[schemars(
+        description = "Timecode the platform shows on the review file's first frame (default from the adapter's config)"
+    )]
+    pub file_start_timecode: Option<String>,
+    #[schemars(
+        description = "Frames added to each comment to place it on the timeline; negative when the review file opens with a slate (default from the adapter's config)"
+    )]
+    pub offset_frames: Option<i64>,
+    #[schemars(
+        description = "Also import comments marked done on the platform (default from the adapter's config)"
+    )]
+    pub include_completed: Option<bool>,
+}
+
+// ---- NEW: Privacy Blur ----
+fn mask_shape_schema(_: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+    registry::string_enum(&["ellipse", "rectangle"])
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct PrivacyBox {
+    #[schemars(description = "Frame of the timeline item, counted from its first frame")]
+    #[schemars(range(min = 0))]
+    pub frame: i64,
+    #[schemars(
+        description = "Left edge as a fraction of the frame width (0-1)",
+        range(min = 0.0, max = 1.0)
+    )]
+    pub x: f64,
+    #[schemars(
+        description = "Top edge as a fraction of the frame height (0-1)",
+        range(min = 0.0, max = 1.0)
+    )]
+    pub y: f64,
+    #[schemars(
+        description = "Width as a fraction of the frame width (0-1)",
+        range(min = 0.0, max = 1.0)
+    )]
+    pub width: f64,
+    #[schemars(
+        description = "Height as a fraction of the frame height (0-1)",
+        range(min = 0.0, max = 1.0)
+    )]
+    pub height: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct PrivacyRegion {
+    #[schemars(description = "Timeline item the region appears on")]
+    pub timeline_item_id: String,
+    #[schemars(
+        description = "Name for the blur node, such as the detector's track ID (default 'Region N')"
+    )]
+    pub label: Option<String>,
+    #[serde(default)]
+    #[schemars(
+        description = "Window shape ('ellipse', default, or 'rectangle')",
+        schema_with = "mask_shape_schema"
+    )]
+    pub shape: Option<String>,
+    #[schemars(
+        description = "Where the region is, one box per tracked frame; the window moves linearly between them"
+    )]
+    pub boxes: Vec<PrivacyBox>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ApplyPrivacyBlurRequest {
+    #[schemars(description = "Regions to blur, as a face or object detector reports them")]
+    pub regions: Vec<PrivacyRegion>,
+    #[schemars(
+        description = "Blur strength (0-100, default 60)",
+        range(min = 0.0, max = 100.0)
+    )]
+    pub blur_strength: Option<f64>,
+    #[schemars(
+        description = "Window edge softness (0-100, default 15)",
+        range(min = 0.0, max = 100.0)
+    )]
+    pub softness: Option<f64>,
+}
+
+// ---- NEW: Motion Effects ----
+fn temporal_estimation_schema(_: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+    registry::string_enum(&["none", "faster", "better"])
+}
+
+fn blur_estimation_schema(_: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+    registry::string_enum(&["faster", "better"])
+}
+
+fn motion_range_schema(_: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+    registry::string_enum(&["small", "medium", "large"])
+}
+
+fn spatial_mode_schema(_: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+    registry::string_enum(&["faster", "better", "enhanced"])
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SetNoiseReductionRequest {
+    #[schemars(description = "Clip to grade (uses the current clip if None)")]
+    pub clip_name: Option<String>,
+    #[schemars(description = "Node to set, starting at 1 (uses the current node if None)")]
+    #[schemars(range(min = 1))]
+    pub node_index: Option<u32>,
+    #[schemars(
+        description = "Temporal NR frames either side of the current one (0-5; 0 turns temporal NR off)"
+    )]
+    #[schemars(range(min = 0, max = 5))]
+    pub temporal_frames: Option<u32>,
+    #[serde(default)]
+    #[schemars(
+        description = "Temporal NR motion estimation type",
+        schema_with = "temporal_estimation_schema"
+    )]
+    pub temporal_motion_estimation: Option<String>,
+    #[serde(default)]
+    #[schemars(
+        description = "Temporal NR motion range",
+        schema_with = "motion_range_schema"
+    )]
+    pub temporal_motion_range: Option<String>,
+    #[schemars(
+        description = "Temporal luma threshold (0-100)",
+        range(min = 0.0, max = 100.0)
+    )]
+    pub temporal_luma_threshold: Option<f64>,
+    #[schemars(
+        description = "Temporal chroma threshold (0-100)",
+        range(min = 0.0, max = 100.0)
+    )]
+    pub temporal_chroma_threshold: Option<f64>,
+    #[schemars(
+        description = "Temporal motion threshold (0-100)",
+        range(min = 0.0, max = 100.0)
+    )]
+    pub temporal_motion_threshold: Option<f64>,
+    #[schemars(
+        description = "Temporal NR blend (0-100)",
+        range(min = 0.0, max = 100.0)
+    )]
+    pub temporal_blend: Option<f64>,
+    #[serde(default)]
+    #[schemars(description = "Spatial NR mode", schema_with = "spatial_mode_schema")]
+    pub spatial_mode: Option<String>,
+    #[serde(default)]
+    #[schemars(description = "Spatial NR radius", schema_with = "motion_range_schema")]
+    pub spatial_radius: Option<String>,
+    #[schemars(
+        description = "Spatial luma threshold (0-100)",
+        range(min = 0.0, max = 100.0)
+    )]
+    pub spatial_luma_threshold: Option<f64>,
+    #[schemars(
+        description = "Spatial chroma threshold (0-100)",
+        range(min = 0.0, max = 100.0)
+    )]
+    pub spatial_chroma_threshold: Option<f64>,
+    #[schemars(
+        description = "Spatial NR blend (0-100)",
+        range(min = 0.0, max = 100.0)
+    )]
+    pub spatial_blend: Option<f64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SetMotionBlurRequest {
+    #[schemars(description = "Clip to grade (uses the current clip if None)")]
+    pub clip_name: Option<String>,
+    #[schemars(description = "Node to set, starting at 1 (uses the current node if None)")]
+    #[schemars(range(min = 1))]
+    pub node_index: Option<u32>,
+    #[serde(default)]
+    #[schemars(
+        description = "Motion estimation type",
+        schema_with = "blur_estimation_schema"
+    )]
+    pub motion_estimation: Option<String>,
+    #[serde(default)]
+    #[schemars(description = "Motion range", schema_with = "motion_range_schema")]
+    pub motion_range: Option<String>,
+    #[schemars(
+        description = "Motion blur amount (0-100; 0 turns it off)",
+        range(min = 0.0, max = 100.0)
+    )]
+    pub amount: Option<f64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct GetMotionEffectsRequest {
+    #[schemars(description = "Clip to read (uses the current clip if None)")]
+    pub clip_name: Option<String>,
+    #[schemars(description = "Node to read, starting at 1 (uses the current node if None)")]
+    #[schemars(range(min = 1))]
+    pub node_index: Option<u32>,
+}
+
+// ---- NEW: Render Cache ----
+fn render_cache_flag_schema(_: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+    registry::string_enum(&["on", "off", "auto"])
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SetItemRenderCacheRequest {
+    #[schemars(description = "Timeline item to set the flags of")]
+    pub timeline_item_id: String,
+    #[serde(default)]
+    #[schemars(
+        description = "Color output cache: 'on' to force caching, 'off' to prevent it, 'auto' to follow the project's render cache mode",
+        schema_with = "render_cache_flag_schema"
+    )]
+    pub color_output: Option<String>,
+    #[serde(default)]
+    #[schemars(
+        description = "Fusion output cache: 'on', 'off' or 'auto'",
+        schema_with = "render_cache_flag_schema"
+    )]
+    pub fusion_output: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct GetRenderCacheStatusRequest {
+    #[schemars(description = "Timeline to report on (uses current if None)")]
+    pub timeline_name: Option<String>,
+}
+
+// ---- NEW: Batch Render ----
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct QueueRendersForTimelinesRequest {
+    #[schemars(description = "Timelines to render, by name or ID, in queue order")]
+    pub timeline_names: Option<Vec<String>>,
+    #[schemars(
+        description = "Glob over timeline names instead of a list ('*' any run of characters, '?' one character), e.g. 'EP1*_v2'"
+    )]
+    pub filter: Option<String>,
+    #[schemars(description = "Render preset every job uses")]
+    pub preset_name: String,
+    #[schemars(
+        description = "Output folder template with {project}, {timeline}, {preset} and {index} placeholders (default '/tmp/renders/{project}/{timeline}'); each file is named after its timeline"
+    )]
+    pub output_directory: Option<String>,
+    #[schemars(description = "Whether to render only each timeline's in/out range")]
+    #[serde(default)]
+    pub use_in_out_range: bool,
+    #[schemars(description = "Render the video track (uses the preset's setting if None)")]
+    pub export_video: Option<bool>,
+    #[schemars(description = "Render the audio track (uses the preset's setting if None)")]
+    pub export_audio: Option<bool>,
+    #[schemars(
+        description = "Audio channel layout ('Mono', 'Stereo', '5.1', '7.1'; uses the preset's if None)"
+    )]
+    pub audio_channel_layout: Option<String>,
+}
+
+// ---- NEW: Conform ----
+fn conform_match_schema(_: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+    registry::string_enum(&["reel_and_timecode", "timecode"])
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ConformTimelineRequest {
+    #[schemars(description = "Timeline to conform (uses current if None)")]
+    pub timeline_name: Option<String>,
+    #[schemars(
+        description = "Media pool clips to conform to (default: the clips in bin_name, or every clip not on the timeline)"
+    )]
+    pub clip_names: Option<Vec<String>>,
+    #[schemars(description = "Bin holding the camera originals")]
+    pub bin_name: Option<String>,
+    #[serde(default)]
+    #[schemars(
+        description = "Match on reel name and timecode ('reel_and_timecode', default) or timecode alone ('timecode')",
+        schema_with = "conform_match_schema"
+    )]
+    pub match_by: Option<String>,
+    #[schemars(description = "Report the matches without changing the timeline")]
+    pub dry_run: Option<bool>,
+}
+
+// ---- NEW: Subtitles ----
+fn subtitle_position_schema(_: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+    registry::string_enum(&["bottom", "center", "top"])
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct AddSubtitleTrackRequest {
+    #[schemars(description = "Track name (default 'Subtitle N')")]
+    pub name: Option<String>,
+    #[schemars(description = "Subtitle language as a BCP-47 tag or language name")]
+    pub language: Option<String>,
+    #[schemars(description = "Font family")]
+    pub font: Option<String>,
+    #[schemars(description = "Font size in points (8-200)", range(min = 8, max = 200))]
+    pub size: Option<u32>,
+    #[serde(default)]
+    #[schemars(
+        description = "Position on screen ('bottom', 'center' or 'top')",
+        schema_with = "subtitle_position_schema"
+    )]
+    pub position: Option<String>,
+    #[schemars(description = "Text color as a hex value such as '#FFFFFF'")]
+    pub color: Option<String>,
+    #[schemars(description = "Timeline to use (uses current if None)")]
+    pub timeline_name: Option<String>,
+    #[schemars(
+        description = "Record timecode of the timeline's first frame (default 01:00:00:00)"
+    )]
+    pub timeline_start_timecode: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct AddSubtitleCueRequest {
+    #[schemars(description = "Cue text")]
+    pub text: String,
+    #[schemars(description = "Record timecode where the cue appears")]
+    pub in_timecode: String,
+    #[schemars(description = "Record timecode where the cue disappears")]
+    pub out_timecode: String,
+    #[schemars(
+        description = "Subtitle track, starting at 1 (default 1)",
+        range(min = 1)
+    )]
+    pub track_index: Option<u32>,
+    #[schemars(description = "Font family")]
+    pub font: Option<String>,
+    #[schemars(description = "Font size in points (8-200)", range(min = 8, max = 200))]
+    pub size: Option<u32>,
+    #[serde(default)]
+    #[schemars(
+        description = "Position on screen ('bottom', 'center' or 'top')",
+        schema_with = "subtitle_position_schema"
+    )]
+    pub position: Option<String>,
+    #[schemars(description = "Text color as a hex value such as '#FFFFFF'")]
+    pub color: Option<String>,
+    #[schemars(description = "Timeline to use (uses current if None)")]
+    pub timeline_name: Option<String>,
+    #[schemars(
+        description = "Record timecode of the timeline's first frame (default 01:00:00:00)"
+    )]
+    pub timeline_start_timecode: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct UpdateSubtitleCueRequest {
+    #[schemars(description = "ID of the cue to change")]
+    pub cue_id: String,
+    #[schemars(description = "New cue text")]
+    pub text: Option<String>,
+    #[schemars(description = "New record timecode where the cue appears")]
+    pub in_timecode: Option<String>,
+    #[schemars(description = "New record timecode where the cue disappears")]
+    pub out_timecode: Option<String>,
+    #[schemars(description = "Font family")]
+    pub font: Option<String>,
+    #[schemars(description = "Font size in points (8-200)", range(min = 8, max = 200))]
+    pub size: Option<u32>,
+    #[serde(default)]
+    #[schemars(
+        description = "Position on screen ('bottom', 'center' or 'top')",
+        schema_with = "subtitle_position_schema"
+    )]
+    pub position: Option<String>,
+    #[schemars(description = "Text color as a hex value such as '#FFFFFF'")]
+    pub color: Option<String>,
+    #[schemars(description = "Timeline to use (uses current if None)")]
+    pub timeline_name: Option<String>,
+    #[schemars(
+        description = "Record timecode of the timeline's first frame (default 01:00:00:00)"
+    )]
+    pub timeline_start_timecode: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct DeleteSubtitleCueRequest {
+    #[schemars(description = "ID of the cue to delete")]
+    pub cue_id: String,
+    #[schemars(description = "Timeline to use (uses current if None)")]
+    pub timeline_name: Option<String>,
+    #[schemars(
+        description = "Record timecode of the timeline's first frame (default 01:00:00:00)"
+    )]
+    pub timeline_start_timecode: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ListSubtitleCuesRequest {
+    #[schemars(
+        description = "Only list this subtitle track, starting at 1",
+        range(min = 1)
+    )]
+    pub track_index: Option<u32>,
+    #[schemars(description = "Timeline to use (uses current if None)")]
+    pub timeline_name: Option<String>,
+    #[schemars(
+        description = "Record timecode of the timeline's first frame (default 01:00:00:00)"
+    )]
+    pub timeline_start_timecode: Option<String>,
+}
+
+// ---- NEW: Transcription Languages ----
+fn transcription_backend_schema(
+    _: &mut schemars::gen::SchemaGenerator,
+) -> schemars::schema::Schema {
+    registry::string_enum(&["resolve", "simulation"])
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ListSupportedLanguagesRequest {
+    #[serde(default)]
+    #[schemars(
+        description = "Transcription backend to list ('resolve' or 'simulation'; uses the current one if None)",
+        schema_with = "transcription_backend_schema"
+    )]
+    pub backend: Option<String>,
+}
+
+// ---- NEW: Identity ----
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct IdentifyRequest {
+    #[schemars(description = "Token of a user in the server's [users] config")]
+    pub token: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct GetAuditLogRequest {
+    #[schemars(description = "Only entries made by this user")]
+    pub user: Option<String>,
+    #[schemars(description = "Only entries for this tool")]
+    pub method: Option<String>,
+    #[schemars(description = "Maximum number of most recent entries (100 if None)")]
+    pub limit: Option<u32>,
+}
+
+// ---- NEW: Render Formats ----
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ListRenderFormatsRequest {
+    #[schemars(
+        description = "Only list this format, by name or Resolve format key (lists all if None)"
+    )]
+    pub format: Option<String>,
+    #[schemars(
+        description = "Only list formats that support this codec (lists all codecs if None)"
+    )]
+    pub codec: Option<String>,
+    #[schemars(description = "Only list codecs that can carry an alpha channel")]
+    #[serde(default)]
+    pub alpha: bool,
+}
+
+// ---- NEW: Clip Grades ----
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct GetColorWheelParamRequest {
+    #[schemars(description = "Which color wheel to read ('lift', 'gamma', 'gain', 'offset')")]
+    pub wheel: String,
+    #[schemars(
+        description = "Which parameter to read ('red', 'green', 'blue', 'master'; reads the whole wheel if None)"
+    )]
+    pub param: Option<String>,
+    #[schemars(description = "Index of the node to read from (uses current node if None)")]
+    pub node_index: Option<i32>,
+    #[schemars(description = "Clip to read (uses current clip if None)")]
+    pub clip_name: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct AdjustColorWheelParamRequest {
+    #[schemars(description = "Which color wheel to adjust ('lift', 'gamma', 'gain', 'offset')")]
+    pub wheel: String,
+    #[schemars(description = "Which parameter to adjust ('red', 'green', 'blue', 'master')")]
+    pub param: String,
+    #[schemars(description = "Amount to add to the current value; negative values subtract")]
+    pub delta: f64,
+    #[schemars(description = "Index of the node to adjust (uses current node if None)")]
+    pub node_index: Option<i32>,
+    #[schemars(description = "Clip to grade (uses current clip if None)")]
+    pub clip_name: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct GetClipGradeRequest {
+    #[schemars(description = "Clip to read (uses current clip if None)")]
+    pub clip_name: Option<String>,
+}
+
+// ---- NEW: Notes ----
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SetNotesRequest {
+    #[schemars(description = "Notes text; an empty string clears the notes")]
+    pub notes: String,
+    #[schemars(description = "Project to annotate (uses current if nothing is named)")]
+    pub project_name: Option<String>,
+    #[schemars(description = "Timeline to annotate")]
+    pub timeline_name: Option<String>,
+    #[schemars(description = "Media pool clip to annotate, stored as its Comments metadata")]
+    pub clip_name: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct GetNotesRequest {
+    #[schemars(description = "Project to read notes from (uses current if nothing is named)")]
+    pub project_name: Option<String>,
+    #[schemars(description = "Timeline to read notes from")]
+    pub timeline_name: Option<String>,
+    #[schemars(description = "Media pool clip to read notes from")]
+    pub clip_name: Option<String>,
+    #[schemars(description = "List every note in the project when nothing is named")]
+    pub all: Option<bool>,
+}
+
+// ---- NEW: Actions ----
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct RunActionRequest {
+    #[schemars(
+        description = "Name of the configured action (lists the available actions if None)"
+    )]
+    pub action: Option<String>,
+    #[schemars(description = "Values for the action's parameters")]
+    pub parameters: Option<serde_json::Map<String, serde_json::Value>>,
+}
+
+// ---- NEW: Session Bootstrap ----
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct BootstrapSessionRequest {
+    #[schemars(
+        description = "Name of the configured bootstrap profile (applies the startup profile if None)"
+    )]
+    pub profile: Option<String>,
+}
+
+// ---- NEW: Frame Description ----
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct DescribeFrameRequest {
+    #[schemars(description = "Media pool clip to describe")]
+    pub clip_name: String,
+    #[schemars(
+        description = "Frame of the clip's media to describe, from its first frame (default 0)"
+    )]
+    pub frame: Option<i64>,
+    #[schemars(
+        description = "PNG, JPEG, WebP or GIF image to describe instead of a frame cut from the clip's media"
+    )]
+    pub image_path: Option<String>,
+    #[schemars(description = "What to pay attention to, added to the question asked")]
+    pub prompt: Option<String>,
+    #[schemars(
+        description = "Store the description and keywords as the clip's Description and Keywords metadata (default true)"
+    )]
+    pub write_metadata: Option<bool>,
+}
+
+// ---- NEW: Timeline Summary ----
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct GetTimelineSummaryRequest {
+    #[schemars(description = "Timeline ID or name (uses current if None)")]
+    pub timeline_name: Option<String>,
+    #[schemars(
+        description = "Record timecode of the timeline's first frame (default 01:00:00:00)"
+    )]
+    pub timeline_start_timecode: Option<String>,
+}
+
+// ---- NEW: Bars and Tone ----
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct InsertBarsAndToneRequest {
+    #[schemars(description = "Timeline ID or name (uses current if None)")]
+    pub timeline_name: Option<String>,
+    #[schemars(description = "Length of the bars and tone, in seconds (default 60)")]
+    pub duration_seconds: Option<f64>,
+    #[schemars(description = "Colour bars generator (default SMPTE Color Bar)")]
+    pub pattern: Option<String>,
+    #[schemars(description = "Tone frequency in Hz (default 1000)")]
+    pub tone_frequency_hz: Option<f64>,
+    #[schemars(description = "Tone level in dBFS (default -20)")]
+    pub tone_level_dbfs: Option<f64>,
+    #[schemars(description = "Video track for the bars (default 1)")]
+    pub track_index: Option<u32>,
+    #[schemars(description = "Audio track for the tone (default 1)")]
+    pub audio_track_index: Option<u32>,
+    #[schemars(description = "Number of tone channels (default 2)")]
+    pub channels: Option<u32>,
+    #[schemars(
+        description = "Record timecode of the timeline's first frame (default 01:00:00:00)"
+    )]
+    pub timeline_start_timecode: Option<String>,
+}
+
+// ---- NEW: Slates ----
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct AddSlateRequest {
+    #[schemars(description = "Timeline ID or name (uses current if None)")]
+    pub timeline_name: Option<String>,
+    #[schemars(description = "Slate field values, overriding or adding to the template")]
+    pub fields: Option<serde_json::Map<String, serde_json::Value>>,
+    #[schemars(description = "Fields shown on the slate, in order (configured template if None)")]
+    pub field_order: Option<Vec<String>>,
+    #[schemars(description = "How long the slate is held, in seconds")]
+    pub slate_seconds: Option<f64>,
+    #[schemars(description = "Length of the countdown after the slate, in seconds; 0 for none")]
+    pub countdown_seconds: Option<f64>,
+    #[schemars(description = "Video track for the slate and countdown (default 1)")]
+    pub track_index: Option<u32>,
+    #[schemars(
+        description = "Record timecode of the timeline's first frame (default 01:00:00:00)"
+    )]
+    pub timeline_start_timecode: Option<String>,
+}
+
+// ---- NEW: Bridge Stats ----
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct GetBridgeStatsRequest {
+    // No additional parameters needed
+}
+
+// ---- NEW: State Sync ----
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SyncRealStateRequest {
+    // No additional parameters needed
+}
+
+// ---- NEW: Job Graph ----
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SubmitJobRequest {
+    #[schemars(description = "API method the job runs, such as transcribe_audio or start_render")]
+    pub method: String,
+    #[schemars(description = "Arguments passed to the method")]
+    pub arguments: Option<serde_json::Map<String, serde_json::Value>>,
+    #[schemars(description = "IDs of jobs that must complete before this one starts")]
+    pub depends_on: Option<Vec<String>>,
+    #[schemars(description = "Description shown in job listings")]
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct GetJobGraphRequest {
+    #[schemars(
+        description = "Only show this job with its prerequisites and dependents (all jobs if None)"
+    )]
+    pub job_id: Option<String>,
+}
+
+// ---- NEW: Grade Reports ----
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct GenerateGradeReportRequest {
+    #[schemars(description = "Timeline ID or name (uses current if None)")]
+    pub timeline_name: Option<String>,
+    #[schemars(description = "Report format: 'json' (default) or 'html'")]
+    pub format: Option<String>,
+    #[schemars(description = "Also write the report to this file")]
+    pub output_path: Option<String>,
+    #[schemars(
+        description = "Record timecode of the timeline's first frame (default 01:00:00:00)"
+    )]
+    pub timeline_start_timecode: Option<String>,
+}
+
+// ---- NEW: Batch Color Presets ----
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ApplyColorPresetBatchRequest {
+    #[schemars(description = "ID of the preset to apply (if known)")]
+    pub preset_id: Option<String>,
+    #[schemars(description = "Name of the preset to apply (searches in album)")]
+    pub preset_name: Option<String>,
+    #[schemars(description = "Album containing the preset")]
+    pub album_name: Option<String>,
+    #[schemars(description = "Copy this clip's grade instead of applying a preset")]
+    pub source_clip: Option<String>,
+    #[schemars(description = "Apply to these clip IDs or names")]
+    pub clip_names: Option<Vec<String>>,
+    #[schemars(description = "Apply to every clip in this bin")]
+    pub bin_name: Option<String>,
+    #[schemars(description = "Apply to every clip in this project color group")]
+    pub color_group: Option<String>,
+    #[schemars(description = "Apply to every clip used in this timeline")]
+    pub timeline_name: Option<String>,
+}
+
+// ---- NEW: Timeline Item Lookup ----
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct FindTimelineItemRequest {
+    #[schemars(description = "Timeline ID or name (uses current if None)")]
+    pub timeline_name: Option<String>,
+    #[schemars(description = "Find items on this video track, starting at 1")]
+    pub track_index: Option<u32>,
+    #[schemars(description = "With track_index, only the item at this position on the track")]
+    pub item_index: Option<u32>,
+    #[schemars(description = "Track type; only 'video' tracks hold items in simulation")]
+    pub track_type: Option<String>,
+    #[schemars(description = "Find items using this media pool clip ID or name")]
+    pub clip_name: Option<String>,
+    #[schemars(description = "Find items under this record timecode")]
+    pub timecode: Option<String>,
+    #[schemars(description = "Find items under the playhead set with set_timeline_timecode")]
+    pub at_playhead: Option<bool>,
+    #[schemars(
+        description = "Record timecode of the timeline's first frame (default 01:00:00:00)"
+    )]
+    pub timeline_start_timecode: Option<String>,
+}
+
+// ---- NEW: Timeline Item Properties ----
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ListTimelineItemPropertiesRequest {
+    #[schemars(
+        description = "Only list this category: transform, crop, composite, retime, sizing, stabilization or audio"
+    )]
+    pub category: Option<String>,
+}
+
+// ---- NEW: Paged Results ----
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct GetResultPageRequest {
+    #[schemars(description = "Cursor from a previous response's next_cursor")]
+    pub cursor: String,
+    #[schemars(
+        description = "Items to return (defaults to the page size of the original request)"
+    )]
+    pub page_size: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct GetMediaPoolItemListRequest {
+    #[schemars(description = "Clips per page (default 500)")]
+    pub page_size: Option<u64>,
+}
+
+// ---- NEW: Clip Usage ----
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct FindClipUsageRequest {
+    #[schemars(description = "Media pool clip to look up")]
+    pub clip_name: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ListUnusedClipsRequest {
+    #[schemars(description = "Only list clips in this bin")]
+    pub bin_name: Option<String>,
+}
+
+// ---- NEW: Embedded Timecode ----
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ReadEmbeddedTimecodeRequest {
+    #[schemars(description = "Clips to read; every media pool clip if None")]
+    pub clip_names: Option<Vec<String>>,
+}
+
+// ---- NEW: Delivery QC ----
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct RunDeliveryQcRequest {
+    #[schemars(description = "Rendered file to check, inside a media storage location")]
+    pub file_path: Option<String>,
+    #[schemars(description = "Timeline to check when no file is given (uses current if None)")]
+    pub timeline_name: Option<String>,
+    #[schemars(description = "Shortest black stretch to report, in seconds (default 0.5)")]
+    pub min_black_seconds: Option<f64>,
+    #[schemars(description = "Shortest frozen stretch to report, in seconds (default 2.0)")]
+    pub min_freeze_seconds: Option<f64>,
+    #[schemars(description = "Shortest silent stretch to report, in seconds (default 2.0)")]
+    pub min_silence_seconds: Option<f64>,
+    #[schemars(description = "Peak level below which audio is silent, in dBFS (default -60)")]
+    pub silence_threshold_db: Option<f64>,
+    #[schemars(
+        description = "Record timecode of the timeline's first frame (default 01:00:00:00)"
+    )]
+    pub timeline_start_timecode: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct GetBackgroundJobRequest {
+    #[schemars(description = "ID of the background job")]
+    pub job_id: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ListBackgroundJobsRequest {
+    #[schemars(description = "Only list jobs of this kind, such as delivery_qc")]
+    pub kind: Option<String>,
+}
+
+// ---- NEW: Color QC ----
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct RunColorQcRequest {
+    #[schemars(description = "Timeline to check (uses current if None)")]
+    pub timeline_name: Option<String>,
+    #[schemars(description = "Frames between samples (default one second)")]
+    pub interval_frames: Option<i64>,
+    #[schemars(
+        description = "Flag a sample when more than this percent of pixels are outside 16-235 (default 1.0)"
+    )]
+    pub illegal_percent_threshold: Option<f64>,
+    #[schemars(
+        description = "Flag a sample when more than this percent of pixels are clipped (default 2.0)"
+    )]
+    pub clipped_percent_threshold: Option<f64>,
+    #[schemars(
+        description = "Flag a change in mean luma between samples larger than this, 0.0-1.0 (default 0.25)"
+    )]
+    pub luma_jump_threshold: Option<f64>,
+    #[schemars(description = "Add a warning marker at each flagged frame")]
+    pub create_markers: Option<bool>,
+    #[schemars(description = "Color of created markers (default Red)")]
+    pub marker_color: Option<String>,
+    #[schemars(
+        description = "Record timecode of the timeline's first frame (default 01:00:00:00)"
+    )]
+    pub timeline_start_timecode: Option<String>,
+}
+
+// ---- NEW: Frame Scopes ----
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct GetFrameScopesRequest {
+    #[schemars(description = "Clip to analyze")]
+    pub clip_name: String,
+    #[schemars(description = "Source frame to analyze (default 0)")]
+    pub frame: Option<i64>,
+    #[schemars(description = "Number of luma histogram bins, 1-256 (default 32)")]
+    pub histogram_bins: Option<u64>,
+}
+
+// ---- NEW: Audio Waveforms ----
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct GetAudioWaveformRequest {
+    #[schemars(description = "Clip to analyze; the timeline is used if None")]
+    pub clip_name: Option<String>,
+    #[schemars(description = "Timeline to analyze when no clip is given (uses current if None)")]
+    pub timeline_name: Option<String>,
+    #[schemars(
+        description = "First frame of the range (source frame for a clip, record frame for a timeline)"
+    )]
+    pub start_frame: Option<i64>,
+    #[schemars(description = "Frame the range ends before (defaults to the clip or timeline end)")]
+    pub end_frame: Option<i64>,
+    #[schemars(description = "Number of min/max peak buckets to return (default 200)")]
+    pub buckets: Option<u64>,
+    #[schemars(description = "Buckets per page (default 500)")]
+    pub page_size: Option<u64>,
+}
+
+// ---- NEW: Transcript Search ----
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SearchTranscriptRequest {
+    #[schemars(description = "Word or phrase to search for; case and punctuation are ignored")]
+    pub query: String,
+    #[schemars(description = "Only search this clip's transcript")]
+    pub clip_name: Option<String>,
+    #[schemars(description = "Skip matches whose lowest word confidence is below this (0.0-1.0)")]
+    pub min_confidence: Option<f64>,
+    #[schemars(description = "Add a marker where each match is used on the timeline")]
+    pub create_markers: Option<bool>,
+    #[schemars(description = "Timeline to add markers to (uses current if None)")]
+    pub timeline_name: Option<String>,
+    #[schemars(description = "Color of created markers (default Purple)")]
+    pub marker_color: Option<String>,
+    #[schemars(description = "Matches per page (default 500)")]
+    pub page_size: Option<u64>,
+}
+
+// ---- NEW: Tags ----
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct TagClipRequest {
+    #[schemars(description = "Media pool clip to tag (set this or timeline_item_id)")]
+    pub clip_name: Option<String>,
+    #[schemars(description = "Timeline item to tag (set this or clip_name)")]
+    pub timeline_item_id: Option<String>,
+    #[schemars(description = "Tags to add; tags are case-insensitive")]
+    pub tags: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct UntagClipRequest {
+    #[schemars(description = "Media pool clip to untag (set this or timeline_item_id)")]
+    pub clip_name: Option<String>,
+    #[schemars(description = "Timeline item to untag (set this or clip_name)")]
+    pub timeline_item_id: Option<String>,
+    #[schemars(description = "Tags to remove (removes all tags if None)")]
+    pub tags: Option<Vec<String>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct FindByTagRequest {
+    #[schemars(description = "Tags to search for")]
+    pub tags: Vec<String>,
+    #[schemars(description = "Match any of the tags or all of them (any, all)")]
+    pub r#match: Option<String>,
+    #[schemars(description = "What to search (clip, timeline_item, all)")]
+    pub target_type: Option<String>,
+}
+
+// ---- NEW: VFX Plates ----
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ExportVfxPlatesRequest {
+    #[schemars(description = "Show code, the first part of plate names")]
+    pub show: String,
+    #[schemars(description = "Sequence code, the second part of plate names")]
+    pub sequence: String,
+    #[schemars(description = "Timeline to export from (uses current if None)")]
+    pub timeline_name: Option<String>,
+    #[schemars(description = "Timeline items to export (all items if None)")]
+    pub timeline_item_ids: Option<Vec<String>>,
+    #[schemars(
+        description = "Export the ranges between markers of this color instead of timeline items"
+    )]
+    pub marker_color: Option<String>,
+    #[schemars(description = "Frames of handle added before and after each shot (default 8)")]
+    pub handles: Option<i64>,
+    #[schemars(description = "Plate format (exr, dpx, prores)")]
+    pub format: Option<String>,
+    #[schemars(description = "Plate version number (default 1)")]
+    pub version: Option<u64>,
+    #[schemars(description = "Number of the first shot (default 10)")]
+    pub shot_start: Option<u64>,
+    #[schemars(description = "Step between shot numbers (default 10)")]
+    pub shot_increment: Option<u64>,
+    #[schemars(description = "Folder plates are rendered into")]
+    pub output_directory: Option<String>,
+    #[schemars(
+        description = "Record timecode of the timeline's first frame (default 01:00:00:00)"
+    )]
+    pub timeline_start_timecode: Option<String>,
+}
+
+// ---- NEW: Media Consolidation ----
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ConsolidateMediaRequest {
+    #[schemars(description = "Timeline to consolidate (uses current if None)")]
+    pub timeline_name: Option<String>,
+    #[schemars(
+        description = "Frames of handle added before and after each used range (default 24)"
+    )]
+    pub handles: Option<i64>,
+    #[schemars(
+        description = "Media storage folder to trim the ranges into; only the pull list is returned if None"
+    )]
+    pub destination_folder: Option<String>,
+    #[schemars(
+        description = "Name of the relinked duplicate timeline (default '<timeline> (Consolidated)')"
+    )]
+    pub new_timeline_name: Option<String>,
+}
+
+// ---- NEW: Shot List ----
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct GenerateShotListRequest {
+    #[schemars(description = "Timeline to walk (uses current if None)")]
+    pub timeline_name: Option<String>,
+    #[schemars(description = "Output format (json, csv); csv also returns the CSV text")]
+    pub format: Option<String>,
+    #[schemars(
+        description = "Record timecode of the timeline's first frame (default 01:00:00:00)"
+    )]
+    pub timeline_start_timecode: Option<String>,
+}
+
+// ---- NEW: Review Notes ----
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct AddReviewNoteRequest {
+    #[schemars(description = "Note text")]
+    pub text: String,
+    #[schemars(description = "Timeline item the note is about (set this or timecode)")]
+    pub timeline_item_id: Option<String>,
+    #[schemars(description = "Record timecode the note is about (set this or timeline_item_id)")]
+    pub timecode: Option<String>,
+    #[schemars(description = "Timeline the note belongs to (uses current if None)")]
+    pub timeline_name: Option<String>,
+    #[schemars(
+        description = "Record timecode of the timeline's first frame (default 01:00:00:00)"
+    )]
+    pub timeline_start_timecode: Option<String>,
+    #[schemars(description = "Initial status (todo, in_progress, approved); defaults to todo")]
+    pub status: Option<String>,
+    #[schemars(description = "Who wrote the note (uses the current user if None)")]
+    pub author: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ListReviewNotesRequest {
+    #[schemars(description = "Only notes with this status (todo, in_progress, approved)")]
+    pub status: Option<String>,
+    #[schemars(description = "Only notes on this timeline")]
+    pub timeline_name: Option<String>,
+    #[schemars(description = "Only notes on this timeline item")]
+    pub timeline_item_id: Option<String>,
+    #[schemars(description = "Only notes by this author")]
+    pub author: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ResolveNoteRequest {
+    #[schemars(description = "ID of the note to update")]
+    pub note_id: String,
+    #[schemars(description = "New status (todo, in_progress, approved); defaults to approved")]
+    pub status: Option<String>,
+    #[schemars(description = "Comment recorded with the status change")]
+    pub comment: Option<String>,
+}
+
+// ---- NEW: Backups ----
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct CreateBackupRequest {
+    #[schemars(description = "Name for the restore point (defaults to project name and time)")]
+    pub name: Option<String>,
+    #[schemars(
+        description = "Also write a timestamped summary of the backup to the configured directory; the file cannot be restored from"
+    )]
+    #[serde(default)]
+    pub write_file: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct RestoreBackupRequest {
+    #[schemars(description = "ID of the backup to restore")]
+    pub backup_id: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ListBackupsRequest {
+    // No additional parameters needed
+}
+
+// ---- NEW: MediaStorage ----
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct GetMountedVolumesRequest {
+    // No additional parameters needed
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct BrowseFolderRequest {
+    #[schemars(description = "Absolute path of the folder to browse")]
+    pub path: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct AddClipsToMediaPoolFromPathsRequest {
+    #[schemars(description = "Absolute paths of media files or folders to add")]
+    pub paths: Vec<String>,
+    #[schemars(description = "Bin to add the clips to (uses the master bin if None)")]
+    pub bin_name: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct AddMediaPoolSubFolderRequest {
+    #[schemars(description = "Name for the new folder")]
+    pub name: String,
+    #[schemars(description = "Optional parent folder")]
+    pub parent_folder: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct GetProjectTimelineByIndexRequest {
+    #[schemars(description = "Timeline index")]
+    pub timeline_index: i32,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct GetProjectCurrentTimelineRequest {
+    // No additional parameters needed
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SetProjectCurrentTimelineRequest {
+    #[schemars(description = "Timeline name to set as current")]
+    pub timeline_name: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct GetProjectNameRequest {
+    // No additional parameters needed
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SetProjectNameRequest {
+    #[schemars(description = "New project name")]
+    pub project_name: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct GetProjectUniqueIdRequest {
+    // No additional parameters needed
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct GetProjectRenderJobListRequest {
+    // No additional parameters needed
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct StartProjectRenderingRequest {
+    // No additional parameters needed
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct StopProjectRenderingRequest {
+    // No additional parameters needed
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct IsProjectRenderingInProgressRequest {
+    // No additional parameters needed
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct GetProjectPresetListRequest {
+    // No additional parameters needed
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct LoadProjectRenderPresetRequest {
+    #[schemars(description = "Name of the preset to load")]
+    pub preset_name: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SaveAsNewProjectRenderPresetRequest {
+    #[schemars(description = "Name for the new preset")]
+    pub preset_name: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct GetCurrentProjectRenderFormatAndCodecRequest {
+    // No additional parameters needed
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SetCurrentProjectRenderFormatAndCodecRequest {
+    #[schemars(description = "Render format")]
+    pub format: String,
+    #[schemars(description = "Render codec")]
+    pub codec: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct GetCurrentProjectRenderModeRequest {
+    // No additional parameters needed
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SetCurrentProjectRenderModeRequest {
+    #[schemars(description = "Render mode")]
+    pub render_mode: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct GetProjectColorGroupsListRequest {
+    // No additional parameters needed
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct AddProjectColorGroupRequest {
+    #[schemars(description = "Name for the new color group")]
+    pub group_name: String,
+    #[schemars(description = "Clip IDs or names to put in the group")]
+    pub clip_names: Option<Vec<String>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct DeleteProjectColorGroupRequest {
+    #[schemars(description = "Name of the color group to delete")]
+    pub group_name: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct AppendToTimelineRequest {
+    #[schemars(description = "List of clip names to append")]
+    pub clip_info: Vec<String>,
+    #[schemars(description = "Optional timeline name (uses current if not specified)")]
+    pub timeline_name: Option<String>,
+}
+
+// ============================================
+// TOOL IMPLEMENTATIONS
+// ============================================
+
+#[derive(Debug)]
+pub struct ProjectTools {
+    bridge: Arc<ResolveBridge>,
+}
+
+impl ProjectTools {
+    pub fn new(bridge: Arc<ResolveBridge>) -> Self {
+        Self { bridge }
+    }
+
+    pub async fn create_project(&self, req: CreateProjectRequest) -> ResolveResult<String> {
+        let args = serde_json::json!({
+            "name": req.name
+        });
+
+        self.bridge.call_api("create_project", args).await?;
+        Ok(format!("Successfully created project '{}'", req.name))
+    }
+
+    pub async fn open_project(&self, req: OpenProjectRequest) -> ResolveResult<String> {
+        let args = serde_json::json!({
+            "name": req.name
+        });
+
+        self.bridge.call_api("open_project", args).await?;
+        Ok(format!("Successfully opened project '{}'", req.name))
+    }
+
+    pub async fn switch_page(&self, req: SwitchPageRequest) -> ResolveResult<String> {
+        let args = serde_json::json!({
+            "page": req.page
+        });
+
+        self.bridge.call_api("switch_page", args).await?;
+        Ok(format!("Successfully switched to {} page", req.page))
+    }
+}
+
+#[derive(Debug)]
+pub struct TimelineTools {
+    bridge: Arc<ResolveBridge>,
+}
+
+impl TimelineTools {
+    pub fn new(bridge: Arc<ResolveBridge>) -> Self {
+        Self { bridge }
+    }
+
+    pub async fn create_timeline(&self, req: CreateTimelineRequest) -> ResolveResult<String> {
+        let args = serde_json::json!({
+            "name": req.name,
+            "frame_rate": req.frame_rate,
+            "resolution_width": req.resolution_width,
+            "resolution_height": req.resolution_height,
+        });
+
+        // The response carries the timeline ID for later lookups
+        let response = self.bridge.call_api("create_timeline", args).await?;
+        Ok(serde_json::to_string_pretty(&response)?)
+    }
+
+    pub async fn add_marker(&self, req: AddMarkerRequest) -> ResolveResult<String> {
+        let args = serde_json::json!({
+            "frame": req.frame,
+            "color": req.color,
+            "note": req.note,
+            "name": req.name,
+            "duration": req.duration,
+        });
+
+        let response = self.bridge.call_api("add_marker", args).await?;
+        Ok(serde_json::to_string_pretty(&response)?)
+    }
+}
+
+#[derive(Debug)]
+pub struct MediaTools {
+    bridge: Arc<ResolveBridge>,
+}
+
+impl MediaTools {
+    pub fn new(bridge: Arc<ResolveBridge>) -> Self {
+        Self { bridge }
+    }
+
+    pub async fn import_media(&self, req: ImportMediaRequest) -> ResolveResult<String> {
+        let args = serde_json::json!({
+            "file_path": req.file_path,
+            "bin_name": req.bin_name,
+            "metadata": req.metadata
+        });
+
+        let response = self.bridge.call_api("import_media", args).await?;
+        Ok(serde_json::to_string_pretty(&response)?)
+    }
+
+    // ---- Phase 3 Week 1: New Media Operations ----
+
+    pub async fn create_bin(&self, req: CreateBinRequest) -> ResolveResult<String> {
+        let args = serde_json::json!({
+            "name": req.name
+        });
+
+        let response = self.bridge.call_api("create_bin", args).await?;
+        Ok(serde_json::to_string_pretty(&response)?)
+    }
+
+    pub async fn auto_sync_audio(&self, req: AutoSyncAudioRequest) -> ResolveResult<String> {
+        let args = serde_json::json!({
+            "clip_names": req.clip_names,
+            "sync_method": req.sync_method,
+            "append_mode": req.append_mode,
+            "target_bin": req.target_bin
+        });
+
+        let response = self.bridge.call_api("auto_sync_audio", args).await?;
+        Ok(serde_json::to_string_pretty(&response)?)
+    }
+
+    pub async fn unlink_clips(&self, req: UnlinkClipsRequest) -> ResolveResult<String> {
+        let args = serde_json::json!({
+            "clip_names": req.clip_names
+        });
+
+        self.bridge.call_api("unlink_clips", args).await?;
+        Ok(format!(
+            "Successfully unlinked {} clips",
+            req.clip_names.len()
+        ))
+    }
+
+    pub async fn relink_clips(&self, req: RelinkClipsRequest) -> ResolveResult<String> {
+        let args = serde_json::json!({
+            "clip_names": req.clip_names,
+            "media_paths": req.media_paths,
+            "folder_path": req.folder_path,
+            "recursive": req.recursive,
+            "match_by": req.match_by
+        });
+
+        let response = self.bridge.call_api("relink_clips", args).await?;
+        // Folder relinks report which clips matched
+        if req.folder_path.is_some() {
+            return Ok(serde_json::to_string_pretty(&response)?);
+        }
+        Ok(format!(
+            "Successfully relinked {} clips",
+            req.clip_names.len()
+        ))
+    }
+
+    pub async fn create_sub_clip(&self, req: CreateSubClipRequest) -> ResolveResult<String> {
+        let args = serde_json::json!({
+            "clip_name": req.clip_name,
+            "start_frame": req.start_frame,
+            "end_frame": req.end_frame,
+            "sub_clip_name": req.sub_clip_name,
+            "bin_name": req.bin_name
+        });
+
+        self.bridge.call_api("create_sub_clip", args).await?;
+        let sub_name = req
+            .sub_clip_name
+            .unwrap_or_else(|| format!("{}_subclip", req.clip_name));
+        Ok(format!(
+            "Successfully created subclip '{}' from '{}' (frames {}-{})",
+            sub_name, req.clip_name, req.start_frame, req.end_frame
+        ))
+    }
+
+    pub async fn link_proxy_media(&self, req: LinkProxyMediaRequest) -> ResolveResult<String> {
+        let args = serde_json::json!({
+            "clip_name": req.clip_name,
+            "proxy_file_path": req.proxy_file_path
+        });
+
+        self.bridge.call_api("link_proxy_media", args).await?;
+        Ok(format!(
+            "Successfully linked proxy media for clip '{}'",
+            req.clip_name
+        ))
+    }
+
+    pub async fn unlink_proxy_media(&self, req: UnlinkProxyMediaRequest) -> ResolveResult<String> {
+        let args = serde_json::json!({
+            "clip_name": req.clip_name
+        });
+
+        self.bridge.call_api("unlink_proxy_media", args).await?;
+        Ok(format!(
+            "Successfully unlinked proxy media for clip '{}'",
+            req.clip_name
+        ))
+    }
+
+    pub async fn replace_clip(&self, req: ReplaceClipRequest) -> ResolveResult<String> {
+        let args = serde_json::json!({
+            "clip_name": req.clip_name,
+            "replacement_path": req.replacement_path
+        });
+
+        self.bridge.call_api("replace_clip", args).await?;
+        Ok(format!(
+            "Successfully replaced clip '{}' with '{}'",
+            req.clip_name, req.replacement_path
+        ))
+    }
+}
+
+// ============================================
+// TOOL ROUTING FUNCTION
+// ============================================
+
+/// Route a tool call and count it in the tool usage statistics
+pub async fn handle_tool_call(
+    tool_name: &str,
+    mut args: serde_json::Value,
+    bridge: Arc<ResolveBridge>,
+) -> ResolveResult<String> {
+    let started = std::time::Instant::now();
+    let return_diff = args
+        .as_object_mut()
+        .and_then(|args| args.remove(RETURN_DIFF_ARGUMENT))
+        .and_then(|value| value.as_bool())
+        .unwrap_or(false);
+    let result = if return_diff {
+        let (result, changes) =
+            ResolveBridge::with_changes(route_tool_call(tool_name, args, bridge.clone())).await;
+        result.and_then(|response| attach_changes(response, changes))
+    } else {
+        route_tool_call(tool_name, args, bridge.clone()).await
+    };
+    // Unknown names are not tools and would grow the statistics without bound
+    if !matches!(result, Err(crate::error::ResolveError::ToolNotFound { .. })) {
+        bridge
+            .record_tool_call(tool_name, result.is_ok(), started.elapsed())
+            .await;
+    }
+    result
+}
+
+/// Argument every mutating tool takes to report what it changed
+pub const RETURN_DIFF_ARGUMENT: &str = "return_diff";
+
+/// A tool response with the changes its call made added as `changes`;
+/// responses that are not JSON objects move to `result`
+fn attach_changes(response: String, changes: Vec<serde_json::Value>) -> ResolveResult<String> {
+    let mut response = match serde_json::from_str::<serde_json::Value>(&response) {
+        Ok(serde_json::Value::Object(object)) => object,
+        _ => {
+            let mut object = serde_json::Map::new();
+            object.insert("result".to_string(), serde_json::Value::String(response));
+            object
+        }
+    };
+    response.insert("changes".to_string(), serde_json::Value::Array(changes));
+    Ok(serde_json::to_string_pretty(&response)?)
+}
+
+async fn route_tool_call(
+    tool_name: &str,
+    args: serde_json::Value,
+    bridge: Arc<ResolveBridge>,
+) -> ResolveResult<String> {
+    let project_tools = ProjectTools::new(bridge.clone());
+    let timeline_tools = TimelineTools::new(bridge.clone());
+    let media_tools = MediaTools::new(bridge.clone());
+
+    match tool_name {
+        // ---- Phase 1 & 2 Tools ----
+        "create_project" => {
+            let req: CreateProjectRequest = serde_json::from_value(args)?;
+            project_tools.create_project(req).await
+        }
+        "open_project" => {
+            let req: OpenProjectRequest = serde_json::from_value(args)?;
+            project_tools.open_project(req).await
+        }
+        "switch_page" => {
+            let req: SwitchPageRequest = serde_json::from_value(args)?;
+            project_tools.switch_page(req).await
+        }
+        "create_timeline" => {
+            let req: CreateTimelineRequest = serde_json::from_value(args)?;
+            timeline_tools.create_timeline(req).await
+        }
+        "import_media" => {
+            let req: ImportMediaRequest = serde_json::from_value(args)?;
+            media_tools.import_media(req).await
+        }
+        "add_marker" => {
+            let req: AddMarkerRequest = serde_json::from_value(args)?;
+            timeline_tools.add_marker(req).await
+        }
+
+        // ---- Phase 3 Week 1: New Media Operations ----
+        "create_bin" => {
+            let req: CreateBinRequest = serde_json::from_value(args)?;
+            media_tools.create_bin(req).await
+        }
+        "auto_sync_audio" => {
+            let req: AutoSyncAudioRequest = serde_json::from_value(args)?;
+            media_tools.auto_sync_audio(req).await
+        }
+        "unlink_clips" => {
+            let req: UnlinkClipsRequest = serde_json::from_value(args)?;
+            media_tools.unlink_clips(req).await
+        }
+        "relink_clips" => {
+            let req: RelinkClipsRequest = serde_json::from_value(args)?;
+            media_tools.relink_clips(req).await
+        }
+        "create_sub_clip" => {
+            let req: CreateSubClipRequest = serde_json::from_value(args)?;
+            media_tools.create_sub_clip(req).await
+        }
+        "link_proxy_media" => {
+            let req: LinkProxyMediaRequest = serde_json::from_value(args)?;
+            media_tools.link_proxy_media(req).await
+        }
+        "unlink_proxy_media" => {
+            let req: UnlinkProxyMediaRequest = serde_json::from_value(args)?;
+            media_tools.unlink_proxy_media(req).await
+        }
+        "replace_clip" => {
+            let req: ReplaceClipRequest = serde_json::from_value(args)?;
+            media_tools.replace_clip(req).await
+        }
+
+        // Timeline Enhancement Tools (Phase 3 Week 2)
+        "delete_timeline" => {
+            let req: DeleteTimelineRequest = serde_json::from_value(args)?;
+            let response = bridge
+                .call_api(
+                    "delete_timeline",
+                    serde_json::json!({
+                        "name": req.name
+                    }),
+                )
+                .await?;
+            // The trash entry is only in the full response
+            Ok(serde_json::to_string_pretty(&response)?)
+        }
+        "set_current_timeline" => {
+            let req: SetCurrentTimelineRequest = serde_json::from_value(args)?;
+            let response = bridge
+                .call_api(
+                    "set_current_timeline",
+                    serde_json::json!({
+                        "name": req.name
+                    }),
+                )
+                .await?;
+            Ok(response["result"].as_str().unwrap_or("Success").to_string())
+        }
+        "create_empty_timeline" => {
+            let req: CreateEmptyTimelineRequest = serde_json::from_value(args)?;
+            let response = bridge
+                .call_api(
+                    "create_empty_timeline",
+                    serde_json::json!({
+                        "name": req.name,
+                        "frame_rate": req.frame_rate,
+                        "resolution_width": req.resolution_width,
+                        "resolution_height": req.resolution_height,
+                        "start_timecode": req.start_timecode,
+                        "video_tracks": req.video_tracks,
+                        "audio_tracks": req.audio_tracks
+                    }),
+                )
+                .await?;
+            Ok(serde_json::to_string_pretty(&response)?)
+        }
+        "add_clip_to_timeline" => {
+            let req: AddClipToTimelineRequest = serde_json::from_value(args)?;
+            let response = bridge
+                .call_api(
+                    "add_clip_to_timeline",
+                    serde_json::json!({
+                        "clip_name": req.clip_name,
+                        "timeline_name": req.timeline_name,
+                        "track_index": req.track_index,
+                        "start_frame": req.start_frame,
+                        "end_frame": req.end_frame,
+                        "record_frame": req.record_frame
+                    }),
+                )
+                .await?;
+            Ok(serde_json::to_string_pretty(&response)?)
+        }
+        "get_timeline_tracks" => {
+            let req: GetTimelineTracksRequest = serde_json::from_value(args)?;
+            let response = bridge
+                .call_api(
+                    "get_timeline_tracks",
+                    serde_json::json!({
+                        "timeline_name": req.timeline_name
+                    }),
+                )
+                .await?;
+            Ok(response["result"].as_str().unwrap_or("Success").to_string())
+        }
+        "list_timelines_tool" => {
+            let response = bridge
+                .call_api("list_timelines_tool", serde_json::json!({}))
+                .await?;
+            Ok(serde_json::to_string_pretty(&response)?)
+        }
+
+        // ---- Color Operations Request Types (Phase 3 Week 3) ----
+        "apply_lut" => {
+            let req: ApplyLutRequest = serde_json::from_value(args)?;
+            let response = bridge
+                .call_api(
+                    "apply_lut",
+                    serde_json::json!({
+                        "lut_path": req.lut_path,
+                        "node_index": req.node_index,
+                        "allow_color_space_mismatch": req.allow_color_space_mismatch
+                    }),
+                )
+                .await?;
+            Ok(response["result"].as_str().unwrap_or("Success").to_string())
+        }
+        "set_color_wheel_param" => {
+            let req: SetColorWheelParamRequest = serde_json::from_value(args)?;
+            let response = bridge
+                .call_api(
+                    "set_color_wheel_param",
+                    serde_json::json!({
+                        "wheel": req.wheel,
+                        "param": req.param,
+                        "value": req.value,
+                        "node_index": req.node_index,
+                        "clip_name": req.clip_name,
+                        "relative": req.relative
+                    }),
+                )
+                .await?;
+            Ok(response["result"].as_str().unwrap_or("Success").to_string())
+        }
+        "add_node" => {
+            let req: AddNodeRequest = serde_json::from_value(args)?;
+            let response = bridge
+                .call_api(
+                    "add_node",
+                    serde_json::json!({
+                        "node_type": req.node_type,
+                        "label": req.label
+                    }),
+                )
+                .await?;
+            Ok(response["result"].as_str().unwrap_or("Success").to_string())
+        }
+        "copy_grade" => {
+            let req: CopyGradeRequest = serde_json::from_value(args)?;
+            let response = bridge
+                .call_api(
+                    "copy_grade",
+                    serde_json::json!({
+                        "source_clip_name": req.source_clip_name,
+                        "target_clip_name": req.target_clip_name,
+                        "mode": req.mode
+                    }),
+                )
+                .await?;
+            Ok(response["result"].as_str().unwrap_or("Success").to_string())
+        }
+        "save_color_preset" => {
+            let req: SaveColorPresetRequest = serde_json::from_value(args)?;
+            let response = bridge
+                .call_api(
+                    "save_color_preset",
+                    serde_json::json!({
+                        "clip_name": req.clip_name,
+                        "preset_name": req.preset_name,
+                        "album_name": req.album_name
+                    }),
+                )
+                .await?;
+            Ok(response["result"].as_str().unwrap_or("Success").to_string())
+        }
+        "apply_color_preset" => {
+            let req: ApplyColorPresetRequest = serde_json::from_value(args)?;
+            let response = bridge
+                .call_api(
+                    "apply_color_preset",
+                    serde_json::json!({
+                        "preset_id": req.preset_id,
+                        "preset_name": req.preset_name,
+                        "clip_name": req.clip_name,
+                        "album_name": req.album_name
+                    }),
+                )
+                .await?;
+            Ok(response["result"].as_str().unwrap_or("Success").to_string())
+        }
+        "delete_color_preset" => {
+            let req: DeleteColorPresetRequest = serde_json::from_value(args)?;
+            let response = bridge
+                .call_api(
+                    "delete_color_preset",
+                    serde_json::json!({
+                        "preset_id": req.preset_id,
+                        "preset_name": req.preset_name,
+                        "album_name": req.album_name
+                    }),
+                )
+                .await?;
+            // The trash entry is only in the full response
+            Ok(serde_json::to_string_pretty(&response)?)
+        }
+        "list_color_presets" => {
+            let req: ListColorPresetsRequest = serde_json::from_value(args)?;
+            let response = bridge
+                .call_api(
+                    "list_color_presets",
+                    serde_json::json!({
+                        "album_name": req.album_name
+                    }),
+                )
+                .await?;
+            Ok(serde_json::to_string_pretty(&response)?)
+        }
+        "export_lut" => {
+            let req: ExportLutRequest = serde_json::from_value(args)?;
+            let response = bridge
+                .call_api(
+                    "export_lut",
+                    serde_json::json!({
+                        "clip_name": req.clip_name,
+                        "export_path": req.export_path,
+                        "lut_format": req.lut_format,
+                        "lut_size": req.lut_size
+                    }),
+                )
+                .await?;
+            Ok(response["result"].as_str().unwrap_or("Success").to_string())
+        }
+
+        // ---- Timeline Item Operations Request Types (Phase 4 Week 1) ----
+        "set_timeline_item_transform" => {
+            let req: SetTimelineItemTransformRequest = serde_json::from_value(args)?;
+            let response = bridge
+                .call_api(
+                    "set_timeline_item_transform",
+                    serde_json::json!({
+                        "timeline_item_id": req.timeline_item_id,
+                        "property_name": req.property_name,
+                        "property_value": req.property_value
+                    }),
+                )
+                .await?;
+            Ok(response["result"].as_str().unwrap_or("Success").to_string())
+        }
+        "set_timeline_item_crop" => {
+            let req: SetTimelineItemCropRequest = serde_json::from_value(args)?;
+            let response = bridge
+                .call_api(
+                    "set_timeline_item_crop",
+                    serde_json::json!({
+                        "timeline_item_id": req.timeline_item_id,
+                        "crop_type": req.crop_type,
+                        "crop_value": req.crop_value
+                    }),
+                )
+                .await?;
+            Ok(response["result"].as_str().unwrap_or("Success").to_string())
+        }
+        "set_timeline_item_composite" => {
+            let req: SetTimelineItemCompositeRequest = serde_json::from_value(args)?;
+            let response = bridge
+                .call_api(
+                    "set_timeline_item_composite",
+                    serde_json::json!({
+                        "timeline_item_id": req.timeline_item_id,
+                        "composite_mode": req.composite_mode,
+                        "opacity": req.opacity
+                    }),
+                )
+                .await?;
+            Ok(response["result"].as_str().unwrap_or("Success").to_string())
+        }
+        "set_timeline_item_retime" => {
+            let req: SetTimelineItemRetimeRequest = serde_json::from_value(args)?;
+            let response = bridge
+                .call_api(
+                    "set_timeline_item_retime",
+                    serde_json::json!({
+                        "timeline_item_id": req.timeline_item_id,
+                        "speed": req.speed,
+                        "process": req.process
+                    }),
+                )
+                .await?;
+            Ok(response["result"].as_str().unwrap_or("Success").to_string())
+        }
+        "set_item_output_sizing" => {
+            let req: SetItemOutputSizingRequest = serde_json::from_value(args)?;
+            let response = bridge
+                .call_api(
+                    "set_item_output_sizing",
+                    serde_json::json!({
+                        "timeline_item_id": req.timeline_item_id,
+                        "scaling": req.scaling,
+                        "super_scale": req.super_scale,
+                        "super_scale_factor": req.super_scale_factor,
+                        "lens_correction": req.lens_correction
+                    }),
+                )
+                .await?;
+            Ok(response["result"].as_str().unwrap_or("Success").to_string())
+        }
+        "set_timeline_item_stabilization" => {
+            let req: SetTimelineItemStabilizationRequest = serde_json::from_value(args)?;
+            let response = bridge
+                .call_api(
+                    "set_timeline_item_stabilization",
+                    serde_json::json!({
+                        "timeline_item_id": req.timeline_item_id,
+                        "enabled": req.enabled,
+                        "method": req.method,
+                        "strength": req.strength
+                    }),
+                )
+                .await?;
+            Ok(response["result"].as_str().unwrap_or("Success").to_string())
+        }
+        "set_timeline_item_audio" => {
+            let req: SetTimelineItemAudioRequest = serde_json::from_value(args)?;
+            let response = bridge
+                .call_api(
+                    "set_timeline_item_audio",
+                    serde_json::json!({
+                        "timeline_item_id": req.timeline_item_id,
+                        "volume": req.volume,
+                        "pan": req.pan,
+                        "eq_enabled": req.eq_enabled
+                    }),
+                )
+                .await?;
+            Ok(response["result"].as_str().unwrap_or("Success").to_string())
+        }
+        "get_timeline_item_properties" => {
+            let req: GetTimelineItemPropertiesRequest = serde_json::from_value(args)?;
+            let response = bridge
+                .call_api(
+                    "get_timeline_item_properties",
+                    serde_json::json!({
+                        "timeline_item_id": req.timeline_item_id
+                    }),
+                )
+                .await?;
+            Ok(response["result"].as_str().unwrap_or("Success").to_string())
+        }
+        "reset_timeline_item_properties" => {
+            let req: ResetTimelineItemPropertiesRequest = serde_json::from_value(args)?;
+            let response = bridge
+                .call_api(
+                    "reset_timeline_item_properties",
+                    serde_json::json!({
+                        "timeline_item_id": req.timeline_item_id,
+                        "property_type": req.property_type
+                    }),
+                )
+                .await?;
+            Ok(response["result"].as_str().unwrap_or("Success").to_string())
+        }
 
-    match tool_name {
-        // ---- Phase 1 & 2 Tools ----
-        "create_project" => {
-            let req: CreateProjectRequest = serde_json::from_value(args)?;
-            project_tools.create_project(req).await
+        // ---- Keyframe Animation Request Types (Phase 4 Week 2) ----
+        "add_keyframe" => {
+            let req: AddKeyframeRequest = serde_json::from_value(args)?;
+            let response = bridge
+                .call_api(
+                    "add_keyframe",
+                    serde_json::json!({
+                        "timeline_item_id": req.timeline_item_id,
+                        "property_name": req.property_name,
+                        "frame": req.frame,
+                        "value": req.value
+                    }),
+                )
+                .await?;
+            Ok(response["result"].as_str().unwrap_or("Success").to_string())
         }
-        "open_project" => {
-            let req: OpenProjectRequest = serde_json::from_value(args)?;
-            project_tools.open_project(req).await
+        "modify_keyframe" => {
+            let req: ModifyKeyframeRequest = serde_json::from_value(args)?;
+            let response = bridge
+                .call_api(
+                    "modify_keyframe",
+                    serde_json::json!({
+                        "timeline_item_id": req.timeline_item_id,
+                        "property_name": req.property_name,
+                        "frame": req.frame,
+                        "new_value": req.new_value,
+                        "new_frame": req.new_frame
+                    }),
+                )
+                .await?;
+            Ok(response["result"].as_str().unwrap_or("Success").to_string())
         }
-        "switch_page" => {
-            let req: SwitchPageRequest = serde_json::from_value(args)?;
-            project_tools.switch_page(req).await
+        "delete_keyframe" => {
+            let req: DeleteKeyframeRequest = serde_json::from_value(args)?;
+            let response = bridge
+                .call_api(
+                    "delete_keyframe",
+                    serde_json::json!({
+                        "timeline_item_id": req.timeline_item_id,
+                        "property_name": req.property_name,
+                        "frame": req.frame
+                    }),
+                )
+                .await?;
+            Ok(response["result"].as_str().unwrap_or("Success").to_string())
         }
-        "create_timeline" => {
-            let req: CreateTimelineRequest = serde_json::from_value(args)?;
-            timeline_tools.create_timeline(req).await
+        "set_keyframe_interpolation" => {
+            let req: SetKeyframeInterpolationRequest = serde_json::from_value(args)?;
+            let response = bridge
+                .call_api(
+                    "set_keyframe_interpolation",
+                    serde_json::json!({
+                        "timeline_item_id": req.timeline_item_id,
+                        "property_name": req.property_name,
+                        "frame": req.frame,
+                        "interpolation_type": req.interpolation_type
+                    }),
+                )
+                .await?;
+            Ok(response["result"].as_str().unwrap_or("Success").to_string())
         }
-        "import_media" => {
-            let req: ImportMediaRequest = serde_json::from_value(args)?;
-            media_tools.import_media(req).await
+        "enable_keyframes" => {
+            let req: EnableKeyframesRequest = serde_json::from_value(args)?;
+            let response = bridge
+                .call_api(
+                    "enable_keyframes",
+                    serde_json::json!({
+                        "timeline_item_id": req.timeline_item_id,
+                        "keyframe_mode": req.keyframe_mode
+                    }),
+                )
+                .await?;
+            Ok(response["result"].as_str().unwrap_or("Success").to_string())
         }
-        "add_marker" => {
-            let req: AddMarkerRequest = serde_json::from_value(args)?;
-            timeline_tools.add_marker(req).await
+        "get_keyframes" => {
+            let req: GetKeyframesRequest = serde_json::from_value(args)?;
+            let response = bridge
+                .call_api(
+                    "get_keyframes",
+                    serde_json::json!({
+                        "timeline_item_id": req.timeline_item_id,
+                        "property_name": req.property_name
+                    }),
+                )
+                .await?;
+            Ok(response["result"].as_str().unwrap_or("Success").to_string())
         }
 
-        // ---- Phase 3 Week 1: New Media Operations ----
-        "create_bin" => {
-            let req: CreateBinRequest = serde_json::from_value(args)?;
-            media_tools.create_bin(req).await
+        // ---- Render and Delivery Operations (Phase 4 Week 3) ----
+        "add_to_render_queue" => {
+            let req: AddToRenderQueueRequest = serde_json::from_value(args)?;
+            let response = bridge
+                .call_api(
+                    "add_to_render_queue",
+                    serde_json::json!({
+                        "preset_name": req.preset_name,
+                        "timeline_name": req.timeline_name,
+                        "use_in_out_range": req.use_in_out_range,
+                        "export_video": req.export_video,
+                        "export_audio": req.export_audio,
+                        "audio_channel_layout": req.audio_channel_layout,
+                        "subtitle_export": req.subtitle_export,
+                        "subtitle_format": req.subtitle_format,
+                        "subtitle_track_index": req.subtitle_track_index
+                    }),
+                )
+                .await?;
+            Ok(response["result"].as_str().unwrap_or("Success").to_string())
         }
-        "auto_sync_audio" => {
-            let req: AutoSyncAudioRequest = serde_json::from_value(args)?;
-            media_tools.auto_sync_audio(req).await
+        "start_render" => {
+            let response = bridge
+                .call_api("start_render", serde_json::json!({}))
+                .await?;
+            Ok(response["result"].as_str().unwrap_or("Success").to_string())
         }
-        "unlink_clips" => {
-            let req: UnlinkClipsRequest = serde_json::from_value(args)?;
-            media_tools.unlink_clips(req).await
+        "clear_render_queue" => {
+            let response = bridge
+                .call_api("clear_render_queue", serde_json::json!({}))
+                .await?;
+            Ok(response["result"].as_str().unwrap_or("Success").to_string())
+        }
+
+        // ---- Project Management Operations ----
+        "save_project" => {
+            let response = bridge
+                .call_api("save_project", serde_json::json!({}))
+                .await?;
+            Ok(response["result"].as_str().unwrap_or("Success").to_string())
+        }
+        "close_project" => {
+            let response = bridge
+                .call_api("close_project", serde_json::json!({}))
+                .await?;
+            Ok(response["result"].as_str().unwrap_or("Success").to_string())
+        }
+        "set_project_setting" => {
+            let req: SetProjectSettingRequest = serde_json::from_value(args)?;
+            let response = bridge
+                .call_api(
+                    "set_project_setting",
+                    serde_json::json!({
+                        "setting_name": req.setting_name,
+                        "setting_value": req.setting_value
+                    }),
+                )
+                .await?;
+            Ok(response["result"].as_str().unwrap_or("Success").to_string())
         }
-        "relink_clips" => {
-            let req: RelinkClipsRequest = serde_json::from_value(args)?;
-            media_tools.relink_clips(req).await
+
+        // ---- Audio Transcription Operations ----
+        "transcribe_audio" => {
+            let req: TranscribeAudioRequest = serde_json::from_value(args)?;
+            let response = bridge
+                .call_api(
+                    "transcribe_audio",
+                    serde_json::json!({
+                        "clip_name": req.clip_name,
+                        "language": req.language
+                    }),
+                )
+                .await?;
+            Ok(response["result"].as_str().unwrap_or("Success").to_string())
         }
-        "create_sub_clip" => {
-            let req: CreateSubClipRequest = serde_json::from_value(args)?;
-            media_tools.create_sub_clip(req).await
+        "clear_transcription" => {
+            let req: ClearTranscriptionRequest = serde_json::from_value(args)?;
+            let response = bridge
+                .call_api(
+                    "clear_transcription",
+                    serde_json::json!({
+                        "clip_name": req.clip_name
+                    }),
+                )
+                .await?;
+            Ok(response["result"].as_str().unwrap_or("Success").to_string())
         }
-        "link_proxy_media" => {
-            let req: LinkProxyMediaRequest = serde_json::from_value(args)?;
-            media_tools.link_proxy_media(req).await
+
+        // ---- Phase 4 Week 3: Rendering & Delivery Operations ----
+        "get_render_status" => {
+            let response = bridge
+                .call_api("get_render_status", serde_json::json!({}))
+                .await?;
+            Ok(serde_json::to_string_pretty(&response)?)
         }
-        "unlink_proxy_media" => {
-            let req: UnlinkProxyMediaRequest = serde_json::from_value(args)?;
-            media_tools.unlink_proxy_media(req).await
+        "export_project" => {
+            let req: ExportProjectRequest = serde_json::from_value(args)?;
+            let response = bridge
+                .call_api(
+                    "export_project",
+                    serde_json::json!({
+                        "export_path": req.export_path,
+                        "include_media": req.include_media,
+                        "project_name": req.project_name
+                    }),
+                )
+                .await?;
+            Ok(response["result"].as_str().unwrap_or("Success").to_string())
         }
-        "replace_clip" => {
-            let req: ReplaceClipRequest = serde_json::from_value(args)?;
-            media_tools.replace_clip(req).await
+        "create_render_preset" => {
+            let req: CreateRenderPresetRequest = serde_json::from_value(args)?;
+            let response = bridge
+                .call_api(
+                    "create_render_preset",
+                    serde_json::json!({
+                        "preset_name": req.preset_name,
+                        "format": req.format,
+                        "codec": req.codec,
+                        "resolution_width": req.resolution_width,
+                        "resolution_height": req.resolution_height,
+                        "frame_rate": req.frame_rate,
+                        "quality": req.quality,
+                        "audio_codec": req.audio_codec,
+                        "audio_bitrate": req.audio_bitrate,
+                        "bit_depth": req.bit_depth,
+                        "export_alpha": req.export_alpha,
+                        "export_video": req.export_video,
+                        "export_audio": req.export_audio,
+                        "audio_channel_layout": req.audio_channel_layout,
+                        "embed_chapters": req.embed_chapters,
+                        "embed_metadata": req.embed_metadata
+                    }),
+                )
+                .await?;
+            // Return full response for create_render_preset to include resolution details
+            Ok(response.to_string())
         }
 
-        // Timeline Enhancement Tools (Phase 3 Week 2)
-        "delete_timeline" => {
-            let req: DeleteTimelineRequest = serde_json::from_value(args)?;
+        // ---- NEW: Extended Project Management Operations ----
+        "delete_media" => {
+            let req: DeleteMediaRequest = serde_json::from_value(args)?;
             let response = bridge
                 .call_api(
-                    "delete_timeline",
+                    "delete_media",
                     serde_json::json!({
-                        "name": req.name
+                        "clip_name": req.clip_name
+                    }),
+                )
+                .await?;
+            // The trash entry is only in the full response
+            Ok(serde_json::to_string_pretty(&response)?)
+        }
+        "move_media_to_bin" => {
+            let req: MoveMediaToBinRequest = serde_json::from_value(args)?;
+            let response = bridge
+                .call_api(
+                    "move_media_to_bin",
+                    serde_json::json!({
+                        "clip_name": req.clip_name,
+                        "bin_name": req.bin_name
                     }),
                 )
                 .await?;
             Ok(response["result"].as_str().unwrap_or("Success").to_string())
         }
-        "set_current_timeline" => {
-            let req: SetCurrentTimelineRequest = serde_json::from_value(args)?;
+        "export_folder" => {
+            let req: ExportFolderRequest = serde_json::from_value(args)?;
             let response = bridge
                 .call_api(
-                    "set_current_timeline",
+                    "export_folder",
                     serde_json::json!({
-                        "name": req.name
+                        "folder_name": req.folder_name,
+                        "export_path": req.export_path,
+                        "export_type": req.export_type
                     }),
                 )
                 .await?;
             Ok(response["result"].as_str().unwrap_or("Success").to_string())
         }
-        "create_empty_timeline" => {
-            let req: CreateEmptyTimelineRequest = serde_json::from_value(args)?;
+        "transcribe_folder_audio" => {
+            let req: TranscribeFolderAudioRequest = serde_json::from_value(args)?;
             let response = bridge
                 .call_api(
-                    "create_empty_timeline",
+                    "transcribe_folder_audio",
                     serde_json::json!({
-                        "name": req.name,
-                        "frame_rate": req.frame_rate,
-                        "resolution_width": req.resolution_width,
-                        "resolution_height": req.resolution_height,
-                        "start_timecode": req.start_timecode,
-                        "video_tracks": req.video_tracks,
-                        "audio_tracks": req.audio_tracks
+                        "folder_name": req.folder_name,
+                        "language": req.language
                     }),
                 )
                 .await?;
             Ok(response["result"].as_str().unwrap_or("Success").to_string())
         }
-        "add_clip_to_timeline" => {
-            let req: AddClipToTimelineRequest = serde_json::from_value(args)?;
+        "clear_folder_transcription" => {
+            let req: ClearFolderTranscriptionRequest = serde_json::from_value(args)?;
             let response = bridge
                 .call_api(
-                    "add_clip_to_timeline",
+                    "clear_folder_transcription",
                     serde_json::json!({
-                        "clip_name": req.clip_name,
-                        "timeline_name": req.timeline_name
+                        "folder_name": req.folder_name
                     }),
                 )
                 .await?;
             Ok(response["result"].as_str().unwrap_or("Success").to_string())
         }
-        "get_timeline_tracks" => {
-            let req: GetTimelineTracksRequest = serde_json::from_value(args)?;
+
+        // ---- NEW: Cache and Optimization Operations ----
+        "set_cache_mode" => {
+            let req: SetCacheModeRequest = serde_json::from_value(args)?;
             let response = bridge
                 .call_api(
-                    "get_timeline_tracks",
+                    "set_cache_mode",
                     serde_json::json!({
-                        "timeline_name": req.timeline_name
+                        "mode": req.mode
                     }),
                 )
                 .await?;
             Ok(response["result"].as_str().unwrap_or("Success").to_string())
         }
-        "list_timelines_tool" => {
+        "set_optimized_media_mode" => {
+            let req: SetOptimizedMediaModeRequest = serde_json::from_value(args)?;
             let response = bridge
-                .call_api("list_timelines_tool", serde_json::json!({}))
+                .call_api(
+                    "set_optimized_media_mode",
+                    serde_json::json!({
+                        "mode": req.mode
+                    }),
+                )
                 .await?;
             Ok(response["result"].as_str().unwrap_or("Success").to_string())
         }
-
-        // ---- Color Operations Request Types (Phase 3 Week 3) ----
-        "apply_lut" => {
-            let req: ApplyLutRequest = serde_json::from_value(args)?;
+        "set_proxy_mode" => {
+            let req: SetProxyModeRequest = serde_json::from_value(args)?;
             let response = bridge
                 .call_api(
-                    "apply_lut",
+                    "set_proxy_mode",
                     serde_json::json!({
-                        "lut_path": req.lut_path,
-                        "node_index": req.node_index
+                        "mode": req.mode
                     }),
                 )
                 .await?;
             Ok(response["result"].as_str().unwrap_or("Success").to_string())
         }
-        "set_color_wheel_param" => {
-            let req: SetColorWheelParamRequest = serde_json::from_value(args)?;
+        "set_proxy_quality" => {
+            let req: SetProxyQualityRequest = serde_json::from_value(args)?;
             let response = bridge
                 .call_api(
-                    "set_color_wheel_param",
+                    "set_proxy_quality",
                     serde_json::json!({
-                        "wheel": req.wheel,
-                        "param": req.param,
-                        "value": req.value,
-                        "node_index": req.node_index
+                        "quality": req.quality
                     }),
                 )
                 .await?;
             Ok(response["result"].as_str().unwrap_or("Success").to_string())
         }
-        "add_node" => {
-            let req: AddNodeRequest = serde_json::from_value(args)?;
+        "set_cache_path" => {
+            let req: SetCachePathRequest = serde_json::from_value(args)?;
             let response = bridge
                 .call_api(
-                    "add_node",
+                    "set_cache_path",
                     serde_json::json!({
-                        "node_type": req.node_type,
-                        "label": req.label
+                        "path_type": req.path_type,
+                        "path": req.path
                     }),
                 )
                 .await?;
             Ok(response["result"].as_str().unwrap_or("Success").to_string())
         }
-        "copy_grade" => {
-            let req: CopyGradeRequest = serde_json::from_value(args)?;
+        "generate_optimized_media" => {
+            let req: GenerateOptimizedMediaRequest = serde_json::from_value(args)?;
             let response = bridge
                 .call_api(
-                    "copy_grade",
+                    "generate_optimized_media",
                     serde_json::json!({
-                        "source_clip_name": req.source_clip_name,
-                        "target_clip_name": req.target_clip_name,
-                        "mode": req.mode
+                        "clip_names": req.clip_names
                     }),
                 )
                 .await?;
             Ok(response["result"].as_str().unwrap_or("Success").to_string())
         }
-        "save_color_preset" => {
-            let req: SaveColorPresetRequest = serde_json::from_value(args)?;
+        "delete_optimized_media" => {
+            let req: DeleteOptimizedMediaRequest = serde_json::from_value(args)?;
             let response = bridge
                 .call_api(
-                    "save_color_preset",
+                    "delete_optimized_media",
                     serde_json::json!({
-                        "clip_name": req.clip_name,
-                        "preset_name": req.preset_name,
-                        "album_name": req.album_name
+                        "clip_names": req.clip_names
                     }),
                 )
                 .await?;
             Ok(response["result"].as_str().unwrap_or("Success").to_string())
         }
-        "apply_color_preset" => {
-            let req: ApplyColorPresetRequest = serde_json::from_value(args)?;
+
+        // ---- NEW: Extended Color Operations ----
+        "create_color_preset_album" => {
+            let req: CreateColorPresetAlbumRequest = serde_json::from_value(args)?;
             let response = bridge
                 .call_api(
-                    "apply_color_preset",
+                    "create_color_preset_album",
                     serde_json::json!({
-                        "preset_id": req.preset_id,
-                        "preset_name": req.preset_name,
-                        "clip_name": req.clip_name,
                         "album_name": req.album_name
                     }),
                 )
                 .await?;
             Ok(response["result"].as_str().unwrap_or("Success").to_string())
         }
-        "delete_color_preset" => {
-            let req: DeleteColorPresetRequest = serde_json::from_value(args)?;
+        "delete_color_preset_album" => {
+            let req: DeleteColorPresetAlbumRequest = serde_json::from_value(args)?;
             let response = bridge
                 .call_api(
-                    "delete_color_preset",
+                    "delete_color_preset_album",
                     serde_json::json!({
-                        "preset_id": req.preset_id,
-                        "preset_name": req.preset_name,
                         "album_name": req.album_name
                     }),
                 )
                 .await?;
             Ok(response["result"].as_str().unwrap_or("Success").to_string())
         }
-        "export_lut" => {
-            let req: ExportLutRequest = serde_json::from_value(args)?;
+        "export_all_power_grade_luts" => {
+            let req: ExportAllPowerGradeLutsRequest = serde_json::from_value(args)?;
             let response = bridge
                 .call_api(
-                    "export_lut",
+                    "export_all_power_grade_luts",
                     serde_json::json!({
-                        "clip_name": req.clip_name,
-                        "export_path": req.export_path,
-                        "lut_format": req.lut_format,
-                        "lut_size": req.lut_size
+                        "export_dir": req.export_dir
                     }),
                 )
                 .await?;
             Ok(response["result"].as_str().unwrap_or("Success").to_string())
         }
 
-        // ---- Timeline Item Operations Request Types (Phase 4 Week 1) ----
-        "set_timeline_item_transform" => {
-            let req: SetTimelineItemTransformRequest = serde_json::from_value(args)?;
+        // ---- NEW: Layout and Interface Management ----
+        "save_layout_preset" => {
+            let req: SaveLayoutPresetRequest = serde_json::from_value(args)?;
             let response = bridge
                 .call_api(
-                    "set_timeline_item_transform",
+                    "save_layout_preset",
                     serde_json::json!({
-                        "timeline_item_id": req.timeline_item_id,
-                        "property_name": req.property_name,
-                        "property_value": req.property_value
+                        "preset_name": req.preset_name
                     }),
                 )
                 .await?;
             Ok(response["result"].as_str().unwrap_or("Success").to_string())
         }
-        "set_timeline_item_crop" => {
-            let req: SetTimelineItemCropRequest = serde_json::from_value(args)?;
+        "load_layout_preset" => {
+            let req: LoadLayoutPresetRequest = serde_json::from_value(args)?;
             let response = bridge
                 .call_api(
-                    "set_timeline_item_crop",
+                    "load_layout_preset",
                     serde_json::json!({
-                        "timeline_item_id": req.timeline_item_id,
-                        "crop_type": req.crop_type,
-                        "crop_value": req.crop_value
+                        "preset_name": req.preset_name
                     }),
                 )
                 .await?;
             Ok(response["result"].as_str().unwrap_or("Success").to_string())
         }
-        "set_timeline_item_composite" => {
-            let req: SetTimelineItemCompositeRequest = serde_json::from_value(args)?;
+        "export_layout_preset" => {
+            let req: ExportLayoutPresetRequest = serde_json::from_value(args)?;
             let response = bridge
                 .call_api(
-                    "set_timeline_item_composite",
+                    "export_layout_preset",
                     serde_json::json!({
-                        "timeline_item_id": req.timeline_item_id,
-                        "composite_mode": req.composite_mode,
-                        "opacity": req.opacity
+                        "preset_name": req.preset_name,
+                        "export_path": req.export_path
                     }),
                 )
                 .await?;
             Ok(response["result"].as_str().unwrap_or("Success").to_string())
         }
-        "set_timeline_item_retime" => {
-            let req: SetTimelineItemRetimeRequest = serde_json::from_value(args)?;
+        "import_layout_preset" => {
+            let req: ImportLayoutPresetRequest = serde_json::from_value(args)?;
             let response = bridge
                 .call_api(
-                    "set_timeline_item_retime",
+                    "import_layout_preset",
                     serde_json::json!({
-                        "timeline_item_id": req.timeline_item_id,
-                        "speed": req.speed,
-                        "process": req.process
+                        "import_path": req.import_path,
+                        "preset_name": req.preset_name
                     }),
                 )
                 .await?;
             Ok(response["result"].as_str().unwrap_or("Success").to_string())
         }
-        "set_timeline_item_stabilization" => {
-            let req: SetTimelineItemStabilizationRequest = serde_json::from_value(args)?;
+        "delete_layout_preset" => {
+            let req: DeleteLayoutPresetRequest = serde_json::from_value(args)?;
             let response = bridge
                 .call_api(
-                    "set_timeline_item_stabilization",
+                    "delete_layout_preset",
                     serde_json::json!({
-                        "timeline_item_id": req.timeline_item_id,
-                        "enabled": req.enabled,
-                        "method": req.method,
-                        "strength": req.strength
+                        "preset_name": req.preset_name
                     }),
                 )
                 .await?;
             Ok(response["result"].as_str().unwrap_or("Success").to_string())
         }
-        "set_timeline_item_audio" => {
-            let req: SetTimelineItemAudioRequest = serde_json::from_value(args)?;
+
+        // ---- NEW: Application Control ----
+        "quit_app" => {
+            let req: QuitAppRequest = serde_json::from_value(args)?;
             let response = bridge
                 .call_api(
-                    "set_timeline_item_audio",
+                    "quit_app",
                     serde_json::json!({
-                        "timeline_item_id": req.timeline_item_id,
-                        "volume": req.volume,
-                        "pan": req.pan,
-                        "eq_enabled": req.eq_enabled
+                        "force": req.force,
+                        "save_project": req.save_project
                     }),
                 )
                 .await?;
             Ok(response["result"].as_str().unwrap_or("Success").to_string())
         }
-        "get_timeline_item_properties" => {
-            let req: GetTimelineItemPropertiesRequest = serde_json::from_value(args)?;
+        "restart_app" => {
+            let req: RestartAppRequest = serde_json::from_value(args)?;
             let response = bridge
                 .call_api(
-                    "get_timeline_item_properties",
+                    "restart_app",
                     serde_json::json!({
-                        "timeline_item_id": req.timeline_item_id
+                        "wait_seconds": req.wait_seconds
                     }),
                 )
                 .await?;
             Ok(response["result"].as_str().unwrap_or("Success").to_string())
         }
-        "reset_timeline_item_properties" => {
-            let req: ResetTimelineItemPropertiesRequest = serde_json::from_value(args)?;
+        "open_settings" => {
             let response = bridge
-                .call_api(
-                    "reset_timeline_item_properties",
-                    serde_json::json!({
-                        "timeline_item_id": req.timeline_item_id,
-                        "property_type": req.property_type
-                    }),
-                )
+                .call_api("open_settings", serde_json::json!({}))
+                .await?;
+            Ok(response["result"].as_str().unwrap_or("Success").to_string())
+        }
+        "open_app_preferences" => {
+            let response = bridge
+                .call_api("open_app_preferences", serde_json::json!({}))
                 .await?;
             Ok(response["result"].as_str().unwrap_or("Success").to_string())
         }
 
-        // ---- Keyframe Animation Request Types (Phase 4 Week 2) ----
-        "add_keyframe" => {
-            let req: AddKeyframeRequest = serde_json::from_value(args)?;
+        // ---- NEW: Cloud Operations ----
+        "create_cloud_project" => {
+            let req: CreateCloudProjectRequest = serde_json::from_value(args)?;
             let response = bridge
                 .call_api(
-                    "add_keyframe",
+                    "create_cloud_project",
                     serde_json::json!({
-                        "timeline_item_id": req.timeline_item_id,
-                        "property_name": req.property_name,
-                        "frame": req.frame,
-                        "value": req.value
+                        "project_name": req.project_name,
+                        "folder_path": req.folder_path
                     }),
                 )
                 .await?;
             Ok(response["result"].as_str().unwrap_or("Success").to_string())
         }
-        "modify_keyframe" => {
-            let req: ModifyKeyframeRequest = serde_json::from_value(args)?;
+        "import_cloud_project" => {
+            let req: ImportCloudProjectRequest = serde_json::from_value(args)?;
             let response = bridge
                 .call_api(
-                    "modify_keyframe",
+                    "import_cloud_project",
                     serde_json::json!({
-                        "timeline_item_id": req.timeline_item_id,
-                        "property_name": req.property_name,
-                        "frame": req.frame,
-                        "new_value": req.new_value,
-                        "new_frame": req.new_frame
+                        "cloud_id": req.cloud_id,
+                        "project_name": req.project_name
                     }),
                 )
                 .await?;
             Ok(response["result"].as_str().unwrap_or("Success").to_string())
         }
-        "delete_keyframe" => {
-            let req: DeleteKeyframeRequest = serde_json::from_value(args)?;
+        "restore_cloud_project" => {
+            let req: RestoreCloudProjectRequest = serde_json::from_value(args)?;
             let response = bridge
                 .call_api(
-                    "delete_keyframe",
+                    "restore_cloud_project",
                     serde_json::json!({
-                        "timeline_item_id": req.timeline_item_id,
-                        "property_name": req.property_name,
-                        "frame": req.frame
+                        "cloud_id": req.cloud_id,
+                        "project_name": req.project_name
                     }),
                 )
                 .await?;
             Ok(response["result"].as_str().unwrap_or("Success").to_string())
         }
-        "set_keyframe_interpolation" => {
-            let req: SetKeyframeInterpolationRequest = serde_json::from_value(args)?;
+        "export_project_to_cloud" => {
+            let req: ExportProjectToCloudRequest = serde_json::from_value(args)?;
             let response = bridge
                 .call_api(
-                    "set_keyframe_interpolation",
+                    "export_project_to_cloud",
                     serde_json::json!({
-                        "timeline_item_id": req.timeline_item_id,
-                        "property_name": req.property_name,
-                        "frame": req.frame,
-                        "interpolation_type": req.interpolation_type
+                        "project_name": req.project_name
                     }),
                 )
                 .await?;
             Ok(response["result"].as_str().unwrap_or("Success").to_string())
         }
-        "enable_keyframes" => {
-            let req: EnableKeyframesRequest = serde_json::from_value(args)?;
+        "add_user_to_cloud_project" => {
+            let req: AddUserToCloudProjectRequest = serde_json::from_value(args)?;
             let response = bridge
                 .call_api(
-                    "enable_keyframes",
+                    "add_user_to_cloud_project",
                     serde_json::json!({
-                        "timeline_item_id": req.timeline_item_id,
-                        "keyframe_mode": req.keyframe_mode
+                        "cloud_id": req.cloud_id,
+                        "user_email": req.user_email,
+                        "permissions": req.permissions
                     }),
                 )
                 .await?;
             Ok(response["result"].as_str().unwrap_or("Success").to_string())
         }
-        "get_keyframes" => {
-            let req: GetKeyframesRequest = serde_json::from_value(args)?;
+        "remove_user_from_cloud_project" => {
+            let req: RemoveUserFromCloudProjectRequest = serde_json::from_value(args)?;
             let response = bridge
                 .call_api(
-                    "get_keyframes",
+                    "remove_user_from_cloud_project",
                     serde_json::json!({
-                        "timeline_item_id": req.timeline_item_id,
-                        "property_name": req.property_name
+                        "cloud_id": req.cloud_id,
+                        "user_email": req.user_email
                     }),
                 )
                 .await?;
             Ok(response["result"].as_str().unwrap_or("Success").to_string())
         }
 
-        // ---- Render and Delivery Operations (Phase 4 Week 3) ----
-        "add_to_render_queue" => {
-            let req: AddToRenderQueueRequest = serde_json::from_value(args)?;
+        // ---- NEW: Object Inspection ----
+        "object_help" => {
+            let req: ObjectHelpRequest = serde_json::from_value(args)?;
             let response = bridge
                 .call_api(
-                    "add_to_render_queue",
+                    "object_help",
                     serde_json::json!({
-                        "preset_name": req.preset_name,
-                        "timeline_name": req.timeline_name,
-                        "use_in_out_range": req.use_in_out_range
+                        "object_type": req.object_type
                     }),
                 )
                 .await?;
             Ok(response["result"].as_str().unwrap_or("Success").to_string())
         }
-        "start_render" => {
-            let response = bridge
-                .call_api("start_render", serde_json::json!({}))
-                .await?;
-            Ok(response["result"].as_str().unwrap_or("Success").to_string())
-        }
-        "clear_render_queue" => {
+        "inspect_custom_object" => {
+            let req: InspectCustomObjectRequest = serde_json::from_value(args)?;
             let response = bridge
-                .call_api("clear_render_queue", serde_json::json!({}))
+                .call_api(
+                    "inspect_custom_object",
+                    serde_json::json!({
+                        "object_path": req.object_path
+                    }),
+                )
                 .await?;
             Ok(response["result"].as_str().unwrap_or("Success").to_string())
         }
 
-        // ---- Project Management Operations ----
-        "save_project" => {
-            let response = bridge
-                .call_api("save_project", serde_json::json!({}))
-                .await?;
-            Ok(response["result"].as_str().unwrap_or("Success").to_string())
-        }
-        "close_project" => {
+        // ---- NEW: Project Properties ----
+        "set_project_property" => {
+            let req: SetProjectPropertyRequest = serde_json::from_value(args)?;
             let response = bridge
-                .call_api("close_project", serde_json::json!({}))
+                .call_api(
+                    "set_project_property",
+                    serde_json::json!({
+                        "property_name": req.property_name,
+                        "property_value": req.property_value
+                    }),
+                )
                 .await?;
             Ok(response["result"].as_str().unwrap_or("Success").to_string())
         }
-        "set_project_setting" => {
-            let req: SetProjectSettingRequest = serde_json::from_value(args)?;
+        "set_timeline_format" => {
+            let req: SetTimelineFormatRequest = serde_json::from_value(args)?;
             let response = bridge
                 .call_api(
-                    "set_project_setting",
+                    "set_timeline_format",
                     serde_json::json!({
-                        "setting_name": req.setting_name,
-                        "setting_value": req.setting_value
+                        "width": req.width,
+                        "height": req.height,
+                        "frame_rate": req.frame_rate,
+                        "interlaced": req.interlaced
                     }),
                 )
                 .await?;
             Ok(response["result"].as_str().unwrap_or("Success").to_string())
         }
 
-        // ---- Audio Transcription Operations ----
-        "transcribe_audio" => {
-            let req: TranscribeAudioRequest = serde_json::from_value(args)?;
+        // ---- NEW: Timeline Object API ----
+        "get_timeline_name" => {
+            let req: GetTimelineNameRequest = serde_json::from_value(args)?;
             let response = bridge
                 .call_api(
-                    "transcribe_audio",
+                    "get_timeline_name",
                     serde_json::json!({
-                        "clip_name": req.clip_name,
-                        "language": req.language
+                        "timeline_name": req.timeline_name
                     }),
                 )
                 .await?;
             Ok(response["result"].as_str().unwrap_or("Success").to_string())
         }
-        "clear_transcription" => {
-            let req: ClearTranscriptionRequest = serde_json::from_value(args)?;
+        "set_timeline_name" => {
+            let req: SetTimelineNameRequest = serde_json::from_value(args)?;
             let response = bridge
                 .call_api(
-                    "clear_transcription",
+                    "set_timeline_name",
                     serde_json::json!({
-                        "clip_name": req.clip_name
+                        "timeline_name": req.timeline_name,
+                        "new_name": req.new_name
                     }),
                 )
                 .await?;
             Ok(response["result"].as_str().unwrap_or("Success").to_string())
         }
-
-        // ---- Phase 4 Week 3: Rendering & Delivery Operations ----
-        "get_render_status" => {
-            let response = bridge
-                .call_api("get_render_status", serde_json::json!({}))
-                .await?;
-            Ok(response["result"].as_str().unwrap_or("Success").to_string())
-        }
-        "export_project" => {
-            let req: ExportProjectRequest = serde_json::from_value(args)?;
+        "get_timeline_frames" => {
+            let req: GetTimelineFramesRequest = serde_json::from_value(args)?;
             let response = bridge
                 .call_api(
-                    "export_project",
+                    "get_timeline_frames",
                     serde_json::json!({
-                        "export_path": req.export_path,
-                        "include_media": req.include_media,
-                        "project_name": req.project_name
+                        "timeline_name": req.timeline_name
                     }),
                 )
                 .await?;
             Ok(response["result"].as_str().unwrap_or("Success").to_string())
         }
-        "create_render_preset" => {
-            let req: CreateRenderPresetRequest = serde_json::from_value(args)?;
+        "set_timeline_timecode" => {
+            let req: SetTimelineTimecodeRequest = serde_json::from_value(args)?;
             let response = bridge
                 .call_api(
-                    "create_render_preset",
+                    "set_timeline_timecode",
                     serde_json::json!({
-                        "preset_name": req.preset_name,
-                        "format": req.format,
-                        "codec": req.codec,
-                        "resolution_width": req.resolution_width,
-                        "resolution_height": req.resolution_height,
-                        "frame_rate": req.frame_rate,
-                        "quality": req.quality,
-                        "audio_codec": req.audio_codec,
-                        "audio_bitrate": req.audio_bitrate
+                        "timeline_name": req.timeline_name,
+                        "timecode": req.timecode,
+                        "timeline_start_timecode": req.timeline_start_timecode
                     }),
                 )
-                .await?;
-            // Return full response for create_render_preset to include resolution details
-            Ok(response.to_string())
+                .await?;
+            Ok(response["result"].as_str().unwrap_or("Success").to_string())
         }
-
-        // ---- NEW: Extended Project Management Operations ----
-        "delete_media" => {
-            let req: DeleteMediaRequest = serde_json::from_value(args)?;
+        "get_timeline_track_count" => {
+            let req: GetTimelineTrackCountRequest = serde_json::from_value(args)?;
             let response = bridge
                 .call_api(
-                    "delete_media",
+                    "get_timeline_track_count",
                     serde_json::json!({
-                        "clip_name": req.clip_name
+                        "timeline_name": req.timeline_name,
+                        "track_type": req.track_type
                     }),
                 )
                 .await?;
             Ok(response["result"].as_str().unwrap_or("Success").to_string())
         }
-        "move_media_to_bin" => {
-            let req: MoveMediaToBinRequest = serde_json::from_value(args)?;
+        "get_timeline_items_in_track" => {
+            let req: GetTimelineItemsInTrackRequest = serde_json::from_value(args)?;
             let response = bridge
                 .call_api(
-                    "move_media_to_bin",
+                    "get_timeline_items_in_track",
                     serde_json::json!({
-                        "clip_name": req.clip_name,
-                        "bin_name": req.bin_name
+                        "timeline_name": req.timeline_name,
+                        "track_type": req.track_type,
+                        "track_index": req.track_index
                     }),
                 )
                 .await?;
             Ok(response["result"].as_str().unwrap_or("Success").to_string())
         }
-        "export_folder" => {
-            let req: ExportFolderRequest = serde_json::from_value(args)?;
+        "add_timeline_marker" => {
+            let req: AddTimelineMarkerRequest = serde_json::from_value(args)?;
             let response = bridge
                 .call_api(
-                    "export_folder",
+                    "add_timeline_marker",
                     serde_json::json!({
-                        "folder_name": req.folder_name,
-                        "export_path": req.export_path,
-                        "export_type": req.export_type
+                        "timeline_name": req.timeline_name,
+                        "frame_id": req.frame_id,
+                        "color": req.color,
+                        "name": req.name,
+                        "note": req.note,
+                        "duration": req.duration,
+                        "custom_data": req.custom_data
                     }),
                 )
                 .await?;
             Ok(response["result"].as_str().unwrap_or("Success").to_string())
         }
-        "transcribe_folder_audio" => {
-            let req: TranscribeFolderAudioRequest = serde_json::from_value(args)?;
+        "get_timeline_markers" => {
+            let req: GetTimelineMarkersRequest = serde_json::from_value(args)?;
             let response = bridge
                 .call_api(
-                    "transcribe_folder_audio",
+                    "get_timeline_markers",
                     serde_json::json!({
-                        "folder_name": req.folder_name,
-                        "language": req.language
+                        "timeline_name": req.timeline_name
                     }),
                 )
                 .await?;
             Ok(response["result"].as_str().unwrap_or("Success").to_string())
         }
-        "clear_folder_transcription" => {
-            let req: ClearFolderTranscriptionRequest = serde_json::from_value(args)?;
+        "delete_timeline_marker" => {
+            let req: DeleteTimelineMarkerRequest = serde_json::from_value(args)?;
             let response = bridge
                 .call_api(
-                    "clear_folder_transcription",
+                    "delete_timeline_marker",
                     serde_json::json!({
-                        "folder_name": req.folder_name
+                        "timeline_name": req.timeline_name,
+                        "frame_num": req.frame_num,
+                        "color": req.color,
+                        "custom_data": req.custom_data
                     }),
                 )
                 .await?;
             Ok(response["result"].as_str().unwrap_or("Success").to_string())
         }
-
-        // ---- NEW: Cache and Optimization Operations ----
-        "set_cache_mode" => {
-            let req: SetCacheModeRequest = serde_json::from_value(args)?;
+        "duplicate_timeline" => {
+            let req: DuplicateTimelineRequest = serde_json::from_value(args)?;
             let response = bridge
                 .call_api(
-                    "set_cache_mode",
+                    "duplicate_timeline",
                     serde_json::json!({
-                        "mode": req.mode
+                        "source_timeline_name": req.source_timeline_name,
+                        "new_timeline_name": req.new_timeline_name
                     }),
                 )
                 .await?;
             Ok(response["result"].as_str().unwrap_or("Success").to_string())
         }
-        "set_optimized_media_mode" => {
-            let req: SetOptimizedMediaModeRequest = serde_json::from_value(args)?;
+        "create_compound_clip" => {
+            let req: CreateCompoundClipRequest = serde_json::from_value(args)?;
             let response = bridge
                 .call_api(
-                    "set_optimized_media_mode",
+                    "create_compound_clip",
                     serde_json::json!({
-                        "mode": req.mode
+                        "timeline_name": req.timeline_name,
+                        "timeline_item_ids": req.timeline_item_ids,
+                        "clip_name": req.clip_name
                     }),
                 )
                 .await?;
             Ok(response["result"].as_str().unwrap_or("Success").to_string())
         }
-        "set_proxy_mode" => {
-            let req: SetProxyModeRequest = serde_json::from_value(args)?;
+        "create_fusion_clip" => {
+            let req: CreateFusionClipRequest = serde_json::from_value(args)?;
             let response = bridge
                 .call_api(
-                    "set_proxy_mode",
+                    "create_fusion_clip",
                     serde_json::json!({
-                        "mode": req.mode
+                        "timeline_name": req.timeline_name,
+                        "timeline_item_ids": req.timeline_item_ids
                     }),
                 )
                 .await?;
             Ok(response["result"].as_str().unwrap_or("Success").to_string())
         }
-        "set_proxy_quality" => {
-            let req: SetProxyQualityRequest = serde_json::from_value(args)?;
+        "export_timeline" => {
+            let req: ExportTimelineRequest = serde_json::from_value(args)?;
             let response = bridge
                 .call_api(
-                    "set_proxy_quality",
+                    "export_timeline",
                     serde_json::json!({
-                        "quality": req.quality
+                        "timeline_name": req.timeline_name,
+                        "file_name": req.file_name,
+                        "export_type": req.export_type,
+                        "export_subtype": req.export_subtype
                     }),
                 )
                 .await?;
             Ok(response["result"].as_str().unwrap_or("Success").to_string())
         }
-        "set_cache_path" => {
-            let req: SetCachePathRequest = serde_json::from_value(args)?;
+        "insert_generator" => {
+            let req: InsertGeneratorRequest = serde_json::from_value(args)?;
             let response = bridge
                 .call_api(
-                    "set_cache_path",
+                    "insert_generator",
                     serde_json::json!({
-                        "path_type": req.path_type,
-                        "path": req.path
+                        "timeline_name": req.timeline_name,
+                        "generator_name": req.generator_name,
+                        "generator_type": req.generator_type
                     }),
                 )
                 .await?;
             Ok(response["result"].as_str().unwrap_or("Success").to_string())
         }
-        "generate_optimized_media" => {
-            let req: GenerateOptimizedMediaRequest = serde_json::from_value(args)?;
+        "insert_title" => {
+            let req: InsertTitleRequest = serde_json::from_value(args)?;
             let response = bridge
                 .call_api(
-                    "generate_optimized_media",
+                    "insert_title",
                     serde_json::json!({
-                        "clip_names": req.clip_names
+                        "timeline_name": req.timeline_name,
+                        "title_name": req.title_name,
+                        "title_type": req.title_type
                     }),
                 )
                 .await?;
             Ok(response["result"].as_str().unwrap_or("Success").to_string())
         }
-        "delete_optimized_media" => {
-            let req: DeleteOptimizedMediaRequest = serde_json::from_value(args)?;
+        "grab_still" => {
+            let req: GrabStillRequest = serde_json::from_value(args)?;
             let response = bridge
                 .call_api(
-                    "delete_optimized_media",
+                    "grab_still",
                     serde_json::json!({
-                        "clip_names": req.clip_names
+                        "timeline_name": req.timeline_name,
+                        "still_frame_source": req.still_frame_source,
+                        "grab_all": req.grab_all
                     }),
                 )
                 .await?;
             Ok(response["result"].as_str().unwrap_or("Success").to_string())
         }
 
-        // ---- NEW: Extended Color Operations ----
-        "create_color_preset_album" => {
-            let req: CreateColorPresetAlbumRequest = serde_json::from_value(args)?;
+        // ---- Missing Tools Implementation ----
+        "get_media_pool_item_name" => {
+            let req: GetMediaPoolItemNameRequest = serde_json::from_value(args)?;
             let response = bridge
                 .call_api(
-                    "create_color_preset_album",
+                    "get_media_pool_item_name",
                     serde_json::json!({
-                        "album_name": req.album_name
+                        "clip_name": req.clip_name
                     }),
                 )
                 .await?;
             Ok(response["result"].as_str().unwrap_or("Success").to_string())
         }
-        "delete_color_preset_album" => {
-            let req: DeleteColorPresetAlbumRequest = serde_json::from_value(args)?;
+        "get_project_timeline_count" => {
+            let _req: GetProjectTimelineCountRequest = serde_json::from_value(args)?;
+            let response = bridge
+                .call_api("get_project_timeline_count", serde_json::json!({}))
+                .await?;
+            Ok(response["result"].as_str().unwrap_or("Success").to_string())
+        }
+        "get_media_pool_root_folder" => {
+            let _req: GetMediaPoolRootFolderRequest = serde_json::from_value(args)?;
+            let response = bridge
+                .call_api("get_media_pool_root_folder", serde_json::json!({}))
+                .await?;
+            Ok(response["result"].as_str().unwrap_or("Success").to_string())
+        }
+        "get_gallery_still_albums" => {
+            let _req: GetGalleryStillAlbumsRequest = serde_json::from_value(args)?;
+            let response = bridge
+                .call_api("get_gallery_still_albums", serde_json::json!({}))
+                .await?;
+            Ok(serde_json::to_string_pretty(&response)?)
+        }
+        "get_fusion_tool_list" => {
+            let req: GetFusionToolListRequest = serde_json::from_value(args)?;
             let response = bridge
                 .call_api(
-                    "delete_color_preset_album",
+                    "get_fusion_tool_list",
                     serde_json::json!({
-                        "album_name": req.album_name
+                        "selected_only": req.selected_only,
+                        "tool_type": req.tool_type
                     }),
                 )
                 .await?;
             Ok(response["result"].as_str().unwrap_or("Success").to_string())
         }
-        "export_all_power_grade_luts" => {
-            let req: ExportAllPowerGradeLutsRequest = serde_json::from_value(args)?;
+        "get_audio_track_count" => {
+            let _req: GetAudioTrackCountRequest = serde_json::from_value(args)?;
+            let response = bridge
+                .call_api("get_audio_track_count", serde_json::json!({}))
+                .await?;
+            Ok(response["result"].as_str().unwrap_or("Success").to_string())
+        }
+
+        // ---- MediaPoolItem API Tools ----
+        "set_media_pool_item_name" => {
+            let req: SetMediaPoolItemNameRequest = serde_json::from_value(args)?;
             let response = bridge
                 .call_api(
-                    "export_all_power_grade_luts",
+                    "set_media_pool_item_name",
                     serde_json::json!({
-                        "export_dir": req.export_dir
+                        "clip_name": req.clip_name,
+                        "new_name": req.new_name
                     }),
                 )
                 .await?;
             Ok(response["result"].as_str().unwrap_or("Success").to_string())
         }
-
-        // ---- NEW: Layout and Interface Management ----
-        "save_layout_preset" => {
-            let req: SaveLayoutPresetRequest = serde_json::from_value(args)?;
+        "get_media_pool_item_property" => {
+            let req: GetMediaPoolItemPropertyRequest = serde_json::from_value(args)?;
             let response = bridge
                 .call_api(
-                    "save_layout_preset",
+                    "get_media_pool_item_property",
                     serde_json::json!({
-                        "preset_name": req.preset_name
+                        "clip_name": req.clip_name,
+                        "property_key": req.property_key
                     }),
                 )
                 .await?;
             Ok(response["result"].as_str().unwrap_or("Success").to_string())
         }
-        "load_layout_preset" => {
-            let req: LoadLayoutPresetRequest = serde_json::from_value(args)?;
+        "set_media_pool_item_property" => {
+            let req: SetMediaPoolItemPropertyRequest = serde_json::from_value(args)?;
             let response = bridge
                 .call_api(
-                    "load_layout_preset",
+                    "set_media_pool_item_property",
                     serde_json::json!({
-                        "preset_name": req.preset_name
+                        "clip_name": req.clip_name,
+                        "property_key": req.property_key,
+                        "property_value": req.property_value
                     }),
                 )
                 .await?;
             Ok(response["result"].as_str().unwrap_or("Success").to_string())
         }
-        "export_layout_preset" => {
-            let req: ExportLayoutPresetRequest = serde_json::from_value(args)?;
+        "get_media_pool_item_metadata" => {
+            let req: GetMediaPoolItemMetadataRequest = serde_json::from_value(args)?;
             let response = bridge
                 .call_api(
-                    "export_layout_preset",
+                    "get_media_pool_item_metadata",
                     serde_json::json!({
-                        "preset_name": req.preset_name,
-                        "export_path": req.export_path
+                        "clip_name": req.clip_name,
+                        "metadata_type": req.metadata_type
                     }),
                 )
                 .await?;
             Ok(response["result"].as_str().unwrap_or("Success").to_string())
         }
-        "import_layout_preset" => {
-            let req: ImportLayoutPresetRequest = serde_json::from_value(args)?;
+        "set_media_pool_item_metadata" => {
+            let req: SetMediaPoolItemMetadataRequest = serde_json::from_value(args)?;
             let response = bridge
                 .call_api(
-                    "import_layout_preset",
+                    "set_media_pool_item_metadata",
                     serde_json::json!({
-                        "import_path": req.import_path,
-                        "preset_name": req.preset_name
+                        "clip_name": req.clip_name,
+                        "metadata_type": req.metadata_type,
+                        "metadata_value": req.metadata_value
                     }),
                 )
                 .await?;
             Ok(response["result"].as_str().unwrap_or("Success").to_string())
         }
-        "delete_layout_preset" => {
-            let req: DeleteLayoutPresetRequest = serde_json::from_value(args)?;
+        "get_media_pool_item_markers" => {
+            let req: GetMediaPoolItemMarkersRequest = serde_json::from_value(args)?;
             let response = bridge
                 .call_api(
-                    "delete_layout_preset",
+                    "get_media_pool_item_markers",
                     serde_json::json!({
-                        "preset_name": req.preset_name
+                        "clip_name": req.clip_name
                     }),
                 )
                 .await?;
             Ok(response["result"].as_str().unwrap_or("Success").to_string())
         }
-
-        // ---- NEW: Application Control ----
-        "quit_app" => {
-            let req: QuitAppRequest = serde_json::from_value(args)?;
+        "add_media_pool_item_marker" => {
+            let req: AddMediaPoolItemMarkerRequest = serde_json::from_value(args)?;
             let response = bridge
                 .call_api(
-                    "quit_app",
+                    "add_media_pool_item_marker",
                     serde_json::json!({
-                        "force": req.force,
-                        "save_project": req.save_project
+                        "clip_name": req.clip_name,
+                        "frame_id": req.frame_id,
+                        "color": req.color,
+                        "name": req.name,
+                        "note": req.note,
+                        "duration": req.duration,
+                        "custom_data": req.custom_data
                     }),
                 )
                 .await?;
             Ok(response["result"].as_str().unwrap_or("Success").to_string())
         }
-        "restart_app" => {
-            let req: RestartAppRequest = serde_json::from_value(args)?;
+        "get_media_pool_item_flag_list" => {
+            let req: GetMediaPoolItemFlagListRequest = serde_json::from_value(args)?;
             let response = bridge
                 .call_api(
-                    "restart_app",
+                    "get_media_pool_item_flag_list",
                     serde_json::json!({
-                        "wait_seconds": req.wait_seconds
+                        "clip_name": req.clip_name
                     }),
                 )
                 .await?;
             Ok(response["result"].as_str().unwrap_or("Success").to_string())
         }
-        "open_settings" => {
-            let response = bridge
-                .call_api("open_settings", serde_json::json!({}))
-                .await?;
-            Ok(response["result"].as_str().unwrap_or("Success").to_string())
-        }
-        "open_app_preferences" => {
-            let response = bridge
-                .call_api("open_app_preferences", serde_json::json!({}))
-                .await?;
-            Ok(response["result"].as_str().unwrap_or("Success").to_string())
-        }
-
-        // ---- NEW: Cloud Operations ----
-        "create_cloud_project" => {
-            let req: CreateCloudProjectRequest = serde_json::from_value(args)?;
+        "add_media_pool_item_flag" => {
+            let req: AddMediaPoolItemFlagRequest = serde_json::from_value(args)?;
             let response = bridge
                 .call_api(
-                    "create_cloud_project",
+                    "add_media_pool_item_flag",
                     serde_json::json!({
-                        "project_name": req.project_name,
-                        "folder_path": req.folder_path
+                        "clip_name": req.clip_name,
+                        "color": req.color
                     }),
                 )
                 .await?;
             Ok(response["result"].as_str().unwrap_or("Success").to_string())
         }
-        "import_cloud_project" => {
-            let req: ImportCloudProjectRequest = serde_json::from_value(args)?;
+        "get_media_pool_item_clip_color" => {
+            let req: GetMediaPoolItemClipColorRequest = serde_json::from_value(args)?;
             let response = bridge
                 .call_api(
-                    "import_cloud_project",
+                    "get_media_pool_item_clip_color",
                     serde_json::json!({
-                        "cloud_id": req.cloud_id,
-                        "project_name": req.project_name
+                        "clip_name": req.clip_name
                     }),
                 )
                 .await?;
             Ok(response["result"].as_str().unwrap_or("Success").to_string())
         }
-        "restore_cloud_project" => {
-            let req: RestoreCloudProjectRequest = serde_json::from_value(args)?;
+        "set_media_pool_item_clip_color" => {
+            let req: SetMediaPoolItemClipColorRequest = serde_json::from_value(args)?;
             let response = bridge
                 .call_api(
-                    "restore_cloud_project",
+                    "set_media_pool_item_clip_color",
                     serde_json::json!({
-                        "cloud_id": req.cloud_id,
-                        "project_name": req.project_name
+                        "clip_name": req.clip_name,
+                        "color_name": req.color_name
                     }),
                 )
                 .await?;
             Ok(response["result"].as_str().unwrap_or("Success").to_string())
         }
-        "export_project_to_cloud" => {
-            let req: ExportProjectToCloudRequest = serde_json::from_value(args)?;
+        "link_media_pool_item_proxy_media" => {
+            let req: LinkMediaPoolItemProxyMediaRequest = serde_json::from_value(args)?;
             let response = bridge
                 .call_api(
-                    "export_project_to_cloud",
+                    "link_media_pool_item_proxy_media",
                     serde_json::json!({
-                        "project_name": req.project_name
+                        "clip_name": req.clip_name,
+                        "proxy_media_file_path": req.proxy_media_file_path
                     }),
                 )
                 .await?;
             Ok(response["result"].as_str().unwrap_or("Success").to_string())
         }
-        "add_user_to_cloud_project" => {
-            let req: AddUserToCloudProjectRequest = serde_json::from_value(args)?;
+        "unlink_media_pool_item_proxy_media" => {
+            let req: UnlinkMediaPoolItemProxyMediaRequest = serde_json::from_value(args)?;
             let response = bridge
                 .call_api(
-                    "add_user_to_cloud_project",
+                    "unlink_media_pool_item_proxy_media",
                     serde_json::json!({
-                        "cloud_id": req.cloud_id,
-                        "user_email": req.user_email,
-                        "permissions": req.permissions
+                        "clip_name": req.clip_name
                     }),
                 )
                 .await?;
             Ok(response["result"].as_str().unwrap_or("Success").to_string())
         }
-        "remove_user_from_cloud_project" => {
-            let req: RemoveUserFromCloudProjectRequest = serde_json::from_value(args)?;
+        "transcribe_media_pool_item_audio" => {
+            let req: TranscribeMediaPoolItemAudioRequest = serde_json::from_value(args)?;
             let response = bridge
                 .call_api(
-                    "remove_user_from_cloud_project",
+                    "transcribe_media_pool_item_audio",
                     serde_json::json!({
-                        "cloud_id": req.cloud_id,
-                        "user_email": req.user_email
+                        "clip_name": req.clip_name,
+                        "language": req.language
                     }),
                 )
                 .await?;
             Ok(response["result"].as_str().unwrap_or("Success").to_string())
         }
-
-        // ---- NEW: Object Inspection ----
-        "object_help" => {
-            let req: ObjectHelpRequest = serde_json::from_value(args)?;
+        "clear_media_pool_item_transcription" => {
+            let req: ClearMediaPoolItemTranscriptionRequest = serde_json::from_value(args)?;
             let response = bridge
                 .call_api(
-                    "object_help",
+                    "clear_media_pool_item_transcription",
                     serde_json::json!({
-                        "object_type": req.object_type
+                        "clip_name": req.clip_name
                     }),
                 )
                 .await?;
             Ok(response["result"].as_str().unwrap_or("Success").to_string())
         }
-        "inspect_custom_object" => {
-            let req: InspectCustomObjectRequest = serde_json::from_value(args)?;
+
+        // ============================================
+        // MISSING TOOLS IMPLEMENTATION - PHASE 3
+        // ============================================
+        "add_fusion_tool" => {
+            let req: AddFusionToolRequest = serde_json::from_value(args)?;
             let response = bridge
                 .call_api(
-                    "inspect_custom_object",
+                    "add_fusion_tool",
                     serde_json::json!({
-                        "object_path": req.object_path
+                        "tool_name": req.tool_name,
+                        "x": req.x,
+                        "y": req.y
                     }),
                 )
                 .await?;
             Ok(response["result"].as_str().unwrap_or("Success").to_string())
         }
-
-        // ---- NEW: Project Properties ----
-        "set_project_property" => {
-            let req: SetProjectPropertyRequest = serde_json::from_value(args)?;
+        "get_audio_track_name" => {
+            let req: GetAudioTrackNameRequest = serde_json::from_value(args)?;
             let response = bridge
                 .call_api(
-                    "set_project_property",
+                    "get_audio_track_name",
                     serde_json::json!({
-                        "property_name": req.property_name,
-                        "property_value": req.property_value
+                        "track_index": req.track_index
                     }),
                 )
                 .await?;
             Ok(response["result"].as_str().unwrap_or("Success").to_string())
         }
-        "set_timeline_format" => {
-            let req: SetTimelineFormatRequest = serde_json::from_value(args)?;
+        "set_audio_track_name" => {
+            let req: SetAudioTrackNameRequest = serde_json::from_value(args)?;
             let response = bridge
                 .call_api(
-                    "set_timeline_format",
+                    "set_audio_track_name",
                     serde_json::json!({
-                        "width": req.width,
-                        "height": req.height,
-                        "frame_rate": req.frame_rate,
-                        "interlaced": req.interlaced
+                        "track_index": req.track_index,
+                        "track_name": req.track_name
                     }),
                 )
                 .await?;
             Ok(response["result"].as_str().unwrap_or("Success").to_string())
         }
-
-        // ---- NEW: Timeline Object API ----
-        "get_timeline_name" => {
-            let req: GetTimelineNameRequest = serde_json::from_value(args)?;
+        "add_gallery_still_album" => {
+            let req: AddGalleryStillAlbumRequest = serde_json::from_value(args)?;
             let response = bridge
                 .call_api(
-                    "get_timeline_name",
+                    "add_gallery_still_album",
                     serde_json::json!({
-                        "timeline_name": req.timeline_name
+                        "album_name": req.album_name
                     }),
                 )
                 .await?;
             Ok(response["result"].as_str().unwrap_or("Success").to_string())
         }
-        "set_timeline_name" => {
-            let req: SetTimelineNameRequest = serde_json::from_value(args)?;
+        "rename_gallery_still_album" => {
+            let req: RenameGalleryStillAlbumRequest = serde_json::from_value(args)?;
             let response = bridge
                 .call_api(
-                    "set_timeline_name",
+                    "rename_gallery_still_album",
                     serde_json::json!({
-                        "timeline_name": req.timeline_name,
+                        "album_name": req.album_name,
                         "new_name": req.new_name
                     }),
                 )
                 .await?;
             Ok(response["result"].as_str().unwrap_or("Success").to_string())
         }
-        "get_timeline_frames" => {
-            let req: GetTimelineFramesRequest = serde_json::from_value(args)?;
+        "set_current_still_album" => {
+            let req: SetCurrentStillAlbumRequest = serde_json::from_value(args)?;
             let response = bridge
                 .call_api(
-                    "get_timeline_frames",
+                    "set_current_still_album",
                     serde_json::json!({
-                        "timeline_name": req.timeline_name
+                        "album_name": req.album_name
                     }),
                 )
                 .await?;
             Ok(response["result"].as_str().unwrap_or("Success").to_string())
         }
-        "set_timeline_timecode" => {
-            let req: SetTimelineTimecodeRequest = serde_json::from_value(args)?;
+        "get_current_still_album" => {
+            let _req: GetCurrentStillAlbumRequest = serde_json::from_value(args)?;
             let response = bridge
-                .call_api(
-                    "set_timeline_timecode",
-                    serde_json::json!({
-                        "timeline_name": req.timeline_name,
-                        "timecode": req.timecode
-                    }),
-                )
+                .call_api("get_current_still_album", serde_json::json!({}))
                 .await?;
-            Ok(response["result"].as_str().unwrap_or("Success").to_string())
+            Ok(serde_json::to_string_pretty(&response)?)
         }
-        "get_timeline_track_count" => {
-            let req: GetTimelineTrackCountRequest = serde_json::from_value(args)?;
+        "get_album_stills" => {
+            let req: GetAlbumStillsRequest = serde_json::from_value(args)?;
             let response = bridge
                 .call_api(
-                    "get_timeline_track_count",
+                    "get_album_stills",
                     serde_json::json!({
-                        "timeline_name": req.timeline_name,
-                        "track_type": req.track_type
+                        "album_name": req.album_name
                     }),
                 )
                 .await?;
-            Ok(response["result"].as_str().unwrap_or("Success").to_string())
+            Ok(serde_json::to_string_pretty(&response)?)
         }
-        "get_timeline_items_in_track" => {
-            let req: GetTimelineItemsInTrackRequest = serde_json::from_value(args)?;
+        "move_gallery_stills" => {
+            let req: TransferGalleryStillsRequest = serde_json::from_value(args)?;
             let response = bridge
                 .call_api(
-                    "get_timeline_items_in_track",
+                    "move_gallery_stills",
                     serde_json::json!({
-                        "timeline_name": req.timeline_name,
-                        "track_type": req.track_type,
-                        "track_index": req.track_index
+                        "still_ids": req.still_ids,
+                        "source_album": req.source_album,
+                        "target_album": req.target_album
                     }),
                 )
                 .await?;
-            Ok(response["result"].as_str().unwrap_or("Success").to_string())
+            Ok(serde_json::to_string_pretty(&response)?)
         }
-        "add_timeline_marker" => {
-            let req: AddTimelineMarkerRequest = serde_json::from_value(args)?;
+        "copy_gallery_stills" => {
+            let req: TransferGalleryStillsRequest = serde_json::from_value(args)?;
             let response = bridge
                 .call_api(
-                    "add_timeline_marker",
+                    "copy_gallery_stills",
                     serde_json::json!({
-                        "timeline_name": req.timeline_name,
-                        "frame_id": req.frame_id,
-                        "color": req.color,
-                        "name": req.name,
-                        "note": req.note,
-                        "duration": req.duration,
-                        "custom_data": req.custom_data
+                        "still_ids": req.still_ids,
+                        "source_album": req.source_album,
+                        "target_album": req.target_album
                     }),
                 )
                 .await?;
-            Ok(response["result"].as_str().unwrap_or("Success").to_string())
+            Ok(serde_json::to_string_pretty(&response)?)
         }
-        "get_timeline_markers" => {
-            let req: GetTimelineMarkersRequest = serde_json::from_value(args)?;
+        "get_resource_usage" => {
+            let _req: GetResourceUsageRequest = serde_json::from_value(args)?;
+            let response = bridge
+                .call_api("get_resource_usage", serde_json::json!({}))
+                .await?;
+            Ok(serde_json::to_string_pretty(&response)?)
+        }
+        "import_markers" => {
+            let req: ImportMarkersRequest = serde_json::from_value(args)?;
             let response = bridge
                 .call_api(
-                    "get_timeline_markers",
+                    "import_markers",
                     serde_json::json!({
-                        "timeline_name": req.timeline_name
+                        "format": req.format,
+                        "data": req.data,
+                        "timeline_name": req.timeline_name,
+                        "timeline_start_timecode": req.timeline_start_timecode
                     }),
                 )
                 .await?;
-            Ok(response["result"].as_str().unwrap_or("Success").to_string())
+            Ok(serde_json::to_string_pretty(&response)?)
         }
-        "delete_timeline_marker" => {
-            let req: DeleteTimelineMarkerRequest = serde_json::from_value(args)?;
+        "export_markers" => {
+            let req: ExportMarkersRequest = serde_json::from_value(args)?;
             let response = bridge
                 .call_api(
-                    "delete_timeline_marker",
+                    "export_markers",
                     serde_json::json!({
+                        "format": req.format,
                         "timeline_name": req.timeline_name,
-                        "frame_num": req.frame_num,
-                        "color": req.color,
-                        "custom_data": req.custom_data
+                        "timeline_start_timecode": req.timeline_start_timecode
                     }),
                 )
                 .await?;
-            Ok(response["result"].as_str().unwrap_or("Success").to_string())
+            Ok(serde_json::to_string_pretty(&response)?)
+        }
+        "identify" => {
+            let req: IdentifyRequest = serde_json::from_value(args)?;
+            let response = bridge
+                .call_api("identify", serde_json::to_value(&req)?)
+                .await?;
+            Ok(serde_json::to_string_pretty(&response)?)
+        }
+        "get_current_user" => {
+            let response = bridge
+                .call_api("get_current_user", serde_json::json!({}))
+                .await?;
+            Ok(serde_json::to_string_pretty(&response)?)
+        }
+        "get_audit_log" => {
+            let req: GetAuditLogRequest = serde_json::from_value(args)?;
+            let response = bridge
+                .call_api("get_audit_log", serde_json::to_value(&req)?)
+                .await?;
+            Ok(serde_json::to_string_pretty(&response)?)
+        }
+        "list_render_formats" => {
+            let req: ListRenderFormatsRequest = serde_json::from_value(args)?;
+            let response = bridge
+                .call_api("list_render_formats", serde_json::to_value(&req)?)
+                .await?;
+            Ok(serde_json::to_string_pretty(&response)?)
+        }
+        "get_color_wheel_param" => {
+            let req: GetColorWheelParamRequest = serde_json::from_value(args)?;
+            let response = bridge
+                .call_api("get_color_wheel_param", serde_json::to_value(&req)?)
+                .await?;
+            Ok(serde_json::to_string_pretty(&response)?)
+        }
+        "adjust_color_wheel_param" => {
+            let req: AdjustColorWheelParamRequest = serde_json::from_value(args)?;
+            let response = bridge
+                .call_api("adjust_color_wheel_param", serde_json::to_value(&req)?)
+                .await?;
+            Ok(serde_json::to_string_pretty(&response)?)
+        }
+        "get_clip_grade" => {
+            let req: GetClipGradeRequest = serde_json::from_value(args)?;
+            let response = bridge
+                .call_api("get_clip_grade", serde_json::to_value(&req)?)
+                .await?;
+            Ok(serde_json::to_string_pretty(&response)?)
+        }
+        "set_notes" => {
+            let req: SetNotesRequest = serde_json::from_value(args)?;
+            let response = bridge
+                .call_api("set_notes", serde_json::to_value(&req)?)
+                .await?;
+            Ok(serde_json::to_string_pretty(&response)?)
+        }
+        "get_notes" => {
+            let req: GetNotesRequest = serde_json::from_value(args)?;
+            let response = bridge
+                .call_api("get_notes", serde_json::to_value(&req)?)
+                .await?;
+            Ok(serde_json::to_string_pretty(&response)?)
+        }
+        "run_action" => {
+            let req: RunActionRequest = serde_json::from_value(args)?;
+            let response = bridge
+                .call_api("run_action", serde_json::to_value(&req)?)
+                .await?;
+            Ok(serde_json::to_string_pretty(&response)?)
+        }
+        "bootstrap_session" => {
+            let req: BootstrapSessionRequest = serde_json::from_value(args)?;
+            let response = bridge
+                .call_api("bootstrap_session", serde_json::to_value(&req)?)
+                .await?;
+            Ok(serde_json::to_string_pretty(&response)?)
+        }
+        "describe_frame" => {
+            let req: DescribeFrameRequest = serde_json::from_value(args)?;
+            let response = bridge
+                .call_api("describe_frame", serde_json::to_value(&req)?)
+                .await?;
+            Ok(serde_json::to_string_pretty(&response)?)
+        }
+        "get_timeline_summary" => {
+            let req: GetTimelineSummaryRequest = serde_json::from_value(args)?;
+            let response = bridge
+                .call_api("get_timeline_summary", serde_json::to_value(&req)?)
+                .await?;
+            Ok(serde_json::to_string_pretty(&response)?)
+        }
+        "insert_bars_and_tone" => {
+            let req: InsertBarsAndToneRequest = serde_json::from_value(args)?;
+            let response = bridge
+                .call_api("insert_bars_and_tone", serde_json::to_value(&req)?)
+                .await?;
+            Ok(serde_json::to_string_pretty(&response)?)
+        }
+        "add_slate" => {
+            let req: AddSlateRequest = serde_json::from_value(args)?;
+            let response = bridge
+                .call_api("add_slate", serde_json::to_value(&req)?)
+                .await?;
+            Ok(serde_json::to_string_pretty(&response)?)
+        }
+        "get_bridge_stats" => {
+            let _req: GetBridgeStatsRequest = serde_json::from_value(args)?;
+            let response = bridge
+                .call_api("get_bridge_stats", serde_json::json!({}))
+                .await?;
+            Ok(serde_json::to_string_pretty(&response)?)
+        }
+        "sync_real_state" => {
+            let _req: SyncRealStateRequest = serde_json::from_value(args)?;
+            let response = bridge
+                .call_api("sync_real_state", serde_json::json!({}))
+                .await?;
+            Ok(serde_json::to_string_pretty(&response)?)
+        }
+        "submit_job" => {
+            let req: SubmitJobRequest = serde_json::from_value(args)?;
+            let response = bridge
+                .call_api("submit_job", serde_json::to_value(&req)?)
+                .await?;
+            Ok(serde_json::to_string_pretty(&response)?)
+        }
+        "get_job_graph" => {
+            let req: GetJobGraphRequest = serde_json::from_value(args)?;
+            let response = bridge
+                .call_api("get_job_graph", serde_json::to_value(&req)?)
+                .await?;
+            Ok(serde_json::to_string_pretty(&response)?)
+        }
+        "generate_grade_report" => {
+            let req: GenerateGradeReportRequest = serde_json::from_value(args)?;
+            let response = bridge
+                .call_api("generate_grade_report", serde_json::to_value(&req)?)
+                .await?;
+            Ok(serde_json::to_string_pretty(&response)?)
         }
-        "duplicate_timeline" => {
-            let req: DuplicateTimelineRequest = serde_json::from_value(args)?;
+        "apply_color_preset_batch" => {
+            let req: ApplyColorPresetBatchRequest = serde_json::from_value(args)?;
             let response = bridge
-                .call_api(
-                    "duplicate_timeline",
-                    serde_json::json!({
-                        "source_timeline_name": req.source_timeline_name,
-                        "new_timeline_name": req.new_timeline_name
-                    }),
-                )
+                .call_api("apply_color_preset_batch", serde_json::to_value(&req)?)
                 .await?;
-            Ok(response["result"].as_str().unwrap_or("Success").to_string())
+            Ok(serde_json::to_string_pretty(&response)?)
         }
-        "create_compound_clip" => {
-            let req: CreateCompoundClipRequest = serde_json::from_value(args)?;
+        "find_timeline_item" => {
+            let req: FindTimelineItemRequest = serde_json::from_value(args)?;
+            let response = bridge
+                .call_api("find_timeline_item", serde_json::to_value(&req)?)
+                .await?;
+            Ok(serde_json::to_string_pretty(&response)?)
+        }
+        "list_timeline_item_properties" => {
+            let req: ListTimelineItemPropertiesRequest = serde_json::from_value(args)?;
             let response = bridge
                 .call_api(
-                    "create_compound_clip",
-                    serde_json::json!({
-                        "timeline_name": req.timeline_name,
-                        "timeline_item_ids": req.timeline_item_ids,
-                        "clip_name": req.clip_name
-                    }),
+                    "list_timeline_item_properties",
+                    serde_json::json!({ "category": req.category }),
                 )
                 .await?;
-            Ok(response["result"].as_str().unwrap_or("Success").to_string())
+            Ok(serde_json::to_string_pretty(&response)?)
         }
-        "create_fusion_clip" => {
-            let req: CreateFusionClipRequest = serde_json::from_value(args)?;
+        "get_timeline_item_property" => {
+            let req: GetTimelineItemPropertyRequest = serde_json::from_value(args)?;
             let response = bridge
                 .call_api(
-                    "create_fusion_clip",
+                    "get_timeline_item_property",
                     serde_json::json!({
-                        "timeline_name": req.timeline_name,
-                        "timeline_item_ids": req.timeline_item_ids
+                        "timeline_item_id": req.timeline_item_id,
+                        "track_type": req.track_type,
+                        "track_index": req.track_index,
+                        "item_index": req.item_index,
+                        "property_key": req.property_key
                     }),
                 )
                 .await?;
-            Ok(response["result"].as_str().unwrap_or("Success").to_string())
+            Ok(serde_json::to_string_pretty(&response)?)
         }
-        "export_timeline" => {
-            let req: ExportTimelineRequest = serde_json::from_value(args)?;
+        "set_cdl" => {
+            let req: SetCDLRequest = serde_json::from_value(args)?;
             let response = bridge
                 .call_api(
-                    "export_timeline",
+                    "set_cdl",
                     serde_json::json!({
-                        "timeline_name": req.timeline_name,
-                        "file_name": req.file_name,
-                        "export_type": req.export_type,
-                        "export_subtype": req.export_subtype
+                        "timeline_item_id": req.timeline_item_id,
+                        "cdl_map": req.cdl_map
                     }),
                 )
                 .await?;
-            Ok(response["result"].as_str().unwrap_or("Success").to_string())
+            Ok(serde_json::to_string_pretty(&response)?)
         }
-        "insert_generator" => {
-            let req: InsertGeneratorRequest = serde_json::from_value(args)?;
+        "stereo_params" => {
+            let req: StereoParamsRequest = serde_json::from_value(args)?;
             let response = bridge
                 .call_api(
-                    "insert_generator",
+                    "stereo_params",
                     serde_json::json!({
-                        "timeline_name": req.timeline_name,
-                        "generator_name": req.generator_name,
-                        "generator_type": req.generator_type
+                        "timeline_item_id": req.timeline_item_id,
+                        "params": req.params
                     }),
                 )
                 .await?;
-            Ok(response["result"].as_str().unwrap_or("Success").to_string())
+            Ok(serde_json::to_string_pretty(&response)?)
         }
-        "insert_title" => {
-            let req: InsertTitleRequest = serde_json::from_value(args)?;
+        "set_timeline_item_property" => {
+            let req: SetTimelineItemPropertyRequest = serde_json::from_value(args)?;
             let response = bridge
                 .call_api(
-                    "insert_title",
+                    "set_timeline_item_property",
                     serde_json::json!({
-                        "timeline_name": req.timeline_name,
-                        "title_name": req.title_name,
-                        "title_type": req.title_type
+                        "timeline_item_id": req.timeline_item_id,
+                        "track_type": req.track_type,
+                        "track_index": req.track_index,
+                        "item_index": req.item_index,
+                        "property_key": req.property_key,
+                        "property_value": req.property_value
                     }),
                 )
                 .await?;
-            Ok(response["result"].as_str().unwrap_or("Success").to_string())
+            Ok(serde_json::to_string_pretty(&response)?)
         }
-        "grab_still" => {
-            let req: GrabStillRequest = serde_json::from_value(args)?;
+        "get_result_page" => {
+            let req: GetResultPageRequest = serde_json::from_value(args)?;
             let response = bridge
                 .call_api(
-                    "grab_still",
+                    "get_result_page",
                     serde_json::json!({
-                        "timeline_name": req.timeline_name,
-                        "still_frame_source": req.still_frame_source,
-                        "grab_all": req.grab_all
+                        "cursor": req.cursor,
+                        "page_size": req.page_size
                     }),
                 )
                 .await?;
-            Ok(response["result"].as_str().unwrap_or("Success").to_string())
+            Ok(serde_json::to_string_pretty(&response)?)
         }
-
-        // ---- Missing Tools Implementation ----
-        "get_media_pool_item_name" => {
-            let req: GetMediaPoolItemNameRequest = serde_json::from_value(args)?;
+        "get_media_pool_item_list" => {
+            let req: GetMediaPoolItemListRequest = serde_json::from_value(args)?;
             let response = bridge
                 .call_api(
-                    "get_media_pool_item_name",
+                    "get_media_pool_item_list",
                     serde_json::json!({
-                        "clip_name": req.clip_name
+                        "page_size": req.page_size
                     }),
                 )
                 .await?;
-            Ok(response["result"].as_str().unwrap_or("Success").to_string())
+            Ok(serde_json::to_string_pretty(&response)?)
         }
-        "get_project_timeline_count" => {
-            let _req: GetProjectTimelineCountRequest = serde_json::from_value(args)?;
+        "find_clip_usage" => {
+            let req: FindClipUsageRequest = serde_json::from_value(args)?;
             let response = bridge
-                .call_api("get_project_timeline_count", serde_json::json!({}))
+                .call_api(
+                    "find_clip_usage",
+                    serde_json::json!({ "clip_name": req.clip_name }),
+                )
                 .await?;
-            Ok(response["result"].as_str().unwrap_or("Success").to_string())
+            Ok(serde_json::to_string_pretty(&response)?)
         }
-        "get_media_pool_root_folder" => {
-            let _req: GetMediaPoolRootFolderRequest = serde_json::from_value(args)?;
+        "list_unused_clips" => {
+            let req: ListUnusedClipsRequest = serde_json::from_value(args)?;
             let response = bridge
-                .call_api("get_media_pool_root_folder", serde_json::json!({}))
+                .call_api(
+                    "list_unused_clips",
+                    serde_json::json!({ "bin_name": req.bin_name }),
+                )
                 .await?;
-            Ok(response["result"].as_str().unwrap_or("Success").to_string())
+            Ok(serde_json::to_string_pretty(&response)?)
         }
-        "get_gallery_still_albums" => {
-            let _req: GetGalleryStillAlbumsRequest = serde_json::from_value(args)?;
+        "read_embedded_timecode" => {
+            let req: ReadEmbeddedTimecodeRequest = serde_json::from_value(args)?;
             let response = bridge
-                .call_api("get_gallery_still_albums", serde_json::json!({}))
+                .call_api(
+                    "read_embedded_timecode",
+                    serde_json::json!({ "clip_names": req.clip_names }),
+                )
                 .await?;
-            Ok(response["result"].as_str().unwrap_or("Success").to_string())
+            Ok(serde_json::to_string_pretty(&response)?)
         }
-        "get_fusion_tool_list" => {
-            let req: GetFusionToolListRequest = serde_json::from_value(args)?;
+        "run_delivery_qc" => {
+            let req: RunDeliveryQcRequest = serde_json::from_value(args)?;
             let response = bridge
                 .call_api(
-                    "get_fusion_tool_list",
+                    "run_delivery_qc",
                     serde_json::json!({
-                        "selected_only": req.selected_only,
-                        "tool_type": req.tool_type
+                        "file_path": req.file_path,
+                        "timeline_name": req.timeline_name,
+                        "min_black_seconds": req.min_black_seconds,
+                        "min_freeze_seconds": req.min_freeze_seconds,
+                        "min_silence_seconds": req.min_silence_seconds,
+                        "silence_threshold_db": req.silence_threshold_db,
+                        "timeline_start_timecode": req.timeline_start_timecode
                     }),
                 )
                 .await?;
-            Ok(response["result"].as_str().unwrap_or("Success").to_string())
+            Ok(serde_json::to_string_pretty(&response)?)
         }
-        "get_audio_track_count" => {
-            let _req: GetAudioTrackCountRequest = serde_json::from_value(args)?;
+        "get_background_job" => {
+            let req: GetBackgroundJobRequest = serde_json::from_value(args)?;
             let response = bridge
-                .call_api("get_audio_track_count", serde_json::json!({}))
+                .call_api(
+                    "get_background_job",
+                    serde_json::json!({ "job_id": req.job_id }),
+                )
                 .await?;
-            Ok(response["result"].as_str().unwrap_or("Success").to_string())
+            Ok(serde_json::to_string_pretty(&response)?)
         }
-
-        // ---- MediaPoolItem API Tools ----
-        "set_media_pool_item_name" => {
-            let req: SetMediaPoolItemNameRequest = serde_json::from_value(args)?;
+        "list_background_jobs" => {
+            let req: ListBackgroundJobsRequest = serde_json::from_value(args)?;
             let response = bridge
                 .call_api(
-                    "set_media_pool_item_name",
-                    serde_json::json!({
-                        "clip_name": req.clip_name,
-                        "new_name": req.new_name
-                    }),
+                    "list_background_jobs",
+                    serde_json::json!({ "kind": req.kind }),
                 )
                 .await?;
-            Ok(response["result"].as_str().unwrap_or("Success").to_string())
+            Ok(serde_json::to_string_pretty(&response)?)
         }
-        "get_media_pool_item_property" => {
-            let req: GetMediaPoolItemPropertyRequest = serde_json::from_value(args)?;
+        "run_color_qc" => {
+            let req: RunColorQcRequest = serde_json::from_value(args)?;
             let response = bridge
                 .call_api(
-                    "get_media_pool_item_property",
+                    "run_color_qc",
                     serde_json::json!({
-                        "clip_name": req.clip_name,
-                        "property_key": req.property_key
+                        "timeline_name": req.timeline_name,
+                        "interval_frames": req.interval_frames,
+                        "illegal_percent_threshold": req.illegal_percent_threshold,
+                        "clipped_percent_threshold": req.clipped_percent_threshold,
+                        "luma_jump_threshold": req.luma_jump_threshold,
+                        "create_markers": req.create_markers,
+                        "marker_color": req.marker_color,
+                        "timeline_start_timecode": req.timeline_start_timecode
                     }),
                 )
                 .await?;
-            Ok(response["result"].as_str().unwrap_or("Success").to_string())
+            Ok(serde_json::to_string_pretty(&response)?)
         }
-        "set_media_pool_item_property" => {
-            let req: SetMediaPoolItemPropertyRequest = serde_json::from_value(args)?;
+        "get_frame_scopes" => {
+            let req: GetFrameScopesRequest = serde_json::from_value(args)?;
             let response = bridge
                 .call_api(
-                    "set_media_pool_item_property",
+                    "get_frame_scopes",
                     serde_json::json!({
                         "clip_name": req.clip_name,
-                        "property_key": req.property_key,
-                        "property_value": req.property_value
+                        "frame": req.frame,
+                        "histogram_bins": req.histogram_bins
                     }),
                 )
                 .await?;
-            Ok(response["result"].as_str().unwrap_or("Success").to_string())
+            Ok(serde_json::to_string_pretty(&response)?)
         }
-        "get_media_pool_item_metadata" => {
-            let req: GetMediaPoolItemMetadataRequest = serde_json::from_value(args)?;
+        "get_audio_waveform" => {
+            let req: GetAudioWaveformRequest = serde_json::from_value(args)?;
             let response = bridge
                 .call_api(
-                    "get_media_pool_item_metadata",
+                    "get_audio_waveform",
                     serde_json::json!({
                         "clip_name": req.clip_name,
-                        "metadata_type": req.metadata_type
+                        "timeline_name": req.timeline_name,
+                        "start_frame": req.start_frame,
+                        "end_frame": req.end_frame,
+                        "buckets": req.buckets,
+                        "page_size": req.page_size
                     }),
                 )
                 .await?;
-            Ok(response["result"].as_str().unwrap_or("Success").to_string())
+            Ok(serde_json::to_string_pretty(&response)?)
         }
-        "set_media_pool_item_metadata" => {
-            let req: SetMediaPoolItemMetadataRequest = serde_json::from_value(args)?;
+        "search_transcript" => {
+            let req: SearchTranscriptRequest = serde_json::from_value(args)?;
             let response = bridge
                 .call_api(
-                    "set_media_pool_item_metadata",
+                    "search_transcript",
                     serde_json::json!({
+                        "query": req.query,
                         "clip_name": req.clip_name,
-                        "metadata_type": req.metadata_type,
-                        "metadata_value": req.metadata_value
+                        "min_confidence": req.min_confidence,
+                        "create_markers": req.create_markers,
+                        "timeline_name": req.timeline_name,
+                        "marker_color": req.marker_color,
+                        "page_size": req.page_size
                     }),
                 )
                 .await?;
-            Ok(response["result"].as_str().unwrap_or("Success").to_string())
+            Ok(serde_json::to_string_pretty(&response)?)
         }
-        "get_media_pool_item_markers" => {
-            let req: GetMediaPoolItemMarkersRequest = serde_json::from_value(args)?;
+        "tag_clip" => {
+            let req: TagClipRequest = serde_json::from_value(args)?;
             let response = bridge
                 .call_api(
-                    "get_media_pool_item_markers",
+                    "tag_clip",
                     serde_json::json!({
-                        "clip_name": req.clip_name
+                        "clip_name": req.clip_name,
+                        "timeline_item_id": req.timeline_item_id,
+                        "tags": req.tags
                     }),
                 )
                 .await?;
             Ok(response["result"].as_str().unwrap_or("Success").to_string())
         }
-        "add_media_pool_item_marker" => {
-            let req: AddMediaPoolItemMarkerRequest = serde_json::from_value(args)?;
+        "untag_clip" => {
+            let req: UntagClipRequest = serde_json::from_value(args)?;
             let response = bridge
                 .call_api(
-                    "add_media_pool_item_marker",
+                    "untag_clip",
                     serde_json::json!({
                         "clip_name": req.clip_name,
-                        "frame_id": req.frame_id,
-                        "color": req.color,
-                        "name": req.name,
-                        "note": req.note,
-                        "duration": req.duration,
-                        "custom_data": req.custom_data
+                        "timeline_item_id": req.timeline_item_id,
+                        "tags": req.tags
                     }),
                 )
                 .await?;
             Ok(response["result"].as_str().unwrap_or("Success").to_string())
         }
-        "get_media_pool_item_flag_list" => {
-            let req: GetMediaPoolItemFlagListRequest = serde_json::from_value(args)?;
+        "find_by_tag" => {
+            let req: FindByTagRequest = serde_json::from_value(args)?;
             let response = bridge
                 .call_api(
-                    "get_media_pool_item_flag_list",
+                    "find_by_tag",
                     serde_json::json!({
-                        "clip_name": req.clip_name
+                        "tags": req.tags,
+                        "match": req.r#match,
+                        "target_type": req.target_type
                     }),
                 )
                 .await?;
-            Ok(response["result"].as_str().unwrap_or("Success").to_string())
+            Ok(serde_json::to_string_pretty(&response)?)
         }
-        "add_media_pool_item_flag" => {
-            let req: AddMediaPoolItemFlagRequest = serde_json::from_value(args)?;
+        "export_vfx_plates" => {
+            let req: ExportVfxPlatesRequest = serde_json::from_value(args)?;
             let response = bridge
                 .call_api(
-                    "add_media_pool_item_flag",
+                    "export_vfx_plates",
                     serde_json::json!({
-                        "clip_name": req.clip_name,
-                        "color": req.color
+                        "show": req.show,
+                        "sequence": req.sequence,
+                        "timeline_name": req.timeline_name,
+                        "timeline_item_ids": req.timeline_item_ids,
+                        "marker_color": req.marker_color,
+                        "handles": req.handles,
+                        "format": req.format,
+                        "version": req.version,
+                        "shot_start": req.shot_start,
+                        "shot_increment": req.shot_increment,
+                        "output_directory": req.output_directory,
+                        "timeline_start_timecode": req.timeline_start_timecode
                     }),
                 )
                 .await?;
-            Ok(response["result"].as_str().unwrap_or("Success").to_string())
+            Ok(serde_json::to_string_pretty(&response)?)
         }
-        "get_media_pool_item_clip_color" => {
-            let req: GetMediaPoolItemClipColorRequest = serde_json::from_value(args)?;
+        "consolidate_media" => {
+            let req: ConsolidateMediaRequest = serde_json::from_value(args)?;
             let response = bridge
                 .call_api(
-                    "get_media_pool_item_clip_color",
+                    "consolidate_media",
                     serde_json::json!({
-                        "clip_name": req.clip_name
+                        "timeline_name": req.timeline_name,
+                        "handles": req.handles,
+                        "destination_folder": req.destination_folder,
+                        "new_timeline_name": req.new_timeline_name
                     }),
                 )
                 .await?;
-            Ok(response["result"].as_str().unwrap_or("Success").to_string())
+            Ok(serde_json::to_string_pretty(&response)?)
         }
-        "set_media_pool_item_clip_color" => {
-            let req: SetMediaPoolItemClipColorRequest = serde_json::from_value(args)?;
+        "generate_shot_list" => {
+            let req: GenerateShotListRequest = serde_json::from_value(args)?;
             let response = bridge
                 .call_api(
-                    "set_media_pool_item_clip_color",
+                    "generate_shot_list",
                     serde_json::json!({
-                        "clip_name": req.clip_name,
-                        "color_name": req.color_name
+                        "timeline_name": req.timeline_name,
+                        "format": req.format,
+                        "timeline_start_timecode": req.timeline_start_timecode
                     }),
                 )
                 .await?;
-            Ok(response["result"].as_str().unwrap_or("Success").to_string())
+            Ok(serde_json::to_string_pretty(&response)?)
         }
-        "link_media_pool_item_proxy_media" => {
-            let req: LinkMediaPoolItemProxyMediaRequest = serde_json::from_value(args)?;
+        "add_review_note" => {
+            let req: AddReviewNoteRequest = serde_json::from_value(args)?;
             let response = bridge
                 .call_api(
-                    "link_media_pool_item_proxy_media",
+                    "add_review_note",
                     serde_json::json!({
-                        "clip_name": req.clip_name,
-                        "proxy_media_file_path": req.proxy_media_file_path
+                        "text": req.text,
+                        "timeline_item_id": req.timeline_item_id,
+                        "timecode": req.timecode,
+                        "timeline_name": req.timeline_name,
+                        "timeline_start_timecode": req.timeline_start_timecode,
+                        "status": req.status,
+                        "author": req.author
                     }),
                 )
                 .await?;
-            Ok(response["result"].as_str().unwrap_or("Success").to_string())
+            Ok(serde_json::to_string_pretty(&response)?)
         }
-        "unlink_media_pool_item_proxy_media" => {
-            let req: UnlinkMediaPoolItemProxyMediaRequest = serde_json::from_value(args)?;
+        "list_review_notes" => {
+            let req: ListReviewNotesRequest = serde_json::from_value(args)?;
             let response = bridge
                 .call_api(
-                    "unlink_media_pool_item_proxy_media",
+                    "list_review_notes",
                     serde_json::json!({
-                        "clip_name": req.clip_name
+                        "status": req.status,
+                        "timeline_name": req.timeline_name,
+                        "timeline_item_id": req.timeline_item_id,
+                        "author": req.author
                     }),
                 )
                 .await?;
-            Ok(response["result"].as_str().unwrap_or("Success").to_string())
+            Ok(serde_json::to_string_pretty(&response)?)
         }
-        "transcribe_media_pool_item_audio" => {
-            let req: TranscribeMediaPoolItemAudioRequest = serde_json::from_value(args)?;
+        "resolve_note" => {
+            let req: ResolveNoteRequest = serde_json::from_value(args)?;
             let response = bridge
                 .call_api(
-                    "transcribe_media_pool_item_audio",
+                    "resolve_note",
                     serde_json::json!({
-                        "clip_name": req.clip_name,
-                        "language": req.language
+                        "note_id": req.note_id,
+                        "status": req.status,
+                        "comment": req.comment
                     }),
                 )
                 .await?;
             Ok(response["result"].as_str().unwrap_or("Success").to_string())
         }
-        "clear_media_pool_item_transcription" => {
-            let req: ClearMediaPoolItemTranscriptionRequest = serde_json::from_value(args)?;
+        "create_backup" => {
+            let req: CreateBackupRequest = serde_json::from_value(args)?;
             let response = bridge
                 .call_api(
-                    "clear_media_pool_item_transcription",
+                    "create_backup",
                     serde_json::json!({
-                        "clip_name": req.clip_name
+                        "name": req.name,
+                        "write_file": req.write_file
                     }),
                 )
                 .await?;
-            Ok(response["result"].as_str().unwrap_or("Success").to_string())
+            Ok(serde_json::to_string_pretty(&response)?)
         }
-
-        // ============================================
-        // MISSING TOOLS IMPLEMENTATION - PHASE 3
-        // ============================================
-        "add_fusion_tool" => {
-            let req: AddFusionToolRequest = serde_json::from_value(args)?;
+        "restore_backup" => {
+            let req: RestoreBackupRequest = serde_json::from_value(args)?;
             let response = bridge
                 .call_api(
-                    "add_fusion_tool",
+                    "restore_backup",
                     serde_json::json!({
-                        "tool_name": req.tool_name,
-                        "x": req.x,
-                        "y": req.y
+                        "backup_id": req.backup_id
                     }),
                 )
                 .await?;
             Ok(response["result"].as_str().unwrap_or("Success").to_string())
         }
-        "get_audio_track_name" => {
-            let req: GetAudioTrackNameRequest = serde_json::from_value(args)?;
+        "list_backups" => {
+            let _req: ListBackupsRequest = serde_json::from_value(args)?;
             let response = bridge
-                .call_api(
-                    "get_audio_track_name",
-                    serde_json::json!({
-                        "track_index": req.track_index
-                    }),
-                )
+                .call_api("list_backups", serde_json::json!({}))
                 .await?;
-            Ok(response["result"].as_str().unwrap_or("Success").to_string())
+            Ok(serde_json::to_string_pretty(&response)?)
         }
-        "set_audio_track_name" => {
-            let req: SetAudioTrackNameRequest = serde_json::from_value(args)?;
+        "get_mounted_volumes" => {
+            let _req: GetMountedVolumesRequest = serde_json::from_value(args)?;
+            let response = bridge
+                .call_api("get_mounted_volumes", serde_json::json!({}))
+                .await?;
+            Ok(serde_json::to_string_pretty(&response)?)
+        }
+        "browse_folder" => {
+            let req: BrowseFolderRequest = serde_json::from_value(args)?;
             let response = bridge
                 .call_api(
-                    "set_audio_track_name",
+                    "browse_folder",
                     serde_json::json!({
-                        "track_index": req.track_index,
-                        "track_name": req.track_name
+                        "path": req.path
                     }),
                 )
                 .await?;
-            Ok(response["result"].as_str().unwrap_or("Success").to_string())
+            Ok(serde_json::to_string_pretty(&response)?)
         }
-        "add_gallery_still_album" => {
-            let req: AddGalleryStillAlbumRequest = serde_json::from_value(args)?;
+        "add_clips_to_media_pool_from_paths" => {
+            let req: AddClipsToMediaPoolFromPathsRequest = serde_json::from_value(args)?;
             let response = bridge
                 .call_api(
-                    "add_gallery_still_album",
+                    "add_clips_to_media_pool_from_paths",
                     serde_json::json!({
-                        "album_name": req.album_name
+                        "paths": req.paths,
+                        "bin_name": req.bin_name
                     }),
                 )
                 .await?;
-            Ok(response["result"].as_str().unwrap_or("Success").to_string())
+            Ok(serde_json::to_string_pretty(&response)?)
         }
         "add_media_pool_sub_folder" => {
             let req: AddMediaPoolSubFolderRequest = serde_json::from_value(args)?;
@@ -3314,7 +6337,7 @@ pub async fn handle_tool_call(
                     }),
                 )
                 .await?;
-            Ok(response["result"].as_str().unwrap_or("Success").to_string())
+            Ok(serde_json::to_string_pretty(&response)?)
         }
         "get_project_timeline_by_index" => {
             let req: GetProjectTimelineByIndexRequest = serde_json::from_value(args)?;
@@ -3440,7 +6463,7 @@ pub async fn handle_tool_call(
                     serde_json::json!({}),
                 )
                 .await?;
-            Ok(response["result"].as_str().unwrap_or("Success").to_string())
+            Ok(serde_json::to_string_pretty(&response)?)
         }
         "set_current_project_render_format_and_codec" => {
             let req: SetCurrentProjectRenderFormatAndCodecRequest = serde_json::from_value(args)?;
@@ -3479,7 +6502,7 @@ pub async fn handle_tool_call(
             let response = bridge
                 .call_api("get_project_color_groups_list", serde_json::json!({}))
                 .await?;
-            Ok(response["result"].as_str().unwrap_or("Success").to_string())
+            Ok(serde_json::to_string_pretty(&response)?)
         }
         "add_project_color_group" => {
             let req: AddProjectColorGroupRequest = serde_json::from_value(args)?;
@@ -3487,7 +6510,8 @@ pub async fn handle_tool_call(
                 .call_api(
                     "add_project_color_group",
                     serde_json::json!({
-                        "group_name": req.group_name
+                        "group_name": req.group_name,
+                        "clip_names": req.clip_names
                     }),
                 )
                 .await?;
@@ -3519,8 +6543,11 @@ pub async fn handle_tool_call(
             Ok(response["result"].as_str().unwrap_or("Success").to_string())
         }
 
-        _ => Err(crate::error::ResolveError::ToolNotFound {
-            name: tool_name.to_string(),
-        }),
+        _ => match registry::call(tool_name, args, &bridge).await {
+            Some(result) => result,
+            None => Err(crate::error::ResolveError::ToolNotFound {
+                name: tool_name.to_string(),
+            }),
+        },
     }
 }
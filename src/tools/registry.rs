@@ -0,0 +1,704 @@
+//! Tool registry
+//!
+//! A registered tool is defined once, by an entry in [`registered_tools!`]:
+//! its name, category, description, request type, the state domains it
+//! writes and the arguments that hold file paths. From that entry the server lists the tool with a schema generated
+//! from the request type, [`super::handle_tool_call`] parses its arguments
+//! into the request and hands them to the bridge, and the bridge dispatches
+//! the call to its handler method of the same name under a matching lock plan.
+//! Adding a tool takes a request struct, a bridge handler and an entry here.
+
+use rmcp::model::Tool;
+use schemars::gen::SchemaSettings;
+use schemars::schema::{InstanceType, Schema, SchemaObject};
+use schemars::JsonSchema;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use std::sync::Arc;
+
+use crate::error::ResolveResult;
+
+/// Request of a tool that takes no arguments
+#[derive(Debug, Default, Serialize, Deserialize, JsonSchema)]
+pub struct NoArgumentsRequest {}
+
+/// Registered tools, in the order the server lists them
+///
+/// Each entry is handed to the macro named by the caller, which expands the
+/// list into what its module needs. `writes` names the bridge state domains
+/// the call changes; an empty list makes the call a reader. `paths` names the
+/// arguments that hold file or folder paths, which the bridge checks against
+/// the `[sandbox]` roots before the call runs.
+macro_rules! registered_tools {
+    ($then:ident) => {
+        $then! {
+            // ---- Clip Marks ----
+            set_clip_in_out {
+                category: "media",
+                description: "Mark in and out points on a media pool clip's video, audio or both, the out frame included; add_clip_to_timeline and append_to_timeline use the video marks as the source range when none is given, for logging clips before assembling them",
+                request: SetClipInOutRequest,
+                writes: [MediaPool],
+                paths: [],
+            }
+            get_clip_in_out {
+                category: "media",
+                description: "Read the video and audio in and out points marked on a media pool clip",
+                request: GetClipInOutRequest,
+                writes: [],
+                paths: [],
+            }
+            clear_clip_in_out {
+                category: "media",
+                description: "Remove the video, audio or all in and out points marked on a media pool clip",
+                request: ClearClipInOutRequest,
+                writes: [MediaPool],
+                paths: [],
+            }
+
+            // ---- Item Trimming ----
+            trim_item_head {
+                category: "timeline",
+                description: "Move the start of a timeline item by a number of frames, trimming or extending its head within its media; it may not overlap the item before it",
+                request: TrimItemHeadRequest,
+                writes: [Timelines],
+                paths: [],
+            }
+            trim_item_tail {
+                category: "timeline",
+                description: "Move the end of a timeline item by a number of frames, trimming or extending its tail within its media; it may not overlap the item after it",
+                request: TrimItemTailRequest,
+                writes: [Timelines],
+                paths: [],
+            }
+            slip_item {
+                category: "timeline",
+                description: "Slip a timeline item: show media a number of frames earlier or later in the same place on the timeline, within the media's bounds",
+                request: SlipItemRequest,
+                writes: [Timelines],
+                paths: [],
+            }
+            slide_item {
+                category: "timeline",
+                description: "Slide a timeline item a number of frames along its track, trimming the tail of the item before it and the head of the item after it so the track keeps its length; checked against both items' media",
+                request: SlideItemRequest,
+                writes: [Timelines],
+                paths: [],
+            }
+
+            // ---- Marker Range Renders ----
+            render_marker_ranges {
+                category: "render",
+                description: "Queue one render job per marked region of a timeline, from duration markers or pairs of in and out markers, naming each output after its marker; for cutting social clips out of long-form content",
+                request: RenderMarkerRangesRequest,
+                writes: [Render],
+                paths: ["output_directory"],
+            }
+
+            // ---- Delivery Packages ----
+            queue_delivery_package {
+                category: "render",
+                description: "Queue a delivery package for timelines: one render job per output, such as a ProRes master, an H.264 review copy and audio stems, with linked file names in one folder per timeline and a manifest of every output; if any job cannot be queued, none is",
+                request: QueueDeliveryPackageRequest,
+                writes: [Render],
+                paths: ["output_directory"],
+            }
+
+            // ---- Media Organization ----
+            auto_organize_media {
+                category: "media",
+                description: "Sort media pool clips into bins by rules on their recording date, camera, reel, resolution or any metadata field such as Scene, creating bins as needed and reporting every move; rules come from the call or the config",
+                request: AutoOrganizeMediaRequest,
+                writes: [MediaPool],
+                paths: [],
+            }
+
+            // ---- Grade History ----
+            set_grade_history {
+                category: "color",
+                description: "Turn grading history on or off: while on, every change to a clip's grade is kept as a version, optionally with a gallery still of the clip",
+                request: SetGradeHistoryRequest,
+                writes: [Color],
+                paths: [],
+            }
+            get_grade_history {
+                category: "color",
+                description: "List the grade versions kept for a clip, each with the call that made it and what it changed, or the clips that have versions",
+                request: GetGradeHistoryRequest,
+                writes: [],
+                paths: [],
+            }
+            revert_grade_to {
+                category: "color",
+                description: "Make an earlier grade version of a clip its grade again, leaving every other clip alone; the revert is kept as a new version",
+                request: RevertGradeToRequest,
+                writes: [Color],
+                paths: [],
+            }
+
+            // ---- App State ----
+            get_current_page {
+                category: "project",
+                description: "Get the page Resolve is on: media, cut, edit, fusion, color, fairlight or deliver",
+                request: NoArgumentsRequest,
+                writes: [],
+                paths: [],
+            }
+            get_app_state {
+                category: "project",
+                description: "Get where the session stands in one call: the open project, the current timeline with its playhead, the page, the selected clip and still album, and the connection mode",
+                request: NoArgumentsRequest,
+                writes: [],
+                paths: [],
+            }
+
+            // ---- Render Job Deletion ----
+            delete_render_job {
+                category: "render",
+                description: "Delete one job from the render queue; a job that is rendering must be stopped first",
+                request: DeleteRenderJobRequest,
+                writes: [Render],
+                paths: [],
+            }
+
+            // ---- Grading Sessions ----
+            start_grading_session {
+                category: "color",
+                description: "Start a grading session that records every grading operation until end_grading_session, for a summary of the clips touched, parameters changed and presets applied",
+                request: StartGradingSessionRequest,
+                writes: [],
+                paths: [],
+            }
+            end_grading_session {
+                category: "color",
+                description: "End the open grading session and return its artifact: the clips touched, each parameter changed with its value before and after the session, the presets applied and a readable summary",
+                request: EndGradingSessionRequest,
+                writes: [],
+                paths: ["output_path"],
+            }
+            get_grading_session {
+                category: "color",
+                description: "Get the artifact of an ended grading session by ID or name, or list the ended sessions and the open one",
+                request: GetGradingSessionRequest,
+                writes: [],
+                paths: [],
+            }
+
+            // ---- ALE ----
+            export_ale {
+                category: "media",
+                description: "Export the metadata of media pool clips, a bin or chosen clips as an Avid Log Exchange (ALE) file, with Tape, Comments and every custom metadata field as columns",
+                request: ExportAleRequest,
+                writes: [],
+                paths: ["output_path"],
+            }
+            import_ale {
+                category: "media",
+                description: "Merge the metadata of an Avid Log Exchange (ALE) file back onto the clips its rows match by Name or Source File, checked against the active metadata schema",
+                request: ImportAleRequest,
+                writes: [MediaPool, Review],
+                paths: [],
+            }
+
+            // ---- Metadata Schemas ----
+            validate_metadata {
+                category: "media",
+                description: "Check every clip's metadata against a configured metadata schema (the active one by default) and list the clips with missing required fields or values the schema does not allow",
+                request: ValidateMetadataRequest,
+                writes: [],
+                paths: [],
+            }
+
+            // ---- A/V Offset ----
+            detect_av_offset {
+                category: "render",
+                description: "Compare a render against an earlier render or its timeline and report how far its sound and picture are offset, the A/V sync error between them and any audio drift (decodes with ffmpeg when available)",
+                request: DetectAvOffsetRequest,
+                writes: [],
+                paths: ["file_path", "reference_path"],
+            }
+
+            // ---- Share Links ----
+            get_share_link {
+                category: "render",
+                description: "Get an HTTP link to a completed render job's output file, served by the local share server, so reviewers and bots can fetch it without filesystem access",
+                request: GetShareLinkRequest,
+                writes: [],
+                paths: [],
+            }
+
+            // ---- Rename Batch ----
+            rename_batch {
+                category: "project",
+                description: "Find and replace with a regular expression across all clip, bin or timeline names, or a clip metadata field; refuses batches where names would collide, and a dry run previews the renames",
+                request: RenameBatchRequest,
+                writes: [MediaPool, Timelines, Color, Render, Review],
+                paths: [],
+            }
+
+            // ---- Trash ----
+            list_trash {
+                category: "project",
+                description: "List the deleted media, timelines and color presets that can still be restored, newest first, with when each expires",
+                request: ListTrashRequest,
+                writes: [],
+                paths: [],
+            }
+            restore_from_trash {
+                category: "project",
+                description: "Restore a deleted clip, timeline or color preset from the trash under its old name and ID, into the project it was deleted from",
+                request: RestoreFromTrashRequest,
+                writes: [Project, MediaPool, Timelines, Color],
+                paths: [],
+            }
+            empty_trash {
+                category: "project",
+                description: "Permanently delete everything in the trash, or only the entries of one kind",
+                request: EmptyTrashRequest,
+                writes: [Project],
+                paths: [],
+            }
+
+            // ---- Qualifier ----
+            set_qualifier {
+                category: "color",
+                description: "Set the HSL qualifier of a grade node: hue, saturation and luminance ranges with softness, and Matte Finesse denoise, clean black, clean white and blur",
+                request: SetQualifierRequest,
+                writes: [Color],
+                paths: [],
+            }
+            get_qualifier {
+                category: "color",
+                description: "Read the HSL qualifier of a grade node, with defaults for controls never set and the Qualifier palette label of each control",
+                request: GetQualifierRequest,
+                writes: [],
+                paths: [],
+            }
+
+            // ---- Power Windows ----
+            add_power_window {
+                category: "color",
+                description: "Add a circle, linear, polygon or gradient Power Window to a grade node, with position, size, rotation and softness as fractions of the frame",
+                request: AddPowerWindowRequest,
+                writes: [Color],
+                paths: [],
+            }
+            modify_power_window {
+                category: "color",
+                description: "Change the position, size, rotation, softness, inversion or polygon points of a Power Window on a grade node",
+                request: ModifyPowerWindowRequest,
+                writes: [Color],
+                paths: [],
+            }
+            track_power_window {
+                category: "color",
+                description: "Make a Power Window follow a subject on a timeline item by keyframing its center at each tracked frame",
+                request: TrackPowerWindowRequest,
+                writes: [Timelines, Color],
+                paths: [],
+            }
+
+            // ---- Stereo 3D ----
+            set_stereo_params {
+                category: "timeline",
+                description: "Set the stereo 3D convergence, floating windows, eye and eye swap of a timeline item; convergence and windows are keyframed at the given frame",
+                request: SetStereoParamsRequest,
+                writes: [Timelines],
+                paths: [],
+            }
+            get_stereo_params {
+                category: "timeline",
+                description: "Get the stereo 3D parameters of a timeline item, with convergence and floating windows keyed by frame as the real API returns them",
+                request: GetStereoParamsRequest,
+                writes: [],
+                paths: [],
+            }
+
+            // ---- State Integrity ----
+            verify_state_integrity {
+                category: "project",
+                description: "Check that items, keyframes, markers, bins, grades, color groups and render jobs only reference things that exist, in the current state or a backup, and optionally repair the orphans",
+                request: VerifyStateIntegrityRequest,
+                writes: [MediaPool, Timelines, Color, Render],
+                paths: [],
+            }
+
+            // ---- Grade Trace ----
+            trace_grades {
+                category: "color",
+                description: "Copy grades from an old timeline version to the matching items of a new cut, matched by source clip and overlapping source timecode, reporting the items nothing matched",
+                request: TraceGradesRequest,
+                writes: [Timelines, Color],
+                paths: [],
+            }
+
+            // ---- Auto Reframe ----
+            auto_reframe_timeline {
+                category: "timeline",
+                description: "Make a 9:16 copy of a timeline for vertical deliveries, zooming each item to fill the frame and keyframing its pan to follow subject positions from a detector (center when none), with per-item confidence",
+                request: AutoReframeTimelineRequest,
+                writes: [Timelines],
+                paths: [],
+            }
+
+            // ---- Output Framing ----
+            set_output_blanking {
+                category: "timeline",
+                description: "Blank a timeline's output to 2.39:1, 1.85:1 or a custom aspect ratio; renders queued from the timeline carry the bars",
+                request: SetOutputBlankingRequest,
+                writes: [Timelines],
+                paths: [],
+            }
+            set_output_reframe {
+                category: "timeline",
+                description: "Reframe a timeline's output to a vertical (9:16) or square frame for social media deliverables; applied to renders and previews of the timeline",
+                request: SetOutputReframeRequest,
+                writes: [Timelines],
+                paths: [],
+            }
+
+            // ---- LUT Library ----
+            scan_lut_directories {
+                category: "color",
+                description: "Re-index the LUT folders in the server config, reading each LUT's format and size, so apply_lut accepts them by relative path",
+                request: NoArgumentsRequest,
+                writes: [Color],
+                paths: [],
+            }
+            list_luts {
+                category: "color",
+                description: "List the LUTs apply_lut accepts, built-in and from the configured LUT folders, with format, size and file metadata",
+                request: ListLutsRequest,
+                writes: [Color],
+                paths: [],
+            }
+
+            // ---- DRX Grades ----
+            apply_drx_file {
+                category: "color",
+                description: "Apply the grade in a .drx file to timeline items, through Resolve's ApplyGradeFromDRX in Real mode; the simulation records the applied file on each item",
+                request: ApplyDrxFileRequest,
+                writes: [Timelines],
+                paths: ["drx_path"],
+            }
+
+            // ---- Preview ----
+            generate_preview {
+                category: "render",
+                description: "Render a low-res animated preview (GIF or 480p MP4) of a timeline or clip range with ffmpeg as a background job, for quick sharing in review threads",
+                request: GeneratePreviewRequest,
+                writes: [Jobs],
+                paths: ["output_path"],
+            }
+
+            // ---- Bin Color Rules ----
+            set_bin_color_rules {
+                category: "media",
+                description: "Attach input color space and input LUT rules to a bin, matched by file name, so clips imported into the bin get those clip color settings automatically",
+                request: SetBinColorRulesRequest,
+                writes: [MediaPool],
+                paths: [],
+            }
+
+            // ---- Frame Rate Advisor ----
+            analyze_frame_rate_mismatches {
+                category: "timeline",
+                description: "Report clips whose native frame rate differs from the timeline's and suggest, or apply, per clip whether to conform the clip to the timeline rate or retime it with nearest frame or optical flow",
+                request: AnalyzeFrameRateMismatchesRequest,
+                writes: [MediaPool, Timelines],
+                paths: [],
+            }
+
+            // ---- Timeline Versions ----
+            create_timeline_version {
+                category: "timeline",
+                description: "Duplicate a timeline, items and keyframes included, as its next version (v001 becomes v002) and make it current, recording the parent timeline, author and note",
+                request: CreateTimelineVersionRequest,
+                writes: [Timelines],
+                paths: [],
+            }
+            list_timeline_versions {
+                category: "timeline",
+                description: "Show the version tree of a timeline, from its first version down, with each version's author, note and creation time",
+                request: ListTimelineVersionsRequest,
+                writes: [],
+                paths: [],
+            }
+
+            // ---- Archive Manifest ----
+            generate_archive_manifest {
+                category: "media",
+                description: "Checksum every media and proxy file of the project and produce an archive manifest (JSON or CSV) with sizes and SHA-256 sums, plus a restore script outline for bringing the files back from LTO",
+                request: GenerateArchiveManifestRequest,
+                writes: [],
+                paths: ["output_path"],
+            }
+
+            // ---- Review Comment Import ----
+            import_review_comments {
+                category: "review",
+                description: "Import comments exported from a review platform (Frame.io CSV, Vimeo review JSON) as timeline markers or review notes, offset from the review file to the timeline as the adapter is configured",
+                request: ImportReviewCommentsRequest,
+                writes: [Timelines, Review],
+                paths: [],
+            }
+
+            // ---- Privacy Blur ----
+            apply_privacy_blur {
+                category: "color",
+                description: "Blur faces or other regions found by a detector or drawn by hand: each region gets a blur node with a window on its item's grade, keyframed to follow the tracked boxes",
+                request: ApplyPrivacyBlurRequest,
+                writes: [Timelines, Color],
+                paths: [],
+            }
+
+            // ---- Motion Effects ----
+            set_noise_reduction {
+                category: "color",
+                description: "Set temporal and spatial noise reduction on a grade node, with the ranges and choices of the color page's Motion Effects palette",
+                request: SetNoiseReductionRequest,
+                writes: [Color],
+                paths: [],
+            }
+            set_motion_blur {
+                category: "color",
+                description: "Set motion blur on a grade node: motion estimation type, motion range and amount",
+                request: SetMotionBlurRequest,
+                writes: [Color],
+                paths: [],
+            }
+            get_motion_effects {
+                category: "color",
+                description: "Read the noise reduction and motion blur settings of a grade node, with defaults for controls never set",
+                request: GetMotionEffectsRequest,
+                writes: [],
+                paths: [],
+            }
+
+            // ---- Render Cache ----
+            set_item_render_cache {
+                category: "cache",
+                description: "Set a timeline item's color and Fusion output render cache flags to on, off or auto, to pre-cache heavy shots before playback",
+                request: SetItemRenderCacheRequest,
+                writes: [Timelines],
+                paths: [],
+            }
+            get_render_cache_status {
+                category: "cache",
+                description: "List the render cache flags of every item on a timeline with how many items and frames are forced into the cache",
+                request: GetRenderCacheStatusRequest,
+                writes: [],
+                paths: [],
+            }
+
+            // ---- Batch Render ----
+            queue_renders_for_timelines {
+                category: "render",
+                description: "Queue one render job per timeline, chosen by a list of names or a glob, with one preset and an output folder template; if any timeline cannot be queued, none is",
+                request: QueueRendersForTimelinesRequest,
+                writes: [Render],
+                paths: ["output_directory"],
+            }
+
+            // ---- Conform ----
+            conform_timeline {
+                category: "media",
+                description: "Conform a timeline cut with offline media to the imported camera originals, matching each item by reel name and source timecode and reporting items nothing matches",
+                request: ConformTimelineRequest,
+                writes: [MediaPool, Timelines],
+                paths: [],
+            }
+
+            // ---- Frame Cache ----
+            get_frame_cache_stats {
+                category: "cache",
+                description: "Show how many decoded frames the still frame cache holds, its memory use and hit rate",
+                request: NoArgumentsRequest,
+                writes: [],
+                paths: [],
+            }
+            clear_frame_cache {
+                category: "cache",
+                description: "Drop every frame from the still frame cache so the next inspections decode again",
+                request: NoArgumentsRequest,
+                writes: [],
+                paths: [],
+            }
+
+            // ---- Subtitles ----
+            add_subtitle_track {
+                category: "subtitles",
+                description: "Add a subtitle track to a timeline with a language and the default style its cues start from",
+                request: AddSubtitleTrackRequest,
+                writes: [Timelines],
+                paths: [],
+            }
+            add_subtitle_cue {
+                category: "subtitles",
+                description: "Add a subtitle cue to a track; cues on a track cannot overlap and take the track's style unless overridden",
+                request: AddSubtitleCueRequest,
+                writes: [Timelines],
+                paths: [],
+            }
+            update_subtitle_cue {
+                category: "subtitles",
+                description: "Change a subtitle cue's text, timing or style",
+                request: UpdateSubtitleCueRequest,
+                writes: [Timelines],
+                paths: [],
+            }
+            delete_subtitle_cue {
+                category: "subtitles",
+                description: "Delete a subtitle cue",
+                request: DeleteSubtitleCueRequest,
+                writes: [Timelines],
+                paths: [],
+            }
+            list_subtitle_cues {
+                category: "subtitles",
+                description: "List a timeline's subtitle tracks with their cues in record order",
+                request: ListSubtitleCuesRequest,
+                writes: [],
+                paths: [],
+            }
+
+            // ---- Transcription Languages ----
+            list_supported_languages {
+                category: "transcription",
+                description: "List the languages audio can be transcribed in, with their BCP-47 codes and accepted names, for the current or a named transcription backend",
+                request: ListSupportedLanguagesRequest,
+                writes: [],
+                paths: [],
+            }
+        }
+    };
+}
+pub(crate) use registered_tools;
+
+/// A registered tool as clients see it
+#[derive(Debug, Clone, Copy)]
+pub struct ToolSpec {
+    pub name: &'static str,
+    pub category: &'static str,
+    pub description: &'static str,
+    /// Whether the tool writes any state domain
+    pub mutating: bool,
+    /// Arguments that hold file or folder paths
+    pub paths: &'static [&'static str],
+    schema: fn() -> Value,
+    parse: fn(Value) -> ResolveResult<Value>,
+}
+
+impl ToolSpec {
+    /// JSON schema of the tool's arguments
+    pub fn input_schema(&self) -> Value {
+        let mut schema = (self.schema)();
+        if let (true, Value::Object(schema)) = (self.mutating, &mut schema) {
+            add_return_diff(schema);
+        }
+        schema
+    }
+
+    /// The tool as the server lists it
+    pub fn to_tool(&self) -> Tool {
+        let schema = match self.input_schema() {
+            Value::Object(schema) => schema,
+            _ => Default::default(),
+        };
+        Tool::new(self.name, self.description, Arc::new(schema))
+    }
+}
+
+macro_rules! tool_specs {
+    ($($name:ident {
+        category: $category:literal,
+        description: $description:literal,
+        request: $request:ident,
+        writes: [$($domain:ident),*],
+        paths: [$($path:literal),*],
+    })*) => {
+        /// Every registered tool
+        pub static TOOLS: &[ToolSpec] = &[$(
+            ToolSpec {
+                name: stringify!($name),
+                category: $category,
+                description: $description,
+                mutating: !(&[$(stringify!($domain)),*] as &[&str]).is_empty(),
+                paths: &[$($path),*],
+                schema: request_schema::<super::$request>,
+                parse: parse_request::<super::$request>,
+            },
+        )*];
+    };
+}
+registered_tools!(tool_specs);
+
+/// The registered tool with this name
+pub fn find(name: &str) -> Option<&'static ToolSpec> {
+    TOOLS.iter().find(|spec| spec.name == name)
+}
+
+/// Schema of a request type, inlined and closed like the hand-written ones
+fn request_schema<T: JsonSchema>() -> Value {
+    let generator = SchemaSettings::draft07()
+        .with(|settings| {
+            settings.option_nullable = false;
+            settings.option_add_null_type = false;
+            settings.inline_subschemas = true;
+            settings.meta_schema = None;
+        })
+        .into_generator();
+    let mut schema = serde_json::to_value(generator.into_root_schema_for::<T>())
+        .unwrap_or_else(|_| json!({ "type": "object" }));
+    if let Some(schema) = schema.as_object_mut() {
+        schema.remove("title");
+        schema.remove("definitions");
+        schema.entry("properties").or_insert_with(|| json!({}));
+        schema.insert("additionalProperties".to_string(), json!(false));
+    }
+    schema
+}
+
+/// Add the argument every mutating tool takes to report what it changed
+pub(crate) fn add_return_diff(schema: &mut Map<String, Value>) {
+    if let Value::Object(properties) = schema.entry("properties").or_insert_with(|| json!({})) {
+        properties.insert(
+            super::RETURN_DIFF_ARGUMENT.to_string(),
+            json!({
+                "type": "boolean",
+                "description": "Also return the entities the call changed, with their values before and after (default false)"
+            }),
+        );
+    }
+}
+
+/// Schema of a string that must be one of `values`, for request fields that
+/// keep the raw string so the bridge can report a bad value itself
+pub(crate) fn string_enum(values: &[&str]) -> Schema {
+    let mut schema = SchemaObject {
+        instance_type: Some(InstanceType::String.into()),
+        ..Default::default()
+    };
+    schema.enum_values = Some(values.iter().map(|value| json!(value)).collect());
+    schema.into()
+}
+
+/// Arguments checked against a request type, as the bridge receives them
+fn parse_request<T: DeserializeOwned + Serialize>(args: Value) -> ResolveResult<Value> {
+    let request: T = serde_json::from_value(args)?;
+    Ok(serde_json::to_value(&request)?)
+}
+
+/// Call a registered tool on the bridge, or None if no tool has this name
+pub(super) async fn call(
+    tool_name: &str,
+    args: Value,
+    bridge: &crate::bridge::ResolveBridge,
+) -> Option<ResolveResult<String>> {
+    let spec = find(tool_name)?;
+    let response = async {
+        let response = bridge.call_api(spec.name, (spec.parse)(args)?).await?;
+        Ok(serde_json::to_string_pretty(&response)?)
+    };
+    Some(response.await)
+}
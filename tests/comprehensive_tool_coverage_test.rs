@@ -338,7 +338,7 @@ async fn test_comprehensive_tool_coverage() {
             "auto_sync_audio",
             Some(
                 json!({
-                    "clip_names": ["video.mp4", "audio.wav"],
+                    "clip_names": ["test_video.mp4", "sample_audio.wav"],
                     "sync_method": "waveform",
                     "append_mode": false,
                     "target_bin": "Test Bin"
@@ -0,0 +1,657 @@
+"""Stand-in for DaVinci Resolve's scripting module, used by real-mode tests.
+
+Put this directory in `resolve.scripting_modules` so real-mode scripts import
+it instead of the module shipped with Resolve, and real-mode code paths can be
+tested end to end on machines without Resolve. It models one project with a
+current timeline holding two video items, one audio item and two markers, a
+media pool with the timeline's source clips in a "Footage" bin, and a render
+queue with one job waiting and one rendering.
+
+Each API call runs in a new process, so by default every script starts from
+that state. When a `fake_resolve_state.json` file sits beside this module, the
+state is read from it on import and written back when the script exits, so
+calls build on each other; copy the directory for each test that needs this.
+An empty JSON object starts from the default state. The file is not locked, so
+turn off periodic state sync in tests that use it. Every API method called is
+added to the state's `calls` list with its arguments, for tests to check.
+"""
+
+import atexit
+import copy
+import json
+import os
+
+COMPOSITE_NORMAL = 0
+COMPOSITE_ADD = 1
+COMPOSITE_SUBTRACT = 2
+COMPOSITE_DIFF = 3
+COMPOSITE_MULTIPLY = 4
+COMPOSITE_SCREEN = 5
+COMPOSITE_OVERLAY = 6
+COMPOSITE_HARDLIGHT = 7
+COMPOSITE_SOFTLIGHT = 8
+COMPOSITE_DARKEN = 9
+COMPOSITE_LIGHTEN = 10
+COMPOSITE_COLOR_DODGE = 11
+COMPOSITE_COLOR_BURN = 12
+COMPOSITE_EXCLUSION = 13
+
+RETIME_USE_PROJECT = 0
+RETIME_NEAREST = 1
+RETIME_FRAME_BLEND = 2
+RETIME_OPTICAL_FLOW = 3
+
+SCALE_USE_PROJECT = 0
+SCALE_CROP = 1
+SCALE_FIT = 2
+SCALE_FILL = 3
+SCALE_STRETCH = 4
+
+EXPORT_AAF = "aaf"
+EXPORT_AAF_NEW = "aaf_new"
+EXPORT_AAF_EXISTING = "aaf_existing"
+EXPORT_EDL = "edl"
+EXPORT_NONE = "none"
+EXPORT_CDL = "cdl"
+EXPORT_SDL = "sdl"
+EXPORT_MISSING_CLIPS = "missing_clips"
+EXPORT_FCP_7_XML = "fcp_7_xml"
+EXPORT_FCPXML_1_8 = "fcpxml_1_8"
+EXPORT_FCPXML_1_9 = "fcpxml_1_9"
+EXPORT_FCPXML_1_10 = "fcpxml_1_10"
+EXPORT_DRT = "drt"
+EXPORT_OTIO = "otio"
+EXPORT_TEXT_CSV = "text_csv"
+EXPORT_TEXT_TAB = "text_tab"
+
+# Properties Resolve accepts through SetProperty, with their defaults
+DEFAULT_PROPERTIES = {
+    "Pan": 0.0,
+    "Tilt": 0.0,
+    "ZoomX": 1.0,
+    "ZoomY": 1.0,
+    "RotationAngle": 0.0,
+    "AnchorPointX": 0.0,
+    "AnchorPointY": 0.0,
+    "Pitch": 0.0,
+    "Yaw": 0.0,
+    "CropLeft": 0.0,
+    "CropRight": 0.0,
+    "CropTop": 0.0,
+    "CropBottom": 0.0,
+    "CompositeMode": COMPOSITE_NORMAL,
+    "Opacity": 100.0,
+    "RetimeProcess": RETIME_USE_PROJECT,
+    "Scaling": SCALE_USE_PROJECT,
+    "Distortion": 0.0,
+}
+
+# Render presets LoadRenderPreset knows
+RENDER_PRESETS = ["H.264 Master", "H.265 Master", "YouTube - 1080p", "ProRes 422 HQ"]
+
+PAGES = ["media", "cut", "edit", "fusion", "color", "fairlight", "deliver"]
+
+STATE_FILE = os.path.join(os.path.dirname(os.path.abspath(__file__)), "fake_resolve_state.json")
+
+
+def _item(unique_id, name, media_id, start, end, **properties):
+    return {
+        "id": unique_id,
+        "name": name,
+        "media_id": media_id,
+        "start": start,
+        "end": end,
+        "properties": dict(DEFAULT_PROPERTIES, **properties),
+        "nodes": 1,
+        "luts": {},
+        "cdl": None,
+        "drx": None,
+    }
+
+
+def _clip(media_id, name, duration):
+    return {
+        "id": media_id,
+        "properties": {
+            "Clip Name": name,
+            "File Name": name,
+            "File Path": "/media/footage/" + name,
+            "Duration": duration,
+        },
+        "metadata": {},
+    }
+
+
+def _job(job_id, filename, status, completion, **settings):
+    job = {
+        "JobId": job_id,
+        "TimelineName": "Fixture Timeline",
+        "PresetName": "H.264 Master",
+        "TargetDir": "/renders",
+        "OutputFilename": filename,
+        "MarkIn": 86400,
+        "MarkOut": 86639,
+        "IsExportVideo": True,
+        "IsExportAudio": True,
+    }
+    job.update(settings)
+    return {"job": job, "status": status, "completion": completion}
+
+
+def default_state():
+    return {
+        "page": "media",
+        "next_id": 1,
+        "calls": [],
+        "project": {
+            "name": "Fixture Project",
+            "settings": {
+                "colorScienceMode": "davinciYRGB",
+                "colorSpaceInput": "Rec.709 Gamma 2.4",
+                "colorSpaceTimeline": "Rec.709 Gamma 2.4",
+            },
+            "current_timeline": "fixture-timeline-1",
+            "timelines": [
+                {
+                    "id": "fixture-timeline-1",
+                    "name": "Fixture Timeline",
+                    "start_frame": 86400,
+                    "settings": {
+                        "timelineFrameRate": "24",
+                        "timelineResolutionWidth": "1920",
+                        "timelineResolutionHeight": "1080",
+                    },
+                    "markers": [
+                        {"frame": 0, "color": "Blue", "duration": 1, "note": "Opening", "name": "Marker 1", "customData": ""},
+                        {"frame": 96, "color": "Red", "duration": 1, "note": "", "name": "VFX shot", "customData": ""},
+                    ],
+                    "tracks": {
+                        "video": [
+                            [
+                                _item("fixture-item-1", "A001_C001.mov", "fixture-clip-1", 86400, 86520, RotationAngle=12.5),
+                                _item(
+                                    "fixture-item-2",
+                                    "A001_C002.mov",
+                                    "fixture-clip-2",
+                                    86520,
+                                    86640,
+                                    CropLeft=192.0,
+                                    CompositeMode=COMPOSITE_SCREEN,
+                                    RetimeProcess=RETIME_OPTICAL_FLOW,
+                                ),
+                            ]
+                        ],
+                        "audio": [[_item("fixture-audio-1", "A001_C001.wav", "fixture-clip-3", 86400, 86520)]],
+                        "subtitle": [],
+                    },
+                    "playhead": 86400,
+                    "exports": [],
+                }
+            ],
+            "media_pool": {
+                "current_folder": "fixture-bin-root",
+                "root": {
+                    "id": "fixture-bin-root",
+                    "name": "Master",
+                    "clips": [],
+                    "folders": [
+                        {
+                            "id": "fixture-bin-1",
+                            "name": "Footage",
+                            "clips": [
+                                _clip("fixture-clip-1", "A001_C001.mov", "00:00:05:00"),
+                                _clip("fixture-clip-2", "A001_C002.mov", "00:00:05:00"),
+                                _clip("fixture-clip-3", "A001_C001.wav", "00:00:05:00"),
+                            ],
+                            "folders": [],
+                        }
+                    ],
+                },
+            },
+            "render_preset": None,
+            "render_settings": {},
+            "render_jobs": [
+                _job("fixture-job-1", "Fixture_v1.mov", "Ready", 0),
+                _job("fixture-job-2", "Fixture_v2.mov", "Rendering", 25, IsExportAudio=False),
+            ],
+        },
+    }
+
+
+def _load():
+    if not os.path.exists(STATE_FILE):
+        return default_state()
+    with open(STATE_FILE) as state_file:
+        state = json.load(state_file)
+    return state if "project" in state else dict(default_state(), calls=state.get("calls", []))
+
+
+STATE = _load()
+
+
+@atexit.register
+def _save():
+    if os.path.exists(STATE_FILE):
+        with open(STATE_FILE, "w") as state_file:
+            json.dump(STATE, state_file, indent=2)
+
+
+def _new_id(kind):
+    STATE["next_id"] += 1
+    return "fake-%s-%d" % (kind, STATE["next_id"] - 1)
+
+
+def _plain(value):
+    """An argument as the calls list records it: API objects by their ID"""
+    if isinstance(value, _Object):
+        return value._data.get("id")
+    if isinstance(value, (list, tuple)):
+        return [_plain(entry) for entry in value]
+    if isinstance(value, dict):
+        return {str(key): _plain(entry) for key, entry in value.items()}
+    return value
+
+
+class _Object:
+    """Records every public method call in the state's calls list"""
+
+    def __init__(self, data):
+        self._data = data
+
+    def __getattribute__(self, name):
+        attribute = object.__getattribute__(self, name)
+        if name.startswith("_") or not callable(attribute):
+            return attribute
+
+        def recorded(*args):
+            STATE["calls"].append([type(self).__name__ + "." + name, _plain(list(args))])
+            return attribute(*args)
+
+        return recorded
+
+
+def _folders(folder):
+    yield folder
+    for sub_folder in folder["folders"]:
+        yield from _folders(sub_folder)
+
+
+def _all_clips():
+    for folder in _folders(STATE["project"]["media_pool"]["root"]):
+        for clip in folder["clips"]:
+            yield clip, folder
+
+
+def _find_clip(media_id):
+    return next((clip for clip, _ in _all_clips() if clip["id"] == media_id), None)
+
+
+class MediaPoolItem(_Object):
+    def GetName(self):
+        return self._data["properties"]["Clip Name"]
+
+    def GetMediaId(self):
+        return self._data["id"]
+
+    def GetUniqueId(self):
+        return self._data["id"]
+
+    def GetClipProperty(self, key=None):
+        if key is None:
+            return dict(self._data["properties"])
+        return self._data["properties"].get(key, "")
+
+    def SetClipProperty(self, key, value):
+        if key not in self._data["properties"]:
+            return False
+        self._data["properties"][key] = value
+        return True
+
+    def GetMetadata(self, key=None):
+        if key is None:
+            return dict(self._data["metadata"])
+        return self._data["metadata"].get(key, "")
+
+    def SetMetadata(self, key, value):
+        self._data["metadata"][key] = value
+        return True
+
+
+class Folder(_Object):
+    def GetName(self):
+        return self._data["name"]
+
+    def GetUniqueId(self):
+        return self._data["id"]
+
+    def GetClipList(self):
+        return [MediaPoolItem(clip) for clip in self._data["clips"]]
+
+    def GetSubFolderList(self):
+        return [Folder(folder) for folder in self._data["folders"]]
+
+
+class MediaPool(_Object):
+    def GetRootFolder(self):
+        return Folder(self._data["root"])
+
+    def GetCurrentFolder(self):
+        return next(
+            Folder(folder) for folder in _folders(self._data["root"]) if folder["id"] == self._data["current_folder"]
+        )
+
+    def SetCurrentFolder(self, folder):
+        self._data["current_folder"] = folder._data["id"]
+        return True
+
+    def AddSubFolder(self, parent, name):
+        folder = {"id": _new_id("bin"), "name": name, "clips": [], "folders": []}
+        parent._data["folders"].append(folder)
+        return Folder(folder)
+
+    def MoveClips(self, clips, folder):
+        for clip in clips:
+            for _, source in list(_all_clips()):
+                if clip._data in source["clips"]:
+                    source["clips"].remove(clip._data)
+            folder._data["clips"].append(clip._data)
+        return True
+
+    def CreateEmptyTimeline(self, name):
+        project = STATE["project"]
+        if any(timeline["name"] == name for timeline in project["timelines"]):
+            return None
+        timeline = {
+            "id": _new_id("timeline"),
+            "name": name,
+            "start_frame": 86400,
+            "settings": dict(project["timelines"][0]["settings"]),
+            "markers": [],
+            "tracks": {"video": [[]], "audio": [[]], "subtitle": []},
+            "playhead": 86400,
+            "exports": [],
+        }
+        project["timelines"].append(timeline)
+        project["current_timeline"] = timeline["id"]
+        return Timeline(timeline)
+
+
+class MediaStorage(_Object):
+    def AddItemListToMediaPool(self, items):
+        pool = STATE["project"]["media_pool"]
+        folder = next(folder for folder in _folders(pool["root"]) if folder["id"] == pool["current_folder"])
+        added = []
+        for entry in items:
+            path = entry["media"] if isinstance(entry, dict) else entry
+            if not path.startswith("/"):
+                return []
+            clip = _clip(_new_id("clip"), os.path.basename(path), "00:00:05:00")
+            clip["properties"]["File Path"] = path
+            if isinstance(entry, dict):
+                clip["properties"]["Start"] = entry.get("startFrame", 0)
+                clip["properties"]["End"] = entry.get("endFrame")
+            folder["clips"].append(clip)
+            added.append(MediaPoolItem(clip))
+        return added
+
+
+class TimelineItem(_Object):
+    def GetUniqueId(self):
+        return self._data["id"]
+
+    def GetName(self):
+        return self._data["name"]
+
+    def GetStart(self):
+        return self._data["start"]
+
+    def GetEnd(self):
+        return self._data["end"]
+
+    def GetDuration(self):
+        return self._data["end"] - self._data["start"]
+
+    def GetMediaPoolItem(self):
+        clip = _find_clip(self._data["media_id"])
+        return MediaPoolItem(clip) if clip else None
+
+    def GetProperty(self, key=None):
+        if key is None:
+            return dict(self._data["properties"])
+        return self._data["properties"].get(key)
+
+    def SetProperty(self, key, value):
+        if key not in self._data["properties"]:
+            return False
+        self._data["properties"][key] = value
+        return True
+
+    def GetNumNodes(self):
+        return self._data["nodes"]
+
+    def SetLUT(self, node_index, lut_path):
+        if not 1 <= node_index <= self._data["nodes"]:
+            return False
+        self._data["luts"][str(node_index)] = lut_path
+        return True
+
+    def GetLUT(self, node_index):
+        return self._data["luts"].get(str(node_index), "")
+
+    def SetCDL(self, cdl):
+        if not {"NodeIndex", "Slope", "Offset", "Power", "Saturation"} <= set(cdl):
+            return False
+        self._data["cdl"] = dict(cdl)
+        return True
+
+    def CopyGrades(self, items):
+        for item in items:
+            for key in ("nodes", "luts", "cdl", "drx"):
+                item._data[key] = copy.deepcopy(self._data[key])
+        return True
+
+
+class Timeline(_Object):
+    def GetName(self):
+        return self._data["name"]
+
+    def GetUniqueId(self):
+        return self._data["id"]
+
+    def GetStartFrame(self):
+        return self._data["start_frame"]
+
+    def GetSetting(self, name):
+        return self._data["settings"].get(name)
+
+    def GetMarkers(self):
+        return {
+            marker["frame"]: {key: value for key, value in marker.items() if key != "frame"}
+            for marker in self._data["markers"]
+        }
+
+    def AddMarker(self, frame, color, name, note, duration, custom_data=""):
+        if any(marker["frame"] == frame for marker in self._data["markers"]):
+            return False
+        self._data["markers"].append(
+            {"frame": frame, "color": color, "duration": duration, "note": note, "name": name, "customData": custom_data}
+        )
+        self._data["markers"].sort(key=lambda marker: marker["frame"])
+        return True
+
+    def DeleteMarkerByCustomData(self, custom_data):
+        kept = [marker for marker in self._data["markers"] if marker["customData"] != custom_data]
+        deleted = len(kept) != len(self._data["markers"])
+        self._data["markers"] = kept
+        return deleted
+
+    def GetTrackCount(self, track_type):
+        return len(self._data["tracks"].get(track_type, []))
+
+    def GetItemListInTrack(self, track_type, index):
+        tracks = self._data["tracks"].get(track_type, [])
+        return [TimelineItem(item) for item in tracks[index - 1]] if 1 <= index <= len(tracks) else None
+
+    def GetCurrentTimecode(self):
+        rate = int(self._data["settings"]["timelineFrameRate"])
+        frame = self._data["playhead"]
+        return "%02d:%02d:%02d:%02d" % (frame // (rate * 3600), frame // (rate * 60) % 60, frame // rate % 60, frame % rate)
+
+    def SetCurrentTimecode(self, timecode):
+        rate = int(self._data["settings"]["timelineFrameRate"])
+        hours, minutes, seconds, frames = (int(part) for part in timecode.split(":"))
+        self._data["playhead"] = ((hours * 60 + minutes) * 60 + seconds) * rate + frames
+        return True
+
+    def GetCurrentVideoItem(self):
+        for track in self._data["tracks"]["video"]:
+            for item in track:
+                if item["start"] <= self._data["playhead"] < item["end"]:
+                    return TimelineItem(item)
+        return None
+
+    def ApplyGradeFromDRX(self, path, grade_mode, items):
+        if not path.endswith(".drx") or grade_mode not in (0, 1, 2):
+            return False
+        for item in items:
+            item._data["drx"] = path
+        return True
+
+    def Export(self, file_name, export_type, export_subtype=None):
+        if export_type in (EXPORT_AAF, EXPORT_EDL) and export_subtype is None:
+            return False
+        self._data["exports"].append({"file_name": file_name, "type": export_type, "subtype": export_subtype})
+        return True
+
+
+class Project(_Object):
+    def GetName(self):
+        return self._data["name"]
+
+    def GetMediaPool(self):
+        return MediaPool(self._data["media_pool"])
+
+    def GetSetting(self, name=None):
+        settings = self._data["settings"]
+        return dict(settings) if name is None else settings.get(name, "")
+
+    def SetSetting(self, name, value):
+        self._data["settings"][name] = value
+        return True
+
+    def GetCurrentTimeline(self):
+        return next(
+            (Timeline(timeline) for timeline in self._data["timelines"] if timeline["id"] == self._data["current_timeline"]),
+            None,
+        )
+
+    def SetCurrentTimeline(self, timeline):
+        self._data["current_timeline"] = timeline._data["id"]
+        return True
+
+    def GetTimelineCount(self):
+        return len(self._data["timelines"])
+
+    def GetTimelineByIndex(self, index):
+        timelines = self._data["timelines"]
+        return Timeline(timelines[index - 1]) if 1 <= index <= len(timelines) else None
+
+    def LoadRenderPreset(self, name):
+        if name not in RENDER_PRESETS:
+            return False
+        self._data["render_preset"] = name
+        return True
+
+    def SetRenderSettings(self, settings):
+        self._data["render_settings"].update(settings)
+        return True
+
+    def AddRenderJob(self):
+        timeline = self.GetCurrentTimeline()
+        if not timeline or not self._data["render_preset"]:
+            return ""
+        settings = self._data["render_settings"]
+        job = _job(
+            _new_id("job"),
+            timeline.GetName() + ".mov",
+            "Ready",
+            0,
+            TimelineName=timeline.GetName(),
+            PresetName=self._data["render_preset"],
+            IsExportVideo=settings.get("ExportVideo", True),
+            IsExportAudio=settings.get("ExportAudio", True),
+        )
+        self._data["render_jobs"].append(job)
+        return job["job"]["JobId"]
+
+    def GetRenderJobList(self):
+        return [dict(job["job"]) for job in self._data["render_jobs"]]
+
+    def GetRenderJobStatus(self, job_id):
+        job = next((job for job in self._data["render_jobs"] if job["job"]["JobId"] == job_id), None)
+        if not job:
+            return {}
+        return {"JobStatus": job["status"], "CompletionPercentage": job["completion"]}
+
+    def DeleteRenderJob(self, job_id):
+        jobs = self._data["render_jobs"]
+        kept = [job for job in jobs if job["job"]["JobId"] != job_id or job["status"] == "Rendering"]
+        self._data["render_jobs"] = kept
+        return len(kept) != len(jobs)
+
+    def DeleteAllRenderJobs(self):
+        if self.IsRenderingInProgress():
+            return False
+        self._data["render_jobs"] = []
+        return True
+
+    def StartRendering(self, job_ids=None, interactive=False):
+        jobs = [job for job in self._data["render_jobs"] if job_ids is None or job["job"]["JobId"] in job_ids]
+        if not jobs:
+            return False
+        for job in jobs:
+            job["status"] = "Rendering"
+        return True
+
+    def StopRendering(self):
+        for job in self._data["render_jobs"]:
+            if job["status"] == "Rendering":
+                job["status"] = "Cancelled"
+
+    def IsRenderingInProgress(self):
+        return any(job["status"] == "Rendering" for job in self._data["render_jobs"])
+
+
+class ProjectManager(_Object):
+    def GetCurrentProject(self):
+        return Project(STATE["project"])
+
+    def LoadProject(self, name):
+        return Project(STATE["project"]) if name == STATE["project"]["name"] else None
+
+
+class Resolve(_Object):
+    def GetProjectManager(self):
+        return ProjectManager({})
+
+    def GetMediaStorage(self):
+        return MediaStorage({})
+
+    def GetCurrentPage(self):
+        return STATE["page"]
+
+    def OpenPage(self, page):
+        if page not in PAGES:
+            return False
+        STATE["page"] = page
+        return True
+
+
+# Constants are reached through the resolve object, as with the real module
+for _name, _value in list(globals().items()):
+    if _name.startswith(("COMPOSITE_", "RETIME_", "SCALE_", "EXPORT_")):
+        setattr(Resolve, _name, _value)
+
+
+def scriptapp(name):
+    return Resolve({}) if name == "Resolve" else None
@@ -0,0 +1,228 @@
+//! Property tests for argument handling
+//!
+//! Every tool the server lists is called on the bridge with arbitrary JSON
+//! arguments: its own parameters, mostly of the type its schema declares, and
+//! stray ones, holding extreme numbers and near-miss strings. Whatever the
+//! arguments, a call must return a result or a typed error; a handler that
+//! panics fails the test with the smallest arguments that trigger it. File paths are confined to a
+//! temporary sandbox so generated paths cannot touch the rest of the disk.
+
+use proptest::prelude::*;
+use proptest::test_runner::{Config as ProptestConfig, TestCaseError, TestRunner};
+use serde_json::{json, Map, Value};
+use std::sync::Arc;
+use std::time::Duration;
+
+use davinci_mcp_rs::bridge::{ConnectionMode, ResolveBridge};
+use davinci_mcp_rs::{Config, DaVinciResolveServer, ResolveError};
+
+/// Generated argument sets per tool
+const CASES_PER_TOOL: u32 = 48;
+
+/// Longest a single call may take before it counts as hung
+const CALL_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Strings that get past a first check in many handlers
+const PLAUSIBLE_STRINGS: &[&str] = &[
+    "",
+    " ",
+    "default_clip",
+    "test_video.mp4",
+    "Timeline 1",
+    "Fuzz Timeline",
+    "01:00:00:00",
+    "00:00:00;00",
+    "99:99:99:99",
+    "01:00:00",
+    "-1",
+    "0",
+    "1e308",
+    "NaN",
+    "red",
+    "gain",
+    "master",
+    "MP4",
+    "H.264",
+    "Stereo",
+    "en-US",
+    "srt",
+    "json",
+    "csv",
+    "edit",
+    "color",
+    "Blue",
+    "#FFFFFF",
+    "/",
+    "\u{0}",
+    "é🎬",
+];
+
+/// Any JSON value, biased towards the kinds handlers read
+fn json_value() -> impl Strategy<Value = Value> {
+    let leaf = prop_oneof![
+        Just(Value::Null),
+        any::<bool>().prop_map(Value::from),
+        (-3i64..300).prop_map(Value::from),
+        any::<i64>().prop_map(Value::from),
+        any::<u64>().prop_map(Value::from),
+        prop::sample::select(vec![
+            0.0,
+            -0.0,
+            0.5,
+            -1.5,
+            1e-300,
+            f64::MAX,
+            f64::MIN,
+            f64::EPSILON
+        ])
+        .prop_map(Value::from),
+        any::<f64>().prop_map(Value::from),
+        prop::sample::select(PLAUSIBLE_STRINGS).prop_map(Value::from),
+        "\\PC{0,12}".prop_map(Value::from),
+    ];
+    leaf.prop_recursive(3, 24, 4, |inner| {
+        prop_oneof![
+            prop::collection::vec(inner.clone(), 0..4).prop_map(Value::Array),
+            prop::collection::btree_map("[a-z_]{1,10}", inner, 0..4)
+                .prop_map(|map| Value::Object(map.into_iter().collect())),
+        ]
+    })
+}
+
+/// Values of the type a parameter's schema asks for, and now and then of any type
+fn parameter_value(schema: &Value) -> BoxedStrategy<Value> {
+    let typed = if let Some(choices) = schema["enum"].as_array().filter(|c| !c.is_empty()) {
+        prop::sample::select(choices.clone()).boxed()
+    } else {
+        match schema["type"].as_str() {
+            Some("string") => prop_oneof![
+                prop::sample::select(PLAUSIBLE_STRINGS).prop_map(Value::from),
+                "\\PC{0,12}".prop_map(Value::from),
+            ]
+            .boxed(),
+            Some("integer") => prop_oneof![
+                (-3i64..300).prop_map(Value::from),
+                any::<i64>().prop_map(Value::from),
+            ]
+            .boxed(),
+            Some("number") => prop_oneof![
+                (-2.0f64..2.0).prop_map(Value::from),
+                any::<f64>().prop_map(Value::from),
+            ]
+            .boxed(),
+            Some("boolean") => any::<bool>().prop_map(Value::from).boxed(),
+            Some("array") => prop::collection::vec(parameter_value(&schema["items"]), 0..4)
+                .prop_map(Value::Array)
+                .boxed(),
+            _ => json_value().boxed(),
+        }
+    };
+    prop_oneof![4 => typed, 1 => json_value()].boxed()
+}
+
+/// Arguments for a tool: required parameters usually, optional ones half the
+/// time, and now and then a stray one
+fn arguments(schema: &Value) -> impl Strategy<Value = Value> {
+    let required: Vec<&str> = schema["required"]
+        .as_array()
+        .map(|names| names.iter().filter_map(Value::as_str).collect())
+        .unwrap_or_default();
+    let parameters: Vec<BoxedStrategy<Option<(String, Value)>>> = schema["properties"]
+        .as_object()
+        .map(|properties| {
+            properties
+                .iter()
+                .map(|(name, property)| {
+                    let presence = if required.contains(&name.as_str()) {
+                        0.9
+                    } else {
+                        0.5
+                    };
+                    let name = name.clone();
+                    prop::option::weighted(presence, parameter_value(property))
+                        .prop_map(move |value| value.map(|value| (name.clone(), value)))
+                        .boxed()
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    (
+        parameters,
+        prop::option::weighted(0.2, ("[a-z_]{1,12}", json_value())),
+    )
+        .prop_map(|(parameters, stray)| {
+            let mut arguments: Map<String, Value> = parameters.into_iter().flatten().collect();
+            if let Some((name, value)) = stray {
+                arguments.insert(name, value);
+            }
+            Value::Object(arguments)
+        })
+}
+
+/// Simulation bridge with a project, a timeline and a clip on it, so calls get
+/// past the "nothing is open" checks
+async fn fuzz_bridge(sandbox: &std::path::Path) -> ResolveBridge {
+    let mut config = Config::default();
+    config.sandbox.allowed_roots = vec![sandbox.to_path_buf()];
+    let bridge = ResolveBridge::with_config(ConnectionMode::Simulation, Arc::new(config));
+    bridge.initialize().await.unwrap();
+    for (method, args) in [
+        ("create_project", json!({ "name": "Fuzz Project" })),
+        ("create_timeline", json!({ "name": "Fuzz Timeline" })),
+        (
+            "add_clip_to_timeline",
+            json!({ "clip_name": "default_clip", "timeline_name": "Fuzz Timeline" }),
+        ),
+    ] {
+        bridge.call_api(method, args).await.unwrap();
+    }
+    bridge
+}
+
+#[test]
+fn test_arbitrary_arguments_never_panic() {
+    let sandbox = std::env::temp_dir().join(format!("davinci-mcp-fuzz-{}", std::process::id()));
+    std::fs::create_dir_all(&sandbox).unwrap();
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .unwrap();
+
+    let tools = DaVinciResolveServer::new().get_tools();
+    assert!(!tools.is_empty());
+    let mut failures = Vec::new();
+    for tool in tools {
+        let method = tool.name.to_string();
+        let schema = Value::Object((*tool.input_schema).clone());
+        let bridge = runtime.block_on(fuzz_bridge(&sandbox));
+
+        let mut runner = TestRunner::new(ProptestConfig {
+            cases: CASES_PER_TOOL,
+            failure_persistence: None,
+            ..ProptestConfig::default()
+        });
+        let outcome = runner.run(&arguments(&schema), |args| {
+            let result = runtime.block_on(async {
+                tokio::time::timeout(CALL_TIMEOUT, bridge.call_api(&method, args)).await
+            });
+            match result {
+                Err(_) => Err(TestCaseError::fail("call did not finish")),
+                Ok(Err(ResolveError::Internal { message })) if message.contains("panicked") => {
+                    Err(TestCaseError::fail(message))
+                }
+                Ok(_) => Ok(()),
+            }
+        });
+        if let Err(failure) = outcome {
+            failures.push(format!("{}: {}", method, failure));
+        }
+    }
+
+    let _ = std::fs::remove_dir_all(&sandbox);
+    assert!(
+        failures.is_empty(),
+        "{} tools failed with arbitrary arguments:\n{}",
+        failures.len(),
+        failures.join("\n")
+    );
+}
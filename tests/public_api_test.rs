@@ -0,0 +1,165 @@
+//! Stability of the public API
+//!
+//! Pins the name and signature of every item in `davinci_mcp_rs::api`. If
+//! one of these tests stops compiling or fails, the change breaks callers:
+//! keep the old item, or bump the major number of `API_VERSION`.
+
+use serde_json::{json, Value};
+
+use davinci_mcp_rs::api::{
+    Client, ClientConfig, ConnectionMode, ErrorKind, ResolveError, ResolveResult, ToolInfo,
+    ToolResponse, API_VERSION,
+};
+
+fn assert_shareable<T: Clone + Send + Sync + 'static>() {}
+
+#[test]
+fn test_api_signatures() {
+    let _: fn(ConnectionMode, ClientConfig) -> Client = Client::new;
+    let _: fn(&Client) -> Vec<ToolInfo> = Client::tools;
+    let _: for<'a> fn(&'a ToolResponse, &str) -> Option<&'a Value> = ToolResponse::field;
+    let _: fn(&ToolResponse) -> Option<&str> = ToolResponse::operation_id;
+    let _: fn(&ResolveError) -> ErrorKind = ResolveError::kind;
+    let _: fn() -> ClientConfig = ClientConfig::default;
+    let _: fn(Value) -> ResolveResult<ClientConfig> = ClientConfig::from_json;
+    let _: ResolveResult<()> = Ok(());
+    assert_shareable::<Client>();
+    assert_shareable::<ClientConfig>();
+}
+
+#[test]
+fn test_api_version() {
+    let parts: Vec<u64> = API_VERSION
+        .split('.')
+        .map(|part| part.parse().expect("API_VERSION should be numeric"))
+        .collect();
+    assert_eq!(parts.len(), 3, "API_VERSION should be major.minor.patch");
+    assert_eq!(
+        parts[0], 1,
+        "a new major API version needs these tests updated"
+    );
+}
+
+#[test]
+fn test_api_enums() {
+    // Variants stay available; new ones may arrive, hence the wildcard arms
+    for mode in [ConnectionMode::Simulation, ConnectionMode::Real] {
+        match mode {
+            ConnectionMode::Simulation | ConnectionMode::Real => {}
+            #[allow(unreachable_patterns)]
+            _ => unreachable!(),
+        }
+    }
+    let kinds = [
+        ErrorKind::NotRunning,
+        ErrorKind::NotFound,
+        ErrorKind::InvalidArgument,
+        ErrorKind::ApiCall,
+        ErrorKind::NotSupported,
+        ErrorKind::PermissionDenied,
+        ErrorKind::Timeout,
+        ErrorKind::LimitExceeded,
+        ErrorKind::Internal,
+    ];
+    assert_eq!(kinds.len(), 9);
+
+    // Each error keeps its kind
+    assert_eq!(ResolveError::NotRunning.kind(), ErrorKind::NotRunning);
+    let not_found = ResolveError::TimelineNotFound {
+        name: "Main".to_string(),
+    };
+    assert_eq!(not_found.kind(), ErrorKind::NotFound);
+    assert_eq!(
+        ResolveError::invalid_parameter("name", "required string").kind(),
+        ErrorKind::InvalidArgument
+    );
+    assert_eq!(
+        ResolveError::not_supported("export").kind(),
+        ErrorKind::NotSupported
+    );
+    assert_eq!(
+        ResolveError::path_not_allowed("/etc", "outside the sandbox").kind(),
+        ErrorKind::PermissionDenied
+    );
+    assert_eq!(
+        ResolveError::resource_limit_exceeded("clips", 10).kind(),
+        ErrorKind::LimitExceeded
+    );
+    assert_eq!(ResolveError::internal("bug").kind(), ErrorKind::Internal);
+}
+
+#[tokio::test]
+async fn test_api_client() {
+    let client = Client::new(ConnectionMode::Simulation, ClientConfig::default());
+    let connected: ResolveResult<()> = client.connect().await;
+    connected.expect("Simulation mode should always connect");
+
+    let response: ResolveResult<ToolResponse> = client
+        .call_tool("create_project", json!({ "name": "API Project" }))
+        .await;
+    let ToolResponse {
+        tool, text, data, ..
+    } = response.expect("create_project should succeed");
+    let _: (String, String, Option<Value>) = (tool.clone(), text.clone(), data);
+    assert_eq!(tool, "create_project");
+    assert!(!text.is_empty());
+
+    // JSON responses are parsed
+    let response = client
+        .call_tool("get_frame_cache_stats", Value::Null)
+        .await
+        .expect("get_frame_cache_stats should succeed");
+    assert_eq!(response.field("frames"), Some(&json!(0)));
+    assert!(response.operation_id().is_some());
+
+    let error = client
+        .call_tool("no_such_tool", json!({}))
+        .await
+        .expect_err("unknown tools should fail");
+    assert_eq!(error.kind(), ErrorKind::NotFound);
+    let error = client
+        .call_tool("create_project", json!(["API Project"]))
+        .await
+        .expect_err("arguments should be an object");
+    assert_eq!(error.kind(), ErrorKind::InvalidArgument);
+
+    let tools = client.tools();
+    let ToolInfo {
+        name,
+        description,
+        input_schema,
+        ..
+    } = tools
+        .iter()
+        .find(|tool| tool.name == "create_project")
+        .cloned()
+        .expect("create_project should be listed");
+    let _: (String, Option<String>, Value) = (name, description, input_schema.clone());
+    assert_eq!(input_schema["type"], "object");
+
+    // Clones share the connection
+    let clone = client.clone();
+    assert!(clone.is_connected().await);
+    let other = Client::new(ConnectionMode::Simulation, ClientConfig::default());
+    assert!(!other.is_connected().await);
+}
+
+#[tokio::test]
+async fn test_api_client_config() {
+    // Settings the JSON leaves out keep their defaults
+    let config = ClientConfig::from_json(json!({ "pagination": { "default_page_size": 2 } }))
+        .expect("a partial configuration should load");
+    let client = Client::new(ConnectionMode::Simulation, config);
+    client
+        .connect()
+        .await
+        .expect("Simulation mode should always connect");
+    assert!(client.is_connected().await);
+
+    let error = ClientConfig::from_json(json!(["not", "an", "object"]))
+        .expect_err("settings should be an object");
+    assert_eq!(error.kind(), ErrorKind::InvalidArgument);
+    let error = ClientConfig::from_json(json!({ "logging": { "level": "loud" } }))
+        .expect_err("settings should be validated");
+    assert_eq!(error.kind(), ErrorKind::InvalidArgument);
+}
@@ -0,0 +1,34 @@
+use davinci_mcp_rs::timecode::FrameRate;
+
+#[test]
+fn test_non_drop_frame_round_trip() {
+    let rate = FrameRate::parse("24").unwrap();
+    assert_eq!(rate.timecode_to_frames("01:00:00:00").unwrap(), 86_400);
+    assert_eq!(rate.frames_to_timecode(86_400 + 25), "01:00:01:01");
+    assert!(rate.timecode_to_frames("00:00:00:24").is_err());
+    assert!(rate.timecode_to_frames("not a timecode").is_err());
+}
+
+#[test]
+fn test_drop_frame_round_trip() {
+    let rate = FrameRate::parse("29.97 DF").unwrap();
+    assert!(rate.is_drop_frame());
+    // Frames 00 and 01 are skipped at the start of each minute except every tenth
+    assert_eq!(rate.timecode_to_frames("00:01:00;02").unwrap(), 1800);
+    assert_eq!(rate.frames_to_timecode(1800), "00:01:00;02");
+    assert_eq!(rate.timecode_to_frames("00:10:00;00").unwrap(), 17_982);
+    assert_eq!(rate.frames_to_timecode(17_982), "00:10:00;00");
+    assert!(rate.timecode_to_frames("00:01:00;00").is_err());
+
+    for frame in [0, 1799, 1800, 17_981, 107_892, 1_000_000] {
+        let timecode = rate.frames_to_timecode(frame);
+        assert_eq!(rate.timecode_to_frames(&timecode).unwrap(), frame);
+    }
+}
+
+#[test]
+fn test_drop_frame_only_at_ntsc_rates() {
+    assert!(FrameRate::parse("25 DF").is_err());
+    assert!(FrameRate::parse("59.94DF").is_ok());
+    assert!(FrameRate::parse("fast").is_err());
+}